@@ -43,6 +43,9 @@ pub enum CredentialOrigin {
 
     /// The credential is from a profile
     ConfigFile(String),
+
+    /// There was no credential to use, so requests are made without an API key
+    Anonymous,
 }
 
 impl Profile {
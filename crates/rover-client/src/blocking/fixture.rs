@@ -0,0 +1,324 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use graphql_client::GraphQLQuery;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::RoverClientError;
+
+/// Names of the variables and response fields whose values are replaced before an exchange
+/// is saved to a [Fixture], since they can identify the person or machine that ran the command.
+const SANITIZED_FIELDS: &[&str] = &["author", "committer", "remoteUrl", "email"];
+
+/// The value sanitized variables and response fields are replaced with.
+const SANITIZED_VALUE: &str = "<redacted>";
+
+/// A sanitized recording of the requests a [crate::blocking::StudioClient] made
+/// to Apollo Studio, along with the responses it got back.
+///
+/// Only the details in [SANITIZED_FIELDS] are redacted. Everything else, like the schemas
+/// sent for a check and the results that came back, is saved as is
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fixture {
+    pub exchanges: Vec<FixtureExchange>,
+}
+
+/// A single request made to Apollo Studio, and the data it responded with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixtureExchange {
+    pub operation_name: String,
+    pub variables: Value,
+    pub response: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixtureMode {
+    Record,
+    Replay,
+}
+
+/// Either records every exchange a [crate::blocking::StudioClient] makes, or answers
+/// its requests from a previously recorded [Fixture] instead of sending them.
+///
+/// Clones share the same exchanges, so a single session can be handed to every
+/// client created while running a command.
+#[derive(Debug, Clone)]
+pub struct FixtureSession {
+    mode: FixtureMode,
+    exchanges: Arc<Mutex<VecDeque<FixtureExchange>>>,
+}
+
+impl FixtureSession {
+    /// Creates a session that records every exchange into an empty [Fixture].
+    pub fn record() -> FixtureSession {
+        FixtureSession {
+            mode: FixtureMode::Record,
+            exchanges: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Creates a session that answers requests with the exchanges of `fixture`, in order.
+    pub fn replay(fixture: Fixture) -> FixtureSession {
+        FixtureSession {
+            mode: FixtureMode::Replay,
+            exchanges: Arc::new(Mutex::new(fixture.exchanges.into())),
+        }
+    }
+
+    /// Whether requests are answered from a fixture rather than sent to Apollo Studio.
+    pub fn is_replaying(&self) -> bool {
+        self.mode == FixtureMode::Replay
+    }
+
+    /// The exchanges recorded so far, or the exchanges left to replay.
+    pub fn get_fixture(&self) -> Fixture {
+        Fixture {
+            exchanges: self.lock_exchanges().iter().cloned().collect(),
+        }
+    }
+
+    /// Saves a successful exchange, with its already serialized `variables`, to the fixture.
+    ///
+    /// Repeated requests with the same variables (such as polling for the results
+    /// of a check workflow) only keep their last response, so replays don't have to wait.
+    pub(crate) fn record_exchange<Q: GraphQLQuery>(
+        &self,
+        mut variables: Value,
+        response: &Q::ResponseData,
+    ) -> Result<(), RoverClientError>
+    where
+        Q::ResponseData: Serialize,
+    {
+        sanitize(&mut variables);
+        let mut response = serde_json::to_value(response)?;
+        sanitize(&mut response);
+        let exchange = FixtureExchange {
+            operation_name: get_operation_name::<Q>(),
+            variables,
+            response,
+        };
+
+        let mut exchanges = self.lock_exchanges();
+        match exchanges.back_mut() {
+            Some(last)
+                if last.operation_name == exchange.operation_name
+                    && last.variables == exchange.variables =>
+            {
+                *last = exchange
+            }
+            _ => exchanges.push_back(exchange),
+        }
+        Ok(())
+    }
+
+    /// Answers a request with the next exchange in the fixture, erroring if the
+    /// fixture has run out of exchanges or the next one is for a different operation.
+    pub(crate) fn replay_exchange<Q: GraphQLQuery>(
+        &self,
+    ) -> Result<Q::ResponseData, RoverClientError> {
+        let operation_name = get_operation_name::<Q>();
+        let exchange =
+            self.lock_exchanges()
+                .pop_front()
+                .ok_or_else(|| RoverClientError::InvalidFixture {
+                    msg: format!(
+                        "it has no response left for the {} operation",
+                        operation_name
+                    ),
+                })?;
+        if exchange.operation_name != operation_name {
+            return Err(RoverClientError::InvalidFixture {
+                msg: format!(
+                    "the next response is for the {} operation, not the {} operation",
+                    exchange.operation_name, operation_name
+                ),
+            });
+        }
+        Ok(serde_json::from_value(exchange.response)?)
+    }
+
+    fn lock_exchanges(&self) -> std::sync::MutexGuard<'_, VecDeque<FixtureExchange>> {
+        // a panic while holding the lock can't leave the exchanges half-written
+        self.exchanges
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The name of the struct a query was derived on, i.e. `GraphCheckWorkflowQuery`
+pub(crate) fn get_operation_name<Q: GraphQLQuery>() -> String {
+    let type_name = std::any::type_name::<Q>();
+    type_name
+        .rsplit("::")
+        .next()
+        .unwrap_or(type_name)
+        .to_string()
+}
+
+/// Redacts the string values of [SANITIZED_FIELDS] anywhere in `value`. Other values,
+/// like `null`, are kept, so a sanitized response can still be deserialized when replayed
+fn sanitize(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if SANITIZED_FIELDS.contains(&key.as_str()) && value.is_string() {
+                    *value = Value::String(SANITIZED_VALUE.to_string());
+                } else {
+                    sanitize(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(sanitize),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    struct TestQuery;
+
+    #[derive(Serialize)]
+    struct TestVariables {
+        id: String,
+        git_context: Value,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct TestResponse {
+        status: String,
+    }
+
+    impl GraphQLQuery for TestQuery {
+        type Variables = TestVariables;
+        type ResponseData = TestResponse;
+
+        fn build_query(variables: Self::Variables) -> graphql_client::QueryBody<Self::Variables> {
+            graphql_client::QueryBody {
+                variables,
+                query: "query TestQuery { status }",
+                operation_name: "TestQuery",
+            }
+        }
+    }
+
+    /// A query whose response is any JSON
+    struct UntypedQuery;
+
+    impl GraphQLQuery for UntypedQuery {
+        type Variables = TestVariables;
+        type ResponseData = Value;
+
+        fn build_query(variables: Self::Variables) -> graphql_client::QueryBody<Self::Variables> {
+            graphql_client::QueryBody {
+                variables,
+                query: "query UntypedQuery { status }",
+                operation_name: "UntypedQuery",
+            }
+        }
+    }
+
+    fn variables() -> TestVariables {
+        TestVariables {
+            id: "my-graph".to_string(),
+            git_context: json!({
+                "branch": "main",
+                "author": "Jane Doe <jane@example.com>",
+                "remoteUrl": "https://token@github.com/org/repo",
+                "committer": null,
+            }),
+        }
+    }
+
+    fn response(status: &str) -> TestResponse {
+        TestResponse {
+            status: status.to_string(),
+        }
+    }
+
+    fn record(session: &FixtureSession, status: &str) {
+        session
+            .record_exchange::<TestQuery>(
+                serde_json::to_value(variables()).unwrap(),
+                &response(status),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn it_records_sanitized_exchanges() {
+        let session = FixtureSession::record();
+        record(&session, "PASSED");
+        assert_eq!(
+            session.get_fixture(),
+            Fixture {
+                exchanges: vec![FixtureExchange {
+                    operation_name: "TestQuery".to_string(),
+                    variables: json!({
+                        "id": "my-graph",
+                        "git_context": {
+                            "branch": "main",
+                            "author": "<redacted>",
+                            "remoteUrl": "<redacted>",
+                            "committer": null,
+                        }
+                    }),
+                    response: json!({ "status": "PASSED" }),
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn it_sanitizes_responses() {
+        let session = FixtureSession::record();
+        session
+            .record_exchange::<UntypedQuery>(
+                serde_json::to_value(variables()).unwrap(),
+                &json!({
+                    "status": "PASSED",
+                    "gitContext": { "author": "Jane Doe", "email": "jane@example.com", "remoteUrl": null },
+                }),
+            )
+            .unwrap();
+        assert_eq!(
+            session.get_fixture().exchanges[0].response,
+            json!({
+                "status": "PASSED",
+                "gitContext": { "author": "<redacted>", "email": "<redacted>", "remoteUrl": null },
+            })
+        );
+    }
+
+    #[test]
+    fn it_only_keeps_the_last_response_to_repeated_requests() {
+        let session = FixtureSession::record();
+        let shared_session = session.clone();
+        record(&session, "PENDING");
+        record(&shared_session, "PASSED");
+        let fixture = session.get_fixture();
+        assert_eq!(fixture.exchanges.len(), 1);
+        assert_eq!(fixture.exchanges[0].response, json!({ "status": "PASSED" }));
+    }
+
+    #[test]
+    fn it_replays_exchanges_in_order() {
+        let recording = FixtureSession::record();
+        record(&recording, "FAILED");
+
+        let replay = FixtureSession::replay(recording.get_fixture());
+        assert!(replay.is_replaying());
+        assert_eq!(
+            replay.replay_exchange::<TestQuery>().unwrap(),
+            response("FAILED")
+        );
+        assert!(matches!(
+            replay.replay_exchange::<TestQuery>(),
+            Err(RoverClientError::InvalidFixture { .. })
+        ));
+    }
+}
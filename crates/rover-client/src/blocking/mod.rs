@@ -1,6 +1,10 @@
+mod fixture;
+mod request_budget;
 mod studio_client;
 
+pub use fixture::{Fixture, FixtureExchange, FixtureSession};
 pub use introspector_gadget::blocking::GraphQLClient;
+pub use request_budget::RequestBudget;
 pub use studio_client::StudioClient;
 
 pub(crate) const CLIENT_NAME: &str = "rover-client";
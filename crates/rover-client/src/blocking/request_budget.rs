@@ -0,0 +1,144 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+use crate::RoverClientError;
+
+/// Keeps count of the requests made to Apollo Studio over the course of a single
+/// invocation of Rover, optionally refusing to make more than `max_requests` of them.
+///
+/// Clones share the same count, so a single budget can be handed to every
+/// [crate::blocking::StudioClient] created while running a command.
+///
+/// Since a command may already have changed something in Apollo Studio by the time
+/// its budget runs out, the budget also keeps the names of the operations that completed,
+/// which the error for exceeding it lists.
+#[derive(Debug, Clone, Default)]
+pub struct RequestBudget {
+    max_requests: Option<u64>,
+    count: Arc<AtomicU64>,
+    completed_operations: Arc<Mutex<Vec<String>>>,
+}
+
+impl RequestBudget {
+    /// Creates a new budget that allows at most `max_requests` requests,
+    /// or any number of requests if `max_requests` is `None`.
+    pub fn new(max_requests: Option<u64>) -> RequestBudget {
+        RequestBudget {
+            max_requests,
+            count: Arc::new(AtomicU64::new(0)),
+            completed_operations: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Records a request that is about to be sent, erroring instead if
+    /// sending it would exceed the budget.
+    pub fn spend(&self) -> Result<(), RoverClientError> {
+        let previous_count = self.count.fetch_add(1, Ordering::SeqCst);
+        match self.max_requests {
+            Some(max_requests) if previous_count >= max_requests => {
+                self.count.fetch_sub(1, Ordering::SeqCst);
+                Err(RoverClientError::RequestBudgetExceeded {
+                    max_requests,
+                    completed_operations: self.completed_operations(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that `planned_requests` more requests fit in the budget, without spending any of it.
+    pub fn plan(&self, planned_requests: u64) -> Result<(), RoverClientError> {
+        match self.max_requests {
+            Some(max_requests) => {
+                let remaining_requests = max_requests.saturating_sub(self.count());
+                if planned_requests > remaining_requests {
+                    Err(RoverClientError::RequestPlanExceedsBudget {
+                        planned_requests,
+                        remaining_requests,
+                        max_requests,
+                    })
+                } else {
+                    Ok(())
+                }
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Records that a request for `operation_name` got a response.
+    pub fn complete(&self, operation_name: &str) {
+        self.completed_operations
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(operation_name.to_string());
+    }
+
+    /// The operations that got a response so far, in the order they completed.
+    pub fn completed_operations(&self) -> Vec<String> {
+        self.completed_operations
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// The number of requests that have been made so far.
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    /// The maximum number of requests allowed, if there is one.
+    pub fn max_requests(&self) -> Option<u64> {
+        self.max_requests
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_allows_unlimited_requests_without_a_max() {
+        let budget = RequestBudget::new(None);
+        for _ in 0..100 {
+            assert!(budget.spend().is_ok());
+        }
+        assert_eq!(budget.count(), 100);
+    }
+
+    #[test]
+    fn it_errs_when_the_budget_is_spent() {
+        let budget = RequestBudget::new(Some(2));
+        let shared_budget = budget.clone();
+        assert!(budget.spend().is_ok());
+        budget.complete("SubgraphPublishMutation");
+        assert!(shared_budget.spend().is_ok());
+        assert!(matches!(
+            budget.spend(),
+            Err(RoverClientError::RequestBudgetExceeded {
+                max_requests: 2,
+                completed_operations,
+            }) if completed_operations == vec!["SubgraphPublishMutation".to_string()]
+        ));
+        assert_eq!(shared_budget.count(), 2);
+    }
+
+    #[test]
+    fn it_errs_when_a_plan_does_not_fit_in_the_budget() {
+        let budget = RequestBudget::new(Some(3));
+        assert!(budget.spend().is_ok());
+        assert!(budget.plan(2).is_ok());
+        assert!(matches!(
+            budget.plan(3),
+            Err(RoverClientError::RequestPlanExceedsBudget {
+                planned_requests: 3,
+                remaining_requests: 2,
+                max_requests: 3,
+            })
+        ));
+        // planning doesn't spend the budget
+        assert_eq!(budget.count(), 1);
+        assert!(RequestBudget::new(None).plan(u64::MAX).is_ok());
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{
-    blocking::{GraphQLClient, CLIENT_NAME},
+    blocking::{
+        fixture::get_operation_name, FixtureSession, GraphQLClient, RequestBudget, CLIENT_NAME,
+    },
     RoverClientError,
 };
 
@@ -10,6 +12,23 @@ use houston::{Credential, CredentialOrigin};
 use graphql_client::GraphQLQuery;
 use reqwest::blocking::Client as ReqwestClient;
 use reqwest::header::{HeaderMap, HeaderValue};
+use serde::Serialize;
+
+use std::thread;
+use std::time::Duration;
+
+/// the delay before the first retry of a request made with [StudioClient::post_with_retries],
+/// which doubles with every subsequent attempt.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// How the underlying [GraphQLClient] retries a request that fails
+#[derive(Debug, Clone, Copy)]
+enum RetryStrategy {
+    /// with its own backoff, which is only safe for requests that can be repeated
+    Automatic,
+    /// not at all, leaving any retries to the caller
+    Never,
+}
 
 /// Represents a client for making GraphQL requests to Apollo Studio.
 pub struct StudioClient {
@@ -17,6 +36,8 @@ pub struct StudioClient {
     client: GraphQLClient,
     version: String,
     is_sudo: bool,
+    request_budget: RequestBudget,
+    fixture: Option<FixtureSession>,
 }
 
 impl StudioClient {
@@ -34,9 +55,25 @@ impl StudioClient {
             client: GraphQLClient::new(graphql_endpoint, client),
             version: version.to_string(),
             is_sudo,
+            request_budget: RequestBudget::default(),
+            fixture: None,
+        }
+    }
+
+    /// Counts every request this client makes against a (possibly shared) [RequestBudget].
+    pub fn with_request_budget(self, request_budget: RequestBudget) -> StudioClient {
+        StudioClient {
+            request_budget,
+            ..self
         }
     }
 
+    /// Records every successful exchange into a [FixtureSession], or answers every
+    /// request from one without touching the network if it is replaying.
+    pub fn with_fixture(self, fixture: Option<FixtureSession>) -> StudioClient {
+        StudioClient { fixture, ..self }
+    }
+
     /// Client method for making a GraphQL request to Apollo Studio.
     ///
     /// Takes one argument, `variables`. Returns a Response or a RoverClientError.
@@ -44,17 +81,11 @@ impl StudioClient {
     pub fn post<Q: GraphQLQuery>(
         &self,
         variables: Q::Variables,
-    ) -> Result<Q::ResponseData, RoverClientError> {
-        let mut header_map = self.build_studio_headers()?;
-        self.client
-            .post::<Q>(variables, &mut header_map)
-            .map_err(|e| match e {
-                IntrospectorGadgetError::SendRequest(source) => RoverClientError::SendRequest {
-                    source,
-                    is_studio: true,
-                },
-                e => e.into(),
-            })
+    ) -> Result<Q::ResponseData, RoverClientError>
+    where
+        Q::ResponseData: Serialize,
+    {
+        self.send::<Q>(variables, RetryStrategy::Automatic)
     }
 
     /// Client method for making a GraphQL request to Apollo Studio.
@@ -64,17 +95,98 @@ impl StudioClient {
     pub fn post_no_retry<Q: GraphQLQuery>(
         &self,
         variables: Q::Variables,
-    ) -> Result<Q::ResponseData, RoverClientError> {
+    ) -> Result<Q::ResponseData, RoverClientError>
+    where
+        Q::ResponseData: Serialize,
+    {
+        self.send::<Q>(variables, RetryStrategy::Never)
+    }
+
+    /// Checks that the request budget has room for the `planned_requests` a command is about
+    /// to make, before it sends any of them. Commands that know how many requests they'll make,
+    /// such as one per subgraph, call this so they fail before changing anything
+    /// instead of running out of budget partway through.
+    pub fn plan_requests(&self, planned_requests: u64) -> Result<(), RoverClientError> {
+        self.request_budget.plan(planned_requests)
+    }
+
+    /// Sends a request, counting it against the request budget, and replaying it from
+    /// or recording it into the fixture if there is one.
+    fn send<Q: GraphQLQuery>(
+        &self,
+        variables: Q::Variables,
+        retry_strategy: RetryStrategy,
+    ) -> Result<Q::ResponseData, RoverClientError>
+    where
+        Q::ResponseData: Serialize,
+    {
+        self.request_budget.spend()?;
+        if let Some(fixture) = self.fixture.as_ref().filter(|f| f.is_replaying()) {
+            return fixture.replay_exchange::<Q>();
+        }
         let mut header_map = self.build_studio_headers()?;
-        self.client
-            .post_no_retry::<Q>(variables, &mut header_map)
-            .map_err(|e| match e {
-                IntrospectorGadgetError::SendRequest(source) => RoverClientError::SendRequest {
-                    source,
-                    is_studio: true,
-                },
-                e => e.into(),
-            })
+        // the variables are moved into the request, so they're serialized up front to be recorded
+        let recorded_variables = match self.fixture {
+            Some(_) => Some(serde_json::to_value(&variables)?),
+            None => None,
+        };
+        let response = match retry_strategy {
+            RetryStrategy::Automatic => self.client.post::<Q>(variables, &mut header_map),
+            RetryStrategy::Never => self.client.post_no_retry::<Q>(variables, &mut header_map),
+        }
+        .map_err(|e| match e {
+            IntrospectorGadgetError::SendRequest(source) => RoverClientError::SendRequest {
+                source,
+                is_studio: true,
+            },
+            e => e.into(),
+        })?;
+        self.request_budget.complete(&get_operation_name::<Q>());
+        if let (Some(fixture), Some(variables)) = (&self.fixture, recorded_variables) {
+            fixture.record_exchange::<Q>(variables, &response)?;
+        }
+        Ok(response)
+    }
+
+    /// Client method for making a GraphQL mutation to Apollo Studio that is safe to replay,
+    /// such as a schema publish (which Studio deduplicates by schema hash).
+    ///
+    /// Takes a closure that builds the `variables` for each attempt, and the maximum number
+    /// of `retries` to make after the first attempt fails with a transient error
+    /// (a 5xx response, a timeout, or a failed connection). When `retries` is `None`,
+    /// this behaves exactly like [StudioClient::post].
+    pub fn post_with_retries<Q: GraphQLQuery>(
+        &self,
+        variables: impl Fn() -> Q::Variables,
+        retries: Option<u32>,
+    ) -> Result<Q::ResponseData, RoverClientError>
+    where
+        Q::ResponseData: Serialize,
+    {
+        let retries = match retries {
+            Some(retries) => retries,
+            None => return self.post::<Q>(variables()),
+        };
+
+        let mut attempt = 0;
+        let mut delay = INITIAL_RETRY_DELAY;
+        loop {
+            match self.post_no_retry::<Q>(variables()) {
+                Err(e) if attempt < retries && e.is_transient() => {
+                    attempt += 1;
+                    tracing::debug!(
+                        "Retrying request to Apollo Studio ({}/{}) in {:?} after a transient error: {}",
+                        attempt,
+                        retries,
+                        delay,
+                        e
+                    );
+                    thread::sleep(delay);
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
     }
 
     /// Function for building a [HeaderMap] for making http requests. Use for making
@@ -99,9 +211,12 @@ impl StudioClient {
         let client_version = HeaderValue::from_str(&self.version)?;
         headers.insert("apollographql-client-version", client_version);
 
-        let mut api_key = HeaderValue::from_str(&self.credential.api_key)?;
-        api_key.set_sensitive(true);
-        headers.insert("x-api-key", api_key);
+        // anonymous clients can only read public variants, which don't need a key
+        if !self.is_anonymous() {
+            let mut api_key = HeaderValue::from_str(&self.credential.api_key)?;
+            api_key.set_sensitive(true);
+            headers.insert("x-api-key", api_key);
+        }
 
         if self.is_sudo {
             headers.insert("apollo-sudo", HeaderValue::from_str("true")?);
@@ -113,4 +228,9 @@ impl StudioClient {
     pub fn get_credential_origin(&self) -> CredentialOrigin {
         self.credential.origin.clone()
     }
+
+    /// Whether this client makes requests without an API key
+    pub fn is_anonymous(&self) -> bool {
+        self.credential.origin == CredentialOrigin::Anonymous
+    }
 }
@@ -74,6 +74,18 @@ pub enum RoverClientError {
         frontend_url_root: String,
     },
 
+    /// The Studio API could not find a schema with the requested hash for a graph
+    #[error(
+        "The graph registry does not contain a schema with hash \"{hash}\" for graph \"{}\"", graph_ref.name
+    )]
+    NoSchemaForHash {
+        /// The graph ref.
+        graph_ref: GraphRef,
+
+        /// The requested schema hash.
+        hash: String,
+    },
+
     /// Encountered an error sending the request.
     #[error("{}", source)]
     SendRequest {
@@ -208,6 +220,59 @@ pub enum RoverClientError {
 
     #[error("Your check took too long to run")]
     ChecksTimeoutError { url: Option<String> },
+
+    /// Sending another request would exceed the `--max-requests` budget for this invocation.
+    #[error("This command needs to make more than the {max_requests} request(s) to Apollo Studio allowed by `--max-requests`. {}", describe_completed_operations(.completed_operations))]
+    RequestBudgetExceeded {
+        max_requests: u64,
+        /// the operations that completed before the budget ran out, which may have changed something
+        completed_operations: Vec<String>,
+    },
+
+    /// The requests a command is about to make don't fit in what's left of the `--max-requests`
+    /// budget for this invocation, so none of them were sent.
+    #[error("This command needs to make {planned_requests} more request(s) to Apollo Studio, but only {remaining_requests} of the {max_requests} allowed by `--max-requests` are left, so it stopped before sending any of them.")]
+    RequestPlanExceedsBudget {
+        planned_requests: u64,
+        remaining_requests: u64,
+        max_requests: u64,
+    },
+
+    /// Publishing a subgraph to a new variant failed after other subgraphs were published to it.
+    #[error(
+        "Could not publish the subgraph \"{subgraph}\" to the new variant {graph_ref}: {source}"
+    )]
+    VariantCreateIncomplete {
+        graph_ref: GraphRef,
+        subgraph: String,
+        /// the subgraphs that were published before the one that failed
+        published_subgraphs: Vec<String>,
+        /// whether the half-created variant was deleted again
+        rolled_back: bool,
+        source: Box<RoverClientError>,
+    },
+
+    /// A fixture passed to `--replay` doesn't match the requests the command made.
+    #[error("Could not replay the fixture because {msg}")]
+    InvalidFixture { msg: String },
+}
+
+impl RoverClientError {
+    /// Whether this error is likely to go away if the same request is retried,
+    /// i.e. the server responded with a 5xx status code, the request timed out,
+    /// or a connection could not be established.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            RoverClientError::SendRequest { source, .. } => {
+                source.is_timeout()
+                    || source.is_connect()
+                    || source
+                        .status()
+                        .map_or(false, |status| status.is_server_error())
+            }
+            _ => false,
+        }
+    }
 }
 
 fn contract_publish_errors_msg(msgs: &Vec<String>, no_launch: &bool) -> String {
@@ -271,6 +336,17 @@ fn other_check_task_failure_msg(has_build_task: &bool, has_downstream_task: &boo
     )
 }
 
+fn describe_completed_operations(completed_operations: &[String]) -> String {
+    if completed_operations.is_empty() {
+        "No request completed before the limit was reached.".to_string()
+    } else {
+        format!(
+            "These requests completed before the limit was reached, so their changes were made: {}.",
+            completed_operations.join(", ")
+        )
+    }
+}
+
 impl From<introspector_gadget::error::RoverClientError> for RoverClientError {
     fn from(e: introspector_gadget::error::RoverClientError) -> Self {
         match e {
@@ -1,8 +1,9 @@
 use crate::operations::graph::check::runner::graph_check_mutation;
-use crate::shared::{CheckConfig, GitContext, GraphRef};
+use crate::shared::{CheckConfig, ClientFilter, GitContext, GraphRef};
 
 type MutationInput = graph_check_mutation::CheckSchemaAsyncInput;
 type MutationConfig = graph_check_mutation::HistoricQueryParametersInput;
+type MutationClientFilter = graph_check_mutation::ClientInfoFilter;
 type MutationGitContextInput = graph_check_mutation::GitContextInput;
 type MutationVariables = graph_check_mutation::Variables;
 pub(crate) type MutationResponseData = graph_check_mutation::ResponseData;
@@ -47,8 +48,18 @@ impl From<CheckConfig> for MutationConfig {
             query_count_threshold_percentage: input.query_count_threshold_percentage,
             from,
             to,
+            excluded_clients: if input.excluded_clients.is_empty() {
+                None
+            } else {
+                Some(
+                    input
+                        .excluded_clients
+                        .into_iter()
+                        .map(MutationClientFilter::from)
+                        .collect(),
+                )
+            },
             // we don't support configuring these, but we can't leave them out
-            excluded_clients: None,
             excluded_operation_names: None,
             ignored_operations: None,
             included_variants: None,
@@ -56,6 +67,16 @@ impl From<CheckConfig> for MutationConfig {
     }
 }
 
+impl From<ClientFilter> for MutationClientFilter {
+    fn from(client: ClientFilter) -> Self {
+        Self {
+            name: client.name,
+            reference_id: None,
+            version: client.version,
+        }
+    }
+}
+
 impl From<GitContext> for MutationGitContextInput {
     fn from(git_context: GitContext) -> MutationGitContextInput {
         MutationGitContextInput {
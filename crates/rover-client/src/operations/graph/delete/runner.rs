@@ -7,6 +7,8 @@ use crate::RoverClientError;
 
 use graphql_client::*;
 
+use std::cell::Cell;
+
 #[derive(GraphQLQuery)]
 #[graphql(
     query_path = "src/operations/graph/delete/delete_mutation.graphql",
@@ -23,9 +25,21 @@ pub(crate) struct GraphDeleteMutation;
 /// This function deletes a single graph variant from the graph registry
 pub fn run(input: GraphDeleteInput, client: &StudioClient) -> Result<(), RoverClientError> {
     let graph_ref = input.graph_ref.clone();
-    let response_data = client
-        .post::<GraphDeleteMutation>(input.into())
-        .map_err(|e| {
+    let retries = input.retries;
+    // deleting a variant isn't idempotent: an attempt that timed out may still have deleted it,
+    // so a variant that's gone by the time a retry reaches Studio was deleted by this command
+    let attempts = Cell::new(0);
+    let response_data = client.post_with_retries::<GraphDeleteMutation>(
+        || {
+            attempts.set(attempts.get() + 1);
+            input.clone().into()
+        },
+        retries,
+    );
+    let was_retried = attempts.get() > 1;
+    let response_data = match response_data {
+        Err(e) if was_retried && e.to_string().contains("Variant not found") => return Ok(()),
+        response_data => response_data.map_err(|e| {
             if e.to_string().contains("Variant not found") {
                 if let Err(no_variant_err) = variant::run(
                     VariantListInput {
@@ -37,14 +51,17 @@ pub fn run(input: GraphDeleteInput, client: &StudioClient) -> Result<(), RoverCl
                 }
             }
             e
-        })?;
+        })?,
+    };
     let graph = response_data.graph.ok_or(RoverClientError::GraphNotFound {
         graph_ref: graph_ref.clone(),
     })?;
 
-    let variant = graph
-        .variant
-        .ok_or(RoverClientError::GraphNotFound { graph_ref })?;
+    let variant = match graph.variant {
+        Some(variant) => variant,
+        None if was_retried => return Ok(()),
+        None => return Err(RoverClientError::GraphNotFound { graph_ref }),
+    };
 
     if variant.delete.deleted {
         Ok(())
@@ -4,6 +4,7 @@ use crate::shared::GraphRef;
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct GraphDeleteInput {
     pub graph_ref: GraphRef,
+    pub retries: Option<u32>,
 }
 
 type MutationVariables = graph_delete_mutation::Variables;
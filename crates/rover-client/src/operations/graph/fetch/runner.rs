@@ -8,6 +8,8 @@ use crate::RoverClientError;
 // I'm not sure where this should live long-term
 /// this is because of the custom GraphQLDocument scalar in the schema
 type GraphQLDocument = String;
+/// this is because of the custom SHA256 scalar in the schema
+type SHA256 = String;
 
 #[derive(GraphQLQuery)]
 // The paths are relative to the directory where your `Cargo.toml` is located.
@@ -23,6 +25,18 @@ type GraphQLDocument = String;
 /// Snake case of this name is the mod name. i.e. graph_fetch_query
 pub(crate) struct GraphFetchQuery;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "src/operations/graph/fetch/fetch_by_hash_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "Eq, PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. graph_fetch_by_hash_query
+pub(crate) struct GraphFetchByHashQuery;
+
 /// The main function to be used from this module. This function fetches a
 /// schema from apollo studio and returns it in either sdl (default) or json format
 pub fn run(
@@ -30,8 +44,13 @@ pub fn run(
     client: &StudioClient,
 ) -> Result<FetchResponse, RoverClientError> {
     let graph_ref = input.graph_ref.clone();
-    let response_data = client.post::<GraphFetchQuery>(input.into())?;
-    let sdl_contents = get_schema_from_response_data(response_data, graph_ref)?;
+    let sdl_contents = if let Some(hash) = input.hash.clone() {
+        let response_data = client.post::<GraphFetchByHashQuery>(input.into())?;
+        get_schema_by_hash_from_response_data(response_data, graph_ref, hash)?
+    } else {
+        let response_data = client.post::<GraphFetchQuery>(input.into())?;
+        get_schema_from_response_data(response_data, graph_ref)?
+    };
     Ok(FetchResponse {
         sdl: Sdl {
             contents: sdl_contents,
@@ -65,6 +84,20 @@ fn get_schema_from_response_data(
     }
 }
 
+fn get_schema_by_hash_from_response_data(
+    response_data: graph_fetch_by_hash_query::ResponseData,
+    graph_ref: GraphRef,
+    hash: String,
+) -> Result<String, RoverClientError> {
+    let graph = response_data.graph.ok_or(RoverClientError::GraphNotFound {
+        graph_ref: graph_ref.clone(),
+    })?;
+
+    graph
+        .document
+        .ok_or(RoverClientError::NoSchemaForHash { graph_ref, hash })
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -121,6 +154,41 @@ mod tests {
         assert!(output.is_err());
     }
 
+    #[test]
+    fn get_schema_by_hash_from_response_data_works() {
+        let json_response = json!({
+            "graph": {
+                "document": "type Query { hello: String }"
+            }
+        });
+        let data: graph_fetch_by_hash_query::ResponseData =
+            serde_json::from_value(json_response).unwrap();
+        let output = get_schema_by_hash_from_response_data(data, mock_graph_ref(), mock_hash());
+
+        assert_eq!(output.unwrap(), "type Query { hello: String }".to_string());
+    }
+
+    #[test]
+    fn get_schema_by_hash_from_response_data_errs_on_unknown_hash() {
+        let json_response = json!({
+            "graph": {
+                "document": null
+            }
+        });
+        let data: graph_fetch_by_hash_query::ResponseData =
+            serde_json::from_value(json_response).unwrap();
+        let output = get_schema_by_hash_from_response_data(data, mock_graph_ref(), mock_hash());
+
+        assert!(matches!(
+            output,
+            Err(RoverClientError::NoSchemaForHash { .. })
+        ));
+    }
+
+    fn mock_hash() -> String {
+        "a".repeat(64)
+    }
+
     fn mock_graph_ref() -> GraphRef {
         GraphRef {
             name: "mygraph".to_string(),
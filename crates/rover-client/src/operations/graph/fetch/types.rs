@@ -1,11 +1,15 @@
-use crate::operations::graph::fetch::runner::graph_fetch_query;
+use crate::operations::graph::fetch::runner::{graph_fetch_by_hash_query, graph_fetch_query};
 use crate::shared::GraphRef;
 
 type QueryVariables = graph_fetch_query::Variables;
+type ByHashQueryVariables = graph_fetch_by_hash_query::Variables;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct GraphFetchInput {
     pub graph_ref: GraphRef,
+    /// the SHA256 hash of a previously published schema to fetch instead of
+    /// the variant's latest publication
+    pub hash: Option<String>,
 }
 
 impl From<GraphFetchInput> for QueryVariables {
@@ -16,3 +20,12 @@ impl From<GraphFetchInput> for QueryVariables {
         }
     }
 }
+
+impl From<GraphFetchInput> for ByHashQueryVariables {
+    fn from(input: GraphFetchInput) -> Self {
+        Self {
+            graph_id: input.graph_ref.name,
+            hash: input.hash.unwrap_or_default(),
+        }
+    }
+}
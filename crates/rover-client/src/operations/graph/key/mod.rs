@@ -0,0 +1,5 @@
+mod runner;
+mod types;
+
+pub use runner::{create, list, remove};
+pub use types::{GraphApiKey, GraphKeyCreateInput, GraphKeyListInput, GraphKeyRemoveInput};
@@ -0,0 +1,184 @@
+use crate::blocking::StudioClient;
+use crate::operations::graph::key::{
+    GraphApiKey, GraphKeyCreateInput, GraphKeyListInput, GraphKeyRemoveInput,
+};
+use crate::shared::GraphRef;
+use crate::RoverClientError;
+
+use graphql_client::*;
+use serde::Serialize;
+
+/// this is because of the custom Void scalar in the schema, which is always null
+type Void = String;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "src/operations/graph/key/list_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "Eq, PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. graph_key_list_query
+pub(crate) struct GraphKeyListQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "src/operations/graph/key/create_mutation.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "Eq, PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. graph_key_create_mutation
+pub(crate) struct GraphKeyCreateMutation;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    query_path = "src/operations/graph/key/remove_mutation.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "Eq, PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. graph_key_remove_mutation
+pub(crate) struct GraphKeyRemoveMutation;
+
+/// Lists every graph API key that is active for a graph
+pub fn list(
+    input: GraphKeyListInput,
+    client: &StudioClient,
+) -> Result<Vec<GraphApiKey>, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let response_data = client.post::<GraphKeyListQuery>(input.into())?;
+    get_keys_from_response_data(response_data, graph_ref)
+}
+
+/// Creates a new graph API key, returning it along with its secret token
+pub fn create(
+    input: GraphKeyCreateInput,
+    client: &StudioClient,
+) -> Result<GraphApiKey, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let response_data = client.post::<GraphKeyCreateMutation>(input.into())?;
+    let key = response_data
+        .graph
+        .ok_or(RoverClientError::GraphNotFound { graph_ref })?
+        .new_key;
+    Ok(GraphApiKey {
+        id: key.id,
+        name: key.key_name,
+        role: get_role_name(&key.role)?,
+        token: key.token,
+    })
+}
+
+/// Revokes a graph API key, so it can no longer be used to authenticate
+pub fn remove(input: GraphKeyRemoveInput, client: &StudioClient) -> Result<(), RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let response_data = client.post::<GraphKeyRemoveMutation>(input.into())?;
+    response_data
+        .graph
+        .ok_or(RoverClientError::GraphNotFound { graph_ref })?;
+    Ok(())
+}
+
+fn get_keys_from_response_data(
+    response_data: graph_key_list_query::ResponseData,
+    graph_ref: GraphRef,
+) -> Result<Vec<GraphApiKey>, RoverClientError> {
+    let graph = response_data
+        .graph
+        .ok_or(RoverClientError::GraphNotFound { graph_ref })?;
+    graph
+        .api_keys
+        .unwrap_or_default()
+        .into_iter()
+        .map(|key| {
+            Ok(GraphApiKey {
+                id: key.id,
+                name: key.key_name,
+                role: get_role_name(&key.role)?,
+                token: key.token,
+            })
+        })
+        .collect()
+}
+
+/// The name of a `UserPermission` from any operation's generated module, i.e. `GRAPH_ADMIN`
+fn get_role_name(role: &impl Serialize) -> Result<String, RoverClientError> {
+    let role = serde_json::to_value(role)?;
+    Ok(role.as_str().unwrap_or_default().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn get_keys_from_response_data_works() {
+        let json_response = json!({
+            "graph": {
+                "apiKeys": [
+                    {
+                        "id": "key-1",
+                        "keyName": "ci",
+                        "role": "CONTRIBUTOR",
+                        "token": "service:mygraph:abc123"
+                    },
+                    {
+                        "id": "key-2",
+                        "keyName": null,
+                        "role": "GRAPH_ADMIN",
+                        "token": "service:mygraph:def456"
+                    }
+                ]
+            }
+        });
+        let data: graph_key_list_query::ResponseData =
+            serde_json::from_value(json_response).unwrap();
+        let output = get_keys_from_response_data(data, mock_graph_ref());
+
+        assert_eq!(
+            output.unwrap(),
+            vec![
+                GraphApiKey {
+                    id: "key-1".to_string(),
+                    name: Some("ci".to_string()),
+                    role: "CONTRIBUTOR".to_string(),
+                    token: "service:mygraph:abc123".to_string(),
+                },
+                GraphApiKey {
+                    id: "key-2".to_string(),
+                    name: None,
+                    role: "GRAPH_ADMIN".to_string(),
+                    token: "service:mygraph:def456".to_string(),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn get_keys_from_response_data_errs_on_no_graph() {
+        let json_response = json!({ "graph": null });
+        let data: graph_key_list_query::ResponseData =
+            serde_json::from_value(json_response).unwrap();
+        let output = get_keys_from_response_data(data, mock_graph_ref());
+
+        assert!(matches!(
+            output,
+            Err(RoverClientError::GraphNotFound { .. })
+        ));
+    }
+
+    fn mock_graph_ref() -> GraphRef {
+        GraphRef {
+            name: "mygraph".to_string(),
+            variant: "current".to_string(),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::operations::graph::key::runner::{
+    graph_key_create_mutation, graph_key_list_query, graph_key_remove_mutation,
+};
+
+use crate::shared::GraphRef;
+
+use serde::Serialize;
+
+type ListQueryVariables = graph_key_list_query::Variables;
+type CreateMutationVariables = graph_key_create_mutation::Variables;
+type RemoveMutationVariables = graph_key_remove_mutation::Variables;
+
+/// A graph API key, including its secret token
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct GraphApiKey {
+    pub id: String,
+    pub name: Option<String>,
+    /// the permission level of the key, i.e. `GRAPH_ADMIN`
+    pub role: String,
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GraphKeyListInput {
+    pub graph_ref: GraphRef,
+}
+
+impl From<GraphKeyListInput> for ListQueryVariables {
+    fn from(input: GraphKeyListInput) -> Self {
+        Self {
+            graph_id: input.graph_ref.name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GraphKeyCreateInput {
+    pub graph_ref: GraphRef,
+    pub name: Option<String>,
+    /// the permission level to give the new key, i.e. `GRAPH_ADMIN`
+    pub role: String,
+}
+
+impl From<GraphKeyCreateInput> for CreateMutationVariables {
+    fn from(input: GraphKeyCreateInput) -> Self {
+        Self {
+            graph_id: input.graph_ref.name,
+            key_name: input.name,
+            role: get_create_role(&input.role),
+        }
+    }
+}
+
+fn get_create_role(role: &str) -> graph_key_create_mutation::UserPermission {
+    use graph_key_create_mutation::UserPermission;
+    match role {
+        "BILLING_MANAGER" => UserPermission::BILLING_MANAGER,
+        "CONSUMER" => UserPermission::CONSUMER,
+        "CONTRIBUTOR" => UserPermission::CONTRIBUTOR,
+        "DOCUMENTER" => UserPermission::DOCUMENTER,
+        "GRAPH_ADMIN" => UserPermission::GRAPH_ADMIN,
+        "LEGACY_GRAPH_KEY" => UserPermission::LEGACY_GRAPH_KEY,
+        "OBSERVER" => UserPermission::OBSERVER,
+        "ORG_ADMIN" => UserPermission::ORG_ADMIN,
+        other => UserPermission::Other(other.to_string()),
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GraphKeyRemoveInput {
+    pub graph_ref: GraphRef,
+    pub id: String,
+}
+
+impl From<GraphKeyRemoveInput> for RemoveMutationVariables {
+    fn from(input: GraphKeyRemoveInput) -> Self {
+        Self {
+            graph_id: input.graph_ref.name,
+            id: input.id,
+        }
+    }
+}
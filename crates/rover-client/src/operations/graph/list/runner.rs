@@ -0,0 +1,156 @@
+use crate::blocking::StudioClient;
+use crate::operations::graph::list::{GraphInfo, GraphListInput, GraphListResponse};
+use crate::RoverClientError;
+
+use graphql_client::*;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/graph/list/list_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "Eq, PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. graph_list_query
+pub(crate) struct GraphListQuery;
+
+/// Lists every graph the API key can access, along with their variants.
+/// For a user key, that's every graph in the user's organizations,
+/// and for a graph key, it's only the graph the key belongs to.
+pub fn run(
+    input: GraphListInput,
+    client: &StudioClient,
+) -> Result<GraphListResponse, RoverClientError> {
+    let organization = input.organization.clone();
+    let response_data = client.post::<GraphListQuery>(input.into())?;
+    let mut graphs = get_graphs_from_response_data(response_data)?;
+    if let Some(organization) = organization {
+        graphs.retain(|graph| graph.organization.as_ref() == Some(&organization));
+    }
+    graphs.sort_unstable_by(|a, b| {
+        a.organization
+            .cmp(&b.organization)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    Ok(GraphListResponse { graphs })
+}
+
+fn get_graphs_from_response_data(
+    response_data: graph_list_query::ResponseData,
+) -> Result<Vec<GraphInfo>, RoverClientError> {
+    let me = response_data.me.ok_or(RoverClientError::InvalidKey)?;
+    let graphs = match me.on {
+        graph_list_query::GraphListQueryMeOn::User(user) => user
+            .memberships
+            .into_iter()
+            .flat_map(|membership| {
+                let organization = membership.account.id;
+                membership
+                    .account
+                    .graphs
+                    .into_iter()
+                    .map(move |graph| GraphInfo {
+                        id: graph.id,
+                        title: graph.title,
+                        organization: Some(organization.clone()),
+                        variants: graph
+                            .variants
+                            .into_iter()
+                            .map(|variant| variant.name)
+                            .collect(),
+                    })
+            })
+            .collect(),
+        graph_list_query::GraphListQueryMeOn::Graph(graph) => vec![GraphInfo {
+            id: graph.id,
+            title: graph.title,
+            organization: graph.account.map(|account| account.id),
+            variants: graph
+                .variants
+                .into_iter()
+                .map(|variant| variant.name)
+                .collect(),
+        }],
+        _ => Vec::new(),
+    };
+    Ok(graphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn get_graphs_from_response_data_works_for_users() {
+        let json_response = json!({
+            "me": {
+                "__typename": "User",
+                "memberships": [
+                    {
+                        "account": {
+                            "id": "my-org",
+                            "graphs": [
+                                {
+                                    "id": "products",
+                                    "title": "Products",
+                                    "variants": [{ "name": "current" }, { "name": "staging" }]
+                                }
+                            ]
+                        }
+                    }
+                ]
+            }
+        });
+        let data: graph_list_query::ResponseData = serde_json::from_value(json_response).unwrap();
+        let output = get_graphs_from_response_data(data);
+
+        assert_eq!(
+            output.unwrap(),
+            vec![GraphInfo {
+                id: "products".to_string(),
+                title: "Products".to_string(),
+                organization: Some("my-org".to_string()),
+                variants: vec!["current".to_string(), "staging".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn get_graphs_from_response_data_works_for_graphs() {
+        let json_response = json!({
+            "me": {
+                "__typename": "Graph",
+                "id": "products",
+                "title": "Products",
+                "account": null,
+                "variants": [{ "name": "current" }]
+            }
+        });
+        let data: graph_list_query::ResponseData = serde_json::from_value(json_response).unwrap();
+        let output = get_graphs_from_response_data(data);
+
+        assert_eq!(
+            output.unwrap(),
+            vec![GraphInfo {
+                id: "products".to_string(),
+                title: "Products".to_string(),
+                organization: None,
+                variants: vec!["current".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn get_graphs_from_response_data_errs_with_no_identity() {
+        let json_response = json!({ "me": null });
+        let data: graph_list_query::ResponseData = serde_json::from_value(json_response).unwrap();
+        let output = get_graphs_from_response_data(data);
+
+        assert!(matches!(output, Err(RoverClientError::InvalidKey)));
+    }
+}
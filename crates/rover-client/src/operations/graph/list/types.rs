@@ -0,0 +1,31 @@
+use crate::operations::graph::list::runner::graph_list_query;
+
+use serde::Serialize;
+
+type QueryVariables = graph_list_query::Variables;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GraphListInput {
+    /// only list the graphs that belong to the organization with this ID
+    pub organization: Option<String>,
+}
+
+impl From<GraphListInput> for QueryVariables {
+    fn from(_input: GraphListInput) -> Self {
+        Self {}
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct GraphListResponse {
+    pub graphs: Vec<GraphInfo>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct GraphInfo {
+    pub id: String,
+    pub title: String,
+    /// the ID of the organization the graph belongs to
+    pub organization: Option<String>,
+    pub variants: Vec<String>,
+}
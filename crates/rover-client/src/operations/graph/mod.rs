@@ -16,5 +16,12 @@ pub mod introspect;
 /// "graph delete" command execution
 pub mod delete;
 
-/// internal module for getting info about variants
-pub(crate) mod variant;
+/// "graph key rotate" command execution
+pub mod key;
+
+/// "graph list" command execution
+pub mod list;
+
+/// "graph open" and "subgraph open" command execution,
+/// and internal module for getting info about variants
+pub mod variant;
@@ -3,6 +3,7 @@ use graphql_client::*;
 use crate::blocking::StudioClient;
 use crate::operations::graph::publish::types::{ChangeSummary, FieldChanges, TypeChanges};
 use crate::operations::graph::publish::{GraphPublishInput, GraphPublishResponse};
+use crate::operations::variant::launch::{self, VariantLaunchInput};
 use crate::shared::GraphRef;
 use crate::RoverClientError;
 
@@ -27,9 +28,25 @@ pub fn run(
     client: &StudioClient,
 ) -> Result<GraphPublishResponse, RoverClientError> {
     let graph_ref = input.graph_ref.clone();
-    let data = client.post::<GraphPublishMutation>(input.into())?;
-    let publish_response = get_publish_response_from_data(data, graph_ref)?;
-    build_response(publish_response)
+    let retries = input.retries;
+    // re-publishing the same schema is a no-op in the registry since publishes
+    // are keyed by schema hash, so it's safe to retry this mutation
+    let data =
+        client.post_with_retries::<GraphPublishMutation>(|| input.clone().into(), retries)?;
+    let publish_response = get_publish_response_from_data(data, graph_ref.clone())?;
+    let started_launch = publish_response.code != "NO_CHANGES";
+    let response = build_response(publish_response)?;
+
+    if !started_launch {
+        return Ok(response);
+    }
+    // the mutation doesn't return the launch it started, so it's looked up afterwards.
+    // the schema was already published, so failing to find the launch isn't an error
+    let launch = launch::run(VariantLaunchInput { graph_ref }, client).unwrap_or_else(|e| {
+        tracing::debug!("could not look up the launch for the publish: {}", e);
+        None
+    });
+    Ok(response.with_launch(launch))
 }
 
 fn get_publish_response_from_data(
@@ -60,8 +77,7 @@ fn build_response(
     }
 
     let hash = match &publish_response.publication {
-        // we only want to print the first 6 chars of a hash
-        Some(publication) => publication.schema.hash.clone()[..6].to_string(),
+        Some(publication) => publication.schema.hash.clone(),
         None => {
             let msg = format!(
                 "No data in response from schema publish. Failed with message: {}",
@@ -96,6 +112,9 @@ fn build_response(
     Ok(GraphPublishResponse {
         api_schema_hash: hash,
         change_summary,
+        launch_id: None,
+        launch_status: None,
+        launch_url: None,
     })
 }
 
@@ -227,6 +246,9 @@ mod tests {
             GraphPublishResponse {
                 api_schema_hash: "123456".to_string(),
                 change_summary: ChangeSummary::none(),
+                launch_id: None,
+                launch_status: None,
+                launch_url: None,
             }
         );
     }
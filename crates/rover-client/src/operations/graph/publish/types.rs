@@ -1,4 +1,5 @@
 use crate::operations::graph::publish::runner::graph_publish_mutation;
+use crate::operations::variant::launch::LaunchInfo;
 use crate::shared::{GitContext, GraphRef};
 
 use serde::Serialize;
@@ -10,6 +11,7 @@ pub struct GraphPublishInput {
     pub graph_ref: GraphRef,
     pub proposed_schema: String,
     pub git_context: GitContext,
+    pub retries: Option<u32>,
 }
 
 type MutationVariables = graph_publish_mutation::Variables;
@@ -42,6 +44,21 @@ pub struct GraphPublishResponse {
     pub api_schema_hash: String,
     #[serde(flatten)]
     pub change_summary: ChangeSummary,
+    /// the launch started by the publish, which is `None` if the schema didn't change
+    pub launch_id: Option<String>,
+    pub launch_status: Option<String>,
+    pub launch_url: Option<String>,
+}
+
+impl GraphPublishResponse {
+    pub(crate) fn with_launch(mut self, launch: Option<LaunchInfo>) -> Self {
+        if let Some(launch) = launch {
+            self.launch_id = Some(launch.id);
+            self.launch_status = Some(launch.status);
+            self.launch_url = Some(launch.url);
+        }
+        self
+    }
 }
 
 #[derive(Clone, Serialize, Debug, Eq, PartialEq)]
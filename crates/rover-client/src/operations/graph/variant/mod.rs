@@ -1,5 +1,5 @@
 mod runner;
 mod types;
 
-pub(crate) use runner::run;
-pub(crate) use types::VariantListInput;
+pub use runner::run;
+pub use types::VariantListInput;
@@ -17,8 +17,9 @@ use graphql_client::*;
 pub(crate) struct VariantListQuery;
 
 /// The main function to be used from this module.
-/// This function lists all the variants for a given graph ref
-pub fn run(input: VariantListInput, client: &StudioClient) -> Result<(), RoverClientError> {
+/// This function lists all the variants for a given graph ref, erroring if the
+/// variant does not exist, and returns the root URL of Apollo Studio's frontend
+pub fn run(input: VariantListInput, client: &StudioClient) -> Result<String, RoverClientError> {
     let graph_ref = input.graph_ref.clone();
     let response_data = client.post::<VariantListQuery>(input.into())?;
     let graph = response_data.graph.ok_or(RoverClientError::GraphNotFound {
@@ -38,6 +39,6 @@ pub fn run(input: VariantListInput, client: &StudioClient) -> Result<(), RoverCl
             frontend_url_root: response_data.frontend_url_root,
         })
     } else {
-        Ok(())
+        Ok(response_data.frontend_url_root)
     }
 }
@@ -1,8 +1,9 @@
 use crate::operations::subgraph::check::runner::subgraph_check_mutation;
-use crate::shared::{CheckConfig, GitContext, GraphRef};
+use crate::shared::{CheckConfig, ClientFilter, GitContext, GraphRef};
 
 type MutationInput = subgraph_check_mutation::SubgraphCheckAsyncInput;
 type MutationConfig = subgraph_check_mutation::HistoricQueryParametersInput;
+type MutationClientFilter = subgraph_check_mutation::ClientInfoFilter;
 type MutationGitContextInput = subgraph_check_mutation::GitContextInput;
 type MutationVariables = subgraph_check_mutation::Variables;
 pub(crate) type MutationResponseData = subgraph_check_mutation::ResponseData;
@@ -49,8 +50,18 @@ impl From<CheckConfig> for MutationConfig {
             query_count_threshold_percentage: input.query_count_threshold_percentage,
             from,
             to,
+            excluded_clients: if input.excluded_clients.is_empty() {
+                None
+            } else {
+                Some(
+                    input
+                        .excluded_clients
+                        .into_iter()
+                        .map(MutationClientFilter::from)
+                        .collect(),
+                )
+            },
             // we don't support configuring these, but we can't leave them out
-            excluded_clients: None,
             excluded_operation_names: None,
             ignored_operations: None,
             included_variants: None,
@@ -58,6 +69,16 @@ impl From<CheckConfig> for MutationConfig {
     }
 }
 
+impl From<ClientFilter> for MutationClientFilter {
+    fn from(client: ClientFilter) -> Self {
+        Self {
+            name: client.name,
+            reference_id: None,
+            version: client.version,
+        }
+    }
+}
+
 impl From<GitContext> for MutationGitContextInput {
     fn from(git_context: GitContext) -> MutationGitContextInput {
         MutationGitContextInput {
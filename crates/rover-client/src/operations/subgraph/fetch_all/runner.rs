@@ -0,0 +1,123 @@
+use super::types::*;
+use crate::blocking::StudioClient;
+use crate::shared::GraphRef;
+use crate::RoverClientError;
+
+use graphql_client::*;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/subgraph/fetch_all/fetch_all_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "Eq, PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. subgraph_fetch_all_query
+pub(crate) struct SubgraphFetchAllQuery;
+
+/// Fetches the name, routing url, and SDL of every subgraph of a graph in a single request
+pub fn run(
+    input: SubgraphFetchAllInput,
+    client: &StudioClient,
+) -> Result<Vec<FetchedSubgraph>, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let response_data = client.post::<SubgraphFetchAllQuery>(input.into())?;
+    get_subgraphs_from_response_data(response_data, graph_ref)
+}
+
+fn get_subgraphs_from_response_data(
+    response_data: SubgraphFetchAllResponseData,
+    graph_ref: GraphRef,
+) -> Result<Vec<FetchedSubgraph>, RoverClientError> {
+    let graph = response_data.graph.ok_or(RoverClientError::GraphNotFound {
+        graph_ref: graph_ref.clone(),
+    })?;
+
+    let variant = graph.variant.ok_or(RoverClientError::GraphNotFound {
+        graph_ref: graph_ref.clone(),
+    })?;
+
+    match variant.subgraphs {
+        Some(subgraphs) if !subgraphs.is_empty() => Ok(subgraphs
+            .into_iter()
+            .map(|subgraph| FetchedSubgraph {
+                name: subgraph.name,
+                url: subgraph.url,
+                sdl: subgraph.active_partial_schema.sdl,
+            })
+            .collect()),
+        _ => Err(RoverClientError::ExpectedFederatedGraph {
+            graph_ref,
+            can_operation_convert: false,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn get_subgraphs_from_response_data_works() {
+        let json_response = json!({
+            "graph": {
+                "variant": {
+                    "subgraphs": [
+                        {
+                            "name": "products",
+                            "url": "http://products.example.com",
+                            "activePartialSchema": {
+                                "sdl": "type Query { products: [String] }"
+                            }
+                        },
+                        {
+                            "name": "reviews",
+                            "url": null,
+                            "activePartialSchema": {
+                                "sdl": "type Query { reviews: [String] }"
+                            }
+                        }
+                    ]
+                }
+            }
+        });
+        let data: SubgraphFetchAllResponseData = serde_json::from_value(json_response).unwrap();
+        let output = get_subgraphs_from_response_data(data, mock_graph_ref()).unwrap();
+
+        assert_eq!(
+            output,
+            vec![
+                FetchedSubgraph {
+                    name: "products".to_string(),
+                    url: Some("http://products.example.com".to_string()),
+                    sdl: "type Query { products: [String] }".to_string(),
+                },
+                FetchedSubgraph {
+                    name: "reviews".to_string(),
+                    url: None,
+                    sdl: "type Query { reviews: [String] }".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn get_subgraphs_from_response_data_errs_with_no_variant() {
+        let json_response = json!({ "graph": { "variant": null } });
+        let data: SubgraphFetchAllResponseData = serde_json::from_value(json_response).unwrap();
+        let output = get_subgraphs_from_response_data(data, mock_graph_ref());
+        assert!(output.is_err());
+    }
+
+    fn mock_graph_ref() -> GraphRef {
+        GraphRef {
+            name: "mygraph".to_string(),
+            variant: "current".to_string(),
+        }
+    }
+}
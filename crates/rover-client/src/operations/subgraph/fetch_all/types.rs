@@ -0,0 +1,28 @@
+use crate::shared::GraphRef;
+
+use super::runner::subgraph_fetch_all_query;
+
+pub(crate) type SubgraphFetchAllResponseData = subgraph_fetch_all_query::ResponseData;
+pub(crate) type QueryVariables = subgraph_fetch_all_query::Variables;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SubgraphFetchAllInput {
+    pub graph_ref: GraphRef,
+}
+
+impl From<SubgraphFetchAllInput> for QueryVariables {
+    fn from(input: SubgraphFetchAllInput) -> Self {
+        Self {
+            graph_id: input.graph_ref.name,
+            variant: input.graph_ref.variant,
+        }
+    }
+}
+
+/// A subgraph of a graph, as it was last published
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FetchedSubgraph {
+    pub name: String,
+    pub url: Option<String>, // optional, and may not be a real url
+    pub sdl: String,
+}
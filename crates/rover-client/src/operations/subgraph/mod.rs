@@ -10,6 +10,9 @@ pub mod check;
 /// "subgraph fetch" command execution
 pub mod fetch;
 
+/// "subgraph fetch" of every subgraph of a graph, for `rover dev --remote-subgraphs`
+pub mod fetch_all;
+
 /// "subgraph publish" command execution
 pub mod publish;
 
@@ -4,6 +4,7 @@ use crate::operations::graph::variant::VariantListInput;
 use crate::operations::{
     config::is_federated::{self, IsFederatedInput},
     graph::variant,
+    variant::launch::{self, VariantLaunchInput},
 };
 use crate::shared::GraphRef;
 use crate::RoverClientError;
@@ -31,7 +32,6 @@ pub fn run(
     client: &StudioClient,
 ) -> Result<SubgraphPublishResponse, RoverClientError> {
     let graph_ref = input.graph_ref.clone();
-    let variables: MutationVariables = input.clone().into();
     // We don't want to implicitly convert non-federated graph to supergraphs.
     // Error here if no --convert flag is passed _and_ the current context
     // is non-federated. Add a suggestion to require a --convert flag.
@@ -70,9 +70,29 @@ pub fn run(
             );
         }
     }
-    let data = client.post::<SubgraphPublishMutation>(variables)?;
-    let publish_response = get_publish_response_from_data(data, graph_ref)?;
-    Ok(build_response(publish_response))
+    // re-publishing the same subgraph schema is a no-op in the registry since publishes
+    // are keyed by schema hash, so it's safe to retry this mutation
+    let data = client.post_with_retries::<SubgraphPublishMutation>(
+        || MutationVariables::from(input.clone()),
+        input.retries,
+    )?;
+    let publish_response = get_publish_response_from_data(data, graph_ref.clone())?;
+    let mut response = build_response(publish_response);
+
+    // Studio only returns a launch URL when the publish started a launch, but it doesn't
+    // return the launch itself. the subgraph was already published,
+    // so failing to find the launch isn't an error
+    if response.launch_url.is_some() {
+        match launch::run(VariantLaunchInput { graph_ref }, client) {
+            Ok(Some(launch)) => {
+                response.launch_id = Some(launch.id);
+                response.launch_status = Some(launch.status);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::debug!("could not look up the launch for the publish: {}", e),
+        }
+    }
+    Ok(response)
 }
 
 fn get_publish_response_from_data(
@@ -111,6 +131,8 @@ fn build_response(publish_response: UpdateResponse) -> SubgraphPublishResponse {
         build_errors,
         launch_cli_copy: publish_response.launch_cli_copy,
         launch_url: publish_response.launch_url,
+        launch_id: None,
+        launch_status: None,
     }
 }
 
@@ -158,6 +180,8 @@ mod tests {
                 subgraph_was_created: true,
                 launch_url: None,
                 launch_cli_copy: None,
+                launch_id: None,
+                launch_status: None,
             }
         );
     }
@@ -182,6 +206,8 @@ mod tests {
                 subgraph_was_created: true,
                 launch_url: None,
                 launch_cli_copy: None,
+                launch_id: None,
+                launch_status: None,
             }
         );
     }
@@ -215,6 +241,8 @@ mod tests {
                 subgraph_was_created: false,
                 launch_url: None,
                 launch_cli_copy: None,
+                launch_id: None,
+                launch_status: None,
             }
         );
     }
@@ -243,6 +271,8 @@ mod tests {
                 launch_cli_copy: Some(
                     "You can monitor this launch in Apollo Studio: test.com/launchurl".to_string()
                 ),
+                launch_id: None,
+                launch_status: None,
             }
         );
     }
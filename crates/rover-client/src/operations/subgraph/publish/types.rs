@@ -22,6 +22,7 @@ pub struct SubgraphPublishInput {
     pub schema: String,
     pub git_context: GitContext,
     pub convert_to_federated_graph: bool,
+    pub retries: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Eq, PartialEq)]
@@ -38,6 +39,11 @@ pub struct SubgraphPublishResponse {
     pub launch_url: Option<String>,
 
     pub launch_cli_copy: Option<String>,
+
+    /// the launch started by the publish, which is `None` if it didn't start one
+    pub launch_id: Option<String>,
+
+    pub launch_status: Option<String>,
 }
 
 impl From<SubgraphPublishInput> for MutationVariables {
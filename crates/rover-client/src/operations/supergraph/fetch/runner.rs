@@ -2,7 +2,7 @@ use apollo_federation_types::build::BuildError;
 use graphql_client::*;
 
 use crate::blocking::StudioClient;
-use crate::operations::supergraph::fetch::SupergraphFetchInput;
+use crate::operations::supergraph::fetch::{SupergraphFetchInput, SupergraphSchema};
 use crate::shared::{FetchResponse, GraphRef, Sdl, SdlType};
 use crate::RoverClientError;
 
@@ -25,19 +25,21 @@ type GraphQLDocument = String;
 pub(crate) struct SupergraphFetchQuery;
 
 /// The main function to be used from this module. This function fetches a
-/// core schema from apollo studio
+/// core schema, or the API schema derived from it, from apollo studio
 pub fn run(
     input: SupergraphFetchInput,
     client: &StudioClient,
 ) -> Result<FetchResponse, RoverClientError> {
     let graph_ref = input.graph_ref.clone();
+    let schema = input.schema;
     let response_data = client.post::<SupergraphFetchQuery>(input.into())?;
-    get_supergraph_sdl_from_response_data(response_data, graph_ref)
+    get_supergraph_sdl_from_response_data(response_data, graph_ref, schema)
 }
 
 fn get_supergraph_sdl_from_response_data(
     response_data: supergraph_fetch_query::ResponseData,
     graph_ref: GraphRef,
+    schema: SupergraphSchema,
 ) -> Result<FetchResponse, RoverClientError> {
     let graph = response_data.graph.ok_or(RoverClientError::GraphNotFound {
         graph_ref: graph_ref.clone(),
@@ -59,13 +61,15 @@ fn get_supergraph_sdl_from_response_data(
                         .map(|error| BuildError::composition_error(error.code, Some(error.message)))
                         .collect(),
                 }),
-            supergraph_fetch_query::SupergraphFetchQueryGraphVariantLatestApprovedLaunchBuildResult::BuildSuccess(success) =>
+            supergraph_fetch_query::SupergraphFetchQueryGraphVariantLatestApprovedLaunchBuildResult::BuildSuccess(success) => {
+                let (contents, r#type) = match schema {
+                    SupergraphSchema::Core => (success.core_schema.core_document, SdlType::Supergraph),
+                    SupergraphSchema::Api => (success.core_schema.api_document, SdlType::Graph),
+                };
                 Ok(FetchResponse {
-                    sdl: Sdl {
-                        contents: success.core_schema.core_document,
-                        r#type: SdlType::Supergraph,
-                    },
+                    sdl: Sdl { contents, r#type },
                 })
+            }
         }
     } else {
         let mut valid_variants = Vec::new();
@@ -108,6 +112,7 @@ mod tests {
                                 "__typename": "BuildSuccess",
                                 "coreSchema": {
                                     "coreDocument": "type Query { hello: String }",
+                                    "apiDocument": "type Query { hello: String }",
                                 },
                             },
                         },
@@ -122,7 +127,7 @@ mod tests {
         let data: supergraph_fetch_query::ResponseData =
             serde_json::from_value(json_response).unwrap();
         let graph_ref = mock_graph_ref();
-        let output = get_supergraph_sdl_from_response_data(data, graph_ref);
+        let output = get_supergraph_sdl_from_response_data(data, graph_ref, SupergraphSchema::Core);
 
         assert!(output.is_ok());
         assert_eq!(
@@ -136,6 +141,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_supergraph_sdl_from_response_data_gets_api_schema() {
+        let json_response = json!({
+            "frontendUrlRoot": "https://studio.apollographql.com",
+            "graph": {
+                "variant": {
+                    "latestApprovedLaunch": {
+                        "build": {
+                            "result": {
+                                "__typename": "BuildSuccess",
+                                "coreSchema": {
+                                    "coreDocument": "type Query @join__type(graph: PRODUCTS) { hello: String }",
+                                    "apiDocument": "type Query { hello: String }",
+                                },
+                            },
+                        },
+                    },
+                },
+                "variants": [],
+            },
+        });
+        let data: supergraph_fetch_query::ResponseData =
+            serde_json::from_value(json_response).unwrap();
+        let output =
+            get_supergraph_sdl_from_response_data(data, mock_graph_ref(), SupergraphSchema::Api);
+        assert_eq!(
+            output.unwrap(),
+            FetchResponse {
+                sdl: Sdl {
+                    contents: "type Query { hello: String }".to_string(),
+                    r#type: SdlType::Graph,
+                }
+            }
+        );
+    }
+
     #[test]
     fn get_schema_from_response_data_errs_on_no_graph() {
         let json_response =
@@ -143,7 +184,8 @@ mod tests {
         let data: supergraph_fetch_query::ResponseData =
             serde_json::from_value(json_response).unwrap();
         let graph_ref = mock_graph_ref();
-        let output = get_supergraph_sdl_from_response_data(data, graph_ref.clone());
+        let output =
+            get_supergraph_sdl_from_response_data(data, graph_ref.clone(), SupergraphSchema::Core);
         let expected_error = RoverClientError::GraphNotFound { graph_ref }.to_string();
         let actual_error = output.unwrap_err().to_string();
         assert_eq!(actual_error, expected_error);
@@ -164,7 +206,8 @@ mod tests {
         let data: supergraph_fetch_query::ResponseData =
             serde_json::from_value(json_response).unwrap();
         let graph_ref = mock_graph_ref();
-        let output = get_supergraph_sdl_from_response_data(data, graph_ref.clone());
+        let output =
+            get_supergraph_sdl_from_response_data(data, graph_ref.clone(), SupergraphSchema::Core);
         let expected_error = RoverClientError::NoSchemaForVariant {
             graph_ref,
             valid_variants: vec![valid_variant],
@@ -199,7 +242,8 @@ mod tests {
         let data: supergraph_fetch_query::ResponseData =
             serde_json::from_value(json_response).unwrap();
         let graph_ref = mock_graph_ref();
-        let output = get_supergraph_sdl_from_response_data(data, graph_ref.clone());
+        let output =
+            get_supergraph_sdl_from_response_data(data, graph_ref.clone(), SupergraphSchema::Core);
         let expected_error = RoverClientError::NoSupergraphBuilds {
             graph_ref,
             source: BuildErrors::new(),
@@ -3,9 +3,21 @@ use crate::shared::GraphRef;
 
 type QueryVariables = supergraph_fetch_query::Variables;
 
+/// The schemas composition produces for a supergraph
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SupergraphSchema {
+    /// The supergraph schema routers run, with the join directives that tell them
+    /// which subgraph resolves each type and field
+    Core,
+
+    /// The schema that clients can query, derived from the supergraph schema
+    Api,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SupergraphFetchInput {
     pub graph_ref: GraphRef,
+    pub schema: SupergraphSchema,
 }
 
 impl From<SupergraphFetchInput> for QueryVariables {
@@ -0,0 +1,289 @@
+use crate::blocking::StudioClient;
+use crate::operations::graph::delete::{self as graph_delete, GraphDeleteInput};
+use crate::operations::graph::publish::{self as graph_publish, GraphPublishInput};
+use crate::operations::subgraph::publish::{self as subgraph_publish, SubgraphPublishInput};
+use crate::operations::variant::create::{VariantCreateInput, VariantCreateResponse};
+use crate::shared::GraphRef;
+use crate::RoverClientError;
+
+use graphql_client::*;
+
+/// this is because of the custom GraphQLDocument scalar in the schema
+type GraphQLDocument = String;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/variant/create/source_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "Eq, PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. variant_create_source_query
+pub(crate) struct VariantCreateSourceQuery;
+
+/// The schemas to publish to a new variant
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum VariantSource {
+    /// the name, routing url, and SDL of each subgraph of a federated variant
+    Subgraphs(Vec<(String, Option<String>, String)>),
+    /// the schema of a non-federated variant
+    Schema(String),
+}
+
+/// Creates a new variant by publishing the latest schemas of an existing variant to it.
+/// Variants can't be created empty in the registry, so this is how preview variants
+/// start out identical to the variant they are branched from.
+///
+/// Subgraphs can only be published one at a time, so if one of them fails after others were
+/// published, the half-created variant is deleted again
+pub fn run(
+    input: VariantCreateInput,
+    client: &StudioClient,
+) -> Result<VariantCreateResponse, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let source_variant = input.source_variant.clone();
+    let git_context = input.git_context.clone();
+    let response_data = client.post::<VariantCreateSourceQuery>(input.into())?;
+    let source = get_source_from_response_data(response_data, &graph_ref, &source_variant)?;
+
+    let subgraphs = match source {
+        VariantSource::Subgraphs(subgraphs) => {
+            client.plan_requests(subgraphs.len() as u64)?;
+            let mut subgraph_names = Vec::new();
+            for (name, url, schema) in subgraphs {
+                if let Err(source) = subgraph_publish::run(
+                    SubgraphPublishInput {
+                        graph_ref: graph_ref.clone(),
+                        subgraph: name.clone(),
+                        url,
+                        schema,
+                        git_context: git_context.clone(),
+                        convert_to_federated_graph: false,
+                        retries: None,
+                    },
+                    client,
+                ) {
+                    return Err(roll_back(&graph_ref, name, subgraph_names, source, client));
+                }
+                subgraph_names.push(name);
+            }
+            subgraph_names
+        }
+        VariantSource::Schema(proposed_schema) => {
+            graph_publish::run(
+                GraphPublishInput {
+                    graph_ref: graph_ref.clone(),
+                    proposed_schema,
+                    git_context,
+                    retries: None,
+                },
+                client,
+            )?;
+            Vec::new()
+        }
+    };
+
+    Ok(VariantCreateResponse {
+        graph_ref,
+        source_variant,
+        subgraphs,
+    })
+}
+
+/// Deletes the new variant after publishing `subgraph` to it failed, if any subgraphs were
+/// published before it, returning the error to report
+fn roll_back(
+    graph_ref: &GraphRef,
+    subgraph: String,
+    published_subgraphs: Vec<String>,
+    source: RoverClientError,
+    client: &StudioClient,
+) -> RoverClientError {
+    if published_subgraphs.is_empty() {
+        return source;
+    }
+    let rolled_back = graph_delete::run(
+        GraphDeleteInput {
+            graph_ref: graph_ref.clone(),
+            retries: None,
+        },
+        client,
+    )
+    .is_ok();
+    RoverClientError::VariantCreateIncomplete {
+        graph_ref: graph_ref.clone(),
+        subgraph,
+        published_subgraphs,
+        rolled_back,
+        source: Box::new(source),
+    }
+}
+
+fn get_source_from_response_data(
+    response_data: variant_create_source_query::ResponseData,
+    graph_ref: &GraphRef,
+    source_variant: &str,
+) -> Result<VariantSource, RoverClientError> {
+    let source_ref = GraphRef {
+        name: graph_ref.name.clone(),
+        variant: source_variant.to_string(),
+    };
+    let graph = response_data
+        .graph
+        .ok_or_else(|| RoverClientError::GraphNotFound {
+            graph_ref: source_ref.clone(),
+        })?;
+
+    let valid_variants: Vec<String> = graph
+        .variants
+        .into_iter()
+        .map(|variant| variant.name)
+        .collect();
+    if valid_variants.contains(&graph_ref.variant) {
+        return Err(RoverClientError::AdhocError {
+            msg: format!("The variant \"{}\" already exists.", graph_ref),
+        });
+    }
+
+    let variant = graph
+        .variant
+        .ok_or_else(|| RoverClientError::NoSchemaForVariant {
+            graph_ref: source_ref.clone(),
+            valid_variants,
+            frontend_url_root: response_data.frontend_url_root,
+        })?;
+
+    match variant.subgraphs {
+        Some(subgraphs) if !subgraphs.is_empty() => Ok(VariantSource::Subgraphs(
+            subgraphs
+                .into_iter()
+                .map(|subgraph| {
+                    (
+                        subgraph.name,
+                        subgraph.url,
+                        subgraph.active_partial_schema.sdl,
+                    )
+                })
+                .collect(),
+        )),
+        _ => variant
+            .latest_publication
+            .map(|publication| VariantSource::Schema(publication.schema.document))
+            .ok_or_else(|| RoverClientError::AdhocError {
+                msg: format!(
+                    "The variant \"{}\" has no published schema to copy.",
+                    source_ref
+                ),
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn get_source_from_response_data_works_for_subgraphs() {
+        let json_response = json!({
+            "frontendUrlRoot": "https://studio.apollographql.com/",
+            "graph": {
+                "variant": {
+                    "latestPublication": {
+                        "schema": { "document": "type Query { products: [Product] }" }
+                    },
+                    "subgraphs": [
+                        {
+                            "name": "products",
+                            "url": "http://localhost:4001",
+                            "activePartialSchema": { "sdl": "type Query { products: [Product] }" }
+                        }
+                    ]
+                },
+                "variants": [{ "name": "current" }]
+            }
+        });
+        let data: variant_create_source_query::ResponseData =
+            serde_json::from_value(json_response).unwrap();
+        let output = get_source_from_response_data(data, &mock_graph_ref(), "current");
+
+        assert_eq!(
+            output.unwrap(),
+            VariantSource::Subgraphs(vec![(
+                "products".to_string(),
+                Some("http://localhost:4001".to_string()),
+                "type Query { products: [Product] }".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn get_source_from_response_data_works_for_non_federated_graphs() {
+        let json_response = json!({
+            "frontendUrlRoot": "https://studio.apollographql.com/",
+            "graph": {
+                "variant": {
+                    "latestPublication": {
+                        "schema": { "document": "type Query { hello: String }" }
+                    },
+                    "subgraphs": null
+                },
+                "variants": [{ "name": "current" }]
+            }
+        });
+        let data: variant_create_source_query::ResponseData =
+            serde_json::from_value(json_response).unwrap();
+        let output = get_source_from_response_data(data, &mock_graph_ref(), "current");
+
+        assert_eq!(
+            output.unwrap(),
+            VariantSource::Schema("type Query { hello: String }".to_string())
+        );
+    }
+
+    #[test]
+    fn get_source_from_response_data_errs_on_existing_variant() {
+        let json_response = json!({
+            "frontendUrlRoot": "https://studio.apollographql.com/",
+            "graph": {
+                "variant": null,
+                "variants": [{ "name": "current" }, { "name": "pr-123" }]
+            }
+        });
+        let data: variant_create_source_query::ResponseData =
+            serde_json::from_value(json_response).unwrap();
+        let output = get_source_from_response_data(data, &mock_graph_ref(), "current");
+
+        assert!(matches!(output, Err(RoverClientError::AdhocError { .. })));
+    }
+
+    #[test]
+    fn get_source_from_response_data_errs_on_unknown_source() {
+        let json_response = json!({
+            "frontendUrlRoot": "https://studio.apollographql.com/",
+            "graph": {
+                "variant": null,
+                "variants": [{ "name": "current" }]
+            }
+        });
+        let data: variant_create_source_query::ResponseData =
+            serde_json::from_value(json_response).unwrap();
+        let output = get_source_from_response_data(data, &mock_graph_ref(), "staging");
+
+        assert!(matches!(
+            output,
+            Err(RoverClientError::NoSchemaForVariant { .. })
+        ));
+    }
+
+    fn mock_graph_ref() -> GraphRef {
+        GraphRef {
+            name: "mygraph".to_string(),
+            variant: "pr-123".to_string(),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::operations::variant::create::runner::variant_create_source_query;
+use crate::shared::{GitContext, GraphRef};
+
+use serde::Serialize;
+
+type QueryVariables = variant_create_source_query::Variables;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VariantCreateInput {
+    /// the new variant
+    pub graph_ref: GraphRef,
+    /// the name of the variant, in the same graph, whose schemas are copied to the new variant
+    pub source_variant: String,
+    pub git_context: GitContext,
+}
+
+impl From<VariantCreateInput> for QueryVariables {
+    fn from(input: VariantCreateInput) -> Self {
+        Self {
+            graph_id: input.graph_ref.name,
+            variant: input.source_variant,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct VariantCreateResponse {
+    pub graph_ref: GraphRef,
+    pub source_variant: String,
+    /// the names of the subgraphs copied to the new variant,
+    /// which is empty when the source variant is not federated
+    pub subgraphs: Vec<String>,
+}
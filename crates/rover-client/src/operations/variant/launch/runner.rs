@@ -0,0 +1,53 @@
+use graphql_client::*;
+
+use crate::blocking::StudioClient;
+use crate::operations::variant::launch::types::{LaunchInfo, VariantLaunchInput};
+use crate::RoverClientError;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/variant/launch/launch_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "Eq, PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. variant_launch_query
+pub(crate) struct VariantLaunchQuery;
+
+/// Fetches the most recent launch of a variant, which is `None` if it has never launched
+pub fn run(
+    input: VariantLaunchInput,
+    client: &StudioClient,
+) -> Result<Option<LaunchInfo>, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let data = client.post::<VariantLaunchQuery>(input.into())?;
+    let graph = data.graph.ok_or(RoverClientError::GraphNotFound {
+        graph_ref: graph_ref.clone(),
+    })?;
+    Ok(graph
+        .variant
+        .and_then(|variant| variant.latest_launch)
+        .map(|launch| LaunchInfo {
+            url: format!(
+                "{}/graph/{}/launches/{}?variant={}",
+                data.frontend_url_root, graph_ref.name, launch.id, graph_ref.variant
+            ),
+            status: get_status(launch.status),
+            id: launch.id,
+        }))
+}
+
+type LaunchStatus = variant_launch_query::LaunchStatus;
+
+fn get_status(status: LaunchStatus) -> String {
+    match status {
+        LaunchStatus::LAUNCH_COMPLETED => "LAUNCH_COMPLETED".to_string(),
+        LaunchStatus::LAUNCH_FAILED => "LAUNCH_FAILED".to_string(),
+        LaunchStatus::LAUNCH_INITIATED => "LAUNCH_INITIATED".to_string(),
+        LaunchStatus::Other(status) => status,
+    }
+}
@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+use crate::operations::variant::launch::runner::variant_launch_query;
+use crate::shared::GraphRef;
+
+type QueryVariables = variant_launch_query::Variables;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VariantLaunchInput {
+    pub graph_ref: GraphRef,
+}
+
+impl From<VariantLaunchInput> for QueryVariables {
+    fn from(input: VariantLaunchInput) -> Self {
+        Self {
+            graph_id: input.graph_ref.name,
+            variant: input.graph_ref.variant,
+        }
+    }
+}
+
+/// The most recent launch of a variant in Apollo Studio
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct LaunchInfo {
+    pub id: String,
+    /// `LAUNCH_INITIATED`, `LAUNCH_COMPLETED`, or `LAUNCH_FAILED`
+    pub status: String,
+    /// the Studio page for the launch
+    pub url: String,
+}
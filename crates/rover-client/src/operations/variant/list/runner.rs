@@ -0,0 +1,139 @@
+use crate::blocking::StudioClient;
+use crate::operations::variant::list::{VariantInfo, VariantListInput, VariantListResponse};
+use crate::shared::GraphRef;
+use crate::RoverClientError;
+
+use graphql_client::*;
+
+type Timestamp = String;
+
+#[derive(GraphQLQuery)]
+// The paths are relative to the directory where your `Cargo.toml` is located.
+// Both json and the GraphQL schema language are supported as sources for the schema
+#[graphql(
+    query_path = "src/operations/variant/list/list_query.graphql",
+    schema_path = ".schema/schema.graphql",
+    response_derives = "Eq, PartialEq, Debug, Serialize, Deserialize",
+    deprecated = "warn"
+)]
+/// This struct is used to generate the module containing `Variables` and
+/// `ResponseData` structs.
+/// Snake case of this name is the mod name. i.e. graph_variant_list_query
+pub(crate) struct GraphVariantListQuery;
+
+/// Lists every variant of a graph along with some metadata about each,
+/// with the most recently published variants first
+pub fn run(
+    input: VariantListInput,
+    client: &StudioClient,
+) -> Result<VariantListResponse, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let response_data = client.post::<GraphVariantListQuery>(input.into())?;
+    let graph_id = graph_ref.name.clone();
+    let variants = get_variants_from_response_data(response_data, graph_ref)?;
+    Ok(VariantListResponse { graph_id, variants })
+}
+
+fn get_variants_from_response_data(
+    response_data: graph_variant_list_query::ResponseData,
+    graph_ref: GraphRef,
+) -> Result<Vec<VariantInfo>, RoverClientError> {
+    let graph = response_data
+        .graph
+        .ok_or(RoverClientError::GraphNotFound { graph_ref })?;
+
+    let mut variants: Vec<VariantInfo> = graph
+        .variants
+        .into_iter()
+        .map(|variant| VariantInfo {
+            name: variant.name,
+            url: variant.url,
+            source_variant: variant.source_variant.map(|source| source.name),
+            last_published_at: variant
+                .latest_publication
+                .and_then(|publication| publication.published_at.parse().ok()),
+            subgraph_count: variant.subgraphs.map(|subgraphs| subgraphs.len()),
+        })
+        .collect();
+    variants.sort_by(|a, b| {
+        b.last_published_at
+            .cmp(&a.last_published_at)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(variants)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn get_variants_from_response_data_works() {
+        let json_response = json!({
+            "graph": {
+                "variants": [
+                    {
+                        "name": "current",
+                        "url": "https://api.example.com/graphql",
+                        "sourceVariant": null,
+                        "latestPublication": { "publishedAt": "2022-11-01T10:00:00Z" },
+                        "subgraphs": [{ "name": "products" }, { "name": "reviews" }]
+                    },
+                    {
+                        "name": "pr-123",
+                        "url": null,
+                        "sourceVariant": null,
+                        "latestPublication": { "publishedAt": "2022-11-02T10:00:00Z" },
+                        "subgraphs": [{ "name": "products" }]
+                    },
+                    {
+                        "name": "public",
+                        "url": null,
+                        "sourceVariant": { "name": "current" },
+                        "latestPublication": null,
+                        "subgraphs": null
+                    }
+                ]
+            }
+        });
+        let data: graph_variant_list_query::ResponseData =
+            serde_json::from_value(json_response).unwrap();
+        let output = get_variants_from_response_data(data, mock_graph_ref()).unwrap();
+
+        let names: Vec<&str> = output.iter().map(|variant| variant.name.as_str()).collect();
+        assert_eq!(names, vec!["pr-123", "current", "public"]);
+        assert_eq!(
+            output[1],
+            VariantInfo {
+                name: "current".to_string(),
+                url: Some("https://api.example.com/graphql".to_string()),
+                source_variant: None,
+                last_published_at: "2022-11-01T10:00:00Z".parse().ok(),
+                subgraph_count: Some(2),
+            }
+        );
+        assert_eq!(output[2].source_variant, Some("current".to_string()));
+        assert_eq!(output[2].subgraph_count, None);
+    }
+
+    #[test]
+    fn get_variants_from_response_data_errs_on_no_graph() {
+        let json_response = json!({ "graph": null });
+        let data: graph_variant_list_query::ResponseData =
+            serde_json::from_value(json_response).unwrap();
+        let output = get_variants_from_response_data(data, mock_graph_ref());
+
+        assert!(matches!(
+            output,
+            Err(RoverClientError::GraphNotFound { .. })
+        ));
+    }
+
+    fn mock_graph_ref() -> GraphRef {
+        GraphRef {
+            name: "mygraph".to_string(),
+            variant: "current".to_string(),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::operations::variant::list::runner::graph_variant_list_query;
+use crate::shared::GraphRef;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+type QueryVariables = graph_variant_list_query::Variables;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VariantListInput {
+    /// only the graph's name is used, since every variant is listed
+    pub graph_ref: GraphRef,
+}
+
+impl From<VariantListInput> for QueryVariables {
+    fn from(input: VariantListInput) -> Self {
+        Self {
+            graph_id: input.graph_ref.name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct VariantListResponse {
+    pub graph_id: String,
+    pub variants: Vec<VariantInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct VariantInfo {
+    pub name: String,
+    /// the URL of the variant's GraphQL endpoint, if one was set in Apollo Studio
+    pub url: Option<String>,
+    /// the variant a contract variant is derived from
+    pub source_variant: Option<String>,
+    /// when a schema was last published to the variant
+    pub last_published_at: Option<DateTime<Utc>>,
+    /// the number of subgraphs in the variant, or `None` for non-federated variants
+    pub subgraph_count: Option<usize>,
+}
@@ -0,0 +1,8 @@
+/// "variant create" command execution
+pub mod create;
+
+/// "variant list" command execution
+pub mod list;
+
+/// looking up a variant's latest launch
+pub mod launch;
@@ -103,6 +103,35 @@ impl CheckResponse {
         self.failure_count
     }
 
+    pub fn get_changes(&self) -> &[SchemaChange] {
+        &self.changes
+    }
+
+    /// Replaces the changes of a finished check, i.e. after applying a local check policy,
+    /// failing if any of the new changes fail
+    pub fn try_with_changes(
+        self,
+        changes: Vec<SchemaChange>,
+        graph_ref: GraphRef,
+    ) -> Result<CheckResponse, RoverClientError> {
+        let result = if changes
+            .iter()
+            .any(|change| change.severity == ChangeSeverity::FAIL)
+        {
+            ChangeSeverity::FAIL
+        } else {
+            ChangeSeverity::PASS
+        };
+        CheckResponse::try_new(
+            self.target_url,
+            self.operation_check_count,
+            changes,
+            result,
+            graph_ref,
+            self.core_schema_modified,
+        )
+    }
+
     pub fn get_json(&self) -> Value {
         json!(self)
     }
@@ -110,7 +139,7 @@ impl CheckResponse {
 
 /// ChangeSeverity indicates whether a proposed change
 /// in a GraphQL schema passed or failed the check
-#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub enum ChangeSeverity {
     /// The proposed schema has passed the checks
     PASS,
@@ -160,6 +189,42 @@ pub struct CheckConfig {
     pub query_count_threshold: Option<i64>,
     pub query_count_threshold_percentage: Option<f64>,
     pub validation_period: Option<ValidationPeriod>,
+    /// clients whose operations are left out of the check
+    pub excluded_clients: Vec<ClientFilter>,
+}
+
+/// A client to leave out of a check, matching every version of the client
+/// unless a version is given
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct ClientFilter {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+// Client filters are parsed as a client name with an optional version
+// such as "ios-app@3.2.0". Only an `@` after the start of the name
+// separates the version, so names like "@acme/web" still work
+impl FromStr for ClientFilter {
+    type Err = RoverClientError;
+    fn from_str(client: &str) -> Result<Self, Self::Err> {
+        let client = client.trim();
+        let (name, version) = match client.rfind('@') {
+            Some(index) if index > 0 => (&client[..index], Some(&client[index + 1..])),
+            _ => (client, None),
+        };
+        if name.is_empty() || version.map_or(false, str::is_empty) {
+            return Err(RoverClientError::AdhocError {
+                msg: format!(
+                    "\"{}\" is not a valid client. Clients are a name with an optional version, i.e. ios-app or ios-app@3.2.0",
+                    client
+                ),
+            });
+        }
+        Ok(ClientFilter {
+            name: name.to_string(),
+            version: version.map(|version| version.to_string()),
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
@@ -202,3 +267,39 @@ impl Display for Period {
         write!(f, "{}", period)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_client_filters() {
+        assert_eq!(
+            "ios-app".parse::<ClientFilter>().unwrap(),
+            ClientFilter {
+                name: "ios-app".to_string(),
+                version: None,
+            }
+        );
+        assert_eq!(
+            "@acme/web@1.0.0-beta.2".parse::<ClientFilter>().unwrap(),
+            ClientFilter {
+                name: "@acme/web".to_string(),
+                version: Some("1.0.0-beta.2".to_string()),
+            }
+        );
+        assert_eq!(
+            "@acme/web".parse::<ClientFilter>().unwrap(),
+            ClientFilter {
+                name: "@acme/web".to_string(),
+                version: None,
+            }
+        );
+    }
+
+    #[test]
+    fn it_errs_on_invalid_client_filters() {
+        assert!("".parse::<ClientFilter>().is_err());
+        assert!("ios-app@".parse::<ClientFilter>().is_err());
+    }
+}
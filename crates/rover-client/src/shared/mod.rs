@@ -6,7 +6,7 @@ mod graph_ref;
 
 pub use async_check_response::CheckRequestSuccessResult;
 pub use check_response::{
-    ChangeSeverity, CheckConfig, CheckResponse, SchemaChange, ValidationPeriod,
+    ChangeSeverity, CheckConfig, CheckResponse, ClientFilter, SchemaChange, ValidationPeriod,
 };
 pub use fetch_response::{FetchResponse, Sdl, SdlType};
 pub use git_context::GitContext;
@@ -145,6 +145,16 @@ impl Fs {
         }
     }
 
+    /// removes a file
+    pub fn remove_file<F>(file: F) -> Result<()>
+    where
+        F: AsRef<Utf8Path>,
+    {
+        let file = file.as_ref();
+        fs::remove_file(file).with_context(|| format!("could not remove {}", file))?;
+        Ok(())
+    }
+
     /// checks if a path is a directory, errors if the path does not exist
     pub fn path_is_dir<D>(dir: D) -> Result<bool>
     where
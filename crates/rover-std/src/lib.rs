@@ -3,6 +3,7 @@ mod fs;
 mod style;
 
 pub mod prompt;
+pub mod tmp;
 pub use emoji::Emoji;
 pub use fs::Fs;
 pub use style::Style;
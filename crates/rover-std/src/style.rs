@@ -11,6 +11,9 @@ pub enum Style {
     CallToAction,
     WhoAmIKey,
     Version,
+    DiffAddition,     // "+" lines of a diff
+    DiffRemoval,      // "-" lines of a diff
+    LogSource(usize), // "[source]" prefixes of interleaved logs, colored by index
 }
 
 impl Style {
@@ -30,6 +33,15 @@ impl Style {
             Style::WarningPrefix => style(message_ref).red(),
             Style::ErrorPrefix => style(message_ref).red().bold(),
             Style::Version => style(message_ref).cyan(),
+            Style::DiffAddition => style(message_ref).green(),
+            Style::DiffRemoval => style(message_ref).red(),
+            Style::LogSource(index) => match index % 5 {
+                0 => style(message_ref).cyan(),
+                1 => style(message_ref).green(),
+                2 => style(message_ref).magenta(),
+                3 => style(message_ref).blue(),
+                _ => style(message_ref).yellow(),
+            },
             Style::Path | Style::Heading => style(message_ref).bold(),
         }
         .to_string()
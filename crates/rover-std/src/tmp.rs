@@ -0,0 +1,327 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::process::{self, Child, Command, Output, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use anyhow::{anyhow, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use lazy_static::lazy_static;
+
+/// The environment variable that sets the directory temp directories are created in
+pub const TMP_DIR_ENV_VAR: &str = "APOLLO_TMP_DIR";
+
+lazy_static! {
+    /// every secure temp directory that has not been cleaned up yet,
+    /// so they can be removed if Rover exits before they are dropped
+    static ref LIVE_TEMP_DIRS: Mutex<HashSet<Utf8PathBuf>> = Mutex::new(HashSet::new());
+
+    /// every child process started with [`output`] that has not exited yet,
+    /// so they can be stopped if Rover exits before they finish
+    static ref LIVE_CHILD_PROCESSES: Mutex<HashMap<u32, Arc<Mutex<Child>>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// A temporary directory that only the current user can access.
+///
+/// The directory is created inside [`TMP_DIR_ENV_VAR`] if it is set, and the
+/// operating system's default temp directory otherwise. Files created with
+/// [`SecureTempDir::write_file`] are only readable and writable by the current
+/// user. The directory and everything in it is removed when this struct is
+/// dropped, or when [`cleanup`] is called before Rover exits.
+#[derive(Debug)]
+pub struct SecureTempDir {
+    path: Utf8PathBuf,
+}
+
+impl SecureTempDir {
+    /// Creates a new temp directory whose name starts with `prefix`
+    pub fn new(prefix: &str) -> Result<Self> {
+        Self::new_in(prefix, &temp_root()?)
+    }
+
+    /// Creates a new temp directory whose name starts with `prefix` in `root`
+    fn new_in(prefix: &str, root: &Utf8Path) -> Result<Self> {
+        let dir = tempfile::Builder::new()
+            .prefix(prefix)
+            .tempdir_in(root)
+            .with_context(|| format!("could not create a temp directory in '{}'", root))?;
+        let path = Utf8PathBuf::try_from(dir.into_path())?;
+        restrict_permissions(&path, 0o700)?;
+        tracing::debug!("secure temp dir created at {}", &path);
+
+        LIVE_TEMP_DIRS
+            .lock()
+            .expect("could not acquire lock on temp directories")
+            .insert(path.clone());
+
+        Ok(Self { path })
+    }
+
+    /// The path to this temp directory
+    pub fn path(&self) -> &Utf8Path {
+        &self.path
+    }
+
+    /// Creates a file named `file_name` in this directory that only the current
+    /// user can read or write, returning its path
+    pub fn create_file(&self, file_name: &str) -> Result<(File, Utf8PathBuf)> {
+        let path = self.path.join(file_name);
+        let file = create_private_file(&path)?;
+        Ok((file, path))
+    }
+
+    /// Writes `contents` to a file named `file_name` in this directory that only
+    /// the current user can read or write, returning its path
+    pub fn write_file(&self, file_name: &str, contents: impl AsRef<[u8]>) -> Result<Utf8PathBuf> {
+        let path = self.path.join(file_name);
+        write_private_file(&path, contents)?;
+        tracing::debug!("temp file written to {}", &path);
+        Ok(path)
+    }
+}
+
+impl Drop for SecureTempDir {
+    fn drop(&mut self) {
+        remove_temp_dir(&self.path);
+        LIVE_TEMP_DIRS
+            .lock()
+            .expect("could not acquire lock on temp directories")
+            .remove(&self.path);
+    }
+}
+
+/// Writes `contents` to the file at `path` so that only the current user can read or write it,
+/// tightening the permissions of a file that's already there
+pub fn write_private_file(path: &Utf8Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    let mut file = create_private_file(path)?;
+    file.write_all(contents.as_ref())
+        .with_context(|| format!("could not write file '{}'", path))?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Creates or truncates the file at `path` with permissions for the current user only
+fn create_private_file(path: &Utf8Path) -> Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let file = options
+        .open(path)
+        .with_context(|| format!("could not create file '{}'", path))?;
+    // `mode` only applies to new files
+    restrict_permissions(path, 0o600)?;
+    Ok(file)
+}
+
+/// Stops every child process started with [`output`] that is still running, and
+/// removes every secure temp directory that is still around.
+///
+/// `std::process::exit` does not run destructors, so this should be called
+/// before exiting the process, including from ctrl-c handlers.
+pub fn cleanup() {
+    stop_child_processes();
+    cleanup_where(|_| true)
+}
+
+/// Calls [`cleanup`] and exits when the command is cancelled with ctrl-c.
+///
+/// Commands that create a [`SecureTempDir`] or run a child process with [`output`] call this
+/// before doing so. If a ctrl-c handler is set already, i.e. the one `rover dev` sets up,
+/// that handler is left in place, and is expected to call [`cleanup`] itself
+pub fn cleanup_on_ctrl_c() -> Result<()> {
+    match ctrlc::set_handler(|| {
+        cleanup();
+        process::exit(130);
+    }) {
+        Ok(()) | Err(ctrlc::Error::MultipleHandlers) => Ok(()),
+        Err(e) => Err(anyhow!(e).context("could not set ctrl-c handler")),
+    }
+}
+
+/// Runs `command` like [`Command::output`], stopping it if [`cleanup`] is called before it exits
+pub fn output(command: &mut Command) -> std::io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout_pipe = child.stdout.take();
+    let stderr_pipe = child.stderr.take();
+    let id = child.id();
+    let child = Arc::new(Mutex::new(child));
+    LIVE_CHILD_PROCESSES
+        .lock()
+        .expect("could not acquire lock on child processes")
+        .insert(id, child.clone());
+
+    // stderr is read on a thread of its own, so a child that fills one pipe
+    // while Rover is reading the other doesn't block forever
+    let stderr_thread = stderr_pipe.map(|mut pipe| {
+        thread::spawn(move || {
+            let mut stderr = Vec::new();
+            let _ = pipe.read_to_end(&mut stderr);
+            stderr
+        })
+    });
+    let mut stdout = Vec::new();
+    let read_result = match stdout_pipe {
+        Some(mut pipe) => pipe.read_to_end(&mut stdout).map(|_| ()),
+        None => Ok(()),
+    };
+    let stderr = stderr_thread
+        .and_then(|thread| thread.join().ok())
+        .unwrap_or_default();
+    let status = child
+        .lock()
+        .expect("could not acquire lock on child process")
+        .wait();
+    LIVE_CHILD_PROCESSES
+        .lock()
+        .expect("could not acquire lock on child processes")
+        .remove(&id);
+
+    read_result?;
+    Ok(Output {
+        status: status?,
+        stdout,
+        stderr,
+    })
+}
+
+fn stop_child_processes() {
+    if let Ok(mut children) = LIVE_CHILD_PROCESSES.lock() {
+        for (id, child) in children.drain() {
+            if let Ok(mut child) = child.lock() {
+                if let Err(e) = child.kill() {
+                    tracing::debug!("could not stop child process {}: {}", id, e);
+                }
+            }
+        }
+    }
+}
+
+/// Removes the secure temp directories that are still around and match `should_remove`
+fn cleanup_where(should_remove: impl Fn(&Utf8Path) -> bool) {
+    if let Ok(mut dirs) = LIVE_TEMP_DIRS.lock() {
+        dirs.retain(|path| {
+            if should_remove(path) {
+                remove_temp_dir(path);
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+fn remove_temp_dir(path: &Utf8Path) {
+    if let Err(e) = std::fs::remove_dir_all(path) {
+        tracing::debug!("could not remove temp dir {}: {}", path, e);
+    }
+}
+
+/// The directory to create temp directories in, creating it if it doesn't exist
+fn temp_root() -> Result<Utf8PathBuf> {
+    let root = match std::env::var(TMP_DIR_ENV_VAR) {
+        Ok(dir) if !dir.is_empty() => Utf8PathBuf::from(dir),
+        _ => Utf8PathBuf::try_from(std::env::temp_dir())?,
+    };
+    std::fs::create_dir_all(&root)
+        .with_context(|| format!("could not create temp directory root '{}'", &root))?;
+    Ok(root)
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Utf8Path, mode: u32) -> Result<()> {
+    use std::fs::Permissions;
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::set_permissions(path, Permissions::from_mode(mode))
+        .with_context(|| format!("could not set permissions on '{}'", path))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Utf8Path, _mode: u32) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn it_removes_the_dir_on_drop() {
+        let dir = SecureTempDir::new("rover-test").unwrap();
+        let path = dir.path().to_path_buf();
+        dir.write_file("schema.graphql", "type Query { hello: String }")
+            .unwrap();
+        assert!(path.exists());
+
+        drop(dir);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    #[serial]
+    fn cleanup_removes_live_dirs() {
+        // a root of its own, so the cleanup doesn't remove the temp dirs of other tests
+        let tmp_root = assert_fs::TempDir::new().unwrap();
+        let root = Utf8PathBuf::try_from(tmp_root.path().to_path_buf()).unwrap();
+        let dir = SecureTempDir::new_in("rover-test", &root).unwrap();
+        let path = dir.path().to_path_buf();
+        std::mem::forget(dir);
+        assert!(path.exists());
+
+        cleanup_where(|path| path.starts_with(&root));
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial]
+    fn cleanup_stops_child_processes() {
+        let started = std::time::Instant::now();
+        let sleep = thread::spawn(|| output(Command::new("sleep").arg("30")).unwrap());
+        while LIVE_CHILD_PROCESSES.lock().unwrap().is_empty() {
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        stop_child_processes();
+        assert!(!sleep.join().unwrap().status.success());
+        assert!(started.elapsed() < std::time::Duration::from_secs(30));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial]
+    fn output_captures_stdout_and_stderr() {
+        let output = output(Command::new("sh").args(["-c", "echo out; echo err >&2"])).unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"out\n");
+        assert_eq!(output.stderr, b"err\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial]
+    fn it_restricts_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = SecureTempDir::new("rover-test").unwrap();
+        let file = dir.write_file("key", "secret").unwrap();
+
+        let dir_mode = std::fs::metadata(dir.path()).unwrap().permissions().mode();
+        let file_mode = std::fs::metadata(file).unwrap().permissions().mode();
+        assert_eq!(dir_mode & 0o777, 0o700);
+        assert_eq!(file_mode & 0o777, 0o600);
+    }
+}
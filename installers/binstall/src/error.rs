@@ -36,6 +36,22 @@ pub enum InstallerError {
     #[error("This binary has already been placed in the installation destination.")]
     AlreadyInstalled,
 
+    /// A downloaded plugin doesn't match the checksum published for it
+    #[error("The SHA-256 checksum of {file} is {actual}, but {expected} is published for it")]
+    ChecksumMismatch {
+        file: String,
+        expected: String,
+        actual: String,
+    },
+
+    /// A downloaded plugin has no published checksum, and checksums are required
+    #[error("No SHA-256 checksum of {file} is published at {checksums_url}")]
+    ChecksumMissing { file: String, checksums_url: String },
+
+    /// The published checksums aren't signed by the configured public key
+    #[error("The published checksums are not signed by the configured plugin public key")]
+    SignatureInvalid,
+
     #[error(transparent)]
     AdhocError(#[from] anyhow::Error),
 }
@@ -1,12 +1,22 @@
+use crate::verify::{self, PluginVerification, CHECKSUMS_FILE_NAME, SIGNATURE_FILE_NAME};
 use crate::InstallerError;
 
-use rover_std::Fs;
+use rover_std::{tmp::SecureTempDir, Fs};
 use std::env;
-use std::io::{self, Write};
+use std::io;
 
 use atty::{self, Stream};
 use camino::Utf8PathBuf;
 
+/// The release a plugin tarball is downloaded from
+struct PluginRelease {
+    /// The URL the tarball redirects to, which its checksums are published next to
+    url: reqwest::Url,
+
+    /// The name of the tarball in the release's checksums
+    tarball_file_name: String,
+}
+
 pub struct Installer {
     pub binary_name: String,
     pub force_install: bool,
@@ -39,15 +49,18 @@ impl Installer {
     /// The main tool should already be installed before calling this function
     ///
     /// Checks if a binary already exists, and if it does not,
-    /// downloads a plugin tarball from a URL, extracts the binary,
+    /// downloads a plugin tarball from a URL,
+    /// verifies it against its published checksum, extracts the binary,
     /// and puts it in the `bin` directory for the main tool
     pub fn install_plugin(
         &self,
         plugin_name: &str,
         plugin_tarball_url: &str,
+        target_arch: &str,
         client: &reqwest::blocking::Client,
+        verification: &PluginVerification,
     ) -> Result<Option<Utf8PathBuf>, InstallerError> {
-        let version = self.get_plugin_version(plugin_tarball_url)?;
+        let (version, release_url) = self.get_plugin_release(plugin_tarball_url)?;
 
         let bin_dir_path = self.get_bin_dir_path()?;
         if !bin_dir_path.exists() {
@@ -62,8 +75,18 @@ impl Installer {
             return Ok(None);
         }
 
-        let plugin_bin_path =
-            self.extract_plugin_tarball(plugin_name, plugin_tarball_url, client)?;
+        let release = PluginRelease {
+            tarball_file_name: verify::get_tarball_file_name(plugin_name, &version, target_arch),
+            url: release_url,
+        };
+        // the download directory is removed once the plugin is copied out of it
+        let (_download_dir, plugin_bin_path) = self.extract_plugin_tarball(
+            plugin_name,
+            plugin_tarball_url,
+            &release,
+            client,
+            verification,
+        )?;
         self.write_plugin_bin_to_fs(plugin_name, &plugin_bin_path, &version)?;
 
         eprintln!(
@@ -75,6 +98,15 @@ impl Installer {
     }
 
     pub fn get_plugin_version(&self, plugin_tarball_url: &str) -> Result<String, InstallerError> {
+        Ok(self.get_plugin_release(plugin_tarball_url)?.0)
+    }
+
+    /// Gets the version of a plugin tarball, and the URL of the release it's downloaded from,
+    /// which is where the tarball URL redirects to, without following the redirect
+    fn get_plugin_release(
+        &self,
+        plugin_tarball_url: &str,
+    ) -> Result<(String, reqwest::Url), InstallerError> {
         let no_redirect_client = reqwest::blocking::Client::builder()
             .redirect(reqwest::redirect::Policy::none())
             .build()?;
@@ -98,7 +130,18 @@ impl Installer {
             )))
         }?;
 
-        Ok(version)
+        // mirrors that serve the tarball themselves don't redirect, and publish their
+        // checksums next to the tarball URL instead
+        let tarball_url = response.url().clone();
+        let release_url = match response.headers().get(reqwest::header::LOCATION) {
+            Some(location) => {
+                let location = location.to_str().map_err(anyhow::Error::from)?;
+                tarball_url.join(location).map_err(anyhow::Error::from)?
+            }
+            None => tarball_url,
+        };
+
+        Ok((version, release_url))
     }
 
     /// Gets the location the executable will be installed to
@@ -114,7 +157,8 @@ impl Installer {
         Ok(bin_dir)
     }
 
-    pub(crate) fn get_base_dir_path(&self) -> Result<Utf8PathBuf, InstallerError> {
+    /// Gets the directory the binary keeps its files in, i.e. `~/.rover`
+    pub fn get_base_dir_path(&self) -> Result<Utf8PathBuf, InstallerError> {
         let base_dir = if let Some(base_dir) = &self.override_install_path {
             Ok(base_dir.to_owned())
         } else {
@@ -165,16 +209,6 @@ impl Installer {
     ) -> Result<(), InstallerError> {
         let plugin_destination = self.get_plugin_bin_path(plugin_name, plugin_version)?;
         Fs::copy(plugin_bin_path, &plugin_destination)?;
-        // clean up temp dir
-        if let Some(dist) = plugin_bin_path.parent() {
-            if let Some(tempdir) = dist.parent() {
-                // attempt to clean up the temp dir
-                // but do not error if it doesn't exist or something goes wrong
-                if let Err(e) = Fs::remove_dir_all(tempdir) {
-                    eprintln!("WARN: {:?}", e);
-                }
-            }
-        }
         Ok(())
     }
 
@@ -218,32 +252,121 @@ impl Installer {
         &self,
         plugin_name: &str,
         plugin_tarball_url: &str,
+        release: &PluginRelease,
         client: &reqwest::blocking::Client,
-    ) -> Result<Utf8PathBuf, InstallerError> {
-        let download_dir = tempdir::TempDir::new(plugin_name)?;
-        let download_dir_path = Utf8PathBuf::try_from(download_dir.into_path())?;
-        let tarball_path = download_dir_path.join(format!("{}.tar.gz", plugin_name));
-        let mut f = std::fs::File::create(&tarball_path)?;
-        let response_bytes = client
+        verification: &PluginVerification,
+    ) -> Result<(SecureTempDir, Utf8PathBuf), InstallerError> {
+        let download_dir = SecureTempDir::new(plugin_name)?;
+        let response = client
             .get(plugin_tarball_url)
             .header(reqwest::header::USER_AGENT, "rover-client")
             .header(reqwest::header::ACCEPT, "application/octet-stream")
             .send()?
-            .error_for_status()?
-            .bytes()?;
-        f.write_all(&response_bytes[..])?;
-        f.sync_all()?;
+            .error_for_status()?;
+        let response_bytes = response.bytes()?;
+        self.verify_plugin_tarball(release, &response_bytes, client, verification)?;
+        let tarball_path =
+            download_dir.write_file(&format!("{}.tar.gz", plugin_name), &response_bytes[..])?;
         let f = std::fs::File::open(&tarball_path)?;
         let tar = flate2::read::GzDecoder::new(f);
         let mut archive = tar::Archive::new(tar);
-        archive.unpack(&download_dir_path)?;
-        let path = download_dir_path.join("dist").join(format!(
+        archive.unpack(download_dir.path())?;
+        let path = download_dir.path().join("dist").join(format!(
             "{}{}",
             plugin_name,
             std::env::consts::EXE_SUFFIX
         ));
         Fs::assert_path_exists(&path)?;
-        Ok(path)
+        Ok((download_dir, path))
+    }
+
+    /// Compares a downloaded tarball to the checksum published with its release, after
+    /// checking the signature of the published checksums if a public key is configured
+    fn verify_plugin_tarball(
+        &self,
+        release: &PluginRelease,
+        tarball: &[u8],
+        client: &reqwest::blocking::Client,
+        verification: &PluginVerification,
+    ) -> Result<(), InstallerError> {
+        let file = release.tarball_file_name.clone();
+        let checksums_url = release
+            .url
+            .join(CHECKSUMS_FILE_NAME)
+            .map_err(anyhow::Error::from)?;
+        let checksums = Self::get_release_file(&checksums_url, client, verification)?;
+        let checksum = checksums
+            .as_ref()
+            .and_then(|checksums| verify::find_checksum(checksums, &file));
+        let expected = match checksum {
+            Some(expected) => expected,
+            None if verification.is_strict() => {
+                return Err(InstallerError::ChecksumMissing {
+                    file,
+                    checksums_url: checksums_url.to_string(),
+                })
+            }
+            None => {
+                tracing::debug!(
+                    "installing {} unverified, since no checksum of it is published at {}",
+                    &file,
+                    &checksums_url
+                );
+                return Ok(());
+            }
+        };
+
+        if let (Some(public_key), Some(checksums)) = (&verification.public_key, &checksums) {
+            let signature_url = release
+                .url
+                .join(SIGNATURE_FILE_NAME)
+                .map_err(anyhow::Error::from)?;
+            let signature = Self::get_release_file(&signature_url, client, verification)?
+                .ok_or(InstallerError::SignatureInvalid)?;
+            verify::verify_signature(checksums.as_bytes(), &signature, public_key)?;
+            tracing::debug!(
+                "verified the signature of {} with the configured plugin public key",
+                &checksums_url
+            );
+        }
+
+        let actual = verify::get_checksum(tarball);
+        if actual != expected {
+            return Err(InstallerError::ChecksumMismatch {
+                file,
+                expected,
+                actual,
+            });
+        }
+        tracing::debug!(
+            "verified the SHA-256 checksum of {} ({}) against {}",
+            &file,
+            &actual,
+            &checksums_url
+        );
+        Ok(())
+    }
+
+    /// A text file published with a release, or `None` if there isn't one at `url`.
+    /// Unless verification is strict, any unsuccessful response means there isn't one,
+    /// since not every host that serves plugins answers a missing file with a 404
+    fn get_release_file(
+        url: &reqwest::Url,
+        client: &reqwest::blocking::Client,
+        verification: &PluginVerification,
+    ) -> Result<Option<String>, InstallerError> {
+        let response = client
+            .get(url.clone())
+            .header(reqwest::header::USER_AGENT, "rover-client")
+            .send()?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND
+            || (!status.is_success() && !verification.is_strict())
+        {
+            tracing::debug!("{} responded with {}", url, status);
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.text()?))
     }
 
     #[cfg(windows)]
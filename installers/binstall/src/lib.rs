@@ -26,9 +26,11 @@ use std::convert::TryFrom;
 mod error;
 mod install;
 mod system;
+mod verify;
 
 pub use error::InstallerError;
 pub use install::Installer;
+pub use verify::PluginVerification;
 
 #[cfg(not(windows))]
 pub(crate) use system::unix;
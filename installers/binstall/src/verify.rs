@@ -0,0 +1,130 @@
+use base64::Engine;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use sha2::{Digest, Sha256};
+
+use crate::InstallerError;
+
+/// The file of SHA-256 checksums published next to each plugin tarball
+pub(crate) const CHECKSUMS_FILE_NAME: &str = "sha256sums.txt";
+
+/// The signature of the checksums file, published next to it by mirrors that sign their plugins
+pub(crate) const SIGNATURE_FILE_NAME: &str = "sha256sums.txt.sig";
+
+/// How a downloaded plugin tarball is verified before it's extracted.
+///
+/// The tarball's checksum is always compared to the published one when checksums are
+/// published. A tarball without a published checksum is installed unverified
+/// unless `require_checksum` is set or a `public_key` is configured.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginVerification {
+    /// Fail instead of installing a tarball that has no published checksum
+    pub require_checksum: bool,
+
+    /// A base64-encoded ed25519 public key that the published checksums file must
+    /// be signed with
+    pub public_key: Option<String>,
+}
+
+impl PluginVerification {
+    /// Whether a tarball without a verifiable checksum fails to install
+    pub(crate) fn is_strict(&self) -> bool {
+        self.require_checksum || self.public_key.is_some()
+    }
+}
+
+/// The name a plugin's tarball is published with, i.e. `supergraph-v2.3.1-x86_64-apple-darwin.tar.gz`
+pub(crate) fn get_tarball_file_name(plugin_name: &str, version: &str, target_arch: &str) -> String {
+    format!("{}-{}-{}.tar.gz", plugin_name, version, target_arch)
+}
+
+/// The checksum of `file_name` in a checksums file, where each line is a hex-encoded
+/// checksum followed by the path of the file it's for
+pub(crate) fn find_checksum(checksums: &str, file_name: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let checksum = parts.next()?;
+        // `sha256sum` marks files read in binary mode with a leading `*`
+        let path = parts.next()?.trim_start_matches('*');
+        let path_file_name = path.rsplit('/').next().unwrap_or(path);
+        (path_file_name == file_name).then(|| checksum.to_lowercase())
+    })
+}
+
+/// The hex-encoded SHA-256 checksum of a downloaded file
+pub(crate) fn get_checksum(contents: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(contents))
+}
+
+/// Checks that `message` was signed by the private key of `public_key`, where both the
+/// signature and the key are base64-encoded
+pub(crate) fn verify_signature(
+    message: &[u8],
+    signature: &str,
+    public_key: &str,
+) -> Result<(), InstallerError> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let public_key = engine.decode(public_key.trim()).map_err(|e| {
+        InstallerError::AdhocError(anyhow::anyhow!(
+            "The plugin public key is not valid base64: {}",
+            e
+        ))
+    })?;
+    let signature = engine
+        .decode(signature.trim())
+        .map_err(|_| InstallerError::SignatureInvalid)?;
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(message, &signature)
+        .map_err(|_| InstallerError::SignatureInvalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    use super::*;
+
+    #[test]
+    fn it_finds_the_checksum_of_a_tarball() {
+        let checksums = "\
+            2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae  dist/supergraph-v2.3.1-x86_64-apple-darwin.tar.gz\n\
+            FCDE2B2EDBA56BF408601FB721FE9B5C338D10EE429EA04FAE5511B68FBF8FB9 *supergraph-v2.3.1-x86_64-unknown-linux-gnu.tar.gz\n";
+        assert_eq!(
+            find_checksum(
+                checksums,
+                &get_tarball_file_name("supergraph", "v2.3.1", "x86_64-apple-darwin")
+            )
+            .unwrap(),
+            "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae"
+        );
+        assert_eq!(
+            find_checksum(
+                checksums,
+                "supergraph-v2.3.1-x86_64-unknown-linux-gnu.tar.gz"
+            )
+            .unwrap(),
+            get_checksum(b"bar")
+        );
+        assert_eq!(
+            find_checksum(checksums, "supergraph-v2.3.1-x86_64-pc-windows-msvc.tar.gz"),
+            None
+        );
+    }
+
+    #[test]
+    fn it_verifies_the_signature_of_the_checksums() {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let public_key = engine.encode(key_pair.public_key().as_ref());
+        let checksums = b"fcde2b2edba56bf408601fb721fe9b5c338d10ee429ea04fae5511b68fbf8fb9  router-v1.10.0-x86_64-unknown-linux-gnu.tar.gz\n";
+        let signature = engine.encode(key_pair.sign(checksums).as_ref());
+
+        assert!(verify_signature(checksums, &signature, &public_key).is_ok());
+        assert!(matches!(
+            verify_signature(b"tampered", &signature, &public_key),
+            Err(InstallerError::SignatureInvalid)
+        ));
+        assert!(verify_signature(checksums, "not base64!", &public_key).is_err());
+    }
+}
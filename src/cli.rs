@@ -1,3 +1,4 @@
+use anyhow::anyhow;
 use camino::Utf8PathBuf;
 use clap::{Parser, ValueEnum};
 use lazycell::{AtomicLazyCell, LazyCell};
@@ -8,19 +9,25 @@ use crate::command::{self, RoverOutput};
 use crate::options::OutputOpts;
 use crate::utils::{
     client::{ClientBuilder, ClientTimeout, StudioClientConfig},
+    duration_budget::DurationBudget,
     env::{RoverEnv, RoverEnvKey},
     stringify::option_from_display,
     version,
 };
-use crate::RoverResult;
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
 
 use config::Config;
 use houston as config;
+use rover_client::blocking::RequestBudget;
 use rover_client::shared::GitContext;
+use rover_std::{tmp, Style};
 use sputnik::Session;
 use timber::Level;
 
-use std::{io, process, thread};
+use std::{
+    io, process, thread,
+    time::{Duration, Instant},
+};
 
 #[derive(Debug, Serialize, Parser)]
 #[command(
@@ -95,6 +102,14 @@ pub struct Rover {
     #[arg(long = "skip-update-check", global = true)]
     skip_update_check: bool,
 
+    /// Fail instead of making more than this many requests to Apollo Studio.
+    ///
+    /// Commands that make a request for each subgraph or variant check that all of them fit
+    /// before sending any. When set, the number of requests that were made is reported once
+    /// the command finishes.
+    #[arg(long = "max-requests", global = true)]
+    max_requests: Option<u64>,
+
     #[arg(skip)]
     #[serde(skip_serializing)]
     env_store: LazyCell<RoverEnv>,
@@ -106,11 +121,15 @@ pub struct Rover {
     #[arg(skip)]
     #[serde(skip_serializing)]
     client: AtomicLazyCell<Client>,
+
+    #[arg(skip)]
+    #[serde(skip_serializing)]
+    request_budget: LazyCell<RequestBudget>,
 }
 
 impl Rover {
     pub fn run_from_args() -> RoverResult<()> {
-        Rover::parse().run()
+        Rover::parse_from(command::run::with_script_mode(std::env::args_os())).run()
     }
 
     pub fn run(&self) -> RoverResult<()> {
@@ -150,6 +169,9 @@ impl Rover {
             Err(_) => self.execute_command(),
         };
 
+        self.report_request_count();
+        tmp::cleanup();
+
         match rover_output {
             Ok(output) => {
                 self.output_opts.handle_output(output)?;
@@ -169,25 +191,31 @@ impl Rover {
         // this only happens once a day automatically
         // we skip this check for the `rover update` commands, since they
         // do their own checks.
-        // the check is also skipped if the `--skip-update-check` flag is passed.
+        // the check is also skipped if the `--skip-update-check` flag is passed,
+        // or if the command was asked not to use the network.
         if let Command::Update(_) = &self.command { /* skip check */
-        } else if !self.skip_update_check {
+        } else if !self.skip_update_check && !self.is_offline() {
             let config = self.get_rover_config();
             if let Ok(config) = config {
                 let _ = version::check_for_update(config, false, self.get_reqwest_client()?);
             }
         }
 
-        match &self.command {
+        // an invalid threshold fails before the command runs, rather than after it's had its effects
+        let duration_budget = self.get_duration_budget()?;
+        let started = Instant::now();
+        let output = match &self.command {
             Command::Config(command) => command.run(self.get_client_config()?),
             Command::Contract(command) => command.run(self.get_client_config()?),
             Command::Dev(command) => {
                 command.run(self.get_install_override_path()?, self.get_client_config()?)
             }
             Command::Fed2(command) => command.run(self.get_client_config()?),
-            Command::Supergraph(command) => {
-                command.run(self.get_install_override_path()?, self.get_client_config()?)
-            }
+            Command::Supergraph(command) => command.run(
+                self.get_install_override_path()?,
+                self.get_client_config()?,
+                &self.output_opts,
+            ),
             Command::Docs(command) => command.run(),
             Command::Graph(command) => command.run(
                 self.get_client_config()?,
@@ -197,6 +225,8 @@ impl Rover {
             ),
             Command::Template(command) => command.run(self.get_client_config()?),
             Command::Readme(command) => command.run(self.get_client_config()?),
+            Command::Schema(command) => command.run(self.get_client_config()?),
+            Command::Run(command) => command.run(&self.output_opts),
             Command::Subgraph(command) => command.run(
                 self.get_client_config()?,
                 self.get_git_context()?,
@@ -206,12 +236,25 @@ impl Rover {
             Command::Update(command) => {
                 command.run(self.get_rover_config()?, self.get_reqwest_client()?)
             }
+            Command::Variant(command) => {
+                command.run(self.get_client_config()?, self.get_git_context()?)
+            }
             Command::Install(command) => {
                 command.do_install(self.get_install_override_path()?, self.get_client_config()?)
             }
             Command::Info(command) => command.run(),
             Command::Explain(command) => command.run(),
+            Command::Meta(command) => command.run(),
+            Command::Ops(command) => command.run(),
+        };
+
+        // let the user know if the command took much longer than expected,
+        // and what they might be able to do about it
+        if let Some(hint) = duration_budget.and_then(|budget| budget.get_hint(started.elapsed())) {
+            eprintln!("{} {}", Style::HintPrefix.paint("HINT:"), hint);
         }
+
+        output
     }
 
     pub(crate) fn get_rover_config(&self) -> RoverResult<Config> {
@@ -236,7 +279,39 @@ impl Rover {
             config,
             is_sudo,
             self.get_reqwest_client_builder()?,
-        ))
+        )
+        .with_request_budget(self.get_request_budget()))
+    }
+
+    pub(crate) fn get_request_budget(&self) -> RequestBudget {
+        if let Some(request_budget) = self.request_budget.borrow() {
+            request_budget.clone()
+        } else {
+            self.request_budget
+                .fill(RequestBudget::new(self.max_requests))
+                .expect("Could not overwrite existing request budget");
+            self.get_request_budget()
+        }
+    }
+
+    fn report_request_count(&self) {
+        if let Some(max_requests) = self.max_requests {
+            eprintln!(
+                "Made {} of {} allowed request(s) to Apollo Studio.",
+                self.get_request_budget().count(),
+                max_requests
+            );
+        }
+    }
+
+    /// Whether the command was asked not to use the network, i.e. `supergraph compose --offline`,
+    /// in which case Rover doesn't check for updates or report usage data either
+    pub(crate) fn is_offline(&self) -> bool {
+        match &self.command {
+            Command::Supergraph(command) => command.is_offline(),
+            Command::Dev(command) => command.is_offline(),
+            _ => false,
+        }
     }
 
     pub(crate) fn get_install_override_path(&self) -> RoverResult<Option<Utf8PathBuf>> {
@@ -297,6 +372,53 @@ impl Rover {
         }
     }
 
+    pub(crate) fn get_duration_budget(&self) -> RoverResult<Option<DurationBudget>> {
+        let budget = match &self.command {
+            Command::Graph(command) => command.duration_budget(),
+            Command::Subgraph(command) => command.duration_budget(),
+            Command::Contract(_)
+            | Command::Readme(_)
+            | Command::Schema(_)
+            | Command::Variant(_) => Some(DurationBudget::studio_request()),
+            Command::Fed2(_) | Command::Supergraph(_) => Some(DurationBudget::composition()),
+            Command::Install(_) | Command::Template(_) | Command::Update(_) => {
+                Some(DurationBudget::download())
+            }
+            // these either return immediately, wait on user input, or run until cancelled
+            Command::Config(_)
+            | Command::Dev(_)
+            | Command::Docs(_)
+            | Command::Explain(_)
+            | Command::Info(_)
+            | Command::Meta(_)
+            | Command::Ops(_)
+            // every step of a script reports its own duration
+            | Command::Run(_) => None,
+        };
+
+        if let Some(seconds) = self.get_env_var(RoverEnvKey::CommandDurationThresholdSeconds)? {
+            let threshold = seconds
+                .parse::<u64>()
+                .map(Duration::from_secs)
+                .map_err(|e| {
+                    let mut err = RoverError::new(anyhow!(
+                        "{} must be a whole number of seconds, not '{}': {}",
+                        RoverEnvKey::CommandDurationThresholdSeconds,
+                        seconds,
+                        e
+                    ));
+                    err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                        "Set {} to a number of seconds, i.e. `60`, or unset it.",
+                        RoverEnvKey::CommandDurationThresholdSeconds
+                    )));
+                    err
+                })?;
+            Ok(budget.map(|budget| budget.with_threshold(threshold)))
+        } else {
+            Ok(budget)
+        }
+    }
+
     pub(crate) fn get_env_var(&self, key: RoverEnvKey) -> io::Result<Option<String>> {
         Ok(if let Some(env_store) = self.env_store.borrow() {
             env_store.get(key)
@@ -382,9 +504,21 @@ pub enum Command {
     /// Readme commands
     Readme(command::Readme),
 
+    /// Schema commands
+    Schema(command::Schema),
+
     /// Subgraph schema commands
     Subgraph(command::Subgraph),
 
+    /// Graph variant commands
+    Variant(command::Variant),
+
+    /// Commands for working with GraphQL operations
+    Ops(command::Ops),
+
+    /// Run a script of Rover commands described in a YAML or TOML file
+    Run(command::Run),
+
     /// Interact with Rover's documentation
     Docs(command::Docs),
 
@@ -401,12 +535,18 @@ pub enum Command {
 
     /// Explain error codes
     Explain(command::Explain),
+
+    /// Generate files for packaging Rover, such as manpages
+    #[command(hide = true)]
+    Meta(command::Meta),
 }
 
 #[derive(ValueEnum, Debug, Serialize, Clone, Eq, PartialEq)]
 pub enum RoverOutputFormatKind {
     Plain,
     Json,
+    /// The schema printed by a command, as the result of an introspection query
+    IntrospectionJson,
 }
 
 #[derive(ValueEnum, Debug, Serialize, Clone, Eq, PartialEq)]
@@ -2,7 +2,7 @@ mod auth;
 mod clear;
 mod delete;
 mod list;
-mod whoami;
+pub(crate) mod whoami;
 
 use clap::Parser;
 use serde::Serialize;
@@ -6,9 +6,10 @@ use serde::Serialize;
 
 use houston::{mask_key, CredentialOrigin};
 
-use crate::options::ProfileOpt;
+use crate::options::{ProfileOpt, ProfilesOpt};
 use crate::utils::client::StudioClientConfig;
 use crate::utils::env::RoverEnvKey;
+use crate::utils::profiles::for_each_profile;
 use crate::{RoverOutput, RoverResult};
 
 use std::fmt::Write as _;
@@ -18,7 +19,7 @@ use houston as config;
 #[derive(Debug, Serialize, Parser)]
 pub struct WhoAmI {
     #[clap(flatten)]
-    profile: ProfileOpt,
+    profiles: ProfilesOpt,
 
     /// Unmask the API key that will be sent to Apollo Studio
     ///
@@ -29,76 +30,114 @@ pub struct WhoAmI {
     insecure_unmask_key: bool,
 }
 
+/// The identity of the API key a profile uses, as registered in Apollo Studio
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct WhoAmIResponse {
+    /// Either `GRAPH` or `USER`
+    pub key_type: String,
+    pub graph_title: Option<String>,
+    /// The ID of the graph or user the key belongs to
+    pub id: String,
+    pub origin: String,
+    /// The API key, masked unless `--insecure-unmask-key` was passed
+    pub api_key: String,
+}
+
+impl WhoAmIResponse {
+    pub fn get_message(&self) -> String {
+        let mut message = format!(
+            "{}: {}\n",
+            Style::WhoAmIKey.paint("Key Type"),
+            self.key_type
+        );
+
+        if let Some(graph_title) = &self.graph_title {
+            let _ = writeln!(
+                message,
+                "{}: {}",
+                Style::WhoAmIKey.paint("Graph Title"),
+                graph_title
+            );
+        }
+        let id_key = if self.key_type == "GRAPH" {
+            "Unique Graph ID"
+        } else {
+            "User ID"
+        };
+        let _ = writeln!(message, "{}: {}", Style::WhoAmIKey.paint(id_key), self.id);
+
+        let _ = write!(
+            message,
+            "{}: {}",
+            Style::WhoAmIKey.paint("Origin"),
+            &self.origin
+        );
+        let _ = write!(
+            message,
+            "\n{}: {}",
+            Style::WhoAmIKey.paint("API Key"),
+            &self.api_key
+        );
+        message
+    }
+}
+
 impl WhoAmI {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
-        let client = client_config.get_authenticated_client(&self.profile)?;
+        if self.profiles.all_profiles {
+            for_each_profile(&self.profiles, &client_config, |profile| {
+                let identity = self.get_identity(&client_config, profile)?;
+                Ok(RoverOutput::WhoAmI(identity))
+            })
+        } else {
+            let identity = self.get_identity(&client_config, &self.profiles.profile)?;
+            eprintln!("{}", identity.get_message());
+            Ok(RoverOutput::EmptySuccess)
+        }
+    }
+
+    fn get_identity(
+        &self,
+        client_config: &StudioClientConfig,
+        profile: &ProfileOpt,
+    ) -> RoverResult<WhoAmIResponse> {
+        let client = client_config.get_authenticated_client(profile)?;
         eprintln!("Checking identity of your API key against the registry.");
 
         let identity = who_am_i::run(ConfigWhoAmIInput {}, &client)?;
 
-        let mut message = format!(
-            "{}: {:?}\n",
-            Style::WhoAmIKey.paint("Key Type"),
-            identity.key_actor_type
-        );
-
-        match identity.key_actor_type {
-            Actor::GRAPH => {
-                if let Some(graph_title) = identity.graph_title {
-                    let _ = writeln!(
-                        message,
-                        "{}: {}",
-                        Style::WhoAmIKey.paint("Graph Title"),
-                        &graph_title
-                    );
-                }
-                let _ = writeln!(
-                    message,
-                    "{}: {}",
-                    Style::WhoAmIKey.paint("Unique Graph ID"),
-                    identity.id
-                );
-                Ok(())
-            }
-            Actor::USER => {
-                let _ = writeln!(
-                    message,
-                    "{}: {}",
-                    Style::WhoAmIKey.paint("User ID"),
-                    identity.id
-                );
-                Ok(())
+        let (key_type, graph_title) = match identity.key_actor_type {
+            Actor::GRAPH => ("GRAPH", identity.graph_title),
+            Actor::USER => ("USER", None),
+            _ => {
+                return Err(anyhow!(
+                    "The key provided is invalid. Rover only accepts personal and graph API keys"
+                )
+                .into())
             }
-            _ => Err(anyhow!(
-                "The key provided is invalid. Rover only accepts personal and graph API keys"
-            )),
-        }?;
+        };
 
         let origin = match client.get_credential_origin() {
             CredentialOrigin::ConfigFile(path) => format!("--profile {}", &path),
             CredentialOrigin::EnvVar => format!("${}", &RoverEnvKey::Key),
+            CredentialOrigin::Anonymous => "(no credentials)".to_string(),
         };
 
-        let _ = write!(message, "{}: {}", Style::WhoAmIKey.paint("Origin"), &origin);
-
         let credential =
-            config::Profile::get_credential(&self.profile.profile_name, &client_config.config)?;
+            config::Profile::get_credential(&profile.profile_name, &client_config.config)?;
 
-        let maybe_masked_key = if self.insecure_unmask_key {
+        let api_key = if self.insecure_unmask_key {
             credential.api_key
         } else {
             mask_key(&credential.api_key)
         };
 
-        let _ = write!(
-            message,
-            "\n{}: {}",
-            Style::WhoAmIKey.paint("API Key"),
-            &maybe_masked_key
-        );
-
-        eprintln!("{}", message);
-
-        Ok(RoverOutput::EmptySuccess)
+        Ok(WhoAmIResponse {
+            key_type: key_type.to_string(),
+            graph_title,
+            id: identity.id,
+            origin,
+            api_key,
+        })
     }
 }
@@ -7,7 +7,9 @@ use camino::Utf8PathBuf;
 use rover_std::{Emoji, Fs};
 
 use crate::command::dev::do_dev::log_err_and_continue;
+use crate::command::dev::logger::{self, DevLogSource};
 use crate::command::supergraph::compose::{Compose, CompositionOutput};
+use crate::command::supergraph::ConfigExtensions;
 use crate::options::PluginOpts;
 use crate::utils::client::StudioClientConfig;
 use crate::{RoverError, RoverResult};
@@ -18,6 +20,7 @@ pub struct ComposeRunner {
     override_install_path: Option<Utf8PathBuf>,
     client_config: StudioClientConfig,
     write_path: Utf8PathBuf,
+    out_path: Option<Utf8PathBuf>,
     composition_state: Option<RoverResult<CompositionOutput>>,
     plugin_exe: Option<Utf8PathBuf>,
 }
@@ -34,11 +37,19 @@ impl ComposeRunner {
             override_install_path,
             client_config,
             write_path,
+            out_path: None,
             composition_state: None,
             plugin_exe: None,
         }
     }
 
+    /// Keeps a copy of every new supergraph schema at `out_path`, which stays at the last
+    /// successful composition when composition fails
+    pub fn with_out_path(mut self, out_path: Option<Utf8PathBuf>) -> Self {
+        self.out_path = out_path;
+        self
+    }
+
     pub fn maybe_install_supergraph(
         &mut self,
         federation_version: FederationVersion,
@@ -56,15 +67,19 @@ impl ComposeRunner {
         }
     }
 
+    /// Composes the supergraph. Each of `proxied_urls` is a routing URL in `supergraph_config`
+    /// that stands in for a subgraph's own routing URL, which `--supergraph-out` exports instead
     pub fn run(
         &mut self,
         supergraph_config: &mut SupergraphConfig,
+        proxied_urls: &[(String, String)],
     ) -> std::result::Result<Option<CompositionOutput>, String> {
         let prev_state = self.composition_state();
         self.composition_state = Some(self.compose.exec(
             self.override_install_path.clone(),
             self.client_config.clone(),
             supergraph_config,
+            &ConfigExtensions::default(),
         ));
         let new_state = self.composition_state();
 
@@ -72,21 +87,18 @@ impl ComposeRunner {
             // wasn't composed, now composed
             (None, Some(Ok(new_success))) | (Some(Err(_)), Some(Ok(new_success))) => {
                 let _ = self
-                    .update_supergraph_schema(&new_success.supergraph_sdl)
+                    .update_supergraph_schema(&new_success.supergraph_sdl, proxied_urls)
                     .map_err(log_err_and_continue);
                 Ok(Some(new_success))
             }
-            // had a composition error, now a new composition error
-            (Some(Err(prev_err)), Some(Err(new_err))) => {
-                if prev_err != new_err {
-                    let _ = self.remove_supergraph_schema();
-                }
-                Err(new_err)
-            }
+            // had a composition error, now a new composition error,
+            // which keeps the last supergraph that composed for the router to serve
+            (Some(Err(_)), Some(Err(new_err))) => Err(new_err),
             // had a successful composition, now a new successful composition
             (Some(Ok(prev_success)), Some(Ok(new_success))) => {
                 if prev_success != new_success {
-                    let _ = self.update_supergraph_schema(&new_success.supergraph_sdl);
+                    let _ =
+                        self.update_supergraph_schema(&new_success.supergraph_sdl, proxied_urls);
                     Ok(Some(new_success))
                 } else {
                     Ok(None)
@@ -97,17 +109,17 @@ impl ComposeRunner {
                 let _ = self.remove_supergraph_schema();
                 Ok(None)
             }
-            // now has an error
-            (_, Some(Err(new_err))) => {
-                let _ = self.remove_supergraph_schema();
-                Err(new_err)
-            }
+            // now has an error, which keeps the last supergraph that composed, if there is one
+            (_, Some(Err(new_err))) => Err(new_err),
         }
     }
 
     fn remove_supergraph_schema(&self) -> RoverResult<()> {
         if Fs::assert_path_exists(&self.write_path).is_ok() {
-            eprintln!("{}composition failed, killing the router", Emoji::Skull);
+            logger::info(
+                &DevLogSource::Rover,
+                format!("{}nothing was composed, killing the router", Emoji::Skull),
+            );
             Ok(fs::remove_file(&self.write_path)
                 .with_context(|| format!("could not remove {}", &self.write_path))?)
         } else {
@@ -115,8 +127,45 @@ impl ComposeRunner {
         }
     }
 
-    fn update_supergraph_schema(&self, sdl: &str) -> RoverResult<()> {
+    /// Writes the schema to a temp file next to `--supergraph-out` before moving it into place,
+    /// so tools watching the file never read half of a schema. The exported schema has each
+    /// subgraph's own routing URL, not the URL of a proxy that stands in for it
+    fn export_supergraph_schema(
+        &self,
+        sdl: &str,
+        proxied_urls: &[(String, String)],
+    ) -> RoverResult<()> {
+        if let Some(out_path) = &self.out_path {
+            let sdl = proxied_urls
+                .iter()
+                .fold(sdl.to_string(), |sdl, (proxy_url, routing_url)| {
+                    sdl.replace(
+                        &format!("url: \"{}\"", proxy_url),
+                        &format!("url: \"{}\"", routing_url),
+                    )
+                });
+            if let Some(out_dir) = out_path.parent().filter(|dir| !dir.as_str().is_empty()) {
+                Fs::create_dir_all(out_dir)?;
+            }
+            let tmp_path = Utf8PathBuf::from(format!("{}.tmp", out_path));
+            Fs::write_file(&tmp_path, sdl)?;
+            fs::rename(&tmp_path, out_path).with_context(|| {
+                format!("could not write the supergraph schema to {}", out_path)
+            })?;
+            tracing::info!("exported the supergraph schema to {}", out_path);
+        }
+        Ok(())
+    }
+
+    fn update_supergraph_schema(
+        &self,
+        sdl: &str,
+        proxied_urls: &[(String, String)],
+    ) -> RoverResult<()> {
         tracing::info!("composition succeeded, updating the supergraph schema...");
+        let _ = self
+            .export_supergraph_schema(sdl, proxied_urls)
+            .map_err(log_err_and_continue);
         let context = format!("could not write SDL to {}", &self.write_path);
         match std::fs::File::create(&self.write_path) {
             Ok(mut opened_file) => {
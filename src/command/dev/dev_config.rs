@@ -0,0 +1,334 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::ValueEnum;
+use rover_client::shared::GraphRef;
+use rover_std::Fs;
+use serde::Deserialize;
+
+use crate::{
+    command::dev::{DevLogFilter, DevLogFormat, DevOpts, SubscriptionProtocol},
+    utils::parsers::{parse_router_version, parse_session_name, parse_subgraph_header},
+    RoverError, RoverErrorSuggestion, RoverResult,
+};
+
+/// The file a bare `rover dev` reads its session's settings from, if it's in the current directory
+pub const DEV_CONFIG_FILE_NAME: &str = "rover-dev.yaml";
+
+/// A committed `rover-dev.yaml`, so everyone on a team starts the same session with a bare `rover dev`.
+///
+/// Each key is named after a `rover dev` flag, with `_` in place of `-`, and the flags passed
+/// on the command line take precedence over it. `subgraphs` lists subgraphs like a `--supergraph-config` file,
+/// and its `file` schemas are the paths the session watches. The flags of the one subgraph of a process,
+/// `--name`, `--url`, `--schema`, and `--mock`, have no key, since its subgraph can be listed under `subgraphs`
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DevConfig {
+    session_name: Option<String>,
+    supergraph_address: Option<String>,
+    supergraph_port: Option<u16>,
+    router_config: Option<String>,
+    router_version: Option<String>,
+    hooks: Option<String>,
+    #[serde(default)]
+    on_recompose: Vec<String>,
+    supergraph_config: Option<String>,
+    docker_compose: Option<String>,
+    supergraph_out: Option<String>,
+    record: Option<String>,
+    replay: Option<String>,
+    open: Option<bool>,
+    remote_subgraphs: Option<String>,
+    health_port: Option<u16>,
+    tls: Option<bool>,
+    tls_cert: Option<String>,
+    tls_key: Option<String>,
+    subscriptions: Option<bool>,
+    subscription_path: Option<String>,
+    subscription_protocol: Option<String>,
+    log_format: Option<String>,
+    log_filter: Option<String>,
+    no_polling: Option<bool>,
+    polling_interval: Option<u64>,
+    #[serde(default)]
+    subgraph_header: Vec<String>,
+    subgraphs: Option<serde_yaml::Value>,
+}
+
+impl DevConfig {
+    pub fn new_from_yaml(contents: &str, config_path: &Utf8Path) -> RoverResult<Self> {
+        serde_yaml::from_str(contents).map_err(|e| {
+            let mut err = RoverError::new(anyhow!("could not parse {}: {}", config_path, e));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Each key of a `rover dev` config is the name of a `rover dev` flag, with `_` in place of `-`, or `subgraphs`.".to_string(),
+            ));
+            err
+        })
+    }
+
+    /// Fills in every option of `opts` that wasn't passed on the command line.
+    ///
+    /// Paths in the config are relative to the directory it's in
+    fn apply(self, config_path: &Utf8Path, dev_opts: &mut DevOpts) -> RoverResult<()> {
+        let config_dir = match config_path.parent() {
+            Some(parent) if !parent.as_str().is_empty() => parent.to_path_buf(),
+            _ => Utf8PathBuf::from("."),
+        };
+        let resolve = |path: Option<String>| path.map(|path| config_dir.join(path));
+        let invalid = |key: &str, e: String| {
+            RoverError::new(anyhow!(
+                "the `{}` in {} is not valid: {}",
+                key,
+                config_path,
+                e
+            ))
+        };
+
+        if self.no_polling == Some(true) && self.polling_interval.is_some() {
+            return Err(RoverError::new(anyhow!(
+                "{} must set `no_polling` or `polling_interval`, not both of them",
+                config_path
+            )));
+        }
+        let subgraph_headers = self
+            .subgraph_header
+            .iter()
+            .map(|header| {
+                parse_subgraph_header(header).map_err(|e| invalid("subgraph_header", e.to_string()))
+            })
+            .collect::<RoverResult<Vec<_>>>()?;
+        dev_opts.subgraph_opts.with_defaults(
+            self.no_polling,
+            self.polling_interval,
+            subgraph_headers,
+        );
+
+        let opts = &mut dev_opts.supergraph_opts;
+        if let Some(session_name) = self.session_name {
+            let session_name = parse_session_name(&session_name)
+                .map_err(|e| invalid("session_name", e.to_string()))?;
+            opts.session_name.get_or_insert(session_name);
+        }
+        if opts.supergraph_address.is_none() {
+            opts.supergraph_address = self.supergraph_address;
+        }
+        if opts.supergraph_port.is_none() {
+            opts.supergraph_port = self.supergraph_port;
+        }
+        if opts.router_config_path.is_none() {
+            opts.router_config_path = resolve(self.router_config);
+        }
+        if let Some(router_version) = self.router_version {
+            let router_version = parse_router_version(&router_version)
+                .map_err(|e| invalid("router_version", e.to_string()))?;
+            opts.router_version.get_or_insert(router_version);
+        }
+        if opts.hooks_path.is_none() {
+            opts.hooks_path = resolve(self.hooks);
+        }
+        if opts.on_recompose.is_empty() {
+            opts.on_recompose = self.on_recompose;
+        }
+
+        // the subgraph sources of the command line replace every subgraph source of the config
+        if opts.supergraph_config_path.is_none() && opts.docker_compose_path.is_none() {
+            if [
+                self.supergraph_config.is_some(),
+                self.docker_compose.is_some(),
+                self.subgraphs.is_some(),
+            ]
+            .iter()
+            .filter(|is_set| **is_set)
+            .count()
+                > 1
+            {
+                let mut err = RoverError::new(anyhow!(
+                    "{} sets more than one of `supergraph_config`, `docker_compose`, and `subgraphs`",
+                    config_path
+                ));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                    "Keep only one of them, since a `rover dev` session runs the subgraphs of one source.".to_string(),
+                ));
+                return Err(err);
+            }
+            opts.supergraph_config_path = resolve(self.supergraph_config);
+            opts.docker_compose_path = resolve(self.docker_compose);
+            opts.dev_config_subgraphs = self.subgraphs;
+        }
+        if opts.record_dir.is_none() && opts.replay_dir.is_none() {
+            if self.record.is_some() && self.replay.is_some() {
+                return Err(RoverError::new(anyhow!(
+                    "{} must set `record` or `replay`, not both of them",
+                    config_path
+                )));
+            }
+            opts.record_dir = resolve(self.record);
+            opts.replay_dir = resolve(self.replay);
+        }
+
+        if opts.supergraph_out_path.is_none() {
+            opts.supergraph_out_path = resolve(self.supergraph_out);
+        }
+        if opts.open.is_none() {
+            opts.open = self.open;
+        }
+        if let Some(remote_subgraphs) = self.remote_subgraphs {
+            let graph_ref = GraphRef::from_str(&remote_subgraphs)
+                .map_err(|e| invalid("remote_subgraphs", e.to_string()))?;
+            opts.remote_subgraphs.get_or_insert(graph_ref);
+        }
+        if opts.health_port.is_none() {
+            opts.health_port = self.health_port;
+        }
+
+        if opts.tls.is_none() {
+            opts.tls = self.tls;
+        }
+        if opts.tls_cert_path.is_none() && opts.tls_key_path.is_none() {
+            match (resolve(self.tls_cert), resolve(self.tls_key)) {
+                (Some(tls_cert_path), Some(tls_key_path)) => {
+                    opts.tls_cert_path = Some(tls_cert_path);
+                    opts.tls_key_path = Some(tls_key_path);
+                }
+                (None, None) => {}
+                _ => {
+                    return Err(RoverError::new(anyhow!(
+                        "{} must set both `tls_cert` and `tls_key`, or neither of them",
+                        config_path
+                    )))
+                }
+            }
+        }
+
+        if opts.subscriptions.is_none() {
+            opts.subscriptions = self.subscriptions;
+        }
+        if opts.subscription_path.is_none() {
+            opts.subscription_path = self.subscription_path;
+        }
+        if let Some(subscription_protocol) = self.subscription_protocol {
+            let subscription_protocol =
+                SubscriptionProtocol::from_str(&subscription_protocol, true)
+                    .map_err(|e| invalid("subscription_protocol", e))?;
+            opts.subscription_protocol
+                .get_or_insert(subscription_protocol);
+        }
+        if (opts.subscription_path.is_some() || opts.subscription_protocol.is_some())
+            && opts.subscriptions != Some(true)
+        {
+            return Err(RoverError::new(anyhow!(
+                "`subscription_path` and `subscription_protocol` need `subscriptions` to be enabled"
+            )));
+        }
+
+        if let Some(log_format) = self.log_format {
+            let log_format =
+                DevLogFormat::from_str(&log_format, true).map_err(|e| invalid("log_format", e))?;
+            opts.log_format.get_or_insert(log_format);
+        }
+        if let Some(log_filter) = self.log_filter {
+            let log_filter =
+                DevLogFilter::from_str(&log_filter).map_err(|e| invalid("log_filter", e))?;
+            opts.log_filter.get_or_insert(log_filter);
+        }
+        Ok(())
+    }
+}
+
+impl DevOpts {
+    /// These options, with the ones that weren't passed on the command line taken from the
+    /// `--dev-config` file, or from a `rover-dev.yaml` in the current directory if there is one
+    pub fn with_dev_config(mut self) -> RoverResult<Self> {
+        let config_path = match &self.supergraph_opts.dev_config_path {
+            Some(config_path) => config_path.clone(),
+            None => {
+                let config_path = Utf8PathBuf::from(DEV_CONFIG_FILE_NAME);
+                if !config_path.exists() {
+                    return Ok(self);
+                }
+                config_path
+            }
+        };
+        let contents = Fs::read_file(&config_path)?;
+        DevConfig::new_from_yaml(&contents, &config_path)?.apply(&config_path, &mut self)?;
+        self.supergraph_opts.dev_config_path = Some(config_path);
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn it_prefers_command_line_options_to_the_dev_config() {
+        let config_path = Utf8PathBuf::from("team/rover-dev.yaml");
+        let config = DevConfig::new_from_yaml(
+            "session_name: checkout\nsupergraph_port: 3100\nrouter_config: router.yaml\non_recompose:\n  - npm run codegen\nsubgraphs:\n  products:\n    routing_url: http://localhost:4001\n    schema:\n      file: products.graphql\n",
+            &config_path,
+        )
+        .unwrap();
+        let mut opts = DevOpts::parse_from(["dev-opts", "--supergraph-port", "3200"]);
+        config.apply(&config_path, &mut opts).unwrap();
+        let opts = opts.supergraph_opts;
+        assert_eq!(opts.session_name.as_deref(), Some("checkout"));
+        assert_eq!(opts.supergraph_port, Some(3200));
+        assert_eq!(
+            opts.router_config_path,
+            Some(Utf8PathBuf::from("team/router.yaml"))
+        );
+        assert_eq!(opts.on_recompose, vec!["npm run codegen".to_string()]);
+        assert!(opts.dev_config_subgraphs.is_some());
+
+        let config = DevConfig::new_from_yaml("subgraphs: {}\n", &config_path).unwrap();
+        let mut opts = DevOpts::parse_from(["dev-opts", "--supergraph-config", "supergraph.yaml"]);
+        config.apply(&config_path, &mut opts).unwrap();
+        assert!(opts.supergraph_opts.dev_config_subgraphs.is_none());
+
+        assert!(DevConfig::new_from_yaml("supergraph-port: 3000\n", &config_path).is_err());
+        let config =
+            DevConfig::new_from_yaml("session_name: 'my session'\n", &config_path).unwrap();
+        assert!(config
+            .apply(&config_path, &mut DevOpts::parse_from(["dev-opts"]))
+            .is_err());
+    }
+
+    #[test]
+    fn it_turns_off_the_dev_configs_flags_from_the_command_line() {
+        let config_path = Utf8PathBuf::from("rover-dev.yaml");
+        let contents = "open: true\ntls: true\nno_polling: true\nsubgraph_header:\n  - products/Authorization:Bearer abc\n";
+
+        let mut opts = DevOpts::parse_from(["dev-opts"]);
+        let config = DevConfig::new_from_yaml(contents, &config_path).unwrap();
+        config.apply(&config_path, &mut opts).unwrap();
+        assert_eq!(opts.supergraph_opts.open, Some(true));
+        assert_eq!(opts.supergraph_opts.tls, Some(true));
+        assert_eq!(opts.subgraph_opts.get_polling_interval(), None);
+        assert_eq!(
+            opts.subgraph_opts.get_subgraph_headers("products"),
+            vec![("Authorization".to_string(), "Bearer abc".to_string())]
+        );
+
+        let mut opts = DevOpts::parse_from([
+            "dev-opts",
+            "--open=false",
+            "--tls",
+            "--polling-interval",
+            "5",
+            "--subgraph-header",
+            "Authorization:Bearer xyz",
+        ]);
+        let config = DevConfig::new_from_yaml(contents, &config_path).unwrap();
+        config.apply(&config_path, &mut opts).unwrap();
+        assert_eq!(opts.supergraph_opts.open, Some(false));
+        assert_eq!(opts.supergraph_opts.tls, Some(true));
+        assert_eq!(opts.subgraph_opts.get_polling_interval(), Some(5));
+        assert_eq!(
+            opts.subgraph_opts.get_subgraph_headers("products"),
+            vec![("Authorization".to_string(), "Bearer xyz".to_string())]
+        );
+    }
+}
@@ -1,14 +1,35 @@
 use anyhow::{anyhow, Context};
 use camino::Utf8PathBuf;
-use rover_std::Emoji;
+use rover_std::{tmp, Emoji, Style};
 
-use super::protocol::{FollowerChannel, FollowerMessenger, LeaderChannel, LeaderSession};
-use super::router::RouterConfigHandler;
-use super::Dev;
+use super::health::HealthEndpoint;
+use super::hooks::DevHooks;
+use super::logger::{self, init_dev_logger, DevLogSource};
+use super::processes::{is_process_running, kill_process};
+use super::protocol::{
+    FollowerChannel, FollowerMessenger, LeaderChannel, LeaderSession, LEADER_THREAD_NAME,
+};
+use super::record::DevRecorder;
+use super::router::{get_default_router_address, RouterConfigHandler};
+use super::session::{Attach, DevSessionProcess, DevSessionProcesses, DevSessionState, Kill, Ps};
+use super::session_file::{
+    get_sessions_dir, SessionAnnouncement, SessionFile, ROUTER_EXECUTABLE, ROVER_EXECUTABLE,
+};
+use super::status::Status;
+use super::supergraph_config::{wait_for_shutdown, ConfigSubgraphs};
+use super::{Command, Dev};
 
-use crate::command::dev::protocol::FollowerMessage;
+use crate::command::dev::protocol::{
+    get_ipc_address, ipc_connect, socket_read, socket_write, FollowerMessage, LeaderMessageKind,
+    SubgraphName,
+};
+use crate::options::OptionalSubgraphOpts;
 use crate::utils::client::StudioClientConfig;
-use crate::{RoverError, RoverOutput, RoverResult};
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::time::Duration;
 
 use crossbeam_channel::bounded as sync_channel;
 
@@ -23,13 +44,81 @@ impl Dev {
         override_install_path: Option<Utf8PathBuf>,
         client_config: StudioClientConfig,
     ) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Some(Command::Status(command)) => return command.run(),
+            Some(Command::Attach(command)) => return command.run(client_config),
+            Some(Command::Kill(command)) => return command.run(override_install_path),
+            Some(Command::Ps(command)) => return command.run(override_install_path),
+            Some(Command::Install(command)) => {
+                let dev_router_version = self
+                    .opts
+                    .clone()
+                    .with_dev_config()?
+                    .supergraph_opts
+                    .router_version;
+                return command.run(override_install_path, client_config, dev_router_version);
+            }
+            Some(Command::List(command)) => return command.run(override_install_path),
+            Some(Command::Prune(command)) => return command.run(override_install_path),
+            None => {}
+        }
+
+        let mut opts = self.opts.clone().with_dev_config()?;
+        // offline, the session runs the plugins installed here without checking for newer ones
+        if self.is_offline() {
+            if opts.supergraph_opts.remote_subgraphs.is_some() {
+                return Err(RoverError::new(anyhow!(
+                    "`--offline` can't be used with the `remote_subgraphs` of the `rover dev` config, which are fetched from Apollo Studio"
+                )));
+            }
+            opts.plugin_opts.skip_update = true;
+        }
+        Dev {
+            command: None,
+            opts,
+        }
+        .run_session(override_install_path, client_config)
+    }
+
+    /// Starts or attaches to a session, with the options of the `--dev-config` file filled in
+    fn run_session(
+        &self,
+        override_install_path: Option<Utf8PathBuf>,
+        client_config: StudioClientConfig,
+    ) -> RoverResult<RoverOutput> {
+        let supergraph_opts = &self.opts.supergraph_opts;
+        init_dev_logger(
+            supergraph_opts.log_format.unwrap_or_default(),
+            supergraph_opts.log_filter.clone().unwrap_or_default(),
+        );
+        if let Some(dev_config_path) = &supergraph_opts.dev_config_path {
+            logger::info(
+                &DevLogSource::Rover,
+                format!(
+                    "{}using the `rover dev` config at {}",
+                    Emoji::Note,
+                    Style::Path.paint(dev_config_path)
+                ),
+            );
+        }
+
         self.opts
             .plugin_opts
             .prompt_for_license_accept(&client_config)?;
 
-        let router_config_handler = RouterConfigHandler::try_from(&self.opts.supergraph_opts)?;
+        let router_config_handler = RouterConfigHandler::from_opts(
+            &self.opts.supergraph_opts,
+            override_install_path.clone(),
+        )?;
+        let hooks = DevHooks::try_from(&self.opts.supergraph_opts)?;
         let router_address = router_config_handler.get_router_address()?;
         let ipc_socket_addr = router_config_handler.get_ipc_address()?;
+        let router_metrics_url = router_config_handler.get_router_metrics_url();
+        let recorder = supergraph_opts
+            .record_dir
+            .as_deref()
+            .map(DevRecorder::new)
+            .transpose()?;
         let leader_channel = LeaderChannel::new();
         let follower_channel = FollowerChannel::new();
 
@@ -40,7 +129,15 @@ impl Dev {
             follower_channel.clone(),
             self.opts.plugin_opts.clone(),
             router_config_handler,
-        )? {
+            hooks,
+        )?
+        .map(|leader_session| {
+            leader_session
+                .with_supergraph_out_path(self.opts.supergraph_opts.supergraph_out_path.clone())
+                .with_open_sandbox(self.opts.supergraph_opts.open.unwrap_or_default())
+                .with_recorder(recorder)
+        }) {
+            let router_address = leader_session.get_router_address();
             let (ready_sender, ready_receiver) = sync_channel(1);
             let follower_messenger = FollowerMessenger::from_main_session(
                 follower_channel.clone().sender,
@@ -49,9 +146,12 @@ impl Dev {
 
             rayon::spawn(move || {
                 ctrlc::set_handler(move || {
-                    eprintln!(
-                        "\n{}shutting down the `rover dev` session and all attached processes...",
-                        Emoji::Stop
+                    logger::info(
+                        &DevLogSource::Rover,
+                        format!(
+                            "\n{}shutting down the `rover dev` session and all attached processes...",
+                            Emoji::Stop
+                        ),
                     );
                     let _ = follower_channel
                         .sender
@@ -66,14 +166,30 @@ impl Dev {
                 .unwrap();
             });
 
-            rayon::spawn(move || {
-                let _ = leader_session
-                    .listen_for_all_subgraph_updates(ready_sender)
-                    .map_err(log_err_and_continue);
-            });
+            std::thread::Builder::new()
+                .name(LEADER_THREAD_NAME.to_string())
+                .spawn(move || {
+                    let _ = leader_session
+                        .listen_for_all_subgraph_updates(ready_sender)
+                        .map_err(log_err_and_continue);
+                })?;
 
             ready_receiver.recv().unwrap();
 
+            if let Some(health_port) = self.opts.supergraph_opts.health_port {
+                HealthEndpoint::new(
+                    SocketAddr::new(router_address.ip(), health_port),
+                    &ipc_socket_addr,
+                )?
+                .with_router_metrics_url(router_metrics_url)
+                .start()?;
+            }
+
+            let config_subgraphs = self.start_config_subgraphs(&client_config, &ipc_socket_addr)?;
+            if !config_subgraphs.is_empty() && self.opts.subgraph_opts.is_empty() {
+                wait_for_shutdown(&config_subgraphs);
+            }
+
             let mut subgraph_watcher = self.opts.subgraph_opts.get_subgraph_watcher(
                 router_address,
                 &client_config,
@@ -85,43 +201,354 @@ impl Dev {
                 .watch_subgraph_for_changes()
                 .map_err(log_err_and_continue);
         } else {
-            // get a [`SubgraphRefresher`] that takes care of getting the schema for a single subgraph
-            // either by polling the introspection endpoint or by watching the file system
-            let mut subgraph_refresher = self.opts.subgraph_opts.get_subgraph_watcher(
+            let config_subgraphs = self.start_config_subgraphs(&client_config, &ipc_socket_addr)?;
+            run_attached_session(
+                &self.opts.subgraph_opts,
+                config_subgraphs,
                 router_address,
+                &ipc_socket_addr,
                 &client_config,
-                FollowerMessenger::from_attached_session(&ipc_socket_addr),
             )?;
-            tracing::info!(
-                "connecting to existing `rover dev` process by communicating via the interprocess socket located at {ipc_socket_addr}"
+        }
+
+        unreachable!()
+    }
+
+    /// Starts the subgraphs of the `--supergraph-config`, `--docker-compose`, `--replay`, or `--dev-config` source, if there is one,
+    /// and then the published `--remote-subgraphs`, returning their names
+    fn start_config_subgraphs(
+        &self,
+        client_config: &StudioClientConfig,
+        ipc_socket_addr: &str,
+    ) -> RoverResult<Vec<SubgraphName>> {
+        let supergraph_opts = &self.opts.supergraph_opts;
+        let config_subgraphs = match (
+            &supergraph_opts.supergraph_config_path,
+            &supergraph_opts.docker_compose_path,
+            &supergraph_opts.replay_dir,
+            (
+                &supergraph_opts.dev_config_path,
+                &supergraph_opts.dev_config_subgraphs,
+            ),
+        ) {
+            (Some(config_path), _, _, _) => Some(ConfigSubgraphs::new(
+                config_path,
+                client_config,
+                &self.opts.plugin_opts.profile,
+                ipc_socket_addr,
+                &self.opts.subgraph_opts,
+            )?),
+            (None, Some(compose_path), _, _) => Some(ConfigSubgraphs::from_docker_compose(
+                compose_path,
+                client_config,
+                &self.opts.plugin_opts.profile,
+                ipc_socket_addr,
+                &self.opts.subgraph_opts,
+            )?),
+            (None, None, Some(replay_dir), _) => Some(ConfigSubgraphs::from_replay(
+                replay_dir,
+                client_config,
+                &self.opts.plugin_opts.profile,
+                ipc_socket_addr,
+                &self.opts.subgraph_opts,
+            )?),
+            (None, None, None, (Some(dev_config_path), Some(subgraphs))) => {
+                Some(ConfigSubgraphs::from_dev_config(
+                    dev_config_path,
+                    subgraphs,
+                    client_config,
+                    &self.opts.plugin_opts.profile,
+                    ipc_socket_addr,
+                    &self.opts.subgraph_opts,
+                )?)
+            }
+            (None, None, None, _) => None,
+        };
+        let mut subgraph_names = Vec::new();
+        if let Some(config_subgraphs) = config_subgraphs {
+            subgraph_names.extend(config_subgraphs.get_names());
+            config_subgraphs.start()?;
+        }
+
+        if let Some(graph_ref) = &supergraph_opts.remote_subgraphs {
+            // the main session replaces each published subgraph with the local subgraph with its name
+            let remote_subgraphs = ConfigSubgraphs::from_remote_subgraphs(
+                graph_ref,
+                client_config,
+                &self.opts.plugin_opts.profile,
+                ipc_socket_addr,
+            )?;
+            subgraph_names.extend(remote_subgraphs.get_names());
+            remote_subgraphs.start()?;
+        }
+        Ok(subgraph_names)
+    }
+}
+
+/// Watches the subgraph of a `rover dev` process that's attached to another process's session,
+/// along with any subgraphs of its `--supergraph-config`, until that session stops
+fn run_attached_session(
+    subgraph_opts: &OptionalSubgraphOpts,
+    config_subgraphs: Vec<SubgraphName>,
+    router_address: SocketAddr,
+    ipc_socket_addr: &str,
+    client_config: &StudioClientConfig,
+) -> RoverResult<()> {
+    // get a [`SubgraphRefresher`] that takes care of getting the schema for a single subgraph
+    // either by polling the introspection endpoint or by watching the file system
+    let subgraph_refresher = if config_subgraphs.is_empty() || !subgraph_opts.is_empty() {
+        Some(subgraph_opts.get_subgraph_watcher(
+            router_address,
+            client_config,
+            FollowerMessenger::from_attached_session(ipc_socket_addr),
+        )?)
+    } else {
+        None
+    };
+    tracing::info!(
+        "connecting to existing `rover dev` process by communicating via the interprocess socket located at {ipc_socket_addr}"
+    );
+
+    let health_messenger = FollowerMessenger::from_attached_session(ipc_socket_addr);
+    // start the interprocess socket health check in the background
+    rayon::spawn(move || {
+        let _ = health_messenger.health_check().map_err(|_| {
+            logger::info(
+                &DevLogSource::Rover,
+                format!("{}shutting down...", Emoji::Stop),
             );
+            tmp::cleanup();
+            std::process::exit(1);
+        });
+    });
 
-            let health_messenger = FollowerMessenger::from_attached_session(&ipc_socket_addr);
-            // start the interprocess socket health check in the background
-            rayon::spawn(move || {
-                let _ = health_messenger.health_check().map_err(|_| {
-                    eprintln!("{}shutting down...", Emoji::Stop);
-                    std::process::exit(1);
-                });
-            });
+    // set up the ctrl+c handler to notify the main session to remove the killed subgraphs
+    let kill_messenger = FollowerMessenger::from_attached_session(ipc_socket_addr);
+    let mut kill_names = config_subgraphs.clone();
+    if let Some(subgraph_refresher) = &subgraph_refresher {
+        kill_names.push(subgraph_refresher.get_name());
+    }
+    ctrlc::set_handler(move || {
+        logger::info(
+            &DevLogSource::Rover,
+            format!("\n{}shutting down...", Emoji::Stop),
+        );
+        for kill_name in &kill_names {
+            let _ = kill_messenger
+                .remove_subgraph(kill_name)
+                .map_err(log_err_and_continue);
+        }
+        tmp::cleanup();
+        std::process::exit(1);
+    })
+    .context("could not set ctrl-c handler")?;
 
-            // set up the ctrl+c handler to notify the main session to remove the killed subgraph
-            let kill_messenger = FollowerMessenger::from_attached_session(&ipc_socket_addr);
-            let kill_name = subgraph_refresher.get_name();
-            ctrlc::set_handler(move || {
-                eprintln!("\n{}shutting down...", Emoji::Stop);
-                let _ = kill_messenger
-                    .remove_subgraph(&kill_name)
-                    .map_err(log_err_and_continue);
-                std::process::exit(1);
-            })
-            .context("could not set ctrl-c handler")?;
+    match subgraph_refresher {
+        // watch for subgraph changes on the main thread
+        // it will take care of updating the main `rover dev` session
+        Some(mut subgraph_refresher) => subgraph_refresher.watch_subgraph_for_changes(),
+        None => wait_for_shutdown(&config_subgraphs),
+    }
+}
+
+/// Whether a main `rover dev` process is listening on a socket, checked the same way
+/// a new `rover dev` process does so the main process doesn't log an invalid message
+fn is_session_running(ipc_socket_addr: &str) -> bool {
+    ipc_connect(ipc_socket_addr)
+        .map_err(RoverError::from)
+        .and_then(|stream| {
+            let mut stream = BufReader::new(stream);
+            socket_write(&FollowerMessage::health_check(false)?, &mut stream)?;
+            Ok(socket_read::<LeaderMessageKind>(&mut stream)?)
+        })
+        .is_ok()
+}
+
+/// The error for a `rover dev` subcommand that needs a session when there isn't one
+fn no_session_error(session_name: Option<&str>, router_address: &SocketAddr) -> RoverError {
+    let mut err = match session_name {
+        Some(session_name) => RoverError::new(anyhow!(
+            "There is no `rover dev` session named '{}' running.",
+            session_name
+        )),
+        None => RoverError::new(anyhow!(
+            "There is no `rover dev` session running at '{}'.",
+            router_address
+        )),
+    };
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(match session_name {
+        Some(session_name) => format!(
+            "Start the session with `rover dev --session-name {}`.",
+            session_name
+        ),
+        None => "If the session's router listens on a different address or port, pass the same `--supergraph-address` and `--supergraph-port`, or pass the `--session-name` it was started with.".to_string(),
+    }));
+    err
+}
+
+/// The socket of a named session, which doesn't depend on its router address
+fn get_session_ipc_address(session_name: &str) -> RoverResult<String> {
+    Ok(get_ipc_address(
+        &get_default_router_address(None, None)?,
+        Some(session_name),
+    ))
+}
 
-            // watch for subgraph changes on the main thread
-            // it will take care of updating the main `rover dev` session
-            subgraph_refresher.watch_subgraph_for_changes()?;
+impl Status {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        let router_address =
+            get_default_router_address(self.supergraph_address.as_deref(), self.supergraph_port)?;
+        let ipc_socket_addr = get_ipc_address(&router_address, self.session_name.as_deref());
+        if !is_session_running(&ipc_socket_addr) {
+            return Err(no_session_error(
+                self.session_name.as_deref(),
+                &router_address,
+            ));
         }
 
+        let messenger = FollowerMessenger::from_attached_session(&ipc_socket_addr);
+        messenger.version_check()?;
+        Ok(RoverOutput::DevSessionStatus(messenger.session_status()?))
+    }
+}
+
+impl Attach {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let ipc_socket_addr = get_session_ipc_address(&self.session_name)?;
+        if !is_session_running(&ipc_socket_addr) {
+            let router_address = get_default_router_address(None, None)?;
+            return Err(no_session_error(Some(&self.session_name), &router_address));
+        }
+
+        let messenger = FollowerMessenger::from_attached_session(&ipc_socket_addr);
+        messenger.version_check()?;
+        // the subgraph's URL can't be the session's router, which is only known to the main process
+        let router_address = messenger.session_status()?.router_address;
+        run_attached_session(
+            &self.subgraph_opts,
+            Vec::new(),
+            router_address,
+            &ipc_socket_addr,
+            &client_config,
+        )?;
         unreachable!()
     }
 }
+
+impl Kill {
+    pub fn run(&self, override_install_path: Option<Utf8PathBuf>) -> RoverResult<RoverOutput> {
+        let session_name = match &self.session_name {
+            Some(session_name) => session_name,
+            None => return kill_all_sessions(override_install_path),
+        };
+        let ipc_socket_addr = get_session_ipc_address(session_name)?;
+        if !is_session_running(&ipc_socket_addr) {
+            let router_address = get_default_router_address(None, None)?;
+            return Err(no_session_error(Some(session_name), &router_address));
+        }
+
+        if stop_session(&ipc_socket_addr)? {
+            logger::info(
+                &DevLogSource::Rover,
+                format!(
+                    "{}stopped the '{}' `rover dev` session",
+                    Emoji::Stop,
+                    session_name
+                ),
+            );
+            Ok(RoverOutput::EmptySuccess)
+        } else {
+            Err(RoverError::new(anyhow!(
+                "The '{}' `rover dev` session did not stop within 10 seconds.",
+                session_name
+            )))
+        }
+    }
+}
+
+impl Ps {
+    pub fn run(&self, override_install_path: Option<Utf8PathBuf>) -> RoverResult<RoverOutput> {
+        let sessions = SessionFile::read_all(&get_sessions_dir(override_install_path)?)
+            .into_iter()
+            .map(|(_, announcement)| {
+                let router_pid = announcement
+                    .router_pid
+                    .filter(|router_pid| is_process_running(*router_pid, ROUTER_EXECUTABLE));
+                let state = if is_main_process_running(&announcement) {
+                    DevSessionState::Running
+                } else if router_pid.is_some() {
+                    DevSessionState::Orphaned
+                } else {
+                    DevSessionState::Stale
+                };
+                DevSessionProcess {
+                    session_name: announcement.session_name,
+                    router_url: announcement.router_url,
+                    pid: announcement.pid,
+                    router_pid,
+                    state,
+                }
+            })
+            .collect();
+        Ok(RoverOutput::DevSessionProcesses(DevSessionProcesses {
+            sessions,
+        }))
+    }
+}
+
+/// Asks the main `rover dev` process of a session to shut down, returning whether it stopped within 10 seconds
+fn stop_session(ipc_socket_addr: &str) -> RoverResult<bool> {
+    let messenger = FollowerMessenger::from_attached_session(ipc_socket_addr);
+    messenger.version_check()?;
+    // the main process exits without responding, so the session is stopped once its socket is gone
+    let _ = messenger.shutdown_session();
+    for _ in 0..40 {
+        if !is_session_running(ipc_socket_addr) {
+            return Ok(true);
+        }
+        std::thread::sleep(Duration::from_millis(250));
+    }
+    Ok(false)
+}
+
+/// Stops every session with a session file, shutting down the ones that respond and
+/// stopping the processes of the ones that crashed or are stuck
+fn kill_all_sessions(override_install_path: Option<Utf8PathBuf>) -> RoverResult<RoverOutput> {
+    let sessions = SessionFile::read_all(&get_sessions_dir(override_install_path)?);
+    if sessions.is_empty() {
+        logger::info(
+            &DevLogSource::Rover,
+            "There are no `rover dev` sessions running on this machine.",
+        );
+        return Ok(RoverOutput::EmptySuccess);
+    }
+    let (mut stopped, mut cleaned_up) = (0, 0);
+    for (session_file, announcement) in sessions {
+        // a session that shuts down stops its own router and removes its own files
+        if is_session_running(&announcement.ipc_socket)
+            && stop_session(&announcement.ipc_socket).unwrap_or(false)
+        {
+            stopped += 1;
+            continue;
+        }
+        if is_main_process_running(&announcement) {
+            kill_process(announcement.pid);
+        }
+        session_file.clean_up(&announcement);
+        cleaned_up += 1;
+    }
+    logger::info(&DevLogSource::Rover, format!("{}stopped {} running `rover dev` {} and cleaned up after {} that crashed or didn't respond",
+        Emoji::Stop,
+        stopped,
+        match stopped {
+            1 => "session",
+            _ => "sessions",
+        },
+        cleaned_up));
+    Ok(RoverOutput::EmptySuccess)
+}
+
+/// Whether the main `rover dev` process of a session is still running, which this process never is
+fn is_main_process_running(announcement: &SessionAnnouncement) -> bool {
+    announcement.pid != std::process::id() && is_process_running(announcement.pid, ROVER_EXECUTABLE)
+}
@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context};
+use apollo_federation_types::config::{SchemaSource, SubgraphConfig};
+use camino::Utf8Path;
+use reqwest::Url;
+use rover_std::Fs;
+use serde_yaml::Value;
+
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// The label that marks a Docker Compose service as a subgraph, and names it
+const NAME_LABEL: &str = "rover.subgraph.name";
+
+/// The label that sets the URL of a subgraph, instead of working it out from the service's ports
+const URL_LABEL: &str = "rover.subgraph.url";
+
+/// The label that picks which of a service's container ports the subgraph listens on
+const PORT_LABEL: &str = "rover.subgraph.port";
+
+/// The label that sets the path the subgraph is served at, which defaults to `/`
+const PATH_LABEL: &str = "rover.subgraph.path";
+
+/// The subgraphs of a Docker Compose file, which are its services with a `rover.subgraph.name` label.
+///
+/// Each one is introspected at the host port its container port is published to, since the
+/// router runs on the host rather than in a container
+pub fn get_compose_subgraphs(
+    compose_path: &Utf8Path,
+) -> RoverResult<BTreeMap<String, SubgraphConfig>> {
+    let contents = Fs::read_file(compose_path)?;
+    parse_compose_subgraphs(&contents, compose_path)
+}
+
+fn parse_compose_subgraphs(
+    contents: &str,
+    compose_path: &Utf8Path,
+) -> RoverResult<BTreeMap<String, SubgraphConfig>> {
+    let compose: Value = serde_yaml::from_str(contents)
+        .with_context(|| format!("{} is not valid YAML.", compose_path))?;
+    let services = compose
+        .get("services")
+        .and_then(|services| services.as_mapping())
+        .ok_or_else(|| {
+            RoverError::new(anyhow!(
+                "{} is not a Docker Compose file, because it has no `services`.",
+                compose_path
+            ))
+        })?;
+
+    let mut subgraphs = BTreeMap::new();
+    for (service_name, service) in services {
+        let service_name = service_name.as_str().unwrap_or_default();
+        let labels = get_labels(service);
+        let subgraph_name = match labels.get(NAME_LABEL) {
+            Some(subgraph_name) => subgraph_name.clone(),
+            None => continue,
+        };
+        let url = match labels.get(URL_LABEL) {
+            Some(url) => url.parse::<Url>().map_err(|_| {
+                RoverError::new(anyhow!(
+                    "The `{}` label of the '{}' service, '{}', is not a valid URL.",
+                    URL_LABEL,
+                    service_name,
+                    url
+                ))
+            })?,
+            None => get_published_url(service_name, service, &labels)?,
+        };
+        if subgraphs.contains_key(&subgraph_name) {
+            return Err(RoverError::new(anyhow!(
+                "More than one service in {} is labeled as the '{}' subgraph.",
+                compose_path,
+                subgraph_name
+            )));
+        }
+        subgraphs.insert(
+            subgraph_name,
+            SubgraphConfig {
+                routing_url: Some(url.to_string()),
+                schema: SchemaSource::SubgraphIntrospection { subgraph_url: url },
+            },
+        );
+    }
+
+    if subgraphs.is_empty() {
+        let mut err = RoverError::new(anyhow!(
+            "None of the services in {} are labeled as subgraphs.",
+            compose_path
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+            "Add a `{}` label to each service that runs a subgraph.",
+            NAME_LABEL
+        )));
+        return Err(err);
+    }
+    Ok(subgraphs)
+}
+
+/// A service's labels, which Docker Compose accepts as a map or as a list of `key=value` strings
+fn get_labels(service: &Value) -> BTreeMap<String, String> {
+    match service.get("labels") {
+        Some(Value::Mapping(labels)) => labels
+            .iter()
+            .filter_map(|(key, value)| Some((key.as_str()?.to_string(), yaml_to_string(value)?)))
+            .collect(),
+        Some(Value::Sequence(labels)) => labels
+            .iter()
+            .filter_map(|label| label.as_str()?.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+        _ => BTreeMap::new(),
+    }
+}
+
+/// The URL a service is reachable at from the host, from the host port of its subgraph's container port
+fn get_published_url(
+    service_name: &str,
+    service: &Value,
+    labels: &BTreeMap<String, String>,
+) -> RoverResult<Url> {
+    let ports: Vec<PublishedPort> = service
+        .get("ports")
+        .and_then(|ports| ports.as_sequence())
+        .map(|ports| ports.iter().filter_map(PublishedPort::parse).collect())
+        .unwrap_or_default();
+    let port = match labels.get(PORT_LABEL) {
+        Some(container_port) => ports
+            .iter()
+            .find(|port| &port.container_port == container_port),
+        None if ports.len() == 1 => ports.first(),
+        None => None,
+    };
+    let port = match port {
+        Some(PublishedPort {
+            host_port: Some(host_port),
+            host_ip,
+            ..
+        }) => (host_ip.as_deref(), host_port),
+        _ => {
+            let mut err = RoverError::new(anyhow!(
+                "Could not find the host port of the '{}' service's subgraph.",
+                service_name
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                "Publish the subgraph's container port to a fixed host port, e.g. `ports: [\"4001:4000\"]`. If the service publishes more than one port, set the `{}` label to the subgraph's container port, or set the `{}` label to the subgraph's URL.",
+                PORT_LABEL, URL_LABEL
+            )));
+            return Err(err);
+        }
+    };
+    let host = match port.0 {
+        Some(host_ip) if host_ip.contains(':') && host_ip != "::" => format!("[{}]", host_ip),
+        Some(host_ip) if host_ip != "0.0.0.0" && host_ip != "::" => host_ip.to_string(),
+        _ => "localhost".to_string(),
+    };
+    let path = labels.get(PATH_LABEL).map(String::as_str).unwrap_or("/");
+    format!(
+        "http://{}:{}/{}",
+        host,
+        port.1,
+        path.trim_start_matches('/')
+    )
+    .parse()
+    .map_err(|_| {
+        RoverError::new(anyhow!(
+            "The `{}` label of the '{}' service, '{}', is not a valid path.",
+            PATH_LABEL,
+            service_name,
+            path
+        ))
+    })
+}
+
+/// One of the `ports` of a Docker Compose service
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PublishedPort {
+    host_ip: Option<String>,
+    host_port: Option<String>,
+    container_port: String,
+}
+
+impl PublishedPort {
+    /// Parses the short syntax, e.g. `127.0.0.1:4001:4000/tcp`, and the long syntax, e.g. `{ target: 4000, published: 4001 }`
+    fn parse(port: &Value) -> Option<Self> {
+        match port {
+            Value::Mapping(_) => Some(Self {
+                host_ip: port.get("host_ip").and_then(yaml_to_string),
+                host_port: port.get("published").and_then(yaml_to_string),
+                container_port: port.get("target").and_then(yaml_to_string)?,
+            }),
+            _ => {
+                let port = yaml_to_string(port)?;
+                let port = port.split('/').next()?;
+                let mut parts = port.rsplitn(3, ':');
+                Some(Self {
+                    container_port: parts.next()?.to_string(),
+                    host_port: parts.next().map(String::from),
+                    host_ip: parts.next().map(String::from),
+                })
+            }
+        }
+    }
+}
+
+fn yaml_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Number(value) => Some(value.to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_discovers_labeled_services() {
+        let compose = r#"
+services:
+  db:
+    image: postgres
+    ports: ["5432:5432"]
+  products:
+    build: ./products
+    ports:
+      - "4001:4000"
+    labels:
+      rover.subgraph.name: products
+  reviews:
+    build: ./reviews
+    ports:
+      - "9229:9229"
+      - target: 4000
+        published: 4002
+        host_ip: 127.0.0.1
+    labels:
+      - rover.subgraph.name=reviews
+      - rover.subgraph.port=4000
+      - rover.subgraph.path=/graphql
+"#;
+        let subgraphs =
+            parse_compose_subgraphs(compose, Utf8Path::new("docker-compose.yaml")).unwrap();
+        assert_eq!(subgraphs.len(), 2);
+        assert_eq!(
+            subgraphs["products"].routing_url.as_deref(),
+            Some("http://localhost:4001/")
+        );
+        assert_eq!(
+            subgraphs["reviews"].routing_url.as_deref(),
+            Some("http://127.0.0.1:4002/graphql")
+        );
+
+        let compose = r#"
+services:
+  products:
+    ports: ["4000", "9229:9229"]
+    labels:
+      rover.subgraph.name: products
+"#;
+        assert!(parse_compose_subgraphs(compose, Utf8Path::new("docker-compose.yaml")).is_err());
+    }
+}
@@ -0,0 +1,276 @@
+use std::{
+    fmt::Write as _,
+    net::{SocketAddr, TcpListener, TcpStream},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use reqwest::{blocking::Client, Url};
+use rover_std::Emoji;
+
+use crate::{
+    command::dev::{
+        http::{self, HttpRequest, HttpResponse},
+        logger::{self, DevLogSource},
+        protocol::FollowerMessenger,
+        DevSessionStatus,
+    },
+    RoverError, RoverErrorSuggestion, RoverResult,
+};
+
+/// How long `/metrics` waits on each subgraph and on the router before reporting it as unreachable
+const METRICS_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A local HTTP endpoint that reports the state of a `rover dev` session, for tools
+/// that wait on the session or restart it when it stops composing, and for dashboards
+/// that scrape its Prometheus metrics
+#[derive(Debug)]
+pub struct HealthEndpoint {
+    listener: TcpListener,
+    ipc_socket_addr: String,
+    router_metrics_url: Option<String>,
+}
+
+impl HealthEndpoint {
+    /// Binds the endpoint, which only serves requests once it's started
+    pub fn new(health_address: SocketAddr, ipc_socket_addr: &str) -> RoverResult<Self> {
+        let listener = TcpListener::bind(health_address).map_err(|_| {
+            let mut err = RoverError::new(anyhow!(
+                "You cannot serve the health endpoint on '{}' because that address is already in use by another process on this machine.",
+                &health_address
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Try setting a different port with the `--health-port` argument.".to_string(),
+            ));
+            err
+        })?;
+        Ok(Self {
+            listener,
+            ipc_socket_addr: ipc_socket_addr.to_string(),
+            router_metrics_url: None,
+        })
+    }
+
+    /// Passes the router's own metrics along with the session's metrics at `/metrics`
+    pub fn with_router_metrics_url(mut self, router_metrics_url: Option<String>) -> Self {
+        self.router_metrics_url = router_metrics_url;
+        self
+    }
+
+    /// Serves requests on a thread of its own, asking the main `rover dev` process
+    /// for the state of the session on each one
+    pub fn start(self) -> RoverResult<()> {
+        let health_address = self.listener.local_addr()?;
+        logger::info(&DevLogSource::Rover, format!("{}serving the session's health endpoint at http://{}/health and its metrics at http://{}/metrics",
+            Emoji::Listen,
+            health_address,
+            health_address));
+        let messenger = FollowerMessenger::from_attached_session(&self.ipc_socket_addr);
+        let router_metrics_url = self.router_metrics_url;
+        http::serve(self.listener, "health endpoint", move |request| {
+            Ok(respond(&request, &messenger, router_metrics_url.as_deref()))
+        });
+        Ok(())
+    }
+}
+
+fn respond(
+    request: &HttpRequest,
+    messenger: &FollowerMessenger,
+    router_metrics_url: Option<&str>,
+) -> HttpResponse {
+    match get_path(&request.request_line) {
+        Some("/health") => match messenger.session_status() {
+            Ok(status) if status.is_healthy() => HttpResponse::json("200 OK", &status.get_json()),
+            Ok(status) => HttpResponse::json("503 Service Unavailable", &status.get_json()),
+            Err(e) => HttpResponse::json(
+                "503 Service Unavailable",
+                &serde_json::json!({ "healthy": false, "error": e.to_string() }),
+            ),
+        },
+        Some("/metrics") => match messenger.session_status() {
+            Ok(status) => {
+                let subgraphs_up: Vec<bool> = status
+                    .subgraphs
+                    .iter()
+                    .map(|subgraph| is_reachable(&subgraph.url))
+                    .collect();
+                let mut metrics = get_metrics(&status, &subgraphs_up);
+                if let Some(router_metrics) = router_metrics_url.and_then(get_router_metrics) {
+                    metrics.push_str(&router_metrics);
+                }
+                HttpResponse::new("200 OK", "text/plain; version=0.0.4", metrics)
+            }
+            Err(e) => HttpResponse::json(
+                "503 Service Unavailable",
+                &serde_json::json!({ "error": e.to_string() }),
+            ),
+        },
+        _ => HttpResponse::json(
+            "404 Not Found",
+            &serde_json::json!({ "error": "not found" }),
+        ),
+    }
+}
+
+/// The session's metrics in the Prometheus text format, given whether each of its subgraphs is reachable
+fn get_metrics(status: &DevSessionStatus, subgraphs_up: &[bool]) -> String {
+    let mut metrics = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, samples: Vec<(String, u64)>| {
+        let _ = writeln!(metrics, "# HELP {} {}", name, help);
+        let _ = writeln!(metrics, "# TYPE {} {}", name, kind);
+        for (labels, value) in samples {
+            let _ = writeln!(metrics, "{}{} {}", name, labels, value);
+        }
+    };
+    metric(
+        "rover_dev_uptime_seconds",
+        "gauge",
+        "How long the rover dev session has been running.",
+        vec![(String::new(), status.uptime_seconds)],
+    );
+    metric(
+        "rover_dev_compositions_total",
+        "counter",
+        "How many times the session has composed, including the compositions that failed.",
+        vec![(String::new(), status.compositions)],
+    );
+    metric(
+        "rover_dev_composition_failures_total",
+        "counter",
+        "How many of the session's compositions failed.",
+        vec![(String::new(), status.composition_failures)],
+    );
+    metric(
+        "rover_dev_healthy",
+        "gauge",
+        "Whether the latest composition succeeded.",
+        vec![(String::new(), u64::from(status.is_healthy()))],
+    );
+    metric(
+        "rover_dev_subgraphs",
+        "gauge",
+        "How many subgraphs are in the session.",
+        vec![(String::new(), status.subgraphs.len() as u64)],
+    );
+    metric(
+        "rover_dev_subgraph_up",
+        "gauge",
+        "Whether a subgraph in the session accepts connections.",
+        status
+            .subgraphs
+            .iter()
+            .zip(subgraphs_up)
+            .map(|(subgraph, up)| {
+                (
+                    format!(
+                        "{{subgraph=\"{}\",url=\"{}\"}}",
+                        escape_label(&subgraph.name),
+                        escape_label(subgraph.url.as_str())
+                    ),
+                    u64::from(*up),
+                )
+            })
+            .collect(),
+    );
+    metrics
+}
+
+/// Escapes a label value, as the Prometheus text format requires
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Whether anything accepts connections at a subgraph's host and port
+fn is_reachable(url: &Url) -> bool {
+    url.socket_addrs(|| None)
+        .map(|socket_addrs| {
+            socket_addrs
+                .iter()
+                .any(|socket_addr| TcpStream::connect_timeout(socket_addr, METRICS_TIMEOUT).is_ok())
+        })
+        .unwrap_or(false)
+}
+
+/// The router's own metrics, i.e. its request counts, or `None` while the router isn't running
+fn get_router_metrics(router_metrics_url: &str) -> Option<String> {
+    let response = Client::new()
+        .get(router_metrics_url)
+        .timeout(METRICS_TIMEOUT)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text());
+    match response {
+        Ok(router_metrics) => Some(router_metrics),
+        Err(e) => {
+            tracing::debug!("could not fetch the router's metrics: {}", e);
+            None
+        }
+    }
+}
+
+/// The path of a `GET` request, without its query string
+fn get_path(request_line: &str) -> Option<&str> {
+    let mut parts = request_line.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => target.split('?').next(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_only_serves_get_requests_for_health() {
+        assert_eq!(get_path("GET /health HTTP/1.1\r\n"), Some("/health"));
+        assert_eq!(
+            get_path("GET /health?verbose=1 HTTP/1.1\r\n"),
+            Some("/health")
+        );
+        assert_eq!(get_path("POST /health HTTP/1.1\r\n"), None);
+        assert_eq!(get_path(""), None);
+    }
+
+    #[test]
+    fn it_reports_metrics_in_the_prometheus_format() {
+        let status = DevSessionStatus {
+            version: "0.11.1".to_string(),
+            session_name: None,
+            router_address: "127.0.0.1:3000".parse().unwrap(),
+            router_tls: false,
+            started_at: chrono::Utc::now(),
+            uptime_seconds: 42,
+            subgraphs: vec![
+                crate::command::dev::DevSessionSubgraph {
+                    name: "products".to_string(),
+                    url: "http://localhost:4001".parse().unwrap(),
+                },
+                crate::command::dev::DevSessionSubgraph {
+                    name: "reviews".to_string(),
+                    url: "http://localhost:4002".parse().unwrap(),
+                },
+            ],
+            last_composition: None,
+            compositions: 3,
+            composition_failures: 1,
+        };
+        let metrics = get_metrics(&status, &[true, false]);
+        assert!(metrics.contains(
+            "# TYPE rover_dev_compositions_total counter\nrover_dev_compositions_total 3\n"
+        ));
+        assert!(metrics.contains("rover_dev_composition_failures_total 1\n"));
+        assert!(metrics.contains("rover_dev_healthy 0\n"));
+        assert!(metrics.contains(
+            "rover_dev_subgraph_up{subgraph=\"products\",url=\"http://localhost:4001/\"} 1\n"
+        ));
+        assert!(metrics.contains(
+            "rover_dev_subgraph_up{subgraph=\"reviews\",url=\"http://localhost:4002/\"} 0\n"
+        ));
+        assert_eq!(escape_label("a\"b"), "a\\\"b");
+    }
+}
@@ -0,0 +1,204 @@
+use anyhow::Context;
+use camino::Utf8Path;
+use rover_std::Fs;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{
+    command::dev::{
+        do_dev::log_err_and_continue,
+        logger::{self, DevLogSource},
+        protocol::CompositionResult,
+        SupergraphOpts,
+    },
+    utils::hooks,
+    RoverError, RoverResult,
+};
+
+/// [`DevHooks`] are the commands a user configured to run
+/// every time the main `rover dev` process recomposes the supergraph
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize)]
+pub struct DevHooks {
+    /// commands to run whenever composition succeeds with a new supergraph schema
+    #[serde(default)]
+    on_composition_success: Vec<String>,
+
+    /// commands to run whenever composition fails
+    #[serde(default)]
+    on_composition_failure: Vec<String>,
+}
+
+/// The layout of the file passed to `--hooks`
+#[derive(Debug, Deserialize)]
+struct DevHooksConfig {
+    #[serde(default)]
+    hooks: DevHooks,
+}
+
+impl TryFrom<&SupergraphOpts> for DevHooks {
+    type Error = RoverError;
+    fn try_from(value: &SupergraphOpts) -> Result<Self, Self::Error> {
+        let hooks = match &value.hooks_path {
+            Some(hooks_path) => Self::from_file(hooks_path)?,
+            None => Self::default(),
+        };
+        Ok(hooks.with_on_recompose(&value.on_recompose))
+    }
+}
+
+impl DevHooks {
+    /// Reads the hooks from the `hooks` section of a YAML file
+    pub fn from_file(hooks_path: &Utf8Path) -> RoverResult<Self> {
+        let contents = Fs::read_file(hooks_path)?;
+        Ok(Self::from_yaml(&contents)
+            .with_context(|| format!("{} is not a valid hooks file.", hooks_path))?)
+    }
+
+    /// Adds the `--on-recompose` commands, which run after the `on_composition_success` commands of the hooks file
+    fn with_on_recompose(mut self, on_recompose: &[String]) -> Self {
+        self.on_composition_success
+            .extend(on_recompose.iter().cloned());
+        self
+    }
+
+    fn from_yaml(contents: &str) -> serde_yaml::Result<Self> {
+        let config: DevHooksConfig = serde_yaml::from_str(contents)?;
+        Ok(config.hooks)
+    }
+
+    /// Runs the hooks that match a composition result on a thread of their own, so a slow hook
+    /// doesn't hold up the session's messages or router reloads, doing nothing if the
+    /// supergraph schema did not change
+    pub fn run(&self, composition_result: &CompositionResult, supergraph_schema_path: &Utf8Path) {
+        let (commands, status) = match composition_result {
+            Ok(Some(_)) => (&self.on_composition_success, "success"),
+            Err(_) => (&self.on_composition_failure, "failure"),
+            Ok(None) => return,
+        };
+        if commands.is_empty() {
+            return;
+        }
+
+        let payload = get_payload(composition_result, supergraph_schema_path).to_string();
+        let commands = commands.clone();
+        let supergraph_schema_path = supergraph_schema_path.to_path_buf();
+        std::thread::spawn(move || {
+            for command in &commands {
+                let _ = run_hook(command, status, &supergraph_schema_path, &payload)
+                    .map_err(log_err_and_continue);
+            }
+        });
+    }
+}
+
+/// The JSON each hook receives on stdin
+fn get_payload(composition_result: &CompositionResult, supergraph_schema_path: &Utf8Path) -> Value {
+    match composition_result {
+        Ok(Some(composition_output)) => json!({
+            "success": true,
+            "supergraph_path": supergraph_schema_path.as_str(),
+            "supergraph_sdl": composition_output.supergraph_sdl,
+            "hints": composition_output.hints,
+            "federation_version": composition_output.federation_version,
+        }),
+        Err(composition_err) => json!({
+            "success": false,
+            "error": composition_err,
+        }),
+        Ok(None) => Value::Null,
+    }
+}
+
+fn run_hook(
+    command: &str,
+    status: &str,
+    supergraph_schema_path: &Utf8Path,
+    payload: &str,
+) -> RoverResult<()> {
+    let mut env = vec![("ROVER_DEV_COMPOSITION_STATUS", status.to_string())];
+    // the supergraph schema is removed when composition fails
+    if status == "success" {
+        env.push((
+            "ROVER_DEV_SUPERGRAPH_PATH",
+            supergraph_schema_path.to_string(),
+        ));
+    }
+    hooks::run_hook(command, "composition hook", &env, Some(payload)).map_err(|e| {
+        logger::warn(
+            &DevLogSource::Rover,
+            format!("the `{}` composition hook failed", command),
+        );
+        e
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reads_hooks_from_yaml() {
+        let hooks = DevHooks::from_yaml(
+            r#"
+hooks:
+  on_composition_success:
+    - npm run codegen
+    - touch .reload
+  on_composition_failure:
+    - printf '\a'
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            hooks,
+            DevHooks {
+                on_composition_success: vec![
+                    "npm run codegen".to_string(),
+                    "touch .reload".to_string()
+                ],
+                on_composition_failure: vec!["printf '\\a'".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn it_defaults_missing_hooks_to_empty() {
+        assert_eq!(
+            DevHooks::from_yaml("hooks:\n  on_composition_failure: []\n").unwrap(),
+            DevHooks::default()
+        );
+        assert_eq!(DevHooks::from_yaml("{}").unwrap(), DevHooks::default());
+    }
+
+    #[test]
+    fn it_runs_on_recompose_commands_after_the_hooks_file() {
+        let hooks = DevHooks::from_yaml("hooks:\n  on_composition_success:\n    - touch .reload\n")
+            .unwrap()
+            .with_on_recompose(&["npm run codegen".to_string()]);
+        assert_eq!(
+            hooks.on_composition_success,
+            vec!["touch .reload".to_string(), "npm run codegen".to_string()]
+        );
+        assert!(hooks.on_composition_failure.is_empty());
+    }
+
+    #[test]
+    fn it_errs_on_invalid_hooks() {
+        assert!(
+            DevHooks::from_yaml("hooks:\n  on_composition_success: npm run codegen\n").is_err()
+        );
+    }
+
+    #[test]
+    fn it_describes_failed_compositions() {
+        let payload = get_payload(
+            &Err("could not compose".to_string()),
+            Utf8Path::new("supergraph.graphql"),
+        );
+        assert_eq!(
+            payload,
+            json!({ "success": false, "error": "could not compose" })
+        );
+    }
+}
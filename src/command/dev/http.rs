@@ -0,0 +1,186 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::Arc,
+};
+
+use anyhow::Context;
+use serde_json::{json, Value};
+
+use crate::{command::dev::do_dev::log_err_and_continue, RoverResult};
+
+/// The largest request body the HTTP servers of a `rover dev` session read, which is far larger
+/// than any GraphQL request the router sends, so a bad request can't use up the session's memory
+const MAX_REQUEST_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// How many requests each HTTP server of a `rover dev` session handles at once.
+/// The rest wait for one of them to finish
+const WORKERS_PER_SERVER: usize = 8;
+
+/// A request to one of the HTTP servers of a `rover dev` session, i.e. a `--mock` subgraph
+/// or the health endpoint. The router's requests are POSTs with a Content-Length, so that's all this reads
+#[derive(Debug)]
+pub struct HttpRequest {
+    pub request_line: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Reads a request from `stream`, or None if its body is larger than [`MAX_REQUEST_BODY_BYTES`],
+    /// in which case the body isn't read at all
+    pub fn read(stream: &TcpStream, server_name: &str) -> RoverResult<Option<Self>> {
+        let context = || format!("could not read the request to the {}", server_name);
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).with_context(context)?;
+
+        let mut headers = Vec::new();
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).with_context(context)?;
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or_default();
+                }
+                headers.push((name.trim().to_string(), value.trim().to_string()));
+            }
+        }
+        if content_length > MAX_REQUEST_BODY_BYTES {
+            return Ok(None);
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).with_context(context)?;
+        Ok(Some(Self {
+            request_line: request_line.trim_end().to_string(),
+            headers,
+            body,
+        }))
+    }
+
+    pub fn is_post(&self) -> bool {
+        self.request_line.starts_with("POST ")
+    }
+}
+
+/// A response to an [`HttpRequest`]
+#[derive(Debug)]
+pub struct HttpResponse {
+    pub status_line: String,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn new(status_line: impl Into<String>, content_type: &'static str, body: String) -> Self {
+        Self {
+            status_line: status_line.into(),
+            content_type,
+            body,
+        }
+    }
+
+    pub fn json(status_line: impl Into<String>, body: &Value) -> Self {
+        Self::new(status_line, "application/json", body.to_string())
+    }
+
+    /// A JSON response with a GraphQL error, which is how every server of a session reports
+    /// a request it can't handle
+    pub fn graphql_error(status_line: impl Into<String>, message: impl AsRef<str>) -> Self {
+        Self::json(
+            status_line,
+            &json!({ "errors": [{ "message": message.as_ref() }] }),
+        )
+    }
+
+    /// The response to a request whose body is larger than [`MAX_REQUEST_BODY_BYTES`]
+    pub fn too_large(server_name: &str) -> Self {
+        Self::graphql_error(
+            "413 Payload Too Large",
+            format!(
+                "The request body is larger than the {} bytes the {} accepts",
+                MAX_REQUEST_BODY_BYTES, server_name
+            ),
+        )
+    }
+
+    /// Writes the response and closes the connection
+    pub fn write(&self, mut stream: TcpStream, server_name: &str) -> RoverResult<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status_line,
+            self.content_type,
+            self.body.len(),
+            self.body
+        )
+        .with_context(|| format!("could not respond to the request to the {}", server_name))?;
+        Ok(())
+    }
+}
+
+/// Answers the requests to `listener` with `handle` on threads of their own, handling at most
+/// a few requests at once. Requests with a body larger than [`MAX_REQUEST_BODY_BYTES`] get a
+/// `413 Payload Too Large` without being handled
+pub fn serve<F>(listener: TcpListener, server_name: &'static str, handle: F)
+where
+    F: Fn(HttpRequest) -> RoverResult<HttpResponse> + Send + Sync + 'static,
+{
+    let listener = Arc::new(listener);
+    let handle = Arc::new(handle);
+    for _ in 0..WORKERS_PER_SERVER {
+        let listener = listener.clone();
+        let handle = handle.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = respond(stream, server_name, handle.as_ref()).map_err(log_err_and_continue);
+            }
+        });
+    }
+}
+
+fn respond(
+    stream: TcpStream,
+    server_name: &str,
+    handle: &impl Fn(HttpRequest) -> RoverResult<HttpResponse>,
+) -> RoverResult<()> {
+    let response = match HttpRequest::read(&stream, server_name)? {
+        Some(request) => handle(request)?,
+        None => HttpResponse::too_large(server_name),
+    };
+    response.write(stream, server_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    #[test]
+    fn it_does_not_read_bodies_that_are_too_large() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        let mut client = TcpStream::connect(address).unwrap();
+        write!(
+            client,
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_REQUEST_BODY_BYTES + 1
+        )
+        .unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        assert!(HttpRequest::read(&stream, "test server").unwrap().is_none());
+
+        let mut client = TcpStream::connect(address).unwrap();
+        write!(client, "POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\n{{}}").unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        let request = HttpRequest::read(&stream, "test server").unwrap().unwrap();
+        assert!(request.is_post());
+        assert_eq!(request.body, b"{}");
+    }
+}
@@ -1,33 +1,46 @@
 use anyhow::anyhow;
 use reqwest::blocking::Client;
-use rover_std::Style;
 
+use crate::command::dev::logger::{self, DevLogSource};
 use crate::command::dev::protocol::{SubgraphSdl, SubgraphUrl};
 use crate::command::graph::Introspect as GraphIntrospect;
 use crate::command::subgraph::Introspect as SubgraphIntrospect;
-use crate::options::IntrospectOpts;
+use crate::options::{DirectiveFilterOpt, IntrospectOpts};
 use crate::{RoverError, RoverErrorSuggestion, RoverResult};
 
 #[derive(Clone, Debug)]
 pub struct UnknownIntrospectRunner {
     endpoint: SubgraphUrl,
     client: Client,
+    headers: Vec<(String, String)>,
 }
 
 impl UnknownIntrospectRunner {
     pub fn new(endpoint: SubgraphUrl, client: Client) -> Self {
-        Self { endpoint, client }
+        Self {
+            endpoint,
+            client,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Sends the `--subgraph-header`s for this subgraph with every introspection request
+    pub fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.headers = headers;
+        self
     }
 
     pub fn run(&self) -> RoverResult<(SubgraphSdl, IntrospectRunnerKind)> {
         let subgraph_runner = SubgraphIntrospectRunner {
             endpoint: self.endpoint.clone(),
             client: self.client.clone(),
+            headers: self.headers.clone(),
         };
 
         let graph_runner = GraphIntrospectRunner {
             endpoint: self.endpoint.clone(),
             client: self.client.clone(),
+            headers: self.headers.clone(),
         };
 
         // we _could_ run these in parallel
@@ -46,8 +59,7 @@ impl UnknownIntrospectRunner {
                 Ok((s, IntrospectRunnerKind::Subgraph(subgraph_runner)))
             }
             (Err(_), Ok(s)) => {
-                let warn_prefix = Style::WarningPrefix.paint("WARN:");
-                eprintln!("{} could not fetch federated SDL, using introspection schema without directives. you should convert this monograph to a federated subgraph. see https://www.apollographql.com/docs/federation/subgraphs/ for more information.", warn_prefix);
+                logger::warn(&DevLogSource::Rover, "could not fetch federated SDL, using introspection schema without directives. you should convert this monograph to a federated subgraph. see https://www.apollographql.com/docs/federation/subgraphs/ for more information.");
                 Ok((s, IntrospectRunnerKind::Graph(graph_runner)))
             }
             (Err(se), Err(ge)) => {
@@ -89,6 +101,7 @@ impl IntrospectRunnerKind {
 pub struct SubgraphIntrospectRunner {
     endpoint: SubgraphUrl,
     client: Client,
+    headers: Vec<(String, String)>,
 }
 
 impl SubgraphIntrospectRunner {
@@ -100,9 +113,12 @@ impl SubgraphIntrospectRunner {
         SubgraphIntrospect {
             opts: IntrospectOpts {
                 endpoint: self.endpoint.clone(),
-                headers: None,
+                headers: Some(self.headers.clone()),
                 watch: false,
+                diff: false,
+                interval: 1,
             },
+            directives: DirectiveFilterOpt::default(),
         }
         .exec(&self.client, false)
     }
@@ -112,6 +128,7 @@ impl SubgraphIntrospectRunner {
 pub struct GraphIntrospectRunner {
     endpoint: SubgraphUrl,
     client: Client,
+    headers: Vec<(String, String)>,
 }
 
 impl GraphIntrospectRunner {
@@ -123,9 +140,12 @@ impl GraphIntrospectRunner {
         GraphIntrospect {
             opts: IntrospectOpts {
                 endpoint: self.endpoint.clone(),
-                headers: None,
+                headers: Some(self.headers.clone()),
                 watch: false,
+                diff: false,
+                interval: 1,
             },
+            directives: DirectiveFilterOpt::default(),
         }
         .exec(&self.client, false)
     }
@@ -0,0 +1,144 @@
+use std::sync::RwLock;
+
+use chrono::Utc;
+use rover_std::Style;
+use serde_json::json;
+use timber::Level;
+
+use crate::{
+    command::dev::{DevLogFilter, DevLogFormat, ROUTER_LOG_SOURCE, ROVER_LOG_SOURCE},
+    RoverError,
+};
+
+lazy_static::lazy_static! {
+    static ref DEV_LOGGER: RwLock<DevLogger> = RwLock::new(DevLogger::default());
+}
+
+#[derive(Debug, Default)]
+struct DevLogger {
+    format: DevLogFormat,
+    filter: DevLogFilter,
+}
+
+/// Sets how every log of this `rover dev` process is written, from `--log-format` and `--log-filter`
+pub fn init_dev_logger(format: DevLogFormat, filter: DevLogFilter) {
+    if let Ok(mut dev_logger) = DEV_LOGGER.write() {
+        *dev_logger = DevLogger { format, filter };
+    }
+}
+
+/// Where a log of a `rover dev` session comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DevLogSource {
+    Rover,
+    Router,
+    Subgraph(String),
+}
+
+impl DevLogSource {
+    pub fn get_name(&self) -> &str {
+        match self {
+            DevLogSource::Rover => ROVER_LOG_SOURCE,
+            DevLogSource::Router => ROUTER_LOG_SOURCE,
+            DevLogSource::Subgraph(subgraph_name) => subgraph_name,
+        }
+    }
+
+    /// The prefix of this source's logs, in the same color in every `rover dev` process of the session
+    fn get_prefix(&self) -> String {
+        let name = self.get_name();
+        let index = name.bytes().map(usize::from).sum();
+        Style::LogSource(index).paint(format!("[{}]", name))
+    }
+}
+
+/// Writes an info log from `source`
+pub fn info(source: &DevLogSource, message: impl AsRef<str>) {
+    log(source, Level::INFO, message)
+}
+
+/// Writes a warning from `source`
+pub fn warn(source: &DevLogSource, message: impl AsRef<str>) {
+    log(source, Level::WARN, message)
+}
+
+/// Writes a log from `source`, unless `--log-filter` leaves out its level
+pub fn log(source: &DevLogSource, level: Level, message: impl AsRef<str>) {
+    log_with_fields(source, level, message, json!({}))
+}
+
+/// Writes a log from `source` like [`log`], with `fields` added to its JSON log,
+/// for what tools reading `--log-format json` need to find without parsing the message
+pub fn log_with_fields(
+    source: &DevLogSource,
+    level: Level,
+    message: impl AsRef<str>,
+    mut fields: serde_json::Value,
+) {
+    let message = message.as_ref();
+    let text = match level {
+        Level::WARN => format!("{} {}", Style::WarningPrefix.paint("WARN:"), message),
+        Level::ERROR => format!("{} {}", Style::ErrorPrefix.paint("ERROR:"), message),
+        _ => message.to_string(),
+    };
+    fields["message"] = json!(strip(message));
+    write(source, level, &text, fields);
+}
+
+/// Writes an error from `source`, with its suggestion
+pub fn log_error(source: &DevLogSource, error: &RoverError) {
+    let mut fields = json!({ "message": strip(&error.message()) });
+    if let Some(code) = error.code() {
+        fields["code"] = json!(code.to_string());
+    }
+    if let Some(suggestion) = error.suggestion() {
+        fields["suggestion"] = json!(strip(&suggestion.to_string()));
+    }
+    write(source, Level::ERROR, &error.to_string(), fields);
+}
+
+/// Writes an error from `source` that was already formatted like a [`RoverError`],
+/// i.e. one sent by the main `rover dev` process to an attached one
+pub fn log_formatted_error(source: &DevLogSource, error: &str) {
+    write(
+        source,
+        Level::ERROR,
+        error,
+        json!({ "message": strip(error) }),
+    );
+}
+
+fn write(source: &DevLogSource, level: Level, text: &str, mut fields: serde_json::Value) {
+    let format = match DEV_LOGGER.read() {
+        Ok(dev_logger) if dev_logger.filter.is_enabled(source.get_name(), level) => {
+            dev_logger.format
+        }
+        Ok(_) => return,
+        Err(_) => DevLogFormat::default(),
+    };
+    match format {
+        DevLogFormat::Text => {
+            let prefix = source.get_prefix();
+            // leading newlines, i.e. the one after a `^C`, stay ahead of the prefix
+            let lines = text.trim_start_matches('\n');
+            eprint!("{}", "\n".repeat(text.len() - lines.len()));
+            for line in lines.trim_end().lines() {
+                eprintln!("{} {}", prefix, line);
+            }
+        }
+        DevLogFormat::Json => {
+            fields["timestamp"] = json!(Utc::now().to_rfc3339());
+            fields["level"] = json!(level.to_string());
+            fields["source"] = json!(source.get_name());
+            eprintln!("{}", fields);
+        }
+    }
+}
+
+/// A message without the colors or emoji of its text log, for log viewers
+fn strip(message: &str) -> String {
+    console::strip_ansi_codes(message)
+        .trim_start_matches(|c: char| !c.is_ascii())
+        .trim()
+        .to_string()
+}
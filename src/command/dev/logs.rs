@@ -0,0 +1,107 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use timber::Level;
+
+/// The name of the source of the logs a `rover dev` process writes itself
+pub const ROVER_LOG_SOURCE: &str = "rover";
+
+/// The name of the source of the logs the router writes
+pub const ROUTER_LOG_SOURCE: &str = "router";
+
+/// How a `rover dev` process writes its logs, from `--log-format`
+#[derive(ValueEnum, Debug, Clone, Copy, Default, Serialize, Eq, PartialEq)]
+pub enum DevLogFormat {
+    /// One line per log, prefixed with the colored name of its source
+    #[default]
+    Text,
+
+    /// One JSON object per line, with a `timestamp`, `level`, `source`, and `message`
+    Json,
+}
+
+/// Which logs a `rover dev` process writes, from `--log-filter`,
+/// i.e. `warn,router=info,products=debug`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DevLogFilter {
+    default_level: Option<Level>,
+    source_levels: BTreeMap<String, Level>,
+}
+
+impl DevLogFilter {
+    /// The most verbose level of logs to write from `source`.
+    ///
+    /// Without a level for it in the filter, the router only writes warnings and errors,
+    /// which keeps its own request logs out of the way, and every other source writes info logs
+    pub fn get_level(&self, source: &str) -> Level {
+        match (self.source_levels.get(source), self.default_level) {
+            (Some(level), _) => *level,
+            (None, Some(level)) => level,
+            (None, None) if source == ROUTER_LOG_SOURCE => Level::WARN,
+            (None, None) => Level::INFO,
+        }
+    }
+
+    /// Whether to write a log from `source` at `level`
+    pub fn is_enabled(&self, source: &str, level: Level) -> bool {
+        level <= self.get_level(source)
+    }
+}
+
+impl FromStr for DevLogFilter {
+    type Err = String;
+
+    fn from_str(filter: &str) -> Result<Self, Self::Err> {
+        let parse_level = |level: &str| {
+            Level::from_str(level.trim()).map_err(|_| {
+                format!(
+                    "'{}' is not a log level. Pass 'error', 'warn', 'info', 'debug', or 'trace'.",
+                    level.trim()
+                )
+            })
+        };
+        let mut dev_log_filter = Self::default();
+        for directive in filter.split(',').filter(|d| !d.trim().is_empty()) {
+            match directive.split_once('=') {
+                Some((source, level)) if !source.trim().is_empty() => {
+                    dev_log_filter
+                        .source_levels
+                        .insert(source.trim().to_string(), parse_level(level)?);
+                }
+                Some(_) => {
+                    return Err(format!(
+                        "'{}' has no source before its '='. Pass a level, i.e. 'debug', or a source and a level, i.e. 'router=debug'.",
+                        directive
+                    ))
+                }
+                None => dev_log_filter.default_level = Some(parse_level(directive)?),
+            }
+        }
+        Ok(dev_log_filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_log_filters() {
+        let filter = DevLogFilter::from_str("").unwrap();
+        assert_eq!(filter.get_level(ROVER_LOG_SOURCE), Level::INFO);
+        assert_eq!(filter.get_level(ROUTER_LOG_SOURCE), Level::WARN);
+        assert!(!filter.is_enabled(ROUTER_LOG_SOURCE, Level::INFO));
+
+        let filter = DevLogFilter::from_str("error, router=info,products=DEBUG").unwrap();
+        assert_eq!(filter.get_level(ROVER_LOG_SOURCE), Level::ERROR);
+        assert_eq!(filter.get_level(ROUTER_LOG_SOURCE), Level::INFO);
+        assert_eq!(filter.get_level("products"), Level::DEBUG);
+        assert_eq!(filter.get_level("reviews"), Level::ERROR);
+        assert!(filter.is_enabled("products", Level::WARN));
+        assert!(!filter.is_enabled("reviews", Level::WARN));
+
+        assert!(DevLogFilter::from_str("router=loud").is_err());
+        assert!(DevLogFilter::from_str("=info").is_err());
+    }
+}
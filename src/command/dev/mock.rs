@@ -0,0 +1,90 @@
+use std::net::{SocketAddr, TcpListener};
+
+use anyhow::anyhow;
+use camino::{Utf8Path, Utf8PathBuf};
+use reqwest::Url;
+use rover_std::{Emoji, Fs, Style};
+use serde_json::Value;
+
+use crate::{
+    command::dev::{
+        http::{self, HttpRequest, HttpResponse},
+        logger::{self, DevLogSource},
+    },
+    utils::schema_mock::MockSchema,
+    RoverError, RoverErrorSuggestion, RoverResult,
+};
+
+/// A subgraph that `rover dev --mock` serves itself, resolving every operation with
+/// fake values from a schema file instead of running the subgraph's service
+#[derive(Debug)]
+pub struct MockSubgraph {
+    listener: TcpListener,
+    schema_path: Utf8PathBuf,
+}
+
+impl MockSubgraph {
+    /// Binds the mocked subgraph, which only serves requests once it's started
+    pub fn new(schema_path: &Utf8Path, address: SocketAddr) -> RoverResult<Self> {
+        Fs::assert_path_exists(schema_path)?;
+        let listener = TcpListener::bind(address).map_err(|_| {
+            let mut err = RoverError::new(anyhow!(
+                "You cannot serve the mocked subgraph on '{}' because that address is already in use by another process on this machine.",
+                &address
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Pass a `--url` with a different port, or leave `--url` out to serve the mocked subgraph on any free port.".to_string(),
+            ));
+            err
+        })?;
+        Ok(Self {
+            listener,
+            schema_path: schema_path.to_path_buf(),
+        })
+    }
+
+    /// The URL the mocked subgraph is served at
+    pub fn get_url(&self) -> RoverResult<Url> {
+        Ok(format!("http://{}", self.listener.local_addr()?).parse()?)
+    }
+
+    /// Serves the mocked subgraph on a thread of its own.
+    ///
+    /// The schema file is read for each request, so the mocks always match the schema that
+    /// `rover dev` last composed
+    pub fn start(self) -> RoverResult<()> {
+        logger::info(
+            &DevLogSource::Rover,
+            format!(
+                "{}serving a mocked subgraph for {} at {}",
+                Emoji::Listen,
+                Style::Path.paint(&self.schema_path),
+                self.get_url()?
+            ),
+        );
+        let schema_path = self.schema_path;
+        http::serve(self.listener, "mocked subgraph", move |request| {
+            Ok(respond(request, &schema_path))
+        });
+        Ok(())
+    }
+}
+
+fn respond(request: HttpRequest, schema_path: &Utf8Path) -> HttpResponse {
+    if !request.is_post() {
+        return HttpResponse::graphql_error(
+            "405 Method Not Allowed",
+            "Mocked subgraphs only accept POST requests",
+        );
+    }
+    let response = serde_json::from_slice::<Value>(&request.body)
+        .map_err(|e| RoverError::new(anyhow!("The request is not valid JSON: {}", e)))
+        .and_then(|request| {
+            let sdl = Fs::read_file(schema_path)?;
+            Ok(MockSchema::new(&sdl)?.execute(&request))
+        });
+    match response {
+        Ok(response) => HttpResponse::json("200 OK", &response),
+        Err(e) => HttpResponse::graphql_error("400 Bad Request", e.message()),
+    }
+}
@@ -1,6 +1,12 @@
 #[cfg(feature = "composition-js")]
 mod compose;
 
+#[cfg(feature = "composition-js")]
+mod dev_config;
+
+#[cfg(feature = "composition-js")]
+mod hooks;
+
 #[cfg(feature = "composition-js")]
 mod introspect;
 
@@ -13,9 +19,48 @@ mod schema;
 #[cfg(feature = "composition-js")]
 mod protocol;
 
+#[cfg(feature = "composition-js")]
+mod record;
+
+#[cfg(feature = "composition-js")]
+mod mock;
+
 #[cfg(feature = "composition-js")]
 mod netstat;
 
+#[cfg(feature = "composition-js")]
+mod health;
+
+#[cfg(feature = "composition-js")]
+mod http;
+
+// `rover dev install` logs through it too, which doesn't need composition
+mod logger;
+
+mod logs;
+
+mod router_versions;
+
+mod session;
+
+#[cfg(feature = "composition-js")]
+mod session_file;
+
+#[cfg(feature = "composition-js")]
+mod processes;
+
+mod status;
+
+pub use logs::{DevLogFilter, DevLogFormat, ROUTER_LOG_SOURCE, ROVER_LOG_SOURCE};
+pub use session::{DevSessionProcess, DevSessionProcesses, DevSessionState};
+pub use status::{DevCompositionStatus, DevSessionStatus, DevSessionSubgraph};
+
+#[cfg(feature = "composition-js")]
+mod supergraph_config;
+
+#[cfg(feature = "composition-js")]
+mod docker_compose;
+
 #[cfg(feature = "composition-js")]
 mod watcher;
 
@@ -26,18 +71,65 @@ mod do_dev;
 mod no_dev;
 
 use crate::options::{OptionalSubgraphOpts, PluginOpts};
+use crate::utils::parsers::{parse_router_version, parse_session_name};
+use crate::RoverResult;
 
+use anyhow::Context;
+use apollo_federation_types::config::{FederationVersion, RouterVersion};
 use camino::Utf8PathBuf;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use rover_client::shared::GraphRef;
+use semver::Version;
 use serde::Serialize;
 
 #[derive(Debug, Serialize, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Dev {
+    #[clap(subcommand)]
+    pub(crate) command: Option<Command>,
+
     #[clap(flatten)]
     pub(crate) opts: DevOpts,
 }
 
+impl Dev {
+    /// Whether `--offline` was passed, in which case the session doesn't use the network
+    pub fn is_offline(&self) -> bool {
+        self.opts.supergraph_opts.offline.unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Report the state of the `rover dev` session running on this machine: its subgraphs, the result of its latest composition, its router address, and its uptime
+    Status(status::Status),
+
+    /// Add a subgraph to the running `rover dev` session started with a `--session-name`
+    ///
+    /// Unlike running `rover dev` again, this fails instead of starting a new session when there isn't one with that name.
+    Attach(session::Attach),
+
+    /// Stop the running `rover dev` session started with a `--session-name`, along with its router and every process attached to it
+    ///
+    /// With `--all`, stop every session on this machine, and any routers left running by sessions that crashed.
+    Kill(session::Kill),
+
+    /// List the `rover dev` sessions on this machine, including ones whose main process crashed and left their router running
+    Ps(session::Ps),
+
+    /// Install the router and composition plugin ahead of time, i.e. while building a development image or before going offline, so `rover dev` doesn't need to download them
+    ///
+    /// Without `--router-version` or `--federation-version`, this installs the versions `rover dev` runs in the current directory.
+    Install(router_versions::Install),
+
+    /// List the router versions that are installed
+    List(router_versions::List),
+
+    /// Remove all but the newest installed router versions of each major version, like `rover supergraph plugin prune` does for composition plugins
+    Prune(router_versions::Prune),
+}
+
+#[derive(Debug, Serialize, Parser, Clone)]
 pub struct DevOpts {
     #[clap(flatten)]
     pub plugin_opts: PluginOpts,
@@ -51,11 +143,25 @@ pub struct DevOpts {
 
 #[derive(Debug, Parser, Serialize, Clone)]
 pub struct SupergraphOpts {
+    /// The path to a `rover dev` config file to read the session's settings from. Defaults to `rover-dev.yaml` in the current directory, if there is one.
+    ///
+    /// Each key of the file is the name of a `rover dev` flag, with `_` in place of `-`, i.e. `supergraph_port: 3000`, except `subgraphs`, which lists the session's subgraphs like a `--supergraph-config` file, whose `file` schemas are watched for changes. Flags passed on the command line take precedence over the file, i.e. `--open=false` when the file sets `open: true`. `--name`, `--url`, `--schema`, and `--mock` describe the one subgraph of this process, so they can't be set in the file; list the subgraph under `subgraphs` instead.
+    #[arg(long = "dev-config", value_name = "DEV_CONFIG_PATH")]
+    #[serde(skip_serializing)]
+    dev_config_path: Option<Utf8PathBuf>,
+
+    /// The `subgraphs` of the `--dev-config` file
+    #[arg(skip)]
+    #[serde(skip_serializing)]
+    dev_config_subgraphs: Option<serde_yaml::Value>,
+
     /// The port the graph router should listen on.
     ///
     /// If you start multiple `rover dev` processes on the same address and port, they will communicate with each other.
     ///
     /// If you start multiple `rover dev` processes with different addresses and ports, they will not communicate with each other.
+    ///
+    /// Without this or a `supergraph.listen` in the router config, the router listens on port 3000, or on a free port if another process already uses port 3000. Other `rover dev` processes still find the session at port 3000.
     #[arg(long, short = 'p')]
     supergraph_port: Option<u16>,
 
@@ -73,6 +179,174 @@ pub struct SupergraphOpts {
     #[arg(long = "router-config")]
     #[serde(skip_serializing)]
     router_config_path: Option<Utf8PathBuf>,
+
+    /// The version of the router to run. Pass `latest`, or an exact version, i.e. `1.10.0`.
+    ///
+    /// An exact version is only downloaded the first time it's needed, so pinning one keeps every session on the same router and lets later sessions start offline. This takes precedence over the `APOLLO_ROVER_DEV_ROUTER_VERSION` environment variable. Install versions ahead of time with `rover dev install --router-version <VERSION>`.
+    #[arg(long = "router-version", value_name = "VERSION", value_parser = parse_router_version)]
+    #[serde(skip_serializing)]
+    router_version: Option<RouterVersion>,
+
+    /// The path to a YAML file listing commands to run whenever the supergraph recomposes.
+    ///
+    /// Commands under `hooks.on_composition_success` run whenever composition produces a new supergraph schema, and commands under `hooks.on_composition_failure` run whenever composition fails. Each command receives the composition result as JSON on stdin.
+    #[arg(long = "hooks", value_name = "HOOKS_PATH")]
+    #[serde(skip_serializing)]
+    hooks_path: Option<Utf8PathBuf>,
+
+    /// A command to run whenever composition produces a new supergraph schema, i.e. `npm run codegen`. Pass this more than once to run several commands, one after another.
+    ///
+    /// Each command runs with your system shell, with the path of the new supergraph schema in the `ROVER_DEV_SUPERGRAPH_PATH` environment variable, after any `hooks.on_composition_success` commands of the `--hooks` file.
+    #[arg(long = "on-recompose", value_name = "COMMAND")]
+    #[serde(skip_serializing)]
+    on_recompose: Vec<String>,
+
+    /// The path to a supergraph configuration file whose subgraphs this `rover dev` process runs, all at once.
+    ///
+    /// Subgraphs with a `file` schema are watched for changes and subgraphs with a `subgraph_url` are introspected every `--polling-interval`, just like subgraphs passed with `--name` and `--url`. Without `--name` or `--url`, this process only runs the subgraphs of the configuration file.
+    #[arg(long = "supergraph-config", value_name = "SUPERGRAPH_CONFIG_PATH")]
+    #[serde(skip_serializing)]
+    supergraph_config_path: Option<Utf8PathBuf>,
+
+    /// The path to a Docker Compose file whose labeled services this `rover dev` process runs as subgraphs, all at once.
+    ///
+    /// Each service with a `rover.subgraph.name` label is introspected every `--polling-interval` at the host port its container port is published to. Set `rover.subgraph.port` to pick the container port when a service publishes more than one, `rover.subgraph.path` to the path it's served at, or `rover.subgraph.url` to its URL. This doesn't start the containers, so run `docker compose up` too.
+    #[arg(
+        long = "docker-compose",
+        value_name = "COMPOSE_PATH",
+        conflicts_with = "supergraph_config_path"
+    )]
+    #[serde(skip_serializing)]
+    docker_compose_path: Option<Utf8PathBuf>,
+
+    /// The path to keep the latest supergraph schema at, for IDE plugins and codegen tools that watch a schema file.
+    ///
+    /// The file is replaced every time composition produces a new supergraph schema, and keeps the last one when composition fails. Only the main `rover dev` process writes it.
+    #[arg(long = "supergraph-out", value_name = "SUPERGRAPH_OUT_PATH")]
+    #[serde(skip_serializing)]
+    supergraph_out_path: Option<Utf8PathBuf>,
+
+    /// Open Apollo Sandbox in your browser once the supergraph first composes, with the router's URL as its endpoint.
+    ///
+    /// Only the main `rover dev` process opens Sandbox, so later recompositions and attached processes don't open more tabs. Set the `$BROWSER` environment variable to open it with a browser other than your default one.
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    open: Option<bool>,
+
+    /// Run the router and composition plugin already installed on this machine without using the network to check for newer ones, like `rover supergraph compose --offline` does.
+    ///
+    /// Install the versions to run ahead of time with `rover dev install`. Rover doesn't check for its own updates or send anonymous usage data either, so this can't be used with `--remote-subgraphs`.
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL", conflicts_with = "remote_subgraphs")]
+    offline: Option<bool>,
+
+    /// A graph ref (i.e. `my-graph@current`) whose published subgraphs this `rover dev` process adds to the session, alongside the subgraphs you're developing locally.
+    ///
+    /// Every published subgraph's schema is fetched from Apollo Studio once, in a single request, and the router sends its requests to the subgraph's published routing URL. A local subgraph with the same name as a published subgraph takes its place, whether it's already running in the session or added later.
+    #[arg(long = "remote-subgraphs", value_name = "GRAPH_REF")]
+    #[serde(skip_serializing)]
+    remote_subgraphs: Option<GraphRef>,
+
+    /// The directory to record the requests the router sends to each subgraph in, with the subgraphs' responses, for `--replay` to serve later.
+    ///
+    /// Each subgraph's recordings are appended to `<SUBGRAPH_NAME>.jsonl`, one JSON object per line, and its latest schema is kept in `<SUBGRAPH_NAME>.graphql`. Only the main `rover dev` process records.
+    #[arg(long = "record", value_name = "RECORD_DIR")]
+    #[serde(skip_serializing)]
+    record_dir: Option<Utf8PathBuf>,
+
+    /// The directory of a `--record`ed session whose subgraphs this `rover dev` process serves itself, all at once, for reproducible demos and integration tests.
+    ///
+    /// Each request is answered with the latest recorded response to the same query and variables, or with fake values from the subgraph's schema, like a `--mock` subgraph, when it wasn't recorded.
+    #[arg(
+        long = "replay",
+        value_name = "RECORD_DIR",
+        conflicts_with_all = ["record_dir", "supergraph_config_path", "docker_compose_path"]
+    )]
+    #[serde(skip_serializing)]
+    replay_dir: Option<Utf8PathBuf>,
+
+    /// The name of the `rover dev` session this process starts or attaches to.
+    ///
+    /// A named session is found by its name instead of by its router address, so you can run several sessions at once (each with its own `--supergraph-port`), add subgraphs to one with `rover dev attach <SESSION_NAME>`, and stop one with `rover dev kill <SESSION_NAME>`.
+    #[arg(long = "session-name", value_parser = parse_session_name)]
+    #[serde(skip_serializing)]
+    session_name: Option<String>,
+
+    /// The port to serve a health endpoint for the session on, at the same address as the router.
+    ///
+    /// `GET /health` responds with the same JSON as `rover dev status --format json`, with a `200` status while the latest composition succeeded and a `503` status otherwise. `GET /metrics` responds with the session's Prometheus metrics, followed by the router's own metrics. Only the main `rover dev` process serves it.
+    #[arg(long)]
+    health_port: Option<u16>,
+
+    /// Serve the router over HTTPS with a self-signed certificate for `localhost`, which is generated the first time it's needed and reused after that.
+    ///
+    /// Pass `--tls-cert` and `--tls-key` instead to serve the router with a certificate of your own, e.g. one that `mkcert` generated and your browser already trusts.
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    tls: Option<bool>,
+
+    /// The path to a PEM certificate (and any intermediate certificates) to serve the router over HTTPS with.
+    #[arg(long = "tls-cert", value_name = "CERT_PATH", requires = "tls_key_path")]
+    #[serde(skip_serializing)]
+    tls_cert_path: Option<Utf8PathBuf>,
+
+    /// The path to the PEM private key of the `--tls-cert` certificate.
+    #[arg(long = "tls-key", value_name = "KEY_PATH", requires = "tls_cert_path")]
+    #[serde(skip_serializing)]
+    tls_key_path: Option<Utf8PathBuf>,
+
+    /// Route GraphQL subscriptions through the router to the subgraphs that serve them, which clients can then subscribe to over WebSockets or multipart HTTP.
+    ///
+    /// The router opens a WebSocket to a subgraph for each subscription. This sets `subscription` in the router config, unless the `--router-config` file already does.
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true", value_name = "BOOL")]
+    subscriptions: Option<bool>,
+
+    /// The path subgraphs accept subscription WebSockets on, in place of the path of each subgraph's URL.
+    #[arg(
+        long = "subscription-path",
+        value_name = "PATH",
+        requires = "subscriptions"
+    )]
+    subscription_path: Option<String>,
+
+    /// The WebSocket protocol the subgraphs serve subscriptions with. Defaults to `graphql-transport-ws`, the protocol of the `graphql-ws` library.
+    #[arg(
+        long = "subscription-protocol",
+        value_enum,
+        value_name = "PROTOCOL",
+        requires = "subscriptions"
+    )]
+    subscription_protocol: Option<SubscriptionProtocol>,
+
+    /// How this `rover dev` process writes the logs of the router, its subgraphs, and itself. Defaults to `text`.
+    ///
+    /// `text` prefixes each line with the colored name of its source, i.e. `[router]` or `[products]`, and `json` writes one JSON object per line for log viewers.
+    #[arg(long = "log-format", value_enum, value_name = "LOG_FORMAT")]
+    log_format: Option<DevLogFormat>,
+
+    /// Which logs this `rover dev` process writes, as a comma-separated list of levels for every source and for single sources, i.e. `warn,router=info,products=debug`.
+    ///
+    /// Sources are `rover`, `router`, and subgraph names. Without a level of its own, the router writes warnings and errors, and every other source writes info logs.
+    #[arg(long = "log-filter", value_name = "LOG_FILTER")]
+    #[serde(skip_serializing)]
+    log_filter: Option<DevLogFilter>,
+}
+
+/// The WebSocket protocols the router can open subscriptions to subgraphs with
+#[derive(ValueEnum, Debug, Clone, Copy, Serialize, Eq, PartialEq)]
+pub enum SubscriptionProtocol {
+    /// The protocol of the legacy `subscriptions-transport-ws` library
+    GraphqlWs,
+
+    /// The protocol of the `graphql-ws` library
+    GraphqlTransportWs,
+}
+
+impl SubscriptionProtocol {
+    /// The name of the protocol in the router config
+    pub fn get_router_name(&self) -> &'static str {
+        match self {
+            SubscriptionProtocol::GraphqlWs => "graphql_ws",
+            SubscriptionProtocol::GraphqlTransportWs => "graphql_transport_ws",
+        }
+    }
 }
 
 lazy_static::lazy_static! {
@@ -84,3 +358,27 @@ lazy_static::lazy_static! {
     pub(crate) static ref OVERRIDE_DEV_COMPOSITION_VERSION: Option<String> =
         std::env::var("APOLLO_ROVER_DEV_COMPOSITION_VERSION").ok();
 }
+
+/// The router version a `rover dev` session runs: its `--router-version`, then
+/// `APOLLO_ROVER_DEV_ROUTER_VERSION`, then the latest router
+pub(crate) fn get_dev_router_version(
+    router_version: Option<RouterVersion>,
+) -> RoverResult<RouterVersion> {
+    Ok(match (router_version, &*OVERRIDE_DEV_ROUTER_VERSION) {
+        (Some(router_version), _) => router_version,
+        (None, Some(version)) => RouterVersion::Exact(Version::parse(version)?),
+        (None, None) => RouterVersion::Latest,
+    })
+}
+
+/// The federation version a `rover dev` session composes with: `APOLLO_ROVER_DEV_COMPOSITION_VERSION`,
+/// or the latest federation 2 composition
+pub(crate) fn get_dev_federation_version() -> RoverResult<FederationVersion> {
+    Ok(match &*OVERRIDE_DEV_COMPOSITION_VERSION {
+        Some(version) => FederationVersion::ExactFedTwo(
+            Version::parse(version)
+                .with_context(|| format!("could not parse composition version: {version}"))?,
+        ),
+        None => FederationVersion::LatestFedTwo,
+    })
+}
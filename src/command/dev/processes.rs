@@ -0,0 +1,91 @@
+use std::process::{Command, Stdio};
+
+/// The name of the executable of a running process, or `None` if there's no process with that ID.
+///
+/// This asks the operating system's own tools rather than tracking processes in memory,
+/// so it also finds processes left behind by a `rover dev` process that crashed
+pub fn get_process_name(pid: u32) -> Option<String> {
+    let output = if cfg!(windows) {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+            .stderr(Stdio::null())
+            .output()
+    } else {
+        Command::new("ps")
+            .args(["-p", &pid.to_string(), "-o", "comm="])
+            .stderr(Stdio::null())
+            .output()
+    }
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_process_name(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Whether a process is running an executable whose name contains `executable`, which keeps
+/// a process that reused the ID of one that exited from being mistaken for it
+pub fn is_process_running(pid: u32, executable: &str) -> bool {
+    matches!(get_process_name(pid), Some(name) if name.contains(executable))
+}
+
+/// Stops a process, returning whether it was stopped
+pub fn kill_process(pid: u32) -> bool {
+    let status = if cfg!(windows) {
+        Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+    } else {
+        Command::new("kill")
+            .arg(pid.to_string())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+    };
+    matches!(status, Ok(status) if status.success())
+}
+
+/// The executable name from the output of `ps -o comm=`, which can be a full path,
+/// or of `tasklist /FO CSV /NH`, which quotes it first in each row
+fn parse_process_name(output: &str) -> Option<String> {
+    let line = output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+    if let Some(row) = line.strip_prefix('"') {
+        return row.split('"').next().map(String::from);
+    }
+    // `tasklist` prints a message instead of a row when no process matches
+    if line.starts_with("INFO:") {
+        return None;
+    }
+    line.rsplit(['/', '\\']).next().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_process_names() {
+        assert_eq!(
+            parse_process_name("/Users/me/.rover/bin/router-v1.10.0\n"),
+            Some("router-v1.10.0".to_string())
+        );
+        assert_eq!(
+            parse_process_name(
+                "\"router-v1.10.0.exe\",\"4242\",\"Console\",\"1\",\"50,000 K\"\r\n"
+            ),
+            Some("router-v1.10.0.exe".to_string())
+        );
+        assert_eq!(
+            parse_process_name(
+                "INFO: No tasks are running which match the specified criteria.\r\n"
+            ),
+            None
+        );
+        assert_eq!(parse_process_name(""), None);
+    }
+}
@@ -4,6 +4,7 @@ use rover_std::Emoji;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
+use crate::command::dev::logger::{self, DevLogSource};
 use crate::command::dev::protocol::{entry_from_definition, SubgraphEntry, SubgraphName};
 use crate::{RoverError, RoverResult, PKG_VERSION};
 
@@ -28,6 +29,13 @@ impl FollowerMessage {
         }
     }
 
+    pub fn get_status(is_from_main_session: bool) -> Self {
+        Self {
+            kind: FollowerMessageKind::get_status(),
+            is_from_main_session,
+        }
+    }
+
     pub fn health_check(is_from_main_session: bool) -> RoverResult<Self> {
         if is_from_main_session {
             Err(RoverError::new(anyhow!(
@@ -51,6 +59,16 @@ impl FollowerMessage {
         })
     }
 
+    pub fn add_remote_subgraph(
+        is_from_main_session: bool,
+        subgraph: &SubgraphDefinition,
+    ) -> RoverResult<Self> {
+        Ok(Self {
+            kind: FollowerMessageKind::add_remote_subgraph(subgraph)?,
+            is_from_main_session,
+        })
+    }
+
     pub fn update_subgraph(
         is_from_main_session: bool,
         subgraph: &SubgraphDefinition,
@@ -98,33 +116,55 @@ impl FollowerMessage {
         match self.kind() {
             FollowerMessageKind::AddSubgraph { subgraph_entry } => {
                 if self.is_from_main_session() {
-                    eprintln!("{0}Do not run this command in production! {0}It is intended for local development.", Emoji::Warn);
-                    eprintln!(
-                        "{}starting a session with the '{}' subgraph",
-                        Emoji::Start,
-                        &subgraph_entry.0 .0
+                    logger::info(&DevLogSource::Rover, format!("{0}Do not run this command in production! {0}It is intended for local development.", Emoji::Warn));
+                    logger::info(
+                        &DevLogSource::Subgraph(subgraph_entry.0 .0.clone()),
+                        format!(
+                            "{}starting a session with the '{}' subgraph",
+                            Emoji::Start,
+                            &subgraph_entry.0 .0
+                        ),
                     );
                 } else {
-                    eprintln!(
-                        "{}adding the '{}' subgraph to the session",
-                        Emoji::New,
-                        &subgraph_entry.0 .0
+                    logger::info(
+                        &DevLogSource::Subgraph(subgraph_entry.0 .0.clone()),
+                        format!(
+                            "{}adding the '{}' subgraph to the session",
+                            Emoji::New,
+                            &subgraph_entry.0 .0
+                        ),
                     );
                 }
             }
+            FollowerMessageKind::AddRemoteSubgraph { subgraph_entry } => {
+                logger::info(
+                    &DevLogSource::Subgraph(subgraph_entry.0 .0.clone()),
+                    format!(
+                        "{}adding the published '{}' subgraph to the session",
+                        Emoji::New,
+                        &subgraph_entry.0 .0
+                    ),
+                );
+            }
             FollowerMessageKind::UpdateSubgraph { subgraph_entry } => {
-                eprintln!(
-                    "{}updating the schema for the '{}' subgraph in the session",
-                    Emoji::Reload,
-                    &subgraph_entry.0 .0
+                logger::info(
+                    &DevLogSource::Subgraph(subgraph_entry.0 .0.clone()),
+                    format!(
+                        "{}updating the schema for the '{}' subgraph in the session",
+                        Emoji::Reload,
+                        &subgraph_entry.0 .0
+                    ),
                 );
             }
             FollowerMessageKind::RemoveSubgraph { subgraph_name } => {
                 if self.is_from_main_session() {
-                    eprintln!(
-                        "{}removing the '{}' subgraph from this session",
-                        Emoji::Reload,
-                        &subgraph_name
+                    logger::info(
+                        &DevLogSource::Subgraph(subgraph_name.clone()),
+                        format!(
+                            "{}removing the '{}' subgraph from this session",
+                            Emoji::Reload,
+                            &subgraph_name
+                        ),
                     );
                 } else {
                     tracing::debug!(
@@ -147,19 +187,35 @@ impl FollowerMessage {
             FollowerMessageKind::GetSubgraphs => {
                 tracing::debug!("asking the main process about existing subgraphs");
             }
+            FollowerMessageKind::GetStatus => {
+                tracing::debug!("asking the main process about the state of the session");
+            }
         }
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum FollowerMessageKind {
-    GetVersion { follower_version: String },
+    GetVersion {
+        follower_version: String,
+    },
     GetSubgraphs,
+    GetStatus,
     HealthCheck,
     Shutdown,
-    AddSubgraph { subgraph_entry: SubgraphEntry },
-    UpdateSubgraph { subgraph_entry: SubgraphEntry },
-    RemoveSubgraph { subgraph_name: SubgraphName },
+    AddSubgraph {
+        subgraph_entry: SubgraphEntry,
+    },
+    /// A published subgraph of `--remote-subgraphs`, which a local subgraph with the same name replaces
+    AddRemoteSubgraph {
+        subgraph_entry: SubgraphEntry,
+    },
+    UpdateSubgraph {
+        subgraph_entry: SubgraphEntry,
+    },
+    RemoveSubgraph {
+        subgraph_name: SubgraphName,
+    },
 }
 
 impl FollowerMessageKind {
@@ -173,6 +229,10 @@ impl FollowerMessageKind {
         Self::GetSubgraphs
     }
 
+    fn get_status() -> Self {
+        Self::GetStatus
+    }
+
     fn health_check() -> Self {
         Self::HealthCheck
     }
@@ -187,6 +247,12 @@ impl FollowerMessageKind {
         })
     }
 
+    fn add_remote_subgraph(subgraph: &SubgraphDefinition) -> RoverResult<Self> {
+        Ok(Self::AddRemoteSubgraph {
+            subgraph_entry: entry_from_definition(subgraph)?,
+        })
+    }
+
     fn update_subgraph(subgraph: &SubgraphDefinition) -> RoverResult<Self> {
         Ok(Self::UpdateSubgraph {
             subgraph_entry: entry_from_definition(subgraph)?,
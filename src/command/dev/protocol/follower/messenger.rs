@@ -4,10 +4,13 @@ use crossbeam_channel::{Receiver, Sender};
 use interprocess::local_socket::LocalSocketStream;
 use std::{fmt::Debug, io::BufReader, time::Duration};
 
-use crate::{RoverError, RoverErrorSuggestion, RoverResult, PKG_VERSION};
+use crate::{
+    command::dev::DevSessionStatus, RoverError, RoverErrorSuggestion, RoverResult, PKG_VERSION,
+};
 
 use crate::command::dev::protocol::{
-    socket_read, socket_write, FollowerMessage, LeaderMessageKind, SubgraphKeys, SubgraphName,
+    ipc_connect, socket_read, socket_write, FollowerMessage, LeaderMessageKind, SubgraphKeys,
+    SubgraphName,
 };
 
 #[derive(Debug)]
@@ -61,6 +64,24 @@ impl FollowerMessenger {
         self.message_leader(FollowerMessage::get_subgraphs(self.is_from_main_session()))
     }
 
+    /// Request the state of the session, i.e. for `rover dev status`
+    pub fn session_status(&self) -> RoverResult<DevSessionStatus> {
+        let follower_message = FollowerMessage::get_status(self.is_from_main_session());
+        match self.kind.send_message(follower_message)? {
+            LeaderMessageKind::SessionStatus { status } => Ok(status),
+            leader_message => Err(RoverError::new(anyhow!(
+                "the main `rover dev` process responded with {:?} instead of the state of the session",
+                leader_message
+            ))),
+        }
+    }
+
+    /// Ask the main session to shut down, along with its router and every attached session
+    pub fn shutdown_session(&self) -> RoverResult<()> {
+        self.message_leader(FollowerMessage::shutdown(self.is_from_main_session()))?;
+        Ok(())
+    }
+
     /// Add a subgraph to the main session
     pub fn add_subgraph(&self, subgraph: &SubgraphDefinition) -> RoverResult<()> {
         self.message_leader(FollowerMessage::add_subgraph(
@@ -70,6 +91,15 @@ impl FollowerMessenger {
         Ok(())
     }
 
+    /// Add a published subgraph to the main session, unless a local subgraph with its name is running
+    pub fn add_remote_subgraph(&self, subgraph: &SubgraphDefinition) -> RoverResult<()> {
+        self.message_leader(FollowerMessage::add_remote_subgraph(
+            self.is_from_main_session(),
+            subgraph,
+        )?)?;
+        Ok(())
+    }
+
     /// Update a subgraph in the main session
     pub fn update_subgraph(&self, subgraph: &SubgraphDefinition) -> RoverResult<()> {
         self.message_leader(FollowerMessage::update_subgraph(
@@ -131,6 +161,12 @@ impl FollowerMessengerKind {
         &self,
         follower_message: FollowerMessage,
     ) -> RoverResult<Option<SubgraphKeys>> {
+        let leader_message = self.send_message(follower_message)?;
+        self.handle_leader_message(&leader_message)
+    }
+
+    /// Sends a message to the leader and returns its response
+    fn send_message(&self, follower_message: FollowerMessage) -> RoverResult<LeaderMessageKind> {
         use FollowerMessengerKind::*;
         follower_message.print();
         let leader_message = match self {
@@ -150,7 +186,7 @@ impl FollowerMessengerKind {
                 leader_message
             }
             FromAttachedSession { ipc_socket_addr } => {
-                let stream = LocalSocketStream::connect(&**ipc_socket_addr).map_err(|_| {
+                let stream = ipc_connect(ipc_socket_addr).map_err(|_| {
                     let mut err = RoverError::new(anyhow!(
                         "there is not a main `rover dev` process to report updates to"
                     ));
@@ -179,7 +215,7 @@ impl FollowerMessengerKind {
             }
         }?;
 
-        self.handle_leader_message(&leader_message)
+        Ok(leader_message)
     }
 
     fn handle_leader_message(
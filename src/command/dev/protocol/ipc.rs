@@ -0,0 +1,324 @@
+use std::{
+    io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+#[cfg(unix)]
+use anyhow::anyhow;
+use anyhow::Context;
+use interprocess::local_socket::{
+    LocalSocketListener, LocalSocketStream,
+    NameTypeSupport::{self, *},
+};
+use sha2::{Digest, Sha256};
+
+use crate::RoverResult;
+#[cfg(unix)]
+use crate::{RoverError, RoverErrorSuggestion};
+
+/// The longest socket path every platform accepts, `sun_path` is 104 bytes on macOS
+const MAX_SOCKET_PATH_LEN: usize = 100;
+
+/// The name of the interprocess socket that the main `rover dev` process of a session listens on.
+/// A named session is found by its name, and any other session by its router address.
+///
+/// Sockets are kept apart per user, so sessions of different users on one machine
+/// never find (or collide with) each other
+pub fn get_ipc_address(router_address: &SocketAddr, session_name: Option<&str>) -> String {
+    let socket_name = match session_name {
+        Some(session_name) => format!("supergraph-session-{}.sock", sanitize(session_name)),
+        None => format!("supergraph-{}.sock", sanitize(&router_address.to_string())),
+    };
+    match NameTypeSupport::query() {
+        OnlyPaths | Both => {
+            let socket_dir = get_socket_dir();
+            let mut socket_path = socket_dir.join(&socket_name);
+            if socket_path.as_os_str().len() > MAX_SOCKET_PATH_LEN {
+                // long session names are hashed so their socket path stays under the limit,
+                // with a hash that every build of Rover agrees on
+                let hash = format!("{:x}", Sha256::digest(socket_name.as_bytes()));
+                socket_path = socket_dir.join(format!("supergraph-{}.sock", &hash[..16]));
+            }
+            socket_path.to_string_lossy().to_string()
+        }
+        // named pipes are namespaced for the whole machine, so they carry the user's name
+        OnlyNamespaced => format!("@rover-dev-{}-{}", get_user_name(), socket_name),
+    }
+}
+
+/// Connects to the socket of the main `rover dev` process at `ipc_socket_addr`,
+/// refusing one that another user is listening on
+pub fn ipc_connect(ipc_socket_addr: &str) -> io::Result<LocalSocketStream> {
+    let stream = LocalSocketStream::connect(ipc_socket_addr)?;
+    check_peer_user(&stream, Peer::Server)?;
+    Ok(stream)
+}
+
+/// Refuses a connection to the main `rover dev` process from another user
+pub fn ipc_check_client(stream: &LocalSocketStream) -> io::Result<()> {
+    check_peer_user(stream, Peer::Client)
+}
+
+/// Listens on the socket at `ipc_socket_addr`, in a directory only the current user can access
+pub fn ipc_bind(ipc_socket_addr: &str) -> RoverResult<LocalSocketListener> {
+    if let Some(socket_dir) = get_socket_path(ipc_socket_addr).and_then(|p| p.parent()) {
+        create_private_dir(socket_dir)?;
+    }
+    let listener = LocalSocketListener::bind(ipc_socket_addr).with_context(|| {
+        format!(
+            "could not start local socket server at {}",
+            &ipc_socket_addr
+        )
+    })?;
+    if let Some(socket_path) = get_socket_path(ipc_socket_addr) {
+        set_private_permissions(socket_path, 0o600);
+    }
+    Ok(listener)
+}
+
+/// Removes the socket at `ipc_socket_addr` if it's a file, named pipes are removed by the OS
+pub fn ipc_remove(ipc_socket_addr: &str) {
+    if let Some(socket_path) = get_socket_path(ipc_socket_addr) {
+        let _ = std::fs::remove_file(socket_path);
+    }
+}
+
+/// The file of a socket address, which namespaced names don't have
+fn get_socket_path(ipc_socket_addr: &str) -> Option<&Path> {
+    if ipc_socket_addr.starts_with('@') {
+        None
+    } else {
+        Some(Path::new(ipc_socket_addr))
+    }
+}
+
+/// The directory the current user's sockets are kept in,
+/// the per-user runtime directory when there is one
+fn get_socket_dir() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(runtime_dir) if !runtime_dir.is_empty() => PathBuf::from(runtime_dir).join("rover"),
+        _ => std::env::temp_dir().join(format!("rover-dev-{}", get_user_name())),
+    }
+}
+
+/// The current user's name, as it can appear in a file or pipe name
+fn get_user_name() -> String {
+    let user_name = ["USER", "LOGNAME", "USERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|name| !name.is_empty()))
+        .unwrap_or_else(|| "default".to_string());
+    sanitize(&user_name)
+}
+
+/// Replaces every character that can't appear in a socket name on every platform,
+/// i.e. the `:` of a router address or a `\` that would end a named pipe's name
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Creates the socket directory, readable only by the current user
+fn create_private_dir(socket_dir: &Path) -> RoverResult<()> {
+    std::fs::create_dir_all(socket_dir).with_context(|| {
+        format!(
+            "could not create the socket directory {}",
+            socket_dir.display()
+        )
+    })?;
+    set_private_permissions(socket_dir, 0o700);
+    check_private_dir(socket_dir)
+}
+
+/// Refuses a socket directory that another user owns or could look into,
+/// i.e. one another user made first, since this one can't change its permissions
+#[cfg(unix)]
+fn check_private_dir(socket_dir: &Path) -> RoverResult<()> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+    let metadata = std::fs::metadata(socket_dir)?;
+    // SAFETY: geteuid can't fail and has no side effects
+    let uid = unsafe { libc::geteuid() };
+    let problem = if metadata.uid() != uid {
+        Some("is owned by another user")
+    } else if metadata.permissions().mode() & 0o077 != 0 {
+        Some("can be accessed by other users")
+    } else {
+        None
+    };
+    if let Some(problem) = problem {
+        let mut err = RoverError::new(anyhow!(
+            "the socket directory {} {}",
+            socket_dir.display(),
+            problem
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+            "Remove {} (owned by uid {}) and run `rover dev` again, or set `XDG_RUNTIME_DIR` to a directory only you can access.",
+            socket_dir.display(),
+            metadata.uid()
+        )));
+        return Err(err);
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_private_dir(_socket_dir: &Path) -> RoverResult<()> {
+    Ok(())
+}
+
+/// Failing to change them is caught by [`check_private_dir`]
+#[cfg(unix)]
+fn set_private_permissions(path: &Path, mode: u32) {
+    use std::os::unix::fs::PermissionsExt;
+    let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode));
+}
+
+/// Named pipes don't have a file, so [`check_peer_user`] keeps other users out of them instead
+#[cfg(not(unix))]
+fn set_private_permissions(_path: &Path, _mode: u32) {}
+
+/// Which end of a connection [`check_peer_user`] checks
+#[derive(Debug, Clone, Copy)]
+enum Peer {
+    Client,
+    Server,
+}
+
+/// Sockets are files in a directory only the current user can access, so their peers are
+/// always the current user
+#[cfg(not(windows))]
+fn check_peer_user(_stream: &LocalSocketStream, _peer: Peer) -> io::Result<()> {
+    Ok(())
+}
+
+/// Refuses a named pipe whose other end is a process of another user. Any user can open
+/// a named pipe with the default security, or create one first with the name of another
+/// user's session, so each end checks who the other is
+#[cfg(windows)]
+fn check_peer_user(stream: &LocalSocketStream, peer: Peer) -> io::Result<()> {
+    use std::os::windows::io::AsRawHandle;
+    use winapi::um::winbase::{GetNamedPipeClientProcessId, GetNamedPipeServerProcessId};
+
+    let pipe = stream.as_raw_handle() as winapi::um::winnt::HANDLE;
+    let mut process_id = 0;
+    // SAFETY: the handle is an open named pipe for as long as `stream` is borrowed
+    let found = unsafe {
+        match peer {
+            Peer::Client => GetNamedPipeClientProcessId(pipe, &mut process_id),
+            Peer::Server => GetNamedPipeServerProcessId(pipe, &mut process_id),
+        }
+    };
+    if found == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if windows_user::is_same_user(process_id, std::process::id())? {
+        Ok(())
+    } else {
+        let peer = match peer {
+            Peer::Client => "process that connected to",
+            Peer::Server => "process listening on",
+        };
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("the {} this `rover dev` named pipe is another user's", peer),
+        ))
+    }
+}
+
+#[cfg(windows)]
+mod windows_user {
+    use std::io;
+
+    use winapi::{
+        shared::minwindef::{DWORD, FALSE},
+        um::{
+            handleapi::CloseHandle,
+            processthreadsapi::{OpenProcess, OpenProcessToken},
+            securitybaseapi::{EqualSid, GetTokenInformation},
+            winnt::{
+                TokenUser, HANDLE, PROCESS_QUERY_LIMITED_INFORMATION, TOKEN_QUERY, TOKEN_USER,
+            },
+        },
+    };
+
+    /// Whether two processes run as the same user
+    pub(super) fn is_same_user(process_id: DWORD, other_process_id: DWORD) -> io::Result<bool> {
+        let user = get_token_user(process_id)?;
+        let other_user = get_token_user(other_process_id)?;
+        // SAFETY: both buffers start with a TOKEN_USER written by GetTokenInformation,
+        // whose SID points into the same buffer
+        let equal = unsafe {
+            let user = &*(user.as_ptr() as *const TOKEN_USER);
+            let other_user = &*(other_user.as_ptr() as *const TOKEN_USER);
+            EqualSid(user.User.Sid, other_user.User.Sid)
+        };
+        Ok(equal != 0)
+    }
+
+    /// The `TOKEN_USER` of a process, in a buffer that also holds the SID it points to
+    fn get_token_user(process_id: DWORD) -> io::Result<Vec<u64>> {
+        // SAFETY: every handle opened here is closed before returning
+        unsafe {
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, process_id);
+            if process.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            let mut token: HANDLE = std::ptr::null_mut();
+            let opened = OpenProcessToken(process, TOKEN_QUERY, &mut token);
+            CloseHandle(process);
+            if opened == 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mut length = 0;
+            GetTokenInformation(token, TokenUser, std::ptr::null_mut(), 0, &mut length);
+            // u64s so the buffer is aligned for TOKEN_USER
+            let mut buffer = vec![0u64; (length as usize + 7) / 8];
+            let found = GetTokenInformation(
+                token,
+                TokenUser,
+                buffer.as_mut_ptr() as *mut _,
+                length,
+                &mut length,
+            );
+            CloseHandle(token);
+            if found == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(buffer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_names_sockets_per_user_without_special_characters() {
+        let router_address: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let ipc_socket_addr = get_ipc_address(&router_address, None);
+        assert!(ipc_socket_addr.ends_with("supergraph-127.0.0.1_4000.sock"));
+        assert!(ipc_socket_addr.contains("rover"));
+
+        let ipc_socket_addr = get_ipc_address(&router_address, Some("my/session"));
+        assert!(ipc_socket_addr.ends_with("supergraph-session-my_session.sock"));
+
+        let long_name = "a".repeat(120);
+        let ipc_socket_addr = get_ipc_address(&router_address, Some(&long_name));
+        assert_eq!(
+            ipc_socket_addr,
+            get_ipc_address(&router_address, Some(&long_name))
+        );
+        if get_socket_path(&ipc_socket_addr).is_some() {
+            assert!(ipc_socket_addr.len() <= MAX_SOCKET_PATH_LEN);
+        }
+    }
+}
@@ -2,11 +2,16 @@ use crate::{
     command::dev::{
         compose::ComposeRunner,
         do_dev::log_err_and_continue,
+        get_dev_federation_version,
+        hooks::DevHooks,
+        logger::{self, DevLogSource},
+        record::DevRecorder,
         router::{RouterConfigHandler, RouterRunner},
-        OVERRIDE_DEV_COMPOSITION_VERSION,
+        session_file::{get_sessions_dir, SessionAnnouncement, SessionFile},
+        DevCompositionStatus, DevSessionStatus, DevSessionSubgraph,
     },
     options::PluginOpts,
-    utils::client::StudioClientConfig,
+    utils::{browser, client::StudioClientConfig},
     RoverError, RoverErrorSuggestion, RoverResult, PKG_VERSION,
 };
 use anyhow::{anyhow, Context};
@@ -15,15 +20,24 @@ use apollo_federation_types::{
     config::{FederationVersion, SupergraphConfig},
 };
 use camino::Utf8PathBuf;
+use chrono::{DateTime, Utc};
 use crossbeam_channel::{bounded, Receiver, Sender};
-use interprocess::local_socket::{LocalSocketListener, LocalSocketStream};
-use rover_std::Emoji;
-use semver::Version;
+use interprocess::local_socket::LocalSocketStream;
+use rover_std::{tmp, Emoji};
 use serde::{Deserialize, Serialize};
-
-use std::{collections::HashMap, fmt::Debug, io::BufReader, net::TcpListener};
+use serde_json::json;
+use timber::Level;
+use url::Url;
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    io::BufReader,
+    net::{SocketAddr, TcpListener},
+};
 
 use super::{
+    ipc::{ipc_bind, ipc_connect, ipc_remove},
     socket::{handle_socket_error, socket_read, socket_write},
     types::{
         CompositionResult, SubgraphEntry, SubgraphKey, SubgraphKeys, SubgraphName, SubgraphSdl,
@@ -31,15 +45,37 @@ use super::{
     FollowerChannel, FollowerMessage, FollowerMessageKind,
 };
 
+/// Apollo Sandbox, which `--open` points at the router by setting its `endpoint`
+const SANDBOX_URL: &str = "https://studio.apollographql.com/sandbox/explorer";
+
+/// The name of the thread that runs [`LeaderSession::listen_for_all_subgraph_updates`]
+pub(crate) const LEADER_THREAD_NAME: &str = "rover-dev-leader";
+
 #[derive(Debug)]
 pub struct LeaderSession {
     subgraphs: HashMap<SubgraphKey, SubgraphSdl>,
+    /// the subgraphs that were published to `--remote-subgraphs`, which local subgraphs replace
+    remote_subgraphs: HashSet<SubgraphName>,
     ipc_socket_addr: String,
     compose_runner: ComposeRunner,
     router_runner: RouterRunner,
     follower_channel: FollowerChannel,
     leader_channel: LeaderChannel,
     federation_version: FederationVersion,
+    hooks: DevHooks,
+    supergraph_schema_path: Utf8PathBuf,
+    router_socket_addr: SocketAddr,
+    router_tls: bool,
+    session_name: Option<String>,
+    started_at: DateTime<Utc>,
+    session_file: SessionFile,
+    announcement: SessionAnnouncement,
+    last_composition: Option<DevCompositionStatus>,
+    last_composed_at: Option<DateTime<Utc>>,
+    compositions: u64,
+    composition_failures: u64,
+    open_sandbox: bool,
+    recorder: Option<DevRecorder>,
 }
 
 impl LeaderSession {
@@ -56,11 +92,12 @@ impl LeaderSession {
         leader_channel: LeaderChannel,
         follower_channel: FollowerChannel,
         plugin_opts: PluginOpts,
-        router_config_handler: RouterConfigHandler,
+        mut router_config_handler: RouterConfigHandler,
+        hooks: DevHooks,
     ) -> RoverResult<Option<Self>> {
         let ipc_socket_addr = router_config_handler.get_ipc_address()?;
         let router_socket_addr = router_config_handler.get_router_address()?;
-        if let Ok(stream) = LocalSocketStream::connect(&*ipc_socket_addr) {
+        if let Ok(stream) = ipc_connect(&ipc_socket_addr) {
             // write to the socket so we don't make the other session deadlock waiting on a message
             let mut stream = BufReader::new(stream);
             socket_write(&FollowerMessage::health_check(false)?, &mut stream)?;
@@ -75,16 +112,90 @@ impl LeaderSession {
         //
         // remove the socket file before starting in case it was here from last time
         // if we can't connect to it, it's safe to remove
-        let _ = std::fs::remove_file(&ipc_socket_addr);
-
-        if TcpListener::bind(router_socket_addr).is_err() {
-            let mut err =
-                RoverError::new(anyhow!("You cannot bind the router to '{}' because that address is already in use by another process on this machine.", &router_socket_addr));
-            err.set_suggestion(RoverErrorSuggestion::Adhoc(
-                format!("Try setting a different port for the router to bind to with the `--supergraph-port` argument, or shut down the process bound to '{}'.", &router_socket_addr)
-            ));
-            return Err(err);
-        }
+        ipc_remove(&ipc_socket_addr);
+
+        let router_socket_addr = match TcpListener::bind(router_socket_addr) {
+            Ok(_) => router_socket_addr,
+            // the default port is often taken by another dev server, so move to a free one
+            // instead of making the user pick a port
+            Err(_) if router_config_handler.is_default_port() => {
+                let free_socket_addr =
+                    TcpListener::bind(SocketAddr::new(router_socket_addr.ip(), 0))
+                        .and_then(|listener| listener.local_addr())
+                        .with_context(|| {
+                            format!(
+                                "could not find a free port for the router on {}",
+                                router_socket_addr.ip()
+                            )
+                        })?;
+                router_config_handler.set_router_port(free_socket_addr.port())?;
+                logger::log_with_fields(
+                    &DevLogSource::Rover,
+                    Level::WARN,
+                    format!("port {} is already in use by another process on this machine, so the router will listen on {} instead",
+                        router_socket_addr.port(),
+                        free_socket_addr),
+                    json!({ "router_address": free_socket_addr.to_string() }),
+                );
+                free_socket_addr
+            }
+            Err(_) => {
+                let mut err =
+                    RoverError::new(anyhow!("You cannot bind the router to '{}' because that address is already in use by another process on this machine.", &router_socket_addr));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                    format!("Try setting a different port for the router to bind to with the `--supergraph-port` argument, or shut down the process bound to '{}'.", &router_socket_addr)
+                ));
+                return Err(err);
+            }
+        };
+
+        // announce where the router listens, for tools that can't ask the user
+        let session_name = router_config_handler.get_session_name();
+        let router_tls = router_config_handler.uses_tls();
+        let started_at = Utc::now();
+        let session_file = SessionFile::new(
+            &get_sessions_dir(override_install_path.clone())?,
+            &router_config_handler.get_session_router_address(),
+            session_name.as_deref(),
+        );
+        let announcement = SessionAnnouncement {
+            session_name: session_name.clone(),
+            router_url: format!(
+                "{}://{}",
+                if router_tls { "https" } else { "http" },
+                router_socket_addr
+            ),
+            router_address: router_socket_addr,
+            requested_address: router_config_handler.get_session_router_address(),
+            pid: std::process::id(),
+            router_pid: None,
+            ipc_socket: ipc_socket_addr.clone(),
+            started_at,
+        };
+        session_file.write(&announcement)?;
+        tracing::info!(
+            "announced the router address in {}",
+            session_file.get_path()
+        );
+
+        // panics unwind, but a panic on the main thread or in the leader loop ends the session
+        // without reaching its shutdown, which would leave the router running and the session
+        // announced. Panics on other threads don't end the session, so they're only passed on
+        // to the hook that was installed before this one
+        let panic_session_file = session_file.clone();
+        let previous_panic_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            let thread_name = std::thread::current().name().map(str::to_string);
+            if matches!(
+                thread_name.as_deref(),
+                Some("main") | Some(LEADER_THREAD_NAME)
+            ) {
+                if let Ok(announcement) = panic_session_file.read() {
+                    panic_session_file.clean_up(&announcement);
+                }
+            }
+            previous_panic_hook(panic_info)
+        }));
 
         // create a [`ComposeRunner`] that will be in charge of composing our supergraph
         let mut compose_runner = ComposeRunner::new(
@@ -100,35 +211,71 @@ impl LeaderSession {
             router_config_handler.get_router_config_path(),
             plugin_opts,
             router_socket_addr,
+            router_config_handler.uses_tls(),
             override_install_path,
             client_config.clone(),
-        );
+        )
+        .with_router_version(router_config_handler.get_router_version());
 
         // install plugins before proceeding
-        let federation_version = match &*OVERRIDE_DEV_COMPOSITION_VERSION {
-            Some(version) => FederationVersion::ExactFedTwo(
-                Version::parse(version)
-                    .with_context(|| format!("could not parse composition version: {version}"))?,
-            ),
-            None => FederationVersion::LatestFedTwo,
-        };
+        let federation_version = get_dev_federation_version()?;
 
         router_runner.maybe_install_router()?;
         compose_runner.maybe_install_supergraph(federation_version.clone())?;
 
+        let supergraph_schema_path = router_config_handler.get_supergraph_schema_path();
         router_config_handler.start()?;
 
         Ok(Some(Self {
             subgraphs: HashMap::new(),
+            remote_subgraphs: HashSet::new(),
             ipc_socket_addr,
             compose_runner,
             router_runner,
             follower_channel,
             leader_channel,
             federation_version,
+            hooks,
+            supergraph_schema_path,
+            router_socket_addr,
+            router_tls,
+            session_name,
+            started_at,
+            session_file,
+            announcement,
+            last_composition: None,
+            last_composed_at: None,
+            compositions: 0,
+            composition_failures: 0,
+            open_sandbox: false,
+            recorder: None,
         }))
     }
 
+    /// Keeps `--supergraph-out` up to date with the latest supergraph schema
+    pub fn with_supergraph_out_path(mut self, supergraph_out_path: Option<Utf8PathBuf>) -> Self {
+        self.compose_runner = self.compose_runner.with_out_path(supergraph_out_path);
+        self
+    }
+
+    /// Opens Apollo Sandbox in the browser, pointed at the router, once the router first starts
+    pub fn with_open_sandbox(mut self, open_sandbox: bool) -> Self {
+        self.open_sandbox = open_sandbox;
+        self
+    }
+
+    /// Records the requests the router sends to each subgraph in `--record`'s directory,
+    /// by routing them through a [`DevRecorder`]
+    pub fn with_recorder(mut self, recorder: Option<DevRecorder>) -> Self {
+        self.recorder = recorder;
+        self
+    }
+
+    /// The address the router listens on, which is a free port if the default one was taken
+    pub fn get_router_address(&self) -> SocketAddr {
+        self.router_socket_addr
+    }
+
     /// Start the session by watching for incoming subgraph updates and re-composing when needed
     pub fn listen_for_all_subgraph_updates(&mut self, ready_sender: Sender<()>) -> RoverResult<()> {
         self.receive_messages_from_attached_sessions()?;
@@ -141,6 +288,17 @@ impl LeaderSession {
         loop {
             tracing::trace!("main session waiting for follower message");
             let follower_message = self.follower_channel.receiver.recv().unwrap();
+            if !follower_message.is_from_main_session()
+                && matches!(follower_message.kind(), FollowerMessageKind::Shutdown)
+            {
+                logger::info(
+                    &DevLogSource::Rover,
+                    format!(
+                        "{}shutting down the `rover dev` session because `rover dev kill` was run...",
+                        Emoji::Stop
+                    ),
+                );
+            }
             let leader_message = self.handle_follower_message_kind(follower_message.kind());
 
             if !follower_message.is_from_main_session() {
@@ -159,12 +317,7 @@ impl LeaderSession {
 
     /// Listen on the socket for incoming [`FollowerMessageKind`] messages.
     fn receive_messages_from_attached_sessions(&self) -> RoverResult<()> {
-        let listener = LocalSocketListener::bind(&*self.ipc_socket_addr).with_context(|| {
-            format!(
-                "could not start local socket server at {}",
-                &self.ipc_socket_addr
-            )
-        })?;
+        let listener = ipc_bind(&self.ipc_socket_addr)?;
         tracing::info!(
             "connected to socket {}, waiting for messages",
             &self.ipc_socket_addr
@@ -202,10 +355,23 @@ impl LeaderSession {
         Ok(())
     }
 
-    /// Adds a subgraph to the internal supergraph representation.
+    /// Adds a subgraph to the internal supergraph representation,
+    /// in place of the published subgraph with its name if there is one.
     fn add_subgraph(&mut self, subgraph_entry: &SubgraphEntry) -> LeaderMessageKind {
-        let is_first_subgraph = self.subgraphs.is_empty();
         let ((name, url), sdl) = subgraph_entry;
+        if self.remote_subgraphs.remove(name) {
+            logger::info(
+                &DevLogSource::Subgraph(name.to_string()),
+                format!(
+                    "{}using the local '{}' subgraph instead of the published one",
+                    Emoji::Person,
+                    name
+                ),
+            );
+            self.subgraphs
+                .retain(|(subgraph_name, _), _| subgraph_name != name);
+        }
+        let is_first_subgraph = self.subgraphs.is_empty();
         if self
             .subgraphs
             .get(&(name.to_string(), url.clone()))
@@ -224,7 +390,11 @@ impl LeaderSession {
                 .insert((name.to_string(), url.clone()), sdl.to_string());
             let composition_result = self.compose();
             if let Err(composition_err) = composition_result {
-                LeaderMessageKind::error(composition_err)
+                LeaderMessageKind::error(get_composition_error_panel(
+                    &format!("adding the '{}' subgraph", name),
+                    &composition_err,
+                    self.last_composed_at,
+                ))
             } else if composition_result.transpose().is_some() && !is_first_subgraph {
                 LeaderMessageKind::add_subgraph_composition_success(name)
             } else {
@@ -233,15 +403,46 @@ impl LeaderSession {
         }
     }
 
+    /// Adds a published subgraph to the internal supergraph representation,
+    /// unless a local subgraph with its name is already running.
+    fn add_remote_subgraph(&mut self, subgraph_entry: &SubgraphEntry) -> LeaderMessageKind {
+        let ((name, _), _) = subgraph_entry;
+        if self
+            .subgraphs
+            .keys()
+            .any(|(subgraph_name, _)| subgraph_name == name)
+        {
+            logger::info(
+                &DevLogSource::Subgraph(name.to_string()),
+                format!(
+                    "{}using the local '{}' subgraph instead of the published one",
+                    Emoji::Person,
+                    name
+                ),
+            );
+            return LeaderMessageKind::message_received();
+        }
+        let message = self.add_subgraph(subgraph_entry);
+        self.remote_subgraphs.insert(name.to_string());
+        message
+    }
+
     /// Updates a subgraph in the internal supergraph representation.
     fn update_subgraph(&mut self, subgraph_entry: &SubgraphEntry) -> LeaderMessageKind {
         let ((name, url), sdl) = &subgraph_entry;
+        if self.remote_subgraphs.contains(name) {
+            return self.add_subgraph(subgraph_entry);
+        }
         if let Some(prev_sdl) = self.subgraphs.get_mut(&(name.to_string(), url.clone())) {
             if prev_sdl != sdl {
                 *prev_sdl = sdl.to_string();
                 let composition_result = self.compose();
                 if let Err(composition_err) = composition_result {
-                    LeaderMessageKind::error(composition_err)
+                    LeaderMessageKind::error(get_composition_error_panel(
+                        &format!("updating the '{}' subgraph", name),
+                        &composition_err,
+                        self.last_composed_at,
+                    ))
                 } else if composition_result.transpose().is_some() {
                     LeaderMessageKind::update_subgraph_composition_success(name)
                 } else {
@@ -265,9 +466,14 @@ impl LeaderSession {
 
         if let Some((name, url)) = found {
             self.subgraphs.remove(&(name.to_string(), url));
+            self.remote_subgraphs.remove(&name);
             let composition_result = self.compose();
             if let Err(composition_err) = composition_result {
-                LeaderMessageKind::error(composition_err)
+                LeaderMessageKind::error(get_composition_error_panel(
+                    &format!("removing the '{}' subgraph", name),
+                    &composition_err,
+                    self.last_composed_at,
+                ))
             } else if composition_result.transpose().is_some() {
                 LeaderMessageKind::remove_subgraph_composition_success(&name)
             } else {
@@ -278,20 +484,71 @@ impl LeaderSession {
         }
     }
 
-    /// Reruns composition, which triggers the router to reload.
+    /// Reruns composition, which triggers the router to reload
+    /// and runs any hooks configured for the result.
+    ///
+    /// A failed composition leaves the router running, so it keeps serving the last
+    /// supergraph that composed until a subgraph change fixes the error
     fn compose(&mut self) -> CompositionResult {
-        self.compose_runner
-            .run(&mut self.supergraph_config())
+        let (mut supergraph_config, proxied_urls) = self.supergraph_config();
+        let composition_result = self
+            .compose_runner
+            .run(&mut supergraph_config, &proxied_urls)
             .map(|maybe_new_schema| {
                 if maybe_new_schema.is_some() {
                     let _ = self.router_runner.spawn().map_err(|e| panic!("{}", e));
                 }
                 maybe_new_schema
-            })
-            .map_err(|e| {
+            });
+        match &composition_result {
+            Ok(Some(_)) => self.last_composed_at = Some(Utc::now()),
+            // nothing was composed, so the supergraph schema was removed and the router has nothing to serve
+            Ok(None) if !self.supergraph_schema_path.exists() => {
                 let _ = self.router_runner.kill().map_err(log_err_and_continue);
-                e
-            })
+                self.last_composed_at = None;
+            }
+            _ => {}
+        }
+        self.hooks
+            .run(&composition_result, &self.supergraph_schema_path);
+        self.announce_router_pid();
+        self.compositions += 1;
+        if composition_result.is_err() {
+            self.composition_failures += 1;
+        }
+        if self.open_sandbox && self.router_runner.get_pid().is_some() {
+            // only the first successful composition opens Sandbox, so recompositions don't open more tabs
+            self.open_sandbox = false;
+            let _ = self.open_sandbox().map_err(log_err_and_continue);
+        }
+        self.last_composition = Some(DevCompositionStatus {
+            at: Utc::now(),
+            succeeded: composition_result.is_ok(),
+            error: composition_result.as_ref().err().cloned(),
+        });
+        composition_result
+    }
+
+    /// Opens Apollo Sandbox with the router's URL as its endpoint
+    fn open_sandbox(&self) -> RoverResult<()> {
+        let sandbox_url = Url::parse_with_params(
+            SANDBOX_URL,
+            &[("endpoint", self.router_runner.get_router_url())],
+        )?;
+        browser::open(sandbox_url.as_str())
+    }
+
+    /// Records the router's process in the session file whenever it starts or stops,
+    /// so it can be found and stopped if this process crashes
+    fn announce_router_pid(&mut self) {
+        let router_pid = self.router_runner.get_pid();
+        if router_pid != self.announcement.router_pid {
+            self.announcement.router_pid = router_pid;
+            let _ = self
+                .session_file
+                .write(&self.announcement)
+                .map_err(log_err_and_continue);
+        }
     }
 
     /// Reads a [`FollowerMessage`] from an open socket connection.
@@ -318,27 +575,75 @@ impl LeaderSession {
 
     /// Gets the supergraph configuration from the internal state.
     /// Calling `.to_string()` on a [`SupergraphConfig`] writes
-    fn supergraph_config(&self) -> SupergraphConfig {
+    ///
+    /// With `--record`, the router sends each subgraph's requests to its recording proxy,
+    /// so this also returns the routing URL each proxy URL stands in for
+    fn supergraph_config(&self) -> (SupergraphConfig, Vec<(String, String)>) {
+        let mut proxied_urls = Vec::new();
         let mut supergraph_config: SupergraphConfig = self
             .subgraphs
             .iter()
-            .map(|((name, url), sdl)| SubgraphDefinition::new(name, url.to_string(), sdl))
+            .map(|((name, url), sdl)| {
+                let routing_url = match &self.recorder {
+                    Some(recorder) => recorder
+                        .record_subgraph(name, url, sdl)
+                        .map_err(log_err_and_continue)
+                        .unwrap_or_else(|_| url.clone()),
+                    None => url.clone(),
+                };
+                if &routing_url != url {
+                    proxied_urls.push((routing_url.to_string(), url.to_string()));
+                }
+                SubgraphDefinition::new(name, routing_url.to_string(), sdl)
+            })
             .collect::<Vec<SubgraphDefinition>>()
             .into();
         supergraph_config.set_federation_version(self.federation_version.clone());
-        supergraph_config
+        (supergraph_config, proxied_urls)
     }
 
-    /// Gets the list of subgraphs running in this session
+    /// Gets the list of local subgraphs running in this session, leaving out published
+    /// subgraphs since a new `rover dev` process can replace them
     fn get_subgraphs(&self) -> SubgraphKeys {
         tracing::debug!("notifying new `rover dev` process about existing subgraphs");
-        self.subgraphs.keys().cloned().collect()
+        self.subgraphs
+            .keys()
+            .filter(|(name, _)| !self.remote_subgraphs.contains(name))
+            .cloned()
+            .collect()
+    }
+
+    /// Gets the state of this session, for `rover dev status` and the health endpoint
+    fn get_status(&self) -> DevSessionStatus {
+        let mut subgraphs: Vec<DevSessionSubgraph> = self
+            .subgraphs
+            .keys()
+            .map(|(name, url)| DevSessionSubgraph {
+                name: name.to_string(),
+                url: url.clone(),
+            })
+            .collect();
+        subgraphs.sort_by(|a, b| a.name.cmp(&b.name));
+        DevSessionStatus {
+            version: PKG_VERSION.to_string(),
+            session_name: self.session_name.clone(),
+            router_address: self.router_socket_addr,
+            router_tls: self.router_tls,
+            started_at: self.started_at,
+            uptime_seconds: (Utc::now() - self.started_at).num_seconds().max(0) as u64,
+            subgraphs,
+            last_composition: self.last_composition.clone(),
+            compositions: self.compositions,
+            composition_failures: self.composition_failures,
+        }
     }
 
     /// Shuts the router down, removes the socket file, and exits the process.
     pub fn shutdown(&mut self) {
         let _ = self.router_runner.kill().map_err(log_err_and_continue);
-        let _ = std::fs::remove_file(&self.ipc_socket_addr);
+        ipc_remove(&self.ipc_socket_addr);
+        self.session_file.remove();
+        tmp::cleanup();
         std::process::exit(1)
     }
 
@@ -352,12 +657,16 @@ impl LeaderSession {
         match follower_message {
             AddSubgraph { subgraph_entry } => self.add_subgraph(subgraph_entry),
 
+            AddRemoteSubgraph { subgraph_entry } => self.add_remote_subgraph(subgraph_entry),
+
             UpdateSubgraph { subgraph_entry } => self.update_subgraph(subgraph_entry),
 
             RemoveSubgraph { subgraph_name } => self.remove_subgraph(subgraph_name),
 
             GetSubgraphs => LeaderMessageKind::current_subgraphs(self.get_subgraphs()),
 
+            GetStatus => LeaderMessageKind::session_status(self.get_status()),
+
             Shutdown => {
                 self.shutdown();
                 LeaderMessageKind::message_received()
@@ -385,6 +694,9 @@ pub enum LeaderMessageKind {
     LeaderSessionInfo {
         subgraphs: SubgraphKeys,
     },
+    SessionStatus {
+        status: DevSessionStatus,
+    },
     CompositionSuccess {
         action: String,
     },
@@ -406,6 +718,10 @@ impl LeaderMessageKind {
         Self::LeaderSessionInfo { subgraphs }
     }
 
+    pub fn session_status(status: DevSessionStatus) -> Self {
+        Self::SessionStatus { status }
+    }
+
     pub fn error(error: String) -> Self {
         Self::ErrorNotification { error }
     }
@@ -435,10 +751,13 @@ impl LeaderMessageKind {
     pub fn print(&self) {
         match self {
             LeaderMessageKind::ErrorNotification { error } => {
-                eprintln!("{}", error);
+                logger::log_formatted_error(&DevLogSource::Rover, error);
             }
             LeaderMessageKind::CompositionSuccess { action } => {
-                eprintln!("{}successfully composed after {}", Emoji::Success, &action);
+                logger::info(
+                    &DevLogSource::Rover,
+                    format!("{}successfully composed after {}", Emoji::Success, &action),
+                );
             }
             LeaderMessageKind::LeaderSessionInfo { subgraphs } => {
                 let subgraphs = match subgraphs.len() {
@@ -448,6 +767,12 @@ impl LeaderMessageKind {
                 };
                 tracing::info!("the main `rover dev` process currently has {}", subgraphs);
             }
+            LeaderMessageKind::SessionStatus { status } => {
+                tracing::debug!(
+                    "the main `rover dev` process has been running for {} seconds",
+                    status.uptime_seconds
+                );
+            }
             LeaderMessageKind::GetVersion {
                 leader_version,
                 follower_version: _,
@@ -480,6 +805,32 @@ impl LeaderChannel {
     }
 }
 
+/// A panel with the build errors of a failed composition, which says what the router serves
+/// in the meantime, so the error stands out from the rest of the session's output
+fn get_composition_error_panel(
+    action: &str,
+    composition_err: &str,
+    last_composed_at: Option<DateTime<Utc>>,
+) -> String {
+    let mut panel = format!("╭─ composition failed after {}\n", action);
+    for line in composition_err.trim_end().lines() {
+        match line.trim_end() {
+            "" => panel.push_str("│\n"),
+            line => panel.push_str(&format!("│ {}\n", line)),
+        }
+    }
+    panel.push_str("│\n");
+    match last_composed_at {
+        Some(last_composed_at) => panel.push_str(&format!(
+            "│ the router keeps serving the supergraph composed at {}\n",
+            last_composed_at.format("%H:%M:%S UTC")
+        )),
+        None => panel.push_str("│ the router starts once the supergraph composes\n"),
+    }
+    panel.push_str("│ rover dev recomposes as soon as a subgraph changes again\n╰─");
+    panel
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -500,4 +851,21 @@ mod tests {
             .to_string()
         )
     }
+
+    #[test]
+    fn composition_error_panel_says_what_the_router_serves() {
+        let panel = get_composition_error_panel(
+            "updating the 'products' subgraph",
+            "error[E029]: Encountered 1 build error while trying to build a supergraph.\n\nINVALID_GRAPHQL: Unknown type Product\n",
+            Some("2022-10-14T09:30:05Z".parse().unwrap()),
+        );
+        assert_eq!(
+            panel,
+            "╭─ composition failed after updating the 'products' subgraph\n│ error[E029]: Encountered 1 build error while trying to build a supergraph.\n│\n│ INVALID_GRAPHQL: Unknown type Product\n│\n│ the router keeps serving the supergraph composed at 09:30:05 UTC\n│ rover dev recomposes as soon as a subgraph changes again\n╰─"
+        );
+        assert!(
+            get_composition_error_panel("adding the 'products' subgraph", "error", None)
+                .contains("the router starts once the supergraph composes")
+        );
+    }
 }
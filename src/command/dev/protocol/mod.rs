@@ -1,9 +1,11 @@
 mod follower;
+mod ipc;
 mod leader;
 mod socket;
 mod types;
 
 pub use follower::*;
+pub(crate) use ipc::*;
 pub use leader::*;
 pub(crate) use socket::*;
 pub use types::*;
@@ -6,15 +6,20 @@ use std::{
     io::{self, BufRead, BufReader, Write},
 };
 
+use super::ipc_check_client;
+use crate::command::dev::logger::{self, DevLogSource};
 use crate::RoverResult;
 
 pub(crate) fn handle_socket_error(
     conn: io::Result<LocalSocketStream>,
 ) -> Option<LocalSocketStream> {
-    match conn {
+    match conn.and_then(|stream| ipc_check_client(&stream).map(|_| stream)) {
         Ok(val) => Some(val),
         Err(error) => {
-            eprintln!("incoming connection failed: {}", error);
+            logger::warn(
+                &DevLogSource::Rover,
+                format!("incoming connection failed: {}", error),
+            );
             None
         }
     }
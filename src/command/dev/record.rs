@@ -0,0 +1,337 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Write,
+    net::{Ipv4Addr, TcpListener},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context};
+use apollo_federation_types::config::{SchemaSource, SubgraphConfig};
+use camino::{Utf8Path, Utf8PathBuf};
+use reqwest::{blocking::Client, Url};
+use rover_std::{Emoji, Fs, Style};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    command::dev::{
+        do_dev::log_err_and_continue,
+        http::{self, HttpRequest, HttpResponse},
+        logger::{self, DevLogSource},
+        protocol::SubgraphName,
+    },
+    utils::schema_mock::MockSchema,
+    RoverError, RoverErrorSuggestion, RoverResult,
+};
+
+/// Headers that describe the connection to the proxy instead of the request, which aren't passed on
+/// to the subgraph. Responses are recorded uncompressed, so `accept-encoding` isn't either
+const SKIPPED_HEADERS: &[&str] = &[
+    "host",
+    "content-length",
+    "connection",
+    "transfer-encoding",
+    "accept-encoding",
+];
+
+/// A request the router sent to a subgraph, and the subgraph's response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Recording {
+    request: Value,
+    response: Value,
+}
+
+impl Recording {
+    /// Whether this recording answers `request`, which it does when they have the same
+    /// `query`, `operationName`, and `variables`
+    fn answers(&self, request: &Value) -> bool {
+        ["query", "operationName", "variables"]
+            .iter()
+            .all(|key| self.request.get(key) == request.get(key))
+    }
+}
+
+/// Records the requests the router sends to each subgraph of a `rover dev --record` session,
+/// with the subgraph's responses, by sending them through a proxy that passes them on.
+///
+/// Each subgraph's recordings are written to `<name>.jsonl` in the recording directory,
+/// one per line, next to its latest schema in `<name>.graphql`
+#[derive(Debug, Clone)]
+pub struct DevRecorder {
+    record_dir: Utf8PathBuf,
+    proxies: Arc<Mutex<HashMap<SubgraphName, RecordingProxy>>>,
+    client: Client,
+}
+
+#[derive(Debug)]
+struct RecordingProxy {
+    url: Url,
+    routing_url: Arc<Mutex<Url>>,
+}
+
+impl DevRecorder {
+    pub fn new(record_dir: &Utf8Path) -> RoverResult<Self> {
+        Fs::create_dir_all(record_dir)?;
+        Ok(Self {
+            record_dir: record_dir.to_path_buf(),
+            proxies: Arc::new(Mutex::new(HashMap::new())),
+            client: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .context("could not create the client to record subgraph requests with")?,
+        })
+    }
+
+    /// The URL the router should send a subgraph's requests to, which records them before
+    /// passing them on to `routing_url`. This also keeps the subgraph's schema next to its
+    /// recordings, so `--replay` can compose it
+    pub fn record_subgraph(&self, name: &str, routing_url: &Url, sdl: &str) -> RoverResult<Url> {
+        Fs::write_file(get_schema_path(&self.record_dir, name), sdl)?;
+        let mut proxies = self.proxies.lock().map_err(|_| {
+            anyhow!(
+                "could not find the recording proxy of the '{}' subgraph",
+                name
+            )
+        })?;
+        if let Some(proxy) = proxies.get(name) {
+            if let Ok(mut proxy_routing_url) = proxy.routing_url.lock() {
+                *proxy_routing_url = routing_url.clone();
+            }
+            return Ok(proxy.url.clone());
+        }
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .context("could not find a free port to record subgraph requests on")?;
+        let proxy = RecordingProxy {
+            url: format!("http://{}", listener.local_addr()?).parse()?,
+            routing_url: Arc::new(Mutex::new(routing_url.clone())),
+        };
+        logger::info(
+            &DevLogSource::Subgraph(name.to_string()),
+            format!(
+                "{}recording requests to {} in {}",
+                Emoji::Memo,
+                routing_url,
+                Style::Path.paint(get_recordings_path(&self.record_dir, name))
+            ),
+        );
+        let url = proxy.url.clone();
+        let proxy_routing_url = proxy.routing_url.clone();
+        let recordings_path = get_recordings_path(&self.record_dir, name);
+        let client = self.client.clone();
+        http::serve(listener, "recorded subgraph", move |request| {
+            let routing_url = proxy_routing_url
+                .lock()
+                .map_err(|_| anyhow!("could not find where to send the recorded request"))?
+                .clone();
+            record(request, &client, &routing_url, &recordings_path)
+        });
+        proxies.insert(name.to_string(), proxy);
+        Ok(url)
+    }
+}
+
+/// Passes a request on to the subgraph at `routing_url`, and records it with the subgraph's response
+fn record(
+    request: HttpRequest,
+    client: &Client,
+    routing_url: &Url,
+    recordings_path: &Utf8Path,
+) -> RoverResult<HttpResponse> {
+    if !request.is_post() {
+        return Ok(HttpResponse::graphql_error(
+            "405 Method Not Allowed",
+            "Recorded subgraphs only accept POST requests",
+        ));
+    }
+
+    let mut subgraph_request = client.post(routing_url.clone()).body(request.body.clone());
+    for (name, value) in &request.headers {
+        if !SKIPPED_HEADERS.contains(&name.to_lowercase().as_str()) {
+            subgraph_request = subgraph_request.header(name, value);
+        }
+    }
+    let subgraph_response = match subgraph_request.send() {
+        Ok(subgraph_response) => subgraph_response,
+        Err(e) => {
+            return Ok(HttpResponse::graphql_error(
+                "502 Bad Gateway",
+                format!("could not reach the subgraph at {}: {}", routing_url, e),
+            ))
+        }
+    };
+    let status = subgraph_response.status();
+    let body = subgraph_response
+        .text()
+        .context("could not read the response of the subgraph")?;
+
+    // only GraphQL responses can be replayed, so anything else isn't recorded
+    if let (true, Ok(request), Ok(response)) = (
+        status.is_success(),
+        serde_json::from_slice::<Value>(&request.body),
+        serde_json::from_str::<Value>(&body),
+    ) {
+        let _ = write_recording(recordings_path, &Recording { request, response })
+            .map_err(log_err_and_continue);
+    }
+    Ok(HttpResponse::new(
+        format!(
+            "{} {}",
+            status.as_u16(),
+            status.canonical_reason().unwrap_or_default()
+        ),
+        "application/json",
+        body,
+    ))
+}
+
+fn write_recording(recordings_path: &Utf8Path, recording: &Recording) -> RoverResult<()> {
+    let mut recordings = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(recordings_path)
+        .with_context(|| format!("could not open {}", recordings_path))?;
+    // each recording is written at once, so recordings of concurrent requests don't interleave
+    let line = format!("{}\n", serde_json::to_string(recording)?);
+    recordings
+        .write_all(line.as_bytes())
+        .with_context(|| format!("could not write to {}", recordings_path))?;
+    Ok(())
+}
+
+/// Serves every subgraph recorded in `replay_dir` by `--record` on a free port of its own,
+/// returning the subgraphs to add to the session.
+///
+/// Each request is answered with the latest recording of the same operation, or with
+/// fake values from the subgraph's schema if it wasn't recorded, like a `--mock` subgraph
+pub fn replay_subgraphs(replay_dir: &Utf8Path) -> RoverResult<BTreeMap<String, SubgraphConfig>> {
+    let mut subgraphs = BTreeMap::new();
+    for entry in Fs::get_dir_entries(replay_dir)?.flatten() {
+        let schema_path = entry.path();
+        let name = match (schema_path.extension(), schema_path.file_stem()) {
+            (Some("graphql"), Some(name)) => name.to_string(),
+            _ => continue,
+        };
+        let recordings = read_recordings(&get_recordings_path(replay_dir, &name))?;
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .context("could not find a free port to replay a recorded subgraph on")?;
+        let url = format!("http://{}", listener.local_addr()?);
+        logger::info(
+            &DevLogSource::Subgraph(name.clone()),
+            format!(
+                "{}replaying {} recorded {} at {}",
+                Emoji::Listen,
+                recordings.len(),
+                match recordings.len() {
+                    1 => "request",
+                    _ => "requests",
+                },
+                url
+            ),
+        );
+        let sdl = Fs::read_file(schema_path)?;
+        // the session watches the schema from wherever it runs, so its path doesn't depend on `replay_dir`
+        let schema_path = Utf8PathBuf::try_from(std::fs::canonicalize(schema_path)?)?;
+        http::serve(listener, "replayed subgraph", move |request| {
+            replay(request, &recordings, &sdl)
+        });
+        subgraphs.insert(
+            name,
+            SubgraphConfig {
+                routing_url: Some(url),
+                schema: SchemaSource::File { file: schema_path },
+            },
+        );
+    }
+    if subgraphs.is_empty() {
+        let mut err = RoverError::new(anyhow!(
+            "There are no recorded subgraphs in {}.",
+            replay_dir
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+            "Record a session first with `rover dev --record {}`.",
+            replay_dir
+        )));
+        return Err(err);
+    }
+    Ok(subgraphs)
+}
+
+/// Answers a request with its latest recording, or a mocked response without one
+fn replay(request: HttpRequest, recordings: &[Recording], sdl: &str) -> RoverResult<HttpResponse> {
+    if !request.is_post() {
+        return Ok(HttpResponse::graphql_error(
+            "405 Method Not Allowed",
+            "Replayed subgraphs only accept POST requests",
+        ));
+    }
+    Ok(match serde_json::from_slice::<Value>(&request.body) {
+        Ok(request) => match recordings.iter().rev().find(|r| r.answers(&request)) {
+            Some(recording) => HttpResponse::json("200 OK", &recording.response),
+            None => HttpResponse::json("200 OK", &MockSchema::new(sdl)?.execute(&request)),
+        },
+        Err(e) => HttpResponse::graphql_error(
+            "400 Bad Request",
+            format!("The request is not valid JSON: {}", e),
+        ),
+    })
+}
+
+/// The recordings of a subgraph, which it doesn't have if it never received a request
+fn read_recordings(recordings_path: &Utf8Path) -> RoverResult<Vec<Recording>> {
+    if !recordings_path.exists() {
+        return Ok(Vec::new());
+    }
+    Fs::read_file(recordings_path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(|e| {
+                RoverError::new(anyhow!(
+                    "{} has a recording that is not valid: {}",
+                    recordings_path,
+                    e
+                ))
+            })
+        })
+        .collect()
+}
+
+fn get_recordings_path(dir: &Utf8Path, subgraph_name: &str) -> Utf8PathBuf {
+    dir.join(format!("{}.jsonl", subgraph_name))
+}
+
+fn get_schema_path(dir: &Utf8Path, subgraph_name: &str) -> Utf8PathBuf {
+    dir.join(format!("{}.graphql", subgraph_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn it_matches_recordings_by_operation() {
+        let recording = Recording {
+            request: json!({
+                "query": "query GetProduct($id: ID!) { product(id: $id) { name } }",
+                "operationName": "GetProduct",
+                "variables": { "id": "1" },
+            }),
+            response: json!({ "data": { "product": { "name": "Table" } } }),
+        };
+        assert!(recording.answers(&json!({
+            "query": "query GetProduct($id: ID!) { product(id: $id) { name } }",
+            "operationName": "GetProduct",
+            "variables": { "id": "1" },
+            "extensions": {},
+        })));
+        assert!(!recording.answers(&json!({
+            "query": "query GetProduct($id: ID!) { product(id: $id) { name } }",
+            "operationName": "GetProduct",
+            "variables": { "id": "2" },
+        })));
+    }
+}
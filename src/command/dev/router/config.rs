@@ -1,10 +1,10 @@
 use anyhow::Context;
+use apollo_federation_types::config::RouterVersion;
 use buildstructor::buildstructor;
 use camino::Utf8PathBuf;
 use crossbeam_channel::{unbounded, Receiver};
-use rover_std::{Emoji, Fs};
+use rover_std::{tmp::SecureTempDir, Emoji, Fs};
 use serde_json::json;
-use tempdir::TempDir;
 
 use std::{
     net::SocketAddr,
@@ -13,8 +13,14 @@ use std::{
 };
 
 use crate::{
-    command::dev::{do_dev::log_err_and_continue, SupergraphOpts},
-    RoverError, RoverResult,
+    command::dev::{
+        do_dev::log_err_and_continue,
+        logger::{self, DevLogSource},
+        protocol::get_ipc_address,
+        router::{RouterMetrics, RouterSubscriptions, RouterTls},
+        SupergraphOpts,
+    },
+    RoverResult,
 };
 
 const DEFAULT_ROUTER_PORT: u16 = 3000;
@@ -27,54 +33,98 @@ pub struct RouterConfigHandler {
     /// the router configuration reader
     config_reader: RouterConfigReader,
 
+    /// the temp directory to write the patched router config and composed schema out to
+    tmp_dir: Arc<SecureTempDir>,
+
     /// the temp path to write the patched router config out to
     tmp_router_config_path: Utf8PathBuf,
 
-    /// the temp path to write the composed schema out to
-    tmp_supergraph_schema_path: Utf8PathBuf,
-
     /// the current state of the router config
     config_state: Arc<Mutex<RouterConfigState>>,
-}
 
-impl TryFrom<&SupergraphOpts> for RouterConfigHandler {
-    type Error = RoverError;
-    fn try_from(value: &SupergraphOpts) -> Result<Self, Self::Error> {
-        Self::new(
-            value.router_config_path.clone(),
-            value.supergraph_address.clone(),
-            value.supergraph_port,
-        )
-    }
+    /// the `--session-name` that other `rover dev` processes find this session by, instead of its router address
+    session_name: Option<String>,
+
+    /// the address the session was started at, which other `rover dev` processes find it by
+    /// even if the router had to listen on a different port
+    session_router_address: SocketAddr,
+
+    /// the `--router-version` to run, instead of the latest version
+    router_version: Option<RouterVersion>,
 }
 
 impl RouterConfigHandler {
+    /// Create a [`RouterConfigHandler`] from the `rover dev` CLI options
+    pub fn from_opts(
+        opts: &SupergraphOpts,
+        override_install_path: Option<Utf8PathBuf>,
+    ) -> RoverResult<Self> {
+        Ok(Self::new(
+            opts.router_config_path.clone(),
+            opts.supergraph_address.clone(),
+            opts.supergraph_port,
+            RouterTls::from_opts(opts, override_install_path)?,
+            RouterSubscriptions::from_opts(opts)?,
+            RouterMetrics::from_opts(opts)?,
+        )?
+        .with_session_name(opts.session_name.clone())
+        .with_router_version(opts.router_version.clone()))
+    }
+
     /// Create a [`RouterConfigHandler`]
     pub fn new(
         input_config_path: Option<Utf8PathBuf>,
         ip_override: Option<String>,
         port_override: Option<u16>,
+        tls: Option<RouterTls>,
+        subscriptions: Option<RouterSubscriptions>,
+        metrics: Option<RouterMetrics>,
     ) -> RoverResult<Self> {
-        let tmp_dir = TempDir::new("supergraph")?;
-        let tmp_config_dir_path = Utf8PathBuf::try_from(tmp_dir.into_path())?;
-
-        let tmp_router_config_path = tmp_config_dir_path.join("router.yaml");
-        let tmp_supergraph_schema_path = tmp_config_dir_path.join("supergraph.graphql");
+        let tmp_dir = SecureTempDir::new("supergraph")?;
 
-        let config_reader = RouterConfigReader::new(input_config_path, ip_override, port_override);
+        let config_reader = RouterConfigReader::new(
+            input_config_path,
+            ip_override,
+            port_override,
+            tls,
+            subscriptions,
+            metrics,
+        );
 
         let config_state = config_reader.read()?;
 
-        Fs::write_file(&tmp_router_config_path, config_state.get_config())?;
+        let tmp_router_config_path =
+            tmp_dir.write_file("router.yaml", config_state.get_config())?;
+        let session_router_address = config_state.get_socket_address()?;
 
         Ok(Self {
+            tmp_dir: Arc::new(tmp_dir),
             config_reader,
             config_state: Arc::new(Mutex::new(config_state)),
             tmp_router_config_path,
-            tmp_supergraph_schema_path,
+            session_name: None,
+            session_router_address,
+            router_version: None,
         })
     }
 
+    /// Names the session, so its socket is found by its name rather than by its router address
+    pub fn with_session_name(mut self, session_name: Option<String>) -> Self {
+        self.session_name = session_name;
+        self
+    }
+
+    /// Pins the router to a `--router-version`, instead of the latest version
+    pub fn with_router_version(mut self, router_version: Option<RouterVersion>) -> Self {
+        self.router_version = router_version;
+        self
+    }
+
+    /// The `--session-name` of the session, if it has one
+    pub fn get_session_name(&self) -> Option<String> {
+        self.session_name.clone()
+    }
+
     /// Start up the router config handler
     pub fn start(self) -> RoverResult<()> {
         // if a router config was passed, start watching it in the background for changes
@@ -86,7 +136,10 @@ impl RouterConfigHandler {
                     .expect("could not watch router config");
                 let _ = Fs::write_file(&self.tmp_router_config_path, config_state.get_config())
                     .map_err(|e| log_err_and_continue(e.into()));
-                eprintln!("{}successfully updated router config", Emoji::Success);
+                logger::info(
+                    &DevLogSource::Rover,
+                    format!("{}successfully updated router config", Emoji::Success),
+                );
                 *self
                     .config_state
                     .lock()
@@ -105,22 +158,60 @@ impl RouterConfigHandler {
             .get_socket_address()
     }
 
+    /// Whether the router listens on the default port, which `rover dev` can move off of when it's taken
+    pub fn is_default_port(&self) -> bool {
+        self.config_state
+            .lock()
+            .expect("could not acquire lock on router config state")
+            .is_default_port()
+    }
+
+    /// Moves the router to a different port, for when the default port is taken by another process
+    pub fn set_router_port(&mut self, port: u16) -> RoverResult<()> {
+        self.config_reader.port_override = Some(port);
+        let config_state = self.config_reader.read()?;
+        Fs::write_file(&self.tmp_router_config_path, config_state.get_config())?;
+        *self
+            .config_state
+            .lock()
+            .expect("could not acquire lock on router config state") = config_state;
+        Ok(())
+    }
+
+    /// The address the session was started at, before the router may have moved to a free port
+    pub fn get_session_router_address(&self) -> SocketAddr {
+        self.session_router_address
+    }
+
+    /// The `--router-version` to run, if one was passed
+    pub fn get_router_version(&self) -> Option<RouterVersion> {
+        self.router_version.clone()
+    }
+
+    /// The URL the router serves its Prometheus metrics at, when the session has a health endpoint
+    pub fn get_router_metrics_url(&self) -> Option<String> {
+        self.config_reader
+            .metrics
+            .as_ref()
+            .map(RouterMetrics::get_url)
+    }
+
+    /// Whether the router is served over HTTPS
+    pub fn uses_tls(&self) -> bool {
+        self.config_reader.tls.is_some()
+    }
+
     /// Get the name of the interprocess socket address to communicate with other rover dev sessions
     pub fn get_ipc_address(&self) -> RoverResult<String> {
-        let socket_name = format!("supergraph-{}.sock", self.get_router_address()?);
-        {
-            use interprocess::local_socket::NameTypeSupport::{self, *};
-            let socket_prefix = match NameTypeSupport::query() {
-                OnlyPaths | Both => "/tmp/",
-                OnlyNamespaced => "@",
-            };
-            Ok(format!("{}{}", socket_prefix, socket_name))
-        }
+        Ok(get_ipc_address(
+            &self.session_router_address,
+            self.session_name.as_deref(),
+        ))
     }
 
-    /// The path to the composed supergraph schema
+    /// The temp path to write the composed supergraph schema out to
     pub fn get_supergraph_schema_path(&self) -> Utf8PathBuf {
-        self.tmp_supergraph_schema_path.clone()
+        self.tmp_dir.path().join("supergraph.graphql")
     }
 
     /// The path to the patched router config YAML
@@ -129,6 +220,18 @@ impl RouterConfigHandler {
     }
 }
 
+/// The address the router listens on when neither the CLI options nor a router config set one
+pub fn get_default_router_address(
+    ip_override: Option<&str>,
+    port_override: Option<u16>,
+) -> RoverResult<SocketAddr> {
+    Ok(SocketAddr::from_str(&format!(
+        "{}:{}",
+        ip_override.unwrap_or(DEFAULT_ROUTER_ADDRESS),
+        port_override.unwrap_or(DEFAULT_ROUTER_PORT)
+    ))?)
+}
+
 #[derive(Debug, Clone)]
 pub struct RouterConfigState {
     /// the IP address for the router to listen on
@@ -137,6 +240,9 @@ pub struct RouterConfigState {
     /// the port for the router to listen on
     port: String,
 
+    /// whether the port is the default one, rather than one from the CLI options or the router config
+    default_port: bool,
+
     /// the resolved YAML content
     config: String,
 }
@@ -145,8 +251,13 @@ pub struct RouterConfigState {
 impl RouterConfigState {
     #[builder]
     /// Create a new [`RouterConfigState`]
-    pub fn new(ip: String, port: String, config: String) -> Self {
-        Self { ip, port, config }
+    pub fn new(ip: String, port: String, default_port: bool, config: String) -> Self {
+        Self {
+            ip,
+            port,
+            default_port,
+            config,
+        }
     }
 
     /// Get the socket address
@@ -158,6 +269,11 @@ impl RouterConfigState {
         ))?)
     }
 
+    /// Whether the port is the default one, which `rover dev` can move off of when it's taken
+    pub fn is_default_port(&self) -> bool {
+        self.default_port
+    }
+
     /// Get the config contents
     pub fn get_config(&self) -> String {
         self.config.clone()
@@ -169,6 +285,9 @@ struct RouterConfigReader {
     input_config_path: Option<Utf8PathBuf>,
     ip_override: Option<String>,
     port_override: Option<u16>,
+    tls: Option<RouterTls>,
+    subscriptions: Option<RouterSubscriptions>,
+    metrics: Option<RouterMetrics>,
 }
 
 impl RouterConfigReader {
@@ -176,19 +295,26 @@ impl RouterConfigReader {
         input_config_path: Option<Utf8PathBuf>,
         ip_override: Option<String>,
         port_override: Option<u16>,
+        tls: Option<RouterTls>,
+        subscriptions: Option<RouterSubscriptions>,
+        metrics: Option<RouterMetrics>,
     ) -> Self {
         Self {
             input_config_path,
             ip_override,
             port_override,
+            tls,
+            subscriptions,
+            metrics,
         }
     }
 
     fn read(&self) -> RoverResult<RouterConfigState> {
+        let mut default_port = self.port_override.is_none();
         let (ip, port, config) = if let Some(input_config_path) = &self.input_config_path {
             if Fs::assert_path_exists(input_config_path).is_err() {
                 let (ip, port, config) = self.get_config_from_opts();
-                eprintln!("{}{input_config_path} does not exist, creating a router config from CLI options.", Emoji::Action);
+                logger::info(&DevLogSource::Rover, format!("{}{input_config_path} does not exist, creating a router config from CLI options.", Emoji::Action));
                 Fs::write_file(input_config_path, &config)?;
                 (ip, port, config)
             } else {
@@ -209,6 +335,7 @@ impl RouterConfigReader {
                 } else {
                     (None, None)
                 };
+                default_port = default_port && yaml_port.is_none();
 
                 // resolve the ip and port
                 // precedence is:
@@ -254,9 +381,24 @@ impl RouterConfigReader {
             self.get_config_from_opts()
         };
 
+        // the certificate is only added to the patched config, never to the user's own file
+        let config = match &self.tls {
+            Some(tls) => tls.patch_config(&config)?,
+            None => config,
+        };
+        let config = match &self.subscriptions {
+            Some(subscriptions) => subscriptions.patch_config(&config)?,
+            None => config,
+        };
+        let config = match &self.metrics {
+            Some(metrics) => metrics.patch_config(&config)?,
+            None => config,
+        };
+
         Ok(RouterConfigState::builder()
             .ip(ip)
             .port(port)
+            .default_port(default_port)
             .config(config)
             .build())
     }
@@ -275,7 +417,10 @@ impl RouterConfigReader {
                         .send(results)
                         .expect("could not update router configuration file");
                 } else {
-                    eprintln!("invalid router configuration, continuing to use old config");
+                    logger::warn(
+                        &DevLogSource::Rover,
+                        "invalid router configuration, continuing to use old config",
+                    );
                 }
             });
             Some(state_rx)
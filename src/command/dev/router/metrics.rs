@@ -0,0 +1,89 @@
+use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+
+use anyhow::Context;
+use serde_json::json;
+
+use crate::{command::dev::SupergraphOpts, RoverResult};
+
+/// Where the router serves its Prometheus metrics, for the session's `/metrics` endpoint to pass along
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouterMetrics {
+    listen: SocketAddr,
+}
+
+impl RouterMetrics {
+    /// The router's metrics address, if `--health-port` was passed, on a free port on localhost
+    pub fn from_opts(opts: &SupergraphOpts) -> RoverResult<Option<Self>> {
+        if opts.health_port.is_none() {
+            return Ok(None);
+        }
+        let listen = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .and_then(|listener| listener.local_addr())
+            .context("could not find a free port for the router's metrics")?;
+        Ok(Some(Self { listen }))
+    }
+
+    /// The URL the router serves its metrics at
+    pub fn get_url(&self) -> String {
+        format!("http://{}/metrics", self.listen)
+    }
+
+    /// Enables the router's Prometheus metrics, unless the router config already configures them
+    pub fn patch_config(&self, config: &str) -> RoverResult<String> {
+        let mut yaml: serde_yaml::Mapping = serde_yaml::from_str(config)?;
+        let telemetry_key = serde_yaml::to_value("telemetry")?;
+        let metrics_key = serde_yaml::to_value("metrics")?;
+        let prometheus_key = serde_yaml::to_value("prometheus")?;
+        let mut telemetry = yaml
+            .get(&telemetry_key)
+            .and_then(|telemetry| telemetry.as_mapping())
+            .cloned()
+            .unwrap_or_default();
+        let mut metrics = telemetry
+            .get(&metrics_key)
+            .and_then(|metrics| metrics.as_mapping())
+            .cloned()
+            .unwrap_or_default();
+        if metrics.contains_key(&prometheus_key) {
+            tracing::info!("using the prometheus settings of the router config");
+            return Ok(config.to_string());
+        }
+        metrics.insert(
+            prometheus_key,
+            serde_yaml::to_value(json!({
+                "enabled": true,
+                "listen": self.listen.to_string(),
+                "path": "/metrics",
+            }))?,
+        );
+        telemetry.insert(metrics_key, serde_yaml::Value::Mapping(metrics));
+        yaml.insert(telemetry_key, serde_yaml::Value::Mapping(telemetry));
+        Ok(serde_yaml::to_string(&yaml)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_only_enables_prometheus_without_router_config_settings() {
+        let metrics = RouterMetrics {
+            listen: "127.0.0.1:9090".parse().unwrap(),
+        };
+        let config = metrics
+            .patch_config("telemetry:\n  tracing:\n    trace_config:\n      service_name: router\n")
+            .unwrap();
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&config).unwrap();
+        let prometheus = &yaml["telemetry"]["metrics"]["prometheus"];
+        assert_eq!(prometheus["enabled"], true);
+        assert_eq!(prometheus["listen"], "127.0.0.1:9090");
+        assert_eq!(
+            yaml["telemetry"]["tracing"]["trace_config"]["service_name"],
+            "router"
+        );
+
+        let config = "telemetry:\n  metrics:\n    prometheus:\n      enabled: false\n";
+        assert_eq!(metrics.patch_config(config).unwrap(), config);
+    }
+}
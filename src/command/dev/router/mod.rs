@@ -1,7 +1,13 @@
 mod command;
 mod config;
+mod metrics;
 mod runner;
+mod subscriptions;
+mod tls;
 
 pub use command::{BackgroundTask, BackgroundTaskLog};
-pub use config::RouterConfigHandler;
+pub use config::{get_default_router_address, RouterConfigHandler};
+pub use metrics::RouterMetrics;
 pub use runner::RouterRunner;
+pub use subscriptions::RouterSubscriptions;
+pub use tls::RouterTls;
@@ -3,16 +3,16 @@ use apollo_federation_types::config::RouterVersion;
 use camino::Utf8PathBuf;
 use crossbeam_channel::bounded;
 use reqwest::blocking::Client;
-use rover_std::{Emoji, Style};
-use semver::Version;
+use rover_std::Emoji;
 
 use std::net::SocketAddr;
 use std::time::{Duration, Instant};
 
 use crate::command::dev::{
     do_dev::log_err_and_continue,
+    get_dev_router_version,
+    logger::{self, DevLogSource},
     router::{BackgroundTask, BackgroundTaskLog},
-    OVERRIDE_DEV_ROUTER_VERSION,
 };
 use crate::command::install::Plugin;
 use crate::command::Install;
@@ -26,6 +26,8 @@ pub struct RouterRunner {
     router_config_path: Utf8PathBuf,
     plugin_opts: PluginOpts,
     router_socket_addr: SocketAddr,
+    router_tls: bool,
+    router_version: Option<RouterVersion>,
     override_install_path: Option<Utf8PathBuf>,
     client_config: StudioClientConfig,
     plugin_exe: Option<Utf8PathBuf>,
@@ -38,6 +40,7 @@ impl RouterRunner {
         router_config_path: Utf8PathBuf,
         plugin_opts: PluginOpts,
         router_socket_addr: SocketAddr,
+        router_tls: bool,
         override_install_path: Option<Utf8PathBuf>,
         client_config: StudioClientConfig,
     ) -> Self {
@@ -46,6 +49,8 @@ impl RouterRunner {
             router_config_path,
             plugin_opts,
             router_socket_addr,
+            router_tls,
+            router_version: None,
             override_install_path,
             client_config,
             router_handle: None,
@@ -53,11 +58,14 @@ impl RouterRunner {
         }
     }
 
+    /// Runs a `--router-version` of the router, which takes precedence over `APOLLO_ROVER_DEV_ROUTER_VERSION`
+    pub fn with_router_version(mut self, router_version: Option<RouterVersion>) -> Self {
+        self.router_version = router_version;
+        self
+    }
+
     fn install_command(&self) -> RoverResult<Install> {
-        let plugin = match &*OVERRIDE_DEV_ROUTER_VERSION {
-            Some(version) => Plugin::Router(RouterVersion::Exact(Version::parse(version)?)),
-            None => Plugin::Router(RouterVersion::Latest),
-        };
+        let plugin = Plugin::Router(get_dev_router_version(self.router_version.clone())?);
         Ok(Install {
             force: false,
             plugin: Some(plugin),
@@ -89,11 +97,52 @@ impl RouterRunner {
         ))
     }
 
+    /// The scheme the router is served with
+    fn get_scheme(&self) -> &'static str {
+        if self.router_tls {
+            "https"
+        } else {
+            "http"
+        }
+    }
+
+    /// The URL to query the router at from a browser on this machine
+    pub fn get_router_url(&self) -> String {
+        format!(
+            "{}://{}",
+            self.get_scheme(),
+            self.router_socket_addr
+                .to_string()
+                .replace("127.0.0.1", "localhost")
+                .replace("0.0.0.0", "localhost")
+                .replace("[::]", "localhost")
+                .replace("[::1]", "localhost")
+        )
+    }
+
+    /// A client for checking on the router, which trusts the self-signed certificate it may be served with
+    fn get_client(&self) -> RoverResult<Client> {
+        if self.router_tls {
+            Ok(self
+                .client_config
+                .get_builder()
+                .accept_invalid_certs(true)
+                .accept_invalid_hostnames(true)
+                .build()?)
+        } else {
+            Ok(self.client_config.get_reqwest_client()?)
+        }
+    }
+
     pub fn wait_for_startup(&mut self, client: Client) -> RoverResult<()> {
         let mut ready = false;
         let now = Instant::now();
         let seconds = 5;
-        let endpoint = format!("http://{}/?query={{__typename}}", &self.router_socket_addr);
+        let endpoint = format!(
+            "{}://{}/?query={{__typename}}",
+            self.get_scheme(),
+            &self.router_socket_addr
+        );
         while !ready && now.elapsed() < Duration::from_secs(seconds) {
             let _ = client
                 .get(&endpoint)
@@ -107,16 +156,13 @@ impl RouterRunner {
         }
 
         if ready {
-            eprintln!(
-                "{}your supergraph is running! head to http://{} to query your supergraph",
-                Emoji::Rocket,
-                &self
-                    .router_socket_addr
-                    .to_string()
-                    .replace("127.0.0.1", "localhost")
-                    .replace("0.0.0.0", "localhost")
-                    .replace("[::]", "localhost")
-                    .replace("[::1]", "localhost")
+            logger::info(
+                &DevLogSource::Rover,
+                format!(
+                    "{}your supergraph is running! head to {} to query your supergraph",
+                    Emoji::Rocket,
+                    self.get_router_url()
+                ),
             );
             Ok(())
         } else {
@@ -133,7 +179,8 @@ impl RouterRunner {
         while ready && now.elapsed() < Duration::from_secs(seconds) {
             let _ = client
                 .get(format!(
-                    "http://{}/?query={{__typename}}",
+                    "{}://{}/?query={{__typename}}",
+                    self.get_scheme(),
                     &self.router_socket_addr
                 ))
                 .header("Content-Type", "application/json")
@@ -155,7 +202,7 @@ impl RouterRunner {
 
     pub fn spawn(&mut self) -> RoverResult<()> {
         if self.router_handle.is_none() {
-            let client = self.client_config.get_reqwest_client()?;
+            let client = self.get_client()?;
             self.maybe_install_router()?;
             let (router_log_sender, router_log_receiver) = bounded(0);
             let router_handle =
@@ -165,24 +212,11 @@ impl RouterRunner {
             rayon::spawn(move || loop {
                 if let Ok(BackgroundTaskLog::Stdout(stdout)) = router_log_receiver.recv() {
                     if let Ok(stdout) = serde_json::from_str::<serde_json::Value>(&stdout) {
-                        let fields = &stdout["fields"];
-                        if let Some(level) = stdout["level"].as_str() {
-                            if let Some(message) = fields["message"].as_str() {
-                                let warn_prefix = Style::WarningPrefix.paint("WARN:");
-                                let error_prefix = Style::ErrorPrefix.paint("ERROR:");
-                                if let Some(router_span) = stdout["target"].as_str() {
-                                    match level {
-                                        "INFO" => tracing::info!(%message, %router_span),
-                                        "DEBUG" => tracing::debug!(%message, %router_span),
-                                        "TRACE" => tracing::trace!(%message, %router_span),
-                                        "WARN" => eprintln!("{} {}", warn_prefix, &message),
-                                        "ERROR" => {
-                                            eprintln!("{} {}", error_prefix, &message)
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                            }
+                        let level = stdout["level"].as_str().and_then(|l| l.parse().ok());
+                        if let (Some(level), Some(message)) =
+                            (level, stdout["fields"]["message"].as_str())
+                        {
+                            logger::log(&DevLogSource::Router, level, message);
                         }
                     }
                 }
@@ -197,11 +231,18 @@ impl RouterRunner {
         }
     }
 
+    /// The process ID of the router, while it's running
+    pub fn get_pid(&self) -> Option<u32> {
+        self.router_handle
+            .as_ref()
+            .map(|router_handle| router_handle.id())
+    }
+
     pub fn kill(&mut self) -> RoverResult<()> {
         if self.router_handle.is_some() {
             tracing::info!("killing the router");
             self.router_handle = None;
-            if let Ok(client) = self.client_config.get_reqwest_client() {
+            if let Ok(client) = self.get_client() {
                 let _ = self.wait_for_stop(client).map_err(log_err_and_continue);
             }
         }
@@ -0,0 +1,118 @@
+use anyhow::anyhow;
+use apollo_federation_types::config::RouterVersion;
+use semver::Version;
+use serde_json::json;
+
+use crate::{
+    command::dev::{get_dev_router_version, SubscriptionProtocol, SupergraphOpts},
+    RoverError, RoverErrorSuggestion, RoverResult,
+};
+
+/// The first router version with a `subscription` section in its config
+const MIN_SUBSCRIPTIONS_ROUTER_VERSION: Version = Version::new(1, 22, 0);
+
+/// How the router opens subscriptions to subgraphs, from `--subscriptions`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouterSubscriptions {
+    path: Option<String>,
+    protocol: SubscriptionProtocol,
+}
+
+impl RouterSubscriptions {
+    /// The subscription settings for the router, if `--subscriptions` was passed.
+    /// Fails if the router that would run doesn't support subscriptions
+    pub fn from_opts(opts: &SupergraphOpts) -> RoverResult<Option<Self>> {
+        if !opts.subscriptions.unwrap_or_default() {
+            return Ok(None);
+        }
+        check_router_version(&get_dev_router_version(opts.router_version.clone())?)?;
+        Ok(Some(Self {
+            path: opts.subscription_path.clone(),
+            protocol: opts
+                .subscription_protocol
+                .unwrap_or(SubscriptionProtocol::GraphqlTransportWs),
+        }))
+    }
+
+    /// Enables subscriptions in the router config for every subgraph,
+    /// unless the router config already has subscription settings of its own
+    pub fn patch_config(&self, config: &str) -> RoverResult<String> {
+        let mut yaml: serde_yaml::Mapping = serde_yaml::from_str(config)?;
+        let subscription_key = serde_yaml::to_value("subscription")?;
+        if yaml.contains_key(&subscription_key) {
+            tracing::info!("using the subscription settings of the router config");
+            return Ok(config.to_string());
+        }
+        let mut websocket = json!({ "protocol": self.protocol.get_router_name() });
+        if let Some(path) = &self.path {
+            websocket["path"] = json!(path);
+        }
+        yaml.insert(
+            subscription_key,
+            serde_yaml::to_value(json!({
+                "enabled": true,
+                "mode": {
+                    "passthrough": {
+                        "all": websocket
+                    }
+                }
+            }))?,
+        );
+        Ok(serde_yaml::to_string(&yaml)?)
+    }
+}
+
+/// Checks that `router_version` supports subscriptions. The latest router that `rover dev`
+/// installs by default predates them, so `--subscriptions` needs an exact router version
+fn check_router_version(router_version: &RouterVersion) -> RoverResult<()> {
+    match router_version {
+        RouterVersion::Exact(version) if version >= &MIN_SUBSCRIPTIONS_ROUTER_VERSION => Ok(()),
+        RouterVersion::Exact(version) => Err(RoverError::new(anyhow!(
+            "`--subscriptions` requires router >= v{}, but `rover dev` would run router v{}",
+            MIN_SUBSCRIPTIONS_ROUTER_VERSION,
+            version
+        ))),
+        RouterVersion::Latest => Err(RoverError::new(anyhow!(
+            "`--subscriptions` requires router >= v{}, which the latest router that `rover dev` installs by default predates",
+            MIN_SUBSCRIPTIONS_ROUTER_VERSION
+        ))),
+    }
+    .map_err(|mut err: RoverError| {
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+            "Pass `--router-version {}` or later, or set it with `router_version` in rover-dev.yaml or with `APOLLO_ROVER_DEV_ROUTER_VERSION`.",
+            MIN_SUBSCRIPTIONS_ROUTER_VERSION
+        )));
+        err
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_only_enables_subscriptions_without_router_config_settings() {
+        let subscriptions = RouterSubscriptions {
+            path: Some("/ws".to_string()),
+            protocol: SubscriptionProtocol::GraphqlTransportWs,
+        };
+        let config = subscriptions
+            .patch_config("supergraph:\n  listen: 127.0.0.1:3000\n")
+            .unwrap();
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&config).unwrap();
+        assert_eq!(yaml["subscription"]["enabled"], true);
+        let websocket = &yaml["subscription"]["mode"]["passthrough"]["all"];
+        assert_eq!(websocket["path"], "/ws");
+        assert_eq!(websocket["protocol"], "graphql_transport_ws");
+
+        let config = "subscription:\n  enabled: false\n";
+        assert_eq!(subscriptions.patch_config(config).unwrap(), config);
+    }
+
+    #[test]
+    fn it_requires_a_router_version_with_subscriptions() {
+        assert!(check_router_version(&RouterVersion::Exact(Version::new(1, 22, 0))).is_ok());
+        assert!(check_router_version(&RouterVersion::Exact(Version::new(1, 10, 0))).is_err());
+        assert!(check_router_version(&RouterVersion::Latest).is_err());
+    }
+}
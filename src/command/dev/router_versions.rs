@@ -0,0 +1,150 @@
+use apollo_federation_types::config::{FederationVersion, PluginVersion, RouterVersion};
+use camino::Utf8PathBuf;
+use clap::Parser;
+use rover_std::Style;
+use serde::Serialize;
+
+use crate::command::dev::logger::{self, DevLogSource};
+use crate::command::dev::{get_dev_federation_version, get_dev_router_version};
+use crate::command::install::Plugin;
+use crate::command::supergraph::{install_plugin, list_plugins, prune_plugins};
+use crate::utils::{
+    client::StudioClientConfig,
+    parsers::{parse_federation_version, parse_router_version},
+};
+use crate::{RoverOutput, RoverResult};
+
+/// The name of the router's binaries, i.e. `router-v1.10.0`
+const PLUGIN_NAME: &str = "router";
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Install {
+    /// The router versions to install. Pass `latest`, or an exact version, i.e. `1.10.0`. Pass this more than once to install several versions.
+    ///
+    /// Defaults to the version `rover dev` runs: the `router_version` of a `rover-dev.yaml` in the current directory, then `APOLLO_ROVER_DEV_ROUTER_VERSION`, then the latest router.
+    #[arg(
+        long = "router-version",
+        value_name = "VERSION",
+        value_parser = parse_router_version
+    )]
+    #[serde(skip_serializing)]
+    router_versions: Vec<RouterVersion>,
+
+    /// The federation versions to install the composition plugin for, i.e. `2` or `=2.3.1`. Pass this more than once to install several versions.
+    ///
+    /// Defaults to the version `rover dev` composes with: `APOLLO_ROVER_DEV_COMPOSITION_VERSION`, then the latest federation 2 composition.
+    #[arg(
+        long = "federation-version",
+        value_name = "VERSION",
+        value_parser = parse_federation_version
+    )]
+    #[serde(skip_serializing)]
+    federation_versions: Vec<FederationVersion>,
+
+    #[clap(flatten)]
+    elv2_license_accepter: LicenseAccepter,
+}
+
+impl Install {
+    /// Installs every router and composition plugin a `rover dev` session needs,
+    /// so later sessions of the same versions start without network access.
+    ///
+    /// `dev_router_version` is the `router_version` of the session's `rover-dev.yaml`
+    pub fn run(
+        &self,
+        override_install_path: Option<Utf8PathBuf>,
+        client_config: StudioClientConfig,
+        dev_router_version: Option<RouterVersion>,
+    ) -> RoverResult<RoverOutput> {
+        self.elv2_license_accepter
+            .require_elv2_license(&client_config)?;
+
+        let router_versions = if self.router_versions.is_empty() {
+            vec![get_dev_router_version(dev_router_version)?]
+        } else {
+            self.router_versions.clone()
+        };
+        let federation_versions = if self.federation_versions.is_empty() {
+            vec![get_dev_federation_version()?]
+        } else {
+            self.federation_versions.clone()
+        };
+
+        let mut installed_plugins = Vec::new();
+        let mut installs_latest = false;
+        for router_version in router_versions {
+            installs_latest |= matches!(router_version, RouterVersion::Latest);
+            let plugin_exe = install_plugin(
+                Plugin::Router(router_version.clone()),
+                &mut installed_plugins,
+                override_install_path.clone(),
+                client_config.clone(),
+                self.elv2_license_accepter,
+            )?;
+            logger::info(
+                &DevLogSource::Rover,
+                format!(
+                    "The router for `--router-version {}` is installed at {}",
+                    router_version.get_tarball_version(),
+                    Style::Path.paint(&plugin_exe)
+                ),
+            );
+        }
+        for federation_version in federation_versions {
+            installs_latest |= matches!(
+                federation_version,
+                FederationVersion::LatestFedOne | FederationVersion::LatestFedTwo
+            );
+            let plugin_exe = install_plugin(
+                Plugin::Supergraph(federation_version.clone()),
+                &mut installed_plugins,
+                override_install_path.clone(),
+                client_config.clone(),
+                self.elv2_license_accepter,
+            )?;
+            logger::info(
+                &DevLogSource::Rover,
+                format!(
+                    "The composition plugin for federation {} is installed at {}",
+                    federation_version.get_tarball_version(),
+                    Style::Path.paint(&plugin_exe)
+                ),
+            );
+        }
+        if installs_latest {
+            logger::info(&DevLogSource::Rover, "To run the latest versions installed here without network access, pass `--offline` to `rover dev`, which otherwise checks for newer ones.");
+        }
+        Ok(RoverOutput::InstalledPlugins(installed_plugins))
+    }
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub struct List {}
+
+impl List {
+    pub fn run(&self, override_install_path: Option<Utf8PathBuf>) -> RoverResult<RoverOutput> {
+        list_plugins(override_install_path, PLUGIN_NAME)
+    }
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Prune {
+    /// How many of the newest router versions of each major version to keep
+    #[arg(long, value_name = "COUNT", default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    keep: u64,
+
+    /// List the router versions that would be removed, without removing them
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Prune {
+    pub fn run(&self, override_install_path: Option<Utf8PathBuf>) -> RoverResult<RoverOutput> {
+        prune_plugins(
+            override_install_path,
+            PLUGIN_NAME,
+            self.keep as usize,
+            self.dry_run,
+        )
+    }
+}
@@ -2,7 +2,7 @@ use std::{net::SocketAddr, time::Duration};
 
 use crate::{
     command::dev::{
-        netstat::normalize_loopback_urls, protocol::FollowerMessenger,
+        mock::MockSubgraph, netstat::normalize_loopback_urls, protocol::FollowerMessenger,
         watcher::SubgraphSchemaWatcher,
     },
     options::OptionalSubgraphOpts,
@@ -27,7 +27,18 @@ impl OptionalSubgraphOpts {
         follower_messenger.version_check()?;
         tracing::info!("checking for existing subgraphs");
         let session_subgraphs = follower_messenger.session_subgraphs()?;
-        let url = self.prompt_for_url()?;
+        // a mocked subgraph without a `--url` is bound to a free port up front, to know its URL.
+        // one with a `--url` is only bound once the URL is known not to conflict with the router's
+        let mut mock_subgraph = None;
+        let url = match self.get_mock_schema_path() {
+            Some(mock_schema_path) if self.is_mock_on_free_port() => {
+                let mock = MockSubgraph::new(mock_schema_path, self.get_mock_address()?)?;
+                let url = mock.get_url()?;
+                mock_subgraph = Some(mock);
+                url
+            }
+            _ => self.prompt_for_url()?,
+        };
         let normalized_user_urls = normalize_loopback_urls(&url);
         let normalized_supergraph_urls = normalize_loopback_urls(
             &Url::parse(&format!("http://{}", router_socket_addr)).unwrap(),
@@ -48,7 +59,11 @@ impl OptionalSubgraphOpts {
         }
 
         let name = self.prompt_for_name()?;
-        let schema = self.prompt_for_schema()?;
+        // a mocked subgraph's schema is watched like a `--schema`
+        let schema = match self.get_mock_schema_path() {
+            Some(mock_schema_path) => Some(mock_schema_path.clone()),
+            None => self.prompt_for_schema()?,
+        };
 
         if let Some(session_subgraphs) = session_subgraphs {
             for (session_subgraph_name, session_subgraph_url) in session_subgraphs {
@@ -72,14 +87,23 @@ impl OptionalSubgraphOpts {
             }
         }
 
+        if let Some(mock_schema_path) = self.get_mock_schema_path() {
+            match mock_subgraph {
+                Some(mock_subgraph) => mock_subgraph.start()?,
+                None => MockSubgraph::new(mock_schema_path, self.get_mock_address()?)?.start()?,
+            }
+        }
+
         if let Some(schema) = schema {
             SubgraphSchemaWatcher::new_from_file_path((name, url), schema, follower_messenger)
         } else {
+            let headers = self.get_subgraph_headers(&name);
             SubgraphSchemaWatcher::new_from_url(
                 (name, url),
                 client,
+                headers,
                 follower_messenger,
-                self.subgraph_polling_interval,
+                self.get_polling_interval(),
             )
         }
     }
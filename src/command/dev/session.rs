@@ -0,0 +1,147 @@
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    options::OptionalSubgraphOpts,
+    utils::{
+        parsers::parse_session_name,
+        table::{self, row},
+    },
+};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Attach {
+    /// The `--session-name` of the running `rover dev` session to add a subgraph to.
+    #[arg(value_name = "SESSION_NAME", value_parser = parse_session_name)]
+    #[serde(skip_serializing)]
+    pub(crate) session_name: String,
+
+    #[clap(flatten)]
+    pub(crate) subgraph_opts: OptionalSubgraphOpts,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Kill {
+    /// The `--session-name` of the running `rover dev` session to stop.
+    #[arg(
+        value_name = "SESSION_NAME",
+        value_parser = parse_session_name,
+        required_unless_present = "all"
+    )]
+    #[serde(skip_serializing)]
+    pub(crate) session_name: Option<String>,
+
+    /// Stop every `rover dev` session on this machine, named or not, along with any routers left running by sessions that crashed.
+    #[arg(long, conflicts_with = "session_name")]
+    pub(crate) all: bool,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Ps {}
+
+/// A `rover dev` session that `rover dev ps` found the session file of
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DevSessionProcess {
+    pub session_name: Option<String>,
+    pub router_url: String,
+
+    /// the process ID of the main `rover dev` process
+    pub pid: u32,
+
+    /// the process ID of the router, if it's running
+    pub router_pid: Option<u32>,
+
+    pub state: DevSessionState,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DevSessionState {
+    /// the main `rover dev` process is running
+    Running,
+
+    /// the main `rover dev` process is gone, but the router it started is still running
+    Orphaned,
+
+    /// neither process is running, and only the session's files were left behind
+    Stale,
+}
+
+impl DevSessionState {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DevSessionState::Running => "running",
+            DevSessionState::Orphaned => "orphaned",
+            DevSessionState::Stale => "stale",
+        }
+    }
+}
+
+/// The output of `rover dev ps`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DevSessionProcesses {
+    pub sessions: Vec<DevSessionProcess>,
+}
+
+impl DevSessionProcesses {
+    pub fn get_table(&self) -> String {
+        if self.sessions.is_empty() {
+            return "There are no `rover dev` sessions running on this machine.".to_string();
+        }
+        let mut table = table::get_table();
+
+        // bc => sets top row to be bold and center
+        table.add_row(row![bc => "Session", "Router", "State", "PID", "Router PID"]);
+        for session in &self.sessions {
+            table.add_row(row![
+                session.session_name.as_deref().unwrap_or("-"),
+                session.router_url,
+                session.state.as_str(),
+                session.pid,
+                session
+                    .router_pid
+                    .map(|router_pid| router_pid.to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            ]);
+        }
+        let mut msg = table.to_string();
+        if self
+            .sessions
+            .iter()
+            .any(|session| session.state != DevSessionState::Running)
+        {
+            msg.push_str(
+                "Run `rover dev kill --all` to stop orphaned routers and remove stale sessions.\n",
+            );
+        }
+        msg
+    }
+
+    pub fn get_json(&self) -> Value {
+        json!({ "sessions": self.sessions })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_suggests_cleaning_up_orphaned_sessions() {
+        let mut processes = DevSessionProcesses {
+            sessions: vec![DevSessionProcess {
+                session_name: Some("checkout".to_string()),
+                router_url: "http://127.0.0.1:3000".to_string(),
+                pid: 4242,
+                router_pid: Some(4343),
+                state: DevSessionState::Running,
+            }],
+        };
+        assert!(!processes.get_table().contains("rover dev kill --all"));
+
+        processes.sessions[0].state = DevSessionState::Orphaned;
+        assert!(processes.get_table().contains("rover dev kill --all"));
+        assert_eq!(processes.get_json()["sessions"][0]["state"], "orphaned");
+    }
+}
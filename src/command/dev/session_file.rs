@@ -0,0 +1,167 @@
+use std::net::SocketAddr;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{DateTime, Utc};
+use rover_std::Fs;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    command::{
+        dev::{
+            processes::{is_process_running, kill_process},
+            protocol::ipc_remove,
+        },
+        install::get_rover_dir,
+    },
+    RoverResult,
+};
+
+/// The file that announces where a `rover dev` session's router is listening, for tools like
+/// frontends and test runners that need to find the session without asking the user for its port.
+///
+/// It also records the processes the session spawned, so `rover dev ps` and `rover dev kill --all`
+/// can find the ones a crashed session left behind
+#[derive(Debug, Clone)]
+pub struct SessionFile {
+    path: Utf8PathBuf,
+}
+
+/// The contents of a [`SessionFile`]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SessionAnnouncement {
+    /// the `--session-name` of the session, if it was started with one
+    pub session_name: Option<String>,
+
+    /// the URL to send GraphQL requests to
+    pub router_url: String,
+
+    /// the address the router listens on, which differs from the requested one when that was taken
+    pub router_address: SocketAddr,
+
+    /// the address the session was started at, which other `rover dev` processes find it by
+    pub requested_address: SocketAddr,
+
+    /// the process ID of the main `rover dev` process
+    pub pid: u32,
+
+    /// the process ID of the router, while it's running
+    #[serde(default)]
+    pub router_pid: Option<u32>,
+
+    /// the interprocess socket the main `rover dev` process listens on
+    pub ipc_socket: String,
+
+    pub started_at: DateTime<Utc>,
+}
+
+impl SessionFile {
+    /// The session file of a session in `sessions_dir`, named the same way as the session's socket
+    pub fn new(
+        sessions_dir: &Utf8Path,
+        requested_address: &SocketAddr,
+        session_name: Option<&str>,
+    ) -> Self {
+        Self {
+            path: sessions_dir.join(get_file_name(requested_address, session_name)),
+        }
+    }
+
+    /// Every session file in `sessions_dir`, skipping any that can't be read
+    pub fn read_all(sessions_dir: &Utf8Path) -> Vec<(Self, SessionAnnouncement)> {
+        let mut session_files: Vec<(Self, SessionAnnouncement)> = Fs::get_dir_entries(sessions_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|entry| entry.path().extension() == Some("json"))
+                    .map(|entry| Self {
+                        path: entry.path().to_path_buf(),
+                    })
+                    .filter_map(|session_file| {
+                        let announcement = session_file.read().ok()?;
+                        Some((session_file, announcement))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        session_files.sort_by(|(a, _), (b, _)| a.path.cmp(&b.path));
+        session_files
+    }
+
+    pub fn get_path(&self) -> &Utf8Path {
+        &self.path
+    }
+
+    pub fn read(&self) -> RoverResult<SessionAnnouncement> {
+        Ok(serde_json::from_str(&Fs::read_file(&self.path)?)?)
+    }
+
+    /// Writes the announcement out, replacing the file of any earlier session at the same address
+    pub fn write(&self, announcement: &SessionAnnouncement) -> RoverResult<()> {
+        if let Some(sessions_dir) = self.path.parent() {
+            Fs::create_dir_all(sessions_dir)?;
+        }
+        Fs::write_file(&self.path, serde_json::to_string_pretty(announcement)?)?;
+        Ok(())
+    }
+
+    /// Removes the file once the session stops, so tools don't find a session that isn't running
+    pub fn remove(&self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+
+    /// Stops the router of a session whose main `rover dev` process is gone,
+    /// and removes the files it left behind
+    pub fn clean_up(&self, announcement: &SessionAnnouncement) {
+        if let Some(router_pid) = announcement.router_pid {
+            if is_process_running(router_pid, ROUTER_EXECUTABLE) {
+                kill_process(router_pid);
+            }
+        }
+        ipc_remove(&announcement.ipc_socket);
+        self.remove();
+    }
+}
+
+/// Part of the executable name of the main `rover dev` process
+pub const ROVER_EXECUTABLE: &str = "rover";
+
+/// Part of the executable name of the router, i.e. `router-v1.10.0`
+pub const ROUTER_EXECUTABLE: &str = "router";
+
+/// The directory session files are kept in
+pub fn get_sessions_dir(override_install_path: Option<Utf8PathBuf>) -> RoverResult<Utf8PathBuf> {
+    Ok(get_rover_dir(override_install_path)?.join("dev-sessions"))
+}
+
+fn get_file_name(requested_address: &SocketAddr, session_name: Option<&str>) -> String {
+    match session_name {
+        Some(session_name) => format!("supergraph-session-{}.json", session_name),
+        // IPv6 addresses have colons, which can't be in a file name on Windows
+        None => format!(
+            "supergraph-{}-{}.json",
+            requested_address.ip().to_string().replace(':', "_"),
+            requested_address.port()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_names_session_files_like_sockets() {
+        assert_eq!(
+            get_file_name(&"127.0.0.1:3000".parse().unwrap(), None),
+            "supergraph-127.0.0.1-3000.json"
+        );
+        assert_eq!(
+            get_file_name(&"[::1]:3000".parse().unwrap(), None),
+            "supergraph-__1-3000.json"
+        );
+        assert_eq!(
+            get_file_name(&"127.0.0.1:3000".parse().unwrap(), Some("checkout")),
+            "supergraph-session-checkout.json"
+        );
+    }
+}
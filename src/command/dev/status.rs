@@ -0,0 +1,212 @@
+use std::net::SocketAddr;
+
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::utils::{
+    parsers::parse_session_name,
+    table::{self, row},
+};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Status {
+    /// The port of the `rover dev` session to report on, if its router doesn't listen on the default port.
+    #[arg(long, short = 'p')]
+    pub(crate) supergraph_port: Option<u16>,
+
+    /// The address of the `rover dev` session to report on, if its router doesn't listen on the default address.
+    #[arg(long)]
+    pub(crate) supergraph_address: Option<String>,
+
+    /// The `--session-name` of the `rover dev` session to report on.
+    #[arg(
+        long = "session-name",
+        value_parser = parse_session_name,
+        conflicts_with_all = ["supergraph_port", "supergraph_address"]
+    )]
+    #[serde(skip_serializing)]
+    pub(crate) session_name: Option<String>,
+}
+
+/// The state of a running `rover dev` session, as the main `rover dev` process reports it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DevSessionStatus {
+    /// the version of Rover the main `rover dev` process is running
+    pub version: String,
+
+    /// the `--session-name` of the session, if it was started with one
+    pub session_name: Option<String>,
+
+    /// the address the router listens on
+    pub router_address: SocketAddr,
+
+    /// whether the router is served over HTTPS
+    #[serde(default)]
+    pub router_tls: bool,
+
+    pub started_at: DateTime<Utc>,
+
+    pub uptime_seconds: u64,
+
+    /// every subgraph in the session, whichever `rover dev` process runs it
+    pub subgraphs: Vec<DevSessionSubgraph>,
+
+    /// the result of the latest composition, which there's none of until a subgraph is added
+    pub last_composition: Option<DevCompositionStatus>,
+
+    /// how many times the session has composed, including the compositions that failed
+    #[serde(default)]
+    pub compositions: u64,
+
+    /// how many of the session's compositions failed
+    #[serde(default)]
+    pub composition_failures: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DevSessionSubgraph {
+    pub name: String,
+    pub url: Url,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DevCompositionStatus {
+    pub at: DateTime<Utc>,
+    pub succeeded: bool,
+    pub error: Option<String>,
+}
+
+impl DevSessionStatus {
+    /// Whether the router is serving the latest changes to the session's subgraphs
+    pub fn is_healthy(&self) -> bool {
+        matches!(&self.last_composition, Some(composition) if composition.succeeded)
+    }
+
+    pub fn get_table(&self) -> String {
+        let session = match &self.session_name {
+            Some(session_name) => format!("The '{}' `rover dev` session", session_name),
+            None => "The `rover dev` session".to_string(),
+        };
+        let mut msg = format!(
+            "{} at {} has been running for {}\n",
+            session,
+            self.router_address,
+            format_uptime(self.uptime_seconds)
+        );
+
+        let mut table = table::get_table();
+        let scheme = if self.router_tls { "https" } else { "http" };
+        table.add_row(row![
+            "Router",
+            format!("{}://{}", scheme, self.router_address)
+        ]);
+        table.add_row(row!["Rover version", self.version]);
+        table.add_row(row!["Started", self.started_at.to_rfc3339()]);
+        let last_composition = match &self.last_composition {
+            Some(composition) if composition.succeeded => {
+                format!("succeeded at {}", composition.at.to_rfc3339())
+            }
+            Some(composition) => format!(
+                "failed at {}: {}",
+                composition.at.to_rfc3339(),
+                composition.error.as_deref().unwrap_or("unknown error")
+            ),
+            None => "none yet".to_string(),
+        };
+        table.add_row(row!["Last composition", last_composition]);
+        msg.push_str(&table.to_string());
+
+        if self.subgraphs.is_empty() {
+            msg.push_str("There are no subgraphs in the session.\n");
+        } else {
+            let mut subgraphs_table = table::get_table();
+
+            // bc => sets top row to be bold and center
+            subgraphs_table.add_row(row![bc => "Subgraph", "URL"]);
+            for subgraph in &self.subgraphs {
+                subgraphs_table.add_row(row![subgraph.name, subgraph.url]);
+            }
+            msg.push_str(&subgraphs_table.to_string());
+        }
+        msg
+    }
+
+    pub fn get_json(&self) -> Value {
+        json!({
+            "version": self.version,
+            "session_name": self.session_name,
+            "router_address": self.router_address,
+            "router_tls": self.router_tls,
+            "started_at": self.started_at,
+            "uptime_seconds": self.uptime_seconds,
+            "healthy": self.is_healthy(),
+            "subgraphs": self.subgraphs,
+            "last_composition": self.last_composition,
+            "compositions": self.compositions,
+            "composition_failures": self.composition_failures,
+        })
+    }
+}
+
+fn format_uptime(uptime_seconds: u64) -> String {
+    let (hours, minutes, seconds) = (
+        uptime_seconds / 3600,
+        uptime_seconds / 60 % 60,
+        uptime_seconds % 60,
+    );
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_a_failed_composition_as_unhealthy() {
+        let mut status = DevSessionStatus {
+            version: "0.11.1".to_string(),
+            session_name: Some("checkout".to_string()),
+            router_address: "127.0.0.1:3000".parse().unwrap(),
+            router_tls: false,
+            started_at: Utc::now(),
+            uptime_seconds: 3725,
+            subgraphs: vec![DevSessionSubgraph {
+                name: "products".to_string(),
+                url: "http://localhost:4001".parse().unwrap(),
+            }],
+            last_composition: None,
+            compositions: 0,
+            composition_failures: 0,
+        };
+        assert!(!status.is_healthy());
+        assert!(status.get_table().contains(
+            "The 'checkout' `rover dev` session at 127.0.0.1:3000 has been running for 1h 2m 5s"
+        ));
+
+        status.last_composition = Some(DevCompositionStatus {
+            at: Utc::now(),
+            succeeded: true,
+            error: None,
+        });
+        assert!(status.is_healthy());
+
+        status.last_composition = Some(DevCompositionStatus {
+            at: Utc::now(),
+            succeeded: false,
+            error: Some("could not compose".to_string()),
+        });
+        let json = status.get_json();
+        assert_eq!(json["healthy"], false);
+        assert_eq!(json["last_composition"]["error"], "could not compose");
+        assert_eq!(json["subgraphs"][0]["name"], "products");
+    }
+}
@@ -0,0 +1,368 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use anyhow::anyhow;
+use apollo_federation_types::{
+    build::SubgraphDefinition,
+    config::{SchemaSource, SubgraphConfig, SupergraphConfig},
+};
+use camino::{Utf8Path, Utf8PathBuf};
+use reqwest::Url;
+use rover_client::{
+    operations::subgraph::fetch_all::{self, SubgraphFetchAllInput},
+    shared::GraphRef,
+};
+use rover_std::{Emoji, Fs};
+
+use crate::{
+    command::{
+        dev::{
+            do_dev::log_err_and_continue,
+            docker_compose::get_compose_subgraphs,
+            introspect::{IntrospectRunnerKind, UnknownIntrospectRunner},
+            logger::{self, DevLogSource},
+            protocol::{FollowerMessenger, SubgraphName},
+            record::replay_subgraphs,
+            watcher::SubgraphSchemaWatcher,
+        },
+        supergraph::{
+            parse_supergraph_yaml, resolve_supergraph_config, ConfigExtensions, ResolveOpts,
+        },
+    },
+    options::{OptionalSubgraphOpts, ProfileOpt},
+    utils::{client::StudioClientConfig, parsers::FileDescriptorType},
+    RoverError, RoverErrorSuggestion, RoverResult,
+};
+
+/// The subgraphs of a `--supergraph-config`, `--docker-compose`, or `--dev-config` file that a `rover dev` session starts with.
+///
+/// Subgraphs with a `file` or `subgraph_url` source are watched like the subgraph of a
+/// `rover dev --name --url` process, and subgraphs with an `sdl` or `graphref` source are
+/// resolved once, since there's nothing to watch
+#[derive(Debug)]
+pub struct ConfigSubgraphs {
+    watchers: Vec<SubgraphSchemaWatcher>,
+    resolved: Vec<SubgraphDefinition>,
+    /// the published subgraphs of `--remote-subgraphs`, which local subgraphs replace
+    published: Vec<SubgraphDefinition>,
+    ipc_socket_addr: String,
+}
+
+impl ConfigSubgraphs {
+    pub fn new(
+        config_path: &Utf8Path,
+        client_config: &StudioClientConfig,
+        profile: &ProfileOpt,
+        ipc_socket_addr: &str,
+        subgraph_opts: &OptionalSubgraphOpts,
+    ) -> RoverResult<Self> {
+        let config_source = FileDescriptorType::File(config_path.to_path_buf());
+        let contents = Fs::read_file(config_path)?;
+        let (supergraph_config, extensions) = parse_supergraph_yaml(&contents, &config_source)?;
+        // the session composes every process's subgraphs together, with the main process's plugin
+        if extensions != ConfigExtensions::default() {
+            logger::warn(
+                &DevLogSource::Rover,
+                format!(
+                    "{}the `composition_binary` and `filter` of {} are ignored, since `rover dev` composes its subgraphs with its own composition plugin",
+                    Emoji::Warn,
+                    config_path
+                ),
+            );
+        }
+        Self::from_supergraph_config(
+            supergraph_config,
+            config_path,
+            client_config,
+            profile,
+            ipc_socket_addr,
+            Some(subgraph_opts),
+        )
+    }
+
+    /// The subgraphs of a `--docker-compose` file, which are all introspected
+    pub fn from_docker_compose(
+        compose_path: &Utf8Path,
+        client_config: &StudioClientConfig,
+        profile: &ProfileOpt,
+        ipc_socket_addr: &str,
+        subgraph_opts: &OptionalSubgraphOpts,
+    ) -> RoverResult<Self> {
+        let subgraphs = get_compose_subgraphs(compose_path)?;
+        let contents = serde_yaml::to_string(&serde_json::json!({ "subgraphs": subgraphs }))?;
+        Self::from_supergraph_config(
+            SupergraphConfig::new_from_yaml(&contents)?,
+            compose_path,
+            client_config,
+            profile,
+            ipc_socket_addr,
+            Some(subgraph_opts),
+        )
+    }
+
+    /// The `subgraphs` of a `--dev-config` file, which are listed like the subgraphs of a `--supergraph-config` file
+    pub fn from_dev_config(
+        dev_config_path: &Utf8Path,
+        subgraphs: &serde_yaml::Value,
+        client_config: &StudioClientConfig,
+        profile: &ProfileOpt,
+        ipc_socket_addr: &str,
+        subgraph_opts: &OptionalSubgraphOpts,
+    ) -> RoverResult<Self> {
+        let contents = serde_yaml::to_string(&serde_json::json!({ "subgraphs": subgraphs }))?;
+        Self::from_supergraph_config(
+            SupergraphConfig::new_from_yaml(&contents)?,
+            dev_config_path,
+            client_config,
+            profile,
+            ipc_socket_addr,
+            Some(subgraph_opts),
+        )
+    }
+
+    /// The subgraphs recorded in a `--replay` directory, which are served from their recordings
+    pub fn from_replay(
+        replay_dir: &Utf8Path,
+        client_config: &StudioClientConfig,
+        profile: &ProfileOpt,
+        ipc_socket_addr: &str,
+        subgraph_opts: &OptionalSubgraphOpts,
+    ) -> RoverResult<Self> {
+        let subgraphs = replay_subgraphs(replay_dir)?;
+        let contents = serde_yaml::to_string(&serde_json::json!({ "subgraphs": subgraphs }))?;
+        Self::from_supergraph_config(
+            SupergraphConfig::new_from_yaml(&contents)?,
+            replay_dir,
+            client_config,
+            profile,
+            ipc_socket_addr,
+            Some(subgraph_opts),
+        )
+    }
+
+    /// The published subgraphs of a `--remote-subgraphs` graph ref, which are fetched once in a single request.
+    ///
+    /// The main session replaces each of them with the local subgraph with its name,
+    /// whether that subgraph is already running or is added later
+    pub fn from_remote_subgraphs(
+        graph_ref: &GraphRef,
+        client_config: &StudioClientConfig,
+        profile: &ProfileOpt,
+        ipc_socket_addr: &str,
+    ) -> RoverResult<Self> {
+        FollowerMessenger::from_attached_session(ipc_socket_addr).version_check()?;
+        let client = client_config.get_authenticated_client(profile)?;
+        let published = fetch_all::run(
+            SubgraphFetchAllInput {
+                graph_ref: graph_ref.clone(),
+            },
+            &client,
+        )?
+        .into_iter()
+        .map(|subgraph| {
+            let routing_url = subgraph.url.ok_or_else(|| {
+                let mut err = RoverError::new(anyhow!(
+                    "The '{}' subgraph published to {} has no routing URL.",
+                    &subgraph.name,
+                    graph_ref
+                ));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                    "Publish the '{}' subgraph with a `--routing-url`, or run it locally.",
+                    &subgraph.name
+                )));
+                err
+            })?;
+            Ok(SubgraphDefinition::new(
+                subgraph.name,
+                routing_url,
+                subgraph.sdl,
+            ))
+        })
+        .collect::<RoverResult<Vec<_>>>()?;
+
+        Ok(Self {
+            watchers: Vec::new(),
+            resolved: Vec::new(),
+            published,
+            ipc_socket_addr: ipc_socket_addr.to_string(),
+        })
+    }
+
+    fn from_supergraph_config(
+        supergraph_config: SupergraphConfig,
+        config_path: &Utf8Path,
+        client_config: &StudioClientConfig,
+        profile: &ProfileOpt,
+        ipc_socket_addr: &str,
+        subgraph_opts: Option<&OptionalSubgraphOpts>,
+    ) -> RoverResult<Self> {
+        let polling_interval = subgraph_opts.and_then(|opts| opts.get_polling_interval());
+        let config_source = FileDescriptorType::File(config_path.to_path_buf());
+        let config_dir = match config_path.parent() {
+            Some(parent) if !parent.as_str().is_empty() => parent.to_path_buf(),
+            _ => Utf8PathBuf::from("."),
+        };
+
+        // each subgraph is added to the session on its own connection, like an attached `rover dev` process
+        let messenger = FollowerMessenger::from_attached_session(ipc_socket_addr);
+        messenger.version_check()?;
+        let session_subgraphs: Vec<SubgraphName> = messenger
+            .session_subgraphs()?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+
+        let client = client_config
+            .get_builder()
+            .with_timeout(Duration::from_secs(5))
+            .build()?;
+        let mut watchers = Vec::new();
+        let mut resolved_subgraphs = Vec::new();
+        for (name, subgraph) in supergraph_config.into_iter() {
+            if session_subgraphs.contains(&name) {
+                return Err(RoverError::new(anyhow!(
+                    "subgraph with name '{}' is already running in this `rover dev` session",
+                    &name
+                )));
+            }
+            let messenger = FollowerMessenger::from_attached_session(ipc_socket_addr);
+            match &subgraph.schema {
+                SchemaSource::File { file } => {
+                    let routing_url = get_routing_url(&name, &subgraph, config_path)?;
+                    watchers.push(SubgraphSchemaWatcher::new_from_file_path(
+                        (name, routing_url),
+                        config_dir.join(file),
+                        messenger,
+                    )?);
+                }
+                SchemaSource::SubgraphIntrospection { subgraph_url } => {
+                    let routing_url = match &subgraph.routing_url {
+                        Some(_) => get_routing_url(&name, &subgraph, config_path)?,
+                        None => subgraph_url.clone(),
+                    };
+                    let headers = subgraph_opts
+                        .map(|opts| opts.get_subgraph_headers(&name))
+                        .unwrap_or_default();
+                    let introspect_runner = IntrospectRunnerKind::Unknown(
+                        UnknownIntrospectRunner::new(subgraph_url.clone(), client.clone())
+                            .with_headers(headers),
+                    );
+                    watchers.push(SubgraphSchemaWatcher::new_from_introspect_runner(
+                        (name, routing_url),
+                        introspect_runner,
+                        messenger,
+                        polling_interval,
+                    )?);
+                }
+                SchemaSource::Sdl { .. } | SchemaSource::Subgraph { .. } => {
+                    resolved_subgraphs.push((name, subgraph.clone()));
+                }
+            }
+        }
+
+        let resolved = if resolved_subgraphs.is_empty() {
+            Vec::new()
+        } else {
+            let subgraphs: BTreeMap<String, SubgraphConfig> =
+                resolved_subgraphs.into_iter().collect();
+            let contents = serde_yaml::to_string(&serde_json::json!({ "subgraphs": subgraphs }))?;
+            let (supergraph_config, _) = resolve_supergraph_config(
+                SupergraphConfig::new_from_yaml(&contents)?,
+                &config_source,
+                &format!("'{}'", config_path),
+                client_config.clone(),
+                profile,
+                &ResolveOpts::default(),
+            )?;
+            supergraph_config.get_subgraph_definitions()?
+        };
+
+        Ok(Self {
+            watchers,
+            resolved,
+            published: Vec::new(),
+            ipc_socket_addr: ipc_socket_addr.to_string(),
+        })
+    }
+
+    /// The names of every subgraph in the config
+    pub fn get_names(&self) -> Vec<SubgraphName> {
+        self.watchers
+            .iter()
+            .map(|watcher| watcher.get_name())
+            .chain(
+                self.resolved
+                    .iter()
+                    .chain(&self.published)
+                    .map(|subgraph| subgraph.name.clone()),
+            )
+            .collect()
+    }
+
+    /// Adds the resolved subgraphs to the session, and watches the others for changes
+    /// on a thread of their own
+    pub fn start(self) -> RoverResult<()> {
+        let messenger = FollowerMessenger::from_attached_session(&self.ipc_socket_addr);
+        for subgraph in &self.resolved {
+            messenger.add_subgraph(subgraph)?;
+        }
+        for subgraph in &self.published {
+            messenger.add_remote_subgraph(subgraph)?;
+        }
+        for mut watcher in self.watchers {
+            std::thread::spawn(move || {
+                let _ = watcher
+                    .watch_subgraph_for_changes()
+                    .map_err(log_err_and_continue);
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The URL the router sends requests for a subgraph to, which `rover dev` can't compose without
+fn get_routing_url(
+    name: &str,
+    subgraph: &SubgraphConfig,
+    config_path: &Utf8Path,
+) -> RoverResult<Url> {
+    let routing_url = subgraph.routing_url.as_ref().ok_or_else(|| {
+        let mut err = RoverError::new(anyhow!(
+            "The '{}' subgraph in '{}' has no `routing_url`.",
+            name,
+            config_path
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+            "Set `routing_url` to the URL the '{}' subgraph is running on.",
+            name
+        )));
+        err
+    })?;
+    routing_url.parse().map_err(|_| {
+        RoverError::new(anyhow!(
+            "The `routing_url` of the '{}' subgraph, '{}', is not a valid URL.",
+            name,
+            routing_url
+        ))
+    })
+}
+
+/// Blocks the main thread of a `rover dev` process that only runs the subgraphs of its
+/// `--supergraph-config` or `--docker-compose` file, which are watched on threads of their own
+pub fn wait_for_shutdown(subgraph_names: &[SubgraphName]) -> ! {
+    logger::info(
+        &DevLogSource::Rover,
+        format!(
+            "{}started {} {} from the configuration file",
+            Emoji::Start,
+            subgraph_names.len(),
+            match subgraph_names.len() {
+                1 => "subgraph",
+                _ => "subgraphs",
+            }
+        ),
+    );
+    loop {
+        std::thread::park();
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     command::dev::{
         introspect::{IntrospectRunnerKind, UnknownIntrospectRunner},
+        logger::{self, DevLogSource},
         protocol::{FollowerMessenger, SubgraphKey},
     },
     RoverError, RoverResult,
@@ -38,12 +39,14 @@ impl SubgraphSchemaWatcher {
     pub fn new_from_url(
         subgraph_key: SubgraphKey,
         client: Client,
+        headers: Vec<(String, String)>,
         message_sender: FollowerMessenger,
-        polling_interval: u64,
+        polling_interval: Option<u64>,
     ) -> RoverResult<Self> {
         let (_, url) = subgraph_key.clone();
-        let introspect_runner =
-            IntrospectRunnerKind::Unknown(UnknownIntrospectRunner::new(url, client));
+        let introspect_runner = IntrospectRunnerKind::Unknown(
+            UnknownIntrospectRunner::new(url, client).with_headers(headers),
+        );
         Self::new_from_introspect_runner(
             subgraph_key,
             introspect_runner,
@@ -56,7 +59,7 @@ impl SubgraphSchemaWatcher {
         subgraph_key: SubgraphKey,
         introspect_runner: IntrospectRunnerKind,
         message_sender: FollowerMessenger,
-        polling_interval: u64,
+        polling_interval: Option<u64>,
     ) -> RoverResult<Self> {
         Ok(Self {
             schema_watcher_kind: SubgraphSchemaWatcherKind::Introspect(
@@ -68,6 +71,11 @@ impl SubgraphSchemaWatcher {
         })
     }
 
+    /// The source of this subgraph's logs, which is the subgraph's name
+    fn get_log_source(&self) -> DevLogSource {
+        DevLogSource::Subgraph(self.subgraph_key.0.clone())
+    }
+
     pub fn get_subgraph_definition_and_maybe_new_runner(
         &self,
     ) -> RoverResult<(SubgraphDefinition, Option<SubgraphSchemaWatcherKind>)> {
@@ -107,9 +115,8 @@ impl SubgraphSchemaWatcher {
     }
 
     fn update_subgraph(&mut self, last_message: Option<&String>) -> RoverResult<Option<String>> {
-        let print_error = |e: RoverError| {
-            let _ = e.print();
-        };
+        let source = self.get_log_source();
+        let print_error = |e: RoverError| logger::log_error(&source, &e);
 
         let maybe_update_message = match self.get_subgraph_definition_and_maybe_new_runner() {
             Ok((subgraph_definition, maybe_new_refresher)) => {
@@ -152,23 +159,62 @@ impl SubgraphSchemaWatcher {
     pub fn watch_subgraph_for_changes(&mut self) -> RoverResult<()> {
         let mut last_message = None;
         match self.schema_watcher_kind.clone() {
-            SubgraphSchemaWatcherKind::Introspect(introspect_runner_kind, polling_interval) => {
+            SubgraphSchemaWatcherKind::Introspect(
+                introspect_runner_kind,
+                Some(polling_interval),
+            ) => {
                 let endpoint = introspect_runner_kind.endpoint();
-                eprintln!(
-                    "{}polling {} every {} {}",
-                    Emoji::Listen,
-                    &endpoint,
-                    polling_interval,
-                    match polling_interval {
-                        1 => "second",
-                        _ => "seconds",
-                    }
+                logger::info(
+                    &self.get_log_source(),
+                    format!(
+                        "{}polling {} every {} {}",
+                        Emoji::Listen,
+                        &endpoint,
+                        polling_interval,
+                        match polling_interval {
+                            1 => "second",
+                            _ => "seconds",
+                        }
+                    ),
                 );
                 loop {
                     last_message = self.update_subgraph(last_message.as_ref())?;
                     std::thread::sleep(std::time::Duration::from_secs(polling_interval));
                 }
             }
+            SubgraphSchemaWatcherKind::Introspect(introspect_runner_kind, None) => {
+                let endpoint = introspect_runner_kind.endpoint();
+                logger::info(
+                    &self.get_log_source(),
+                    format!(
+                        "{}introspecting {} until its schema is fetched, since polling is disabled",
+                        Emoji::Listen,
+                        &endpoint
+                    ),
+                );
+                // a subgraph that isn't running yet is retried every second until it responds
+                let mut last_error = None;
+                loop {
+                    match self.get_subgraph_definition_and_maybe_new_runner() {
+                        Ok((subgraph_definition, _)) => {
+                            self.message_sender.add_subgraph(&subgraph_definition)?;
+                            break;
+                        }
+                        Err(e) => {
+                            let error_str = e.to_string();
+                            if last_error.as_ref() != Some(&error_str) {
+                                logger::log_error(&self.get_log_source(), &e);
+                                last_error = Some(error_str);
+                            }
+                        }
+                    }
+                    std::thread::sleep(std::time::Duration::from_secs(1));
+                }
+                // the subgraph stays in the session until this process stops
+                loop {
+                    std::thread::park();
+                }
+            }
             SubgraphSchemaWatcherKind::File(path) => {
                 // populate the schema for the first time (last_message is always None to start)
                 last_message = self.update_subgraph(last_message.as_ref())?;
@@ -222,6 +268,7 @@ impl SubgraphSchemaWatcher {
 
 #[derive(Debug, Clone)]
 pub enum SubgraphSchemaWatcherKind {
-    Introspect(IntrospectRunnerKind, u64),
+    /// introspects the subgraph every number of seconds, or only until its schema is fetched once
+    Introspect(IntrospectRunnerKind, Option<u64>),
     File(Utf8PathBuf),
 }
@@ -0,0 +1,69 @@
+use camino::Utf8PathBuf;
+use clap::Parser;
+use serde::Serialize;
+
+use rover_client::operations::graph::check_workflow::{self, CheckWorkflowInput};
+use rover_std::Style;
+
+use crate::options::{CheckReportOpt, GraphRefOpt, ProfileOpt};
+use crate::utils::client::StudioClientConfig;
+use crate::utils::rover_config::RoverConfig;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Await {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    /// The ID of the check workflow to wait for, as printed by `rover graph check --background`
+    #[arg(value_name = "WORKFLOW_ID")]
+    workflow_id: String,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The config file to read the check policy from, instead of the nearest `.rover.yaml`
+    /// in the current directory or its parents
+    #[arg(long, value_name = "PATH")]
+    #[serde(skip)]
+    rover_config: Option<Utf8PathBuf>,
+
+    #[clap(flatten)]
+    #[serde(skip_serializing)]
+    report: CheckReportOpt,
+}
+
+impl Await {
+    pub fn run(
+        &self,
+        client_config: StudioClientConfig,
+        checks_timeout_seconds: u64,
+    ) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        // the check was started with its policy's config, so only the ignored changes
+        // and severity overrides are left to apply to its result
+        let policy = RoverConfig::load(self.rover_config.as_deref())?.checks;
+        eprintln!(
+            "Waiting for check workflow {} on {} to complete using credentials from the {} profile.",
+            Style::Command.paint(&self.workflow_id),
+            Style::Link.paint(self.graph.graph_ref.to_string()),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+        let result = policy
+            .apply(
+                check_workflow::run(
+                    CheckWorkflowInput {
+                        graph_ref: self.graph.graph_ref.clone(),
+                        workflow_id: self.workflow_id.clone(),
+                        checks_timeout_seconds,
+                    },
+                    &client,
+                ),
+                &self.graph.graph_ref,
+            )
+            .map(RoverOutput::CheckResponse);
+        self.report
+            .write(&self.graph.graph_ref, &self.workflow_id, &result)?;
+        result
+    }
+}
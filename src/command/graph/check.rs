@@ -1,14 +1,17 @@
 use clap::Parser;
 use serde::Serialize;
 
+use rover_client::blocking::StudioClient;
 use rover_client::operations::graph::{
     check::{self, CheckSchemaAsyncInput},
     check_workflow::{self, CheckWorkflowInput},
 };
-use rover_client::shared::{CheckConfig, GitContext};
+use rover_client::shared::{ClientFilter, GitContext};
+use rover_std::Style;
 
-use crate::options::{CheckConfigOpts, GraphRefOpt, ProfileOpt, SchemaOpt};
+use crate::options::{CheckConfigOpts, FixtureOpt, GraphRefOpt, ProfileOpt, SchemaOpt};
 use crate::utils::client::StudioClientConfig;
+use crate::utils::rover_config::RoverConfig;
 use crate::{RoverOutput, RoverResult};
 
 #[derive(Debug, Serialize, Parser)]
@@ -25,6 +28,17 @@ pub struct Check {
 
     #[clap(flatten)]
     config: CheckConfigOpts,
+
+    /// A client whose operations shouldn't be checked, such as internal tooling,
+    /// as a name with an optional version, i.e. `ios-app` or `ios-app@3.2.0`.
+    /// Leaving off the version excludes every version of the client.
+    /// Can be passed more than once
+    #[arg(long = "exclude-client", value_name = "NAME[@VERSION]")]
+    excluded_clients: Vec<ClientFilter>,
+
+    #[clap(flatten)]
+    #[serde(skip_serializing)]
+    fixture: FixtureOpt,
 }
 
 impl Check {
@@ -34,10 +48,27 @@ impl Check {
         git_context: GitContext,
         checks_timeout_seconds: u64,
     ) -> RoverResult<RoverOutput> {
-        let client = client_config.get_authenticated_client(&self.profile)?;
+        let session = self.fixture.get_session()?;
+        let client = client_config
+            .with_fixture(session.clone())
+            .get_authenticated_client(&self.profile)?;
+        let result = self.check(&client, git_context, checks_timeout_seconds);
+        // failed checks are recorded too, since those are the responses worth reproducing
+        self.fixture.save(session)?;
+        result
+    }
+
+    fn check(
+        &self,
+        client: &StudioClient,
+        git_context: GitContext,
+        checks_timeout_seconds: u64,
+    ) -> RoverResult<RoverOutput> {
         let proposed_schema = self
             .schema
             .read_file_descriptor("SDL", &mut std::io::stdin())?;
+        let policy = RoverConfig::load(self.config.rover_config.as_deref())?.checks;
+        let config = policy.get_check_config(&self.config, &self.excluded_clients)?;
 
         eprintln!(
             "Checking the proposed schema against metrics from {}",
@@ -48,26 +79,37 @@ impl Check {
                 graph_ref: self.graph.graph_ref.clone(),
                 proposed_schema,
                 git_context,
-                config: CheckConfig {
-                    validation_period: self.config.validation_period.clone(),
-                    query_count_threshold: self.config.query_count_threshold,
-                    query_count_threshold_percentage: self.config.query_percentage_threshold,
-                },
+                config,
             },
-            &client,
+            client,
         )?;
         if self.config.background {
+            eprintln!(
+                "To wait for the check to complete, run {}",
+                Style::Command.paint(format!(
+                    "`rover graph await {} {}`",
+                    &self.graph.graph_ref, &workflow_res.workflow_id
+                ))
+            );
             Ok(RoverOutput::AsyncCheckResponse(workflow_res))
         } else {
-            let check_res = check_workflow::run(
-                CheckWorkflowInput {
-                    graph_ref: self.graph.graph_ref.clone(),
-                    workflow_id: workflow_res.workflow_id,
-                    checks_timeout_seconds,
-                },
-                &client,
-            )?;
-            Ok(RoverOutput::CheckResponse(check_res))
+            let result = policy
+                .apply(
+                    check_workflow::run(
+                        CheckWorkflowInput {
+                            graph_ref: self.graph.graph_ref.clone(),
+                            workflow_id: workflow_res.workflow_id.clone(),
+                            checks_timeout_seconds,
+                        },
+                        client,
+                    ),
+                    &self.graph.graph_ref,
+                )
+                .map(RoverOutput::CheckResponse);
+            self.config
+                .report
+                .write(&self.graph.graph_ref, &workflow_res.workflow_id, &result)?;
+            result
         }
     }
 }
@@ -4,7 +4,7 @@ use serde::Serialize;
 use rover_client::operations::graph::delete::{self, GraphDeleteInput};
 use rover_std::{prompt, Style};
 
-use crate::options::{GraphRefOpt, ProfileOpt};
+use crate::options::{GraphRefOpt, ProfileOpt, RetryOpt};
 use crate::utils::client::StudioClientConfig;
 use crate::{RoverOutput, RoverResult};
 
@@ -17,9 +17,12 @@ pub struct Delete {
     profile: ProfileOpt,
 
     /// Skips the step where the command asks for user confirmation before
-    /// deleting the graph.
+    /// deleting the variant.
     #[arg(long)]
     confirm: bool,
+
+    #[clap(flatten)]
+    retry: RetryOpt,
 }
 
 impl Delete {
@@ -41,6 +44,7 @@ impl Delete {
         delete::run(
             GraphDeleteInput {
                 graph_ref: self.graph.graph_ref.clone(),
+                retries: self.retry.retries,
             },
             &client,
         )?;
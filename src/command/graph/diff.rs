@@ -0,0 +1,83 @@
+use clap::Parser;
+use serde::Serialize;
+
+use rover_client::blocking::StudioClient;
+use rover_client::operations::graph::fetch::{self, GraphFetchInput};
+use rover_client::shared::GraphRef;
+use rover_std::Style;
+
+use crate::options::ProfileOpt;
+use crate::utils::client::StudioClientConfig;
+use crate::utils::parsers::FileDescriptorType;
+use crate::utils::schema_diff::SchemaDiff;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Diff {
+    /// <NAME>@<VARIANT> of the graph in Apollo Studio to compare against.
+    /// @<VARIANT> may be left off, defaulting to @current
+    #[arg(value_name = "BASE_GRAPH_REF")]
+    #[serde(skip_serializing)]
+    base: GraphRef,
+
+    /// <NAME>@<VARIANT> of the graph in Apollo Studio with the changes.
+    /// Leave this off and pass `--schema` to compare a local schema instead
+    #[arg(
+        value_name = "TARGET_GRAPH_REF",
+        required_unless_present = "schema",
+        conflicts_with = "schema"
+    )]
+    #[serde(skip_serializing)]
+    target: Option<GraphRef>,
+
+    /// The schema file with the changes. You can pass `-` to use stdin instead of a file.
+    #[arg(long, short = 's')]
+    #[serde(skip_serializing)]
+    schema: Option<FileDescriptorType>,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+}
+
+impl Diff {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+
+        let (target, target_sdl) = match (&self.target, &self.schema) {
+            (Some(target), _) => {
+                eprintln!(
+                    "Comparing the schemas of {} and {} using credentials from the {} profile.",
+                    Style::Link.paint(self.base.to_string()),
+                    Style::Link.paint(target.to_string()),
+                    Style::Command.paint(&self.profile.profile_name)
+                );
+                (target.to_string(), fetch_sdl(target, &client)?)
+            }
+            (None, Some(schema)) => {
+                let sdl = schema.read_file_descriptor("SDL", &mut std::io::stdin())?;
+                eprintln!(
+                    "Comparing the schema of {} to the local schema using credentials from the {} profile.",
+                    Style::Link.paint(self.base.to_string()),
+                    Style::Command.paint(&self.profile.profile_name)
+                );
+                ("the local schema".to_string(), sdl)
+            }
+            (None, None) => unreachable!("clap requires one of <TARGET_GRAPH_REF> or --schema"),
+        };
+        let base_sdl = fetch_sdl(&self.base, &client)?;
+
+        let diff = SchemaDiff::new(self.base.to_string(), &base_sdl, target, &target_sdl)?;
+        Ok(RoverOutput::SchemaDiff(diff))
+    }
+}
+
+fn fetch_sdl(graph_ref: &GraphRef, client: &StudioClient) -> RoverResult<String> {
+    let fetch_response = fetch::run(
+        GraphFetchInput {
+            graph_ref: graph_ref.clone(),
+            hash: None,
+        },
+        client,
+    )?;
+    Ok(fetch_response.sdl.contents)
+}
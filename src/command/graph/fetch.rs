@@ -1,12 +1,33 @@
+use anyhow::anyhow;
+use camino::{Utf8Path, Utf8PathBuf};
 use clap::Parser;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use serde::Serialize;
 
+use rover_client::blocking::StudioClient;
 use rover_client::operations::graph::fetch::{self, GraphFetchInput};
-use rover_std::Style;
+use rover_client::operations::supergraph::fetch::{self as supergraph_fetch, SupergraphFetchInput};
+use rover_client::operations::variant::list::{self as variant_list, VariantListInput};
+use rover_client::shared::{FetchResponse, GraphRef};
+use rover_client::RoverClientError;
+use rover_std::Fs;
 
-use crate::options::{GraphRefOpt, ProfileOpt};
-use crate::utils::client::StudioClientConfig;
-use crate::{RoverOutput, RoverResult};
+use crate::options::{DirectiveFilterOpt, GraphRefOpt, ProfileOpt, SchemaType};
+use crate::utils::client::{
+    explain_anonymous_failure, get_credentials_profile, StudioClientConfig,
+};
+use crate::utils::parsers::parse_schema_hash;
+use crate::utils::progress::ProgressEvent;
+use crate::utils::schema_split;
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+
+/// The schema of one variant written by `rover graph fetch --all-variants`
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct VariantSchemaFile {
+    pub variant: String,
+    /// the path of the file, relative to the directory it's written to
+    pub path: String,
+}
 
 #[derive(Debug, Serialize, Parser)]
 pub struct Fetch {
@@ -15,25 +36,200 @@ pub struct Fetch {
 
     #[clap(flatten)]
     profile: ProfileOpt,
+
+    #[clap(flatten)]
+    directives: DirectiveFilterOpt,
+
+    /// The SHA256 hash of a previously published schema to fetch instead of
+    /// the variant's latest schema
+    #[arg(
+        long,
+        value_name = "SCHEMA_HASH",
+        value_parser = parse_schema_hash,
+        conflicts_with = "all_variants"
+    )]
+    hash: Option<String>,
+
+    /// Write the schema to this directory as one file per type, along with an `index.json`
+    /// listing every file, instead of printing it. Files listed in an existing `index.json`
+    /// are replaced, so types that were removed from the schema don't leave files behind.
+    ///
+    /// With `--all-variants`, one `<VARIANT>.graphql` file is written per variant instead
+    #[arg(long = "out-dir", value_name = "DIR")]
+    #[serde(skip_serializing)]
+    out_dir: Option<Utf8PathBuf>,
+
+    /// Fetch the schema of every variant of the graph into `--out-dir`, ignoring the
+    /// variant in <GRAPH_REF>. Variants are fetched concurrently, and variants that have
+    /// never had a schema published are skipped
+    #[arg(long, requires = "out_dir")]
+    all_variants: bool,
+
+    /// Fetch the API schema that clients can query, or the core schema that routers run,
+    /// which only federated graphs have. Only API schemas can be fetched by `--hash`
+    #[arg(long, value_enum, default_value_t = SchemaType::Api)]
+    schema_type: SchemaType,
 }
 
 impl Fetch {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
-        let client = client_config.get_authenticated_client(&self.profile)?;
+        if self.hash.is_some() && self.schema_type == SchemaType::Core {
+            let mut err = RoverError::new(anyhow!("Only API schemas can be fetched by hash."));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Leave off `--schema-type core` to fetch the API schema with this hash, or leave off `--hash` to fetch the variant's latest core schema.".to_string(),
+            ));
+            return Err(err);
+        }
+        let client = client_config.get_public_client(&self.profile)?;
+        if let (true, Some(out_dir)) = (self.all_variants, &self.out_dir) {
+            return self.fetch_all_variants(&client, &client_config, out_dir);
+        }
         let graph_ref = self.graph.graph_ref.to_string();
-        eprintln!(
-            "Fetching SDL from {} using credentials from the {} profile.",
-            Style::Link.paint(&graph_ref),
-            Style::Command.paint(&self.profile.profile_name)
-        );
-
-        let fetch_response = fetch::run(
-            GraphFetchInput {
+        let progress = client_config.get_progress();
+        progress.emit(ProgressEvent::SchemaFetchStarted {
+            graph_ref: graph_ref.clone(),
+            subgraph: None,
+            hash: self.hash.clone(),
+            supergraph: self.schema_type == SchemaType::Core,
+            profile: get_credentials_profile(&client, &self.profile),
+        });
+
+        let fetch_response = self
+            .fetch_schema(&client, &self.graph.graph_ref, self.hash.clone())
+            .map_err(|e| explain_anonymous_failure(&client, &self.graph.graph_ref, e))?;
+        progress.emit(ProgressEvent::SchemaFetched {
+            graph_ref,
+            subgraph: None,
+        });
+
+        let filter = self.directives.get_filter();
+        if let Some(out_dir) = &self.out_dir {
+            let files = schema_split::split(&filter.apply(&fetch_response.sdl.contents))?;
+            schema_split::write(out_dir, &files)?;
+            return Ok(RoverOutput::SchemaFilesWritten {
+                out_dir: out_dir.clone(),
+                files,
+            });
+        }
+        Ok(RoverOutput::FetchResponse(fetch_response).transform_sdl(|sdl| filter.apply(sdl)))
+    }
+
+    /// Fetches the API schema of a variant, optionally by hash, or its core schema
+    fn fetch_schema(
+        &self,
+        client: &StudioClient,
+        graph_ref: &GraphRef,
+        hash: Option<String>,
+    ) -> Result<FetchResponse, RoverClientError> {
+        match self.schema_type {
+            SchemaType::Api => fetch::run(
+                GraphFetchInput {
+                    graph_ref: graph_ref.clone(),
+                    hash,
+                },
+                client,
+            ),
+            SchemaType::Core => supergraph_fetch::run(
+                SupergraphFetchInput {
+                    graph_ref: graph_ref.clone(),
+                    schema: self.schema_type.into(),
+                },
+                client,
+            ),
+        }
+    }
+
+    fn fetch_all_variants(
+        &self,
+        client: &StudioClient,
+        client_config: &StudioClientConfig,
+        out_dir: &Utf8Path,
+    ) -> RoverResult<RoverOutput> {
+        let graph_id = &self.graph.graph_ref.name;
+        let variants = variant_list::run(
+            VariantListInput {
                 graph_ref: self.graph.graph_ref.clone(),
             },
-            &client,
-        )?;
+            client,
+        )
+        .map_err(|e| explain_anonymous_failure(client, &self.graph.graph_ref, e))?
+        .variants;
+        let (variants, unpublished): (Vec<_>, Vec<_>) = variants
+            .into_iter()
+            .partition(|variant| variant.last_published_at.is_some());
+        if !unpublished.is_empty() {
+            let names: Vec<String> = unpublished
+                .into_iter()
+                .map(|variant| variant.name)
+                .collect();
+            eprintln!(
+                "Skipping variants that have never had a schema published: {}",
+                names.join(", ")
+            );
+        }
+
+        client.plan_requests(variants.len() as u64)?;
+        let progress = client_config.get_progress();
+        let profile = get_credentials_profile(client, &self.profile);
+        let filter = self.directives.get_filter();
+        let results: Vec<(String, RoverResult<String>)> = variants
+            .into_par_iter()
+            .map(|variant| {
+                let graph_ref = GraphRef {
+                    name: graph_id.clone(),
+                    variant: variant.name,
+                };
+                progress.emit(ProgressEvent::SchemaFetchStarted {
+                    graph_ref: graph_ref.to_string(),
+                    subgraph: None,
+                    hash: None,
+                    supergraph: self.schema_type == SchemaType::Core,
+                    profile: profile.clone(),
+                });
+                let result = self
+                    .fetch_schema(client, &graph_ref, None)
+                    .map_err(|e| explain_anonymous_failure(client, &graph_ref, e))
+                    .map(|response| {
+                        progress.emit(ProgressEvent::SchemaFetched {
+                            graph_ref: graph_ref.to_string(),
+                            subgraph: None,
+                        });
+                        filter.apply(&response.sdl.contents)
+                    });
+                (graph_ref.variant, result)
+            })
+            .collect();
+
+        Fs::create_dir_all(out_dir)?;
+        let mut files = Vec::new();
+        let mut failures = Vec::new();
+        for (variant, result) in results {
+            match result {
+                Ok(sdl) => {
+                    let path = format!("{}.graphql", variant);
+                    Fs::write_file(out_dir.join(&path), sdl)?;
+                    files.push(VariantSchemaFile { variant, path });
+                }
+                Err(e) => failures.push(format!("{}: {}", variant, e.message())),
+            }
+        }
 
-        Ok(RoverOutput::FetchResponse(fetch_response))
+        if !failures.is_empty() {
+            let mut err = RoverError::new(anyhow!(
+                "Could not fetch the schemas of {} variants. The other {} schemas were written to {}.\n{}",
+                failures.len(),
+                files.len(),
+                out_dir,
+                failures.join("\n")
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Re-run this command to try fetching every variant again.".to_string(),
+            ));
+            return Err(err);
+        }
+        Ok(RoverOutput::VariantSchemasWritten {
+            out_dir: out_dir.to_path_buf(),
+            files,
+        })
     }
 }
@@ -9,7 +9,7 @@ use rover_client::{
 };
 
 use crate::{
-    options::{IntrospectOpts, OutputOpts},
+    options::{DirectiveFilterOpt, IntrospectOpts, OutputOpts},
     RoverOutput, RoverResult,
 };
 
@@ -17,6 +17,9 @@ use crate::{
 pub struct Introspect {
     #[clap(flatten)]
     pub opts: IntrospectOpts,
+
+    #[clap(flatten)]
+    pub directives: DirectiveFilterOpt,
 }
 
 impl Introspect {
@@ -25,7 +28,8 @@ impl Introspect {
             self.exec_and_watch(&client, output_opts)
         } else {
             let sdl = self.exec(&client, true)?;
-            Ok(RoverOutput::Introspection(sdl))
+            let filter = self.directives.get_filter();
+            Ok(RoverOutput::Introspection(sdl).transform_sdl(|sdl| filter.apply(sdl)))
         }
     }
 
@@ -44,7 +48,10 @@ impl Introspect {
     }
 
     pub fn exec_and_watch(&self, client: &Client, output_opts: &OutputOpts) -> ! {
-        self.opts
-            .exec_and_watch(|| self.exec(client, false), output_opts)
+        let filter = self.directives.get_filter();
+        self.opts.exec_and_watch(
+            || self.exec(client, false).map(|sdl| filter.apply(&sdl)),
+            output_opts,
+        )
     }
 }
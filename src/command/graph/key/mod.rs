@@ -0,0 +1,29 @@
+mod rotate;
+
+pub use rotate::Rotate;
+
+use clap::Parser;
+use serde::Serialize;
+
+use crate::utils::client::StudioClientConfig;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Key {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Replace a graph API key with a new one, revoking the old key once the new key works
+    Rotate(rotate::Rotate),
+}
+
+impl Key {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Rotate(command) => command.run(client_config),
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::{thread, time::Duration};
+
+use anyhow::anyhow;
+use clap::Parser;
+use serde::Serialize;
+
+use houston::mask_key;
+use rover_client::blocking::StudioClient;
+use rover_client::operations::config::who_am_i::{self, Actor, ConfigWhoAmIInput};
+use rover_client::operations::graph::key::{
+    self, GraphApiKey, GraphKeyCreateInput, GraphKeyListInput, GraphKeyRemoveInput,
+};
+use rover_client::shared::GraphRef;
+use rover_std::Style;
+
+use crate::options::{GraphRefOpt, ProfileOpt};
+use crate::utils::client::StudioClientConfig;
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Rotate {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The beginning of the graph API key to revoke, i.e. `service:my-graph:AbC1`.
+    /// It must match exactly one of the graph's keys
+    #[arg(long = "revoke", value_name = "KEY_PREFIX")]
+    #[serde(skip_serializing)]
+    revoke: String,
+
+    /// The name to give the new key. Defaults to the name of the key being revoked
+    #[arg(long = "name")]
+    name: Option<String>,
+
+    /// How many seconds to wait after the new key has been verified before
+    /// revoking the old one, so requests already using the old key can finish
+    #[arg(long = "grace-period", value_name = "SECONDS", default_value = "0")]
+    grace_period: u64,
+}
+
+impl Rotate {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self.graph.graph_ref.clone();
+
+        eprintln!(
+            "Rotating API key {} for {} using credentials from the {} profile.",
+            mask_key(&self.revoke),
+            Style::Link.paint(&graph_ref.name),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let keys = key::list(
+            GraphKeyListInput {
+                graph_ref: graph_ref.clone(),
+            },
+            &client,
+        )?;
+        let old_key = find_key(&keys, &self.revoke)?;
+
+        let new_key = key::create(
+            GraphKeyCreateInput {
+                graph_ref: graph_ref.clone(),
+                name: self.name.clone().or_else(|| old_key.name.clone()),
+                role: old_key.role.clone(),
+            },
+            &client,
+        )?;
+
+        // the old key is only revoked once the new one is known to work,
+        // and a new key that doesn't work isn't left lying around
+        if let Err(verify_error) = verify_key(&client_config, &new_key, &graph_ref) {
+            remove_key(&client, &graph_ref, &new_key)?;
+            return Err(verify_error);
+        }
+        eprintln!("Verified the new API key {}.", mask_key(&new_key.token));
+
+        if self.grace_period > 0 {
+            eprintln!(
+                "Waiting {} seconds before revoking the old API key.",
+                self.grace_period
+            );
+            thread::sleep(Duration::from_secs(self.grace_period));
+        }
+        remove_key(&client, &graph_ref, old_key)?;
+
+        Ok(RoverOutput::GraphKeyRotation {
+            graph_ref,
+            new_key,
+            revoked_key_id: old_key.id.clone(),
+        })
+    }
+}
+
+/// The only key whose token starts with `prefix`
+fn find_key<'a>(keys: &'a [GraphApiKey], prefix: &str) -> RoverResult<&'a GraphApiKey> {
+    let matches: Vec<&GraphApiKey> = keys
+        .iter()
+        .filter(|key| !prefix.is_empty() && key.token.starts_with(prefix))
+        .collect();
+    match matches.as_slice() {
+        [key] => Ok(key),
+        [] => {
+            let mut err = RoverError::new(anyhow!(
+                "None of this graph's API keys start with {}.",
+                mask_key(prefix)
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Check the beginning of the key in the API Keys tab of your graph's settings in Apollo Studio.".to_string(),
+            ));
+            Err(err)
+        }
+        _ => {
+            let mut err = RoverError::new(anyhow!(
+                "{} of this graph's API keys start with {}, so Rover can't tell which one to revoke.",
+                matches.len(),
+                mask_key(prefix)
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Pass more of the key to revoke to `--revoke`.".to_string(),
+            ));
+            Err(err)
+        }
+    }
+}
+
+/// Checks that `new_key` authenticates as the graph it was created for
+fn verify_key(
+    client_config: &StudioClientConfig,
+    new_key: &GraphApiKey,
+    graph_ref: &GraphRef,
+) -> RoverResult<()> {
+    let client = client_config.get_client_for_key(new_key.token.clone())?;
+    let identity = who_am_i::run(ConfigWhoAmIInput {}, &client)?;
+    if identity.key_actor_type == Actor::GRAPH && identity.id == graph_ref.name {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "The new API key did not authenticate as {}, so the old key was not revoked.",
+            graph_ref.name
+        )
+        .into())
+    }
+}
+
+fn remove_key(client: &StudioClient, graph_ref: &GraphRef, key: &GraphApiKey) -> RoverResult<()> {
+    key::remove(
+        GraphKeyRemoveInput {
+            graph_ref: graph_ref.clone(),
+            id: key.id.clone(),
+        },
+        client,
+    )?;
+    eprintln!("Revoked the API key {}.", mask_key(&key.token));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_keys() -> Vec<GraphApiKey> {
+        ["service:mygraph:abc123", "service:mygraph:abd456"]
+            .iter()
+            .enumerate()
+            .map(|(i, token)| GraphApiKey {
+                id: format!("key-{}", i),
+                name: None,
+                role: "GRAPH_ADMIN".to_string(),
+                token: token.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_finds_the_key_matching_a_prefix() {
+        let keys = mock_keys();
+        assert_eq!(find_key(&keys, "service:mygraph:abc").unwrap().id, "key-0");
+    }
+
+    #[test]
+    fn it_errs_on_unknown_or_ambiguous_prefixes() {
+        let keys = mock_keys();
+        assert!(find_key(&keys, "service:mygraph:ab").is_err());
+        assert!(find_key(&keys, "service:othergraph").is_err());
+        assert!(find_key(&keys, "").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use std::io;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use camino::Utf8PathBuf;
+use clap::Parser;
+use serde::Serialize;
+
+use rover_client::operations::graph::fetch::{self, GraphFetchInput};
+use rover_client::shared::GraphRef;
+use rover_std::Style;
+
+use crate::options::ProfileOpt;
+use crate::utils::client::StudioClientConfig;
+use crate::utils::parsers::FileDescriptorType;
+use crate::utils::rover_config::RoverConfig;
+use crate::utils::schema_lint::{self, LintFailure, LintLevel, LintRule};
+use crate::{RoverError, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Lint {
+    /// <NAME>@<VARIANT> of the graph in Apollo Studio whose published schema should be linted.
+    /// Leave this off and pass `--schema` to lint a local schema instead
+    #[arg(
+        value_name = "GRAPH_REF",
+        required_unless_present = "schema",
+        conflicts_with = "schema"
+    )]
+    #[serde(skip_serializing)]
+    graph_ref: Option<GraphRef>,
+
+    /// The schema file to lint. You can pass `-` to use stdin instead of a file.
+    #[arg(long, short = 's')]
+    #[serde(skip_serializing)]
+    schema: Option<FileDescriptorType>,
+
+    /// Change the level of a lint rule, i.e. `--rule INPUT_TYPE_SUFFIX=error`.
+    /// Levels can be `error`, `warning`, or `off`. Errors make the command fail
+    #[arg(long = "rule", value_name = "RULE=LEVEL", value_parser = parse_rule_level)]
+    #[serde(skip_serializing)]
+    rules: Vec<(LintRule, Option<LintLevel>)>,
+
+    /// The config file to read rule levels from, instead of the nearest `.rover.yaml`
+    /// in the current directory or its parents. `--rule` takes precedence over the file
+    #[arg(long, value_name = "PATH")]
+    #[serde(skip_serializing)]
+    rover_config: Option<Utf8PathBuf>,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+}
+
+impl Lint {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let sdl = match (&self.graph_ref, &self.schema) {
+            (Some(graph_ref), _) => {
+                let client = client_config.get_authenticated_client(&self.profile)?;
+                eprintln!(
+                    "Linting the schema of {} using credentials from the {} profile.",
+                    Style::Link.paint(graph_ref.to_string()),
+                    Style::Command.paint(&self.profile.profile_name)
+                );
+                fetch::run(
+                    GraphFetchInput {
+                        graph_ref: graph_ref.clone(),
+                        hash: None,
+                    },
+                    &client,
+                )?
+                .sdl
+                .contents
+            }
+            (None, Some(schema)) => schema.read_file_descriptor("SDL", &mut io::stdin())?,
+            (None, None) => unreachable!("clap requires one of <GRAPH_REF> or --schema"),
+        };
+
+        let config = self.rules.iter().fold(
+            RoverConfig::load(self.rover_config.as_deref())?
+                .lint
+                .get_lint_config(),
+            |config, (rule, level)| config.with_rule(*rule, *level),
+        );
+        let response = schema_lint::lint(&sdl, &config);
+
+        if response.count(LintLevel::Error) > 0 {
+            Err(RoverError::new(LintFailure { response }))
+        } else {
+            Ok(RoverOutput::LintResponse(response))
+        }
+    }
+}
+
+fn parse_rule_level(rule_level: &str) -> Result<(LintRule, Option<LintLevel>), io::Error> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, anyhow!(msg));
+
+    let (rule, level) = rule_level
+        .split_once('=')
+        .ok_or_else(|| invalid(format!("'{}' should look like RULE=LEVEL", rule_level)))?;
+    let rule = LintRule::from_str(&rule.trim().to_uppercase())
+        .map_err(|_| invalid(format!("'{}' is not a lint rule", rule)))?;
+    let level = match level.trim().to_lowercase().as_str() {
+        "error" => Some(LintLevel::Error),
+        "warning" | "warn" => Some(LintLevel::Warning),
+        "off" => None,
+        _ => {
+            return Err(invalid(format!(
+                "'{}' is not a lint level. Valid levels are error, warning, and off",
+                level
+            )))
+        }
+    };
+    Ok((rule, level))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_rule_levels() {
+        assert_eq!(
+            parse_rule_level("input_type_suffix=off").unwrap(),
+            (LintRule::InputTypeSuffix, None)
+        );
+        assert_eq!(
+            parse_rule_level("FIELD_NAMES_SHOULD_BE_CAMEL_CASE=error").unwrap(),
+            (
+                LintRule::FieldNamesShouldBeCamelCase,
+                Some(LintLevel::Error)
+            )
+        );
+        assert!(parse_rule_level("NOT_A_RULE=error").is_err());
+        assert!(parse_rule_level("INPUT_TYPE_SUFFIX=loud").is_err());
+        assert!(parse_rule_level("INPUT_TYPE_SUFFIX").is_err());
+    }
+}
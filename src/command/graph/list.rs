@@ -0,0 +1,69 @@
+use clap::Parser;
+use serde::Serialize;
+
+use rover_client::operations::graph::list::{self, GraphInfo, GraphListInput};
+use rover_std::Style;
+
+use crate::options::{ListItem, ListOpts, ListValue, ProfileOpt, ProfilesOpt};
+use crate::utils::client::StudioClientConfig;
+use crate::utils::profiles::for_each_profile;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct List {
+    #[clap(flatten)]
+    profiles: ProfilesOpt,
+
+    /// Only list the graphs that belong to the organization with this ID
+    #[arg(long = "organization", value_name = "ORGANIZATION_ID")]
+    organization: Option<String>,
+
+    #[clap(flatten)]
+    list: ListOpts,
+}
+
+impl List {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        for_each_profile(&self.profiles, &client_config, |profile| {
+            self.list(&client_config, profile)
+        })
+    }
+
+    fn list(
+        &self,
+        client_config: &StudioClientConfig,
+        profile: &ProfileOpt,
+    ) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(profile)?;
+
+        eprintln!(
+            "Listing graphs using credentials from the {} profile.",
+            Style::Command.paint(&profile.profile_name)
+        );
+
+        let mut list_response = list::run(
+            GraphListInput {
+                organization: self.organization.clone(),
+            },
+            &client,
+        )?;
+        list_response.graphs = self.list.apply(list_response.graphs)?;
+
+        Ok(RoverOutput::GraphList(list_response))
+    }
+}
+
+impl ListItem for GraphInfo {
+    const FIELDS: &'static [&'static str] = &["organization", "id", "title", "variants"];
+
+    fn get_field(&self, field: &str) -> Option<ListValue> {
+        match field {
+            "organization" => self.organization.clone().map(ListValue::Text),
+            "id" => Some(ListValue::Text(self.id.clone())),
+            "title" => Some(ListValue::Text(self.title.clone())),
+            // filters match against every variant at once, i.e. `variants=~(^|,)prod(,|$)`
+            "variants" => Some(ListValue::Text(self.variants.join(","))),
+            _ => None,
+        }
+    }
+}
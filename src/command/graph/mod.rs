@@ -1,20 +1,34 @@
+mod await_check;
 mod check;
 mod delete;
+mod diff;
 mod fetch;
 mod introspect;
+mod key;
+mod lint;
+mod list;
+mod open;
 mod publish;
+mod rollback;
 
+pub use await_check::Await;
 pub use check::Check;
 pub use delete::Delete;
-pub use fetch::Fetch;
+pub use diff::Diff;
+pub use fetch::{Fetch, VariantSchemaFile};
 pub use introspect::Introspect;
+pub use key::Key;
+pub use lint::Lint;
+pub use list::List;
+pub use open::Open;
 pub use publish::Publish;
+pub use rollback::Rollback;
 
 use clap::Parser;
 use serde::Serialize;
 
 use crate::options::OutputOpts;
-use crate::utils::client::StudioClientConfig;
+use crate::utils::{client::StudioClientConfig, duration_budget::DurationBudget};
 use crate::{RoverOutput, RoverResult};
 
 use rover_client::shared::GitContext;
@@ -27,6 +41,9 @@ pub struct Graph {
 
 #[derive(Debug, Serialize, Parser)]
 pub enum Command {
+    /// Wait for a check started with `rover graph check --background` to complete
+    Await(await_check::Await),
+
     /// Check for breaking changes in a local graph schema
     /// against a graph schema in the Apollo graph registry
     Check(check::Check),
@@ -34,17 +51,45 @@ pub enum Command {
     /// Delete a graph schema from the Apollo graph registry
     Delete(delete::Delete),
 
+    /// Compare the API schemas of two graph variants, or of a graph variant and a local schema
+    Diff(diff::Diff),
+
     /// Fetch a graph schema from the Apollo graph registry
     Fetch(fetch::Fetch),
 
+    /// Manage the API keys of a graph in Apollo Studio
+    Key(key::Key),
+
+    /// Lint a local graph schema, or a graph schema in the Apollo graph registry
+    Lint(lint::Lint),
+
+    /// List the graphs and variants your API key can access
+    List(list::List),
+
+    /// Open a graph's schema, checks, or launches in Apollo Studio
+    Open(open::Open),
+
     /// Publish an updated graph schema to the Apollo graph registry
     Publish(publish::Publish),
 
+    /// Republish a previously published schema to a graph variant
+    Rollback(rollback::Rollback),
+
     /// Introspect current graph schema.
     Introspect(introspect::Introspect),
 }
 
 impl Graph {
+    /// How long this command is expected to take before Rover suggests ways to speed it up
+    pub(crate) fn duration_budget(&self) -> Option<DurationBudget> {
+        match &self.command {
+            // checks wait on Apollo Studio until they finish, key rotations can wait
+            // out a grace period, and opening a page in the browser doesn't wait at all
+            Command::Await(_) | Command::Check(_) | Command::Key(_) | Command::Open(_) => None,
+            _ => Some(DurationBudget::studio_request()),
+        }
+    }
+
     pub fn run(
         &self,
         client_config: StudioClientConfig,
@@ -53,12 +98,19 @@ impl Graph {
         output_opts: &OutputOpts,
     ) -> RoverResult<RoverOutput> {
         match &self.command {
+            Command::Await(command) => command.run(client_config, checks_timeout_seconds),
             Command::Check(command) => {
                 command.run(client_config, git_context, checks_timeout_seconds)
             }
             Command::Delete(command) => command.run(client_config),
+            Command::Diff(command) => command.run(client_config),
             Command::Fetch(command) => command.run(client_config),
+            Command::Key(command) => command.run(client_config),
+            Command::Lint(command) => command.run(client_config),
+            Command::List(command) => command.run(client_config),
+            Command::Open(command) => command.run(client_config),
             Command::Publish(command) => command.run(client_config, git_context),
+            Command::Rollback(command) => command.run(client_config, git_context),
             Command::Introspect(command) => {
                 command.run(client_config.get_reqwest_client()?, output_opts)
             }
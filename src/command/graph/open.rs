@@ -0,0 +1,47 @@
+use clap::Parser;
+use serde::Serialize;
+
+use rover_client::operations::graph::variant::{self, VariantListInput};
+use rover_std::Style;
+
+use crate::options::{GraphRefOpt, ProfileOpt, StudioPageOpt};
+use crate::utils::{browser, client::StudioClientConfig};
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Open {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    #[clap(flatten)]
+    page: StudioPageOpt,
+}
+
+impl Open {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        eprintln!(
+            "Looking up {} in Apollo Studio using credentials from the {} profile.",
+            Style::Link.paint(self.graph.graph_ref.to_string()),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let root_url = variant::run(
+            VariantListInput {
+                graph_ref: self.graph.graph_ref.clone(),
+            },
+            &client,
+        )?;
+
+        let url = self
+            .page
+            .page
+            .get_url(&root_url, &self.graph.graph_ref, None);
+        browser::open(&url)?;
+
+        Ok(RoverOutput::EmptySuccess)
+    }
+}
@@ -1,3 +1,4 @@
+use anyhow::anyhow;
 use clap::Parser;
 use serde::Serialize;
 
@@ -5,9 +6,10 @@ use rover_client::operations::graph::publish::{self, GraphPublishInput};
 use rover_client::shared::GitContext;
 use rover_std::Style;
 
-use crate::options::{GraphRefOpt, ProfileOpt, SchemaOpt};
+use crate::options::{GraphRefOpt, ProfileOpt, ProtectedVariantOpt, RetryOpt, SchemaOpt};
 use crate::utils::client::StudioClientConfig;
-use crate::{RoverOutput, RoverResult};
+use crate::utils::schema_validate;
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
 
 #[derive(Debug, Serialize, Parser)]
 pub struct Publish {
@@ -20,6 +22,16 @@ pub struct Publish {
     #[clap(flatten)]
     #[serde(skip_serializing)]
     schema: SchemaOpt,
+
+    #[clap(flatten)]
+    retry: RetryOpt,
+
+    #[clap(flatten)]
+    protected_variant: ProtectedVariantOpt,
+
+    /// Skip validating the schema locally before publishing it, leaving validation to Apollo Studio
+    #[arg(long)]
+    skip_validation: bool,
 }
 
 impl Publish {
@@ -36,10 +48,16 @@ impl Publish {
             Style::Command.paint(&self.profile.profile_name)
         );
 
+        self.protected_variant.confirm(&self.graph.graph_ref)?;
+
         let proposed_schema = self
             .schema
             .read_file_descriptor("SDL", &mut std::io::stdin())?;
 
+        if !self.skip_validation {
+            validate(&proposed_schema)?;
+        }
+
         tracing::debug!("Publishing \n{}", &proposed_schema);
 
         let publish_response = publish::run(
@@ -47,6 +65,7 @@ impl Publish {
                 graph_ref: self.graph.graph_ref.clone(),
                 proposed_schema,
                 git_context,
+                retries: self.retry.retries,
             },
             &client,
         )?;
@@ -57,3 +76,23 @@ impl Publish {
         })
     }
 }
+
+/// Fails with every error in the schema, so typos don't need a round trip to Apollo Studio
+fn validate(proposed_schema: &str) -> RoverResult<()> {
+    let errors = schema_validate::validate(proposed_schema);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let mut msg = match errors.len() {
+        1 => "The schema has 1 error, so it was not published:".to_string(),
+        count => format!("The schema has {} errors, so it was not published:", count),
+    };
+    for error in &errors {
+        msg.push_str(&format!("\n  {}", error));
+    }
+    let mut err = RoverError::new(anyhow!(msg));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(
+        "Fix the errors above and publish again. If you're sure the schema is valid, pass `--skip-validation` to let Apollo Studio validate it instead.".to_string(),
+    ));
+    Err(err)
+}
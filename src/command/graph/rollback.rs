@@ -0,0 +1,121 @@
+use clap::Parser;
+use serde::Serialize;
+
+use rover_client::blocking::StudioClient;
+use rover_client::operations::graph::fetch::{self, GraphFetchInput};
+use rover_client::operations::graph::publish::{self, GraphPublishInput};
+use rover_client::shared::GitContext;
+use rover_std::{prompt, Style};
+
+use crate::options::{GraphRefOpt, ProfileOpt, ProtectedVariantOpt, RetryOpt};
+use crate::utils::client::StudioClientConfig;
+use crate::utils::parsers::parse_rollback_target;
+use crate::utils::schema_diff::SchemaDiff;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Rollback {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The full SHA256 hash of the previously published schema to republish, as
+    /// shown on the variant's History page in Studio. `previous` isn't supported,
+    /// since Rover can't look up a variant's earlier publishes
+    #[arg(long = "to", value_name = "SCHEMA_HASH", value_parser = parse_rollback_target)]
+    to: String,
+
+    /// Print the changes the rollback would make to the variant's schema without publishing
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Skips the step where the command asks for user confirmation before
+    /// republishing the schema.
+    #[arg(long)]
+    confirm: bool,
+
+    #[clap(flatten)]
+    retry: RetryOpt,
+
+    #[clap(flatten)]
+    protected_variant: ProtectedVariantOpt,
+}
+
+impl Rollback {
+    pub fn run(
+        &self,
+        client_config: StudioClientConfig,
+        git_context: GitContext,
+    ) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = &self.graph.graph_ref;
+        eprintln!(
+            "Rolling back the schema of {} using credentials from the {} profile.",
+            Style::Link.paint(graph_ref.to_string()),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let target_hash = self.to.clone();
+        let current_sdl = self.fetch_sdl(None, &client)?;
+        let target_sdl = self.fetch_sdl(Some(target_hash.clone()), &client)?;
+        let target = format!("schema {}", &target_hash[..6]);
+
+        if current_sdl == target_sdl {
+            eprintln!(
+                "{} already has {}, so there is nothing to roll back.",
+                graph_ref, target
+            );
+            return Ok(RoverOutput::EmptySuccess);
+        }
+
+        let diff = SchemaDiff::new(
+            format!("the current schema of {}", graph_ref),
+            &current_sdl,
+            target.clone(),
+            &target_sdl,
+        )?;
+        if self.dry_run {
+            return Ok(RoverOutput::SchemaDiff(diff));
+        }
+        eprintln!("{}", diff.get_table());
+
+        self.protected_variant.confirm(graph_ref)?;
+        if !self.confirm
+            && !prompt::prompt_confirm_default_no(&format!(
+                "Would you like to republish {} to {}?",
+                target, graph_ref
+            ))?
+        {
+            eprintln!("Rollback cancelled by user");
+            return Ok(RoverOutput::EmptySuccess);
+        }
+
+        let publish_response = publish::run(
+            GraphPublishInput {
+                graph_ref: graph_ref.clone(),
+                proposed_schema: target_sdl,
+                git_context,
+                retries: self.retry.retries,
+            },
+            &client,
+        )?;
+
+        Ok(RoverOutput::GraphPublishResponse {
+            graph_ref: graph_ref.clone(),
+            publish_response,
+        })
+    }
+
+    fn fetch_sdl(&self, hash: Option<String>, client: &StudioClient) -> RoverResult<String> {
+        let fetch_response = fetch::run(
+            GraphFetchInput {
+                graph_ref: self.graph.graph_ref.clone(),
+                hash,
+            },
+            client,
+        )?;
+        Ok(fetch_response.sdl.contents)
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::anyhow;
 use camino::Utf8PathBuf;
 use clap::Parser;
-use rover_std::Style;
+use rover_std::{tmp, Style};
 use serde::Serialize;
 
 use binstall::{Installer, InstallerError};
@@ -15,7 +15,7 @@ use std::convert::TryFrom;
 use std::env;
 
 mod plugin;
-pub(crate) use plugin::{Plugin, PluginInstaller};
+pub(crate) use plugin::{list_installed_plugins, InstalledPlugin, Plugin, PluginInstaller};
 
 #[derive(Debug, Serialize, Parser)]
 pub struct Install {
@@ -46,6 +46,8 @@ impl Install {
                 self.elv2_license_accepter
                     .require_elv2_license(&client_config)?;
             }
+            // plugins are downloaded to a secure temp directory
+            tmp::cleanup_on_ctrl_c()?;
             let plugin_installer = PluginInstaller::new(client_config, rover_installer);
             plugin_installer.install(plugin, false)?;
 
@@ -100,7 +102,6 @@ impl Install {
         }
     }
 
-    #[cfg(feature = "composition-js")]
     pub(crate) fn get_versioned_plugin(
         &self,
         override_install_path: Option<Utf8PathBuf>,
@@ -124,16 +125,40 @@ impl Install {
         binary_name: String,
         override_install_path: Option<Utf8PathBuf>,
     ) -> RoverResult<Installer> {
-        if let Ok(executable_location) = env::current_exe() {
-            let executable_location = Utf8PathBuf::try_from(executable_location)?;
-            Ok(Installer {
-                binary_name,
-                force_install: self.force,
-                override_install_path,
-                executable_location,
-            })
-        } else {
-            Err(anyhow!("Failed to get the current executable's path.").into())
-        }
+        get_installer(binary_name, self.force, override_install_path)
+    }
+}
+
+/// The directory plugins are installed to, which `APOLLO_NODE_MODULES_BIN_DIR` overrides
+pub(crate) fn get_plugin_dir(
+    override_install_path: Option<Utf8PathBuf>,
+) -> RoverResult<Utf8PathBuf> {
+    let rover_installer = get_installer(PKG_NAME.to_string(), false, override_install_path)?;
+    Ok(rover_installer.get_bin_dir_path()?)
+}
+
+/// The directory Rover keeps its own files in, i.e. `~/.rover`
+pub(crate) fn get_rover_dir(
+    override_install_path: Option<Utf8PathBuf>,
+) -> RoverResult<Utf8PathBuf> {
+    let rover_installer = get_installer(PKG_NAME.to_string(), false, override_install_path)?;
+    Ok(rover_installer.get_base_dir_path()?)
+}
+
+fn get_installer(
+    binary_name: String,
+    force_install: bool,
+    override_install_path: Option<Utf8PathBuf>,
+) -> RoverResult<Installer> {
+    if let Ok(executable_location) = env::current_exe() {
+        let executable_location = Utf8PathBuf::try_from(executable_location)?;
+        Ok(Installer {
+            binary_name,
+            force_install,
+            override_install_path,
+            executable_location,
+        })
+    } else {
+        Err(anyhow!("Failed to get the current executable's path.").into())
     }
 }
@@ -2,13 +2,14 @@ use std::{env::consts, str::FromStr};
 
 use anyhow::{anyhow, Context};
 use apollo_federation_types::config::{FederationVersion, PluginVersion, RouterVersion};
-use binstall::Installer;
-use camino::Utf8PathBuf;
+use binstall::{Installer, InstallerError, PluginVerification};
+use camino::{Utf8Path, Utf8PathBuf};
 use rover_std::Fs;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 
-use crate::{utils::client::StudioClientConfig, RoverError, RoverErrorSuggestion, RoverResult};
+use crate::utils::{client::StudioClientConfig, env::RoverEnvKey, progress::ProgressEvent};
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Plugin {
@@ -136,6 +137,31 @@ impl FromStr for Plugin {
     }
 }
 
+/// A version of a plugin installed in Rover's plugin directory
+#[derive(Clone, Debug, Serialize, Eq, PartialEq)]
+pub struct InstalledPlugin {
+    pub name: String,
+    pub version: Version,
+    pub path: Utf8PathBuf,
+}
+
+impl InstalledPlugin {
+    /// Reads the name and version of a plugin from the name of its binary, i.e. `supergraph-v2.3.1`
+    pub(crate) fn from_path(path: &Utf8Path, plugin_name: &str) -> Option<Self> {
+        let file_name = path.file_name()?;
+        let version = file_name
+            .strip_suffix(consts::EXE_SUFFIX)
+            .unwrap_or(file_name)
+            .strip_prefix(plugin_name)?
+            .strip_prefix("-v")?;
+        Some(Self {
+            name: plugin_name.to_string(),
+            version: Version::parse(version).ok()?,
+            path: path.to_path_buf(),
+        })
+    }
+}
+
 pub struct PluginInstaller {
     client_config: StudioClientConfig,
     rover_installer: Installer,
@@ -324,15 +350,76 @@ impl PluginInstaller {
     fn do_install(&self, plugin: &Plugin) -> RoverResult<Option<Utf8PathBuf>> {
         let plugin_name = plugin.get_name();
         let plugin_tarball_url = plugin.get_tarball_url()?;
-        eprintln!("downloading the '{plugin_name}' plugin from {plugin_tarball_url}");
-        Ok(self.rover_installer.install_plugin(
-            &plugin_name,
-            &plugin_tarball_url,
-            &self.client_config.get_reqwest_client()?,
-        )?)
+        let progress = self.client_config.get_progress();
+        progress.emit(ProgressEvent::PluginDownloadStarted {
+            plugin: plugin_name.clone(),
+            url: plugin_tarball_url.clone(),
+        });
+        let install_location = self
+            .rover_installer
+            .install_plugin(
+                &plugin_name,
+                &plugin_tarball_url,
+                &plugin.get_target_arch()?,
+                &self.client_config.get_reqwest_client()?,
+                &get_plugin_verification(),
+            )
+            .map_err(|e| get_verification_error(&plugin_name, e))?;
+        if let Some(path) = &install_location {
+            progress.emit(ProgressEvent::PluginInstalled {
+                plugin: plugin_name,
+                path: path.to_string(),
+            });
+        }
+        Ok(install_location)
     }
 }
 
+/// How downloaded plugins are verified, which `$APOLLO_REQUIRE_PLUGIN_CHECKSUMS`
+/// and `$APOLLO_PLUGIN_PUBLIC_KEY` make stricter
+fn get_plugin_verification() -> PluginVerification {
+    let require_checksum = std::env::var(RoverEnvKey::RequirePluginChecksums.to_string())
+        .map(|value| {
+            let value = value.to_lowercase();
+            value == "true" || value == "1"
+        })
+        .unwrap_or(false);
+    let public_key = std::env::var(RoverEnvKey::PluginPublicKey.to_string())
+        .ok()
+        .filter(|key| !key.trim().is_empty());
+    PluginVerification {
+        require_checksum,
+        public_key,
+    }
+}
+
+/// Adds a suggestion to the errors of a plugin that failed verification
+fn get_verification_error(plugin_name: &str, e: InstallerError) -> RoverError {
+    let suggestion = match &e {
+        InstallerError::ChecksumMismatch { .. } => format!(
+            "The '{}' plugin was not installed, since it may have been tampered with. Try installing it again, and if the checksum still doesn't match, please report it.",
+            plugin_name
+        ),
+        InstallerError::ChecksumMissing { .. } => format!(
+            "Install a version of the '{}' plugin that has published checksums, or unset `${}` to install it unverified.",
+            plugin_name,
+            RoverEnvKey::RequirePluginChecksums
+        ),
+        InstallerError::SignatureInvalid => format!(
+            "Make sure `${}` is the public key the checksums of your plugin mirror are signed with.",
+            RoverEnvKey::PluginPublicKey
+        ),
+        _ => return e.into(),
+    };
+    let mut err = RoverError::new(anyhow!(
+        "Could not verify the '{}' plugin: {}",
+        plugin_name,
+        e
+    ));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(suggestion));
+    err
+}
+
 fn find_installed_plugins(
     plugin_dir: &Utf8PathBuf,
     plugin_name: &str,
@@ -406,3 +493,66 @@ fn find_installed_plugin(
         Err(err)
     }
 }
+
+/// Every version of a plugin installed in `plugin_dir`, sorted by semver
+pub(crate) fn list_installed_plugins(
+    plugin_dir: &Utf8Path,
+    plugin_name: &str,
+) -> RoverResult<Vec<InstalledPlugin>> {
+    // nothing has been installed yet
+    if !plugin_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut installed_plugins = Vec::new();
+    for entry in Fs::get_dir_entries(plugin_dir)?.flatten() {
+        if matches!(entry.file_type(), Ok(file_type) if file_type.is_file()) {
+            if let Some(installed_plugin) = InstalledPlugin::from_path(entry.path(), plugin_name) {
+                installed_plugins.push(installed_plugin);
+            }
+        }
+    }
+    installed_plugins.sort_by(|a, b| a.version.cmp(&b.version));
+    Ok(installed_plugins)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use assert_fs::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn it_lists_installed_plugins_by_version() {
+        let tmp_home = TempDir::new().unwrap();
+        let plugin_dir = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        for binary in [
+            "supergraph-v2.3.1",
+            "supergraph-v0.36.0",
+            "supergraph-v2.10.0",
+            "supergraph-vlatest",
+            "router-v1.10.0",
+            "rover",
+        ] {
+            fs::write(
+                plugin_dir.join(format!("{}{}", binary, consts::EXE_SUFFIX)),
+                "",
+            )
+            .unwrap();
+        }
+        fs::create_dir(plugin_dir.join("supergraph-v2.4.0")).unwrap();
+
+        let versions: Vec<String> = list_installed_plugins(&plugin_dir, "supergraph")
+            .unwrap()
+            .iter()
+            .map(|plugin| plugin.version.to_string())
+            .collect();
+        assert_eq!(versions, vec!["0.36.0", "2.3.1", "2.10.0"]);
+        assert!(
+            list_installed_plugins(&plugin_dir.join("missing"), "supergraph")
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
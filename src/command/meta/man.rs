@@ -0,0 +1,33 @@
+use camino::Utf8PathBuf;
+use clap::{CommandFactory, Parser};
+use serde::Serialize;
+
+use rover_std::{Fs, Style};
+
+use crate::cli::Rover;
+use crate::utils::man;
+use crate::{RoverOutput, RoverResult, PKG_VERSION};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Man {
+    /// The directory to write the manpages to, i.e. `rover-subgraph-publish.1`.
+    /// It is created if it doesn't exist yet
+    #[arg(long = "out", value_name = "DIR")]
+    out: Utf8PathBuf,
+}
+
+impl Man {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        Fs::create_dir_all(&self.out)?;
+        let pages = man::render_all(Rover::command(), PKG_VERSION);
+        for page in &pages {
+            Fs::write_file(self.out.join(page.get_file_name()), &page.contents)?;
+        }
+        eprintln!(
+            "Wrote {} manpages to {}",
+            pages.len(),
+            Style::Path.paint(&self.out)
+        );
+        Ok(RoverOutput::EmptySuccess)
+    }
+}
@@ -0,0 +1,28 @@
+mod man;
+
+pub use man::Man;
+
+use clap::Parser;
+use serde::Serialize;
+
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Meta {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Generate manpages for every Rover command
+    Man(man::Man),
+}
+
+impl Meta {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Man(command) => command.run(),
+        }
+    }
+}
@@ -7,11 +7,16 @@ mod fed2;
 mod graph;
 mod info;
 pub(crate) mod install;
+mod meta;
+mod ops;
 mod readme;
+pub(crate) mod run;
+mod schema;
 pub(crate) mod subgraph;
 mod supergraph;
 mod template;
 mod update;
+mod variant;
 
 pub(crate) mod output;
 
@@ -24,9 +29,14 @@ pub use fed2::Fed2;
 pub use graph::Graph;
 pub use info::Info;
 pub use install::Install;
+pub use meta::Meta;
+pub use ops::Ops;
 pub use output::RoverOutput;
 pub use readme::Readme;
+pub use run::Run;
+pub use schema::Schema;
 pub use subgraph::Subgraph;
 pub use supergraph::Supergraph;
 pub use template::Template;
 pub use update::Update;
+pub use variant::Variant;
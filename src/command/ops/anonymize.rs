@@ -0,0 +1,84 @@
+use std::io;
+
+use anyhow::{anyhow, Context};
+use camino::Utf8PathBuf;
+use clap::Parser;
+use serde::Serialize;
+use serde_json::Value;
+
+use rover_std::{Fs, Style};
+
+use crate::utils::operation_anonymize::{NameMapping, OperationAnonymizer};
+use crate::utils::parsers::FileDescriptorType;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Anonymize {
+    /// The operations to anonymize, either as a GraphQL document or as a persisted
+    /// query manifest. You can pass `-` to use stdin instead of a file.
+    #[arg(value_name = "OPERATIONS")]
+    #[serde(skip_serializing)]
+    operations: FileDescriptorType,
+
+    /// A JSON file mapping original names to the names that should replace them, for each kind
+    /// of name, i.e. `{ "types": { "Product": "Type1" } }`. Any names it leaves out are given
+    /// generated names
+    #[arg(long = "mapping", value_name = "MAPPING")]
+    #[serde(skip_serializing)]
+    mapping: Option<Utf8PathBuf>,
+
+    /// Save every replacement that was made to a JSON file, which can be passed to
+    /// `--mapping` to anonymize more operations consistently. Keep this file private
+    #[arg(long = "save-mapping", value_name = "MAPPING")]
+    #[serde(skip_serializing)]
+    save_mapping: Option<Utf8PathBuf>,
+}
+
+impl Anonymize {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        let operations = self
+            .operations
+            .read_file_descriptor("operations", &mut io::stdin())?;
+
+        let mapping = match &self.mapping {
+            Some(path) => {
+                let contents = Fs::read_file(path)?;
+                serde_json::from_str::<NameMapping>(&contents).with_context(|| {
+                    format!(
+                        "{} should be a JSON object of names to replacements for each kind of name",
+                        path
+                    )
+                })?
+            }
+            None => NameMapping::default(),
+        };
+        let mut anonymizer = OperationAnonymizer::new(mapping);
+
+        // anything that parses as a JSON object is treated as a manifest, since
+        // GraphQL documents never do
+        let anonymized = match serde_json::from_str::<Value>(&operations) {
+            Ok(manifest @ Value::Object(_)) => {
+                let manifest = anonymizer.anonymize_manifest(manifest)?;
+                serde_json::to_string_pretty(&manifest)?
+            }
+            Ok(_) => {
+                let message = "JSON operations should be a persisted query manifest with an \"operations\" array";
+                return Err(anyhow!(message).into());
+            }
+            Err(_) => anonymizer.anonymize_document(&operations)?,
+        };
+
+        if let Some(path) = &self.save_mapping {
+            Fs::write_file(
+                path,
+                serde_json::to_string_pretty(anonymizer.get_mapping())?,
+            )?;
+            eprintln!(
+                "Saved the names that were replaced to {}. Keep this file private, since it can be used to undo the anonymization.",
+                Style::Path.paint(path)
+            );
+        }
+
+        Ok(RoverOutput::AnonymizedOperations(anonymized))
+    }
+}
@@ -0,0 +1,29 @@
+mod anonymize;
+
+pub use anonymize::Anonymize;
+
+use clap::Parser;
+use serde::Serialize;
+
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Ops {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Anonymize the names and literal values in a set of operations, so they
+    /// can be shared without revealing business data
+    Anonymize(anonymize::Anonymize),
+}
+
+impl Ops {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Anonymize(command) => command.run(),
+        }
+    }
+}
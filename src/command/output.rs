@@ -2,27 +2,47 @@ use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::io;
 
-use crate::command::supergraph::compose::CompositionOutput;
+use anyhow::anyhow;
+
+use crate::command::config::whoami::WhoAmIResponse;
+use crate::command::dev::{DevSessionProcesses, DevSessionStatus};
+use crate::command::graph::VariantSchemaFile;
+use crate::command::install::InstalledPlugin;
+use crate::command::run::RunSummary;
+use crate::command::supergraph::compose::{get_hint_code, get_hint_subgraphs, CompositionOutput};
 use crate::options::JsonVersion;
+use crate::utils::introspection_json::sdl_to_introspection_json;
+use crate::utils::line_diff::{self, DiffLine, DiffLineKind};
+use crate::utils::profiles::ProfileOutput;
+use crate::utils::schema_diff::SchemaDiff;
+use crate::utils::schema_export::ExportRecord;
+use crate::utils::schema_lint::LintResponse;
+use crate::utils::schema_split::SchemaFile;
+use crate::utils::schema_stats::SchemaStats;
+use crate::utils::supergraph_diff::SupergraphDiff;
 use crate::utils::table::{self, row};
-use crate::RoverError;
+use crate::{RoverError, RoverResult};
 
 use crate::options::GithubTemplate;
 use atty::Stream;
 use calm_io::{stderr, stderrln};
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use crossterm::style::Attribute::Underlined;
 use rover_client::operations::contract::describe::ContractDescribeResponse;
 use rover_client::operations::contract::publish::ContractPublishResponse;
+use rover_client::operations::graph::key::GraphApiKey;
+use rover_client::operations::graph::list::GraphListResponse;
 use rover_client::operations::graph::publish::GraphPublishResponse;
 use rover_client::operations::subgraph::delete::SubgraphDeleteResponse;
 use rover_client::operations::subgraph::list::SubgraphListResponse;
 use rover_client::operations::subgraph::publish::SubgraphPublishResponse;
+use rover_client::operations::variant::create::VariantCreateResponse;
+use rover_client::operations::variant::list::VariantListResponse;
 use rover_client::shared::{
     CheckRequestSuccessResult, CheckResponse, FetchResponse, GraphRef, SdlType,
 };
 use rover_client::RoverClientError;
-use rover_std::Style;
+use rover_std::{Emoji, Style};
 use serde_json::{json, Value};
 use termimad::MadSkin;
 
@@ -41,9 +61,30 @@ pub enum RoverOutput {
     DocsList(BTreeMap<&'static str, &'static str>),
     FetchResponse(FetchResponse),
     SupergraphSchema(String),
+    SupergraphConfig {
+        config: String,
+        /// the subgraph schemas written alongside the config, which it points to
+        schema_files: Vec<Utf8PathBuf>,
+    },
     CompositionResult(CompositionOutput),
+    GraphList(GraphListResponse),
     SubgraphList(SubgraphListResponse),
+    VariantList(VariantListResponse),
+    VariantCreateResponse(VariantCreateResponse),
     CheckResponse(CheckResponse),
+    SchemaDiff(SchemaDiff),
+    SupergraphDiff(SupergraphDiff),
+    InstalledPlugins(Vec<InstalledPlugin>),
+    PrunedPlugins {
+        removed: Vec<InstalledPlugin>,
+        dry_run: bool,
+    },
+    LintResponse(LintResponse),
+    SchemaExport(Vec<ExportRecord>),
+    SchemaStats(SchemaStats),
+    RunSummary(RunSummary),
+    DevSessionStatus(DevSessionStatus),
+    DevSessionProcesses(DevSessionProcesses),
     AsyncCheckResponse(CheckRequestSuccessResult),
     GraphPublishResponse {
         graph_ref: GraphRef,
@@ -60,13 +101,35 @@ pub enum RoverOutput {
         dry_run: bool,
         delete_response: SubgraphDeleteResponse,
     },
+    GraphKeyRotation {
+        graph_ref: GraphRef,
+        new_key: GraphApiKey,
+        revoked_key_id: String,
+    },
     TemplateList(Vec<GithubTemplate>),
     TemplateUseSuccess {
         template: GithubTemplate,
         path: Utf8PathBuf,
+        revision: String,
+    },
+    LocalTemplateUseSuccess {
+        template_path: Utf8PathBuf,
+        path: Utf8PathBuf,
+    },
+    SchemaFilesWritten {
+        out_dir: Utf8PathBuf,
+        files: Vec<SchemaFile>,
+    },
+    VariantSchemasWritten {
+        out_dir: Utf8PathBuf,
+        files: Vec<VariantSchemaFile>,
     },
     Profiles(Vec<String>),
+    ProfileOutputs(Vec<ProfileOutput>),
+    WhoAmI(WhoAmIResponse),
     Introspection(String),
+    IntrospectionDiff(Vec<DiffLine>),
+    AnonymizedOperations(String),
     ErrorExplanation(String),
     ReadmeFetchResponse {
         graph_ref: GraphRef,
@@ -82,6 +145,46 @@ pub enum RoverOutput {
 }
 
 impl RoverOutput {
+    /// Applies `transform` to the schema printed by outputs that contain SDL,
+    /// leaving every other output untouched
+    pub(crate) fn transform_sdl<F>(self, transform: F) -> Self
+    where
+        F: Fn(&str) -> String,
+    {
+        match self {
+            RoverOutput::FetchResponse(mut fetch_response) => {
+                fetch_response.sdl.contents = transform(&fetch_response.sdl.contents);
+                RoverOutput::FetchResponse(fetch_response)
+            }
+            RoverOutput::SupergraphSchema(csdl) => RoverOutput::SupergraphSchema(transform(&csdl)),
+            RoverOutput::CompositionResult(mut composition_output) => {
+                composition_output.supergraph_sdl = transform(&composition_output.supergraph_sdl);
+                RoverOutput::CompositionResult(composition_output)
+            }
+            RoverOutput::Introspection(sdl) => RoverOutput::Introspection(transform(&sdl)),
+            output => output,
+        }
+    }
+
+    /// The schema printed by outputs that contain SDL, converted to the result
+    /// of an introspection query for `--format introspection-json`
+    pub(crate) fn get_introspection_json(&self) -> RoverResult<String> {
+        let sdl = match self {
+            RoverOutput::FetchResponse(fetch_response) => &fetch_response.sdl.contents,
+            RoverOutput::SupergraphSchema(csdl) => csdl,
+            RoverOutput::CompositionResult(composition_output) => {
+                &composition_output.supergraph_sdl
+            }
+            RoverOutput::Introspection(sdl) => sdl,
+            _ => {
+                let message = "`--format introspection-json` can only be used with commands that print a schema";
+                return Err(RoverError::new(anyhow!(message)));
+            }
+        };
+        let introspection = sdl_to_introspection_json(sdl)?;
+        Ok(serde_json::to_string_pretty(&introspection)?)
+    }
+
     pub fn get_stdout(&self) -> io::Result<Option<String>> {
         Ok(match self {
             RoverOutput::ContractDescribe(describe_response) => Some(format!(
@@ -131,6 +234,12 @@ impl RoverOutput {
                     publish_response.api_schema_hash,
                     publish_response.change_summary
                 )?;
+                if let Some(launch_url) = &publish_response.launch_url {
+                    stderrln!(
+                        "You can monitor this launch in Apollo Studio: {}",
+                        launch_url
+                    )?;
+                }
                 Some((publish_response.api_schema_hash).to_string())
             }
             RoverOutput::SubgraphPublishResponse {
@@ -220,6 +329,7 @@ impl RoverOutput {
                 }
             }
             RoverOutput::SupergraphSchema(csdl) => Some((csdl).to_string()),
+            RoverOutput::SupergraphConfig { config, .. } => Some(config.to_string()),
             RoverOutput::CompositionResult(composition_output) => {
                 let warn_prefix = Style::HintPrefix.paint("HINT:");
 
@@ -231,8 +341,84 @@ impl RoverOutput {
 
                 stderrln!("{}", hints_string)?;
 
+                if !composition_output.missing_subgraphs.is_empty() {
+                    let missing_subgraphs = composition_output
+                        .missing_subgraphs
+                        .iter()
+                        .map(|subgraph| subgraph.name.as_str())
+                        .collect::<Vec<&str>>()
+                        .join(", ");
+                    stderrln!(
+                        "{} This supergraph is partial. It was composed without these subgraphs, whose schemas couldn't be resolved: {}",
+                        Style::WarningPrefix.paint("WARN:"),
+                        missing_subgraphs
+                    )?;
+                }
+
                 Some((composition_output.supergraph_sdl).to_string())
             }
+            RoverOutput::GraphList(list_response) => {
+                if list_response.graphs.is_empty() {
+                    stderrln!("No graphs found.")?;
+                    None
+                } else {
+                    let mut table = table::get_table();
+
+                    // bc => sets top row to be bold and center
+                    table.add_row(row![bc => "Organization", "Graph ID", "Title", "Variants"]);
+
+                    for graph in &list_response.graphs {
+                        table.add_row(row![
+                            graph.organization.as_deref().unwrap_or("N/A"),
+                            graph.id,
+                            graph.title,
+                            graph.variants.join("\n")
+                        ]);
+                    }
+                    Some(table.to_string())
+                }
+            }
+            RoverOutput::VariantList(list_response) => {
+                let mut table = table::get_table();
+
+                // bc => sets top row to be bold and center
+                table.add_row(
+                    row![bc => "Name", "Subgraphs", "Last Published", "Source Variant", "URL"],
+                );
+
+                for variant in &list_response.variants {
+                    let subgraphs = variant
+                        .subgraph_count
+                        .map(|count| count.to_string())
+                        .unwrap_or_else(|| "N/A".to_string());
+                    let last_published_at = variant
+                        .last_published_at
+                        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S %Z").to_string())
+                        .unwrap_or_else(|| "N/A".to_string());
+                    table.add_row(row![
+                        variant.name,
+                        subgraphs,
+                        last_published_at,
+                        variant.source_variant.as_deref().unwrap_or("N/A"),
+                        variant.url.as_deref().unwrap_or("N/A")
+                    ]);
+                }
+                Some(table.to_string())
+            }
+            RoverOutput::VariantCreateResponse(create_response) => {
+                let copied = if create_response.subgraphs.is_empty() {
+                    "the schema".to_string()
+                } else {
+                    format!("{} subgraph(s)", create_response.subgraphs.len())
+                };
+                stderrln!(
+                    "Created {} by publishing {} from the {} variant.",
+                    Style::Link.paint(create_response.graph_ref.to_string()),
+                    copied,
+                    create_response.source_variant
+                )?;
+                None
+            }
             RoverOutput::SubgraphList(details) => {
                 let mut table = table::get_table();
 
@@ -280,7 +466,23 @@ impl RoverOutput {
 
                 Some(format!("{}", table))
             }
-            RoverOutput::TemplateUseSuccess { template, path } => {
+            RoverOutput::SchemaFilesWritten { out_dir, files } => {
+                stderrln!(
+                    "Wrote the schema to {} files in {}.",
+                    files.len(),
+                    Style::Path.paint(out_dir.as_str())
+                )?;
+                None
+            }
+            RoverOutput::VariantSchemasWritten { out_dir, files } => {
+                stderrln!(
+                    "Wrote the schemas of {} variants to {}.",
+                    files.len(),
+                    Style::Path.paint(out_dir.as_str())
+                )?;
+                None
+            }
+            RoverOutput::TemplateUseSuccess { template, path, .. } => {
                 let template_id = Style::Command.paint(template.id);
                 let path = Style::Path.paint(path.as_str());
                 let readme = Style::Path.paint("README.md");
@@ -294,20 +496,104 @@ impl RoverOutput {
                 readme,
                 forum_call_to_action))
             }
+            RoverOutput::LocalTemplateUseSuccess {
+                template_path,
+                path,
+            } => {
+                let template_path = Style::Path.paint(template_path.as_str());
+                let path = Style::Path.paint(path.as_str());
+                Some(format!(
+                    "Successfully created a new project from the template at {} in {}",
+                    template_path, path
+                ))
+            }
             RoverOutput::CheckResponse(check_response) => Some(check_response.get_table()),
+            RoverOutput::SchemaDiff(diff) => Some(diff.get_table()),
+            RoverOutput::SupergraphDiff(diff) => Some(diff.get_table()),
+            RoverOutput::InstalledPlugins(plugins) => {
+                if plugins.is_empty() {
+                    stderrln!("There are no versions of this plugin installed.")?;
+                    None
+                } else {
+                    let mut table = table::get_table();
+
+                    // bc => sets top row to be bold and center
+                    table.add_row(row![bc => "Name", "Version", "Path"]);
+                    for plugin in plugins {
+                        table.add_row(row![plugin.name, plugin.version, plugin.path]);
+                    }
+                    Some(table.to_string())
+                }
+            }
+            RoverOutput::PrunedPlugins { removed, dry_run } => {
+                let verb = if *dry_run { "Would remove" } else { "Removed" };
+                if removed.is_empty() {
+                    stderrln!("There are no versions of this plugin to remove.")?;
+                } else {
+                    for plugin in removed {
+                        stderrln!("{} {}", verb, Style::Path.paint(plugin.path.as_str()))?;
+                    }
+                }
+                None
+            }
+            RoverOutput::LintResponse(lint_response) => Some(lint_response.get_table()),
+            RoverOutput::RunSummary(summary) => Some(summary.get_table()),
+            RoverOutput::DevSessionStatus(status) => Some(status.get_table()),
+            RoverOutput::DevSessionProcesses(processes) => Some(processes.get_table()),
+            RoverOutput::SchemaExport(records) => {
+                // one record per line, so the export can be streamed into other tools
+                let mut lines = Vec::with_capacity(records.len());
+                for record in records {
+                    lines.push(serde_json::to_string(record)?);
+                }
+                Some(lines.join("\n"))
+            }
+            RoverOutput::SchemaStats(stats) => Some(stats.get_table()),
             RoverOutput::AsyncCheckResponse(check_response) => Some(format!(
-                "Check successfully started with workflow ID: {}/nView full details at {}",
+                "Check successfully started with workflow ID: {}\nView full details at {}",
                 check_response.workflow_id, check_response.target_url
             )),
+            RoverOutput::GraphKeyRotation {
+                graph_ref,
+                new_key,
+                revoked_key_id,
+            } => {
+                stderrln!(
+                    "Created a new API key for {} and revoked the key with ID {}. The new key will not be shown again, so save it now.",
+                    graph_ref.name,
+                    revoked_key_id
+                )?;
+                Some(new_key.token.to_string())
+            }
             RoverOutput::Profiles(profiles) => {
                 if profiles.is_empty() {
                     stderrln!("No profiles found.")?;
                 }
                 Some(profiles.join("\n"))
             }
+            RoverOutput::ProfileOutputs(outputs) => {
+                let mut stdout = Vec::with_capacity(outputs.len());
+                for output in outputs {
+                    let heading = Style::Heading.paint(format!("Profile {}:", output.profile_name));
+                    let result = match &output.result {
+                        Ok(output) => output.get_stdout()?.unwrap_or_default(),
+                        Err(error) => {
+                            format!("{} {}", Style::ErrorPrefix.paint("error:"), error.message)
+                        }
+                    };
+                    stdout.push(format!("{}\n{}", heading, result));
+                }
+                Some(stdout.join("\n\n"))
+            }
+            RoverOutput::WhoAmI(identity) => Some(identity.get_message()),
             RoverOutput::Introspection(introspection_response) => {
                 Some((introspection_response).to_string())
             }
+            RoverOutput::IntrospectionDiff(lines) => {
+                stderrln!("{}the schema changed", Emoji::Reload)?;
+                Some(line_diff::get_unified_diff(lines, 3))
+            }
+            RoverOutput::AnonymizedOperations(operations) => Some(operations.to_string()),
             RoverOutput::ErrorExplanation(explanation) => {
                 // underline bolded md
                 let mut skin = MadSkin::default();
@@ -332,6 +618,33 @@ impl RoverOutput {
         })
     }
 
+    /// A short summary to print to stdout after this output is written to an `--output` file,
+    /// for outputs that are too long to be worth printing again
+    pub(crate) fn get_file_summary(&self, path: &Utf8Path, changed: bool) -> Option<String> {
+        match self {
+            RoverOutput::CompositionResult(composition_output) => {
+                let mut details = Vec::new();
+                if let Some(federation_version) = &composition_output.federation_version {
+                    details.push(format!("Federation {}", federation_version));
+                }
+                details.push(match composition_output.hints.len() {
+                    1 => "1 hint".to_string(),
+                    hints => format!("{} hints", hints),
+                });
+                if !composition_output.missing_subgraphs.is_empty() {
+                    details.push("partial".to_string());
+                }
+                let summary = if changed {
+                    format!("Wrote the supergraph schema to {}", path)
+                } else {
+                    format!("The supergraph schema in {} is up to date", path)
+                };
+                Some(format!("{} ({})", summary, details.join(", ")))
+            }
+            _ => None,
+        }
+    }
+
     pub(crate) fn get_internal_data_json(&self) -> Value {
         match self {
             RoverOutput::ContractDescribe(describe_response) => json!(describe_response),
@@ -347,19 +660,47 @@ impl RoverOutput {
             }
             RoverOutput::FetchResponse(fetch_response) => json!(fetch_response),
             RoverOutput::SupergraphSchema(csdl) => json!({ "core_schema": csdl }),
+            RoverOutput::SupergraphConfig {
+                config,
+                schema_files,
+            } => json!({ "config": config, "schema_files": schema_files }),
             RoverOutput::CompositionResult(composition_output) => {
-                if let Some(federation_version) = &composition_output.federation_version {
-                    json!({
-                      "core_schema": composition_output.supergraph_sdl,
-                      "hints": composition_output.hints,
-                      "federation_version": federation_version
-                    })
-                } else {
-                    json!({
-                        "core_schema": composition_output.supergraph_sdl,
-                        "hints": composition_output.hints
+                // every hint has a `code`, which is null for hints from older composition plugins,
+                // and the `subgraphs` it's about, which may be empty
+                let hints: Vec<Value> = composition_output
+                    .hints
+                    .iter()
+                    .map(|hint| {
+                        let mut hint_json = json!(hint);
+                        hint_json["code"] = json!(get_hint_code(hint));
+                        hint_json["subgraphs"] = json!(get_hint_subgraphs(hint));
+                        hint_json
                     })
+                    .collect();
+                let mut json =
+                    if let Some(federation_version) = &composition_output.federation_version {
+                        json!({
+                          "core_schema": composition_output.supergraph_sdl,
+                          "hints": hints,
+                          "federation_version": federation_version
+                        })
+                    } else {
+                        json!({
+                            "core_schema": composition_output.supergraph_sdl,
+                            "hints": hints
+                        })
+                    };
+                if !composition_output.missing_subgraphs.is_empty() {
+                    json["partial"] = json!(true);
+                    json["missing_subgraphs"] = json!(composition_output.missing_subgraphs);
+                }
+                if !composition_output.subgraph_metrics.is_empty() {
+                    json["subgraphs"] = json!(composition_output.subgraph_metrics);
+                }
+                if let Some(cache) = &composition_output.cache {
+                    json["cache"] = json!(cache);
                 }
+                json
             }
             RoverOutput::GraphPublishResponse {
                 graph_ref: _,
@@ -378,17 +719,84 @@ impl RoverOutput {
             } => {
                 json!(delete_response)
             }
+            RoverOutput::GraphList(list_response) => json!(list_response),
             RoverOutput::SubgraphList(list_response) => json!(list_response),
+            RoverOutput::VariantList(list_response) => json!(list_response),
+            RoverOutput::VariantCreateResponse(create_response) => json!({
+                "graph_ref": create_response.graph_ref.to_string(),
+                "source_variant": create_response.source_variant,
+                "subgraphs": create_response.subgraphs,
+            }),
             RoverOutput::TemplateList(templates) => json!({ "templates": templates }),
-            RoverOutput::TemplateUseSuccess { template, path } => {
-                json!({ "template_id": template.id, "path": path })
+            RoverOutput::TemplateUseSuccess {
+                template,
+                path,
+                revision,
+            } => {
+                json!({ "template_id": template.id, "path": path, "revision": revision })
+            }
+            RoverOutput::LocalTemplateUseSuccess {
+                template_path,
+                path,
+            } => json!({ "template_path": template_path, "path": path }),
+            RoverOutput::SchemaFilesWritten { out_dir, files } => {
+                json!({ "out_dir": out_dir, "files": files })
+            }
+            RoverOutput::VariantSchemasWritten { out_dir, files } => {
+                json!({ "out_dir": out_dir, "files": files })
             }
             RoverOutput::CheckResponse(check_response) => check_response.get_json(),
+            RoverOutput::SchemaDiff(diff) => diff.get_json(),
+            RoverOutput::SupergraphDiff(diff) => diff.get_json(),
+            RoverOutput::InstalledPlugins(plugins) => json!({ "plugins": plugins }),
+            RoverOutput::PrunedPlugins { removed, dry_run } => {
+                json!({ "removed": removed, "dry_run": dry_run })
+            }
+            RoverOutput::LintResponse(lint_response) => lint_response.get_json(),
+            RoverOutput::SchemaExport(records) => json!({ "records": records }),
+            RoverOutput::SchemaStats(stats) => stats.get_json(),
+            RoverOutput::RunSummary(summary) => summary.get_json(),
+            RoverOutput::DevSessionStatus(status) => status.get_json(),
+            RoverOutput::DevSessionProcesses(processes) => processes.get_json(),
             RoverOutput::AsyncCheckResponse(check_response) => check_response.get_json(),
+            RoverOutput::GraphKeyRotation {
+                graph_ref: _,
+                new_key,
+                revoked_key_id,
+            } => json!({ "new_key": new_key, "revoked_key_id": revoked_key_id }),
             RoverOutput::Profiles(profiles) => json!({ "profiles": profiles }),
+            RoverOutput::ProfileOutputs(outputs) => {
+                let profiles: Vec<Value> = outputs
+                    .iter()
+                    .map(|output| match &output.result {
+                        Ok(result) => json!({
+                            "profile": output.profile_name,
+                            "success": true,
+                            "data": result.get_internal_data_json(),
+                            "error": null,
+                        }),
+                        Err(error) => json!({
+                            "profile": output.profile_name,
+                            "success": false,
+                            "data": null,
+                            "error": error.json,
+                        }),
+                    })
+                    .collect();
+                json!({ "profiles": profiles })
+            }
+            RoverOutput::WhoAmI(identity) => json!(identity),
             RoverOutput::Introspection(introspection_response) => {
                 json!({ "introspection_response": introspection_response })
             }
+            RoverOutput::IntrospectionDiff(lines) => {
+                let changes: Vec<&DiffLine> = lines
+                    .iter()
+                    .filter(|line| line.kind != DiffLineKind::Unchanged)
+                    .collect();
+                json!({ "changes": changes })
+            }
+            RoverOutput::AnonymizedOperations(operations) => json!({ "operations": operations }),
             RoverOutput::ErrorExplanation(explanation_markdown) => {
                 json!({ "explanation_markdown": explanation_markdown })
             }
@@ -479,11 +887,28 @@ impl RoverOutput {
             RoverOutput::CompositionResult(_) | RoverOutput::SupergraphSchema(_) => {
                 Some("Supergraph Schema")
             }
-            RoverOutput::TemplateUseSuccess { .. } => Some("Project generated"),
+            RoverOutput::SupergraphConfig { .. } => Some("Supergraph Config"),
+            RoverOutput::TemplateUseSuccess { .. }
+            | RoverOutput::LocalTemplateUseSuccess { .. } => Some("Project generated"),
             RoverOutput::CheckResponse(_) => Some("Check Result"),
+            RoverOutput::GraphList(_) => Some("Graphs"),
+            RoverOutput::VariantList(_) => Some("Variants"),
+            RoverOutput::SchemaDiff(_) => Some("Schema Diff"),
+            RoverOutput::SupergraphDiff(_) => Some("Supergraph Diff"),
+            RoverOutput::InstalledPlugins(_) => Some("Installed Plugins"),
+            RoverOutput::LintResponse(_) => Some("Lint Result"),
+            RoverOutput::SchemaExport(_) => Some("Schema Export"),
+            RoverOutput::SchemaStats(_) => Some("Schema Stats"),
+            RoverOutput::RunSummary(summary) if summary.dry_run => Some("Run Plan"),
+            RoverOutput::RunSummary(_) => Some("Run Summary"),
+            RoverOutput::DevSessionStatus(_) => Some("Dev Session"),
+            RoverOutput::DevSessionProcesses(_) => Some("Dev Sessions"),
             RoverOutput::AsyncCheckResponse(_) => Some("Check Started"),
+            RoverOutput::GraphKeyRotation { .. } => Some("New API Key"),
             RoverOutput::Profiles(_) => Some("Profiles"),
             RoverOutput::Introspection(_) => Some("Introspection Response"),
+            RoverOutput::IntrospectionDiff(_) => Some("Schema Changes"),
+            RoverOutput::AnonymizedOperations(_) => Some("Anonymized Operations"),
             RoverOutput::ReadmeFetchResponse { .. } => Some("Readme"),
             RoverOutput::GraphPublishResponse { .. } => Some("Schema Hash"),
             _ => None,
@@ -499,11 +924,15 @@ mod tests {
     use chrono::{DateTime, Local, Utc};
     use rover_client::{
         operations::{
-            graph::publish::{ChangeSummary, FieldChanges, TypeChanges},
+            graph::{
+                list::GraphInfo,
+                publish::{ChangeSummary, FieldChanges, TypeChanges},
+            },
             subgraph::{
                 delete::SubgraphDeleteResponse,
                 list::{SubgraphInfo, SubgraphUpdatedAt},
             },
+            variant::list::VariantInfo,
         },
         shared::{ChangeSeverity, SchemaChange, Sdl, SdlType},
     };
@@ -512,7 +941,10 @@ mod tests {
 
     use anyhow::anyhow;
 
+    use crate::command::run::{FailurePolicy, Step, StepResult, StepStatus};
+    use crate::command::supergraph::compose::{MissingSubgraph, SubgraphMetrics};
     use crate::options::JsonOutput;
+    use crate::utils::composition_cache::CompositionCacheStatus;
 
     use super::*;
 
@@ -584,6 +1016,174 @@ mod tests {
         assert_json_eq!(expected_json, actual_json);
     }
 
+    #[test]
+    fn supergraph_config_json() {
+        let actual_json: JsonOutput = RoverOutput::SupergraphConfig {
+            config: "subgraphs: {}\n".to_string(),
+            schema_files: vec![Utf8PathBuf::from("./subgraphs/products.graphql")],
+        }
+        .into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "config": "subgraphs: {}\n",
+                "schema_files": ["./subgraphs/products.graphql"],
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn pruned_plugins_json() {
+        let actual_json: JsonOutput = RoverOutput::PrunedPlugins {
+            removed: vec![InstalledPlugin {
+                name: "supergraph".to_string(),
+                version: semver::Version::new(2, 3, 1),
+                path: Utf8PathBuf::from("/home/.rover/bin/supergraph-v2.3.1"),
+            }],
+            dry_run: true,
+        }
+        .into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "removed": [{
+                    "name": "supergraph",
+                    "version": "2.3.1",
+                    "path": "/home/.rover/bin/supergraph-v2.3.1"
+                }],
+                "dry_run": true,
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn graph_list_json() {
+        let mock_graph_list_response = GraphListResponse {
+            graphs: vec![
+                GraphInfo {
+                    id: "products".to_string(),
+                    title: "Products".to_string(),
+                    organization: Some("my-org".to_string()),
+                    variants: vec!["current".to_string(), "staging".to_string()],
+                },
+                GraphInfo {
+                    id: "reviews".to_string(),
+                    title: "Reviews".to_string(),
+                    organization: None,
+                    variants: vec![],
+                },
+            ],
+        };
+        let actual_json: JsonOutput = RoverOutput::GraphList(mock_graph_list_response).into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "graphs": [
+                    {
+                        "id": "products",
+                        "title": "Products",
+                        "organization": "my-org",
+                        "variants": ["current", "staging"]
+                    },
+                    {
+                        "id": "reviews",
+                        "title": "Reviews",
+                        "organization": null,
+                        "variants": []
+                    }
+                ],
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn variant_list_json() {
+        let published_at: DateTime<Utc> = "2022-11-01T10:00:00Z".parse().unwrap();
+        let mock_variant_list_response = VariantListResponse {
+            graph_id: "products".to_string(),
+            variants: vec![
+                VariantInfo {
+                    name: "current".to_string(),
+                    url: Some("https://api.example.com/graphql".to_string()),
+                    source_variant: None,
+                    last_published_at: Some(published_at),
+                    subgraph_count: Some(2),
+                },
+                VariantInfo {
+                    name: "public".to_string(),
+                    url: None,
+                    source_variant: Some("current".to_string()),
+                    last_published_at: None,
+                    subgraph_count: None,
+                },
+            ],
+        };
+        let actual_json: JsonOutput = RoverOutput::VariantList(mock_variant_list_response).into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "graph_id": "products",
+                "variants": [
+                    {
+                        "name": "current",
+                        "url": "https://api.example.com/graphql",
+                        "source_variant": null,
+                        "last_published_at": published_at,
+                        "subgraph_count": 2
+                    },
+                    {
+                        "name": "public",
+                        "url": null,
+                        "source_variant": "current",
+                        "last_published_at": null,
+                        "subgraph_count": null
+                    }
+                ],
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn variant_create_json() {
+        let actual_json: JsonOutput = RoverOutput::VariantCreateResponse(VariantCreateResponse {
+            graph_ref: GraphRef {
+                name: "products".to_string(),
+                variant: "pr-123".to_string(),
+            },
+            source_variant: "current".to_string(),
+            subgraphs: vec!["inventory".to_string(), "reviews".to_string()],
+        })
+        .into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "graph_ref": "products@pr-123",
+                "source_variant": "current",
+                "subgraphs": ["inventory", "reviews"],
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
     #[test]
     fn subgraph_list_json() {
         let now_utc: DateTime<Utc> = Utc::now();
@@ -829,6 +1429,389 @@ mod tests {
         }
     }
 
+    #[test]
+    fn schema_diff_json() {
+        let diff = SchemaDiff::new(
+            "mygraph@current",
+            "type Query { a: String b: Int }",
+            "mygraph@dev",
+            "type Query { a: String c: Int }",
+        )
+        .unwrap();
+        let actual_json: JsonOutput = RoverOutput::SchemaDiff(diff).into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "base": "mygraph@current",
+                "target": "mygraph@dev",
+                "changes": [
+                    {
+                        "code": "FIELD_REMOVED",
+                        "description": "field `Query.b`: removed",
+                        "severity": "BREAKING"
+                    },
+                    {
+                        "code": "FIELD_ADDED",
+                        "description": "field `Query.c`: added",
+                        "severity": "SAFE"
+                    }
+                ],
+                "breaking_count": 1,
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn run_summary_json() {
+        let summary = RunSummary {
+            dry_run: false,
+            steps: vec![StepResult {
+                step: Step {
+                    name: "lint".to_string(),
+                    args: vec!["graph".to_string(), "lint".to_string()],
+                    on_failure: FailurePolicy::Stop,
+                },
+                status: StepStatus::Succeeded,
+                exit_code: Some(0),
+                duration_ms: Some(1200),
+                stdout: Some(r#"{"data":{"success":true}}"#.to_string()),
+            }],
+        };
+        let actual_json: JsonOutput = RoverOutput::RunSummary(summary).into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "dry_run": false,
+                "steps": [
+                    {
+                        "name": "lint",
+                        "args": ["graph", "lint"],
+                        "on_failure": "stop",
+                        "status": "SUCCEEDED",
+                        "exit_code": 0,
+                        "duration_ms": 1200,
+                        "output": { "data": { "success": true } }
+                    }
+                ],
+                "failed_count": 0,
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn schema_files_written_json() {
+        let actual_json: JsonOutput = RoverOutput::SchemaFilesWritten {
+            out_dir: Utf8PathBuf::from("schema"),
+            files: vec![SchemaFile {
+                path: "Query.graphql".to_string(),
+                type_name: Some("Query".to_string()),
+                contents: "type Query { a: String }".to_string(),
+            }],
+        }
+        .into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "out_dir": "schema",
+                "files": [
+                    {
+                        "path": "Query.graphql",
+                        "type_name": "Query"
+                    }
+                ],
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn variant_schemas_written_json() {
+        let actual_json: JsonOutput = RoverOutput::VariantSchemasWritten {
+            out_dir: Utf8PathBuf::from("archive"),
+            files: vec![VariantSchemaFile {
+                variant: "current".to_string(),
+                path: "current.graphql".to_string(),
+            }],
+        }
+        .into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "out_dir": "archive",
+                "files": [
+                    {
+                        "variant": "current",
+                        "path": "current.graphql"
+                    }
+                ],
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn partial_composition_result_json() {
+        let actual_json: JsonOutput = RoverOutput::CompositionResult(CompositionOutput {
+            supergraph_sdl: "type Query { a: String }".to_string(),
+            hints: Vec::new(),
+            federation_version: Some("2.3.1".to_string()),
+            missing_subgraphs: vec![MissingSubgraph {
+                name: "reviews".to_string(),
+                reason: "Could not connect to http://localhost:4002".to_string(),
+            }],
+            subgraph_metrics: Vec::new(),
+            cache: None,
+        })
+        .into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "core_schema": "type Query { a: String }",
+                "hints": [],
+                "federation_version": "2.3.1",
+                "partial": true,
+                "missing_subgraphs": [
+                    {
+                        "name": "reviews",
+                        "reason": "Could not connect to http://localhost:4002"
+                    }
+                ],
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn composition_result_hint_codes_json() {
+        let hints = vec![
+            serde_json::from_value(json!({
+                "message": "a is described differently",
+                "code": "INCONSISTENT_DESCRIPTION"
+            }))
+            .unwrap(),
+            serde_json::from_value(json!({ "message": "an old hint" })).unwrap(),
+        ];
+        let actual_json: JsonOutput = RoverOutput::CompositionResult(CompositionOutput {
+            supergraph_sdl: "type Query { a: String }".to_string(),
+            hints,
+            federation_version: Some("2.3.1".to_string()),
+            missing_subgraphs: Vec::new(),
+            subgraph_metrics: Vec::new(),
+            cache: None,
+        })
+        .into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "core_schema": "type Query { a: String }",
+                "hints": [
+                    {
+                        "message": "a is described differently",
+                        "code": "INCONSISTENT_DESCRIPTION",
+                        "subgraphs": []
+                    },
+                    {
+                        "message": "an old hint",
+                        "code": null,
+                        "subgraphs": []
+                    }
+                ],
+                "federation_version": "2.3.1",
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn composition_result_subgraph_metrics_json() {
+        let actual_json: JsonOutput = RoverOutput::CompositionResult(CompositionOutput {
+            supergraph_sdl: "type Query { a: String }".to_string(),
+            hints: Vec::new(),
+            federation_version: Some("2.3.1".to_string()),
+            missing_subgraphs: Vec::new(),
+            subgraph_metrics: vec![SubgraphMetrics {
+                name: "products".to_string(),
+                source: "subgraph_url".to_string(),
+                resolution_time_ms: 1250,
+                sdl_bytes: Some(24),
+            }],
+            cache: None,
+        })
+        .into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "core_schema": "type Query { a: String }",
+                "hints": [],
+                "federation_version": "2.3.1",
+                "subgraphs": [
+                    {
+                        "name": "products",
+                        "source": "subgraph_url",
+                        "resolution_time_ms": 1250,
+                        "sdl_bytes": 24
+                    }
+                ],
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn composition_result_cache_json() {
+        let actual_json: JsonOutput = RoverOutput::CompositionResult(CompositionOutput {
+            supergraph_sdl: "type Query { a: String }".to_string(),
+            hints: Vec::new(),
+            federation_version: Some("2.3.1".to_string()),
+            missing_subgraphs: Vec::new(),
+            subgraph_metrics: Vec::new(),
+            cache: Some(CompositionCacheStatus {
+                hit: false,
+                changed_subgraphs: vec!["products".to_string()],
+            }),
+        })
+        .into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "core_schema": "type Query { a: String }",
+                "hints": [],
+                "federation_version": "2.3.1",
+                "cache": {
+                    "hit": false,
+                    "changed_subgraphs": ["products"]
+                },
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn composition_result_file_summary() {
+        let output = RoverOutput::CompositionResult(CompositionOutput {
+            supergraph_sdl: "type Query { a: String }".to_string(),
+            hints: Vec::new(),
+            federation_version: Some("2.3.1".to_string()),
+            missing_subgraphs: vec![MissingSubgraph {
+                name: "reviews".to_string(),
+                reason: "Could not connect to http://localhost:4002".to_string(),
+            }],
+            subgraph_metrics: Vec::new(),
+            cache: None,
+        });
+        let path = Utf8Path::new("supergraph.graphql");
+        assert_eq!(
+            output.get_file_summary(path, true),
+            Some(
+                "Wrote the supergraph schema to supergraph.graphql (Federation 2.3.1, 0 hints, partial)"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            output.get_file_summary(path, false),
+            Some(
+                "The supergraph schema in supergraph.graphql is up to date (Federation 2.3.1, 0 hints, partial)"
+                    .to_string()
+            )
+        );
+        assert_eq!(RoverOutput::EmptySuccess.get_file_summary(path, true), None);
+    }
+
+    #[test]
+    fn schema_export_json() {
+        let records = vec![ExportRecord {
+            id: "Product.name".to_string(),
+            kind: "FIELD",
+            parent_type: Some("Product".to_string()),
+            ty: Some("String".to_string()),
+            description: None,
+            deprecated: false,
+            deprecation_reason: None,
+            tags: vec!["public".to_string()],
+            subgraphs: vec!["products".to_string()],
+            text: "Product.name: String".to_string(),
+        }];
+        let actual_json: JsonOutput = RoverOutput::SchemaExport(records).into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "records": [
+                    {
+                        "id": "Product.name",
+                        "kind": "FIELD",
+                        "parent_type": "Product",
+                        "type": "String",
+                        "description": null,
+                        "deprecated": false,
+                        "deprecation_reason": null,
+                        "tags": ["public"],
+                        "subgraphs": ["products"],
+                        "text": "Product.name: String"
+                    }
+                ],
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn schema_stats_json() {
+        let stats = SchemaStats::new(
+            "my-graph@current",
+            "type Query { a: String @deprecated }\nenum Color { RED }",
+        )
+        .unwrap();
+        let actual_json: JsonOutput = RoverOutput::SchemaStats(stats).into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "schema": "my-graph@current",
+                "types": 2,
+                "types_by_kind": { "OBJECT": 1, "ENUM": 1 },
+                "fields": 1,
+                "input_fields": 0,
+                "enum_values": 1,
+                "arguments": 0,
+                "deprecations": 1,
+                "directive_definitions": 0,
+                "directive_usages": { "deprecated": 1 },
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
     #[test]
     fn check_failure_response_json() {
         let graph_ref = GraphRef {
@@ -905,6 +1888,12 @@ mod tests {
                     edits: 7,
                 },
             },
+            launch_id: Some("a1b2c3".to_string()),
+            launch_status: Some("LAUNCH_INITIATED".to_string()),
+            launch_url: Some(
+                "https://studio.apollographql.com/graph/graph/launches/a1b2c3?variant=variant"
+                    .to_string(),
+            ),
         };
         let actual_json: JsonOutput = RoverOutput::GraphPublishResponse {
             graph_ref: GraphRef {
@@ -929,6 +1918,9 @@ mod tests {
                     "removals": 0,
                     "edits": 7
                 },
+                "launch_id": "a1b2c3",
+                "launch_status": "LAUNCH_INITIATED",
+                "launch_url": "https://studio.apollographql.com/graph/graph/launches/a1b2c3?variant=variant",
                 "success": true
             },
             "error": null
@@ -947,6 +1939,8 @@ mod tests {
             launch_cli_copy: Some(
                 "You can monitor this launch in Apollo Studio: test.com/launchurl".to_string(),
             ),
+            launch_id: Some("a1b2c3".to_string()),
+            launch_status: Some("LAUNCH_COMPLETED".to_string()),
         };
         let actual_json: JsonOutput = RoverOutput::SubgraphPublishResponse {
             graph_ref: GraphRef {
@@ -967,6 +1961,8 @@ mod tests {
                 "success": true,
                 "launch_url": "test.com/launchurl",
                 "launch_cli_copy": "You can monitor this launch in Apollo Studio: test.com/launchurl",
+                "launch_id": "a1b2c3",
+                "launch_status": "LAUNCH_COMPLETED",
             },
             "error": null
         });
@@ -993,6 +1989,8 @@ mod tests {
             subgraph_was_created: false,
             launch_url: None,
             launch_cli_copy: None,
+            launch_id: None,
+            launch_status: None,
         };
         let actual_json: JsonOutput = RoverOutput::SubgraphPublishResponse {
             graph_ref: GraphRef {
@@ -1013,6 +2011,8 @@ mod tests {
                 "success": true,
                 "launch_url": null,
                 "launch_cli_copy": null,
+                "launch_id": null,
+                "launch_status": null,
             },
             "error": {
                 "message": "Encountered 2 build errors while trying to build subgraph \"subgraph\" into supergraph \"name@current\".",
@@ -1055,6 +2055,93 @@ mod tests {
         assert_json_eq!(expected_json, actual_json);
     }
 
+    #[test]
+    fn graph_key_rotation_json() {
+        let actual_json: JsonOutput = RoverOutput::GraphKeyRotation {
+            graph_ref: GraphRef {
+                name: "mygraph".to_string(),
+                variant: "current".to_string(),
+            },
+            new_key: GraphApiKey {
+                id: "key-2".to_string(),
+                name: Some("ci".to_string()),
+                role: "CONTRIBUTOR".to_string(),
+                token: "service:mygraph:def456".to_string(),
+            },
+            revoked_key_id: "key-1".to_string(),
+        }
+        .into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "new_key": {
+                    "id": "key-2",
+                    "name": "ci",
+                    "role": "CONTRIBUTOR",
+                    "token": "service:mygraph:def456"
+                },
+                "revoked_key_id": "key-1",
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn profile_outputs_json() {
+        let mock_outputs = vec![
+            ProfileOutput {
+                profile_name: "default".to_string(),
+                result: Ok(Box::new(RoverOutput::WhoAmI(WhoAmIResponse {
+                    key_type: "GRAPH".to_string(),
+                    graph_title: Some("Products".to_string()),
+                    id: "products".to_string(),
+                    origin: "--profile default".to_string(),
+                    api_key: "serv****1234".to_string(),
+                }))),
+            },
+            ProfileOutput {
+                profile_name: "staging".to_string(),
+                result: Err(RoverError::new(anyhow!("The API key is invalid.")).into()),
+            },
+        ];
+        let actual_json: JsonOutput = RoverOutput::ProfileOutputs(mock_outputs).into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "profiles": [
+                    {
+                        "profile": "default",
+                        "success": true,
+                        "data": {
+                            "key_type": "GRAPH",
+                            "graph_title": "Products",
+                            "id": "products",
+                            "origin": "--profile default",
+                            "api_key": "serv****1234"
+                        },
+                        "error": null
+                    },
+                    {
+                        "profile": "staging",
+                        "success": false,
+                        "data": null,
+                        "error": {
+                            "message": "The API key is invalid.",
+                            "code": null
+                        }
+                    }
+                ],
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
     #[test]
     fn introspection_json() {
         let actual_json: JsonOutput = RoverOutput::Introspection(
@@ -1073,6 +2160,54 @@ mod tests {
         assert_json_eq!(expected_json, actual_json);
     }
 
+    #[test]
+    fn introspection_diff_json() {
+        let lines = line_diff::diff_lines(
+            "type Query {\n  a: String\n}",
+            "type Query {\n  b: String\n}",
+        );
+        let actual_json: JsonOutput = RoverOutput::IntrospectionDiff(lines).into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "changes": [
+                    {
+                        "kind": "REMOVED",
+                        "old_line": 2,
+                        "new_line": null,
+                        "text": "  a: String"
+                    },
+                    {
+                        "kind": "ADDED",
+                        "old_line": null,
+                        "new_line": 2,
+                        "text": "  b: String"
+                    }
+                ],
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
+    #[test]
+    fn anonymized_operations_json() {
+        let actual_json: JsonOutput =
+            RoverOutput::AnonymizedOperations("query Operation1 { field1 }".to_string()).into();
+        let expected_json = json!(
+        {
+            "json_version": "1",
+            "data": {
+                "operations": "query Operation1 { field1 }",
+                "success": true
+            },
+            "error": null
+        });
+        assert_json_eq!(expected_json, actual_json);
+    }
+
     #[test]
     fn error_explanation_json() {
         let actual_json: JsonOutput = RoverOutput::ErrorExplanation(
@@ -0,0 +1,197 @@
+mod plan;
+
+pub use plan::{FailurePolicy, RunFailure, RunPlan, RunSummary, Step, StepResult, StepStatus};
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::io;
+use std::process::{Command, Stdio};
+use std::time::Instant;
+
+use anyhow::{anyhow, Context};
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::Parser;
+use rover_std::{Emoji, Fs, Style};
+use serde::Serialize;
+
+use crate::cli::RoverOutputFormatKind;
+use crate::options::OutputOpts;
+use crate::{RoverError, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Run {
+    /// The YAML or TOML file describing the Rover commands to run
+    #[arg(value_name = "SCRIPT")]
+    #[serde(skip_serializing)]
+    script: Utf8PathBuf,
+
+    /// Set a variable used by the script, overriding any value in its `vars`,
+    /// i.e. `--var graph=my-graph@staging`. Can be passed more than once
+    #[arg(long = "var", value_name = "NAME=VALUE", value_parser = parse_var)]
+    #[serde(skip_serializing)]
+    vars: Vec<(String, String)>,
+
+    /// Print the commands the script would run without running them
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Run {
+    pub fn run(&self, output_opts: &OutputOpts) -> RoverResult<RoverOutput> {
+        let contents = Fs::read_file(&self.script)?;
+        let overrides: BTreeMap<String, String> = self.vars.iter().cloned().collect();
+        let plan = RunPlan::new(&self.script, &contents, &overrides)?;
+
+        if self.dry_run {
+            return Ok(RoverOutput::RunSummary(RunSummary::planned(plan)));
+        }
+
+        let (format_kind, _) = output_opts.get_format_and_strategy();
+        let capture_stdout = matches!(format_kind, RoverOutputFormatKind::Json);
+
+        let mut results = Vec::with_capacity(plan.steps.len());
+        let mut stopped = false;
+        for step in plan.steps {
+            if stopped {
+                results.push(StepResult {
+                    step,
+                    status: StepStatus::Skipped,
+                    exit_code: None,
+                    duration_ms: None,
+                    stdout: None,
+                });
+                continue;
+            }
+            let result = run_step(step, capture_stdout)?;
+            stopped = result.status == StepStatus::Failed
+                && result.step.on_failure == FailurePolicy::Stop;
+            results.push(result);
+        }
+
+        let summary = RunSummary {
+            dry_run: false,
+            steps: results,
+        };
+        if summary.failed_count() > 0 {
+            Err(RoverError::new(RunFailure { summary }))
+        } else {
+            Ok(RoverOutput::RunSummary(summary))
+        }
+    }
+}
+
+/// Runs one step as its own Rover process, so each step behaves
+/// exactly as it would if it were run from a shell
+fn run_step(step: Step, capture_stdout: bool) -> RoverResult<StepResult> {
+    eprintln!(
+        "{}running {}: {}",
+        Emoji::Action,
+        &step.name,
+        Style::Command.paint(step.command())
+    );
+    let rover = std::env::current_exe().context("could not find the Rover executable")?;
+    let mut command = Command::new(rover);
+    command.args(&step.args);
+    if capture_stdout {
+        command.stdout(Stdio::piped());
+        if !step.args.iter().any(|arg| arg == "--format") {
+            command.args(["--format", "json"]);
+        }
+    }
+
+    let started = Instant::now();
+    let output = command
+        .spawn()
+        .and_then(|child| child.wait_with_output())
+        .with_context(|| format!("could not run the step \"{}\"", &step.name))?;
+    let duration_ms = started.elapsed().as_millis();
+
+    let status = match (output.status.success(), step.on_failure) {
+        (true, _) => StepStatus::Succeeded,
+        (false, FailurePolicy::Ignore) => StepStatus::FailedIgnored,
+        (false, _) => StepStatus::Failed,
+    };
+    if status != StepStatus::Succeeded {
+        eprintln!(
+            "{}the step \"{}\" failed with {}",
+            Emoji::Warn,
+            &step.name,
+            output.status
+        );
+    }
+
+    Ok(StepResult {
+        step,
+        status,
+        exit_code: output.status.code(),
+        duration_ms: Some(duration_ms),
+        stdout: capture_stdout.then(|| String::from_utf8_lossy(&output.stdout).to_string()),
+    })
+}
+
+fn parse_var(var: &str) -> Result<(String, String), io::Error> {
+    match var.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.to_string()))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            anyhow!("Variables must be passed as NAME=VALUE, i.e. `--var graph=my-graph@staging`"),
+        )),
+    }
+}
+
+/// Lets a script start with `#!/usr/bin/env rover`, which runs `rover <SCRIPT> [ARGS]`,
+/// by treating that as `rover run <SCRIPT> [ARGS]`
+pub(crate) fn with_script_mode(args: impl IntoIterator<Item = OsString>) -> Vec<OsString> {
+    let mut args: Vec<OsString> = args.into_iter().collect();
+    let is_script = args
+        .get(1)
+        .and_then(|arg| arg.to_str())
+        .map(Utf8Path::new)
+        .map_or(false, |path| {
+            path.extension() == Some("rover") && path.is_file()
+        });
+    if is_script {
+        args.insert(1, OsString::from("run"));
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_vars() {
+        assert_eq!(
+            parse_var("graph=my-graph@staging").unwrap(),
+            ("graph".to_string(), "my-graph@staging".to_string())
+        );
+        assert_eq!(
+            parse_var("filter=a=b").unwrap(),
+            ("filter".to_string(), "a=b".to_string())
+        );
+        assert!(parse_var("graph").is_err());
+        assert!(parse_var("=my-graph").is_err());
+    }
+
+    #[test]
+    fn it_only_rewrites_existing_scripts() {
+        let args = |args: &[&str]| -> Vec<OsString> { args.iter().map(OsString::from).collect() };
+        assert_eq!(
+            with_script_mode(args(&["rover", "graph", "fetch", "my-graph"])),
+            args(&["rover", "graph", "fetch", "my-graph"])
+        );
+
+        let script = tempfile::Builder::new()
+            .suffix(".rover")
+            .tempfile()
+            .unwrap();
+        let script_path = script.path().to_str().unwrap();
+        assert_eq!(
+            with_script_mode(args(&["rover", script_path, "--dry-run"])),
+            args(&["rover", "run", script_path, "--dry-run"])
+        );
+    }
+}
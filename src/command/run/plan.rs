@@ -0,0 +1,415 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::anyhow;
+use camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::utils::table::{self, row};
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// What to do when a step of a script fails
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// skip every step after this one and fail the run
+    #[default]
+    Stop,
+
+    /// keep running the steps after this one, but fail the run once they finish
+    Continue,
+
+    /// keep running the steps after this one as if this one succeeded
+    Ignore,
+}
+
+/// The arguments of a step, written either as one string split on whitespace
+/// or as a list for arguments that contain whitespace themselves
+#[derive(Debug, Deserialize, Clone, Eq, PartialEq)]
+#[serde(untagged)]
+enum StepArgs {
+    Line(String),
+    List(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StepConfig {
+    name: Option<String>,
+    run: StepArgs,
+    on_failure: Option<FailurePolicy>,
+}
+
+/// The layout of a file passed to `rover run`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScriptConfig {
+    #[serde(default)]
+    vars: BTreeMap<String, String>,
+    #[serde(default)]
+    on_failure: FailurePolicy,
+    steps: Vec<StepConfig>,
+}
+
+/// One Rover command to run, with its variables already filled in
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct Step {
+    pub name: String,
+    /// the arguments passed to Rover, leaving off `rover` itself
+    pub args: Vec<String>,
+    pub on_failure: FailurePolicy,
+}
+
+impl Step {
+    pub fn command(&self) -> String {
+        format!("rover {}", self.args.join(" "))
+    }
+}
+
+/// Every step of a script, in the order they run
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct RunPlan {
+    pub steps: Vec<Step>,
+}
+
+impl RunPlan {
+    /// Reads a script from YAML, or from TOML if the file ends in `.toml`,
+    /// filling in its variables with `overrides`, then its own `vars`, then the environment
+    pub fn new(
+        script_path: &Utf8Path,
+        contents: &str,
+        overrides: &BTreeMap<String, String>,
+    ) -> RoverResult<Self> {
+        let config: ScriptConfig = if script_path.extension() == Some("toml") {
+            toml::from_str(contents).map_err(|e| invalid_script(script_path, e))?
+        } else {
+            serde_yaml::from_str(contents).map_err(|e| invalid_script(script_path, e))?
+        };
+
+        let mut vars = config.vars;
+        vars.extend(overrides.clone());
+
+        let mut steps = Vec::with_capacity(config.steps.len());
+        for (index, step) in config.steps.into_iter().enumerate() {
+            let name = step.name.unwrap_or_else(|| format!("step {}", index + 1));
+            let args = match step.run {
+                StepArgs::Line(line) => line.split_whitespace().map(str::to_string).collect(),
+                StepArgs::List(args) => args,
+            };
+            let args = args
+                .iter()
+                .map(|arg| substitute(arg, &vars))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|var| unknown_var(&name, &var))?;
+            if args.is_empty() {
+                return Err(anyhow!("The step \"{}\" has no Rover command to run.", name).into());
+            }
+            steps.push(Step {
+                name,
+                args,
+                on_failure: step.on_failure.unwrap_or(config.on_failure),
+            });
+        }
+        Ok(Self { steps })
+    }
+}
+
+fn invalid_script(script_path: &Utf8Path, error: impl fmt::Display) -> RoverError {
+    RoverError::new(anyhow!(
+        "{} is not a valid Rover script: {}",
+        script_path,
+        error
+    ))
+}
+
+fn unknown_var(step_name: &str, var: &str) -> RoverError {
+    let mut err = RoverError::new(anyhow!(
+        "The step \"{}\" uses the variable `{}`, which isn't set.",
+        step_name,
+        var
+    ));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+        "Add `{}` to the script's `vars`, pass `--var {}=<VALUE>`, or set it as an environment variable.",
+        var, var
+    )));
+    err
+}
+
+/// Replaces every `${NAME}` in `arg`, returning the name of the first variable that isn't set
+fn substitute(arg: &str, vars: &BTreeMap<String, String>) -> Result<String, String> {
+    let mut substituted = String::with_capacity(arg.len());
+    let mut rest = arg;
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let name = rest[start + 2..end].trim();
+        let value = vars
+            .get(name)
+            .cloned()
+            .or_else(|| std::env::var(name).ok())
+            .ok_or_else(|| name.to_string())?;
+        substituted.push_str(&rest[..start]);
+        substituted.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    substituted.push_str(rest);
+    Ok(substituted)
+}
+
+#[derive(Debug, Serialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StepStatus {
+    /// the step would run, but `--dry-run` was passed
+    Planned,
+    Succeeded,
+    Failed,
+    /// the step failed, but its failure policy is `ignore`
+    FailedIgnored,
+    /// an earlier step failed with a failure policy of `stop`
+    Skipped,
+}
+
+impl fmt::Display for StepStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            StepStatus::Planned => "planned",
+            StepStatus::Succeeded => "succeeded",
+            StepStatus::Failed => "failed",
+            StepStatus::FailedIgnored => "failed (ignored)",
+            StepStatus::Skipped => "skipped",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct StepResult {
+    pub step: Step,
+    pub status: StepStatus,
+    pub exit_code: Option<i32>,
+    pub duration_ms: Option<u128>,
+    /// what the step printed to stdout, which is only captured for `--format json`
+    #[serde(skip_serializing)]
+    pub stdout: Option<String>,
+}
+
+/// The result of every step of a script
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct RunSummary {
+    pub dry_run: bool,
+    pub steps: Vec<StepResult>,
+}
+
+impl RunSummary {
+    /// The summary of a run that only prints its plan
+    pub fn planned(plan: RunPlan) -> Self {
+        Self {
+            dry_run: true,
+            steps: plan
+                .steps
+                .into_iter()
+                .map(|step| StepResult {
+                    step,
+                    status: StepStatus::Planned,
+                    exit_code: None,
+                    duration_ms: None,
+                    stdout: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|result| result.status == StepStatus::Failed)
+            .count()
+    }
+
+    pub fn get_table(&self) -> String {
+        let mut table = table::get_table();
+
+        // bc => sets top row to be bold and center
+        table.add_row(row![bc => "Step", "Command", "Status", "Duration"]);
+        for result in &self.steps {
+            let duration = result
+                .duration_ms
+                .map(|ms| format!("{:.1}s", ms as f64 / 1000.0))
+                .unwrap_or_else(|| "N/A".to_string());
+            table.add_row(row![
+                result.step.name,
+                result.step.command(),
+                result.status,
+                duration
+            ]);
+        }
+        table.to_string()
+    }
+
+    pub fn get_json(&self) -> Value {
+        let steps: Vec<Value> = self
+            .steps
+            .iter()
+            .map(|result| {
+                // steps are run with the same `--format` as the script,
+                // so their output is usually Rover's own JSON
+                let output = result.stdout.as_ref().map(|stdout| {
+                    serde_json::from_str::<Value>(stdout)
+                        .unwrap_or_else(|_| Value::String(stdout.to_string()))
+                });
+                json!({
+                    "name": result.step.name,
+                    "args": result.step.args,
+                    "on_failure": result.step.on_failure,
+                    "status": result.status,
+                    "exit_code": result.exit_code,
+                    "duration_ms": result.duration_ms,
+                    "output": output,
+                })
+            })
+            .collect();
+        json!({
+            "dry_run": self.dry_run,
+            "steps": steps,
+            "failed_count": self.failed_count(),
+        })
+    }
+}
+
+/// The error returned when a step fails without a failure policy of `ignore`
+#[derive(Debug)]
+pub struct RunFailure {
+    pub summary: RunSummary,
+}
+
+impl fmt::Display for RunFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.summary.failed_count() {
+            1 => write!(f, "1 step of the script failed."),
+            failed_count => write!(f, "{} steps of the script failed.", failed_count),
+        }
+    }
+}
+
+impl std::error::Error for RunFailure {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"#!/usr/bin/env rover
+vars:
+  graph: my-graph@current
+on_failure: continue
+steps:
+  - name: lint
+    run: graph lint ${graph}
+  - run: [graph, check, "${graph}", --schema, "${schema}"]
+    on_failure: stop
+"#;
+
+    fn overrides(vars: &[(&str, &str)]) -> BTreeMap<String, String> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn it_reads_yaml_scripts() {
+        let plan = RunPlan::new(
+            Utf8Path::new("deploy.rover"),
+            SCRIPT,
+            &overrides(&[("schema", "./schema.graphql")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            plan.steps,
+            vec![
+                Step {
+                    name: "lint".to_string(),
+                    args: vec![
+                        "graph".to_string(),
+                        "lint".to_string(),
+                        "my-graph@current".to_string()
+                    ],
+                    on_failure: FailurePolicy::Continue,
+                },
+                Step {
+                    name: "step 2".to_string(),
+                    args: vec![
+                        "graph".to_string(),
+                        "check".to_string(),
+                        "my-graph@current".to_string(),
+                        "--schema".to_string(),
+                        "./schema.graphql".to_string()
+                    ],
+                    on_failure: FailurePolicy::Stop,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reads_toml_scripts() {
+        let plan = RunPlan::new(
+            Utf8Path::new("deploy.toml"),
+            r#"
+[vars]
+graph = "my-graph@current"
+
+[[steps]]
+run = "graph fetch ${graph}"
+on_failure = "ignore"
+"#,
+            &overrides(&[("graph", "my-graph@staging")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            plan.steps[0].command(),
+            "rover graph fetch my-graph@staging"
+        );
+        assert_eq!(plan.steps[0].on_failure, FailurePolicy::Ignore);
+    }
+
+    #[test]
+    fn it_errs_on_unknown_vars() {
+        let plan = RunPlan::new(
+            Utf8Path::new("deploy.rover"),
+            "steps:\n  - run: graph fetch ${ROVER_RUN_TEST_UNSET_VAR}\n",
+            &BTreeMap::new(),
+        );
+        assert!(plan.is_err());
+    }
+
+    #[test]
+    fn it_only_counts_failures_that_are_not_ignored() {
+        let step = Step {
+            name: "lint".to_string(),
+            args: vec!["graph".to_string(), "lint".to_string()],
+            on_failure: FailurePolicy::Ignore,
+        };
+        let summary = RunSummary {
+            dry_run: false,
+            steps: [
+                StepStatus::Failed,
+                StepStatus::FailedIgnored,
+                StepStatus::Skipped,
+            ]
+            .into_iter()
+            .map(|status| StepResult {
+                step: step.clone(),
+                status,
+                exit_code: None,
+                duration_ms: None,
+                stdout: None,
+            })
+            .collect(),
+        };
+        assert_eq!(summary.failed_count(), 1);
+    }
+}
@@ -0,0 +1,100 @@
+use std::io;
+
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+
+use rover_client::operations::graph::fetch::{self as graph_fetch, GraphFetchInput};
+use rover_client::operations::supergraph::fetch::{
+    self as supergraph_fetch, SupergraphFetchInput, SupergraphSchema,
+};
+use rover_client::shared::GraphRef;
+use rover_client::RoverClientError;
+use rover_std::Style;
+
+use crate::options::ProfileOpt;
+use crate::utils::client::StudioClientConfig;
+use crate::utils::parsers::FileDescriptorType;
+use crate::utils::schema_export;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(ValueEnum, Debug, Serialize, Clone, Copy, Default, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExportFormat {
+    /// One JSON record per line for every type, field, input field, and enum value
+    #[default]
+    JsonlChunks,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Export {
+    /// <NAME>@<VARIANT> of the graph in Apollo Studio whose schema should be exported.
+    /// Leave this off and pass `--schema` to export a local schema instead
+    #[arg(
+        value_name = "GRAPH_REF",
+        required_unless_present = "schema",
+        conflicts_with = "schema"
+    )]
+    #[serde(skip_serializing)]
+    graph_ref: Option<GraphRef>,
+
+    /// The schema file to export, which can be a supergraph schema.
+    /// You can pass `-` to use stdin instead of a file.
+    #[arg(long, short = 's')]
+    #[serde(skip_serializing)]
+    schema: Option<FileDescriptorType>,
+
+    /// The format to export the schema in.
+    /// This isn't `--format`, which sets how Rover prints its own output
+    #[arg(long = "export-format", value_enum, default_value_t = ExportFormat::JsonlChunks)]
+    export_format: ExportFormat,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+}
+
+impl Export {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let sdl = match (&self.graph_ref, &self.schema) {
+            (Some(graph_ref), _) => {
+                let client = client_config.get_authenticated_client(&self.profile)?;
+                eprintln!(
+                    "Exporting the schema of {} using credentials from the {} profile.",
+                    Style::Link.paint(graph_ref.to_string()),
+                    Style::Command.paint(&self.profile.profile_name)
+                );
+                // a supergraph schema knows which subgraph defines each type and field,
+                // so it's exported instead of the API schema whenever the graph has one
+                let supergraph = supergraph_fetch::run(
+                    SupergraphFetchInput {
+                        graph_ref: graph_ref.clone(),
+                        schema: SupergraphSchema::Core,
+                    },
+                    &client,
+                );
+                match supergraph {
+                    Ok(response) => response.sdl.contents,
+                    Err(RoverClientError::ExpectedFederatedGraph { .. }) => {
+                        graph_fetch::run(
+                            GraphFetchInput {
+                                graph_ref: graph_ref.clone(),
+                                hash: None,
+                            },
+                            &client,
+                        )?
+                        .sdl
+                        .contents
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            (None, Some(schema)) => schema.read_file_descriptor("SDL", &mut io::stdin())?,
+            (None, None) => unreachable!("clap requires one of <GRAPH_REF> or --schema"),
+        };
+
+        match self.export_format {
+            ExportFormat::JsonlChunks => {
+                Ok(RoverOutput::SchemaExport(schema_export::export(&sdl)?))
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+mod export;
+mod stats;
+
+pub use export::{Export, ExportFormat};
+pub use stats::Stats;
+
+use clap::Parser;
+use serde::Serialize;
+
+use crate::utils::client::StudioClientConfig;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Schema {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Export a graph's schema as chunks with stable IDs, such as one JSON line
+    /// per type and field, for indexing by other tools
+    Export(export::Export),
+
+    /// Count the types, fields, arguments, deprecations, and directive usages
+    /// of a graph's schema or a local schema
+    Stats(stats::Stats),
+}
+
+impl Schema {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Export(command) => command.run(client_config),
+            Command::Stats(command) => command.run(client_config),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::io;
+
+use clap::Parser;
+use serde::Serialize;
+
+use rover_client::operations::graph::fetch::{self, GraphFetchInput};
+use rover_client::shared::GraphRef;
+
+use crate::options::ProfileOpt;
+use crate::utils::client::{
+    explain_anonymous_failure, get_credentials_profile, StudioClientConfig,
+};
+use crate::utils::parsers::FileDescriptorType;
+use crate::utils::progress::ProgressEvent;
+use crate::utils::schema_stats::SchemaStats;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Stats {
+    /// <NAME>@<VARIANT> of the graph in Apollo Studio whose API schema should be counted.
+    /// Leave this off and pass `--schema` to count a local schema instead
+    #[arg(
+        value_name = "GRAPH_REF",
+        required_unless_present = "schema",
+        conflicts_with = "schema"
+    )]
+    #[serde(skip_serializing)]
+    graph_ref: Option<GraphRef>,
+
+    /// The schema file to count.
+    /// You can pass `-` to use stdin instead of a file.
+    #[arg(long, short = 's')]
+    #[serde(skip_serializing)]
+    schema: Option<FileDescriptorType>,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+}
+
+impl Stats {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let (schema, sdl) = match (&self.graph_ref, &self.schema) {
+            (Some(graph_ref), _) => {
+                let client = client_config.get_public_client(&self.profile)?;
+                let progress = client_config.get_progress();
+                progress.emit(ProgressEvent::SchemaFetchStarted {
+                    graph_ref: graph_ref.to_string(),
+                    subgraph: None,
+                    hash: None,
+                    supergraph: false,
+                    profile: get_credentials_profile(&client, &self.profile),
+                });
+                let fetch_response = fetch::run(
+                    GraphFetchInput {
+                        graph_ref: graph_ref.clone(),
+                        hash: None,
+                    },
+                    &client,
+                )
+                .map_err(|e| explain_anonymous_failure(&client, graph_ref, e))?;
+                progress.emit(ProgressEvent::SchemaFetched {
+                    graph_ref: graph_ref.to_string(),
+                    subgraph: None,
+                });
+                (graph_ref.to_string(), fetch_response.sdl.contents)
+            }
+            (None, Some(schema)) => (
+                schema.to_string(),
+                schema.read_file_descriptor("SDL", &mut io::stdin())?,
+            ),
+            (None, None) => unreachable!("clap requires one of <GRAPH_REF> or --schema"),
+        };
+
+        Ok(RoverOutput::SchemaStats(SchemaStats::new(schema, &sdl)?))
+    }
+}
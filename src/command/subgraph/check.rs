@@ -1,12 +1,20 @@
+use camino::Utf8PathBuf;
 use clap::Parser;
+use rover_client::blocking::StudioClient;
 use rover_client::operations::subgraph::check::{self, SubgraphCheckAsyncInput};
+use rover_std::Fs;
 use serde::Serialize;
 
 use rover_client::operations::subgraph::check_workflow::{self, CheckWorkflowInput};
-use rover_client::shared::{CheckConfig, GitContext};
+use rover_client::operations::subgraph::fetch::{self, SubgraphFetchInput};
+use rover_client::shared::GitContext;
 
-use crate::options::{CheckConfigOpts, GraphRefOpt, ProfileOpt, SchemaOpt, SubgraphOpt};
+use crate::options::{
+    CheckConfigOpts, FixtureOpt, GraphRefOpt, ProfileOpt, SchemaOpt, SubgraphOpt,
+};
 use crate::utils::client::StudioClientConfig;
+use crate::utils::rover_config::RoverConfig;
+use crate::utils::schema_diff::SchemaDiff;
 use crate::{RoverOutput, RoverResult};
 
 #[derive(Debug, Serialize, Parser)]
@@ -26,6 +34,28 @@ pub struct Check {
 
     #[clap(flatten)]
     config: CheckConfigOpts,
+
+    #[clap(flatten)]
+    #[serde(skip_serializing)]
+    fixture: FixtureOpt,
+
+    /// Compare the proposed schema to the subgraph's last published schema locally
+    /// instead of running checks in Apollo Studio. Changes are classified with static
+    /// rules, without looking at the operations your clients send
+    #[arg(long, conflicts_with_all = ["background", "record", "replay", "pre_screen"])]
+    offline: bool,
+
+    /// Compare the proposed schema to the subgraph's last published schema locally,
+    /// and print the changes found, before running checks in Apollo Studio
+    #[arg(long)]
+    pre_screen: bool,
+
+    /// A local snapshot of the subgraph's schema for `--offline` and `--pre-screen`
+    /// to compare against, instead of fetching its last published schema. Apollo Studio
+    /// isn't contacted at all when this is passed with `--offline`
+    #[arg(long, value_name = "BASE_SCHEMA")]
+    #[serde(skip_serializing)]
+    base_schema: Option<Utf8PathBuf>,
 }
 
 impl Check {
@@ -35,12 +65,84 @@ impl Check {
         git_context: GitContext,
         checks_timeout_seconds: u64,
     ) -> RoverResult<RoverOutput> {
-        let client = client_config.get_authenticated_client(&self.profile)?;
-
         let proposed_schema = self
             .schema
             .read_file_descriptor("SDL", &mut std::io::stdin())?;
 
+        if self.offline {
+            let diff = self.diff_locally(&client_config, &proposed_schema)?;
+            return Ok(RoverOutput::SchemaDiff(diff));
+        }
+
+        let session = self.fixture.get_session()?;
+        let client_config = client_config.with_fixture(session.clone());
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let result = self
+            .pre_screen(&client_config, &proposed_schema)
+            .and_then(|_| {
+                self.check(
+                    &client,
+                    git_context,
+                    checks_timeout_seconds,
+                    proposed_schema,
+                )
+            });
+        // failed checks are recorded too, since those are the responses worth reproducing
+        self.fixture.save(session)?;
+        result
+    }
+
+    fn pre_screen(
+        &self,
+        client_config: &StudioClientConfig,
+        proposed_schema: &str,
+    ) -> RoverResult<()> {
+        if self.pre_screen {
+            let diff = self.diff_locally(client_config, proposed_schema)?;
+            eprintln!("{}", diff.get_table());
+        }
+        Ok(())
+    }
+
+    /// Classifies the changes in the proposed schema without running checks in Apollo Studio
+    fn diff_locally(
+        &self,
+        client_config: &StudioClientConfig,
+        proposed_schema: &str,
+    ) -> RoverResult<SchemaDiff> {
+        let (base, base_sdl) = match &self.base_schema {
+            Some(path) => (path.to_string(), Fs::read_file(path)?),
+            None => {
+                let client = client_config.get_authenticated_client(&self.profile)?;
+                let fetch_response = fetch::run(
+                    SubgraphFetchInput {
+                        graph_ref: self.graph.graph_ref.clone(),
+                        subgraph_name: self.subgraph.subgraph_name.clone(),
+                    },
+                    &client,
+                )?;
+                (
+                    format!(
+                        "subgraph {} of {}",
+                        &self.subgraph.subgraph_name, &self.graph.graph_ref
+                    ),
+                    fetch_response.sdl.contents,
+                )
+            }
+        };
+        SchemaDiff::new(base, &base_sdl, "the proposed schema", proposed_schema)
+    }
+
+    fn check(
+        &self,
+        client: &StudioClient,
+        git_context: GitContext,
+        checks_timeout_seconds: u64,
+        proposed_schema: String,
+    ) -> RoverResult<RoverOutput> {
+        let policy = RoverConfig::load(self.config.rover_config.as_deref())?.checks;
+        let config = policy.get_check_config(&self.config, &[])?;
+
         eprintln!(
             "Checking the proposed schema for subgraph {} against {}",
             &self.subgraph.subgraph_name, &self.graph.graph_ref
@@ -52,28 +154,31 @@ impl Check {
                 subgraph: self.subgraph.subgraph_name.clone(),
                 git_context,
                 proposed_schema,
-                config: CheckConfig {
-                    query_count_threshold: self.config.query_count_threshold,
-                    query_count_threshold_percentage: self.config.query_percentage_threshold,
-                    validation_period: self.config.validation_period.clone(),
-                },
+                config,
             },
-            &client,
+            client,
         )?;
         if self.config.background {
             Ok(RoverOutput::AsyncCheckResponse(workflow_res))
         } else {
-            let check_res = check_workflow::run(
-                CheckWorkflowInput {
-                    graph_ref: self.graph.graph_ref.clone(),
-                    workflow_id: workflow_res.workflow_id,
-                    checks_timeout_seconds,
-                },
-                self.subgraph.subgraph_name.clone(),
-                &client,
-            )?;
-
-            Ok(RoverOutput::CheckResponse(check_res))
+            let result = policy
+                .apply(
+                    check_workflow::run(
+                        CheckWorkflowInput {
+                            graph_ref: self.graph.graph_ref.clone(),
+                            workflow_id: workflow_res.workflow_id.clone(),
+                            checks_timeout_seconds,
+                        },
+                        self.subgraph.subgraph_name.clone(),
+                        client,
+                    ),
+                    &self.graph.graph_ref,
+                )
+                .map(RoverOutput::CheckResponse);
+            self.config
+                .report
+                .write(&self.graph.graph_ref, &workflow_res.workflow_id, &result)?;
+            result
         }
     }
 }
@@ -2,10 +2,12 @@ use clap::Parser;
 use serde::Serialize;
 
 use rover_client::operations::subgraph::fetch::{self, SubgraphFetchInput};
-use rover_std::Style;
 
-use crate::options::{GraphRefOpt, ProfileOpt, SubgraphOpt};
-use crate::utils::client::StudioClientConfig;
+use crate::options::{DirectiveFilterOpt, GraphRefOpt, ProfileOpt, SubgraphOpt};
+use crate::utils::client::{
+    explain_anonymous_failure, get_credentials_profile, StudioClientConfig,
+};
+use crate::utils::progress::ProgressEvent;
 use crate::{RoverOutput, RoverResult};
 
 #[derive(Debug, Serialize, Parser)]
@@ -18,18 +20,23 @@ pub struct Fetch {
 
     #[clap(flatten)]
     profile: ProfileOpt,
+
+    #[clap(flatten)]
+    directives: DirectiveFilterOpt,
 }
 
 impl Fetch {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
-        let client = client_config.get_authenticated_client(&self.profile)?;
+        let client = client_config.get_public_client(&self.profile)?;
         let graph_ref = self.graph.graph_ref.to_string();
-        eprintln!(
-            "Fetching SDL from {} (subgraph: {}) using credentials from the {} profile.",
-            Style::Link.paint(&graph_ref),
-            Style::Link.paint(&self.subgraph.subgraph_name),
-            Style::Command.paint(&self.profile.profile_name)
-        );
+        let progress = client_config.get_progress();
+        progress.emit(ProgressEvent::SchemaFetchStarted {
+            graph_ref: graph_ref.clone(),
+            subgraph: Some(self.subgraph.subgraph_name.clone()),
+            hash: None,
+            supergraph: false,
+            profile: get_credentials_profile(&client, &self.profile),
+        });
 
         let fetch_response = fetch::run(
             SubgraphFetchInput {
@@ -37,8 +44,14 @@ impl Fetch {
                 subgraph_name: self.subgraph.subgraph_name.clone(),
             },
             &client,
-        )?;
-
-        Ok(RoverOutput::FetchResponse(fetch_response))
+        )
+        .map_err(|e| explain_anonymous_failure(&client, &self.graph.graph_ref, e))?;
+        progress.emit(ProgressEvent::SchemaFetched {
+            graph_ref,
+            subgraph: Some(self.subgraph.subgraph_name.clone()),
+        });
+
+        let filter = self.directives.get_filter();
+        Ok(RoverOutput::FetchResponse(fetch_response).transform_sdl(|sdl| filter.apply(sdl)))
     }
 }
@@ -8,13 +8,16 @@ use rover_client::{
     operations::subgraph::introspect::{self, SubgraphIntrospectInput},
 };
 
-use crate::options::{IntrospectOpts, OutputOpts};
+use crate::options::{DirectiveFilterOpt, IntrospectOpts, OutputOpts};
 use crate::{RoverOutput, RoverResult};
 
 #[derive(Debug, Serialize, Parser)]
 pub struct Introspect {
     #[clap(flatten)]
     pub opts: IntrospectOpts,
+
+    #[clap(flatten)]
+    pub directives: DirectiveFilterOpt,
 }
 
 impl Introspect {
@@ -23,7 +26,8 @@ impl Introspect {
             self.exec_and_watch(&client, output_opts)
         } else {
             let sdl = self.exec(&client, true)?;
-            Ok(RoverOutput::Introspection(sdl))
+            let filter = self.directives.get_filter();
+            Ok(RoverOutput::Introspection(sdl).transform_sdl(|sdl| filter.apply(sdl)))
         }
     }
 
@@ -42,7 +46,10 @@ impl Introspect {
     }
 
     pub fn exec_and_watch(&self, client: &Client, output_opts: &OutputOpts) -> ! {
-        self.opts
-            .exec_and_watch(|| self.exec(client, false), output_opts)
+        let filter = self.directives.get_filter();
+        self.opts.exec_and_watch(
+            || self.exec(client, false).map(|sdl| filter.apply(&sdl)),
+            output_opts,
+        )
     }
 }
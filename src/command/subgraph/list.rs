@@ -1,11 +1,12 @@
 use clap::Parser;
 use serde::Serialize;
 
-use rover_client::operations::subgraph::list::{self, SubgraphListInput};
+use rover_client::operations::subgraph::list::{self, SubgraphInfo, SubgraphListInput};
 use rover_std::Style;
 
-use crate::options::{GraphRefOpt, ProfileOpt};
+use crate::options::{GraphRefOpt, ListItem, ListOpts, ListValue, ProfileOpt, ProfilesOpt};
 use crate::utils::client::StudioClientConfig;
+use crate::utils::profiles::for_each_profile;
 use crate::{RoverOutput, RoverResult};
 
 #[derive(Debug, Serialize, Parser)]
@@ -14,26 +15,53 @@ pub struct List {
     graph: GraphRefOpt,
 
     #[clap(flatten)]
-    profile: ProfileOpt,
+    profiles: ProfilesOpt,
+
+    #[clap(flatten)]
+    list: ListOpts,
 }
 
 impl List {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
-        let client = client_config.get_authenticated_client(&self.profile)?;
+        for_each_profile(&self.profiles, &client_config, |profile| {
+            self.list(&client_config, profile)
+        })
+    }
+
+    fn list(
+        &self,
+        client_config: &StudioClientConfig,
+        profile: &ProfileOpt,
+    ) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(profile)?;
 
         eprintln!(
             "Listing subgraphs for {} using credentials from the {} profile.",
             Style::Link.paint(self.graph.graph_ref.to_string()),
-            Style::Link.paint(&self.profile.profile_name)
+            Style::Link.paint(&profile.profile_name)
         );
 
-        let list_details = list::run(
+        let mut list_details = list::run(
             SubgraphListInput {
                 graph_ref: self.graph.graph_ref.clone(),
             },
             &client,
         )?;
+        list_details.subgraphs = self.list.apply(list_details.subgraphs)?;
 
         Ok(RoverOutput::SubgraphList(list_details))
     }
 }
+
+impl ListItem for SubgraphInfo {
+    const FIELDS: &'static [&'static str] = &["name", "url", "updated_at"];
+
+    fn get_field(&self, field: &str) -> Option<ListValue> {
+        match field {
+            "name" => Some(ListValue::Text(self.name.clone())),
+            "url" => self.url.clone().map(ListValue::Text),
+            "updated_at" => self.updated_at.utc.map(ListValue::Time),
+            _ => None,
+        }
+    }
+}
@@ -3,6 +3,7 @@ mod delete;
 mod fetch;
 mod introspect;
 mod list;
+mod open;
 mod publish;
 
 pub use check::Check;
@@ -10,13 +11,14 @@ pub use delete::Delete;
 pub use fetch::Fetch;
 pub use introspect::Introspect;
 pub use list::List;
+pub use open::Open;
 pub use publish::Publish;
 
 use clap::Parser;
 use serde::Serialize;
 
 use crate::options::OutputOpts;
-use crate::utils::client::StudioClientConfig;
+use crate::utils::{client::StudioClientConfig, duration_budget::DurationBudget};
 use crate::{RoverOutput, RoverResult};
 
 use rover_client::shared::GitContext;
@@ -45,11 +47,24 @@ pub enum Command {
     /// List all subgraphs for a federated graph
     List(list::List),
 
+    /// Open a subgraph's schema, or its graph's checks or launches, in Apollo Studio
+    Open(open::Open),
+
     /// Publish an updated subgraph schema to the Apollo graph registry and trigger composition in the graph router
     Publish(publish::Publish),
 }
 
 impl Subgraph {
+    /// How long this command is expected to take before Rover suggests ways to speed it up
+    pub(crate) fn duration_budget(&self) -> Option<DurationBudget> {
+        match &self.command {
+            // checks wait on Apollo Studio until they finish, and opening a page
+            // in the browser doesn't wait at all
+            Command::Check(_) | Command::Open(_) => None,
+            _ => Some(DurationBudget::studio_request()),
+        }
+    }
+
     pub fn run(
         &self,
         client_config: StudioClientConfig,
@@ -67,6 +82,7 @@ impl Subgraph {
             }
             Command::Fetch(command) => command.run(client_config),
             Command::List(command) => command.run(client_config),
+            Command::Open(command) => command.run(client_config),
             Command::Publish(command) => command.run(client_config, git_context),
         }
     }
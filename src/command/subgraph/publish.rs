@@ -1,7 +1,9 @@
 use clap::Parser;
 use serde::Serialize;
 
-use crate::options::{GraphRefOpt, ProfileOpt, SchemaOpt, SubgraphOpt};
+use crate::options::{
+    GraphRefOpt, ProfileOpt, ProtectedVariantOpt, RetryOpt, SchemaOpt, SubgraphOpt,
+};
 use crate::utils::client::StudioClientConfig;
 use crate::{RoverOutput, RoverResult};
 
@@ -34,6 +36,12 @@ pub struct Publish {
     #[arg(long)]
     #[serde(skip_serializing)]
     routing_url: Option<String>,
+
+    #[clap(flatten)]
+    retry: RetryOpt,
+
+    #[clap(flatten)]
+    protected_variant: ProtectedVariantOpt,
 }
 
 impl Publish {
@@ -50,6 +58,8 @@ impl Publish {
             Style::Command.paint(&self.profile.profile_name)
         );
 
+        self.protected_variant.confirm(&self.graph.graph_ref)?;
+
         let schema = self
             .schema
             .read_file_descriptor("SDL", &mut std::io::stdin())?;
@@ -64,6 +74,7 @@ impl Publish {
                 schema,
                 git_context,
                 convert_to_federated_graph: self.convert,
+                retries: self.retry.retries,
             },
             &client,
         )?;
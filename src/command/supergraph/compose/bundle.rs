@@ -0,0 +1,155 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use rover_std::{Emoji, Fs, Style};
+
+use crate::RoverResult;
+
+/// The router image the bundle's Dockerfile builds on, which `--build-arg ROUTER_VERSION=..` overrides
+const ROUTER_VERSION: &str = "v1.10.0";
+
+const SUPERGRAPH_FILE_NAME: &str = "supergraph.graphql";
+const ROUTER_CONFIG_FILE_NAME: &str = "router.yaml";
+const DOCKERFILE_FILE_NAME: &str = "Dockerfile";
+
+/// The directory `supergraph compose --bundle` writes a supergraph schema to, along with a
+/// router config and a Dockerfile that runs the router with both.
+///
+/// The supergraph schema is rewritten each time, but the router config and the Dockerfile
+/// are only starters, so a bundle that already has them keeps the ones it has
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RouterBundle {
+    dir: Utf8PathBuf,
+}
+
+impl RouterBundle {
+    pub(crate) fn new(dir: &Utf8Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    /// Writes the bundle, returning each of its files and whether it was written
+    pub(crate) fn write(&self, supergraph_sdl: &str) -> RoverResult<Vec<(Utf8PathBuf, bool)>> {
+        Fs::create_dir_all(&self.dir)?;
+        let supergraph_path = self.dir.join(SUPERGRAPH_FILE_NAME);
+        Fs::write_file(&supergraph_path, supergraph_sdl)?;
+        Ok(vec![
+            (supergraph_path, true),
+            self.write_starter(ROUTER_CONFIG_FILE_NAME, &get_router_config())?,
+            self.write_starter(DOCKERFILE_FILE_NAME, &get_dockerfile())?,
+        ])
+    }
+
+    /// Writes the bundle, and prints which of its files were written
+    pub(crate) fn write_and_report(&self, supergraph_sdl: &str) -> RoverResult<()> {
+        for (path, written) in self.write(supergraph_sdl)? {
+            if written {
+                eprintln!("{}wrote {}", Emoji::Memo, Style::Path.paint(&path));
+            } else {
+                eprintln!(
+                    "{}kept the existing {}",
+                    Emoji::Memo,
+                    Style::Path.paint(&path)
+                );
+            }
+        }
+        eprintln!(
+            "{}to run the router, build the bundle with {}",
+            Emoji::Rocket,
+            Style::Command.paint(format!(
+                "docker build -t my-router {} && docker run -p 4000:4000 my-router",
+                &self.dir
+            ))
+        );
+        Ok(())
+    }
+
+    fn write_starter(&self, file_name: &str, contents: &str) -> RoverResult<(Utf8PathBuf, bool)> {
+        let path = self.dir.join(file_name);
+        if path.exists() {
+            return Ok((path, false));
+        }
+        Fs::write_file(&path, contents)?;
+        Ok((path, true))
+    }
+}
+
+fn get_router_config() -> String {
+    r#"# generated by `rover supergraph compose --bundle`
+# the router runs with this config, and it isn't overwritten when you compose again
+supergraph:
+  listen: 0.0.0.0:4000
+health_check:
+  listen: 0.0.0.0:8088
+  enabled: true
+include_subgraph_errors:
+  all: false
+"#
+    .to_string()
+}
+
+fn get_dockerfile() -> String {
+    format!(
+        r#"# generated by `rover supergraph compose --bundle`
+# docker build -t my-router . && docker run -p 4000:4000 my-router
+ARG ROUTER_VERSION={router_version}
+FROM ghcr.io/apollographql/router:${{ROUTER_VERSION}}
+COPY {supergraph} /dist/schema/{supergraph}
+COPY {router_config} /dist/config/{router_config}
+ENV APOLLO_ROUTER_SUPERGRAPH_PATH=/dist/schema/{supergraph}
+ENV APOLLO_ROUTER_CONFIG_PATH=/dist/config/{router_config}
+EXPOSE 4000 8088
+"#,
+        router_version = ROUTER_VERSION,
+        supergraph = SUPERGRAPH_FILE_NAME,
+        router_config = ROUTER_CONFIG_FILE_NAME,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use assert_fs::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn it_keeps_an_edited_router_config() {
+        let tmp_home = TempDir::new().unwrap();
+        let dir = Utf8PathBuf::try_from(tmp_home.path().to_path_buf())
+            .unwrap()
+            .join("bundle");
+        let bundle = RouterBundle::new(&dir);
+
+        let written = bundle.write("schema { query: Query }").unwrap();
+        assert!(written.iter().all(|(_, written)| *written));
+        let dockerfile = fs::read_to_string(dir.join("Dockerfile")).unwrap();
+        assert!(dockerfile.contains("COPY supergraph.graphql /dist/schema/supergraph.graphql"));
+        assert!(dockerfile.contains("FROM ghcr.io/apollographql/router:${ROUTER_VERSION}"));
+
+        fs::write(
+            dir.join("router.yaml"),
+            "supergraph:\n  listen: 0.0.0.0:4001\n",
+        )
+        .unwrap();
+        let written = bundle.write("schema { query: Query } type Query").unwrap();
+        assert_eq!(
+            written
+                .iter()
+                .map(|(path, written)| (path.file_name().unwrap(), *written))
+                .collect::<Vec<_>>(),
+            vec![
+                ("supergraph.graphql", true),
+                ("router.yaml", false),
+                ("Dockerfile", false)
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("supergraph.graphql")).unwrap(),
+            "schema { query: Query } type Query"
+        );
+        assert!(fs::read_to_string(dir.join("router.yaml"))
+            .unwrap()
+            .contains("4001"));
+    }
+}
@@ -1,49 +1,268 @@
-use crate::command::supergraph::resolve_supergraph_yaml;
-use crate::utils::{client::StudioClientConfig, parsers::FileDescriptorType};
+use crate::command::supergraph::{
+    parse_supergraph_yaml, resolve_supergraph_config, ConfigExtensions, ResolutionReport,
+    ResolveOpts, SubgraphOverride, DEFAULT_RESOLUTION_CONCURRENCY,
+};
+use crate::utils::{
+    client::StudioClientConfig,
+    composition_cache::{CompositionCache, CompositionInputs},
+    introspection_cache::IntrospectionCache,
+    parsers::{parse_federation_version, FileDescriptorType},
+    progress::ProgressEvent,
+};
 use crate::{
+    cli::RoverOutputFormatKind,
     command::{
         install::{Install, Plugin},
-        supergraph::compose::CompositionOutput,
+        supergraph::compose::{
+            bundle::RouterBundle, hooks::RecompositionHooks, CompositionOutput, HintPolicy,
+        },
+    },
+    options::{
+        format_output, write_if_changed, DirectiveFilterOpt, OutputOpts, PluginOpts,
+        RoverOutputDestination,
     },
-    options::PluginOpts,
     RoverError, RoverErrorSuggestion, RoverOutput, RoverResult,
 };
 
 use anyhow::{anyhow, Context};
-use apollo_federation_types::config::SupergraphConfig;
+use apollo_federation_types::config::{SchemaSource, SubgraphConfig, SupergraphConfig};
 use apollo_federation_types::{
     build::BuildResult,
     config::{FederationVersion, PluginVersion},
 };
+use calm_io::stdoutln;
+use chrono::{DateTime, Utc};
+use notify::{watcher, DebouncedEvent, RecursiveMode, Watcher};
+use rover_client::operations::subgraph::list::{self as subgraph_list, SubgraphListInput};
+use rover_client::shared::GraphRef;
 use rover_client::RoverClientError;
-use rover_std::{Emoji, Style};
+use rover_std::{tmp, Emoji, Fs, Style};
+use serde_json::Value;
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use clap::Parser;
 use serde::Serialize;
-use tempdir::TempDir;
 
-use std::{fs::File, io::Write, process::Command, str};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use std::{process::Command, str};
 
 #[derive(Debug, Clone, Serialize, Parser)]
 pub struct Compose {
     /// The relative path to the supergraph configuration file. You can pass `-` to use stdin instead of a file.
     #[serde(skip_serializing)]
-    #[arg(long = "config")]
-    supergraph_yaml: FileDescriptorType,
+    #[arg(long = "config", required_unless_present = "graph_ref")]
+    supergraph_yaml: Option<FileDescriptorType>,
+
+    /// Compose the subgraphs published to this variant in Apollo Studio, i.e. `my-graph@current`,
+    /// instead of the subgraphs in a supergraph config
+    #[arg(
+        long = "graph-ref",
+        value_name = "GRAPH_REF",
+        conflicts_with_all = ["supergraph_yaml", "watch", "offline"]
+    )]
+    #[serde(skip_serializing)]
+    graph_ref: Option<GraphRef>,
+
+    /// Compose a subgraph with the schema in a local file instead of its usual source,
+    /// i.e. `products=./products.graphql`. Can be passed more than once
+    #[arg(
+        long = "override-subgraph",
+        value_name = "NAME=FILE",
+        value_parser = parse_subgraph_override
+    )]
+    #[serde(skip_serializing)]
+    override_subgraph: Vec<SubgraphOverride>,
 
     #[clap(flatten)]
     opts: PluginOpts,
+
+    #[clap(flatten)]
+    directives: DirectiveFilterOpt,
+
+    /// Compose without any subgraph whose schema can't be fetched from its `subgraph_url`
+    /// or from Apollo Studio, instead of failing. The output is marked as partial
+    #[arg(long)]
+    allow_missing_subgraphs: bool,
+
+    /// The maximum number of subgraph schemas to resolve at once. Raise this to compose
+    /// large graphs with many `subgraph_url` or `graphref` sources faster
+    #[arg(
+        long = "resolution-concurrency",
+        value_name = "COUNT",
+        default_value_t = DEFAULT_RESOLUTION_CONCURRENCY,
+        value_parser = clap::value_parser!(u16).range(1..=256)
+    )]
+    resolution_concurrency: u16,
+
+    /// Keep running, and recompose whenever the supergraph config or a subgraph's schema
+    /// file changes, or a `subgraph_url` source's schema changes. Each supergraph is written
+    /// to the `--output` file, and a line of JSON is printed to stdout for each recomposition
+    #[arg(long)]
+    watch: bool,
+
+    /// With `--watch`, the number of seconds between introspection requests to
+    /// `subgraph_url` sources
+    #[arg(
+        long = "polling-interval",
+        value_name = "SECONDS",
+        default_value = "1",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    polling_interval: u64,
+
+    /// Reuse the schemas introspected from `subgraph_url` sources for this many seconds
+    /// instead of introspecting every subgraph each time. The schemas are cached in
+    /// Rover's config directory
+    #[arg(
+        long = "cache-ttl",
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    cache_ttl: Option<u64>,
+
+    /// Reuse the last composition of this supergraph config if no subgraph's routing URL or
+    /// source changed since, instead of resolving the subgraphs and running the composition
+    /// plugin again. Compositions with `subgraph_url` or `graphref` sources are only reused
+    /// for `--cache-ttl` seconds. The composition is cached in Rover's config directory
+    #[arg(long = "cache-composition", conflicts_with = "watch")]
+    cache_composition: bool,
+
+    /// Before resolving any schema, check that every subgraph's `subgraph_url` and routing URL
+    /// can be reached and implement the federation subgraph spec, and fail with a report of
+    /// the subgraphs that aren't ready instead of composing
+    #[arg(long, conflicts_with = "offline")]
+    preflight: bool,
+
+    /// The federation version to compose with, i.e. `2` or `=2.3.1`, instead of the config's
+    /// `federation_version`. Without either, the version is detected from the subgraphs
+    #[arg(long = "federation-version", value_name = "VERSION", value_parser = parse_federation_version)]
+    #[serde(skip_serializing)]
+    federation_version: Option<FederationVersion>,
+
+    /// Compose without using the network, for air-gapped environments. The composition
+    /// plugin has to be installed already, every subgraph needs a `file` or `sdl` source,
+    /// and Rover doesn't check for updates or send anonymous usage data
+    #[arg(long)]
+    offline: bool,
+
+    /// Compose with the composition plugin at this path, i.e. an internally mirrored or patched
+    /// `supergraph` binary, instead of one Rover downloads. Overrides the config's `composition_binary`
+    #[arg(long = "composition-binary", value_name = "PATH")]
+    #[serde(skip_serializing)]
+    composition_binary: Option<Utf8PathBuf>,
+
+    /// Also write the supergraph schema to this directory, with a starter router config and a
+    /// Dockerfile that runs a self-hosted router with both
+    #[arg(long = "bundle", value_name = "DIR", conflicts_with = "watch")]
+    #[serde(skip_serializing)]
+    bundle: Option<Utf8PathBuf>,
+
+    /// Fail if composition produces a hint with this code, i.e. `INCONSISTENT_DESCRIPTION`,
+    /// or any hint with `all`. Can be passed more than once
+    #[arg(long = "fail-on-hint", value_name = "CODE|all")]
+    fail_on_hint: Vec<String>,
+
+    /// Leave hints with this code out of the output, so they're neither printed nor failed on.
+    /// Can be passed more than once
+    #[arg(long = "ignore-hint", value_name = "CODE")]
+    ignore_hint: Vec<String>,
+
+    /// Don't print composition hints to stderr, i.e. when composing in a loop.
+    /// Hints are still part of JSON output
+    #[arg(long = "skip-hints")]
+    skip_hints: bool,
+
+    /// With `--watch`, a shell command to run after each successful recomposition, i.e. to
+    /// reload a local router. The recomposition is passed in `ROVER_*` environment variables
+    #[arg(long = "on-success", value_name = "COMMAND", requires = "watch")]
+    #[serde(skip_serializing)]
+    on_success: Option<String>,
+
+    /// With `--watch`, a shell command to run after each failed recomposition. The error is
+    /// passed in the `ROVER_COMPOSITION_ERROR` environment variable
+    #[arg(long = "on-error", value_name = "COMMAND", requires = "watch")]
+    #[serde(skip_serializing)]
+    on_error: Option<String>,
+}
+
+/// A supergraph config that's been read, but whose subgraphs haven't been resolved
+struct LoadedConfig {
+    supergraph_config: SupergraphConfig,
+    extensions: ConfigExtensions,
+    /// where the config was read from, which its `file` sources are relative to
+    source: FileDescriptorType,
+    /// names the config in errors, i.e. `'./supergraph.yaml'`
+    description: String,
+}
+
+impl LoadedConfig {
+    /// The directory the config's `file` sources are relative to
+    fn get_config_dir(&self) -> Utf8PathBuf {
+        match &self.source {
+            FileDescriptorType::File(config_path) => match config_path.parent() {
+                Some(parent) if !parent.as_str().is_empty() => parent.to_path_buf(),
+                _ => Utf8PathBuf::from("."),
+            },
+            FileDescriptorType::Stdin => Utf8PathBuf::from("."),
+        }
+    }
+}
+
+/// One recomposition by `supergraph compose --watch`, printed to stdout as a line of JSON
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RecompositionEvent {
+    CompositionSucceeded {
+        at: DateTime<Utc>,
+        /// the file the supergraph schema was written to
+        output: Utf8PathBuf,
+        /// whether the file was rewritten, which it isn't if the supergraph schema is the same
+        changed: bool,
+        federation_version: Option<String>,
+        subgraphs: usize,
+        hints: usize,
+        /// subgraphs left out because of `--allow-missing-subgraphs`
+        missing_subgraphs: Vec<String>,
+    },
+    /// The output file is left as it was, with the last supergraph that composed
+    CompositionFailed { at: DateTime<Utc>, error: Value },
 }
 
 impl Compose {
     pub fn new(compose_opts: PluginOpts) -> Self {
         Self {
-            supergraph_yaml: FileDescriptorType::File("RAM".into()),
+            supergraph_yaml: Some(FileDescriptorType::File("RAM".into())),
+            graph_ref: None,
+            override_subgraph: Vec::new(),
             opts: compose_opts,
+            directives: DirectiveFilterOpt::default(),
+            allow_missing_subgraphs: false,
+            resolution_concurrency: DEFAULT_RESOLUTION_CONCURRENCY,
+            watch: false,
+            polling_interval: 1,
+            cache_ttl: None,
+            cache_composition: false,
+            preflight: false,
+            federation_version: None,
+            offline: false,
+            composition_binary: None,
+            bundle: None,
+            fail_on_hint: Vec::new(),
+            ignore_hint: Vec::new(),
+            skip_hints: false,
+            on_success: None,
+            on_error: None,
         }
     }
 
+    /// Whether `--offline` was passed, in which case nothing may use the network
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
     pub(crate) fn maybe_install_supergraph(
         &self,
         override_install_path: Option<Utf8PathBuf>,
@@ -65,56 +284,545 @@ impl Compose {
         };
 
         // maybe do the install, maybe find a pre-existing installation, maybe fail
-        let plugin_exe = install_command.get_versioned_plugin(
+        let plugin_exe = install_command
+            .get_versioned_plugin(
+                override_install_path,
+                client_config,
+                self.opts.skip_update || self.offline,
+            )
+            .map_err(|e| {
+                if self.offline {
+                    get_offline_install_error(&federation_version, &e)
+                } else {
+                    e
+                }
+            })?;
+        Ok(plugin_exe)
+    }
+
+    /// How the subgraphs of the supergraph config are resolved
+    fn get_resolve_opts(&self, client_config: &StudioClientConfig) -> ResolveOpts {
+        ResolveOpts {
+            allow_missing_subgraphs: self.allow_missing_subgraphs,
+            concurrency: self.resolution_concurrency,
+            offline: self.offline,
+            // the cache of introspected schemas is only used with `--cache-ttl`
+            introspection_cache: self.cache_ttl.map(|ttl| {
+                IntrospectionCache::new(&client_config.config.home, Duration::from_secs(ttl))
+            }),
+            federation_version: self.federation_version.clone(),
+            subgraph_overrides: self.override_subgraph.clone(),
+            preflight: self.preflight,
+        }
+    }
+
+    /// Resolves the subgraphs of the `--config` file, or the subgraphs published to the
+    /// `--graph-ref` variant
+    fn resolve(
+        &self,
+        client_config: &StudioClientConfig,
+    ) -> RoverResult<(SupergraphConfig, ResolutionReport)> {
+        let loaded_config = self.load(client_config)?;
+        self.resolve_loaded(loaded_config, client_config)
+    }
+
+    /// Reads the `--config` file, or lists the subgraphs published to the `--graph-ref`
+    /// variant, without resolving any subgraph's schema
+    fn load(&self, client_config: &StudioClientConfig) -> RoverResult<LoadedConfig> {
+        match (&self.supergraph_yaml, &self.graph_ref) {
+            (Some(supergraph_yaml), _) => {
+                let contents = supergraph_yaml
+                    .read_file_descriptor("supergraph config", &mut std::io::stdin())?;
+                let (supergraph_config, extensions) =
+                    parse_supergraph_yaml(&contents, supergraph_yaml)?;
+                Ok(LoadedConfig {
+                    supergraph_config,
+                    extensions,
+                    source: supergraph_yaml.clone(),
+                    description: format!("'{}'", supergraph_yaml),
+                })
+            }
+            // every source is a graph ref, and overrides are relative to the current directory
+            (None, Some(graph_ref)) => Ok(LoadedConfig {
+                supergraph_config: self.get_published_config(graph_ref, client_config)?,
+                extensions: ConfigExtensions::default(),
+                source: FileDescriptorType::Stdin,
+                description: format!("the subgraphs published to {}", graph_ref),
+            }),
+            (None, None) => Err(RoverError::new(anyhow!(
+                "Pass a supergraph config to `--config` or a graph ref to `--graph-ref`."
+            ))),
+        }
+    }
+
+    /// Resolves the subgraphs of a config that's already loaded
+    fn resolve_loaded(
+        &self,
+        loaded_config: LoadedConfig,
+        client_config: &StudioClientConfig,
+    ) -> RoverResult<(SupergraphConfig, ResolutionReport)> {
+        let (supergraph_config, mut report) = resolve_supergraph_config(
+            loaded_config.supergraph_config,
+            &loaded_config.source,
+            &loaded_config.description,
+            client_config.clone(),
+            &self.opts.profile,
+            &self.get_resolve_opts(client_config),
+        )?;
+        report.contract_filter = loaded_config.extensions.contract_filter;
+        report.composition_binary = loaded_config.extensions.composition_binary;
+        Ok((supergraph_config, report))
+    }
+
+    /// A supergraph config that fetches each subgraph published to a variant from Apollo Studio
+    fn get_published_config(
+        &self,
+        graph_ref: &GraphRef,
+        client_config: &StudioClientConfig,
+    ) -> RoverResult<SupergraphConfig> {
+        let client = client_config.get_authenticated_client(&self.opts.profile)?;
+        let list_response = subgraph_list::run(
+            SubgraphListInput {
+                graph_ref: graph_ref.clone(),
+            },
+            &client,
+        )?;
+        let subgraphs: BTreeMap<String, SubgraphConfig> = list_response
+            .subgraphs
+            .into_iter()
+            .map(|subgraph| {
+                let subgraph_config = SubgraphConfig {
+                    routing_url: subgraph.url,
+                    schema: SchemaSource::Subgraph {
+                        graphref: graph_ref.to_string(),
+                        subgraph: subgraph.name.clone(),
+                    },
+                };
+                (subgraph.name, subgraph_config)
+            })
+            .collect();
+        let contents = serde_yaml::to_string(&serde_json::json!({ "subgraphs": subgraphs }))?;
+        Ok(SupergraphConfig::new_from_yaml(&contents)?)
+    }
+
+    /// The cache of this supergraph config's last composition, which is only used with `--cache-composition`
+    fn get_composition_cache(
+        &self,
+        client_config: &StudioClientConfig,
+    ) -> Option<CompositionCache> {
+        if !self.cache_composition {
+            return None;
+        }
+        let config_id = match (&self.supergraph_yaml, &self.graph_ref) {
+            (Some(FileDescriptorType::File(config_path)), _) => config_path
+                .canonicalize_utf8()
+                .unwrap_or_else(|_| config_path.clone())
+                .to_string(),
+            (_, Some(graph_ref)) => format!("graph-ref:{}", graph_ref),
+            _ => "-".to_string(),
+        };
+        Some(CompositionCache::new(
+            &client_config.config.home,
+            &config_id,
+            self.cache_ttl.map(Duration::from_secs),
+        ))
+    }
+
+    /// Which composition hints are ignored, and which fail composition
+    fn get_hint_policy(&self) -> HintPolicy {
+        HintPolicy {
+            ignored: self.ignore_hint.clone(),
+            failing: self.fail_on_hint.clone(),
+        }
+    }
+
+    pub fn run(
+        &self,
+        override_install_path: Option<Utf8PathBuf>,
+        client_config: StudioClientConfig,
+        output_opts: &OutputOpts,
+    ) -> RoverResult<RoverOutput> {
+        tmp::cleanup_on_ctrl_c()?;
+        if self.watch {
+            return self.watch(override_install_path, client_config, output_opts);
+        }
+        client_config
+            .get_progress()
+            .emit(ProgressEvent::SupergraphConfigResolutionStarted {
+                config: match (&self.supergraph_yaml, &self.graph_ref) {
+                    (Some(supergraph_yaml), _) => supergraph_yaml.to_string(),
+                    (None, Some(graph_ref)) => graph_ref.to_string(),
+                    (None, None) => String::new(),
+                },
+            });
+        let loaded_config = self.load(&client_config)?;
+        let mut output = match self.get_composition_cache(&client_config) {
+            Some(composition_cache) => self.compose_cached(
+                override_install_path,
+                client_config,
+                loaded_config,
+                &composition_cache,
+            )?,
+            None => {
+                let (mut supergraph_config, report) =
+                    self.resolve_loaded(loaded_config, &client_config)?;
+                self.compose(
+                    override_install_path,
+                    client_config,
+                    &mut supergraph_config,
+                    report,
+                )?
+            }
+        };
+        if let (Some(bundle_dir), RoverOutput::CompositionResult(composition_output)) =
+            (&self.bundle, &output)
+        {
+            RouterBundle::new(bundle_dir).write_and_report(&composition_output.supergraph_sdl)?;
+        }
+        self.skip_hints(&mut output, output_opts);
+        Ok(output)
+    }
+
+    /// Leaves the hints out of a composition if `--skip-hints` was passed and it's printed as
+    /// plain text, which prints hints to stderr. JSON output keeps every hint
+    fn skip_hints(&self, output: &mut RoverOutput, output_opts: &OutputOpts) {
+        let (format_kind, _) = output_opts.get_format_and_strategy();
+        if let RoverOutput::CompositionResult(composition_output) = output {
+            if self.skip_hints && matches!(format_kind, RoverOutputFormatKind::Plain) {
+                composition_output.hints.clear();
+            }
+        }
+    }
+
+    pub fn compose(
+        &self,
+        override_install_path: Option<Utf8PathBuf>,
+        client_config: StudioClientConfig,
+        supergraph_config: &mut SupergraphConfig,
+        report: ResolutionReport,
+    ) -> RoverResult<RoverOutput> {
+        let output = self.exec_with_binary(
             override_install_path,
             client_config,
-            self.opts.skip_update,
+            supergraph_config,
+            self.get_composition_binary(&report.composition_binary),
         )?;
-        Ok(plugin_exe)
+        self.finish(output, report)
     }
 
-    pub fn run(
+    /// Reuses the cached composition of a loaded config if none of its sources changed,
+    /// and otherwise resolves and composes it and caches the composition
+    fn compose_cached(
         &self,
         override_install_path: Option<Utf8PathBuf>,
         client_config: StudioClientConfig,
+        loaded_config: LoadedConfig,
+        composition_cache: &CompositionCache,
     ) -> RoverResult<RoverOutput> {
-        eprintln!(
-            "{}resolving SDL for subgraphs defined in {}",
-            Emoji::Hourglass,
-            Style::Path.paint(&self.supergraph_yaml.to_string())
+        let override_files = self
+            .override_subgraph
+            .iter()
+            .map(|subgraph_override| {
+                (
+                    subgraph_override.name.clone(),
+                    subgraph_override.file.clone(),
+                )
+            })
+            .collect();
+        let inputs = CompositionInputs::new(
+            &loaded_config.supergraph_config,
+            &loaded_config.get_config_dir(),
+            &override_files,
+            self.federation_version.as_ref(),
+            self.get_composition_binary(&loaded_config.extensions.composition_binary),
         );
-        let mut supergraph_config = resolve_supergraph_yaml(
-            &self.supergraph_yaml,
-            client_config.clone(),
-            &self.opts.profile,
+        let (status, cached_output) = composition_cache.get(&inputs);
+        client_config
+            .get_progress()
+            .emit(ProgressEvent::CompositionCacheChecked {
+                hit: status.hit,
+                changed_subgraphs: status.changed_subgraphs.clone(),
+            });
+        if let Some(cached_output) = cached_output {
+            let report = ResolutionReport {
+                contract_filter: loaded_config.extensions.contract_filter,
+                composition_binary: loaded_config.extensions.composition_binary,
+                ..ResolutionReport::default()
+            };
+            return self.finish(cached_output, report);
+        }
+
+        let (mut supergraph_config, report) = self.resolve_loaded(loaded_config, &client_config)?;
+        let mut output = self.exec_with_binary(
+            override_install_path,
+            client_config,
+            &mut supergraph_config,
+            self.get_composition_binary(&report.composition_binary),
         )?;
-        self.compose(override_install_path, client_config, &mut supergraph_config)
+        output.cache = Some(status);
+        // a composition that can't be cached is run again next time
+        if let Err(e) = composition_cache.put(&inputs, &output) {
+            tracing::debug!("could not cache the composition: {}", e);
+        }
+        self.finish(output, report)
     }
 
-    pub fn compose(
+    /// Applies the config's `filter` section and the hint and directive options to a composition
+    fn finish(
+        &self,
+        mut output: CompositionOutput,
+        report: ResolutionReport,
+    ) -> RoverResult<RoverOutput> {
+        if let Some(contract_filter) = &report.contract_filter {
+            output.supergraph_sdl = contract_filter.apply(&output.supergraph_sdl)?;
+        }
+        output.missing_subgraphs = report.missing_subgraphs;
+        output.subgraph_metrics = report.subgraph_metrics;
+        self.get_hint_policy().apply(&mut output)?;
+        let filter = self.directives.get_filter();
+        Ok(RoverOutput::CompositionResult(output).transform_sdl(|sdl| filter.apply(sdl)))
+    }
+
+    /// Recomposes whenever one of the supergraph's sources changes, until Rover is stopped
+    fn watch(
         &self,
         override_install_path: Option<Utf8PathBuf>,
         client_config: StudioClientConfig,
-        supergraph_config: &mut SupergraphConfig,
+        output_opts: &OutputOpts,
     ) -> RoverResult<RoverOutput> {
-        let output = self.exec(override_install_path, client_config, supergraph_config)?;
-        Ok(RoverOutput::CompositionResult(output))
+        let config_path = match &self.supergraph_yaml {
+            Some(FileDescriptorType::File(config_path)) => config_path.canonicalize_utf8()?,
+            // `--graph-ref` can't be used with `--watch`, so there's always a config
+            _ => {
+                let mut err = RoverError::new(anyhow!(
+                    "A supergraph config read from stdin can't be watched for changes."
+                ));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                    "Pass the path of the supergraph config to `--config` instead of `-`."
+                        .to_string(),
+                ));
+                return Err(err);
+            }
+        };
+        let output_path = match output_opts.get_format_and_strategy() {
+            (_, RoverOutputDestination::File(output_path)) => output_path,
+            (_, RoverOutputDestination::Stdout) => {
+                let mut err = RoverError::new(anyhow!(
+                    "`--watch` writes the supergraph schema to a file, since it prints an event to stdout for each recomposition."
+                ));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                    "Pass the path to write the supergraph schema to with `--output`.".to_string(),
+                ));
+                return Err(err);
+            }
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = watcher(tx, Duration::from_secs(1))?;
+        let mut watched_dirs = BTreeSet::new();
+        let mut sources = WatchedSources::default();
+        let mut last_state = None;
+        loop {
+            // the config is read again after each change, so files it starts referring to are watched too
+            match WatchedSources::new(&config_path) {
+                Ok(new_sources) => sources = new_sources,
+                Err(e) => tracing::debug!("could not read the files to watch: {}", e),
+            }
+            for subgraph_override in &self.override_subgraph {
+                let file = &subgraph_override.file;
+                sources
+                    .files
+                    .insert(file.canonicalize_utf8().unwrap_or_else(|_| file.clone()));
+            }
+            for dir in sources.get_dirs() {
+                if !watched_dirs.contains(&dir) {
+                    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+                    watched_dirs.insert(dir);
+                }
+            }
+            if last_state.is_none() {
+                for file in &sources.files {
+                    eprintln!(
+                        "{}watching {} for changes",
+                        Emoji::Watch,
+                        Style::Path.paint(file)
+                    );
+                }
+                if sources.polls_subgraphs {
+                    eprintln!(
+                        "{}polling subgraph_url sources every {} {}",
+                        Emoji::Listen,
+                        self.polling_interval,
+                        match self.polling_interval {
+                            1 => "second",
+                            _ => "seconds",
+                        }
+                    );
+                }
+            }
+
+            self.recompose(
+                override_install_path.clone(),
+                client_config.clone(),
+                output_opts,
+                &output_path,
+                &mut last_state,
+            )?;
+
+            let polling_interval = if sources.polls_subgraphs {
+                Some(Duration::from_secs(self.polling_interval))
+            } else {
+                None
+            };
+            sources.wait_for_change(&rx, polling_interval)?;
+        }
     }
 
-    pub fn exec(
+    /// Resolves and composes the supergraph, writing it and printing an event,
+    /// unless nothing changed since the last recomposition
+    fn recompose(
+        &self,
+        override_install_path: Option<Utf8PathBuf>,
+        client_config: StudioClientConfig,
+        output_opts: &OutputOpts,
+        output_path: &Utf8Path,
+        last_state: &mut Option<String>,
+    ) -> RoverResult<()> {
+        let result = self
+            .resolve(&client_config)
+            .and_then(|(mut supergraph_config, report)| {
+                // a changed `filter` or `composition_binary` recomposes too, even if no subgraph changed
+                let state = format!(
+                    "{}{:?}{:?}",
+                    serde_yaml::to_string(&supergraph_config)?,
+                    report.contract_filter,
+                    report.composition_binary
+                );
+                if last_state.as_ref() == Some(&state) {
+                    return Ok(None);
+                }
+                *last_state = Some(state);
+                let subgraphs = supergraph_config.get_subgraph_definitions()?.len();
+                let mut output = self.exec_with_binary(
+                    override_install_path,
+                    client_config,
+                    &mut supergraph_config,
+                    self.get_composition_binary(&report.composition_binary),
+                )?;
+                if let Some(contract_filter) = &report.contract_filter {
+                    output.supergraph_sdl = contract_filter.apply(&output.supergraph_sdl)?;
+                }
+                output.missing_subgraphs = report.missing_subgraphs;
+                output.subgraph_metrics = report.subgraph_metrics;
+                self.get_hint_policy().apply(&mut output)?;
+                Ok(Some((subgraphs, output)))
+            });
+
+        let event = match result {
+            Ok(None) => return Ok(()),
+            Ok(Some((subgraphs, output))) => {
+                let federation_version = output.federation_version.clone();
+                let hints = output.hints.len();
+                let missing_subgraphs = output
+                    .missing_subgraphs
+                    .iter()
+                    .map(|subgraph| subgraph.name.clone())
+                    .collect();
+                let filter = self.directives.get_filter();
+                let mut output =
+                    RoverOutput::CompositionResult(output).transform_sdl(|sdl| filter.apply(sdl));
+                self.skip_hints(&mut output, output_opts);
+                let (format_kind, _) = output_opts.get_format_and_strategy();
+                // the summary `--output` prints otherwise would break up the events on stdout
+                let changed = match format_output(&output, format_kind)? {
+                    Some(contents) => write_if_changed(output_path, &contents)?,
+                    None => false,
+                };
+                RecompositionEvent::CompositionSucceeded {
+                    at: Utc::now(),
+                    output: output_path.to_path_buf(),
+                    changed,
+                    federation_version,
+                    subgraphs,
+                    hints,
+                    missing_subgraphs,
+                }
+            }
+            Err(error) => {
+                // a source that keeps failing the same way is only reported once
+                let state = error.message();
+                if last_state.as_ref() == Some(&state) {
+                    return Ok(());
+                }
+                *last_state = Some(state);
+                eprint!("{}", error);
+                RecompositionEvent::CompositionFailed {
+                    at: Utc::now(),
+                    error: error.get_internal_error_json(),
+                }
+            }
+        };
+        stdoutln!("{}", serde_json::to_string(&event)?)?;
+        RecompositionHooks {
+            on_success: self.on_success.clone(),
+            on_error: self.on_error.clone(),
+        }
+        .run(&event);
+        Ok(())
+    }
+
+    /// The composition plugin passed to `--composition-binary`, or else the config's `composition_binary`
+    fn get_composition_binary<'a>(
+        &'a self,
+        config_composition_binary: &'a Option<Utf8PathBuf>,
+    ) -> Option<&'a Utf8Path> {
+        self.composition_binary
+            .as_deref()
+            .or(config_composition_binary.as_deref())
+    }
+
+    /// Composes a resolved supergraph config with the `composition_binary` of the config it was
+    /// read from, unless `--composition-binary` was passed, and applies the config's `filter`
+    pub(crate) fn exec(
         &self,
         override_install_path: Option<Utf8PathBuf>,
         client_config: StudioClientConfig,
         supergraph_config: &mut SupergraphConfig,
+        extensions: &ConfigExtensions,
     ) -> RoverResult<CompositionOutput> {
-        // first, grab the _actual_ federation version from the config we just resolved
-        // (this will always be `Some` as long as we have created with `resolve_supergraph_yaml` so it is safe to unwrap)
-        let federation_version = supergraph_config.get_federation_version().unwrap();
-        let exe = self.maybe_install_supergraph(
+        let mut output = self.exec_with_binary(
             override_install_path,
             client_config,
-            federation_version.clone(),
+            supergraph_config,
+            self.get_composition_binary(&extensions.composition_binary),
         )?;
+        if let Some(contract_filter) = &extensions.contract_filter {
+            output.supergraph_sdl = contract_filter.apply(&output.supergraph_sdl)?;
+        }
+        Ok(output)
+    }
+
+    /// Composes with the composition plugin at `composition_binary`, or with one that's
+    /// installed for the config's federation version if there isn't one
+    fn exec_with_binary(
+        &self,
+        override_install_path: Option<Utf8PathBuf>,
+        client_config: StudioClientConfig,
+        supergraph_config: &mut SupergraphConfig,
+        composition_binary: Option<&Utf8Path>,
+    ) -> RoverResult<CompositionOutput> {
+        // first, grab the _actual_ federation version from the config we just resolved
+        // (this will always be `Some` as long as we have created with `resolve_supergraph_yaml` so it is safe to unwrap)
+        let federation_version = supergraph_config.get_federation_version().unwrap();
+        let progress = client_config.get_progress().clone();
+        let exe = match composition_binary {
+            Some(composition_binary) => get_custom_composition_binary(composition_binary)?,
+            None => self.maybe_install_supergraph(
+                override_install_path,
+                client_config,
+                federation_version.clone(),
+            )?,
+        };
 
         // _then_, overwrite the federation_version with _only_ the major version
         // before sending it to the supergraph plugin.
@@ -130,35 +838,34 @@ impl Compose {
         supergraph_config.set_federation_version(v);
         let num_subgraphs = supergraph_config.get_subgraph_definitions()?.len();
         let supergraph_config_yaml = serde_yaml::to_string(&supergraph_config)?;
-        let dir = TempDir::new("supergraph")?;
-        tracing::debug!("temp dir created at {}", dir.path().display());
-        let yaml_path = Utf8PathBuf::try_from(dir.path().join("config.yml"))?;
-        let mut f = File::create(&yaml_path)?;
-        f.write_all(supergraph_config_yaml.as_bytes())?;
-        f.sync_all()?;
-        tracing::debug!("config file written to {}", &yaml_path);
-
-        let federation_version =
-            exe.as_str().split("supergraph-").collect::<Vec<&str>>()[1].to_string();
-        eprintln!(
-            "{}composing supergraph with Federation {}",
-            Emoji::Compose,
-            &federation_version
-        );
+        let dir = tmp::SecureTempDir::new("supergraph")?;
+        let yaml_path = dir.write_file("config.yml", supergraph_config_yaml)?;
 
-        let output = Command::new(&exe)
-            .args(["compose", yaml_path.as_ref()])
-            .output()
+        let federation_version = get_plugin_version(&exe);
+
+        progress.emit(ProgressEvent::CompositionStarted {
+            federation_version: federation_version.clone(),
+        });
+
+        let output = tmp::output(Command::new(&exe).args(["compose", yaml_path.as_ref()]))
             .context("Failed to execute command")?;
         let stdout = str::from_utf8(&output.stdout)
             .with_context(|| format!("Could not parse output of `{} compose`", &exe))?;
 
-        match serde_json::from_str::<BuildResult>(stdout) {
+        let build_result = serde_json::from_str::<BuildResult>(stdout);
+        progress.emit(ProgressEvent::CompositionFinished {
+            federation_version: federation_version.clone(),
+            succeeded: matches!(build_result, Ok(Ok(_))),
+        });
+        match build_result {
             Ok(build_result) => match build_result {
                 Ok(build_output) => Ok(CompositionOutput {
                     hints: build_output.hints,
                     supergraph_sdl: build_output.supergraph_sdl,
                     federation_version: Some(federation_version),
+                    missing_subgraphs: Vec::new(),
+                    subgraph_metrics: Vec::new(),
+                    cache: None,
                 }),
                 Err(build_errors) => Err(RoverError::from(RoverClientError::BuildErrors {
                     source: build_errors,
@@ -177,11 +884,173 @@ impl Compose {
     }
 }
 
+/// Parses an `--override-subgraph`, i.e. `products=./products.graphql`
+fn parse_subgraph_override(subgraph_override: &str) -> Result<SubgraphOverride, io::Error> {
+    match subgraph_override.split_once('=') {
+        Some((name, file)) if !name.trim().is_empty() && !file.trim().is_empty() => {
+            Ok(SubgraphOverride {
+                name: name.trim().to_string(),
+                file: Utf8PathBuf::from(file.trim()),
+            })
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "'{}' is not a subgraph override. Pass the subgraph's name and the file with its schema, i.e. 'products=./products.graphql'.",
+                subgraph_override
+            ),
+        )),
+    }
+}
+
+/// The error for a composition plugin that `--offline` can't find installed
+fn get_offline_install_error(federation_version: &FederationVersion, e: &RoverError) -> RoverError {
+    let mut err = RoverError::new(anyhow!(
+        "`--offline` requires the composition plugin for `federation_version: {}` to be installed already. {}",
+        federation_version,
+        e.message()
+    ));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+        "Run {} on a machine with network access and copy Rover's `bin` directory to this machine, or set {} to a directory that contains the plugin.",
+        Style::Command.paint(format!("`rover supergraph plugin install {}`", federation_version)),
+        Style::Command.paint("APOLLO_NODE_MODULES_BIN_DIR")
+    )));
+    err
+}
+
+/// A composition plugin passed to `--composition-binary` or set as the config's `composition_binary`
+fn get_custom_composition_binary(composition_binary: &Utf8Path) -> RoverResult<Utf8PathBuf> {
+    if composition_binary.is_file() {
+        tracing::info!(
+            "composing with the composition plugin at {}",
+            composition_binary
+        );
+        return Ok(composition_binary.to_path_buf());
+    }
+    let mut err = RoverError::new(anyhow!(
+        "There is no composition plugin at {}.",
+        composition_binary
+    ));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(
+        "Point `--composition-binary` or the config's `composition_binary` at a `supergraph` binary, such as one installed by `rover supergraph plugin install`.".to_string(),
+    ));
+    Err(err)
+}
+
+/// The version of a composition plugin, i.e. `v2.3.1`. Plugins that Rover installed have
+/// it in their file name, and any other binary is asked for it with `--version`
+fn get_plugin_version(exe: &Utf8Path) -> String {
+    if let Some(version) = exe
+        .file_name()
+        .and_then(|file_name| file_name.strip_prefix("supergraph-"))
+    {
+        return version.to_string();
+    }
+    Command::new(exe)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|stdout| {
+            let version = stdout.split_whitespace().last()?.trim_start_matches('v');
+            semver::Version::parse(version).ok()
+        })
+        .map(|version| format!("v{}", version))
+        .unwrap_or_else(|| "custom".to_string())
+}
+
+/// The files `supergraph compose --watch` recomposes on changes to
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct WatchedSources {
+    /// the supergraph config and the schema file of every subgraph with a `file` source
+    files: BTreeSet<Utf8PathBuf>,
+    /// whether any subgraph has a `subgraph_url` source, which has to be polled
+    polls_subgraphs: bool,
+}
+
+impl WatchedSources {
+    fn new(config_path: &Utf8Path) -> RoverResult<Self> {
+        let contents = Fs::read_file(config_path)?;
+        let (supergraph_config, _) = parse_supergraph_yaml(
+            &contents,
+            &FileDescriptorType::File(config_path.to_path_buf()),
+        )?;
+        let config_dir = config_path.parent().unwrap_or_else(|| Utf8Path::new("."));
+
+        let mut sources = Self::default();
+        sources.files.insert(config_path.to_path_buf());
+        for (_, subgraph) in supergraph_config.into_iter() {
+            match &subgraph.schema {
+                SchemaSource::File { file } => {
+                    let file = config_dir.join(file);
+                    // a file that doesn't exist yet is watched at the path it's expected at
+                    sources
+                        .files
+                        .insert(file.canonicalize_utf8().unwrap_or(file));
+                }
+                SchemaSource::SubgraphIntrospection { .. } => sources.polls_subgraphs = true,
+                _ => {}
+            }
+        }
+        Ok(sources)
+    }
+
+    /// The directories of the watched files. Directories are watched instead of the files
+    /// themselves, so files that editors replace when saving them are still watched
+    fn get_dirs(&self) -> BTreeSet<Utf8PathBuf> {
+        self.files
+            .iter()
+            .filter_map(|file| file.parent())
+            .map(|dir| dir.to_path_buf())
+            .collect()
+    }
+
+    /// Blocks until a watched file changes, or until it's time to poll `subgraph_url` sources
+    fn wait_for_change(
+        &self,
+        rx: &mpsc::Receiver<DebouncedEvent>,
+        polling_interval: Option<Duration>,
+    ) -> RoverResult<()> {
+        let deadline = polling_interval.map(|interval| Instant::now() + interval);
+        loop {
+            let event = match deadline {
+                Some(deadline) => {
+                    match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                        Ok(event) => event,
+                        Err(RecvTimeoutError::Timeout) => return Ok(()),
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                None => match rx.recv() {
+                    Ok(event) => event,
+                    Err(_) => break,
+                },
+            };
+            let path = match event {
+                DebouncedEvent::Create(path)
+                | DebouncedEvent::Write(path)
+                | DebouncedEvent::Remove(path)
+                | DebouncedEvent::Rename(_, path) => path,
+                _ => continue,
+            };
+            if let Ok(path) = Utf8PathBuf::try_from(path) {
+                if self.files.contains(&path) {
+                    eprintln!("{}change detected in {}...", Emoji::Sparkle, &path);
+                    return Ok(());
+                }
+            }
+        }
+        Err(anyhow!("Rover stopped receiving changes to the supergraph's files.").into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::command::supergraph::resolve_supergraph_yaml;
     use crate::options::ProfileOpt;
     use crate::utils::client::ClientBuilder;
+    use crate::utils::progress::ProgressReporter;
     use assert_fs::TempDir;
     use houston as houston_config;
     use houston_config::Config;
@@ -219,11 +1088,153 @@ mod tests {
             get_studio_config(),
             &ProfileOpt {
                 profile_name: "profile".to_string()
-            }
+            },
+            &ResolveOpts::default(),
         )
         .is_err())
     }
 
+    #[test]
+    fn it_attributes_resolution_errors_to_their_source() {
+        let raw_yaml = r#"subgraphs:
+  films:
+    routing_url: https://films.example.com
+    schema:
+      file: ./films-do-not-exist.graphql
+  people:
+    schema:
+      subgraph_url: http://127.0.0.1:1/graphql"#;
+        let tmp_home = TempDir::new().unwrap();
+        let config_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf())
+            .unwrap()
+            .join("config.yaml");
+        fs::write(&config_path, raw_yaml).unwrap();
+        let error = resolve_supergraph_yaml(
+            &FileDescriptorType::File(config_path),
+            get_studio_config(),
+            &ProfileOpt {
+                profile_name: "profile".to_string(),
+            },
+            &ResolveOpts {
+                concurrency: 1,
+                ..ResolveOpts::default()
+            },
+        )
+        .unwrap_err();
+        let error_json = error.get_internal_error_json().to_string();
+        assert!(error_json.contains("'films' subgraph from the file ./films-do-not-exist.graphql"));
+        assert!(error_json
+            .contains("'people' subgraph from its subgraph_url http://127.0.0.1:1/graphql"));
+    }
+
+    #[test]
+    fn it_rejects_remote_sources_offline() {
+        let raw_yaml = r#"subgraphs:
+  films:
+    routing_url: https://films.example.com
+    schema:
+      file: ./films.graphql
+  people:
+    schema:
+      subgraph_url: http://127.0.0.1:1/graphql"#;
+        let tmp_home = TempDir::new().unwrap();
+        let config_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf())
+            .unwrap()
+            .join("config.yaml");
+        fs::write(&config_path, raw_yaml).unwrap();
+        let error = resolve_supergraph_yaml(
+            &FileDescriptorType::File(config_path),
+            get_studio_config(),
+            &ProfileOpt {
+                profile_name: "profile".to_string(),
+            },
+            &ResolveOpts {
+                offline: true,
+                ..ResolveOpts::default()
+            },
+        )
+        .unwrap_err();
+        let message = error.message();
+        assert!(message.contains("'people' from its subgraph_url http://127.0.0.1:1/graphql"));
+        assert!(!message.contains("films"));
+    }
+
+    #[test]
+    fn it_detects_and_overrides_the_federation_version() {
+        let raw_yaml = r#"subgraphs:
+  films:
+    routing_url: https://films.example.com
+    schema:
+      file: ./films.graphql
+  people:
+    routing_url: https://people.example.com
+    schema:
+      file: ./people.graphql"#;
+        let tmp_home = TempDir::new().unwrap();
+        let mut config_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        config_path.push("config.yaml");
+        fs::write(&config_path, raw_yaml).unwrap();
+        let films_path = config_path.parent().unwrap().join("films.graphql");
+        let people_path = config_path.parent().unwrap().join("people.graphql");
+        fs::write(
+            films_path,
+            "extend schema @link(url: \"https://specs.apollo.dev/federation/v2.0\", import: [\"@key\"]) @link(url: \"https://specs.apollo.dev/link/v1.0\")\ntype Query { films: [String] }",
+        )
+        .unwrap();
+        fs::write(people_path, "type Query { people: [String] }").unwrap();
+        let profile = ProfileOpt {
+            profile_name: "profile".to_string(),
+        };
+
+        let (progress, events) = ProgressReporter::channel();
+        let (supergraph_config, _) = resolve_supergraph_yaml(
+            &FileDescriptorType::File(config_path.clone()),
+            get_studio_config().with_progress(progress),
+            &profile,
+            &ResolveOpts::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            supergraph_config
+                .get_federation_version()
+                .map(|v| v.get_tarball_version()),
+            Some("latest-2".to_string())
+        );
+        assert!(events.try_iter().any(|event| event
+            == ProgressEvent::FederationVersionDetected {
+                federation_version: "2".to_string(),
+                subgraphs: vec!["films".to_string()],
+            }));
+
+        let (supergraph_config, _) = resolve_supergraph_yaml(
+            &FileDescriptorType::File(config_path.clone()),
+            get_studio_config(),
+            &profile,
+            &ResolveOpts {
+                federation_version: Some(parse_federation_version("=2.3.1").unwrap()),
+                ..ResolveOpts::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            supergraph_config
+                .get_federation_version()
+                .map(|v| v.get_tarball_version()),
+            Some("v2.3.1".to_string())
+        );
+
+        assert!(resolve_supergraph_yaml(
+            &FileDescriptorType::File(config_path),
+            get_studio_config(),
+            &profile,
+            &ResolveOpts {
+                federation_version: Some(parse_federation_version("1").unwrap()),
+                ..ResolveOpts::default()
+            },
+        )
+        .is_err());
+    }
+
     #[test]
     fn it_can_get_subgraph_definitions_from_fs() {
         let raw_good_yaml = r#"subgraphs:
@@ -249,11 +1260,311 @@ mod tests {
             get_studio_config(),
             &ProfileOpt {
                 profile_name: "profile".to_string()
-            }
+            },
+            &ResolveOpts::default(),
         )
         .is_ok())
     }
 
+    #[test]
+    fn it_resolves_subgraphs_from_a_glob() {
+        let raw_yaml = r#"subgraphs_glob:
+  pattern: ./services/*/schema.graphql
+  routing_url: http://localhost:4000/{name}/graphql"#;
+        let tmp_home = TempDir::new().unwrap();
+        let mut config_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        config_path.push("config.yaml");
+        fs::write(&config_path, raw_yaml).unwrap();
+        let tmp_dir = config_path.parent().unwrap().to_path_buf();
+        for name in ["films", "people"] {
+            let service_dir = tmp_dir.join("services").join(name);
+            fs::create_dir_all(&service_dir).unwrap();
+            fs::write(
+                service_dir.join("schema.graphql"),
+                format!("type Query {{ {}: [String] }}", name),
+            )
+            .unwrap();
+        }
+
+        let (supergraph_config, _) = resolve_supergraph_yaml(
+            &FileDescriptorType::File(config_path),
+            get_studio_config(),
+            &ProfileOpt {
+                profile_name: "profile".to_string(),
+            },
+            &ResolveOpts::default(),
+        )
+        .unwrap();
+        let subgraphs = supergraph_config.get_subgraph_definitions().unwrap();
+        assert_eq!(subgraphs.len(), 2);
+        for name in ["films", "people"] {
+            let subgraph = subgraphs
+                .iter()
+                .find(|subgraph| subgraph.name == name)
+                .unwrap();
+            assert_eq!(subgraph.sdl, format!("type Query {{ {}: [String] }}", name));
+            assert_eq!(
+                subgraph.url,
+                format!("http://localhost:4000/{}/graphql", name)
+            );
+        }
+    }
+
+    #[test]
+    fn it_overrides_subgraphs_with_local_files() {
+        let raw_yaml = r#"subgraphs:
+  films:
+    routing_url: https://films.example.com
+    schema:
+      file: ./films.graphql
+  people:
+    routing_url: https://people.example.com
+    schema:
+      subgraph_url: http://127.0.0.1:1/graphql"#;
+        let tmp_home = TempDir::new().unwrap();
+        let mut config_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        config_path.push("config.yaml");
+        fs::write(&config_path, raw_yaml).unwrap();
+        let tmp_dir = config_path.parent().unwrap().to_path_buf();
+        fs::write(
+            tmp_dir.join("films.graphql"),
+            "type Query { films: [String] }",
+        )
+        .unwrap();
+        let people_path = tmp_dir.join("local-people.graphql");
+        fs::write(&people_path, "type Query { people: [String] }").unwrap();
+        let profile = ProfileOpt {
+            profile_name: "profile".to_string(),
+        };
+
+        let subgraph_override =
+            parse_subgraph_override(&format!("people={}", people_path)).unwrap();
+        let (supergraph_config, _) = resolve_supergraph_yaml(
+            &FileDescriptorType::File(config_path.clone()),
+            get_studio_config(),
+            &profile,
+            &ResolveOpts {
+                subgraph_overrides: vec![subgraph_override],
+                ..ResolveOpts::default()
+            },
+        )
+        .unwrap();
+        let people = supergraph_config
+            .get_subgraph_definitions()
+            .unwrap()
+            .into_iter()
+            .find(|subgraph| subgraph.name == "people")
+            .unwrap();
+        assert_eq!(people.sdl, "type Query { people: [String] }");
+        assert_eq!(people.url, "https://people.example.com");
+
+        let message = resolve_supergraph_yaml(
+            &FileDescriptorType::File(config_path),
+            get_studio_config(),
+            &profile,
+            &ResolveOpts {
+                subgraph_overrides: vec![
+                    parse_subgraph_override("planets=./planets.graphql").unwrap()
+                ],
+                ..ResolveOpts::default()
+            },
+        )
+        .unwrap_err()
+        .message();
+        assert!(message.contains("Can't override the 'planets' subgraph"));
+        assert!(parse_subgraph_override("./people.graphql").is_err());
+    }
+
+    #[test]
+    fn it_reports_unready_subgraphs_before_resolving_them() {
+        let raw_yaml = r#"subgraphs:
+  films:
+    routing_url: http://127.0.0.1:1/graphql
+    schema:
+      file: ./films.graphql
+  people:
+    schema:
+      subgraph_url: http://127.0.0.1:1/people
+  planets:
+    schema:
+      file: ./planets.graphql"#;
+        let tmp_home = TempDir::new().unwrap();
+        let mut config_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        config_path.push("config.yaml");
+        fs::write(&config_path, raw_yaml).unwrap();
+
+        let message = resolve_supergraph_yaml(
+            &FileDescriptorType::File(config_path),
+            get_studio_config(),
+            &ProfileOpt {
+                profile_name: "profile".to_string(),
+            },
+            &ResolveOpts {
+                preflight: true,
+                ..ResolveOpts::default()
+            },
+        )
+        .unwrap_err()
+        .message();
+        assert!(message.contains("2 of 3 subgraphs aren't ready to be composed"));
+        assert!(message
+            .contains("films (unreachable): http://127.0.0.1:1/graphql could not be reached"));
+        assert!(message.contains("people (unreachable): http://127.0.0.1:1/people"));
+        // a subgraph without an endpoint is resolved as usual
+        assert!(!message.contains("planets"));
+    }
+
+    #[test]
+    fn it_takes_the_contract_filter_out_of_the_config() {
+        let raw_yaml = r#"filter:
+  exclude_tags: [internal]
+subgraphs:
+  films:
+    routing_url: https://films.example.com
+    schema:
+      sdl: "type Query { films: [String] }""#;
+        let (supergraph_config, extensions) =
+            parse_supergraph_yaml(raw_yaml, &FileDescriptorType::Stdin).unwrap();
+        assert_eq!(supergraph_config.into_iter().count(), 1);
+        assert_eq!(
+            extensions.contract_filter.unwrap().exclude_tags,
+            vec!["internal".to_string()]
+        );
+
+        let message = parse_supergraph_yaml(
+            &raw_yaml.replace("exclude_tags", "exclude"),
+            &FileDescriptorType::Stdin,
+        )
+        .unwrap_err()
+        .message();
+        assert!(message.contains("The `filter` in 'stdin' is invalid"));
+    }
+
+    #[test]
+    fn it_takes_the_composition_binary_out_of_the_config() {
+        let raw_yaml = r#"composition_binary: ./bin/supergraph
+subgraphs:
+  films:
+    routing_url: https://films.example.com
+    schema:
+      sdl: "type Query { films: [String] }""#;
+        let (supergraph_config, extensions) = parse_supergraph_yaml(
+            raw_yaml,
+            &FileDescriptorType::File("./config/supergraph.yaml".into()),
+        )
+        .unwrap();
+        assert_eq!(supergraph_config.into_iter().count(), 1);
+        // the binary is relative to the config, like `file` sources
+        assert_eq!(
+            extensions.composition_binary.unwrap(),
+            Utf8PathBuf::from("./config/./bin/supergraph")
+        );
+        assert_eq!(
+            get_plugin_version(Utf8Path::new("/home/me/.rover/bin/supergraph-v2.3.1")),
+            "v2.3.1"
+        );
+
+        let message = get_custom_composition_binary(Utf8Path::new("./bin/missing-supergraph"))
+            .unwrap_err()
+            .message();
+        assert!(message.contains("There is no composition plugin at ./bin/missing-supergraph"));
+    }
+
+    #[test]
+    fn it_only_leaves_out_unreachable_subgraphs_when_allowed() {
+        let raw_yaml = r#"subgraphs:
+  films:
+    routing_url: https://films.example.com
+    schema:
+      file: ./films.graphql
+  people:
+    routing_url: https://people.example.com
+    schema:
+      subgraph_url: http://127.0.0.1:1/graphql"#;
+        let tmp_home = TempDir::new().unwrap();
+        let mut config_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        config_path.push("config.yaml");
+        fs::write(&config_path, raw_yaml).unwrap();
+        let films_path = config_path.parent().unwrap().join("films.graphql");
+        fs::write(films_path, "there is something here").unwrap();
+        let profile = ProfileOpt {
+            profile_name: "profile".to_string(),
+        };
+
+        assert!(resolve_supergraph_yaml(
+            &FileDescriptorType::File(config_path.clone()),
+            get_studio_config(),
+            &profile,
+            &ResolveOpts::default(),
+        )
+        .is_err());
+
+        let (supergraph_config, report) = resolve_supergraph_yaml(
+            &FileDescriptorType::File(config_path),
+            get_studio_config(),
+            &profile,
+            &ResolveOpts {
+                allow_missing_subgraphs: true,
+                ..ResolveOpts::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            supergraph_config.get_subgraph_definitions().unwrap().len(),
+            1
+        );
+        assert_eq!(report.missing_subgraphs.len(), 1);
+        assert_eq!(report.missing_subgraphs[0].name, "people");
+        // the unreachable subgraph is still timed, so slow sources can be found
+        let metrics: Vec<(&str, &str, Option<usize>)> = report
+            .subgraph_metrics
+            .iter()
+            .map(|metrics| {
+                (
+                    metrics.name.as_str(),
+                    metrics.source.as_str(),
+                    metrics.sdl_bytes,
+                )
+            })
+            .collect();
+        assert_eq!(
+            metrics,
+            vec![
+                ("films", "file", Some(23)),
+                ("people", "subgraph_url", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn it_watches_the_config_and_file_sources() {
+        let raw_yaml = r#"subgraphs:
+  films:
+    routing_url: https://films.example.com
+    schema:
+      file: ./films.graphql
+  people:
+    routing_url: https://people.example.com
+    schema:
+      subgraph_url: http://127.0.0.1:1/graphql"#;
+        let tmp_home = TempDir::new().unwrap();
+        let tmp_dir = Utf8PathBuf::try_from(tmp_home.path().to_path_buf())
+            .unwrap()
+            .canonicalize_utf8()
+            .unwrap();
+        let config_path = tmp_dir.join("config.yaml");
+        fs::write(&config_path, raw_yaml).unwrap();
+        fs::write(tmp_dir.join("films.graphql"), "there is something here").unwrap();
+
+        let sources = WatchedSources::new(&config_path).unwrap();
+        assert_eq!(
+            sources.files,
+            BTreeSet::from([config_path, tmp_dir.join("films.graphql")])
+        );
+        assert!(sources.polls_subgraphs);
+        assert_eq!(sources.get_dirs(), BTreeSet::from([tmp_dir]));
+    }
+
     #[test]
     fn it_can_compute_relative_schema_paths() {
         let raw_good_yaml = r#"subgraphs:
@@ -283,8 +1594,10 @@ mod tests {
             &ProfileOpt {
                 profile_name: "profile".to_string(),
             },
+            &ResolveOpts::default(),
         )
         .unwrap()
+        .0
         .get_subgraph_definitions()
         .unwrap();
         let film_subgraph = subgraph_definitions.get(0).unwrap();
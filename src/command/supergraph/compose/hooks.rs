@@ -0,0 +1,131 @@
+use rover_std::Emoji;
+
+use crate::command::supergraph::compose::do_compose::RecompositionEvent;
+use crate::utils::hooks;
+use crate::RoverResult;
+
+/// The commands `supergraph compose --watch` runs after each recomposition,
+/// i.e. to reload a local router or to post a webhook
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RecompositionHooks {
+    /// the command passed to `--on-success`
+    pub(crate) on_success: Option<String>,
+    /// the command passed to `--on-error`
+    pub(crate) on_error: Option<String>,
+}
+
+impl RecompositionHooks {
+    /// Runs the hook that matches a recomposition, if there is one. A hook that fails
+    /// is reported, but doesn't stop Rover from watching for changes
+    pub(crate) fn run(&self, event: &RecompositionEvent) {
+        let command = match event {
+            RecompositionEvent::CompositionSucceeded { .. } => &self.on_success,
+            RecompositionEvent::CompositionFailed { .. } => &self.on_error,
+        };
+        if let Some(command) = command {
+            if let Err(e) = run_hook(command, event) {
+                eprintln!("{}{}", Emoji::Warn, e.message());
+            }
+        }
+    }
+}
+
+/// The environment variables a hook receives the recomposition in
+fn get_hook_env(event: &RecompositionEvent) -> Vec<(&'static str, String)> {
+    let mut env = vec![(
+        "ROVER_COMPOSITION_EVENT",
+        serde_json::to_string(event).unwrap_or_default(),
+    )];
+    match event {
+        RecompositionEvent::CompositionSucceeded {
+            output,
+            changed,
+            federation_version,
+            subgraphs,
+            hints,
+            missing_subgraphs,
+            ..
+        } => {
+            env.push(("ROVER_COMPOSITION_STATUS", "success".to_string()));
+            env.push(("ROVER_SUPERGRAPH_PATH", output.to_string()));
+            env.push(("ROVER_SUPERGRAPH_CHANGED", changed.to_string()));
+            if let Some(federation_version) = federation_version {
+                env.push(("ROVER_FEDERATION_VERSION", federation_version.clone()));
+            }
+            env.push(("ROVER_SUBGRAPH_COUNT", subgraphs.to_string()));
+            env.push(("ROVER_HINT_COUNT", hints.to_string()));
+            env.push(("ROVER_MISSING_SUBGRAPHS", missing_subgraphs.join(",")));
+        }
+        RecompositionEvent::CompositionFailed { error, .. } => {
+            env.push(("ROVER_COMPOSITION_STATUS", "failure".to_string()));
+            let message = error
+                .get("message")
+                .and_then(|message| message.as_str())
+                .unwrap_or_default();
+            env.push(("ROVER_COMPOSITION_ERROR", message.to_string()));
+        }
+    }
+    env
+}
+
+fn run_hook(command: &str, event: &RecompositionEvent) -> RoverResult<()> {
+    hooks::run_hook(command, "recomposition hook", &get_hook_env(event), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use super::*;
+
+    fn get_var(env: &[(&'static str, String)], name: &str) -> Option<String> {
+        env.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.clone())
+    }
+
+    #[test]
+    fn it_passes_the_recomposition_to_hooks() {
+        let env = get_hook_env(&RecompositionEvent::CompositionSucceeded {
+            at: Utc::now(),
+            output: "supergraph.graphql".into(),
+            changed: true,
+            federation_version: Some("2.3.1".to_string()),
+            subgraphs: 3,
+            hints: 1,
+            missing_subgraphs: vec!["reviews".to_string(), "users".to_string()],
+        });
+        assert_eq!(
+            get_var(&env, "ROVER_COMPOSITION_STATUS").unwrap(),
+            "success"
+        );
+        assert_eq!(
+            get_var(&env, "ROVER_SUPERGRAPH_PATH").unwrap(),
+            "supergraph.graphql"
+        );
+        assert_eq!(get_var(&env, "ROVER_SUPERGRAPH_CHANGED").unwrap(), "true");
+        assert_eq!(get_var(&env, "ROVER_SUBGRAPH_COUNT").unwrap(), "3");
+        assert_eq!(
+            get_var(&env, "ROVER_MISSING_SUBGRAPHS").unwrap(),
+            "reviews,users"
+        );
+        assert!(get_var(&env, "ROVER_COMPOSITION_EVENT")
+            .unwrap()
+            .contains(r#""event":"composition_succeeded""#));
+
+        let env = get_hook_env(&RecompositionEvent::CompositionFailed {
+            at: Utc::now(),
+            error: json!({ "message": "could not compose", "code": "E029" }),
+        });
+        assert_eq!(
+            get_var(&env, "ROVER_COMPOSITION_STATUS").unwrap(),
+            "failure"
+        );
+        assert_eq!(
+            get_var(&env, "ROVER_COMPOSITION_ERROR").unwrap(),
+            "could not compose"
+        );
+        assert_eq!(get_var(&env, "ROVER_SUPERGRAPH_PATH"), None);
+    }
+}
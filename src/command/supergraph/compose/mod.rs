@@ -10,11 +10,217 @@ mod do_compose;
 #[cfg(feature = "composition-js")]
 pub(crate) use do_compose::Compose;
 
+#[cfg(feature = "composition-js")]
+mod bundle;
+
+#[cfg(feature = "composition-js")]
+mod hooks;
+
 use apollo_federation_types::build::BuildHint;
+use serde::Serialize;
+
+use crate::utils::composition_cache::CompositionCacheStatus;
+
+#[cfg(feature = "composition-js")]
+use anyhow::anyhow;
+
+#[cfg(feature = "composition-js")]
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct CompositionOutput {
     pub supergraph_sdl: String,
     pub hints: Vec<BuildHint>,
     pub federation_version: Option<String>,
+    /// subgraphs left out because `--allow-missing-subgraphs` was passed and their
+    /// schemas couldn't be fetched, which makes the supergraph partial
+    pub missing_subgraphs: Vec<MissingSubgraph>,
+    /// how long each subgraph's schema took to resolve, empty if the supergraph
+    /// wasn't composed from a supergraph config
+    pub subgraph_metrics: Vec<SubgraphMetrics>,
+    /// whether the supergraph was reused from the composition cache, `None` if it isn't used
+    pub cache: Option<CompositionCacheStatus>,
+}
+
+/// A subgraph whose remote schema couldn't be resolved
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct MissingSubgraph {
+    pub name: String,
+    pub reason: String,
+}
+
+/// How a subgraph's schema was resolved, for finding the subgraphs that slow down composition
+#[derive(Debug, Clone, Serialize, Eq, PartialEq)]
+pub struct SubgraphMetrics {
+    pub name: String,
+    /// `file`, `subgraph_url`, `graphref`, or `sdl`
+    pub source: String,
+    pub resolution_time_ms: u64,
+    /// the size of the resolved schema, or `None` if it couldn't be resolved
+    pub sdl_bytes: Option<usize>,
+}
+
+/// The machine-readable code of a composition hint, i.e. `INCONSISTENT_DESCRIPTION`,
+/// which hints from older composition plugins don't have
+pub(crate) fn get_hint_code(hint: &BuildHint) -> Option<String> {
+    serde_json::to_value(hint).ok().and_then(|hint| {
+        hint.get("code")
+            .and_then(|code| code.as_str().map(String::from))
+    })
+}
+
+/// The subgraphs a composition hint is about, from the locations composition attached to it
+pub(crate) fn get_hint_subgraphs(hint: &BuildHint) -> Vec<String> {
+    let hint = match serde_json::to_value(hint) {
+        Ok(hint) => hint,
+        Err(_) => return Vec::new(),
+    };
+    let mut subgraphs: Vec<String> = Vec::new();
+    for node in hint
+        .get("nodes")
+        .and_then(|nodes| nodes.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if let Some(subgraph) = node.get("subgraph").and_then(|subgraph| subgraph.as_str()) {
+            if !subgraphs.iter().any(|known| known == subgraph) {
+                subgraphs.push(subgraph.to_string());
+            }
+        }
+    }
+    subgraphs
+}
+
+/// The composition hints that are left out of the output, and the ones that fail composition
+#[cfg(feature = "composition-js")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct HintPolicy {
+    /// hint codes passed to `--ignore-hint`
+    pub(crate) ignored: Vec<String>,
+    /// hint codes passed to `--fail-on-hint`, where `all` matches every hint
+    pub(crate) failing: Vec<String>,
+}
+
+#[cfg(feature = "composition-js")]
+impl HintPolicy {
+    /// Leaves the ignored hints out of the output, and fails if any hint that's left is failing
+    pub(crate) fn apply(&self, output: &mut CompositionOutput) -> RoverResult<()> {
+        let is_ignored = |hint: &BuildHint| match get_hint_code(hint) {
+            Some(code) => self
+                .ignored
+                .iter()
+                .any(|ignored| ignored.eq_ignore_ascii_case(&code)),
+            None => false,
+        };
+        // hints without a code only fail with `all`
+        let is_failing = |hint: &BuildHint| {
+            let code = get_hint_code(hint);
+            self.failing.iter().any(|failing| {
+                failing.eq_ignore_ascii_case("all")
+                    || matches!(&code, Some(code) if failing.eq_ignore_ascii_case(code))
+            })
+        };
+        output.hints.retain(|hint| !is_ignored(hint));
+
+        let failing_hints: Vec<String> = output
+            .hints
+            .iter()
+            .filter(|hint| is_failing(hint))
+            .map(|hint| match get_hint_code(hint) {
+                Some(code) => format!("[{}] {}", code, hint.message),
+                None => hint.message.clone(),
+            })
+            .collect();
+        if failing_hints.is_empty() {
+            return Ok(());
+        }
+        let mut err = RoverError::new(anyhow!(
+            "Composition produced {} that `--fail-on-hint` doesn't allow:\n{}",
+            match failing_hints.len() {
+                1 => "1 hint".to_string(),
+                hints => format!("{} hints", hints),
+            },
+            failing_hints.join("\n")
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(
+            "Change the subgraphs the hints are about, or pass `--ignore-hint <CODE>` to allow a kind of hint.".to_string(),
+        ));
+        Err(err)
+    }
+}
+
+#[cfg(all(test, feature = "composition-js"))]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn hint(message: &str, code: Option<&str>) -> BuildHint {
+        serde_json::from_value(json!({ "message": message, "code": code })).unwrap()
+    }
+
+    fn output() -> CompositionOutput {
+        CompositionOutput {
+            supergraph_sdl: "type Query { a: String }".to_string(),
+            hints: vec![
+                hint(
+                    "a is described differently",
+                    Some("INCONSISTENT_DESCRIPTION"),
+                ),
+                hint(
+                    "b is not shareable",
+                    Some("INCONSISTENT_NON_REPEATABLE_DIRECTIVE_ARGUMENTS"),
+                ),
+                hint("an old hint", None),
+            ],
+            federation_version: Some("2.3.1".to_string()),
+            missing_subgraphs: Vec::new(),
+            subgraph_metrics: Vec::new(),
+            cache: None,
+        }
+    }
+
+    #[test]
+    fn it_ignores_and_fails_on_hints_by_code() {
+        let mut composition_output = output();
+        HintPolicy {
+            ignored: vec!["inconsistent_description".to_string()],
+            failing: vec!["INCONSISTENT_DESCRIPTION".to_string()],
+        }
+        .apply(&mut composition_output)
+        .unwrap();
+        assert_eq!(composition_output.hints.len(), 2);
+        assert_eq!(
+            get_hint_code(&composition_output.hints[0]),
+            Some("INCONSISTENT_NON_REPEATABLE_DIRECTIVE_ARGUMENTS".to_string())
+        );
+
+        let message = HintPolicy {
+            ignored: vec!["INCONSISTENT_DESCRIPTION".to_string()],
+            failing: vec!["all".to_string()],
+        }
+        .apply(&mut output())
+        .unwrap_err()
+        .message();
+        assert!(message.contains("Composition produced 2 hints"));
+        assert!(message
+            .contains("[INCONSISTENT_NON_REPEATABLE_DIRECTIVE_ARGUMENTS] b is not shareable"));
+        assert!(message.contains("an old hint"));
+    }
+
+    #[test]
+    fn it_gets_the_subgraphs_a_hint_is_about() {
+        let hint: BuildHint = serde_json::from_value(json!({
+            "message": "a is described differently",
+            "code": "INCONSISTENT_DESCRIPTION",
+            "nodes": [
+                { "subgraph": "products" },
+                { "subgraph": "reviews" },
+                { "subgraph": "products" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(get_hint_subgraphs(&hint), vec!["products", "reviews"]);
+        assert!(get_hint_subgraphs(&output().hints[2]).is_empty());
+    }
 }
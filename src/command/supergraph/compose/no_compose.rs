@@ -3,7 +3,7 @@ use camino::Utf8PathBuf;
 use clap::Parser;
 use serde::Serialize;
 
-use crate::options::ProfileOpt;
+use crate::options::{OutputOpts, ProfileOpt};
 use crate::utils::client::StudioClientConfig;
 use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
 
@@ -21,10 +21,15 @@ pub struct Compose {
 }
 
 impl Compose {
+    pub fn is_offline(&self) -> bool {
+        false
+    }
+
     pub fn run(
         &self,
         _override_install_path: Option<Utf8PathBuf>,
         _client_config: StudioClientConfig,
+        _output_opts: &OutputOpts,
     ) -> RoverResult<RoverOutput> {
         let mut err = RoverError::new(anyhow!(
             "This version of Rover does not support this command."
@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+
+use apollo_federation_types::config::{SchemaSource, SubgraphConfig};
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+
+use rover_client::blocking::StudioClient;
+use rover_client::operations::subgraph::fetch::{self, SubgraphFetchInput};
+use rover_client::operations::subgraph::list::{self, SubgraphInfo, SubgraphListInput};
+use rover_client::shared::SdlType;
+use rover_std::{Fs, Style};
+
+use crate::options::{GraphRefOpt, OutputOpts, ProfileOpt, RoverOutputDestination};
+use crate::utils::{client::StudioClientConfig, progress::ProgressEvent};
+use crate::{RoverOutput, RoverResult};
+
+/// Where the generated config's subgraphs get their schemas from
+#[derive(ValueEnum, Debug, Clone, Copy, Serialize, Eq, PartialEq)]
+pub enum ResolveMode {
+    /// each subgraph's schema is fetched from Apollo Studio whenever the supergraph is composed
+    GraphRef,
+    /// each subgraph's schema is fetched now and written to a file the config points to
+    Files,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Fetch {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// How the generated config sources each subgraph's schema
+    #[arg(long, value_enum, default_value_t = ResolveMode::GraphRef)]
+    resolve: ResolveMode,
+
+    /// With `--resolve files`, the directory to write subgraph schemas to, relative to
+    /// the directory of the `--output` file, or the current directory without one
+    #[arg(long = "schemas-dir", value_name = "DIR", default_value = "subgraphs")]
+    schemas_dir: Utf8PathBuf,
+}
+
+/// The layout of the generated supergraph config
+#[derive(Debug, Serialize)]
+struct GeneratedConfig {
+    subgraphs: BTreeMap<String, SubgraphConfig>,
+}
+
+impl Fetch {
+    pub fn run(
+        &self,
+        client_config: StudioClientConfig,
+        output_opts: &OutputOpts,
+    ) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = &self.graph.graph_ref;
+        eprintln!(
+            "Generating a supergraph config for {} using credentials from the {} profile.",
+            Style::Link.paint(graph_ref.to_string()),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let list_response = list::run(
+            SubgraphListInput {
+                graph_ref: graph_ref.clone(),
+            },
+            &client,
+        )?;
+
+        let config_dir = get_config_dir(output_opts);
+        let mut subgraphs = BTreeMap::new();
+        let mut schema_files = Vec::new();
+        for subgraph in &list_response.subgraphs {
+            let subgraph_config = match self.resolve {
+                ResolveMode::GraphRef => SubgraphConfig {
+                    routing_url: subgraph.url.clone(),
+                    schema: SchemaSource::Subgraph {
+                        graphref: graph_ref.to_string(),
+                        subgraph: subgraph.name.clone(),
+                    },
+                },
+                ResolveMode::Files => {
+                    let (subgraph_config, schema_file) =
+                        self.fetch_to_file(subgraph, &config_dir, &client_config, &client)?;
+                    schema_files.push(schema_file);
+                    subgraph_config
+                }
+            };
+            subgraphs.insert(subgraph.name.clone(), subgraph_config);
+        }
+        if !schema_files.is_empty() {
+            eprintln!(
+                "Wrote the schemas of {} subgraphs to {}",
+                schema_files.len(),
+                Style::Path.paint(config_dir.join(&self.schemas_dir))
+            );
+        }
+
+        let config = format!(
+            "# generated from {} by `rover supergraph config fetch`\n{}",
+            graph_ref,
+            serde_yaml::to_string(&GeneratedConfig { subgraphs })?
+        );
+        Ok(RoverOutput::SupergraphConfig {
+            config,
+            schema_files,
+        })
+    }
+
+    /// Fetches the schema of a subgraph and writes it to `--schemas-dir`,
+    /// returning the subgraph's config and the path of the file written
+    fn fetch_to_file(
+        &self,
+        subgraph: &SubgraphInfo,
+        config_dir: &Utf8Path,
+        client_config: &StudioClientConfig,
+        client: &StudioClient,
+    ) -> RoverResult<(SubgraphConfig, Utf8PathBuf)> {
+        let graph_ref = &self.graph.graph_ref;
+        let progress = client_config.get_progress();
+        progress.emit(ProgressEvent::SchemaFetchStarted {
+            graph_ref: graph_ref.to_string(),
+            subgraph: Some(subgraph.name.clone()),
+            hash: None,
+            supergraph: false,
+            profile: Some(self.profile.profile_name.clone()),
+        });
+        let fetch_response = fetch::run(
+            SubgraphFetchInput {
+                graph_ref: graph_ref.clone(),
+                subgraph_name: subgraph.name.clone(),
+            },
+            client,
+        )?;
+        progress.emit(ProgressEvent::SchemaFetched {
+            graph_ref: graph_ref.to_string(),
+            subgraph: Some(subgraph.name.clone()),
+        });
+
+        let routing_url = match fetch_response.sdl.r#type {
+            SdlType::Subgraph {
+                routing_url: Some(routing_url),
+            } => Some(routing_url),
+            _ => subgraph.url.clone(),
+        };
+        if routing_url.is_none() {
+            eprintln!(
+                "{} The '{}' subgraph has no routing URL. Add a `routing_url` for it to the config before composing.",
+                Style::WarningPrefix.paint("WARN:"),
+                &subgraph.name
+            );
+        }
+
+        let file = self.schemas_dir.join(format!("{}.graphql", &subgraph.name));
+        let schema_file = config_dir.join(&file);
+        if let Some(parent) = schema_file.parent() {
+            Fs::create_dir_all(parent)?;
+        }
+        Fs::write_file(&schema_file, &fetch_response.sdl.contents)?;
+        Ok((
+            SubgraphConfig {
+                routing_url,
+                schema: SchemaSource::File { file },
+            },
+            schema_file,
+        ))
+    }
+}
+
+/// The directory the config's `file` paths are relative to, which is
+/// the directory the config is written to
+fn get_config_dir(output_opts: &OutputOpts) -> Utf8PathBuf {
+    match output_opts.get_format_and_strategy() {
+        (_, RoverOutputDestination::File(path)) => match path.parent() {
+            Some(parent) if !parent.as_str().is_empty() => parent.to_path_buf(),
+            _ => Utf8PathBuf::from("."),
+        },
+        (_, RoverOutputDestination::Stdout) => Utf8PathBuf::from("."),
+    }
+}
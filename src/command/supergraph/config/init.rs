@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use apollo_federation_types::config::{FederationVersion, SchemaSource, SubgraphConfig};
+use atty::Stream;
+use camino::Utf8PathBuf;
+use clap::Parser;
+use console::Term;
+use dialoguer::{Input, Select};
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+
+use rover_client::operations::subgraph::list::{self, SubgraphListInput};
+use rover_client::shared::GraphRef;
+use rover_std::Style;
+
+use crate::options::ProfileOpt;
+use crate::utils::{client::StudioClientConfig, parsers::parse_federation_version};
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Init {
+    /// Start from the subgraphs published to this variant in Apollo Studio, i.e. `my-graph@current`,
+    /// instead of prompting for each subgraph
+    #[arg(long = "from-graph-ref", value_name = "GRAPH_REF")]
+    #[serde(skip_serializing)]
+    from_graph_ref: Option<GraphRef>,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The federation version to compose with, i.e. `2` or `=2.3.1`.
+    /// Without it, you're prompted for one
+    #[arg(long = "federation-version", value_name = "VERSION", value_parser = parse_federation_version)]
+    #[serde(skip_serializing)]
+    federation_version: Option<FederationVersion>,
+}
+
+/// The kinds of schema source a prompted subgraph can have
+const SOURCES: [&str; 3] = [
+    "file: a schema file on this machine",
+    "subgraph_url: introspect a running subgraph",
+    "graphref: a subgraph published to Apollo Studio",
+];
+
+impl Init {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let subgraphs = match &self.from_graph_ref {
+            Some(graph_ref) => self.get_published_subgraphs(graph_ref, &client_config)?,
+            None => {
+                if !atty::is(Stream::Stderr) {
+                    let mut err = RoverError::new(anyhow!(
+                        "`rover supergraph config init` prompts for each subgraph, which it can't do when not attached to a TTY."
+                    ));
+                    err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                        "Pass `--from-graph-ref <GRAPH_REF>` to start from the subgraphs of a graph in Apollo Studio instead.".to_string(),
+                    ));
+                    return Err(err);
+                }
+                prompt_for_subgraphs()?
+            }
+        };
+        let federation_version = match &self.federation_version {
+            Some(federation_version) => federation_version.clone(),
+            None if atty::is(Stream::Stderr) => prompt_for_federation_version()?,
+            None => FederationVersion::LatestFedTwo,
+        };
+
+        Ok(RoverOutput::SupergraphConfig {
+            config: render_config(subgraphs, &federation_version)?,
+            schema_files: Vec::new(),
+        })
+    }
+
+    /// Every subgraph published to the variant, with a `graphref` source
+    fn get_published_subgraphs(
+        &self,
+        graph_ref: &GraphRef,
+        client_config: &StudioClientConfig,
+    ) -> RoverResult<BTreeMap<String, SubgraphConfig>> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        eprintln!(
+            "Listing the subgraphs of {} using credentials from the {} profile.",
+            Style::Link.paint(graph_ref.to_string()),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+        let list_response = list::run(
+            SubgraphListInput {
+                graph_ref: graph_ref.clone(),
+            },
+            &client,
+        )?;
+        Ok(list_response
+            .subgraphs
+            .into_iter()
+            .map(|subgraph| {
+                let subgraph_config = SubgraphConfig {
+                    routing_url: subgraph.url,
+                    schema: SchemaSource::Subgraph {
+                        graphref: graph_ref.to_string(),
+                        subgraph: subgraph.name.clone(),
+                    },
+                };
+                (subgraph.name, subgraph_config)
+            })
+            .collect())
+    }
+}
+
+/// Prompts for subgraphs until an empty name is entered
+fn prompt_for_subgraphs() -> RoverResult<BTreeMap<String, SubgraphConfig>> {
+    let mut subgraphs = BTreeMap::new();
+    loop {
+        let name: String = Input::new()
+            .with_prompt(if subgraphs.is_empty() {
+                "What is the name of the first subgraph?"
+            } else {
+                "What is the name of the next subgraph? (leave empty to finish)"
+            })
+            .allow_empty(!subgraphs.is_empty())
+            .interact_text()?;
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Ok(subgraphs);
+        }
+
+        let source = Select::new()
+            .with_prompt(format!(
+                "Where does the '{}' subgraph's schema come from?",
+                name
+            ))
+            .items(&SOURCES)
+            .default(0)
+            .interact_on_opt(&Term::stderr())?
+            .ok_or_else(|| RoverError::new(anyhow!("No schema source selected")))?;
+        let (schema, routing_url_required) = match source {
+            0 => {
+                let file: String = Input::new()
+                    .with_prompt("What is the path of the schema file, relative to the config?")
+                    .default(format!("./{}.graphql", name))
+                    .interact_text()?;
+                (
+                    SchemaSource::File {
+                        file: Utf8PathBuf::from(file),
+                    },
+                    true,
+                )
+            }
+            1 => {
+                let subgraph_url: String = Input::new()
+                    .with_prompt("What URL should the subgraph be introspected at?")
+                    .interact_text()?;
+                let subgraph_url = subgraph_url.parse().map_err(|_| {
+                    RoverError::new(anyhow!("'{}' is not a valid URL.", subgraph_url))
+                })?;
+                (SchemaSource::SubgraphIntrospection { subgraph_url }, false)
+            }
+            _ => {
+                let graph_ref: GraphRef = Input::<String>::new()
+                    .with_prompt(
+                        "What graph ref is the subgraph published to, i.e. my-graph@current?",
+                    )
+                    .interact_text()?
+                    .parse()?;
+                let subgraph: String = Input::new()
+                    .with_prompt("What is the name of the published subgraph?")
+                    .default(name.clone())
+                    .interact_text()?;
+                (
+                    SchemaSource::Subgraph {
+                        graphref: graph_ref.to_string(),
+                        subgraph,
+                    },
+                    false,
+                )
+            }
+        };
+
+        let routing_url: String = Input::new()
+            .with_prompt(if routing_url_required {
+                "What URL should the router send requests for this subgraph to?"
+            } else {
+                "What URL should the router send requests for this subgraph to? (leave empty to use the source's)"
+            })
+            .allow_empty(!routing_url_required)
+            .interact_text()?;
+        let routing_url = Some(routing_url.trim().to_string()).filter(|url| !url.is_empty());
+        subgraphs.insert(
+            name,
+            SubgraphConfig {
+                routing_url,
+                schema,
+            },
+        );
+    }
+}
+
+fn prompt_for_federation_version() -> RoverResult<FederationVersion> {
+    let selection = Select::new()
+        .with_prompt("Which federation version should the supergraph be composed with?")
+        .items(&["2", "1"])
+        .default(0)
+        .interact_on_opt(&Term::stderr())?;
+    match selection {
+        Some(0) => Ok(FederationVersion::LatestFedTwo),
+        Some(_) => Ok(FederationVersion::LatestFedOne),
+        None => Err(RoverError::new(anyhow!("No federation version selected"))),
+    }
+}
+
+/// The YAML of a supergraph config, with the federation version as it's usually
+/// written by hand, i.e. `2` or `=2.3.1`
+fn render_config(
+    subgraphs: BTreeMap<String, SubgraphConfig>,
+    federation_version: &FederationVersion,
+) -> RoverResult<String> {
+    let federation_version = match federation_version {
+        FederationVersion::LatestFedOne => Value::from(1),
+        FederationVersion::LatestFedTwo => Value::from(2),
+        FederationVersion::ExactFedOne(version) | FederationVersion::ExactFedTwo(version) => {
+            Value::from(format!("={}", version))
+        }
+    };
+    let mut config = Mapping::new();
+    config.insert("federation_version".into(), federation_version);
+    config.insert("subgraphs".into(), serde_yaml::to_value(subgraphs)?);
+    Ok(format!(
+        "# generated by `rover supergraph config init`\n{}",
+        serde_yaml::to_string(&config)?
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use apollo_federation_types::config::SupergraphConfig;
+
+    use super::*;
+
+    #[test]
+    fn it_renders_a_config_that_can_be_composed() {
+        let mut subgraphs = BTreeMap::new();
+        subgraphs.insert(
+            "products".to_string(),
+            SubgraphConfig {
+                routing_url: Some("http://localhost:4001".to_string()),
+                schema: SchemaSource::File {
+                    file: "./products.graphql".into(),
+                },
+            },
+        );
+        let config =
+            render_config(subgraphs, &parse_federation_version("=2.3.1").unwrap()).unwrap();
+        assert!(config.contains("federation_version: =2.3.1"));
+
+        let supergraph_config = SupergraphConfig::new_from_yaml(&config).unwrap();
+        assert_eq!(
+            supergraph_config
+                .get_federation_version()
+                .unwrap()
+                .get_tarball_version(),
+            "v2.3.1"
+        );
+    }
+}
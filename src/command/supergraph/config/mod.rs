@@ -0,0 +1,37 @@
+mod fetch;
+mod init;
+
+use clap::Parser;
+use serde::Serialize;
+
+use crate::options::OutputOpts;
+use crate::utils::client::StudioClientConfig;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Config {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Generate a supergraph config file from the subgraphs of a graph in Apollo Studio
+    Fetch(fetch::Fetch),
+
+    /// Generate a starter supergraph config file, either by answering prompts or from a graph in Apollo Studio
+    Init(init::Init),
+}
+
+impl Config {
+    pub fn run(
+        &self,
+        client_config: StudioClientConfig,
+        output_opts: &OutputOpts,
+    ) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Fetch(command) => command.run(client_config, output_opts),
+            Command::Init(command) => command.run(client_config),
+        }
+    }
+}
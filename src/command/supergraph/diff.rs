@@ -0,0 +1,119 @@
+use camino::Utf8PathBuf;
+use clap::Parser;
+use serde::Serialize;
+
+use crate::options::{GraphRefOpt, PluginOpts};
+use crate::utils::client::StudioClientConfig;
+use crate::utils::parsers::FileDescriptorType;
+use crate::{RoverOutput, RoverResult};
+
+#[cfg(feature = "composition-js")]
+use rover_client::operations::supergraph::fetch::{self, SupergraphFetchInput, SupergraphSchema};
+#[cfg(feature = "composition-js")]
+use rover_std::{tmp, Style};
+
+#[cfg(feature = "composition-js")]
+use crate::command::supergraph::{
+    compose::Compose, resolve_supergraph_yaml, ConfigExtensions, ResolveOpts,
+};
+#[cfg(feature = "composition-js")]
+use crate::utils::{progress::ProgressEvent, supergraph_diff::SupergraphDiff};
+
+#[cfg(not(feature = "composition-js"))]
+use crate::{RoverError, RoverErrorSuggestion};
+
+#[derive(Debug, Serialize, Parser)]
+#[cfg_attr(not(feature = "composition-js"), allow(unused))]
+pub struct Diff {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    /// The relative path to the supergraph configuration file to compose locally.
+    /// You can pass `-` to use stdin instead of a file.
+    #[serde(skip_serializing)]
+    #[arg(long = "config")]
+    supergraph_yaml: FileDescriptorType,
+
+    #[clap(flatten)]
+    opts: PluginOpts,
+}
+
+#[cfg(feature = "composition-js")]
+impl Diff {
+    pub fn run(
+        &self,
+        override_install_path: Option<Utf8PathBuf>,
+        client_config: StudioClientConfig,
+    ) -> RoverResult<RoverOutput> {
+        tmp::cleanup_on_ctrl_c()?;
+        let client = client_config.get_authenticated_client(&self.opts.profile)?;
+        let graph_ref = &self.graph.graph_ref;
+        eprintln!(
+            "Comparing the published supergraph of {} to the local supergraph using credentials from the {} profile.",
+            Style::Link.paint(graph_ref.to_string()),
+            Style::Command.paint(&self.opts.profile.profile_name)
+        );
+
+        let progress = client_config.get_progress();
+        progress.emit(ProgressEvent::SchemaFetchStarted {
+            graph_ref: graph_ref.to_string(),
+            subgraph: None,
+            hash: None,
+            supergraph: true,
+            profile: Some(self.opts.profile.profile_name.clone()),
+        });
+        let published = fetch::run(
+            SupergraphFetchInput {
+                graph_ref: graph_ref.clone(),
+                schema: SupergraphSchema::Core,
+            },
+            &client,
+        )?;
+        progress.emit(ProgressEvent::SchemaFetched {
+            graph_ref: graph_ref.to_string(),
+            subgraph: None,
+        });
+
+        progress.emit(ProgressEvent::SupergraphConfigResolutionStarted {
+            config: self.supergraph_yaml.to_string(),
+        });
+        let (mut supergraph_config, report) = resolve_supergraph_yaml(
+            &self.supergraph_yaml,
+            client_config.clone(),
+            &self.opts.profile,
+            &ResolveOpts::default(),
+        )?;
+        let local = Compose::new(self.opts.clone()).exec(
+            override_install_path,
+            client_config,
+            &mut supergraph_config,
+            &ConfigExtensions {
+                contract_filter: report.contract_filter,
+                composition_binary: report.composition_binary,
+            },
+        )?;
+
+        let diff = SupergraphDiff::new(
+            format!("the published supergraph of {}", graph_ref),
+            &published.sdl.contents,
+            "the local supergraph",
+            &local.supergraph_sdl,
+        )?;
+        Ok(RoverOutput::SupergraphDiff(diff))
+    }
+}
+
+#[cfg(not(feature = "composition-js"))]
+impl Diff {
+    pub fn run(
+        &self,
+        _override_install_path: Option<Utf8PathBuf>,
+        _client_config: StudioClientConfig,
+    ) -> RoverResult<RoverOutput> {
+        let mut err = RoverError::new(anyhow::anyhow!(
+            "This version of Rover does not support this command."
+        ));
+        err.set_suggestion(RoverErrorSuggestion::CheckGnuVersion);
+        Err(err)
+    }
+}
@@ -1,11 +1,10 @@
-use crate::utils::client::StudioClientConfig;
+use crate::utils::{client::StudioClientConfig, progress::ProgressEvent};
 use crate::{
-    options::{GraphRefOpt, ProfileOpt},
+    options::{DirectiveFilterOpt, GraphRefOpt, ProfileOpt, SchemaType},
     RoverOutput, RoverResult,
 };
 
 use rover_client::operations::supergraph::fetch::{self, SupergraphFetchInput};
-use rover_std::Style;
 
 use clap::Parser;
 use serde::Serialize;
@@ -17,25 +16,42 @@ pub struct Fetch {
 
     #[clap(flatten)]
     profile: ProfileOpt,
+
+    #[clap(flatten)]
+    directives: DirectiveFilterOpt,
+
+    /// Fetch the core schema that routers run, or the API schema that clients can
+    /// query, which is derived from it
+    #[arg(long, value_enum, default_value_t = SchemaType::Core)]
+    schema_type: SchemaType,
 }
 
 impl Fetch {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
         let graph_ref = self.graph.graph_ref.to_string();
-        eprintln!(
-            "Fetching supergraph SDL from {} using credentials from the {} profile.",
-            Style::Link.paint(&graph_ref),
-            Style::Command.paint(&self.profile.profile_name)
-        );
+        let progress = client_config.get_progress();
+        progress.emit(ProgressEvent::SchemaFetchStarted {
+            graph_ref: graph_ref.clone(),
+            subgraph: None,
+            hash: None,
+            supergraph: self.schema_type == SchemaType::Core,
+            profile: Some(self.profile.profile_name.clone()),
+        });
 
         let fetch_response = fetch::run(
             SupergraphFetchInput {
                 graph_ref: self.graph.graph_ref.clone(),
+                schema: self.schema_type.into(),
             },
             &client,
         )?;
+        progress.emit(ProgressEvent::SchemaFetched {
+            graph_ref,
+            subgraph: None,
+        });
 
-        Ok(RoverOutput::FetchResponse(fetch_response))
+        let filter = self.directives.get_filter();
+        Ok(RoverOutput::FetchResponse(fetch_response).transform_sdl(|sdl| filter.apply(sdl)))
     }
 }
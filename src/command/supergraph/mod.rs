@@ -1,15 +1,27 @@
 pub(crate) mod compose;
+mod config;
+mod diff;
 mod fetch;
+mod plugin;
+pub(crate) use plugin::{install_plugin, list_plugins, prune_plugins};
 
+#[cfg(feature = "composition-js")]
+mod preflight;
 #[cfg(feature = "composition-js")]
 mod resolve_config;
 #[cfg(feature = "composition-js")]
-pub(crate) use resolve_config::resolve_supergraph_yaml;
+mod subgraphs_glob;
+#[cfg(feature = "composition-js")]
+pub(crate) use resolve_config::{
+    parse_supergraph_yaml, resolve_supergraph_config, resolve_supergraph_yaml, ConfigExtensions,
+    ResolutionReport, ResolveOpts, SubgraphOverride, DEFAULT_RESOLUTION_CONCURRENCY,
+};
 
 use camino::Utf8PathBuf;
 use clap::Parser;
 use serde::Serialize;
 
+use crate::options::OutputOpts;
 use crate::utils::client::StudioClientConfig;
 use crate::{RoverOutput, RoverResult};
 
@@ -26,17 +38,41 @@ pub enum Command {
 
     /// Fetch supergraph SDL from the graph registry
     Fetch(fetch::Fetch),
+
+    /// Compose a supergraph locally and compare it to the supergraph published to a graph
+    /// in Apollo Studio, including what each subgraph contributes to it
+    Diff(diff::Diff),
+
+    /// Work with supergraph config files
+    Config(config::Config),
+
+    /// Manage the installed composition plugins
+    Plugin(plugin::Plugin),
 }
 
 impl Supergraph {
+    /// Whether the command was asked not to use the network at all
+    pub fn is_offline(&self) -> bool {
+        match &self.command {
+            Command::Compose(command) => command.is_offline(),
+            Command::Fetch(_) | Command::Config(_) | Command::Diff(_) | Command::Plugin(_) => false,
+        }
+    }
+
     pub fn run(
         &self,
         override_install_path: Option<Utf8PathBuf>,
         client_config: StudioClientConfig,
+        output_opts: &OutputOpts,
     ) -> RoverResult<RoverOutput> {
         match &self.command {
             Command::Fetch(command) => command.run(client_config),
-            Command::Compose(command) => command.run(override_install_path, client_config),
+            Command::Diff(command) => command.run(override_install_path, client_config),
+            Command::Config(command) => command.run(client_config, output_opts),
+            Command::Plugin(command) => command.run(override_install_path, client_config),
+            Command::Compose(command) => {
+                command.run(override_install_path, client_config, output_opts)
+            }
         }
     }
 }
@@ -0,0 +1,87 @@
+use apollo_federation_types::config::FederationVersion;
+use camino::Utf8PathBuf;
+use clap::Parser;
+use rover_std::Style;
+use serde::Serialize;
+
+use crate::command::install::{self, InstalledPlugin, Plugin};
+use crate::options::LicenseAccepter;
+use crate::utils::{client::StudioClientConfig, parsers::parse_federation_version};
+use crate::{RoverOutput, RoverResult};
+
+use super::PLUGIN_NAME;
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Install {
+    /// The federation versions to install composition plugins for. Pass '1' or '2' for the
+    /// latest version of a major version, or an exact version preceded with an '=', i.e. '=2.3.1'
+    #[arg(
+        value_name = "VERSION",
+        required = true,
+        value_parser = parse_federation_version
+    )]
+    federation_versions: Vec<FederationVersion>,
+
+    #[clap(flatten)]
+    elv2_license_accepter: LicenseAccepter,
+}
+
+impl Install {
+    pub fn run(
+        &self,
+        override_install_path: Option<Utf8PathBuf>,
+        client_config: StudioClientConfig,
+    ) -> RoverResult<RoverOutput> {
+        if self
+            .federation_versions
+            .iter()
+            .any(|federation_version| federation_version.get_major_version() == 2)
+        {
+            self.elv2_license_accepter
+                .require_elv2_license(&client_config)?;
+        }
+
+        let mut installed_plugins = Vec::new();
+        for federation_version in &self.federation_versions {
+            let plugin_exe = install_plugin(
+                Plugin::Supergraph(federation_version.clone()),
+                &mut installed_plugins,
+                override_install_path.clone(),
+                client_config.clone(),
+                self.elv2_license_accepter,
+            )?;
+            eprintln!(
+                "The composition plugin for `federation_version: {}` is installed at {}",
+                federation_version,
+                Style::Path.paint(&plugin_exe)
+            );
+        }
+        Ok(RoverOutput::InstalledPlugins(installed_plugins))
+    }
+}
+
+/// Installs a plugin, adding it to `installed_plugins` unless an earlier version argument
+/// installed it, which `rover dev install` does for the router and composition plugin.
+/// Checking the ELv2 license is up to the caller
+pub(crate) fn install_plugin(
+    plugin: Plugin,
+    installed_plugins: &mut Vec<InstalledPlugin>,
+    override_install_path: Option<Utf8PathBuf>,
+    client_config: StudioClientConfig,
+    elv2_license_accepter: LicenseAccepter,
+) -> RoverResult<Utf8PathBuf> {
+    let plugin_name = plugin.get_name();
+    let install_command = install::Install {
+        force: false,
+        plugin: Some(plugin),
+        elv2_license_accepter,
+    };
+    let plugin_exe =
+        install_command.get_versioned_plugin(override_install_path, client_config, false)?;
+    if let Some(installed_plugin) = InstalledPlugin::from_path(&plugin_exe, &plugin_name) {
+        if !installed_plugins.contains(&installed_plugin) {
+            installed_plugins.push(installed_plugin);
+        }
+    }
+    Ok(plugin_exe)
+}
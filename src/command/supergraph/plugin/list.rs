@@ -0,0 +1,29 @@
+use camino::Utf8PathBuf;
+use clap::Parser;
+use serde::Serialize;
+
+use crate::command::install::{get_plugin_dir, list_installed_plugins};
+use crate::{RoverOutput, RoverResult};
+
+use super::PLUGIN_NAME;
+
+#[derive(Debug, Serialize, Parser)]
+pub struct List {}
+
+impl List {
+    pub fn run(&self, override_install_path: Option<Utf8PathBuf>) -> RoverResult<RoverOutput> {
+        list_plugins(override_install_path, PLUGIN_NAME)
+    }
+}
+
+/// Lists the installed versions of a plugin, which `rover dev list` does for the router
+pub(crate) fn list_plugins(
+    override_install_path: Option<Utf8PathBuf>,
+    plugin_name: &str,
+) -> RoverResult<RoverOutput> {
+    let plugin_dir = get_plugin_dir(override_install_path)?;
+    Ok(RoverOutput::InstalledPlugins(list_installed_plugins(
+        &plugin_dir,
+        plugin_name,
+    )?))
+}
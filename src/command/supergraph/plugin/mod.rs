@@ -0,0 +1,50 @@
+mod install;
+mod list;
+mod prune;
+
+pub(crate) use install::install_plugin;
+pub(crate) use list::list_plugins;
+pub(crate) use prune::prune_plugins;
+
+use camino::Utf8PathBuf;
+use clap::Parser;
+use serde::Serialize;
+
+use crate::utils::client::StudioClientConfig;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Plugin {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Install composition plugins ahead of time, i.e. while building a CI image,
+    /// so `rover supergraph compose` doesn't need to download them
+    Install(install::Install),
+
+    /// List the composition plugins that are installed
+    List(list::List),
+
+    /// Remove all but the newest installed composition plugins of each major version
+    Prune(prune::Prune),
+}
+
+impl Plugin {
+    pub fn run(
+        &self,
+        override_install_path: Option<Utf8PathBuf>,
+        client_config: StudioClientConfig,
+    ) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Install(command) => command.run(override_install_path, client_config),
+            Command::List(command) => command.run(override_install_path),
+            Command::Prune(command) => command.run(override_install_path),
+        }
+    }
+}
+
+/// The name of the composition plugin's binaries, i.e. `supergraph-v2.3.1`
+const PLUGIN_NAME: &str = "supergraph";
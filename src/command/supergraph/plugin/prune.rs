@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+use camino::Utf8PathBuf;
+use clap::Parser;
+use rover_std::Fs;
+use serde::Serialize;
+
+use crate::command::install::{get_plugin_dir, list_installed_plugins, InstalledPlugin};
+use crate::{RoverOutput, RoverResult};
+
+use super::PLUGIN_NAME;
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Prune {
+    /// How many of the newest versions of each major version to keep
+    #[arg(long, value_name = "COUNT", default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    keep: u64,
+
+    /// List the composition plugins that would be removed, without removing them
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Prune {
+    pub fn run(&self, override_install_path: Option<Utf8PathBuf>) -> RoverResult<RoverOutput> {
+        prune_plugins(
+            override_install_path,
+            PLUGIN_NAME,
+            self.keep as usize,
+            self.dry_run,
+        )
+    }
+}
+
+/// Removes all but the `keep` newest installed versions of each major version of a plugin,
+/// which `rover dev prune` does for the router
+pub(crate) fn prune_plugins(
+    override_install_path: Option<Utf8PathBuf>,
+    plugin_name: &str,
+    keep: usize,
+    dry_run: bool,
+) -> RoverResult<RoverOutput> {
+    let plugin_dir = get_plugin_dir(override_install_path)?;
+    let installed_plugins = list_installed_plugins(&plugin_dir, plugin_name)?;
+    let removed = get_prunable_plugins(installed_plugins, keep);
+    if !dry_run {
+        for plugin in &removed {
+            Fs::remove_file(&plugin.path)?;
+        }
+    }
+    Ok(RoverOutput::PrunedPlugins { removed, dry_run })
+}
+
+/// Every plugin that isn't one of the `keep` newest of its major version,
+/// given plugins sorted by semver
+fn get_prunable_plugins(
+    installed_plugins: Vec<InstalledPlugin>,
+    keep: usize,
+) -> Vec<InstalledPlugin> {
+    let mut by_major: BTreeMap<u64, Vec<InstalledPlugin>> = BTreeMap::new();
+    for plugin in installed_plugins {
+        by_major
+            .entry(plugin.version.major)
+            .or_default()
+            .push(plugin);
+    }
+    by_major
+        .into_values()
+        .flat_map(|mut plugins| {
+            let prunable = plugins.len().saturating_sub(keep);
+            plugins.truncate(prunable);
+            plugins
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+
+    use super::*;
+
+    fn plugin(version: &str) -> InstalledPlugin {
+        InstalledPlugin {
+            name: PLUGIN_NAME.to_string(),
+            version: Version::parse(version).unwrap(),
+            path: format!("supergraph-v{}", version).into(),
+        }
+    }
+
+    #[test]
+    fn it_keeps_the_newest_plugins_of_each_major_version() {
+        let installed_plugins = vec![
+            plugin("0.36.0"),
+            plugin("0.37.1"),
+            plugin("2.1.0"),
+            plugin("2.3.1"),
+            plugin("2.4.0"),
+        ];
+        assert_eq!(
+            get_prunable_plugins(installed_plugins.clone(), 1),
+            vec![plugin("0.36.0"), plugin("2.1.0"), plugin("2.3.1")]
+        );
+        assert_eq!(
+            get_prunable_plugins(installed_plugins, 2),
+            vec![plugin("2.1.0")]
+        );
+    }
+}
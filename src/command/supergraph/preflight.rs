@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use apollo_federation_types::config::{SchemaSource, SubgraphConfig};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rayon::ThreadPool;
+
+use rover_client::blocking::GraphQLClient;
+use rover_client::operations::subgraph::introspect::{self, SubgraphIntrospectInput};
+use rover_client::RoverClientError;
+
+use crate::utils::{client::StudioClientConfig, progress::ProgressEvent};
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// Whether a subgraph's endpoints are ready for the supergraph to be composed and served
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SubgraphReadiness {
+    name: String,
+    status: ReadinessStatus,
+    /// the `subgraph_url` and routing URL of the subgraph, which are each probed once
+    endpoints: Vec<String>,
+    /// why the subgraph isn't ready, naming the endpoint that failed
+    reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReadinessStatus {
+    Ready,
+    /// an endpoint couldn't be reached, or didn't answer with GraphQL
+    Unreachable,
+    /// an endpoint answered, but without the `_service { sdl }` field the federation
+    /// subgraph spec requires
+    NotFederated,
+    /// the subgraph has no endpoint to probe, i.e. a `file` source without a `routing_url`
+    Skipped,
+}
+
+impl ReadinessStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ready => "ready",
+            Self::Unreachable => "unreachable",
+            Self::NotFederated => "not_federated",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+/// Probes the endpoints of every subgraph before any schema is resolved, so a subgraph that
+/// isn't running, or doesn't implement the federation subgraph spec, fails with a report of
+/// every subgraph that isn't ready instead of failing composition partway through
+pub(crate) fn check_readiness(
+    supergraph_config: &[(String, SubgraphConfig)],
+    client_config: &StudioClientConfig,
+    pool: &ThreadPool,
+) -> RoverResult<()> {
+    let readiness: Vec<SubgraphReadiness> = pool.install(|| {
+        supergraph_config
+            .par_iter()
+            .map(|(subgraph_name, subgraph_data)| {
+                let readiness = check_subgraph(subgraph_name, subgraph_data, client_config);
+                client_config
+                    .get_progress()
+                    .emit(ProgressEvent::SubgraphPreflightChecked {
+                        subgraph: readiness.name.clone(),
+                        status: readiness.status.as_str().to_string(),
+                        endpoints: readiness.endpoints.clone(),
+                        reason: readiness.reason.clone(),
+                    });
+                readiness
+            })
+            .collect()
+    });
+
+    // subgraphs without an endpoint to probe don't fail the preflight
+    let not_ready: Vec<String> = readiness
+        .iter()
+        .filter(|readiness| {
+            matches!(
+                readiness.status,
+                ReadinessStatus::Unreachable | ReadinessStatus::NotFederated
+            )
+        })
+        .map(|readiness| {
+            format!(
+                "    {} ({}): {}",
+                readiness.name,
+                readiness.status.as_str(),
+                readiness.reason.clone().unwrap_or_default()
+            )
+        })
+        .collect();
+    if not_ready.is_empty() {
+        return Ok(());
+    }
+    let mut err = RoverError::new(anyhow!(
+        "{} of {} subgraphs aren't ready to be composed:\n{}",
+        not_ready.len(),
+        readiness.len(),
+        not_ready.join("\n")
+    ));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(
+        "Start the subgraphs that can't be reached, and make sure each subgraph implements the federation subgraph spec, or compose without `--preflight`.".to_string(),
+    ));
+    Err(err)
+}
+
+/// Probes each endpoint of one subgraph, stopping at the first that isn't ready
+fn check_subgraph(
+    subgraph_name: &str,
+    subgraph_data: &SubgraphConfig,
+    client_config: &StudioClientConfig,
+) -> SubgraphReadiness {
+    let endpoints = get_endpoints(subgraph_data);
+    let mut readiness = SubgraphReadiness {
+        name: subgraph_name.to_string(),
+        status: ReadinessStatus::Ready,
+        endpoints: endpoints.clone(),
+        reason: None,
+    };
+    if endpoints.is_empty() {
+        readiness.status = ReadinessStatus::Skipped;
+        readiness.reason = Some("it has no routing_url or subgraph_url to probe".to_string());
+        return readiness;
+    }
+    for endpoint in &endpoints {
+        if let Err((status, reason)) = probe(endpoint, client_config) {
+            readiness.status = status;
+            readiness.reason = Some(format!("{} {}", endpoint, reason));
+            break;
+        }
+    }
+    readiness
+}
+
+/// The endpoints a subgraph is introspected at or routed to. A `graphref` source
+/// without a `routing_url` is routed to the URL published to Apollo Studio, which isn't
+/// known until its schema is fetched
+fn get_endpoints(subgraph_data: &SubgraphConfig) -> Vec<String> {
+    let mut endpoints = Vec::new();
+    if let SchemaSource::SubgraphIntrospection { subgraph_url } = &subgraph_data.schema {
+        endpoints.push(subgraph_url.to_string());
+    }
+    if let Some(routing_url) = &subgraph_data.routing_url {
+        if !endpoints.contains(routing_url) {
+            endpoints.push(routing_url.clone());
+        }
+    }
+    endpoints
+}
+
+/// Asks an endpoint for its subgraph schema, the way `subgraph introspect` does
+fn probe(
+    endpoint: &str,
+    client_config: &StudioClientConfig,
+) -> Result<(), (ReadinessStatus, String)> {
+    let reqwest_client = client_config.get_reqwest_client().map_err(|e| {
+        (
+            ReadinessStatus::Unreachable,
+            format!("could not be probed: {}", e),
+        )
+    })?;
+    let client = GraphQLClient::new(endpoint, reqwest_client);
+    match introspect::run(
+        SubgraphIntrospectInput {
+            headers: HashMap::new(),
+        },
+        &client,
+        false,
+    ) {
+        Ok(_) => Ok(()),
+        Err(RoverClientError::SubgraphIntrospectionNotAvailable) => Err((
+            ReadinessStatus::NotFederated,
+            "doesn't answer `_service { sdl }`, which the federation subgraph spec requires"
+                .to_string(),
+        )),
+        Err(e) => Err((
+            ReadinessStatus::Unreachable,
+            format!("could not be reached: {}", e),
+        )),
+    }
+}
@@ -4,10 +4,12 @@ use apollo_federation_types::{
     config::{FederationVersion, SchemaSource, SubgraphConfig, SupergraphConfig},
 };
 use apollo_parser::{ast, Parser};
+use camino::{Utf8Path, Utf8PathBuf};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rayon::ThreadPoolBuilder;
 use rover_std::Fs;
 
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, str::FromStr, time::Instant};
 
 use rover_client::operations::subgraph::fetch::{self, SubgraphFetchInput};
 use rover_client::operations::subgraph::introspect::{self, SubgraphIntrospectInput};
@@ -15,38 +17,199 @@ use rover_client::shared::GraphRef;
 use rover_client::{blocking::GraphQLClient, RoverClientError};
 
 use crate::{
+    command::supergraph::{
+        compose::{MissingSubgraph, SubgraphMetrics},
+        preflight::check_readiness,
+        subgraphs_glob::expand_subgraphs_glob,
+    },
     options::ProfileOpt,
-    utils::{client::StudioClientConfig, parsers::FileDescriptorType},
+    utils::{
+        client::StudioClientConfig, env_substitution::expand_env_vars,
+        introspection_cache::IntrospectionCache, parsers::FileDescriptorType,
+        progress::ProgressEvent, schema_contract::ContractFilter,
+    },
 };
 use crate::{RoverError, RoverErrorSuggestion, RoverResult};
 
+/// The default number of subgraph schemas that are resolved at once
+pub(crate) const DEFAULT_RESOLUTION_CONCURRENCY: u16 = 16;
+
+/// How the subgraphs of a supergraph config are resolved
+#[derive(Debug, Clone)]
+pub(crate) struct ResolveOpts {
+    /// Subgraphs whose schema has to be fetched from a `subgraph_url` or from Apollo Studio
+    /// are left out if that fails, and returned alongside the config instead of failing it
+    pub(crate) allow_missing_subgraphs: bool,
+
+    /// The number of subgraphs resolved at once. Fetching schemas from `subgraph_url`s
+    /// and Apollo Studio mostly waits on the network
+    pub(crate) concurrency: u16,
+
+    /// A config with any subgraph that would have to be fetched fails before anything is fetched
+    pub(crate) offline: bool,
+
+    /// Schemas introspected from a `subgraph_url` are reused from this cache until they expire
+    pub(crate) introspection_cache: Option<IntrospectionCache>,
+
+    /// Used instead of the config's `federation_version`, or the one detected from the subgraphs
+    pub(crate) federation_version: Option<FederationVersion>,
+
+    /// Subgraphs whose schema is read from a local file instead of from the config's source
+    pub(crate) subgraph_overrides: Vec<SubgraphOverride>,
+
+    /// Every subgraph's `subgraph_url` and routing URL are probed before any schema is
+    /// resolved, and the config fails if any of them isn't ready
+    pub(crate) preflight: bool,
+}
+
+/// A subgraph of a supergraph config whose schema is replaced with a local file,
+/// i.e. to compose a subgraph's unpublished changes with the rest of a published graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SubgraphOverride {
+    pub(crate) name: String,
+    /// relative to the current directory, not to the supergraph config
+    pub(crate) file: Utf8PathBuf,
+}
+
+impl Default for ResolveOpts {
+    fn default() -> Self {
+        Self {
+            allow_missing_subgraphs: false,
+            concurrency: DEFAULT_RESOLUTION_CONCURRENCY,
+            offline: false,
+            introspection_cache: None,
+            federation_version: None,
+            subgraph_overrides: Vec::new(),
+            preflight: false,
+        }
+    }
+}
+
+/// What happened to the subgraphs of a resolved supergraph config
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ResolutionReport {
+    /// subgraphs left out of the config because of `allow_missing_subgraphs`
+    pub(crate) missing_subgraphs: Vec<MissingSubgraph>,
+
+    /// how long each subgraph took to resolve, by subgraph name
+    pub(crate) subgraph_metrics: Vec<SubgraphMetrics>,
+
+    /// the config's `filter` section, which is applied to the composed supergraph
+    pub(crate) contract_filter: Option<ContractFilter>,
+
+    /// the config's `composition_binary`, which is composed with instead of a downloaded plugin
+    pub(crate) composition_binary: Option<Utf8PathBuf>,
+}
+
+/// The sections of a supergraph config that Rover handles itself, which aren't passed
+/// to the composition plugin
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ConfigExtensions {
+    /// the `filter` section
+    pub(crate) contract_filter: Option<ContractFilter>,
+
+    /// the `composition_binary`, relative to the directory of the config
+    pub(crate) composition_binary: Option<Utf8PathBuf>,
+}
+
+/// Resolves the schema of every subgraph in a supergraph config.
+///
+/// Without a `federation_version` in the config or in `opts`, the version is detected
+/// from the subgraphs: any `@link` directive on a subgraph's schema requires Federation 2.
 pub(crate) fn resolve_supergraph_yaml(
     unresolved_supergraph_yaml: &FileDescriptorType,
     client_config: StudioClientConfig,
     profile_opt: &ProfileOpt,
-) -> RoverResult<SupergraphConfig> {
+    opts: &ResolveOpts,
+) -> RoverResult<(SupergraphConfig, ResolutionReport)> {
+    let contents = unresolved_supergraph_yaml
+        .read_file_descriptor("supergraph config", &mut std::io::stdin())?;
+    let (supergraph_config, extensions) =
+        parse_supergraph_yaml(&contents, unresolved_supergraph_yaml)?;
+    let (supergraph_config, mut report) = resolve_supergraph_config(
+        supergraph_config,
+        unresolved_supergraph_yaml,
+        &format!("'{}'", unresolved_supergraph_yaml),
+        client_config,
+        profile_opt,
+        opts,
+    )?;
+    report.contract_filter = extensions.contract_filter;
+    report.composition_binary = extensions.composition_binary;
+    Ok((supergraph_config, report))
+}
+
+/// Resolves the schema of every subgraph in a supergraph config that's already parsed.
+/// Its `file` sources are relative to the directory of `config_source`,
+/// and `description` names the config in errors, i.e. `'./supergraph.yaml'`
+pub(crate) fn resolve_supergraph_config(
+    supergraph_config: SupergraphConfig,
+    config_source: &FileDescriptorType,
+    description: &str,
+    client_config: StudioClientConfig,
+    profile_opt: &ProfileOpt,
+    opts: &ResolveOpts,
+) -> RoverResult<(SupergraphConfig, ResolutionReport)> {
     let err_no_routing_url = || {
         let err = anyhow!("No routing_url found for schema file.");
         let mut err = RoverError::new(err);
         err.set_suggestion(RoverErrorSuggestion::ValidComposeRoutingUrl);
         err
     };
-    let contents = unresolved_supergraph_yaml
-        .read_file_descriptor("supergraph config", &mut std::io::stdin())?;
-    let supergraph_config = SupergraphConfig::new_from_yaml(&contents)?;
     let maybe_specified_federation_version = supergraph_config.get_federation_version();
-    let supergraph_config = supergraph_config
+    let mut supergraph_config = supergraph_config
         .into_iter()
         .collect::<Vec<(String, SubgraphConfig)>>();
+    apply_subgraph_overrides(
+        &mut supergraph_config,
+        &opts.subgraph_overrides,
+        description,
+    )?;
+    if opts.offline {
+        check_offline_sources(&supergraph_config)?;
+    }
+    // every `graphref` source is fetched from Apollo Studio with a request of its own
+    let studio_requests = supergraph_config
+        .iter()
+        .filter(|(_, subgraph_data)| matches!(subgraph_data.schema, SchemaSource::Subgraph { .. }))
+        .count();
+    client_config.plan_requests(studio_requests as u64)?;
 
-    let subgraph_definition_results: Vec<(String, RoverResult<SubgraphDefinition>)> =
+    // a pool of our own keeps slow sources from waiting on, or holding up,
+    // anything else that runs on rayon's global pool
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(usize::from(opts.concurrency))
+        .thread_name(|index| format!("subgraph-resolution-{}", index))
+        .build()?;
+    if opts.preflight {
+        check_readiness(&supergraph_config, &client_config, &pool)?;
+    }
+    let subgraph_definition_results: Vec<SubgraphResolution> = pool.install(|| {
         supergraph_config
             .into_par_iter()
             .map(|(subgraph_name, subgraph_data)| {
                 let cloned_subgraph_name = subgraph_name.to_string();
+                let source_description = describe_source(&subgraph_data.schema);
+                let is_remote = matches!(
+                    &subgraph_data.schema,
+                    SchemaSource::SubgraphIntrospection { .. } | SchemaSource::Subgraph { .. }
+                );
+                let source = match &subgraph_data.schema {
+                    SchemaSource::File { .. } => "file",
+                    SchemaSource::SubgraphIntrospection { .. } => "subgraph_url",
+                    SchemaSource::Subgraph { .. } => "graphref",
+                    SchemaSource::Sdl { .. } => "sdl",
+                };
+                client_config
+                    .get_progress()
+                    .emit(ProgressEvent::SubgraphResolutionStarted {
+                        subgraph: cloned_subgraph_name.clone(),
+                        source: source.to_string(),
+                    });
+                let started_at = Instant::now();
                 let result = match &subgraph_data.schema {
                     SchemaSource::File { file } => {
-                        let relative_schema_path = match unresolved_supergraph_yaml {
+                        let relative_schema_path = match config_source {
                             FileDescriptorType::File(config_path) => match config_path.parent() {
                                 Some(parent) => {
                                     let mut schema_path = parent.to_path_buf();
@@ -73,35 +236,56 @@ pub(crate) fn resolve_supergraph_yaml(
                             })
                     }
                     SchemaSource::SubgraphIntrospection { subgraph_url } => {
-                        client_config
-                            .get_reqwest_client()
-                            .map_err(RoverError::from)
-                            .and_then(|reqwest_client| {
-                                let client =
-                                    GraphQLClient::new(subgraph_url.as_ref(), reqwest_client);
-
-                                // given a federated introspection URL, use subgraph introspect to
-                                // obtain SDL and add it to subgraph_definition.
-                                introspect::run(
-                                    SubgraphIntrospectInput {
-                                        headers: HashMap::new(),
-                                    },
-                                    &client,
-                                    false,
-                                )
-                                .map(|introspection_response| {
-                                    let schema = introspection_response.result;
+                        let headers = HashMap::new();
+                        let introspection_cache = opts.introspection_cache.as_ref();
+                        let cached_schema = introspection_cache
+                            .and_then(|cache| cache.get(subgraph_url.as_ref(), &headers));
+                        match cached_schema {
+                            Some(schema) => Ok(schema),
+                            None => client_config
+                                .get_reqwest_client()
+                                .map_err(RoverError::from)
+                                .and_then(|reqwest_client| {
+                                    let client =
+                                        GraphQLClient::new(subgraph_url.as_ref(), reqwest_client);
 
-                                    // We don't require a routing_url in config for this variant of a schema,
-                                    // if one isn't provided, just use the URL they passed for introspection.
-                                    let url = &subgraph_data
-                                        .routing_url
-                                        .clone()
-                                        .unwrap_or_else(|| subgraph_url.to_string());
-                                    SubgraphDefinition::new(subgraph_name, url, &schema)
+                                    // given a federated introspection URL, use subgraph introspect to
+                                    // obtain SDL and add it to subgraph_definition.
+                                    introspect::run(
+                                        SubgraphIntrospectInput {
+                                            headers: headers.clone(),
+                                        },
+                                        &client,
+                                        false,
+                                    )
+                                    .map(|introspection_response| introspection_response.result)
+                                    .map_err(RoverError::from)
                                 })
-                                .map_err(RoverError::from)
-                            })
+                                .map(|schema| {
+                                    if let Some(cache) = introspection_cache {
+                                        // a schema that can't be cached is introspected again next time
+                                        if let Err(e) =
+                                            cache.put(subgraph_url.as_ref(), &headers, &schema)
+                                        {
+                                            tracing::debug!(
+                                                "could not cache the schema of {}: {}",
+                                                subgraph_url,
+                                                e
+                                            );
+                                        }
+                                    }
+                                    schema
+                                }),
+                        }
+                        .map(|schema| {
+                            // We don't require a routing_url in config for this variant of a schema,
+                            // if one isn't provided, just use the URL they passed for introspection.
+                            let url = &subgraph_data
+                                .routing_url
+                                .clone()
+                                .unwrap_or_else(|| subgraph_url.to_string());
+                            SubgraphDefinition::new(subgraph_name, url, &schema)
+                        })
                     }
                     SchemaSource::Subgraph {
                         graphref: graph_ref,
@@ -150,19 +334,67 @@ pub(crate) fn resolve_supergraph_yaml(
                         .map(|url| SubgraphDefinition::new(subgraph_name, url, sdl)),
                 };
 
-                (cloned_subgraph_name, result)
+                SubgraphResolution {
+                    metrics: SubgraphMetrics {
+                        name: cloned_subgraph_name,
+                        source: source.to_string(),
+                        resolution_time_ms: started_at.elapsed().as_millis() as u64,
+                        sdl_bytes: result
+                            .as_ref()
+                            .ok()
+                            .map(|subgraph_definition| subgraph_definition.sdl.len()),
+                    },
+                    source_description,
+                    is_remote,
+                    result,
+                }
             })
-            .collect();
+            .collect()
+    });
 
     let mut subgraph_definitions = Vec::new();
     let mut subgraph_definition_errors = Vec::new();
+    let mut missing_subgraphs = Vec::new();
+    let mut subgraph_metrics = Vec::new();
 
     let num_subgraphs = subgraph_definition_results.len();
 
-    for (subgraph_name, subgraph_definition_result) in subgraph_definition_results {
-        match subgraph_definition_result {
-            Ok(subgraph_definition) => subgraph_definitions.push(subgraph_definition),
-            Err(e) => subgraph_definition_errors.push((subgraph_name, e)),
+    let progress = client_config.get_progress();
+    for SubgraphResolution {
+        metrics,
+        source_description,
+        is_remote,
+        result,
+    } in subgraph_definition_results
+    {
+        let subgraph_name = metrics.name.clone();
+        subgraph_metrics.push(metrics);
+        match result {
+            Ok(subgraph_definition) => {
+                progress.emit(ProgressEvent::SubgraphResolved {
+                    subgraph: subgraph_name,
+                });
+                subgraph_definitions.push(subgraph_definition)
+            }
+            // only sources that can be temporarily unreachable are left out,
+            // since a missing file or routing_url won't fix itself
+            Err(e) if opts.allow_missing_subgraphs && is_remote => {
+                progress.emit(ProgressEvent::SubgraphSkipped {
+                    subgraph: subgraph_name.clone(),
+                    reason: e.message(),
+                });
+                missing_subgraphs.push(MissingSubgraph {
+                    name: subgraph_name,
+                    reason: e.message(),
+                });
+            }
+            Err(e) => {
+                progress.emit(ProgressEvent::SubgraphResolutionFailed {
+                    subgraph: subgraph_name.clone(),
+                    reason: e.message(),
+                });
+                subgraph_definition_errors.push((subgraph_name, source_description, e))
+            }
         }
     }
 
@@ -170,14 +402,14 @@ pub(crate) fn resolve_supergraph_yaml(
         let source = BuildErrors::from(
             subgraph_definition_errors
                 .iter()
-                .map(|(subgraph_name, error)| {
+                .map(|(subgraph_name, source_description, error)| {
                     let mut message = error.message();
                     if message.ends_with('.') {
                         message.pop();
                     }
                     let mut message = format!(
-                        "{} while resolving the schema for the '{}' subgraph",
-                        message, subgraph_name
+                        "{} while resolving the schema for the '{}' subgraph from {}",
+                        message, subgraph_name, source_description
                     );
                     if let Some(suggestion) = error.suggestion() {
                         message = format!("{}\n        {}", message, suggestion)
@@ -192,53 +424,277 @@ pub(crate) fn resolve_supergraph_yaml(
         }));
     }
 
+    if subgraph_definitions.is_empty() && !missing_subgraphs.is_empty() {
+        let mut err = RoverError::new(anyhow!(
+            "None of the subgraphs in {} could be resolved, so there is nothing to compose.",
+            description
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(
+            "Make sure each subgraph's `subgraph_url` is reachable, or that the graph exists in Apollo Studio.".to_string(),
+        ));
+        return Err(err);
+    }
+
     let mut resolved_supergraph_config: SupergraphConfig = subgraph_definitions.into();
 
-    let mut fed_two_subgraph_names = Vec::new();
-    for subgraph_definition in resolved_supergraph_config.get_subgraph_definitions()? {
-        let parser = Parser::new(&subgraph_definition.sdl);
-        let parsed_ast = parser.parse();
-        let doc = parsed_ast.document();
-        for definition in doc.definitions() {
-            let maybe_directives = match definition {
-                ast::Definition::SchemaExtension(ext) => ext.directives(),
-                ast::Definition::SchemaDefinition(def) => def.directives(),
-                _ => None,
-            }
-            .map(|d| d.directives());
-            if let Some(directives) = maybe_directives {
-                for directive in directives {
-                    if let Some(directive_name) = directive.name() {
-                        if "link" == directive_name.text() {
-                            fed_two_subgraph_names.push(subgraph_definition.name.clone());
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let fed_two_subgraph_names =
+        get_fed_two_subgraph_names(&resolved_supergraph_config.get_subgraph_definitions()?);
 
-    if let Some(specified_federation_version) = maybe_specified_federation_version {
+    let specified_federation_version = match &opts.federation_version {
+        Some(federation_version) => Some((
+            federation_version.clone(),
+            "passed to `--federation-version`".to_string(),
+        )),
+        None => maybe_specified_federation_version
+            .map(|federation_version| (federation_version, format!("set in {}", description))),
+    };
+    if let Some((specified_federation_version, origin)) = specified_federation_version {
         // error if we detect an `@link` directive and the explicitly set `federation_version` to 1
         if specified_federation_version.is_fed_one() && !fed_two_subgraph_names.is_empty() {
             let mut err =
-                RoverError::new(anyhow!("The 'federation_version' set in '{}' is invalid. The following subgraphs contain '@link' directives, which are only valid in Federation 2: {}", unresolved_supergraph_yaml, fed_two_subgraph_names.join(", ")));
+                RoverError::new(anyhow!("The federation version {} is invalid. The following subgraphs contain '@link' directives, which are only valid in Federation 2: {}", origin, fed_two_subgraph_names.join(", ")));
             err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
-                "Either remove the 'federation_version' entry from '{}', or set the value to '2'.",
-                unresolved_supergraph_yaml
+                "Either remove the 'federation_version' entry from {} and don't pass `--federation-version`, or set the version to '2'.",
+                description
             )));
             return Err(err);
         }
 
         // otherwise, set the version to what they set
         resolved_supergraph_config.set_federation_version(specified_federation_version)
-    } else if fed_two_subgraph_names.is_empty() {
-        // if they did not specify a version and no subgraphs contain `@link` directives, use Federation 1
-        resolved_supergraph_config.set_federation_version(FederationVersion::LatestFedOne)
     } else {
-        // if they did not specify a version and at least one subgraph contains an `@link` directive, use Federation 2
-        resolved_supergraph_config.set_federation_version(FederationVersion::LatestFedTwo)
+        // if they did not specify a version, subgraphs that contain `@link` directives
+        // require Federation 2, and Federation 1 is used otherwise
+        let federation_version = if fed_two_subgraph_names.is_empty() {
+            FederationVersion::LatestFedOne
+        } else {
+            FederationVersion::LatestFedTwo
+        };
+        client_config
+            .get_progress()
+            .emit(ProgressEvent::FederationVersionDetected {
+                federation_version: federation_version.get_major_version().to_string(),
+                subgraphs: fed_two_subgraph_names,
+            });
+        resolved_supergraph_config.set_federation_version(federation_version)
     }
 
-    Ok(resolved_supergraph_config)
+    Ok((
+        resolved_supergraph_config,
+        ResolutionReport {
+            missing_subgraphs,
+            subgraph_metrics,
+            contract_filter: None,
+            composition_binary: None,
+        },
+    ))
+}
+
+/// The outcome of resolving the schema of one subgraph
+struct SubgraphResolution {
+    metrics: SubgraphMetrics,
+    source_description: String,
+    is_remote: bool,
+    result: RoverResult<SubgraphDefinition>,
+}
+
+/// Parses a supergraph config after expanding the environment variables it refers to
+/// and the schema files its `subgraphs_glob` matches, along with the sections Rover handles itself
+pub(crate) fn parse_supergraph_yaml(
+    contents: &str,
+    source: &FileDescriptorType,
+) -> RoverResult<(SupergraphConfig, ConfigExtensions)> {
+    let contents = expand_env_vars(contents, &format!("'{}'", source), |name| {
+        std::env::var(name).ok()
+    })?;
+    // `file` sources, and so the schema files a glob matches, are relative to the config
+    let config_dir = match source {
+        FileDescriptorType::File(config_path) => match config_path.parent() {
+            Some(parent) if !parent.as_str().is_empty() => parent.to_path_buf(),
+            _ => Utf8PathBuf::from("."),
+        },
+        FileDescriptorType::Stdin => Utf8PathBuf::from("."),
+    };
+    let contents = expand_subgraphs_glob(&contents, &config_dir)?;
+    let (contents, extensions) = take_extensions(&contents, source, &config_dir)?;
+    Ok((SupergraphConfig::new_from_yaml(&contents)?, extensions))
+}
+
+/// Removes the `filter` section and the `composition_binary` from a supergraph config,
+/// since they aren't part of the config that's passed to the composition plugin.
+/// Configs that aren't valid YAML are returned as they are
+fn take_extensions(
+    contents: &str,
+    source: &FileDescriptorType,
+    config_dir: &Utf8Path,
+) -> RoverResult<(String, ConfigExtensions)> {
+    let mut document: serde_yaml::Value = match serde_yaml::from_str(contents) {
+        Ok(document) => document,
+        Err(_) => return Ok((contents.to_string(), ConfigExtensions::default())),
+    };
+    let (filter, composition_binary) = match document.as_mapping_mut() {
+        Some(document) => (
+            document.remove("filter"),
+            document.remove("composition_binary"),
+        ),
+        None => (None, None),
+    };
+    if filter.is_none() && composition_binary.is_none() {
+        return Ok((contents.to_string(), ConfigExtensions::default()));
+    }
+    let composition_binary = match composition_binary {
+        Some(serde_yaml::Value::String(path)) => Some(config_dir.join(path)),
+        Some(_) => {
+            let mut err = RoverError::new(anyhow!(
+                "The `composition_binary` in '{}' is invalid: it must be a path",
+                source
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Set `composition_binary` to the path of a `supergraph` binary, relative to the config.".to_string(),
+            ));
+            return Err(err);
+        }
+        None => None,
+    };
+    let contract_filter = match filter {
+        Some(filter) => Some(get_contract_filter(filter, source)?),
+        None => None,
+    };
+    Ok((
+        serde_yaml::to_string(&document)?,
+        ConfigExtensions {
+            contract_filter,
+            composition_binary,
+        },
+    ))
+}
+
+fn get_contract_filter(
+    filter: serde_yaml::Value,
+    source: &FileDescriptorType,
+) -> RoverResult<ContractFilter> {
+    serde_yaml::from_value::<ContractFilter>(filter).map_err(|e| {
+        let mut err = RoverError::new(anyhow!(
+            "The `filter` in '{}' is invalid: {}",
+            source,
+            e
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(
+            "Set `include_tags` and `exclude_tags` under `filter` to lists of tag names, i.e. `exclude_tags: [internal]`.".to_string(),
+        ));
+        err
+    })
+}
+
+/// The names of the subgraphs with an `@link` directive on their schema,
+/// which is only valid in Federation 2
+fn get_fed_two_subgraph_names(subgraph_definitions: &[SubgraphDefinition]) -> Vec<String> {
+    subgraph_definitions
+        .iter()
+        .filter(|subgraph_definition| {
+            let parser = Parser::new(&subgraph_definition.sdl);
+            let parsed_ast = parser.parse();
+            let doc = parsed_ast.document();
+            doc.definitions().any(|definition| {
+                match definition {
+                    ast::Definition::SchemaExtension(ext) => ext.directives(),
+                    ast::Definition::SchemaDefinition(def) => def.directives(),
+                    _ => None,
+                }
+                .map_or(false, |directives| {
+                    directives.directives().any(|directive| {
+                        directive
+                            .name()
+                            .map_or(false, |directive_name| directive_name.text() == "link")
+                    })
+                })
+            })
+        })
+        .map(|subgraph_definition| subgraph_definition.name.clone())
+        .collect()
+}
+
+/// Replaces the schema source of each overridden subgraph with its file, keeping its
+/// routing URL. Every overridden subgraph has to be in the config, since a new one
+/// wouldn't have a routing URL
+fn apply_subgraph_overrides(
+    supergraph_config: &mut [(String, SubgraphConfig)],
+    subgraph_overrides: &[SubgraphOverride],
+    description: &str,
+) -> RoverResult<()> {
+    for subgraph_override in subgraph_overrides {
+        let subgraph_data = supergraph_config
+            .iter_mut()
+            .find(|(subgraph_name, _)| subgraph_name == &subgraph_override.name)
+            .map(|(_, subgraph_data)| subgraph_data)
+            .ok_or_else(|| {
+                let mut err = RoverError::new(anyhow!(
+                    "Can't override the '{}' subgraph, because it isn't one of the subgraphs in {}.",
+                    subgraph_override.name,
+                    description
+                ));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                    "Pass the name of a subgraph to `--override-subgraph`, i.e. `--override-subgraph products=./products.graphql`.".to_string(),
+                ));
+                err
+            })?;
+        // `file` sources are joined to the config's directory, which leaves absolute paths as they are
+        let file = match std::env::current_dir() {
+            Ok(current_dir) if subgraph_override.file.is_relative() => {
+                Utf8PathBuf::try_from(current_dir)?.join(&subgraph_override.file)
+            }
+            _ => subgraph_override.file.clone(),
+        };
+        subgraph_data.schema = SchemaSource::File { file };
+    }
+    Ok(())
+}
+
+/// Fails if any subgraph's schema would have to be fetched from a `subgraph_url`
+/// or from Apollo Studio, which `--offline` doesn't allow
+fn check_offline_sources(supergraph_config: &[(String, SubgraphConfig)]) -> RoverResult<()> {
+    let remote_subgraphs: Vec<String> = supergraph_config
+        .iter()
+        .filter(|(_, subgraph_data)| {
+            matches!(
+                &subgraph_data.schema,
+                SchemaSource::SubgraphIntrospection { .. } | SchemaSource::Subgraph { .. }
+            )
+        })
+        .map(|(subgraph_name, subgraph_data)| {
+            format!(
+                "'{}' from {}",
+                subgraph_name,
+                describe_source(&subgraph_data.schema)
+            )
+        })
+        .collect();
+    if remote_subgraphs.is_empty() {
+        return Ok(());
+    }
+    let mut err = RoverError::new(anyhow!(
+        "`--offline` can't resolve the schemas of these subgraphs without the network: {}",
+        remote_subgraphs.join(", ")
+    ));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(
+        "Save each of these schemas to a file, i.e. with `rover subgraph introspect` or `rover subgraph fetch`, and use a `file` source for it instead.".to_string(),
+    ));
+    Err(err)
+}
+
+/// Where a subgraph's schema comes from, for attributing errors to the source that failed
+fn describe_source(source: &SchemaSource) -> String {
+    match source {
+        SchemaSource::File { file } => format!("the file {}", file),
+        SchemaSource::SubgraphIntrospection { subgraph_url } => {
+            format!("its subgraph_url {}", subgraph_url)
+        }
+        SchemaSource::Subgraph { graphref, subgraph } => {
+            format!(
+                "the '{}' subgraph of {} in Apollo Studio",
+                subgraph, graphref
+            )
+        }
+        SchemaSource::Sdl { .. } => "its inline sdl".to_string(),
+    }
 }
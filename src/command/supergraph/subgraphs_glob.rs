@@ -0,0 +1,260 @@
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use apollo_federation_types::config::{SchemaSource, SubgraphConfig};
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// The `subgraphs_glob` key of a supergraph config, which adds a subgraph with a `file`
+/// source for every schema file that matches a pattern
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SubgraphsGlobConfig {
+    /// the schema files to add, relative to the supergraph config
+    pattern: String,
+
+    /// the name of each subgraph, with `{dir}` for the name of the directory
+    /// its schema is in and `{stem}` for its file name without the extension
+    name: Option<String>,
+
+    /// the routing URL of each subgraph, with `{name}` for the subgraph's name.
+    /// Composition needs one for every subgraph with a `file` source
+    routing_url: String,
+}
+
+impl SubgraphsGlobConfig {
+    /// Subgraphs are named after their directory, unless the pattern has a wildcard
+    /// in the file name, in which case they're named after their file
+    fn get_name_rule(&self) -> &str {
+        match &self.name {
+            Some(name) => name,
+            None => match self.pattern.rsplit('/').next() {
+                Some(file_name) if file_name.contains(['*', '?', '[']) => "{stem}",
+                _ => "{dir}",
+            },
+        }
+    }
+}
+
+/// Replaces the `subgraphs_glob` key of a supergraph config with an entry in `subgraphs`
+/// for every matching schema file. Subgraphs listed in `subgraphs` take precedence over
+/// matched ones with the same name, so a matched subgraph's config can be overridden.
+///
+/// Configs without a `subgraphs_glob`, and configs that aren't valid YAML,
+/// are returned as they are.
+pub(crate) fn expand_subgraphs_glob(contents: &str, config_dir: &Utf8Path) -> RoverResult<String> {
+    let mut document: Value = match serde_yaml::from_str(contents) {
+        Ok(document) => document,
+        Err(_) => return Ok(contents.to_string()),
+    };
+    let glob = match document
+        .as_mapping_mut()
+        .and_then(|document| document.remove("subgraphs_glob"))
+    {
+        Some(glob) => serde_yaml::from_value::<SubgraphsGlobConfig>(glob)
+            .map_err(|e| invalid_glob(&format!("it isn't a valid config: {}", e)))?,
+        None => return Ok(contents.to_string()),
+    };
+
+    let matched = find_subgraphs(&glob, config_dir)?;
+    let document = document
+        .as_mapping_mut()
+        .ok_or_else(|| invalid_glob("the supergraph config isn't a mapping"))?;
+    if !matches!(document.get("subgraphs"), Some(Value::Mapping(_))) {
+        document.insert("subgraphs".into(), Value::Mapping(Mapping::new()));
+    }
+    if let Some(Value::Mapping(subgraphs)) = document.get_mut("subgraphs") {
+        for (name, subgraph) in matched {
+            if !subgraphs.contains_key(name.as_str()) {
+                subgraphs.insert(name.into(), serde_yaml::to_value(subgraph)?);
+            }
+        }
+    }
+    Ok(serde_yaml::to_string(&document)?)
+}
+
+/// A subgraph for every file that matches the pattern, by name
+fn find_subgraphs(
+    glob: &SubgraphsGlobConfig,
+    config_dir: &Utf8Path,
+) -> RoverResult<BTreeMap<String, SubgraphConfig>> {
+    let pattern = config_dir.join(&glob.pattern);
+    let paths = glob::glob(pattern.as_str())
+        .map_err(|e| invalid_glob(&format!("'{}' is not a valid pattern: {}", glob.pattern, e)))?;
+
+    let mut files_by_name: BTreeMap<String, Vec<Utf8PathBuf>> = BTreeMap::new();
+    for path in paths {
+        let path = Utf8PathBuf::try_from(path.map_err(|e| RoverError::new(anyhow!("{}", e)))?)?;
+        if !path.is_file() {
+            continue;
+        }
+        // `file` sources are relative to the supergraph config
+        let file = path
+            .strip_prefix(config_dir)
+            .map(Utf8Path::to_path_buf)
+            .unwrap_or_else(|_| path.clone());
+        let name = glob
+            .get_name_rule()
+            .replace(
+                "{dir}",
+                file.parent().and_then(Utf8Path::file_name).unwrap_or(""),
+            )
+            .replace("{stem}", file.file_stem().unwrap_or(""));
+        if name.is_empty() {
+            return Err(invalid_glob(&format!(
+                "the subgraph name for {} is empty",
+                file
+            )));
+        }
+        files_by_name.entry(name).or_default().push(file);
+    }
+
+    if files_by_name.is_empty() {
+        let mut err = RoverError::new(anyhow!(
+            "The subgraphs_glob pattern '{}' doesn't match any schema files.",
+            glob.pattern
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(
+            "Make sure the pattern is relative to the directory of the supergraph config."
+                .to_string(),
+        ));
+        return Err(err);
+    }
+    let duplicates: Vec<String> = files_by_name
+        .iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|(name, files)| {
+            let files: Vec<&str> = files.iter().map(Utf8PathBuf::as_str).collect();
+            format!("'{}' ({})", name, files.join(", "))
+        })
+        .collect();
+    if !duplicates.is_empty() {
+        return Err(invalid_glob(&format!(
+            "more than one schema file would be named {}. Use a `name` rule that tells them apart, i.e. '{{dir}}-{{stem}}'",
+            duplicates.join(", ")
+        )));
+    }
+
+    Ok(files_by_name
+        .into_iter()
+        .map(|(name, mut files)| {
+            let subgraph = SubgraphConfig {
+                routing_url: Some(glob.routing_url.replace("{name}", &name)),
+                schema: SchemaSource::File {
+                    file: files.remove(0),
+                },
+            };
+            (name, subgraph)
+        })
+        .collect())
+}
+
+fn invalid_glob(message: &str) -> RoverError {
+    let mut err = RoverError::new(anyhow!(
+        "The subgraphs_glob of the supergraph config is invalid: {}.",
+        message
+    ));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(
+        "Set `subgraphs_glob` to a `pattern`, i.e. `./services/*/schema.graphql`, and a `routing_url`, i.e. `http://localhost:4000/{name}/graphql`, with an optional `name`.".to_string(),
+    ));
+    err
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use apollo_federation_types::config::SupergraphConfig;
+    use assert_fs::TempDir;
+
+    use super::*;
+
+    fn write_services(names: &[&str]) -> (TempDir, Utf8PathBuf) {
+        let tmp_home = TempDir::new().unwrap();
+        let config_dir = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        for name in names {
+            let service_dir = config_dir.join("services").join(name);
+            fs::create_dir_all(&service_dir).unwrap();
+            fs::write(
+                service_dir.join("schema.graphql"),
+                "type Query { a: String }",
+            )
+            .unwrap();
+        }
+        (tmp_home, config_dir)
+    }
+
+    #[test]
+    fn it_adds_a_subgraph_for_each_matching_file() {
+        let (_tmp_home, config_dir) = write_services(&["products", "reviews", "users"]);
+        let config = r#"federation_version: 2
+subgraphs_glob:
+  pattern: ./services/*/schema.graphql
+  routing_url: http://localhost:4000/{name}/graphql
+subgraphs:
+  users:
+    routing_url: https://users.example.com
+    schema:
+      file: ./users.graphql
+"#;
+        let expanded = expand_subgraphs_glob(config, &config_dir).unwrap();
+        let expected = r#"federation_version: 2
+subgraphs:
+  products:
+    routing_url: http://localhost:4000/products/graphql
+    schema:
+      file: services/products/schema.graphql
+  reviews:
+    routing_url: http://localhost:4000/reviews/graphql
+    schema:
+      file: services/reviews/schema.graphql
+  users:
+    routing_url: https://users.example.com
+    schema:
+      file: ./users.graphql
+"#;
+        assert_eq!(
+            serde_yaml::from_str::<Value>(&expanded).unwrap(),
+            serde_yaml::from_str::<Value>(expected).unwrap()
+        );
+        assert!(SupergraphConfig::new_from_yaml(&expanded).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_globs_that_match_nothing_or_collide() {
+        let (_tmp_home, config_dir) = write_services(&["products"]);
+        assert!(expand_subgraphs_glob(
+            "subgraphs_glob:\n  pattern: ./schemas/*.graphql\n  routing_url: http://localhost:4000\n",
+            &config_dir
+        )
+        .is_err());
+
+        fs::write(
+            config_dir.join("services").join("schema.graphql"),
+            "type Query { b: String }",
+        )
+        .unwrap();
+        let config = "subgraphs_glob:\n  pattern: ./services/**/*.graphql\n  routing_url: http://localhost:4000/{name}\n";
+        let message = expand_subgraphs_glob(config, &config_dir)
+            .unwrap_err()
+            .message();
+        assert!(message.contains("more than one schema file would be named 'schema'"));
+    }
+
+    #[test]
+    fn it_requires_a_routing_url() {
+        let (_tmp_home, config_dir) = write_services(&["products"]);
+        for config in [
+            "subgraphs_glob: ./services/*/schema.graphql\n",
+            "subgraphs_glob:\n  pattern: ./services/*/schema.graphql\n",
+        ] {
+            let message = expand_subgraphs_glob(config, &config_dir)
+                .unwrap_err()
+                .message();
+            assert!(message.contains("subgraphs_glob of the supergraph config is invalid"));
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use clap::Parser;
 use serde::Serialize;
 
-use crate::options::TemplateOpt;
+use crate::options::{GithubTemplate, ListItem, ListOpts, ListValue, TemplateOpt};
 use crate::{RoverOutput, RoverResult};
 
 use super::templates::GithubTemplates;
@@ -10,6 +10,9 @@ use super::templates::GithubTemplates;
 pub struct List {
     #[clap(flatten)]
     options: TemplateOpt,
+
+    #[clap(flatten)]
+    list: ListOpts,
 }
 
 impl List {
@@ -18,6 +21,22 @@ impl List {
         if let Some(project_language) = self.options.language {
             templates = templates.filter_language(project_language);
         }
-        Ok(RoverOutput::TemplateList(templates.values()?))
+        Ok(RoverOutput::TemplateList(
+            self.list.apply(templates.values()?)?,
+        ))
+    }
+}
+
+impl ListItem for GithubTemplate {
+    const FIELDS: &'static [&'static str] = &["name", "id", "language", "repo_url"];
+
+    fn get_field(&self, field: &str) -> Option<ListValue> {
+        match field {
+            "name" => Some(ListValue::Text(self.display.to_string())),
+            "id" => Some(ListValue::Text(self.id.to_string())),
+            "language" => Some(ListValue::Text(self.language.to_string())),
+            "repo_url" => Some(ListValue::Text(self.git_url.to_string())),
+            _ => None,
+        }
     }
 }
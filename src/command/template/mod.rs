@@ -1,9 +1,12 @@
 mod list;
+mod search;
 mod templates;
 mod r#use;
+mod variables;
 
 pub use list::List;
 pub use r#use::Use;
+pub use search::Search;
 
 use clap::Parser;
 use serde::Serialize;
@@ -25,6 +28,9 @@ enum Command {
 
     /// List available templates that can be used
     List(List),
+
+    /// Search the available templates by keyword
+    Search(Search),
 }
 
 impl Template {
@@ -32,6 +38,7 @@ impl Template {
         match &self.command {
             Command::Use(use_template) => use_template.run(client_config),
             Command::List(list) => list.run(),
+            Command::Search(search) => search.run(),
         }
     }
 }
@@ -0,0 +1,33 @@
+use clap::Parser;
+use serde::Serialize;
+
+use crate::options::{ListOpts, TemplateOpt};
+use crate::{RoverOutput, RoverResult};
+
+use super::templates::GithubTemplates;
+
+#[derive(Clone, Debug, Parser, Serialize)]
+pub struct Search {
+    /// The keywords to search for, ex. "apollo server".
+    ///
+    /// A template matches when its ID, name, or language contains every keyword, ignoring case.
+    query: String,
+
+    #[clap(flatten)]
+    options: TemplateOpt,
+
+    #[clap(flatten)]
+    list: ListOpts,
+}
+
+impl Search {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        let mut templates = GithubTemplates::new().search(&self.query);
+        if let Some(project_language) = self.options.language {
+            templates = templates.filter_language(project_language);
+        }
+        Ok(RoverOutput::TemplateList(
+            self.list.apply(templates.values()?)?,
+        ))
+    }
+}
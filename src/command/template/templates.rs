@@ -93,6 +93,27 @@ impl GithubTemplates {
         self
     }
 
+    /// Filter templates by keywords, keeping the templates whose ID, name, or language
+    /// contain every word of `query`, ignoring case
+    #[must_use]
+    pub fn search(mut self, query: &str) -> Self {
+        let keywords: Vec<String> = query
+            .split_whitespace()
+            .map(|keyword| keyword.to_lowercase())
+            .collect();
+        self.templates.retain(|template| {
+            let haystack = format!(
+                "{} {} {}",
+                template.id,
+                template.display,
+                template.language.descriptor()
+            )
+            .to_lowercase();
+            keywords.iter().all(|keyword| haystack.contains(keyword))
+        });
+        self
+    }
+
     /// Consume self and return the list of templates that were selected.
     ///
     /// # Errors
@@ -123,3 +144,31 @@ impl GithubTemplates {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_searches_templates_by_every_keyword() {
+        let ids = |templates: GithubTemplates| {
+            templates
+                .templates
+                .into_iter()
+                .map(|template| template.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(GithubTemplates::new().search("apollo SERVER ts")),
+            vec!["subgraph-typescript-apollo-server"]
+        );
+        assert_eq!(
+            ids(GithubTemplates::new().search("fastapi")),
+            vec![
+                "subgraph-python-strawberry-fastapi",
+                "subgraph-python-ariadne-fastapi"
+            ]
+        );
+        assert!(GithubTemplates::new().search("haskell").values().is_err());
+    }
+}
@@ -4,14 +4,15 @@ use anyhow::{anyhow, Context};
 use camino::Utf8PathBuf;
 use clap::{error::ErrorKind as ClapErrorKind, CommandFactory, Parser};
 use dialoguer::Input;
+use rover_std::tmp;
 use serde::Serialize;
 
 use crate::cli::Rover;
-use crate::options::TemplateOpt;
-use crate::utils::client::StudioClientConfig;
+use crate::options::{extract_local_template, TemplateOpt};
+use crate::utils::{client::StudioClientConfig, parsers::parse_template_var};
 use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
 
-use super::templates::GithubTemplates;
+use super::{templates::GithubTemplates, variables::apply_template_variables};
 
 #[derive(Clone, Debug, Parser, Serialize)]
 pub struct Use {
@@ -20,9 +21,33 @@ pub struct Use {
 
     /// The ID for the official template to use.
     /// Use `rover template list` to see available options.
-    #[arg(short = 't', long = "template")]
+    ///
+    /// Pin the template to a git tag or commit SHA with `<ID>@<REVISION>`,
+    /// ex. subgraph-rust-async-graphql@v1.2.0. Without one, the head of its `main` branch is used.
+    #[arg(short = 't', long = "template", value_name = "ID[@REVISION]")]
     pub template: Option<String>,
 
+    /// The path to a local template to use instead of an official one,
+    /// either a directory or a `.tar.gz` tarball of one.
+    ///
+    /// This doesn't need a network connection, so it also works for company-internal templates.
+    #[arg(long = "path", value_name = "TEMPLATE_PATH", conflicts_with_all = ["template", "language"])]
+    pub template_path: Option<Utf8PathBuf>,
+
+    /// A value for one of the variables the template declares, ex. --var package_name=reviews.
+    ///
+    /// Rover asks for the value of every variable that isn't passed with `--var`.
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_template_var)]
+    #[serde(skip_serializing)]
+    pub vars: Vec<(String, String)>,
+
+    /// Never ask for anything, for scripts and CI.
+    ///
+    /// The template and `<PATH>` have to be passed, and the template's variables
+    /// that aren't passed with `--var` use their defaults.
+    #[arg(long = "yes", visible_alias = "defaults")]
+    pub yes: bool,
+
     /// The relative or absolute path to create the template directory.
     ///
     /// If omitted, the template will be extracted to a child directory
@@ -32,13 +57,41 @@ pub struct Use {
 
 impl Use {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        tmp::cleanup_on_ctrl_c()?;
+        // a local template is copied from disk, without looking up the official templates
+        if let Some(template_path) = &self.template_path {
+            let path = self.get_or_prompt_path()?;
+            extract_local_template(template_path, &path, client_config.get_progress())?;
+            apply_template_variables(&path, &self.vars, self.is_interactive())?;
+            return Ok(RoverOutput::LocalTemplateUseSuccess {
+                template_path: template_path.clone(),
+                path,
+            });
+        }
+
         // initialize the available templates
         let templates = GithubTemplates::new();
 
+        // a template ID may pin a revision after an `@`
+        let (template_id, revision) = match &self.template {
+            Some(template) => {
+                let (template_id, revision) = get_template_revision(template)?;
+                (Some(template_id), revision)
+            }
+            None => (None, None),
+        };
+
         // find the template to extract
-        let template = if let Some(template_id) = &self.template {
+        let template = if let Some(template_id) = template_id {
             // if they specify an ID, get it
             templates.get(template_id)
+        } else if self.yes {
+            Rover::command()
+                .error(
+                    ClapErrorKind::MissingRequiredArgument,
+                    "--template or --path is required with --yes",
+                )
+                .exit();
         } else {
             // otherwise, ask them what language they want to use
             let project_language = self.options.get_or_prompt_language()?;
@@ -52,15 +105,30 @@ impl Use {
         let path = self.get_or_prompt_path()?;
 
         // download and extract a tarball from github
-        template.extract_github_tarball(&path, &client_config.get_reqwest_client()?)?;
+        let revision = template.extract_github_tarball(
+            &path,
+            revision,
+            &client_config.get_reqwest_client()?,
+            client_config.get_progress(),
+        )?;
+        apply_template_variables(&path, &self.vars, self.is_interactive())?;
 
-        Ok(RoverOutput::TemplateUseSuccess { template, path })
+        Ok(RoverOutput::TemplateUseSuccess {
+            template,
+            path,
+            revision,
+        })
+    }
+
+    /// Whether this can ask for what wasn't passed, which it can't with `--yes` or without a TTY
+    fn is_interactive(&self) -> bool {
+        !self.yes && atty::is(atty::Stream::Stderr)
     }
 
     pub(crate) fn get_or_prompt_path(&self) -> RoverResult<Utf8PathBuf> {
         let path: Utf8PathBuf = if let Some(path) = &self.path {
             Ok::<Utf8PathBuf, RoverError>(path.clone())
-        } else if atty::is(atty::Stream::Stderr) {
+        } else if self.is_interactive() {
             let mut input = Input::new();
             input.with_prompt("What path would you like to extract the template to?");
             let path: Utf8PathBuf = input.interact_text()?;
@@ -69,7 +137,7 @@ impl Use {
             let mut cmd = Rover::command();
             cmd.error(
                 ClapErrorKind::MissingRequiredArgument,
-                "<PATH> is required when not attached to a TTY",
+                "<PATH> is required with --yes or when not attached to a TTY",
             )
             .exit();
         }?;
@@ -99,3 +167,57 @@ impl Use {
         }
     }
 }
+
+/// Splits a `--template` into the template's ID and the git tag or commit SHA it's pinned to,
+/// which can only have the characters of a tag or SHA, since it's part of the tarball's URL
+fn get_template_revision(template: &str) -> RoverResult<(&str, Option<&str>)> {
+    match template.split_once('@') {
+        Some((template_id, revision)) => {
+            let is_valid = !revision.is_empty()
+                && !revision.starts_with('.')
+                && !revision.contains("..")
+                && revision
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'));
+            if is_valid {
+                Ok((template_id, Some(revision)))
+            } else {
+                let mut err = RoverError::new(anyhow!(
+                    "'{}' is not a valid revision for the '{}' template",
+                    revision,
+                    template_id
+                ));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                    "Pin the template to a git tag or commit SHA, like `--template {}@v1.0.0`.",
+                    template_id
+                )));
+                Err(err)
+            }
+        }
+        None => Ok((template, None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_splits_pinned_template_revisions() {
+        assert!(matches!(
+            get_template_revision("subgraph-rust-async-graphql"),
+            Ok(("subgraph-rust-async-graphql", None))
+        ));
+        assert!(matches!(
+            get_template_revision("subgraph-rust-async-graphql@v1.2.0"),
+            Ok(("subgraph-rust-async-graphql", Some("v1.2.0")))
+        ));
+        assert!(matches!(
+            get_template_revision("subgraph-go-gqlgen@3f2c1e9"),
+            Ok(("subgraph-go-gqlgen", Some("3f2c1e9")))
+        ));
+        assert!(get_template_revision("subgraph-go-gqlgen@").is_err());
+        assert!(get_template_revision("subgraph-go-gqlgen@main?x=1").is_err());
+        assert!(get_template_revision("subgraph-go-gqlgen@../main").is_err());
+    }
+}
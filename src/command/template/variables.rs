@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context};
+use camino::{Utf8Path, Utf8PathBuf};
+use console::Term;
+use dialoguer::Input;
+use rover_std::Fs;
+use serde::Deserialize;
+
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// The file a template declares its variables in, which isn't copied to the new project
+pub const TEMPLATE_MANIFEST_FILE_NAME: &str = "rover-template.yaml";
+
+/// The `rover-template.yaml` at the root of a template
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateManifest {
+    #[serde(default)]
+    variables: Vec<TemplateVariable>,
+}
+
+/// A variable of a template, which replaces each `{{name}}` in the template's files and paths
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateVariable {
+    name: String,
+    prompt: Option<String>,
+    default: Option<String>,
+}
+
+impl TemplateVariable {
+    /// Asks for the variable's value if `interactive`, and uses its default otherwise
+    fn get_or_prompt_value(&self, interactive: bool) -> RoverResult<String> {
+        if interactive {
+            let mut input = Input::<String>::new();
+            input.with_prompt(self.prompt.as_deref().unwrap_or(&self.name));
+            if let Some(default) = &self.default {
+                input.default(default.clone());
+            }
+            Ok(input.interact_text_on(&Term::stderr())?)
+        } else if let Some(default) = &self.default {
+            Ok(default.clone())
+        } else {
+            let mut err = RoverError::new(anyhow!(
+                "The template's '{}' variable has no default value",
+                self.name
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                "Pass its value with `--var {}=<VALUE>` with `--yes` or when not attached to a TTY.",
+                self.name
+            )));
+            Err(err)
+        }
+    }
+}
+
+/// Fills in the variables a template declares in its `rover-template.yaml` once it's extracted
+/// to `template_path`, with the `--var` values passed for them, and asks for the rest if `interactive`
+pub fn apply_template_variables(
+    template_path: &Utf8Path,
+    vars: &[(String, String)],
+    interactive: bool,
+) -> RoverResult<()> {
+    let manifest_path = template_path.join(TEMPLATE_MANIFEST_FILE_NAME);
+    let manifest: TemplateManifest = if manifest_path.exists() {
+        serde_yaml::from_str(&Fs::read_file(&manifest_path)?)
+            .with_context(|| format!("could not parse the template's {}", &manifest_path))?
+    } else {
+        TemplateManifest::default()
+    };
+
+    if let Some((key, _)) = vars
+        .iter()
+        .find(|(key, _)| !manifest.variables.iter().any(|v| &v.name == key))
+    {
+        let mut err = RoverError::new(anyhow!("The template has no '{}' variable", key));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(
+            if manifest.variables.is_empty() {
+                "This template doesn't declare any variables, so remove `--var`.".to_string()
+            } else {
+                format!(
+                    "This template declares these variables: {}.",
+                    manifest
+                        .variables
+                        .iter()
+                        .map(|v| v.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            },
+        ));
+        return Err(err);
+    }
+
+    let mut values = BTreeMap::new();
+    for variable in &manifest.variables {
+        // the last `--var` of a variable takes precedence, like repeated flags do elsewhere
+        let value = match vars.iter().rev().find(|(key, _)| key == &variable.name) {
+            Some((_, value)) => value.clone(),
+            None => variable.get_or_prompt_value(interactive)?,
+        };
+        values.insert(format!("{{{{{}}}}}", variable.name), value);
+    }
+
+    if manifest_path.exists() {
+        Fs::remove_file(&manifest_path)?;
+    }
+    if values.is_empty() {
+        Ok(())
+    } else {
+        substitute_dir(template_path, &values)
+    }
+}
+
+/// Replaces each placeholder in the contents and names of every file under `dir`.
+/// Files that aren't valid UTF-8, i.e. images, are copied as they are
+fn substitute_dir(dir: &Utf8Path, values: &BTreeMap<String, String>) -> RoverResult<()> {
+    let entry_paths: Vec<Utf8PathBuf> = Fs::get_dir_entries(dir)?
+        .flatten()
+        .map(|entry| entry.path().to_path_buf())
+        .collect();
+    for entry_path in entry_paths {
+        if entry_path.is_dir() {
+            substitute_dir(&entry_path, values)?;
+        } else if let Ok(contents) = String::from_utf8(std::fs::read(&entry_path)?) {
+            let substituted = substitute(&contents, values);
+            if substituted != contents {
+                Fs::write_file(&entry_path, substituted)?;
+            }
+        }
+
+        if let Some(entry_name) = entry_path.file_name() {
+            let substituted_name = substitute(entry_name, values);
+            if substituted_name != entry_name {
+                let substituted_path = dir.join(substituted_name);
+                std::fs::rename(&entry_path, &substituted_path).with_context(|| {
+                    format!("could not rename {} to {}", &entry_path, &substituted_path)
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn substitute(text: &str, values: &BTreeMap<String, String>) -> String {
+    values
+        .iter()
+        .fold(text.to_string(), |text, (placeholder, value)| {
+            text.replace(placeholder, value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn it_substitutes_variables_in_file_contents_and_paths() {
+        let tmp_home = TempDir::new().unwrap();
+        let template_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        Fs::write_file(
+            template_path.join(TEMPLATE_MANIFEST_FILE_NAME),
+            "variables:\n  - name: package_name\n  - name: port\n    default: \"4001\"\n",
+        )
+        .unwrap();
+        Fs::create_dir_all(template_path.join("src/{{package_name}}")).unwrap();
+        Fs::write_file(
+            template_path.join("src/{{package_name}}/{{package_name}}.graphql.template"),
+            "# {{package_name}} listens on {{port}}, unlike {{other}}",
+        )
+        .unwrap();
+
+        apply_template_variables(
+            &template_path,
+            &[
+                ("package_name".to_string(), "reviews".to_string()),
+                ("port".to_string(), "4002".to_string()),
+            ],
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            Fs::read_file(template_path.join("src/reviews/reviews.graphql.template")).unwrap(),
+            "# reviews listens on 4002, unlike {{other}}"
+        );
+        assert!(!template_path.join(TEMPLATE_MANIFEST_FILE_NAME).exists());
+        assert!(!template_path.join("src/{{package_name}}").exists());
+
+        assert!(apply_template_variables(
+            &template_path,
+            &[("port".to_string(), "4002".to_string())],
+            false
+        )
+        .is_err());
+    }
+}
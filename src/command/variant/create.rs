@@ -0,0 +1,51 @@
+use clap::Parser;
+use serde::Serialize;
+
+use rover_client::operations::variant::create::{self, VariantCreateInput};
+use rover_client::shared::GitContext;
+use rover_std::Style;
+
+use crate::options::{GraphRefOpt, ProfileOpt};
+use crate::utils::client::StudioClientConfig;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Create {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The name of the variant to copy schemas from. The latest schema of every
+    /// subgraph (or the graph's schema, if it isn't federated) is published to the new variant
+    #[arg(long = "from", value_name = "VARIANT", default_value = "current")]
+    from: String,
+}
+
+impl Create {
+    pub fn run(
+        &self,
+        client_config: StudioClientConfig,
+        git_context: GitContext,
+    ) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        eprintln!(
+            "Creating {} from the {} variant using credentials from the {} profile.",
+            Style::Link.paint(self.graph.graph_ref.to_string()),
+            Style::Link.paint(&self.from),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let create_response = create::run(
+            VariantCreateInput {
+                graph_ref: self.graph.graph_ref.clone(),
+                source_variant: self.from.clone(),
+                git_context,
+            },
+            &client,
+        )?;
+
+        Ok(RoverOutput::VariantCreateResponse(create_response))
+    }
+}
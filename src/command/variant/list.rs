@@ -0,0 +1,65 @@
+use clap::Parser;
+use serde::Serialize;
+
+use rover_client::operations::variant::list::{self, VariantInfo, VariantListInput};
+use rover_std::Style;
+
+use crate::options::{GraphRefOpt, ListItem, ListOpts, ListValue, ProfileOpt};
+use crate::utils::client::StudioClientConfig;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct List {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    #[clap(flatten)]
+    list: ListOpts,
+}
+
+impl List {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        eprintln!(
+            "Listing variants for {} using credentials from the {} profile.",
+            Style::Link.paint(&self.graph.graph_ref.name),
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let mut list_response = list::run(
+            VariantListInput {
+                graph_ref: self.graph.graph_ref.clone(),
+            },
+            &client,
+        )?;
+        list_response.variants = self.list.apply(list_response.variants)?;
+
+        Ok(RoverOutput::VariantList(list_response))
+    }
+}
+
+impl ListItem for VariantInfo {
+    const FIELDS: &'static [&'static str] = &[
+        "name",
+        "subgraph_count",
+        "last_published_at",
+        "source_variant",
+        "url",
+    ];
+
+    fn get_field(&self, field: &str) -> Option<ListValue> {
+        match field {
+            "name" => Some(ListValue::Text(self.name.clone())),
+            "subgraph_count" => self
+                .subgraph_count
+                .map(|count| ListValue::Number(count as u64)),
+            "last_published_at" => self.last_published_at.map(ListValue::Time),
+            "source_variant" => self.source_variant.clone().map(ListValue::Text),
+            "url" => self.url.clone().map(ListValue::Text),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+mod create;
+mod list;
+
+pub use create::Create;
+pub use list::List;
+
+use clap::Parser;
+use serde::Serialize;
+
+use rover_client::shared::GitContext;
+
+use crate::command::graph;
+use crate::utils::client::StudioClientConfig;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Variant {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Create a variant from the latest schemas of another variant of the same graph
+    Create(create::Create),
+
+    /// Delete a variant from the Apollo graph registry, like `rover graph delete`
+    Delete(graph::Delete),
+
+    /// List the variants of a graph in the Apollo graph registry
+    List(list::List),
+}
+
+impl Variant {
+    pub fn run(
+        &self,
+        client_config: StudioClientConfig,
+        git_context: GitContext,
+    ) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Create(command) => command.run(client_config, git_context),
+            Command::Delete(command) => command.run(client_config),
+            Command::List(command) => command.run(client_config),
+        }
+    }
+}
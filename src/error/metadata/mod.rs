@@ -7,7 +7,10 @@ pub use suggestion::RoverErrorSuggestion;
 use houston::HoustonProblem;
 use rover_client::RoverClientError;
 
-use crate::{options::JsonVersion, utils::env::RoverEnvKey};
+use crate::{
+    options::JsonVersion,
+    utils::{env::RoverEnvKey, schema_lint::LintFailure},
+};
 
 use std::env;
 
@@ -209,6 +212,10 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
                     }),
                     Some(RoverErrorCode::E008),
                 ),
+                RoverClientError::NoSchemaForHash { .. } => (
+                    Some(RoverErrorSuggestion::CheckSchemaHash),
+                    Some(RoverErrorCode::E042),
+                ),
                 RoverClientError::NoSubgraphInGraph {
                     invalid_subgraph: _,
                     valid_subgraphs,
@@ -272,6 +279,28 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
                     Some(RoverErrorSuggestion::IncreaseChecksTimeout { url: url.clone() }),
                     None,
                 ),
+                RoverClientError::RequestBudgetExceeded { .. }
+                | RoverClientError::RequestPlanExceedsBudget { .. } => (
+                    Some(RoverErrorSuggestion::IncreaseMaxRequests),
+                    Some(RoverErrorCode::E041),
+                ),
+                RoverClientError::VariantCreateIncomplete {
+                    graph_ref,
+                    published_subgraphs,
+                    rolled_back,
+                    ..
+                } => (
+                    Some(RoverErrorSuggestion::RetryVariantCreate {
+                        graph_ref: graph_ref.clone(),
+                        published_subgraphs: published_subgraphs.clone(),
+                        rolled_back: *rolled_back,
+                    }),
+                    None,
+                ),
+                RoverClientError::InvalidFixture { .. } => (
+                    Some(RoverErrorSuggestion::RecordFixtureAgain),
+                    Some(RoverErrorCode::E044),
+                ),
             };
             return RoverErrorMetadata {
                 json_version: JsonVersion::default(),
@@ -348,6 +377,15 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
             };
         }
 
+        if error.downcast_ref::<LintFailure>().is_some() {
+            return RoverErrorMetadata {
+                json_version: JsonVersion::default(),
+                suggestion: None,
+                code: Some(RoverErrorCode::E043),
+                skip_printing_cause,
+            };
+        }
+
         RoverErrorMetadata::default()
     }
 }
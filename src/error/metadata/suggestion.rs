@@ -64,6 +64,14 @@ pub enum RoverErrorSuggestion {
         graph_ref: GraphRef,
     },
     UpgradePlan,
+    IncreaseMaxRequests,
+    CheckSchemaHash,
+    RecordFixtureAgain,
+    RetryVariantCreate {
+        graph_ref: GraphRef,
+        published_subgraphs: Vec<String>,
+        rolled_back: bool,
+    },
 }
 
 impl Display for RoverErrorSuggestion {
@@ -212,6 +220,21 @@ IncreaseClientTimeout => "You can try increasing the timeout value by passing a
 IncreaseChecksTimeout {url} => format!("You can try increasing the timeout value by setting APOLLO_CHECKS_TIMEOUT_SECONDS to a higher value in your env. The default value is 300 seconds. You can also view the live check progress by visiting {}.", Style::Link.paint(url.clone().unwrap_or_else(|| "https://studio.apollographql.com".to_string()))),
 FixChecksInput { graph_ref } => format!("Graph {} has no published schema or is not a composition variant. Please publish a schema or use a different variant.", Style::Link.paint(graph_ref.to_string())),
 UpgradePlan => "Rover has likely reached rate limits while running graph or subgraph checks. Please try again later or contact your graph admin about upgrading your billing plan.".to_string(),
+IncreaseMaxRequests => format!("You can allow more requests by passing a higher value to the {} option, or run the command on fewer subgraphs at a time.", Style::Command.paint("`--max-requests`")),
+            CheckSchemaHash => "Make sure you are passing the full SHA256 hash of a schema published to this graph. You can find the hashes of previous publishes on the graph's History page in Apollo Studio.".to_string(),
+            RecordFixtureAgain => format!("Record the fixture again by running the same command with {} instead of {}.", Style::Command.paint("`--record`"), Style::Command.paint("`--replay`")),
+            RetryVariantCreate { graph_ref, published_subgraphs, rolled_back } => {
+                if *rolled_back {
+                    format!("{} was deleted again, so you can run this command again once the error is fixed.", Style::Link.paint(graph_ref.to_string()))
+                } else {
+                    format!(
+                        "{} could not be deleted again, and has these subgraphs published to it: {}. Delete it with {} before running this command again.",
+                        Style::Link.paint(graph_ref.to_string()),
+                        published_subgraphs.join(", "),
+                        Style::Command.paint(format!("`rover variant delete {}`", graph_ref))
+                    )
+                }
+            }
         };
         write!(formatter, "{}", &suggestion)
     }
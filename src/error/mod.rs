@@ -5,6 +5,7 @@ pub use metadata::{RoverErrorCode, RoverErrorMetadata, RoverErrorSuggestion};
 pub type RoverResult<T> = std::result::Result<T, RoverError>;
 
 use calm_io::{stderr, stdoutln};
+use rover_client::shared::CheckResponse;
 use rover_client::RoverClientError;
 use rover_std::Style;
 use serde::ser::SerializeStruct;
@@ -18,7 +19,9 @@ use std::io;
 
 use apollo_federation_types::build::BuildErrors;
 
+use crate::command::run::RunFailure;
 use crate::options::JsonVersion;
+use crate::utils::schema_lint::LintFailure;
 
 /// A specialized `Error` type for Rover that wraps `anyhow`
 /// and provides some extra `Metadata` for end users depending
@@ -92,10 +95,28 @@ impl RoverError {
             stdoutln!("{}", check_response.get_table())?;
         }
 
+        if let Some(LintFailure { response }) = self.error.downcast_ref::<LintFailure>() {
+            stdoutln!("{}", response.get_table())?;
+        }
+
+        if let Some(RunFailure { summary }) = self.error.downcast_ref::<RunFailure>() {
+            stdoutln!("{}", summary.get_table())?;
+        }
+
         stderr!("{}", self)?;
         Ok(())
     }
 
+    /// The response of a check that finished with failing changes, if this is that failure
+    pub(crate) fn get_check_response(&self) -> Option<&CheckResponse> {
+        match self.error.downcast_ref::<RoverClientError>() {
+            Some(RoverClientError::OperationCheckFailure { check_response, .. }) => {
+                Some(check_response)
+            }
+            _ => None,
+        }
+    }
+
     pub(crate) fn get_internal_data_json(&self) -> Value {
         if let Some(RoverClientError::OperationCheckFailure {
             graph_ref: _,
@@ -104,6 +125,14 @@ impl RoverError {
         {
             return check_response.get_json();
         }
+
+        if let Some(LintFailure { response }) = self.error.downcast_ref::<LintFailure>() {
+            return response.get_json();
+        }
+
+        if let Some(RunFailure { summary }) = self.error.downcast_ref::<RunFailure>() {
+            return summary.get_json();
+        }
         Value::Null
     }
 
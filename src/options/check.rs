@@ -1,9 +1,12 @@
 use anyhow::anyhow;
+use camino::Utf8PathBuf;
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 
 use rover_client::shared::ValidationPeriod;
 
+use crate::options::CheckReportOpt;
+
 use std::io;
 
 #[derive(Debug, Serialize, Deserialize, Parser)]
@@ -26,6 +29,16 @@ pub struct CheckConfigOpts {
     /// If the check should be run asynchronously and exit without waiting for check results
     #[arg(long)]
     pub background: bool,
+
+    /// The config file to read the check policy from, instead of the nearest `.rover.yaml`
+    /// in the current directory or its parents. Flags take precedence over the policy
+    #[arg(long, value_name = "PATH")]
+    #[serde(skip)]
+    pub rover_config: Option<Utf8PathBuf>,
+
+    #[clap(flatten)]
+    #[serde(skip)]
+    pub report: CheckReportOpt,
 }
 
 fn parse_query_count_threshold(threshold: &str) -> Result<i64, io::Error> {
@@ -0,0 +1,92 @@
+use camino::Utf8PathBuf;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use rover_client::shared::GraphRef;
+use rover_std::{Fs, Style};
+
+use crate::options::JsonOutput;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Parser)]
+pub struct CheckReportOpt {
+    /// Write a JSON report and a plain text report of the finished check to this directory,
+    /// named by the graph ref and the ID of the check workflow, i.e.
+    /// `my-graph@current-<WORKFLOW_ID>.json`. The directory is created if it doesn't exist
+    #[arg(long = "report-dir", value_name = "DIR")]
+    pub report_dir: Option<Utf8PathBuf>,
+}
+
+impl CheckReportOpt {
+    /// Writes the reports of a finished check to the `--report-dir` directory.
+    /// Failed checks are reported too, but errors that kept the check from finishing aren't
+    pub fn write(
+        &self,
+        graph_ref: &GraphRef,
+        workflow_id: &str,
+        result: &RoverResult<RoverOutput>,
+    ) -> RoverResult<()> {
+        let report_dir = match &self.report_dir {
+            Some(report_dir) => report_dir,
+            None => return Ok(()),
+        };
+        let (json, text) = match result {
+            Ok(output) => (
+                JsonOutput::from(output.clone()),
+                output.get_stdout()?.unwrap_or_default(),
+            ),
+            Err(error) => match error.get_check_response() {
+                Some(check_response) => (
+                    JsonOutput::from(error),
+                    format!("{}\n{}", check_response.get_table(), error.message()),
+                ),
+                None => return Ok(()),
+            },
+        };
+
+        Fs::create_dir_all(report_dir)?;
+        let file_stem = get_file_stem(graph_ref, workflow_id);
+        let json_path = report_dir.join(format!("{}.json", &file_stem));
+        let text_path = report_dir.join(format!("{}.txt", &file_stem));
+        Fs::write_file(&json_path, serde_json::to_string_pretty(&json)?)?;
+        Fs::write_file(&text_path, text)?;
+        eprintln!(
+            "Wrote the check report to {} and {}",
+            Style::Path.paint(&json_path),
+            Style::Path.paint(&text_path)
+        );
+        Ok(())
+    }
+}
+
+/// The name reports of a check share, without an extension. Characters that aren't safe
+/// in file names, which variant names can contain, are replaced with `_`
+fn get_file_stem(graph_ref: &GraphRef, workflow_id: &str) -> String {
+    format!("{}-{}", graph_ref, workflow_id)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '@' | '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_names_reports_by_graph_ref_and_workflow() {
+        let graph_ref = GraphRef {
+            name: "my-graph".to_string(),
+            variant: "feature/reviews".to_string(),
+        };
+        assert_eq!(
+            get_file_stem(&graph_ref, "2f3a1c"),
+            "my-graph@feature_reviews-2f3a1c"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::schema_transform::DirectiveFilter;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Parser)]
+pub struct DirectiveFilterOpt {
+    /// Only keep applications of these directives in the printed schema,
+    /// i.e. `--include-directives deprecated,key`. Names ending in `*`
+    /// match every directive starting with that prefix
+    #[arg(
+        long = "include-directives",
+        value_name = "DIRECTIVES",
+        value_delimiter = ',',
+        conflicts_with = "exclude_directives"
+    )]
+    pub include_directives: Option<Vec<String>>,
+
+    /// Remove applications of these directives from the printed schema,
+    /// i.e. `--exclude-directives 'join__*'`. Names ending in `*`
+    /// match every directive starting with that prefix
+    #[arg(
+        long = "exclude-directives",
+        value_name = "DIRECTIVES",
+        value_delimiter = ','
+    )]
+    pub exclude_directives: Option<Vec<String>>,
+}
+
+impl DirectiveFilterOpt {
+    pub fn get_filter(&self) -> DirectiveFilter {
+        match (&self.include_directives, &self.exclude_directives) {
+            (Some(include), _) => DirectiveFilter::Include(include.clone()),
+            (None, Some(exclude)) => DirectiveFilter::Exclude(exclude.clone()),
+            (None, None) => DirectiveFilter::KeepAll,
+        }
+    }
+}
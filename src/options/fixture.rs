@@ -0,0 +1,50 @@
+use anyhow::Context;
+use camino::Utf8PathBuf;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use rover_client::blocking::{Fixture, FixtureSession};
+use rover_std::{Fs, Style};
+
+use crate::RoverResult;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Parser)]
+pub struct FixtureOpt {
+    /// Save every request this command makes to Apollo Studio, and the responses to them,
+    /// to a JSON fixture at this path. Git author details are redacted, but the fixture still
+    /// holds the schema and check results, so keep it out of public repositories
+    #[arg(long = "record", value_name = "FIXTURE", conflicts_with = "replay")]
+    pub record: Option<Utf8PathBuf>,
+
+    /// Answer every request this command makes from a fixture saved with `--record`
+    /// instead of sending it to Apollo Studio. No credentials are needed to replay a fixture
+    #[arg(long = "replay", value_name = "FIXTURE")]
+    pub replay: Option<Utf8PathBuf>,
+}
+
+impl FixtureOpt {
+    /// The session client requests should be recorded into or replayed from, if any.
+    pub fn get_session(&self) -> RoverResult<Option<FixtureSession>> {
+        match (&self.record, &self.replay) {
+            (Some(_), _) => Ok(Some(FixtureSession::record())),
+            (None, Some(path)) => {
+                let contents = Fs::read_file(path)?;
+                let fixture: Fixture = serde_json::from_str(&contents)
+                    .with_context(|| format!("{} is not a valid fixture", path))?;
+                eprintln!("Replaying requests from {}", Style::Path.paint(path));
+                Ok(Some(FixtureSession::replay(fixture)))
+            }
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Writes everything `session` recorded to the `--record` path.
+    pub fn save(&self, session: Option<FixtureSession>) -> RoverResult<()> {
+        if let (Some(path), Some(session)) = (&self.record, session) {
+            let fixture = serde_json::to_string_pretty(&session.get_fixture())?;
+            Fs::write_file(path, fixture)?;
+            eprintln!("Recorded requests to {}", Style::Path.paint(path));
+        }
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     options::{OutputOpts, RoverPrinter},
-    utils::parsers::parse_header,
+    utils::{line_diff, parsers::parse_header},
     RoverOutput, RoverResult,
 };
 
@@ -16,7 +16,8 @@ pub struct IntrospectOpts {
 
     /// headers to pass to the endpoint. Values must be key:value pairs.
     /// If a value has a space in it, use quotes around the pair,
-    /// ex. -H "Auth:some key"
+    /// ex. -H "Auth:some key". `${VAR}` in a value is replaced with the
+    /// value of the VAR environment variable, ex. -H 'Auth:Bearer ${TOKEN}'
 
     // The `value_name` here is for the help text and error messages, to print like
     // --header <KEY:VALUE> rather than the plural field name --header <headers>
@@ -27,6 +28,14 @@ pub struct IntrospectOpts {
     /// poll the endpoint, printing the introspection result if/when its contents change
     #[arg(long)]
     pub watch: bool,
+
+    /// with `--watch`, print a diff of the schema when it changes instead of the whole schema
+    #[arg(long, requires = "watch")]
+    pub diff: bool,
+
+    /// with `--watch`, how many seconds to wait between introspections
+    #[arg(long, value_name = "SECONDS", default_value = "1", value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
 }
 
 impl IntrospectOpts {
@@ -35,6 +44,7 @@ impl IntrospectOpts {
         F: Fn() -> RoverResult<String>,
     {
         let mut last_result = None;
+        let mut last_sdl: Option<String> = None;
         loop {
             match exec_fn() {
                 Ok(sdl) => {
@@ -46,9 +56,19 @@ impl IntrospectOpts {
                     }
 
                     if was_updated {
-                        let output = RoverOutput::Introspection(sdl.to_string());
+                        let output = match &last_sdl {
+                            // the first schema, and a schema that's back after an error,
+                            // are printed in full so there's something to diff against
+                            Some(last_sdl) if self.diff && last_sdl != &sdl => {
+                                RoverOutput::IntrospectionDiff(line_diff::diff_lines(
+                                    last_sdl, &sdl,
+                                ))
+                            }
+                            _ => RoverOutput::Introspection(sdl.to_string()),
+                        };
                         let _ = output.write_or_print(output_opts).map_err(|e| e.print());
                     }
+                    last_sdl = Some(sdl.clone());
                     last_result = Some(sdl);
                 }
                 Err(error) => {
@@ -65,7 +85,7 @@ impl IntrospectOpts {
                     last_result = Some(e);
                 }
             }
-            std::thread::sleep(std::time::Duration::from_secs(1))
+            std::thread::sleep(std::time::Duration::from_secs(self.interval))
         }
     }
 }
@@ -0,0 +1,212 @@
+use std::cmp::Ordering;
+use std::io;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// One field of an item printed by a list command, which can be filtered and sorted on
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ListValue {
+    Text(String),
+    Number(u64),
+    Time(DateTime<Utc>),
+}
+
+impl ListValue {
+    fn get_text(&self) -> String {
+        match self {
+            ListValue::Text(text) => text.clone(),
+            ListValue::Number(number) => number.to_string(),
+            ListValue::Time(time) => time.to_rfc3339(),
+        }
+    }
+}
+
+/// An item printed by a list command, projected into the fields that
+/// `--filter` and `--sort` can refer to
+pub(crate) trait ListItem {
+    /// the names of every field, in the order they're printed
+    const FIELDS: &'static [&'static str];
+
+    /// the value of `field`, which is `None` if the item doesn't have one
+    fn get_field(&self, field: &str) -> Option<ListValue>;
+}
+
+#[derive(Debug, Clone)]
+enum FilterMatch {
+    Equals(String),
+    NotEquals(String),
+    Matches(Regex),
+}
+
+/// A condition passed to `--filter`, like `name=products` or `name=~^prod`
+#[derive(Debug, Clone)]
+pub struct ListFilter {
+    field: String,
+    matcher: FilterMatch,
+}
+
+impl ListFilter {
+    fn is_match(&self, value: Option<ListValue>) -> bool {
+        let text = value.map(|value| value.get_text());
+        match (&self.matcher, text) {
+            (FilterMatch::Equals(expected), Some(text)) => &text == expected,
+            (FilterMatch::NotEquals(expected), Some(text)) => &text != expected,
+            (FilterMatch::NotEquals(_), None) => true,
+            (FilterMatch::Matches(regex), Some(text)) => regex.is_match(&text),
+            (_, None) => false,
+        }
+    }
+}
+
+fn parse_filter(filter: &str) -> Result<ListFilter, io::Error> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, anyhow!(message));
+    let (field, matcher) = if let Some((field, pattern)) = filter.split_once("=~") {
+        let regex = Regex::new(pattern)
+            .map_err(|e| invalid(format!("'{}' is not a valid regex: {}", pattern, e)))?;
+        (field, FilterMatch::Matches(regex))
+    } else if let Some((field, value)) = filter.split_once("!=") {
+        (field, FilterMatch::NotEquals(value.to_string()))
+    } else if let Some((field, value)) = filter.split_once('=') {
+        (field, FilterMatch::Equals(value.to_string()))
+    } else {
+        return Err(invalid(format!(
+            "'{}' is not a valid filter. Filters look like `<FIELD>=<VALUE>`, `<FIELD>!=<VALUE>`, or `<FIELD>=~<REGEX>`",
+            filter
+        )));
+    };
+    Ok(ListFilter {
+        field: field.trim().to_string(),
+        matcher,
+    })
+}
+
+#[derive(Debug, Serialize, Parser, Default)]
+pub struct ListOpts {
+    /// Only list the items whose field matches a condition: `<FIELD>=<VALUE>`,
+    /// `<FIELD>!=<VALUE>`, or `<FIELD>=~<REGEX>`. Pass `--filter` more than once to
+    /// only list items that match every condition
+    #[arg(long = "filter", value_name = "CONDITION", value_parser = parse_filter)]
+    #[serde(skip_serializing)]
+    pub filters: Vec<ListFilter>,
+
+    /// Sort the listed items by this field. Items without a value for it are listed last
+    #[arg(long = "sort", value_name = "FIELD")]
+    pub sort: Option<String>,
+
+    /// List the items in reverse order
+    #[arg(long = "reverse")]
+    pub reverse: bool,
+}
+
+impl ListOpts {
+    /// Filters and sorts `items`, failing if a filter or `--sort` refers to a field they don't have
+    pub(crate) fn apply<T: ListItem>(&self, items: Vec<T>) -> RoverResult<Vec<T>> {
+        for field in self
+            .filters
+            .iter()
+            .map(|filter| &filter.field)
+            .chain(self.sort.iter())
+        {
+            if !T::FIELDS.contains(&field.as_str()) {
+                let mut err = RoverError::new(anyhow!("'{}' is not a field of this list.", field));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                    "Filter or sort on one of these fields instead: {}",
+                    T::FIELDS.join(", ")
+                )));
+                return Err(err);
+            }
+        }
+
+        let mut items: Vec<T> = items
+            .into_iter()
+            .filter(|item| {
+                self.filters
+                    .iter()
+                    .all(|filter| filter.is_match(item.get_field(&filter.field)))
+            })
+            .collect();
+        if let Some(sort) = &self.sort {
+            items.sort_by(|a, b| match (a.get_field(sort), b.get_field(sort)) {
+                (Some(a), Some(b)) if self.reverse => b.cmp(&a),
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            });
+        } else if self.reverse {
+            items.reverse();
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Subgraph(&'static str, Option<u64>);
+
+    impl ListItem for Subgraph {
+        const FIELDS: &'static [&'static str] = &["name", "size"];
+
+        fn get_field(&self, field: &str) -> Option<ListValue> {
+            match field {
+                "name" => Some(ListValue::Text(self.0.to_string())),
+                "size" => self.1.map(ListValue::Number),
+                _ => None,
+            }
+        }
+    }
+
+    fn names(items: Vec<Subgraph>) -> Vec<&'static str> {
+        items.into_iter().map(|item| item.0).collect()
+    }
+
+    fn subgraphs() -> Vec<Subgraph> {
+        vec![
+            Subgraph("reviews", Some(10)),
+            Subgraph("products", None),
+            Subgraph("prices", Some(2)),
+        ]
+    }
+
+    #[test]
+    fn it_filters_and_sorts() {
+        let opts = ListOpts {
+            filters: vec![parse_filter("name=~^pr").unwrap()],
+            sort: Some("size".to_string()),
+            reverse: false,
+        };
+        assert_eq!(
+            names(opts.apply(subgraphs()).unwrap()),
+            vec!["prices", "products"]
+        );
+
+        let opts = ListOpts {
+            filters: vec![parse_filter("name!=prices").unwrap()],
+            sort: Some("size".to_string()),
+            reverse: true,
+        };
+        assert_eq!(
+            names(opts.apply(subgraphs()).unwrap()),
+            vec!["reviews", "products"]
+        );
+    }
+
+    #[test]
+    fn it_rejects_unknown_fields() {
+        assert!(parse_filter("name").is_err());
+        assert!(parse_filter("name=~(").is_err());
+        let opts = ListOpts {
+            sort: Some("updated_at".to_string()),
+            ..ListOpts::default()
+        };
+        assert!(opts.apply(subgraphs()).is_err());
+    }
+}
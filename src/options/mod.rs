@@ -1,21 +1,35 @@
 mod check;
+mod check_report;
 mod compose;
+mod directives;
+mod fixture;
 mod graph;
 mod introspect;
 mod license;
+mod list;
 mod output;
 mod profile;
+mod protected_variant;
+mod retry;
 mod schema;
+mod studio;
 mod subgraph;
 mod template;
 
 pub(crate) use check::*;
+pub(crate) use check_report::*;
 pub(crate) use compose::*;
+pub(crate) use directives::*;
+pub(crate) use fixture::*;
 pub(crate) use graph::*;
 pub(crate) use introspect::*;
 pub(crate) use license::*;
+pub(crate) use list::*;
 pub(crate) use output::*;
 pub(crate) use profile::*;
+pub(crate) use protected_variant::*;
+pub(crate) use retry::*;
 pub(crate) use schema::*;
+pub(crate) use studio::*;
 pub(crate) use subgraph::*;
 pub(crate) use template::*;
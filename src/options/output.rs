@@ -2,7 +2,7 @@ use std::{fmt, io, str::FromStr};
 
 use anyhow::Result;
 use calm_io::{stderrln, stdoutln};
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use clap::{error::ErrorKind as ClapErrorKind, CommandFactory, Parser, ValueEnum};
 use rover_std::{Emoji, Fs, Style};
 use serde::Serialize;
@@ -52,24 +52,39 @@ impl RoverPrinter for RoverOutput {
     fn write_or_print(self, output_opts: &OutputOpts) -> RoverResult<()> {
         let (format_kind, output_destination) = output_opts.get_format_and_strategy();
 
-        // Format the RoverOutput as either plain text or JSON.
-        let output = match format_kind {
-            RoverOutputFormatKind::Plain => self.get_stdout(),
-            RoverOutputFormatKind::Json => Ok(Some(JsonOutput::from(self.clone()).to_string())),
-        };
-
         // Print the RoverOutput to file or stdout.
-        if let Ok(Some(result)) = output {
+        if let Some(result) = format_output(&self, format_kind)? {
             match output_destination {
                 RoverOutputDestination::File(path) => {
+                    let descriptor = self.descriptor().unwrap_or("The output");
                     let success_heading = Style::Heading.paint(format!(
                         "{}{} was printed to",
                         Emoji::Memo,
-                        self.descriptor().unwrap_or("The output")
+                        descriptor
                     ));
                     let path_text = Style::Path.paint(&path);
-                    Fs::write_file(&path, result)?;
-                    stderrln!("{} {}", success_heading, path_text)?;
+                    if let RoverOutput::CompositionResult(_) = self {
+                        // `supergraph compose` leaves an up-to-date file alone, so tools watching it
+                        // aren't triggered, and prints a summary in place of the schema
+                        let changed = write_if_changed(&path, &result)?;
+                        match format_kind {
+                            RoverOutputFormatKind::Plain => {
+                                if let Some(summary) = self.get_file_summary(&path, changed) {
+                                    stdoutln!("{}", summary)?;
+                                }
+                            }
+                            _ if changed => stderrln!("{} {}", success_heading, path_text)?,
+                            _ => stderrln!(
+                                "{}{} in {} is unchanged, so the file was left as it was",
+                                Emoji::Memo,
+                                descriptor,
+                                path_text
+                            )?,
+                        }
+                    } else {
+                        Fs::write_file(&path, result)?;
+                        stderrln!("{} {}", success_heading, path_text)?;
+                    }
                 }
                 RoverOutputDestination::Stdout => {
                     // Call the appropriate method based on the variant of RoverOutput.
@@ -88,11 +103,35 @@ impl RoverPrinter for RoverOutput {
     }
 }
 
+/// Formats a RoverOutput as plain text or JSON
+pub(crate) fn format_output(
+    output: &RoverOutput,
+    format_kind: RoverOutputFormatKind,
+) -> RoverResult<Option<String>> {
+    Ok(match format_kind {
+        RoverOutputFormatKind::Plain => output.get_stdout()?,
+        RoverOutputFormatKind::Json => Some(JsonOutput::from(output.clone()).to_string()),
+        RoverOutputFormatKind::IntrospectionJson => Some(output.get_introspection_json()?),
+    })
+}
+
+/// Writes `contents` to `path` unless the file already has exactly those contents,
+/// so anything watching the file isn't told about a change that didn't happen.
+/// Returns whether the file was written
+pub(crate) fn write_if_changed(path: &Utf8Path, contents: &str) -> RoverResult<bool> {
+    if path.is_file() && Fs::read_file(path)? == contents {
+        return Ok(false);
+    }
+    Fs::write_file(path, contents)?;
+    Ok(true)
+}
+
 impl RoverPrinter for RoverError {
     fn write_or_print(self, output_opts: &OutputOpts) -> RoverResult<()> {
         let (format_kind, output_destination) = output_opts.get_format_and_strategy();
         match format_kind {
-            RoverOutputFormatKind::Plain => self.print(),
+            // errors don't have a schema to print, so they're printed as plain text
+            RoverOutputFormatKind::Plain | RoverOutputFormatKind::IntrospectionJson => self.print(),
             RoverOutputFormatKind::Json => {
                 let json = JsonOutput::from(self);
                 match output_destination {
@@ -167,6 +206,12 @@ impl OutputOpts {
             (None, Some(OutputOpt::LegacyOutputType(RoverOutputFormatKind::Json))) => {
                 (RoverOutputFormatKind::Json, RoverOutputDestination::Stdout)
             }
+            (None, Some(OutputOpt::LegacyOutputType(RoverOutputFormatKind::IntrospectionJson))) => {
+                (
+                    RoverOutputFormatKind::IntrospectionJson,
+                    RoverOutputDestination::Stdout,
+                )
+            }
             (None, Some(OutputOpt::File(path))) => (
                 RoverOutputFormatKind::Plain,
                 RoverOutputDestination::File(path),
@@ -180,6 +225,13 @@ impl OutputOpts {
                 Some(RoverOutputFormatKind::Plain),
                 Some(OutputOpt::LegacyOutputType(RoverOutputFormatKind::Json)),
             ) => (RoverOutputFormatKind::Json, RoverOutputDestination::Stdout),
+            (
+                Some(RoverOutputFormatKind::Plain),
+                Some(OutputOpt::LegacyOutputType(RoverOutputFormatKind::IntrospectionJson)),
+            ) => (
+                RoverOutputFormatKind::IntrospectionJson,
+                RoverOutputDestination::Stdout,
+            ),
             (Some(RoverOutputFormatKind::Plain), Some(OutputOpt::File(path))) => (
                 RoverOutputFormatKind::Plain,
                 RoverOutputDestination::File(path),
@@ -192,6 +244,18 @@ impl OutputOpts {
                 RoverOutputFormatKind::Json,
                 RoverOutputDestination::File(path),
             ),
+            (Some(RoverOutputFormatKind::IntrospectionJson), None)
+            | (
+                Some(RoverOutputFormatKind::IntrospectionJson),
+                Some(OutputOpt::LegacyOutputType(_)),
+            ) => (
+                RoverOutputFormatKind::IntrospectionJson,
+                RoverOutputDestination::Stdout,
+            ),
+            (Some(RoverOutputFormatKind::IntrospectionJson), Some(OutputOpt::File(path))) => (
+                RoverOutputFormatKind::IntrospectionJson,
+                RoverOutputDestination::File(path),
+            ),
         }
     }
 }
@@ -233,6 +297,12 @@ impl fmt::Display for JsonOutput {
 
 impl From<RoverError> for JsonOutput {
     fn from(error: RoverError) -> Self {
+        JsonOutput::from(&error)
+    }
+}
+
+impl From<&RoverError> for JsonOutput {
+    fn from(error: &RoverError) -> Self {
         let data_json = error.get_internal_data_json();
         let error_json = error.get_internal_error_json();
         JsonOutput::failure(data_json, error_json, error.get_json_version())
@@ -15,3 +15,14 @@ impl Display for ProfileOpt {
         write!(f, "{}", &self.profile_name)
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct ProfilesOpt {
+    #[clap(flatten)]
+    pub profile: ProfileOpt,
+
+    /// Run this command once with each configuration profile, reporting the
+    /// result for every profile, instead of only using `--profile`
+    #[arg(long = "all-profiles", conflicts_with = "profile_name")]
+    pub all_profiles: bool,
+}
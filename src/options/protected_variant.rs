@@ -0,0 +1,134 @@
+use anyhow::anyhow;
+use atty::Stream;
+use clap::Parser;
+use dialoguer::Input;
+use serde::Serialize;
+
+use rover_client::shared::GraphRef;
+use rover_std::Style;
+
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct ProtectedVariantOpt {
+    /// The name of the variant this command is expected to publish to, i.e. `production`.
+    /// The command fails without publishing if the graph ref points at a different variant.
+    ///
+    /// Publishing to a protected variant requires either passing this argument or
+    /// re-typing the variant's name when prompted.
+    #[arg(long = "confirm-variant", value_name = "VARIANT")]
+    confirm_variant: Option<String>,
+
+    /// A comma-separated list of variants that can only be published to after confirming
+    /// the variant's name, i.e. `current,production`
+    #[arg(
+        long = "protected-variants",
+        value_name = "VARIANTS",
+        value_delimiter = ',',
+        env = "APOLLO_PROTECTED_VARIANTS"
+    )]
+    protected_variants: Vec<String>,
+}
+
+impl ProtectedVariantOpt {
+    /// Errors unless it's safe to publish to `graph_ref`,
+    /// prompting for the variant's name if it's protected and hasn't been confirmed
+    pub fn confirm(&self, graph_ref: &GraphRef) -> RoverResult<()> {
+        match &self.confirm_variant {
+            Some(confirm_variant) if confirm_variant == &graph_ref.variant => Ok(()),
+            Some(confirm_variant) => {
+                let mut err = RoverError::new(anyhow!(
+                    "Expected to publish to the {} variant, but this command publishes to {}.",
+                    confirm_variant,
+                    graph_ref
+                ));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                    "Check the graph ref passed to this command, including any set with the `APOLLO_GRAPH_REF` environment variable.".to_string(),
+                ));
+                Err(err)
+            }
+            None if !self.is_protected(graph_ref) => Ok(()),
+            None => self.prompt_confirm(graph_ref),
+        }
+    }
+
+    fn is_protected(&self, graph_ref: &GraphRef) -> bool {
+        self.protected_variants
+            .iter()
+            .any(|variant| variant.trim() == graph_ref.variant)
+    }
+
+    fn prompt_confirm(&self, graph_ref: &GraphRef) -> RoverResult<()> {
+        // If we're not attached to a TTY then we can't get user input, so there's
+        // nothing to do except inform the user about the `--confirm-variant` flag.
+        if !atty::is(Stream::Stdin) {
+            let mut err = RoverError::new(anyhow!(
+                "{} is a protected variant, so publishing to it must be confirmed.",
+                graph_ref
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                "If you meant to publish to {}, pass `--confirm-variant {}`.",
+                graph_ref, graph_ref.variant
+            )));
+            return Err(err);
+        }
+
+        let typed_variant: String = Input::new()
+            .with_prompt(format!(
+                "{} is a protected variant. Type {} to continue",
+                Style::Link.paint(graph_ref.to_string()),
+                Style::Command.paint(&graph_ref.variant)
+            ))
+            .allow_empty(true)
+            .interact_text()?;
+
+        if typed_variant.trim() == graph_ref.variant {
+            Ok(())
+        } else {
+            Err(RoverError::new(anyhow!(
+                "Publish cancelled, since the variant name entered did not match {}.",
+                graph_ref.variant
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_opt(confirm_variant: Option<&str>, protected_variants: &[&str]) -> ProtectedVariantOpt {
+        ProtectedVariantOpt {
+            confirm_variant: confirm_variant.map(|variant| variant.to_string()),
+            protected_variants: protected_variants
+                .iter()
+                .map(|variant| variant.to_string())
+                .collect(),
+        }
+    }
+
+    fn mock_graph_ref(variant: &str) -> GraphRef {
+        GraphRef {
+            name: "mygraph".to_string(),
+            variant: variant.to_string(),
+        }
+    }
+
+    #[test]
+    fn it_allows_unprotected_variants() {
+        let opt = mock_opt(None, &["current", "production"]);
+        assert!(opt.confirm(&mock_graph_ref("staging")).is_ok());
+    }
+
+    #[test]
+    fn it_allows_confirmed_protected_variants() {
+        let opt = mock_opt(Some("production"), &["current", "production"]);
+        assert!(opt.confirm(&mock_graph_ref("production")).is_ok());
+    }
+
+    #[test]
+    fn it_errs_on_mismatched_confirmations() {
+        let opt = mock_opt(Some("production"), &[]);
+        assert!(opt.confirm(&mock_graph_ref("staging")).is_err());
+    }
+}
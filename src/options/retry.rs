@@ -0,0 +1,12 @@
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct RetryOpt {
+    /// The number of times to retry the request if Apollo Studio responds with a server error,
+    /// the request times out, or a connection cannot be established.
+    ///
+    /// Each retry waits twice as long as the one before it, starting at half a second.
+    #[arg(long = "retries")]
+    pub retries: Option<u32>,
+}
@@ -1,14 +1,25 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use rover_client::operations::supergraph::fetch::SupergraphSchema;
+use serde::Serialize;
 
-use crate::{utils::parsers::FileDescriptorType, RoverResult};
+use crate::{
+    utils::{json_input::RoverJsonInput, parsers::FileDescriptorType},
+    RoverResult,
+};
 
 use std::io::Read;
 
 #[derive(Debug, Parser)]
 pub struct SchemaOpt {
     /// The schema file to check. You can pass `-` to use stdin instead of a file.
-    #[arg(long, short = 's')]
-    schema: FileDescriptorType,
+    #[arg(long, short = 's', required_unless_present = "from_json")]
+    schema: Option<FileDescriptorType>,
+
+    /// The JSON output of a previous Rover command to read the schema from,
+    /// i.e. `rover graph fetch <GRAPH_REF> --format json`.
+    /// You can pass `-` to use stdin instead of a file.
+    #[arg(long = "from-json", value_name = "JSON", conflicts_with = "schema")]
+    from_json: Option<FileDescriptorType>,
 }
 
 impl SchemaOpt {
@@ -17,6 +28,33 @@ impl SchemaOpt {
         file_description: &str,
         stdin: &mut impl Read,
     ) -> RoverResult<String> {
-        self.schema.read_file_descriptor(file_description, stdin)
+        match (&self.schema, &self.from_json) {
+            (Some(schema), _) => schema.read_file_descriptor(file_description, stdin),
+            (None, Some(from_json)) => {
+                let json = from_json.read_file_descriptor("JSON", stdin)?;
+                RoverJsonInput::parse(&json)?.get_sdl()
+            }
+            (None, None) => unreachable!("clap requires one of --schema or --from-json"),
+        }
+    }
+}
+
+/// Which of a federated graph's schemas to fetch
+#[derive(ValueEnum, Debug, Clone, Copy, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaType {
+    /// The schema clients can query
+    Api,
+
+    /// The supergraph schema, with the join directives routers use to plan queries
+    Core,
+}
+
+impl From<SchemaType> for SupergraphSchema {
+    fn from(schema_type: SchemaType) -> Self {
+        match schema_type {
+            SchemaType::Api => SupergraphSchema::Api,
+            SchemaType::Core => SupergraphSchema::Core,
+        }
     }
 }
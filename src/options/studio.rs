@@ -0,0 +1,101 @@
+use clap::{Parser, ValueEnum};
+use rover_client::shared::GraphRef;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct StudioPageOpt {
+    /// The page in Apollo Studio to open
+    #[arg(long = "page", value_enum, default_value_t = StudioPage::Schema)]
+    pub page: StudioPage,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub enum StudioPage {
+    Schema,
+    Checks,
+    Launches,
+    History,
+}
+
+impl StudioPage {
+    /// Builds the URL of this page for a variant (and optionally one of its subgraphs)
+    /// given the root URL of Apollo Studio's frontend.
+    pub fn get_url(&self, root_url: &str, graph_ref: &GraphRef, subgraph: Option<&str>) -> String {
+        let graph_url = format!(
+            "{}/graph/{}",
+            root_url.trim_end_matches('/'),
+            &graph_ref.name
+        );
+        match (self, subgraph) {
+            (StudioPage::Schema, None) => format!(
+                "{}/schema/reference?variant={}",
+                graph_url, &graph_ref.variant
+            ),
+            (StudioPage::Schema, Some(subgraph)) => format!(
+                "{}/schema/sdl?variant={}&selectedSchema={}",
+                graph_url, &graph_ref.variant, subgraph
+            ),
+            (StudioPage::Checks, _) => {
+                format!("{}/checks?variant={}", graph_url, &graph_ref.variant)
+            }
+            (StudioPage::Launches, _) => {
+                format!("{}/launches?variant={}", graph_url, &graph_ref.variant)
+            }
+            (StudioPage::History, _) => {
+                format!("{}/history?variant={}", graph_url, &graph_ref.variant)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_graph_ref() -> GraphRef {
+        GraphRef {
+            name: "my-graph".to_string(),
+            variant: "prod".to_string(),
+        }
+    }
+
+    #[test]
+    fn it_builds_graph_urls() {
+        assert_eq!(
+            StudioPage::Schema.get_url(
+                "https://studio.apollographql.com/",
+                &mock_graph_ref(),
+                None
+            ),
+            "https://studio.apollographql.com/graph/my-graph/schema/reference?variant=prod"
+        );
+        assert_eq!(
+            StudioPage::Launches.get_url(
+                "https://studio.apollographql.com",
+                &mock_graph_ref(),
+                None
+            ),
+            "https://studio.apollographql.com/graph/my-graph/launches?variant=prod"
+        );
+        assert_eq!(
+            StudioPage::History.get_url(
+                "https://studio.apollographql.com",
+                &mock_graph_ref(),
+                None
+            ),
+            "https://studio.apollographql.com/graph/my-graph/history?variant=prod"
+        );
+    }
+
+    #[test]
+    fn it_builds_subgraph_urls() {
+        assert_eq!(
+            StudioPage::Schema.get_url(
+                "https://studio.apollographql.com",
+                &mock_graph_ref(),
+                Some("products")
+            ),
+            "https://studio.apollographql.com/graph/my-graph/schema/sdl?variant=prod&selectedSchema=products"
+        );
+    }
+}
@@ -2,8 +2,13 @@ use camino::Utf8PathBuf;
 use clap::{self, Parser};
 use serde::{Deserialize, Serialize};
 
+use crate::utils::parsers::parse_subgraph_header;
+
+#[cfg(feature = "composition-js")]
+use anyhow::{anyhow, Context, Result};
+
 #[cfg(feature = "composition-js")]
-use anyhow::{Context, Result};
+use std::net::SocketAddr;
 
 #[cfg(feature = "composition-js")]
 use clap::{error::ErrorKind as ClapErrorKind, CommandFactory};
@@ -28,6 +33,14 @@ pub struct SubgraphOpt {
     pub subgraph_name: String,
 }
 
+/// A header `rover dev` sends with its introspection requests, to every subgraph or to one of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubgraphHeader {
+    pub subgraph_name: Option<String>,
+    pub key: String,
+    pub value: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Parser)]
 pub struct OptionalSubgraphOpts {
     /// The name of the subgraph.
@@ -58,15 +71,135 @@ pub struct OptionalSubgraphOpts {
     #[arg(
         long = "polling-interval",
         short = 'i',
-        default_value = "1",
         conflicts_with = "subgraph_schema_path"
     )]
     #[serde(skip_serializing)]
-    pub subgraph_polling_interval: u64,
+    pub subgraph_polling_interval: Option<u64>,
+
+    /// Introspect the running subgraph until its schema is fetched once, and then stop.
+    ///
+    /// Use this for subgraphs whose schema doesn't change during a session, to avoid sending them an introspection request every `--polling-interval`.
+    #[arg(
+        long = "no-polling",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_name = "BOOL",
+        conflicts_with_all = ["subgraph_schema_path", "subgraph_polling_interval"]
+    )]
+    #[serde(skip_serializing)]
+    pub no_polling: Option<bool>,
+
+    /// The path to a GraphQL schema file to serve a mocked subgraph for, so you can develop the rest of the supergraph before this subgraph exists.
+    ///
+    /// `rover dev` serves the subgraph itself, resolving every field with fake values that are the same each time they're requested, and recomposes whenever the file changes. Without `--url`, the mocked subgraph is served on a free port on localhost.
+    #[arg(
+        long = "mock",
+        value_name = "SCHEMA_PATH",
+        conflicts_with_all = ["subgraph_schema_path", "subgraph_polling_interval", "no_polling"]
+    )]
+    #[serde(skip_serializing)]
+    mock_schema_path: Option<Utf8PathBuf>,
+
+    /// A header to send with the introspection requests to the subgraphs this `rover dev` process runs, i.e. for subgraphs behind an auth gateway. Pass this more than once to send several headers.
+    ///
+    /// Pass a `key:value` pair to send the header to every subgraph, or put a subgraph's name and a `/` before the key, i.e. `products/Authorization:Bearer ${TOKEN}`, to send it to that subgraph only. `${VAR}` in a value is replaced with the value of the VAR environment variable.
+    #[arg(long = "subgraph-header", value_name = "KEY:VALUE", value_parser = parse_subgraph_header)]
+    #[serde(skip)]
+    subgraph_headers: Vec<SubgraphHeader>,
 }
 
 #[cfg(feature = "composition-js")]
 impl OptionalSubgraphOpts {
+    /// The number of seconds between introspection requests to the running subgraph,
+    /// or `None` if polling is disabled with `--no-polling`
+    pub fn get_polling_interval(&self) -> Option<u64> {
+        if self.no_polling.unwrap_or_default() {
+            None
+        } else {
+            Some(self.subgraph_polling_interval.unwrap_or(1))
+        }
+    }
+
+    /// Fills in `--no-polling`, `--polling-interval`, and `--subgraph-header` from a `rover dev`
+    /// config file, unless they were passed on the command line
+    pub fn with_defaults(
+        &mut self,
+        no_polling: Option<bool>,
+        polling_interval: Option<u64>,
+        subgraph_headers: Vec<SubgraphHeader>,
+    ) {
+        // either flag passed on the command line replaces both keys of the file
+        if self.no_polling.is_none() && self.subgraph_polling_interval.is_none() {
+            self.no_polling = no_polling;
+            self.subgraph_polling_interval = polling_interval;
+        }
+        if self.subgraph_headers.is_empty() {
+            self.subgraph_headers = subgraph_headers;
+        }
+    }
+
+    /// The `--subgraph-header`s to send to the subgraph named `subgraph_name`, with the ones
+    /// for that subgraph after the ones for every subgraph, so they take precedence
+    pub fn get_subgraph_headers(&self, subgraph_name: &str) -> Vec<(String, String)> {
+        let for_every_subgraph = self
+            .subgraph_headers
+            .iter()
+            .filter(|header| header.subgraph_name.is_none());
+        let for_this_subgraph = self
+            .subgraph_headers
+            .iter()
+            .filter(|header| header.subgraph_name.as_deref() == Some(subgraph_name));
+        for_every_subgraph
+            .chain(for_this_subgraph)
+            .map(|header| (header.key.clone(), header.value.clone()))
+            .collect()
+    }
+
+    /// Whether no subgraph was passed with `--name`, `--url`, `--schema`, or `--mock`
+    pub fn is_empty(&self) -> bool {
+        self.subgraph_name.is_none()
+            && self.subgraph_url.is_none()
+            && self.subgraph_schema_path.is_none()
+            && self.mock_schema_path.is_none()
+    }
+
+    /// The `--name` of the subgraph, if it was passed rather than prompted for
+    pub fn get_subgraph_name(&self) -> Option<&str> {
+        self.subgraph_name.as_deref()
+    }
+
+    /// The schema passed to `--mock`, if this subgraph is mocked
+    pub fn get_mock_schema_path(&self) -> Option<&Utf8PathBuf> {
+        self.mock_schema_path.as_ref()
+    }
+
+    /// Whether a mocked subgraph is served on any free port, since no `--url` was passed
+    pub fn is_mock_on_free_port(&self) -> bool {
+        self.mock_schema_path.is_some() && self.subgraph_url.is_none()
+    }
+
+    /// The address to serve a mocked subgraph on, which is the `--url` if one was passed
+    pub fn get_mock_address(&self) -> Result<SocketAddr> {
+        match &self.subgraph_url {
+            Some(subgraph_url) => {
+                let url: Url = subgraph_url
+                    .parse()
+                    .with_context(|| format!("'{}' is not a valid subgraph URL.", subgraph_url))?;
+                url.socket_addrs(|| None)?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "'{}' does not have an address to serve a mocked subgraph on.",
+                            subgraph_url
+                        )
+                    })
+            }
+            None => Ok(SocketAddr::from(([127, 0, 0, 1], 0))),
+        }
+    }
+
     pub fn prompt_for_name(&self) -> Result<String> {
         if let Some(name) = &self.subgraph_name {
             Ok(name.to_string())
@@ -1,15 +1,16 @@
 use std::fmt::{self, Display};
-use std::io::Write;
+use std::io::Read;
 
 use anyhow::{anyhow, Context};
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use clap::{Parser, ValueEnum};
 use console::Term;
 use dialoguer::Select;
-use rover_std::Fs;
+use rover_std::{tmp::SecureTempDir, Fs};
 use serde::{Deserialize, Serialize};
 
-use crate::{RoverError, RoverResult};
+use crate::utils::progress::{ProgressEvent, ProgressReporter};
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Parser)]
 pub struct TemplateOpt {
@@ -55,46 +56,178 @@ impl GithubTemplate {
             .ok_or_else(|| anyhow!("Could not determine tarball path.").into())
     }
 
+    /// Downloads the template at `revision`, a git tag or commit SHA, or the head of its `main`
+    /// branch without one, and extracts it to `template_path`.
+    ///
+    /// Returns the commit the template was extracted from, or the revision it was downloaded at
+    /// if the tarball doesn't say which commit that is
     pub(crate) fn extract_github_tarball(
         &self,
         template_path: &Utf8PathBuf,
+        revision: Option<&str>,
         client: &reqwest::blocking::Client,
-    ) -> RoverResult<()> {
-        let download_dir = tempdir::TempDir::new(self.id)?;
-        let download_dir_path = Utf8PathBuf::try_from(download_dir.into_path())?;
+        progress: &ProgressReporter,
+    ) -> RoverResult<String> {
+        let download_dir = SecureTempDir::new(self.id)?;
         let git_repo_slug = self.repo_slug()?;
-        let tarball_path = download_dir_path.join(format!("{}.tar.gz", git_repo_slug));
-        let tarball_url = format!("{}/archive/refs/heads/main.tar.gz", &self.git_url);
-        let mut f = std::fs::File::create(&tarball_path)?;
-        eprintln!("Downloading {}", &self.git_url);
-        eprintln!("\tfrom {}", tarball_url);
-        let response_bytes = client
-            .get(tarball_url)
+        let tarball_url = match revision {
+            Some(revision) => format!("{}/archive/{}.tar.gz", &self.git_url, revision),
+            None => format!("{}/archive/refs/heads/main.tar.gz", &self.git_url),
+        };
+        progress.emit(ProgressEvent::TemplateDownloadStarted {
+            template_id: self.id.to_string(),
+            repository: self.git_url.to_string(),
+            url: tarball_url.clone(),
+        });
+        let response = client
+            .get(&tarball_url)
             .header(reqwest::header::USER_AGENT, "rover-client")
             .header(reqwest::header::ACCEPT, "application/octet-stream")
-            .send()?
-            .error_for_status()?
-            .bytes()?;
-        f.write_all(&response_bytes[..])?;
-        f.sync_all()?;
-        let f = std::fs::File::open(&tarball_path)?;
-        let tar = flate2::read::GzDecoder::new(f);
-        let mut archive = tar::Archive::new(tar);
-        archive
-            .unpack(template_path)
-            .with_context(|| format!("could not unpack tarball to '{}'", &template_path))?;
-
-        let tar_path = template_path.join(format!("{}-main", git_repo_slug));
-
-        // The unpacked tar will be in the folder{git_repo_id}-{branch}
-        // For this reason, we must copy the contents of the folder, then delete it
-        Fs::copy_dir_all(&tar_path, template_path)?;
-
-        // Delete old unpacked zip
-        Fs::remove_dir_all(&tar_path)?;
-
-        Ok(())
+            .send()?;
+        if let (Some(revision), reqwest::StatusCode::NOT_FOUND) = (revision, response.status()) {
+            let mut err = RoverError::new(anyhow!(
+                "The '{}' template has no tag or commit '{}'",
+                self.id,
+                revision
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                "See the tags and commits of the template at {}.",
+                self.git_url
+            )));
+            return Err(err);
+        }
+        let mut response = response.error_for_status()?;
+
+        // read the tarball a chunk at a time so the download's progress can be reported
+        let total_bytes = response.content_length();
+        let mut response_bytes = Vec::new();
+        let mut chunk = [0; 64 * 1024];
+        loop {
+            let read = response
+                .read(&mut chunk)
+                .with_context(|| format!("could not download {}", &tarball_url))?;
+            if read == 0 {
+                break;
+            }
+            response_bytes.extend_from_slice(&chunk[..read]);
+            progress.emit(ProgressEvent::DownloadProgress {
+                url: tarball_url.clone(),
+                downloaded_bytes: response_bytes.len() as u64,
+                total_bytes,
+            });
+        }
+        let tarball_path =
+            download_dir.write_file(&format!("{}.tar.gz", git_repo_slug), &response_bytes[..])?;
+
+        // The unpacked tar will be in the folder {git_repo_id}-{revision}, which GitHub
+        // names after a tag without its leading `v`, so it's found by unpacking it on its own
+        unpack_tarball(
+            &tarball_path,
+            &download_dir.path().join(git_repo_slug),
+            template_path,
+        )?;
+
+        progress.emit(ProgressEvent::TemplateExtracted {
+            template_id: self.id.to_string(),
+            path: template_path.to_string(),
+        });
+
+        Ok(get_tarball_commit(&tarball_path)
+            .unwrap_or_else(|| revision.unwrap_or("main").to_string()))
+    }
+}
+
+/// Copies a template from a local directory or `.tar.gz` tarball to `template_path`,
+/// i.e. a company-internal template that isn't one of the official templates
+pub(crate) fn extract_local_template(
+    source_path: &Utf8Path,
+    template_path: &Utf8PathBuf,
+    progress: &ProgressReporter,
+) -> RoverResult<()> {
+    if Fs::path_is_dir(source_path)? {
+        copy_template_dir(source_path, template_path)?;
+    } else if is_tarball(source_path) {
+        let unpack_dir = SecureTempDir::new("local-template")?;
+        unpack_tarball(source_path, unpack_dir.path(), template_path)?;
+    } else {
+        let mut err = RoverError::new(anyhow!(
+            "{} is neither a directory nor a `.tar.gz` tarball",
+            source_path
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(
+            "Pass the path of a template's directory, or of a `.tar.gz` or `.tgz` tarball of it, to `--path`.".to_string(),
+        ));
+        return Err(err);
+    }
+
+    progress.emit(ProgressEvent::TemplateExtracted {
+        template_id: source_path.to_string(),
+        path: template_path.to_string(),
+    });
+
+    Ok(())
+}
+
+/// Unpacks a `.tar.gz` tarball to `unpack_dir`, and copies the template in it to `template_path`
+fn unpack_tarball(
+    tarball_path: &Utf8Path,
+    unpack_dir: &Utf8Path,
+    template_path: &Utf8Path,
+) -> RoverResult<()> {
+    let f = std::fs::File::open(tarball_path)
+        .with_context(|| format!("could not open {}", tarball_path))?;
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(f));
+    archive
+        .unpack(unpack_dir)
+        .with_context(|| format!("could not unpack {}", tarball_path))?;
+
+    // a tarball of a repository keeps its files in one top-level directory, like GitHub's do
+    let entries: Vec<Utf8PathBuf> = Fs::get_dir_entries(unpack_dir)?
+        .flatten()
+        .map(|entry| entry.path().to_path_buf())
+        .collect();
+    let template_root = match entries.as_slice() {
+        [only_entry] if only_entry.is_dir() => only_entry.clone(),
+        _ => unpack_dir.to_path_buf(),
+    };
+    copy_template_dir(&template_root, template_path)
+}
+
+/// The commit a GitHub tarball was made from, which GitHub keeps in the `comment` of the
+/// tarball's global pax header
+fn get_tarball_commit(tarball_path: &Utf8Path) -> Option<String> {
+    let f = std::fs::File::open(tarball_path).ok()?;
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(f));
+    let mut entry = archive.entries().ok()?.next()?.ok()?;
+    if entry.header().entry_type() != tar::EntryType::XGlobalHeader {
+        return None;
+    }
+    let extensions = entry.pax_extensions().ok()??;
+    extensions
+        .flatten()
+        .find(|extension| extension.key() == Ok("comment"))
+        .and_then(|extension| extension.value().ok().map(|commit| commit.to_string()))
+}
+
+fn is_tarball(path: &Utf8Path) -> bool {
+    let file_name = path.file_name().unwrap_or_default();
+    file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz")
+}
+
+/// Copies the contents of a template's directory, except its `.git` directory,
+/// so the new project starts its own history instead of the template's
+fn copy_template_dir(source_dir: &Utf8Path, template_path: &Utf8Path) -> RoverResult<()> {
+    for entry in Fs::get_dir_entries(source_dir)?.flatten() {
+        let entry_path = entry.path();
+        match entry_path.file_name() {
+            Some(".git") | None => continue,
+            Some(entry_name) if entry_path.is_dir() => {
+                Fs::copy_dir_all(entry_path, template_path.join(entry_name))?
+            }
+            Some(entry_name) => Fs::copy(entry_path, template_path.join(entry_name))?,
+        }
     }
+    Ok(())
 }
 
 impl Display for GithubTemplate {
@@ -152,3 +285,36 @@ impl Display for ProjectLanguage {
         write!(f, "{}", self.descriptor())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn it_copies_local_templates_without_their_history() {
+        let tmp_home = TempDir::new().unwrap();
+        let tmp_dir = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        let source_path = tmp_dir.join("my-template");
+        Fs::create_dir_all(source_path.join("src")).unwrap();
+        Fs::create_dir_all(source_path.join(".git")).unwrap();
+        Fs::write_file(source_path.join("src/index.js"), "console.log('hello')").unwrap();
+        Fs::write_file(source_path.join("README.md"), "# My template").unwrap();
+        Fs::write_file(source_path.join(".git/HEAD"), "ref: refs/heads/main").unwrap();
+
+        let template_path = tmp_dir.join("my-project");
+        Fs::create_dir_all(&template_path).unwrap();
+        extract_local_template(&source_path, &template_path, &ProgressReporter::default()).unwrap();
+        assert!(template_path.join("src/index.js").exists());
+        assert!(template_path.join("README.md").exists());
+        assert!(!template_path.join(".git").exists());
+
+        assert!(extract_local_template(
+            &source_path.join("README.md"),
+            &template_path,
+            &ProgressReporter::default()
+        )
+        .is_err());
+    }
+}
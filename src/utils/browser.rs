@@ -0,0 +1,36 @@
+use anyhow::anyhow;
+use rover_std::Style;
+
+use crate::RoverResult;
+
+use std::process::Command;
+
+/// Opens `url` with the application specified by `$BROWSER`,
+/// falling back to the system's default browser.
+pub(crate) fn open(url: &str) -> RoverResult<()> {
+    let painted_browser_var = Style::Command.paint("$BROWSER");
+    let painted_url = Style::Link.paint(url);
+
+    if let Some(browser_override) = std::env::var_os("BROWSER") {
+        eprintln!(
+            "Opening {} with the application specified by {}.",
+            &painted_url, &painted_browser_var
+        );
+        if let Err(e) = Command::new(&browser_override).arg(url).status() {
+            Err(anyhow!(
+                "Couldn't open {} with {}: {}",
+                url,
+                browser_override.to_string_lossy(),
+                e
+            ))
+        } else {
+            Ok(())
+        }
+    } else {
+        eprintln!("Opening {} with your default browser. This can be overridden by setting the {} environment variable.", &painted_url, &painted_browser_var);
+        opener::open(url)?;
+        Ok(())
+    }?;
+
+    Ok(())
+}
@@ -1,12 +1,17 @@
 use core::fmt;
 use std::{io, str::FromStr, time::Duration};
 
-use crate::{options::ProfileOpt, PKG_NAME, PKG_VERSION};
-use anyhow::Result;
+use crate::utils::progress::ProgressReporter;
+use crate::{
+    options::ProfileOpt, RoverError, RoverErrorSuggestion, RoverResult, PKG_NAME, PKG_VERSION,
+};
+use anyhow::{anyhow, Result};
 
-use houston as config;
+use houston::{self as config, HoustonProblem};
 use reqwest::blocking::Client;
-use rover_client::blocking::StudioClient;
+use rover_client::blocking::{FixtureSession, RequestBudget, StudioClient};
+use rover_client::shared::GraphRef;
+use rover_client::RoverClientError;
 
 use serde::Serialize;
 
@@ -116,6 +121,9 @@ pub struct StudioClientConfig {
     version: String,
     is_sudo: bool,
     client: Option<Client>,
+    request_budget: RequestBudget,
+    fixture: Option<FixtureSession>,
+    progress: ProgressReporter,
 }
 
 impl StudioClientConfig {
@@ -138,9 +146,41 @@ impl StudioClientConfig {
             client_builder,
             is_sudo,
             client: None,
+            request_budget: RequestBudget::default(),
+            fixture: None,
+            progress: ProgressReporter::default(),
         }
     }
 
+    /// Counts the requests made by every client created from this config against `request_budget`.
+    pub fn with_request_budget(self, request_budget: RequestBudget) -> StudioClientConfig {
+        StudioClientConfig {
+            request_budget,
+            ..self
+        }
+    }
+
+    /// Checks that the `planned_requests` a command is about to make to Apollo Studio fit in
+    /// what's left of the request budget, before any of them are sent
+    pub fn plan_requests(&self, planned_requests: u64) -> RoverResult<()> {
+        Ok(self.request_budget.plan(planned_requests)?)
+    }
+
+    /// Records the exchanges of every client created from this config into `fixture`,
+    /// or answers their requests from it if it is replaying.
+    pub fn with_fixture(self, fixture: Option<FixtureSession>) -> StudioClientConfig {
+        StudioClientConfig { fixture, ..self }
+    }
+
+    /// Reports the progress of commands run with this config to `progress`
+    pub fn with_progress(self, progress: ProgressReporter) -> StudioClientConfig {
+        StudioClientConfig { progress, ..self }
+    }
+
+    pub(crate) fn get_progress(&self) -> &ProgressReporter {
+        &self.progress
+    }
+
     pub(crate) fn get_reqwest_client(&self) -> Result<Client> {
         if let Some(client) = &self.client {
             Ok(client.clone())
@@ -156,13 +196,104 @@ impl StudioClientConfig {
     }
 
     pub fn get_authenticated_client(&self, profile_opt: &ProfileOpt) -> Result<StudioClient> {
-        let credential = config::Profile::get_credential(&profile_opt.profile_name, &self.config)?;
+        let credential = match &self.fixture {
+            // replays never reach Apollo Studio, so they don't need a real API key
+            Some(fixture) if fixture.is_replaying() => config::Credential {
+                api_key: String::new(),
+                origin: config::CredentialOrigin::EnvVar,
+            },
+            _ => config::Profile::get_credential(&profile_opt.profile_name, &self.config)?,
+        };
         Ok(StudioClient::new(
             credential,
             &self.uri,
             &self.version,
             self.is_sudo,
             self.get_reqwest_client()?,
-        ))
+        )
+        .with_request_budget(self.request_budget.clone())
+        .with_fixture(self.fixture.clone()))
+    }
+
+    /// Creates a client authenticated with a profile's key, or a client without any
+    /// credentials if Rover hasn't been configured with any, for commands that can
+    /// read public variants
+    pub fn get_public_client(&self, profile_opt: &ProfileOpt) -> Result<StudioClient> {
+        match self.get_authenticated_client(profile_opt) {
+            Err(e)
+                if matches!(
+                    e.downcast_ref::<HoustonProblem>(),
+                    Some(HoustonProblem::NoConfigProfiles)
+                ) =>
+            {
+                tracing::debug!("no credentials were found, so requests are made anonymously");
+                let credential = config::Credential {
+                    api_key: String::new(),
+                    origin: config::CredentialOrigin::Anonymous,
+                };
+                Ok(StudioClient::new(
+                    credential,
+                    &self.uri,
+                    &self.version,
+                    self.is_sudo,
+                    self.get_reqwest_client()?,
+                )
+                .with_request_budget(self.request_budget.clone())
+                .with_fixture(self.fixture.clone()))
+            }
+            result => result,
+        }
+    }
+
+    /// Creates a client authenticated with `api_key` instead of a profile's key,
+    /// i.e. to check that a newly created key works
+    pub fn get_client_for_key(&self, api_key: String) -> Result<StudioClient> {
+        let credential = config::Credential {
+            api_key,
+            origin: config::CredentialOrigin::EnvVar,
+        };
+        Ok(StudioClient::new(
+            credential,
+            &self.uri,
+            &self.version,
+            self.is_sudo,
+            self.get_reqwest_client()?,
+        )
+        .with_request_budget(self.request_budget.clone())
+        .with_fixture(self.fixture.clone()))
+    }
+}
+
+/// The profile whose credentials a client uses, which is `None` for anonymous clients
+pub fn get_credentials_profile(client: &StudioClient, profile_opt: &ProfileOpt) -> Option<String> {
+    if client.is_anonymous() {
+        None
+    } else {
+        Some(profile_opt.profile_name.clone())
+    }
+}
+
+/// Anonymous clients can only read public variants, so when one can't find a variant,
+/// or isn't allowed to read it, the user most likely needs to authenticate
+pub fn explain_anonymous_failure(
+    client: &StudioClient,
+    graph_ref: &GraphRef,
+    error: RoverClientError,
+) -> RoverError {
+    match error {
+        RoverClientError::GraphNotFound { .. }
+        | RoverClientError::PermissionError { .. }
+        | RoverClientError::InvalidKey
+        | RoverClientError::GraphQl { .. }
+            if client.is_anonymous() =>
+        {
+            let mut err = RoverError::new(anyhow!(
+                "Could not read {} without credentials. Only public variants can be read without authenticating Rover.",
+                graph_ref
+            ));
+            err.set_suggestion(RoverErrorSuggestion::NewUserNoProfiles);
+            err
+        }
+        error => RoverError::new(error),
     }
 }
@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use apollo_federation_types::build::BuildHint;
+use apollo_federation_types::config::{FederationVersion, SchemaSource, SupergraphConfig};
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{DateTime, Utc};
+use rover_std::Fs;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::command::supergraph::compose::CompositionOutput;
+use crate::RoverResult;
+
+/// An on-disk record of the last composition of each supergraph config, so composing
+/// a supergraph whose sources haven't changed reuses the last supergraph schema
+/// without resolving any subgraph or running the composition plugin again.
+///
+/// Each record holds a hash of every subgraph's routing URL and source, so a
+/// composition that isn't reused can say which subgraphs changed. `file` and `sdl`
+/// sources are hashed with their schema, but `subgraph_url` and `graphref` sources
+/// can only be hashed with where their schema comes from, so a composition with any
+/// of those is only reused until it's older than `remote_ttl`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompositionCache {
+    path: Utf8PathBuf,
+    remote_ttl: Option<Duration>,
+}
+
+/// Whether a composition was reused from the [`CompositionCache`]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct CompositionCacheStatus {
+    pub hit: bool,
+    /// the subgraphs added, removed, or changed since the cached composition,
+    /// which is every subgraph if nothing was cached
+    pub changed_subgraphs: Vec<String>,
+}
+
+/// What a supergraph config is composed from, read without resolving any subgraph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompositionInputs {
+    /// the requested federation version and composition binary, since either composes differently
+    composer: String,
+    /// the hash of each subgraph's routing URL and source, by name
+    subgraphs: BTreeMap<String, String>,
+    /// whether any subgraph has a `subgraph_url` or `graphref` source
+    has_remote_sources: bool,
+}
+
+/// The last composition of a supergraph config, as stored in the cache directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct CachedComposition {
+    composer: String,
+    /// the hash of each subgraph, by name
+    subgraphs: BTreeMap<String, String>,
+    composed_at: DateTime<Utc>,
+    supergraph_sdl: String,
+    hints: Vec<BuildHint>,
+    federation_version: Option<String>,
+}
+
+impl CompositionInputs {
+    /// The inputs of an unresolved supergraph config whose `file` sources are relative to
+    /// `config_dir`. Each of `override_files` replaces the source of the subgraph it's keyed by
+    pub fn new(
+        supergraph_config: &SupergraphConfig,
+        config_dir: &Utf8Path,
+        override_files: &BTreeMap<String, Utf8PathBuf>,
+        federation_version: Option<&FederationVersion>,
+        composition_binary: Option<&Utf8Path>,
+    ) -> Self {
+        let config_federation_version = supergraph_config.get_federation_version();
+        let composer = format!(
+            "{}\n{}",
+            federation_version
+                .or(config_federation_version.as_ref())
+                .map(|version| version.get_tarball_version())
+                .unwrap_or_else(|| "detected".to_string()),
+            composition_binary.map(Utf8Path::as_str).unwrap_or_default()
+        );
+        let mut has_remote_sources = false;
+        let subgraphs = supergraph_config
+            .clone()
+            .into_iter()
+            .map(|(name, subgraph)| {
+                let mut hasher = Sha256::new();
+                hasher.update(subgraph.routing_url.unwrap_or_default().as_bytes());
+                hasher.update(b"\n");
+                match (override_files.get(&name), subgraph.schema) {
+                    (Some(file), _) => hash_file(&mut hasher, file),
+                    (None, SchemaSource::File { file }) => {
+                        hash_file(&mut hasher, &config_dir.join(file))
+                    }
+                    (None, SchemaSource::Sdl { sdl }) => {
+                        hasher.update(b"sdl\n");
+                        hasher.update(sdl.as_bytes());
+                    }
+                    (None, SchemaSource::SubgraphIntrospection { subgraph_url }) => {
+                        has_remote_sources = true;
+                        hasher.update(b"subgraph_url\n");
+                        hasher.update(subgraph_url.as_str().as_bytes());
+                    }
+                    (None, SchemaSource::Subgraph { graphref, subgraph }) => {
+                        has_remote_sources = true;
+                        hasher.update(b"graphref\n");
+                        hasher.update(format!("{}\n{}", graphref, subgraph).as_bytes());
+                    }
+                }
+                (name, format!("{:x}", hasher.finalize()))
+            })
+            .collect();
+        Self {
+            composer,
+            subgraphs,
+            has_remote_sources,
+        }
+    }
+}
+
+/// Hashes the contents of a schema file, or its path if it can't be read, which resolving it reports
+fn hash_file(hasher: &mut Sha256, file: &Utf8Path) {
+    match std::fs::read(file) {
+        Ok(contents) => {
+            hasher.update(b"file\n");
+            hasher.update(contents);
+        }
+        Err(_) => {
+            hasher.update(b"unreadable file\n");
+            hasher.update(file.as_str().as_bytes());
+        }
+    }
+}
+
+impl CompositionCache {
+    /// The cache of the supergraph config at `config_id` in Rover's config directory.
+    /// Compositions with `subgraph_url` or `graphref` sources are reused for `remote_ttl`
+    pub fn new(config_home: &Utf8Path, config_id: &str, remote_ttl: Option<Duration>) -> Self {
+        let key = format!("{:x}", Sha256::digest(config_id.as_bytes()));
+        Self {
+            path: config_home
+                .join("cache")
+                .join("composition")
+                .join(format!("{}.json", key)),
+            remote_ttl,
+        }
+    }
+
+    /// The cached composition of these inputs, if there is one
+    pub fn get(
+        &self,
+        inputs: &CompositionInputs,
+    ) -> (CompositionCacheStatus, Option<CompositionOutput>) {
+        // an entry that can't be read, i.e. one written by another version of Rover, is a miss
+        let cached = Fs::read_file(&self.path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<CachedComposition>(&contents).ok());
+        let cached = match cached {
+            Some(cached) => cached,
+            None => {
+                let status = CompositionCacheStatus {
+                    hit: false,
+                    changed_subgraphs: inputs.subgraphs.keys().cloned().collect(),
+                };
+                return (status, None);
+            }
+        };
+
+        let mut changed_subgraphs: Vec<String> = inputs
+            .subgraphs
+            .iter()
+            .filter(|(name, hash)| cached.subgraphs.get(*name) != Some(*hash))
+            .chain(
+                cached
+                    .subgraphs
+                    .iter()
+                    .filter(|(name, _)| !inputs.subgraphs.contains_key(*name)),
+            )
+            .map(|(name, _)| name.clone())
+            .collect();
+        changed_subgraphs.sort();
+        let is_fresh = !inputs.has_remote_sources
+            || match (
+                self.remote_ttl,
+                Utc::now()
+                    .signed_duration_since(cached.composed_at)
+                    .to_std(),
+            ) {
+                (Some(remote_ttl), Ok(age)) => age < remote_ttl,
+                _ => false,
+            };
+        let status = CompositionCacheStatus {
+            hit: changed_subgraphs.is_empty() && cached.composer == inputs.composer && is_fresh,
+            changed_subgraphs,
+        };
+        if !status.hit {
+            return (status, None);
+        }
+
+        tracing::debug!("using the composition cached at {}", &self.path);
+        let output = CompositionOutput {
+            supergraph_sdl: cached.supergraph_sdl,
+            hints: cached.hints,
+            federation_version: cached.federation_version,
+            missing_subgraphs: Vec::new(),
+            subgraph_metrics: Vec::new(),
+            cache: Some(status.clone()),
+        };
+        (status, Some(output))
+    }
+
+    /// Caches the composition of these inputs, replacing the last one
+    pub fn put(&self, inputs: &CompositionInputs, output: &CompositionOutput) -> RoverResult<()> {
+        if let Some(dir) = self.path.parent() {
+            Fs::create_dir_all(dir)?;
+        }
+        let cached = CachedComposition {
+            composer: inputs.composer.clone(),
+            subgraphs: inputs.subgraphs.clone(),
+            composed_at: Utc::now(),
+            supergraph_sdl: output.supergraph_sdl.clone(),
+            hints: output.hints.clone(),
+            federation_version: output.federation_version.clone(),
+        };
+        Fs::write_file(&self.path, serde_json::to_string(&cached)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+
+    fn inputs(yaml: &str, config_dir: &Utf8Path) -> CompositionInputs {
+        let supergraph_config = SupergraphConfig::new_from_yaml(yaml).unwrap();
+        CompositionInputs::new(&supergraph_config, config_dir, &BTreeMap::new(), None, None)
+    }
+
+    #[test]
+    fn it_reuses_compositions_of_unchanged_sources() {
+        let tmp_home = TempDir::new().unwrap();
+        let home = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        Fs::write_file(home.join("reviews.graphql"), "type Query { b: String }").unwrap();
+        let cache = CompositionCache::new(&home, "/supergraph.yaml", None);
+        let config = r#"federation_version: =2.3.1
+subgraphs:
+  products:
+    routing_url: http://products.example.com
+    schema:
+      sdl: "type Query { a: String }"
+  reviews:
+    routing_url: http://reviews.example.com
+    schema:
+      file: ./reviews.graphql
+"#;
+        let subgraphs = inputs(config, &home);
+
+        let (status, cached) = cache.get(&subgraphs);
+        assert!(!status.hit && cached.is_none());
+        assert_eq!(status.changed_subgraphs, vec!["products", "reviews"]);
+
+        let output = CompositionOutput {
+            supergraph_sdl: "type Query { a: String b: String }".to_string(),
+            hints: Vec::new(),
+            federation_version: Some("2.3.1".to_string()),
+            missing_subgraphs: Vec::new(),
+            subgraph_metrics: Vec::new(),
+            cache: None,
+        };
+        cache.put(&subgraphs, &output).unwrap();
+        let (status, cached) = cache.get(&subgraphs);
+        assert!(status.hit);
+        assert_eq!(cached.unwrap().supergraph_sdl, output.supergraph_sdl);
+
+        let (status, cached) = cache.get(&inputs(&config.replace("=2.3.1", "=2.4.0"), &home));
+        assert!(!status.hit && cached.is_none());
+        assert!(status.changed_subgraphs.is_empty());
+
+        Fs::write_file(home.join("reviews.graphql"), "type Query { b: Int }").unwrap();
+        let (status, _) = cache.get(&inputs(config, &home));
+        assert_eq!(status.changed_subgraphs, vec!["reviews"]);
+        assert!(CompositionCache::new(&home, "/other.yaml", None)
+            .get(&subgraphs)
+            .1
+            .is_none());
+    }
+
+    #[test]
+    fn it_only_reuses_compositions_of_remote_sources_for_the_ttl() {
+        let tmp_home = TempDir::new().unwrap();
+        let home = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        let remote = inputs(
+            r#"subgraphs:
+  products:
+    routing_url: http://products.example.com
+    schema:
+      subgraph_url: http://products.example.com/graphql
+"#,
+            &home,
+        );
+        let output = CompositionOutput {
+            supergraph_sdl: "type Query { a: String }".to_string(),
+            hints: Vec::new(),
+            federation_version: Some("2.3.1".to_string()),
+            missing_subgraphs: Vec::new(),
+            subgraph_metrics: Vec::new(),
+            cache: None,
+        };
+        let cache = CompositionCache::new(&home, "/supergraph.yaml", None);
+        cache.put(&remote, &output).unwrap();
+        assert!(!cache.get(&remote).0.hit);
+        let cache =
+            CompositionCache::new(&home, "/supergraph.yaml", Some(Duration::from_secs(300)));
+        assert!(cache.get(&remote).0.hit);
+    }
+}
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use rover_std::Style;
+
+/// How long a command is expected to take, and what to suggest
+/// to the user when it takes longer than that
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DurationBudget {
+    threshold: Duration,
+    hint: String,
+}
+
+impl DurationBudget {
+    pub fn new(threshold: Duration, hint: impl Into<String>) -> Self {
+        Self {
+            threshold,
+            hint: hint.into(),
+        }
+    }
+
+    /// The budget for commands that make requests to Apollo Studio
+    pub fn studio_request() -> Self {
+        Self::new(
+            Duration::from_secs(10),
+            format!(
+                "If your network is slow or behind a proxy, make sure {} is set (see {}), and consider raising {} or passing {} to publishes.",
+                Style::Command.paint("`HTTPS_PROXY`"),
+                Style::Link.paint("https://www.apollographql.com/docs/rover/proxy"),
+                Style::Command.paint("`--client-timeout`"),
+                Style::Command.paint("`--retries`")
+            ),
+        )
+    }
+
+    /// The budget for commands that run a composition plugin
+    pub fn composition() -> Self {
+        Self::new(
+            Duration::from_secs(30),
+            format!(
+                "Pass {} to reuse a composition plugin that is already installed instead of checking for updates.",
+                Style::Command.paint("`--skip-update`")
+            ),
+        )
+    }
+
+    /// The budget for commands that download files from the internet
+    pub fn download() -> Self {
+        Self::new(
+            Duration::from_secs(30),
+            format!(
+                "If your network is behind a proxy, make sure {} is set (see {}).",
+                Style::Command.paint("`HTTPS_PROXY`"),
+                Style::Link.paint("https://www.apollographql.com/docs/rover/proxy")
+            ),
+        )
+    }
+
+    /// Replaces the default threshold, i.e. with the value of `$APOLLO_COMMAND_DURATION_THRESHOLD_SECONDS`
+    pub fn with_threshold(self, threshold: Duration) -> Self {
+        Self { threshold, ..self }
+    }
+
+    /// Returns a one-line hint if a command that took `elapsed` went over budget.
+    /// A threshold of zero disables the hint.
+    pub fn get_hint(&self, elapsed: Duration) -> Option<String> {
+        if self.threshold.is_zero() || elapsed <= self.threshold {
+            None
+        } else {
+            Some(format!(
+                "This command took {}s. {}",
+                elapsed.as_secs(),
+                &self.hint
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_is_silent_within_budget() {
+        let budget = DurationBudget::new(Duration::from_secs(10), "try harder");
+        assert_eq!(budget.get_hint(Duration::from_secs(3)), None);
+    }
+
+    #[test]
+    fn it_hints_over_budget() {
+        let budget = DurationBudget::new(Duration::from_secs(10), "try harder");
+        assert_eq!(
+            budget.get_hint(Duration::from_secs(12)),
+            Some("This command took 12s. try harder".to_string())
+        );
+    }
+
+    #[test]
+    fn a_zero_threshold_disables_hints() {
+        let budget = DurationBudget::studio_request().with_threshold(Duration::ZERO);
+        assert_eq!(budget.get_hint(Duration::from_secs(600)), None);
+    }
+}
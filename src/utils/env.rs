@@ -106,6 +106,10 @@ pub enum RoverEnvKey {
     VcsAuthor,
     NodeModulesBin,
     ChecksTimeoutSeconds,
+    TmpDir,
+    CommandDurationThresholdSeconds,
+    PluginPublicKey,
+    RequirePluginChecksums,
 }
 
 impl fmt::Display for RoverEnvKey {
@@ -130,6 +134,14 @@ mod tests {
         assert_eq!(&RoverEnvKey::ConfigHome.to_string(), expected_key);
     }
 
+    #[test]
+    fn it_matches_the_tmp_dir_rover_std_reads() {
+        assert_eq!(
+            RoverEnvKey::TmpDir.to_string(),
+            rover_std::tmp::TMP_DIR_ENV_VAR
+        );
+    }
+
     #[test]
     fn it_can_set_and_read_from_mock() {
         let expected_value = "hey whats the big idea anyway!??";
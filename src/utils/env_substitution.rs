@@ -0,0 +1,216 @@
+use std::collections::BTreeSet;
+
+use anyhow::anyhow;
+use serde_yaml::Value;
+
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// Expands `${VAR}` and `${VAR:-default}` in the string values of a YAML document,
+/// so one supergraph config can point at different URLs and files in each environment.
+///
+/// The default is used when the variable is unset or empty, and `$${` is left as a
+/// literal `${`. Inline `sdl` values are left alone, since they're schemas, not settings.
+/// Documents without a `${` are returned as they are.
+pub fn expand_env_vars(
+    contents: &str,
+    description: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> RoverResult<String> {
+    if !contents.contains("${") {
+        return Ok(contents.to_string());
+    }
+    let mut document: Value = serde_yaml::from_str(contents)?;
+    let mut missing = BTreeSet::new();
+    expand_value(&mut document, "", &lookup, &mut missing);
+    if missing.is_empty() {
+        return Ok(serde_yaml::to_string(&document)?);
+    }
+
+    let missing: Vec<String> = missing
+        .iter()
+        .map(|(name, path)| format!("`{}` (in {})", name, path))
+        .collect();
+    let mut err = RoverError::new(anyhow!(
+        "{} refers to environment variables that aren't set: {}",
+        description,
+        missing.join(", ")
+    ));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(
+        "Set these environment variables, or give them a default with `${VAR:-default}`."
+            .to_string(),
+    ));
+    Err(err)
+}
+
+fn expand_value(
+    value: &mut Value,
+    path: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+    missing: &mut BTreeSet<(String, String)>,
+) {
+    match value {
+        Value::String(string) => {
+            *string = expand_env_vars_in_string(string, lookup, &mut |name| {
+                missing.insert((name.to_string(), path.to_string()));
+            })
+        }
+        Value::Sequence(sequence) => {
+            for (index, value) in sequence.iter_mut().enumerate() {
+                expand_value(value, &format!("{}[{}]", path, index), lookup, missing);
+            }
+        }
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping.iter_mut() {
+                let key = match key {
+                    Value::String(key) => key.clone(),
+                    key => serde_yaml::to_string(key)
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                };
+                if key == "sdl" {
+                    continue;
+                }
+                let path = if path.is_empty() {
+                    key
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                expand_value(value, &path, lookup, missing);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Expands the variables in one string, i.e. a `--header` value, reporting each one that
+/// isn't set and has no default. A `${` without a closing `}`, or with something other than
+/// a variable name in it, is left as it is
+pub fn expand_env_vars_in_string(
+    string: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+    on_missing: &mut impl FnMut(&str),
+) -> String {
+    let mut expanded = String::with_capacity(string.len());
+    let mut rest = string;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(escaped) = rest.strip_prefix("$${") {
+            expanded.push_str("${");
+            rest = escaped;
+            continue;
+        }
+        let reference = match rest
+            .strip_prefix("${")
+            .and_then(|inner| inner.find('}').map(|end| &inner[..end]))
+        {
+            Some(reference) => reference,
+            None => {
+                expanded.push('$');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        let (name, default) = match reference.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
+        };
+        if !is_variable_name(name) {
+            expanded.push('$');
+            rest = &rest[1..];
+            continue;
+        }
+        match (lookup(name), default) {
+            (Some(value), Some(default)) if value.is_empty() => expanded.push_str(default),
+            (Some(value), _) => expanded.push_str(&value),
+            (None, Some(default)) => expanded.push_str(default),
+            (None, None) => on_missing(name),
+        }
+        rest = &rest["${".len() + reference.len() + "}".len()..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "PRODUCTS_HOST" => Some("products.staging.example.com".to_string()),
+            "SCHEMA_DIR" => Some("./staging".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn it_expands_env_vars_outside_of_sdl() {
+        let config = r#"subgraphs:
+  products:
+    routing_url: https://${PRODUCTS_HOST}/graphql
+    schema:
+      file: ${SCHEMA_DIR}/products.graphql
+  reviews:
+    routing_url: ${REVIEWS_URL:-http://localhost:4002/graphql}
+    schema:
+      sdl: "type Query { price: String @deprecated(reason: \"use $${PRICE}\") } # ${NOT_EXPANDED}"
+  users:
+    routing_url: http://${EMPTY:-localhost}:4003/$${LITERAL}
+    schema:
+      file: ./users.graphql
+"#;
+        let expanded: Value =
+            serde_yaml::from_str(&expand_env_vars(config, "the config", lookup).unwrap()).unwrap();
+        let subgraphs = &expanded["subgraphs"];
+        assert_eq!(
+            subgraphs["products"]["routing_url"],
+            "https://products.staging.example.com/graphql"
+        );
+        assert_eq!(
+            subgraphs["products"]["schema"]["file"],
+            "./staging/products.graphql"
+        );
+        assert_eq!(
+            subgraphs["reviews"]["routing_url"],
+            "http://localhost:4002/graphql"
+        );
+        assert_eq!(
+            subgraphs["reviews"]["schema"]["sdl"],
+            "type Query { price: String @deprecated(reason: \"use $${PRICE}\") } # ${NOT_EXPANDED}"
+        );
+        assert_eq!(
+            subgraphs["users"]["routing_url"],
+            "http://localhost:4003/${LITERAL}"
+        );
+    }
+
+    #[test]
+    fn it_reports_every_missing_env_var() {
+        let config = r#"subgraphs:
+  products:
+    routing_url: https://${PRODUCTS_HOST}/graphql
+    schema:
+      file: ${PRODUCTS_DIR}/products.graphql
+  reviews:
+    routing_url: ${REVIEWS_URL}
+    schema:
+      file: ${PRODUCTS_DIR}/reviews.graphql
+"#;
+        let message = expand_env_vars(config, "the config", lookup)
+            .unwrap_err()
+            .message();
+        assert!(message.contains("`PRODUCTS_DIR` (in subgraphs.products.schema.file)"));
+        assert!(message.contains("`PRODUCTS_DIR` (in subgraphs.reviews.schema.file)"));
+        assert!(message.contains("`REVIEWS_URL` (in subgraphs.reviews.routing_url)"));
+        assert!(!message.contains("PRODUCTS_HOST"));
+    }
+}
@@ -0,0 +1,90 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use anyhow::{anyhow, Context};
+
+use crate::RoverResult;
+
+/// Runs a hook, which is a shell command a user configured to run after a recomposition.
+/// `kind` describes the hook in errors, i.e. `composition hook`.
+///
+/// The hook gets `APOLLO_ROVER=true` and `env` in its environment, and `stdin` on its stdin
+/// if there is one. Its stdout goes to Rover's stderr, since Rover may be printing one line
+/// of JSON per recomposition to stdout
+pub fn run_hook(
+    command: &str,
+    kind: &str,
+    env: &[(&str, String)],
+    stdin: Option<&str>,
+) -> RoverResult<()> {
+    tracing::info!("running the `{}` {}", command, kind);
+    let mut child = shell_command(command)
+        .env("APOLLO_ROVER", "true")
+        .envs(env.iter().cloned())
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| format!("could not run the `{}` {}", command, kind))?;
+
+    if let (Some(mut hook_stdin), Some(stdin)) = (child.stdin.take(), stdin) {
+        // written from another thread so a hook that fills its stdout before reading stdin
+        // can't block on Rover, and hooks are free to ignore stdin, so a closed pipe isn't an error
+        let stdin = stdin.to_string();
+        std::thread::spawn(move || {
+            let _ = hook_stdin.write_all(stdin.as_bytes());
+        });
+    }
+
+    let output = child
+        .wait_with_output()
+        .with_context(|| format!("could not wait for the `{}` {}", command, kind))?;
+    eprint!("{}", String::from_utf8_lossy(&output.stdout));
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(anyhow!("the `{}` {} exited with {}", command, kind, output.status).into())
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_passes_the_env_and_stdin_to_hooks() {
+        let env = [("ROVER_TEST_HOOK_STATUS", "success".to_string())];
+        assert!(run_hook(
+            r#"test "$APOLLO_ROVER $ROVER_TEST_HOOK_STATUS" = "true success" && test "$(cat)" = payload"#,
+            "test hook",
+            &env,
+            Some("payload"),
+        )
+        .is_ok());
+
+        let message = run_hook("exit 3", "test hook", &env, None)
+            .unwrap_err()
+            .message();
+        assert!(message.contains("the `exit 3` test hook exited with"));
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{DateTime, Utc};
+use rover_std::Fs;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::RoverResult;
+
+/// An on-disk cache of the schemas introspected from `subgraph_url` sources,
+/// so composing the same supergraph over and over doesn't introspect every
+/// subgraph each time.
+///
+/// Schemas are keyed by the URL they were introspected from and a hash of the
+/// headers sent along, so the headers themselves are never written to disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntrospectionCache {
+    dir: Utf8PathBuf,
+    ttl: Duration,
+}
+
+/// One cached introspection result, as stored in the cache directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct CachedSchema {
+    subgraph_url: String,
+    fetched_at: DateTime<Utc>,
+    sdl: String,
+}
+
+impl IntrospectionCache {
+    /// A cache in Rover's config directory whose schemas are reused for `ttl`
+    pub fn new(config_home: &Utf8Path, ttl: Duration) -> Self {
+        Self {
+            dir: config_home.join("cache").join("introspection"),
+            ttl,
+        }
+    }
+
+    /// The schema introspected from `subgraph_url` with `headers`,
+    /// if it was cached less than the TTL ago
+    pub fn get(&self, subgraph_url: &str, headers: &HashMap<String, String>) -> Option<String> {
+        let path = self.get_path(subgraph_url, headers);
+        let contents = Fs::read_file(&path).ok()?;
+        // an entry that can't be read, i.e. one written by another version of Rover, is a miss
+        let cached: CachedSchema = serde_json::from_str(&contents).ok()?;
+        let age = Utc::now()
+            .signed_duration_since(cached.fetched_at)
+            .to_std()
+            .ok()?;
+        if cached.subgraph_url == subgraph_url && age < self.ttl {
+            tracing::debug!("using the schema of {} cached at {}", subgraph_url, &path);
+            Some(cached.sdl)
+        } else {
+            None
+        }
+    }
+
+    /// Caches the schema just introspected from `subgraph_url` with `headers`
+    pub fn put(
+        &self,
+        subgraph_url: &str,
+        headers: &HashMap<String, String>,
+        sdl: &str,
+    ) -> RoverResult<()> {
+        Fs::create_dir_all(&self.dir)?;
+        let cached = CachedSchema {
+            subgraph_url: subgraph_url.to_string(),
+            fetched_at: Utc::now(),
+            sdl: sdl.to_string(),
+        };
+        Fs::write_file(
+            self.get_path(subgraph_url, headers),
+            serde_json::to_string(&cached)?,
+        )?;
+        Ok(())
+    }
+
+    fn get_path(&self, subgraph_url: &str, headers: &HashMap<String, String>) -> Utf8PathBuf {
+        self.dir
+            .join(format!("{}.json", get_key(subgraph_url, headers)))
+    }
+}
+
+/// The SHA256 of a URL and its headers, which are sorted so their order doesn't matter
+fn get_key(subgraph_url: &str, headers: &HashMap<String, String>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(subgraph_url.as_bytes());
+    for (name, value) in headers.iter().collect::<BTreeMap<_, _>>() {
+        hasher.update(format!("\n{}: {}", name.to_lowercase(), value).as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+
+    #[test]
+    fn it_caches_schemas_by_url_and_headers() {
+        let tmp_home = TempDir::new().unwrap();
+        let home = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        let cache = IntrospectionCache::new(&home, Duration::from_secs(60));
+        let url = "http://localhost:4001/graphql";
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), "Bearer abc".to_string());
+
+        assert_eq!(cache.get(url, &headers), None);
+        cache
+            .put(url, &headers, "type Query { a: String }")
+            .unwrap();
+        assert_eq!(
+            cache.get(url, &headers),
+            Some("type Query { a: String }".to_string())
+        );
+        assert_eq!(cache.get(url, &HashMap::new()), None);
+        assert_eq!(cache.get("http://localhost:4002/graphql", &headers), None);
+
+        let expired = IntrospectionCache::new(&home, Duration::from_secs(0));
+        assert_eq!(expired.get(url, &headers), None);
+    }
+}
@@ -0,0 +1,573 @@
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use apollo_parser::{ast, Parser, SyntaxKind, SyntaxNode};
+use serde_json::{json, Value};
+
+use crate::RoverResult;
+
+/// Scalars that are part of every schema, whether or not the SDL defines them
+const BUILT_IN_SCALARS: &[&str] = &["String", "Int", "Float", "Boolean", "ID"];
+
+/// The reason given to `@deprecated` elements that don't have one of their own
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// Converts an SDL schema into the result of an introspection query against it,
+/// i.e. `{ "__schema": { "queryType": { "name": "Query" }, "types": [...], ... } }`,
+/// for tools that only understand introspection JSON.
+///
+/// The `__Schema`, `__Type`, etc. introspection types aren't included, since every
+/// GraphQL server provides the same ones and clients add them back on their own.
+pub fn sdl_to_introspection_json(sdl: &str) -> RoverResult<Value> {
+    use ast::AstNode;
+
+    let parsed_ast = Parser::new(sdl).parse();
+    if let Some(error) = parsed_ast.errors().next() {
+        let message = error.message();
+        return Err(anyhow!("Could not parse the schema: {}", message).into());
+    }
+    let document = parsed_ast.document();
+
+    // type references need to know the kind of the type they point to,
+    // so every type is named before any of them are built
+    let mut kinds: BTreeMap<String, &'static str> = BUILT_IN_SCALARS
+        .iter()
+        .map(|scalar| (scalar.to_string(), "SCALAR"))
+        .collect();
+    for definition in document.definitions() {
+        if let Some((name, kind)) = get_name_and_kind(&definition) {
+            kinds.insert(name, kind);
+        }
+    }
+
+    let mut types: Vec<IntrospectionType> = Vec::new();
+    let mut directives = Vec::new();
+    let mut root_types: BTreeMap<String, String> = BTreeMap::new();
+    for definition in document.definitions() {
+        if let ast::Definition::SchemaDefinition(def) = &definition {
+            for root in def.root_operation_type_definitions() {
+                let operation = root
+                    .operation_type()
+                    .map(|op| significant_text(op.syntax()));
+                let name = root.named_type().and_then(|ty| ty.name());
+                if let (Some(operation), Some(name)) = (operation, name) {
+                    root_types.insert(operation, name.text().to_string());
+                }
+            }
+            continue;
+        }
+        if let ast::Definition::DirectiveDefinition(def) = &definition {
+            directives.push(get_directive(def, &kinds));
+            continue;
+        }
+        let (name, kind) = match get_name_and_kind(&definition) {
+            Some(name_and_kind) => name_and_kind,
+            None => continue,
+        };
+        let index = match types.iter().position(|ty| ty.name == name) {
+            Some(index) => index,
+            None => {
+                types.push(IntrospectionType::new(name, kind));
+                types.len() - 1
+            }
+        };
+        types[index].add_definition(&definition, &kinds);
+    }
+
+    for scalar in BUILT_IN_SCALARS {
+        if !types.iter().any(|ty| &ty.name == scalar) {
+            types.push(IntrospectionType::new(scalar.to_string(), "SCALAR"));
+        }
+    }
+
+    // every object type is a possible type of the interfaces it implements
+    let implementations: Vec<(String, String)> = types
+        .iter()
+        .filter(|ty| ty.kind == "OBJECT")
+        .flat_map(|ty| {
+            ty.interfaces
+                .iter()
+                .map(|interface| (interface.clone(), ty.name.clone()))
+        })
+        .collect();
+    for (interface, object) in implementations {
+        if let Some(ty) = types.iter_mut().find(|ty| ty.name == interface) {
+            ty.possible_types.push(object);
+        }
+    }
+
+    add_built_in_directives(&mut directives);
+
+    let get_root_type = |operation: &str, default: &str| {
+        let name = root_types
+            .get(operation)
+            .map(String::as_str)
+            .or_else(|| types.iter().any(|ty| ty.name == default).then_some(default));
+        match name {
+            Some(name) => json!({ "name": name }),
+            None => Value::Null,
+        }
+    };
+
+    Ok(json!({
+        "__schema": {
+            "queryType": get_root_type("query", "Query"),
+            "mutationType": get_root_type("mutation", "Mutation"),
+            "subscriptionType": get_root_type("subscription", "Subscription"),
+            "types": types.iter().map(|ty| ty.get_json(&kinds)).collect::<Vec<Value>>(),
+            "directives": directives,
+        }
+    }))
+}
+
+#[derive(Debug)]
+struct IntrospectionType {
+    name: String,
+    kind: &'static str,
+    description: Option<String>,
+    fields: Vec<Value>,
+    input_fields: Vec<Value>,
+    interfaces: Vec<String>,
+    enum_values: Vec<Value>,
+    possible_types: Vec<String>,
+}
+
+impl IntrospectionType {
+    fn new(name: String, kind: &'static str) -> IntrospectionType {
+        IntrospectionType {
+            name,
+            kind,
+            description: None,
+            fields: Vec::new(),
+            input_fields: Vec::new(),
+            interfaces: Vec::new(),
+            enum_values: Vec::new(),
+            possible_types: Vec::new(),
+        }
+    }
+
+    /// Adds everything a type definition or extension declares to this type
+    fn add_definition(&mut self, definition: &ast::Definition, kinds: &BTreeMap<String, &str>) {
+        use ast::Definition::*;
+
+        let description = match definition {
+            ObjectTypeDefinition(def) => def.description(),
+            InterfaceTypeDefinition(def) => def.description(),
+            UnionTypeDefinition(def) => def.description(),
+            EnumTypeDefinition(def) => def.description(),
+            InputObjectTypeDefinition(def) => def.description(),
+            ScalarTypeDefinition(def) => def.description(),
+            _ => None,
+        };
+        if let Some(description) = get_description(description) {
+            self.description = Some(description);
+        }
+
+        let (fields, interfaces) = match definition {
+            ObjectTypeDefinition(def) => (def.fields_definition(), def.implements_interfaces()),
+            ObjectTypeExtension(def) => (def.fields_definition(), def.implements_interfaces()),
+            InterfaceTypeDefinition(def) => (def.fields_definition(), def.implements_interfaces()),
+            InterfaceTypeExtension(def) => (def.fields_definition(), def.implements_interfaces()),
+            _ => (None, None),
+        };
+        for field in fields.iter().flat_map(|fields| fields.field_definitions()) {
+            let deprecation = get_deprecation(field.directives());
+            self.fields.push(json!({
+                "name": get_name(field.name()),
+                "description": get_description(field.description()),
+                "args": get_input_values(
+                    field
+                        .arguments_definition()
+                        .iter()
+                        .flat_map(|args| args.input_value_definitions()),
+                    kinds
+                ),
+                "type": get_type_ref(&type_string(field.ty()), kinds),
+                "isDeprecated": deprecation.is_some(),
+                "deprecationReason": deprecation,
+            }));
+        }
+        for interface in interfaces.iter().flat_map(|i| i.named_types()) {
+            self.interfaces.push(get_name(interface.name()));
+        }
+
+        let input_fields = match definition {
+            InputObjectTypeDefinition(def) => def.input_fields_definition(),
+            InputObjectTypeExtension(def) => def.input_fields_definition(),
+            _ => None,
+        };
+        if let Some(input_fields) = input_fields {
+            self.input_fields.extend(get_input_values(
+                input_fields.input_value_definitions(),
+                kinds,
+            ));
+        }
+
+        let enum_values = match definition {
+            EnumTypeDefinition(def) => def.enum_values_definition(),
+            EnumTypeExtension(def) => def.enum_values_definition(),
+            _ => None,
+        };
+        for value in enum_values.iter().flat_map(|v| v.enum_value_definitions()) {
+            let deprecation = get_deprecation(value.directives());
+            self.enum_values.push(json!({
+                "name": get_name(value.enum_value().and_then(|v| v.name())),
+                "description": get_description(value.description()),
+                "isDeprecated": deprecation.is_some(),
+                "deprecationReason": deprecation,
+            }));
+        }
+
+        let members = match definition {
+            UnionTypeDefinition(def) => def.union_member_types(),
+            UnionTypeExtension(def) => def.union_member_types(),
+            _ => None,
+        };
+        for member in members.iter().flat_map(|m| m.named_types()) {
+            self.possible_types.push(get_name(member.name()));
+        }
+    }
+
+    fn get_json(&self, kinds: &BTreeMap<String, &str>) -> Value {
+        let named_types = |names: &[String]| -> Value {
+            names
+                .iter()
+                .map(|name| get_type_ref(name, kinds))
+                .collect::<Vec<Value>>()
+                .into()
+        };
+        let has = |with_kinds: &[&str]| with_kinds.contains(&self.kind);
+        json!({
+            "kind": self.kind,
+            "name": self.name,
+            "description": self.description,
+            "fields": has(&["OBJECT", "INTERFACE"]).then(|| self.fields.clone()),
+            "inputFields": has(&["INPUT_OBJECT"]).then(|| self.input_fields.clone()),
+            "interfaces": has(&["OBJECT", "INTERFACE"]).then(|| named_types(&self.interfaces)),
+            "enumValues": has(&["ENUM"]).then(|| self.enum_values.clone()),
+            "possibleTypes": has(&["INTERFACE", "UNION"]).then(|| named_types(&self.possible_types)),
+        })
+    }
+}
+
+pub(crate) fn get_name_and_kind(definition: &ast::Definition) -> Option<(String, &'static str)> {
+    use ast::Definition::*;
+
+    let (name, kind) = match definition {
+        ObjectTypeDefinition(def) => (def.name(), "OBJECT"),
+        ObjectTypeExtension(def) => (def.name(), "OBJECT"),
+        InterfaceTypeDefinition(def) => (def.name(), "INTERFACE"),
+        InterfaceTypeExtension(def) => (def.name(), "INTERFACE"),
+        UnionTypeDefinition(def) => (def.name(), "UNION"),
+        UnionTypeExtension(def) => (def.name(), "UNION"),
+        EnumTypeDefinition(def) => (def.name(), "ENUM"),
+        EnumTypeExtension(def) => (def.name(), "ENUM"),
+        InputObjectTypeDefinition(def) => (def.name(), "INPUT_OBJECT"),
+        InputObjectTypeExtension(def) => (def.name(), "INPUT_OBJECT"),
+        ScalarTypeDefinition(def) => (def.name(), "SCALAR"),
+        ScalarTypeExtension(def) => (def.name(), "SCALAR"),
+        _ => return None,
+    };
+    Some((name?.text().to_string(), kind))
+}
+
+fn get_directive(def: &ast::DirectiveDefinition, kinds: &BTreeMap<String, &str>) -> Value {
+    use ast::AstNode;
+
+    let locations: Vec<String> = def
+        .directive_locations()
+        .iter()
+        .flat_map(|locations| locations.directive_locations())
+        .map(|location| significant_text(location.syntax()))
+        .collect();
+    json!({
+        "name": get_name(def.name()),
+        "description": get_description(def.description()),
+        "locations": locations,
+        "args": get_input_values(
+            def.arguments_definition()
+                .iter()
+                .flat_map(|args| args.input_value_definitions()),
+            kinds
+        ),
+        "isRepeatable": def.repeatable_token().is_some(),
+    })
+}
+
+/// Adds the directives every schema supports, unless the SDL redefines them
+fn add_built_in_directives(directives: &mut Vec<Value>) {
+    let string = json!({ "kind": "SCALAR", "name": "String", "ofType": null });
+    let non_null_boolean = json!({
+        "kind": "NON_NULL",
+        "name": null,
+        "ofType": { "kind": "SCALAR", "name": "Boolean", "ofType": null }
+    });
+    let non_null_string = json!({ "kind": "NON_NULL", "name": null, "ofType": string });
+    let arg = |name: &str, ty: &Value, default: Option<&str>| json!({ "name": name, "description": null, "type": ty, "defaultValue": default });
+
+    let built_ins = [
+        (
+            "skip",
+            vec!["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+            vec![arg("if", &non_null_boolean, None)],
+        ),
+        (
+            "include",
+            vec!["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+            vec![arg("if", &non_null_boolean, None)],
+        ),
+        (
+            "deprecated",
+            vec![
+                "FIELD_DEFINITION",
+                "ARGUMENT_DEFINITION",
+                "INPUT_FIELD_DEFINITION",
+                "ENUM_VALUE",
+            ],
+            vec![arg(
+                "reason",
+                &string,
+                Some(&format!("\"{}\"", DEFAULT_DEPRECATION_REASON)),
+            )],
+        ),
+        (
+            "specifiedBy",
+            vec!["SCALAR"],
+            vec![arg("url", &non_null_string, None)],
+        ),
+    ];
+    for (name, locations, args) in built_ins {
+        if !directives.iter().any(|directive| directive["name"] == name) {
+            directives.push(json!({
+                "name": name,
+                "description": null,
+                "locations": locations,
+                "args": args,
+                "isRepeatable": false,
+            }));
+        }
+    }
+}
+
+fn get_input_values(
+    values: impl Iterator<Item = ast::InputValueDefinition>,
+    kinds: &BTreeMap<String, &str>,
+) -> Vec<Value> {
+    use ast::AstNode;
+
+    values
+        .map(|value| {
+            let default_value = value
+                .default_value()
+                .and_then(|default| default.value())
+                .map(|default| default.syntax().text().to_string().trim().to_string());
+            json!({
+                "name": get_name(value.name()),
+                "description": get_description(value.description()),
+                "type": get_type_ref(&type_string(value.ty()), kinds),
+                "defaultValue": default_value,
+            })
+        })
+        .collect()
+}
+
+/// Turns a type as written in SDL, i.e. `[String!]!`, into a nested type reference
+fn get_type_ref(ty: &str, kinds: &BTreeMap<String, &str>) -> Value {
+    if let Some(inner) = ty.strip_suffix('!') {
+        json!({ "kind": "NON_NULL", "name": null, "ofType": get_type_ref(inner, kinds) })
+    } else if let Some(inner) = ty.strip_prefix('[').and_then(|ty| ty.strip_suffix(']')) {
+        json!({ "kind": "LIST", "name": null, "ofType": get_type_ref(inner, kinds) })
+    } else {
+        let kind = kinds.get(ty).copied().unwrap_or("OBJECT");
+        json!({ "kind": kind, "name": ty, "ofType": null })
+    }
+}
+
+/// The reason an element is deprecated, if it has a `@deprecated` directive
+pub(crate) fn get_deprecation(directives: Option<ast::Directives>) -> Option<String> {
+    let directive = directives
+        .iter()
+        .flat_map(|directives| directives.directives())
+        .find(|directive| {
+            directive
+                .name()
+                .map_or(false, |name| "deprecated" == name.text())
+        })?;
+    let reason = directive
+        .arguments()
+        .iter()
+        .flat_map(|args| args.arguments())
+        .find(|arg| arg.name().map_or(false, |name| "reason" == name.text()))
+        .and_then(|arg| arg.value());
+    match reason {
+        Some(ast::Value::StringValue(reason)) => Some(String::from(reason)),
+        _ => Some(DEFAULT_DEPRECATION_REASON.to_string()),
+    }
+}
+
+pub(crate) fn get_description(description: Option<ast::Description>) -> Option<String> {
+    description
+        .and_then(|description| description.string_value())
+        .map(String::from)
+}
+
+pub(crate) fn get_name(name: Option<ast::Name>) -> String {
+    name.map(|name| name.text().to_string()).unwrap_or_default()
+}
+
+pub(crate) fn type_string(ty: Option<ast::Type>) -> String {
+    use ast::AstNode;
+
+    ty.map(|ty| significant_text(ty.syntax()))
+        .unwrap_or_default()
+}
+
+/// The text of a node without any whitespace, commas, or comments
+fn significant_text(node: &SyntaxNode) -> String {
+    node.descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| {
+            !matches!(
+                token.kind(),
+                SyntaxKind::WHITESPACE | SyntaxKind::COMMENT | SyntaxKind::COMMA
+            )
+        })
+        .map(|token| token.text().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use assert_json_diff::assert_json_eq;
+
+    const SDL: &str = r#"
+"The root of all queries"
+type Query {
+  products(first: Int = 10): [Product!]!
+  node(id: ID!): Node
+}
+
+interface Node {
+  id: ID!
+}
+
+type Product implements Node {
+  id: ID!
+  name: String @deprecated(reason: "Use title")
+  status: Status
+}
+
+enum Status {
+  IN_STOCK
+  SOLD_OUT @deprecated
+}
+"#;
+
+    #[test]
+    fn it_converts_types_to_introspection_json() {
+        let introspection = sdl_to_introspection_json(SDL).unwrap();
+        let schema = &introspection["__schema"];
+        assert_json_eq!(schema["queryType"], json!({ "name": "Query" }));
+        assert_eq!(schema["mutationType"], Value::Null);
+
+        let names: Vec<&str> = schema["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ty| ty["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["Query", "Node", "Product", "Status", "String", "Int", "Float", "Boolean", "ID"]
+        );
+
+        assert_json_eq!(
+            schema["types"][0],
+            json!({
+                "kind": "OBJECT",
+                "name": "Query",
+                "description": "The root of all queries",
+                "fields": [
+                    {
+                        "name": "products",
+                        "description": null,
+                        "args": [{
+                            "name": "first",
+                            "description": null,
+                            "type": { "kind": "SCALAR", "name": "Int", "ofType": null },
+                            "defaultValue": "10"
+                        }],
+                        "type": {
+                            "kind": "NON_NULL",
+                            "name": null,
+                            "ofType": {
+                                "kind": "LIST",
+                                "name": null,
+                                "ofType": {
+                                    "kind": "NON_NULL",
+                                    "name": null,
+                                    "ofType": { "kind": "OBJECT", "name": "Product", "ofType": null }
+                                }
+                            }
+                        },
+                        "isDeprecated": false,
+                        "deprecationReason": null
+                    },
+                    {
+                        "name": "node",
+                        "description": null,
+                        "args": [{
+                            "name": "id",
+                            "description": null,
+                            "type": {
+                                "kind": "NON_NULL",
+                                "name": null,
+                                "ofType": { "kind": "SCALAR", "name": "ID", "ofType": null }
+                            },
+                            "defaultValue": null
+                        }],
+                        "type": { "kind": "INTERFACE", "name": "Node", "ofType": null },
+                        "isDeprecated": false,
+                        "deprecationReason": null
+                    }
+                ],
+                "inputFields": null,
+                "interfaces": [],
+                "enumValues": null,
+                "possibleTypes": null
+            })
+        );
+        assert_json_eq!(
+            schema["types"][1]["possibleTypes"],
+            json!([{ "kind": "OBJECT", "name": "Product", "ofType": null }])
+        );
+        assert_eq!(schema["types"][2]["fields"][1]["isDeprecated"], true);
+        assert_eq!(
+            schema["types"][2]["fields"][1]["deprecationReason"],
+            "Use title"
+        );
+        assert_eq!(
+            schema["types"][3]["enumValues"][1]["deprecationReason"],
+            DEFAULT_DEPRECATION_REASON
+        );
+    }
+
+    #[test]
+    fn it_includes_built_in_directives() {
+        let introspection = sdl_to_introspection_json(SDL).unwrap();
+        let names: Vec<&str> = introspection["__schema"]["directives"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|directive| directive["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["skip", "include", "deprecated", "specifiedBy"]);
+    }
+
+    #[test]
+    fn it_errs_on_invalid_sdl() {
+        assert!(sdl_to_introspection_json("type Query {").is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::anyhow;
+use serde_json::Value;
+
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// The fields in the `data` object of Rover's JSON output that can contain a schema,
+/// in order of preference.
+/// `sdl.contents` comes from `graph fetch` and `subgraph fetch`,
+/// `core_schema` comes from `supergraph fetch` and `supergraph compose`,
+/// and `introspection_response` comes from `graph introspect` and `subgraph introspect`.
+const SDL_POINTERS: [&str; 3] = ["/sdl/contents", "/core_schema", "/introspection_response"];
+
+/// RoverJsonInput reads the JSON that a previous Rover command printed
+/// with `--format json`, so its output can be piped into another command.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RoverJsonInput {
+    data: Value,
+}
+
+impl RoverJsonInput {
+    /// Parses the `{ "json_version", "data", "error" }` envelope printed by Rover,
+    /// erroring if the previous command did not succeed.
+    pub(crate) fn parse(contents: &str) -> RoverResult<RoverJsonInput> {
+        let envelope: Value = serde_json::from_str(contents).map_err(|e| {
+            let mut err = RoverError::new(anyhow!("Could not parse the input as JSON: {}", e));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Make sure the command you are piping to Rover was run with `--format json`."
+                    .to_string(),
+            ));
+            err
+        })?;
+
+        match envelope.get("json_version").and_then(Value::as_str) {
+            Some("1") => (),
+            Some(json_version) => {
+                return Err(RoverError::new(anyhow!(
+                    "This version of Rover does not understand JSON output with \"json_version\": \"{}\"",
+                    json_version
+                )))
+            }
+            None => {
+                return Err(RoverError::new(anyhow!(
+                    "The input does not look like the JSON output of a Rover command, it is missing \"json_version\""
+                )))
+            }
+        }
+
+        let data = envelope.get("data").cloned().unwrap_or(Value::Null);
+        if data.get("success").and_then(Value::as_bool) != Some(true) {
+            let message = envelope
+                .pointer("/error/message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(RoverError::new(anyhow!(
+                "The command that produced this input did not succeed: {}",
+                message
+            )));
+        }
+
+        Ok(RoverJsonInput { data })
+    }
+
+    /// Returns the schema contained in the input.
+    pub(crate) fn get_sdl(&self) -> RoverResult<String> {
+        SDL_POINTERS
+            .iter()
+            .find_map(|pointer| self.data.pointer(pointer).and_then(Value::as_str))
+            .map(|sdl| sdl.to_string())
+            .ok_or_else(|| {
+                let mut err = RoverError::new(anyhow!("The input does not contain a schema"));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                    "Pipe in the JSON output of a command that prints a schema, such as `rover graph fetch`, `rover supergraph compose`, or `rover graph introspect`.".to_string(),
+                ));
+                err
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::RoverJsonInput;
+
+    #[test]
+    fn it_reads_sdl_from_fetch_output() {
+        let input = json!({
+            "json_version": "1",
+            "data": {
+                "sdl": { "contents": "type Query { hello: String }" },
+                "success": true
+            },
+            "error": null
+        });
+        let sdl = RoverJsonInput::parse(&input.to_string())
+            .unwrap()
+            .get_sdl()
+            .unwrap();
+        assert_eq!(sdl, "type Query { hello: String }");
+    }
+
+    #[test]
+    fn it_reads_sdl_from_compose_output() {
+        let input = json!({
+            "json_version": "1",
+            "data": {
+                "core_schema": "schema @core { query: Query }",
+                "hints": [],
+                "success": true
+            },
+            "error": null
+        });
+        let sdl = RoverJsonInput::parse(&input.to_string())
+            .unwrap()
+            .get_sdl()
+            .unwrap();
+        assert_eq!(sdl, "schema @core { query: Query }");
+    }
+
+    #[test]
+    fn it_errs_on_failed_output() {
+        let input = json!({
+            "json_version": "1",
+            "data": { "success": false },
+            "error": { "message": "Some random error", "code": null }
+        });
+        assert!(RoverJsonInput::parse(&input.to_string()).is_err());
+    }
+
+    #[test]
+    fn it_errs_on_non_rover_json() {
+        assert!(RoverJsonInput::parse(r#"{ "hello": "world" }"#).is_err());
+        assert!(RoverJsonInput::parse("type Query { hello: String }").is_err());
+    }
+
+    #[test]
+    fn it_errs_on_output_without_sdl() {
+        let input = json!({
+            "json_version": "1",
+            "data": { "profiles": ["default"], "success": true },
+            "error": null
+        });
+        assert!(RoverJsonInput::parse(&input.to_string())
+            .unwrap()
+            .get_sdl()
+            .is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use rover_std::Style;
+use serde::Serialize;
+
+/// Diffs with more pairs of changed lines than this aren't aligned line by line,
+/// so comparing two very different schemas doesn't take too much memory
+const MAX_ALIGNED_LINES: usize = 4_000_000;
+
+#[derive(Debug, Serialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DiffLineKind {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+/// One line of a diff between two texts
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// the line's number in the old text, which is `None` for added lines
+    pub old_line: Option<usize>,
+    /// the line's number in the new text, which is `None` for removed lines
+    pub new_line: Option<usize>,
+    pub text: String,
+}
+
+/// Every line of `old` and `new`, in order, marking the ones that were added or removed
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lines at the start and end that didn't change don't need to be aligned
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_changed = &old[prefix..old.len() - suffix];
+    let new_changed = &new[prefix..new.len() - suffix];
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let mut old_line = 0;
+    let mut new_line = 0;
+    let mut push = |kind: DiffLineKind, text: &str| {
+        let old_number = (kind != DiffLineKind::Added).then(|| {
+            old_line += 1;
+            old_line
+        });
+        let new_number = (kind != DiffLineKind::Removed).then(|| {
+            new_line += 1;
+            new_line
+        });
+        lines.push(DiffLine {
+            kind,
+            old_line: old_number,
+            new_line: new_number,
+            text: text.to_string(),
+        });
+    };
+
+    for text in &old[..prefix] {
+        push(DiffLineKind::Unchanged, text);
+    }
+
+    let (n, m) = (old_changed.len(), new_changed.len());
+    if n * m <= MAX_ALIGNED_LINES {
+        // the length of the longest common subsequence of every pair of remaining lines
+        let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old_changed[i] == new_changed[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if old_changed[i] == new_changed[j] {
+                push(DiffLineKind::Unchanged, old_changed[i]);
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                push(DiffLineKind::Removed, old_changed[i]);
+                i += 1;
+            } else {
+                push(DiffLineKind::Added, new_changed[j]);
+                j += 1;
+            }
+        }
+        for text in &old_changed[i..] {
+            push(DiffLineKind::Removed, text);
+        }
+        for text in &new_changed[j..] {
+            push(DiffLineKind::Added, text);
+        }
+    } else {
+        for text in old_changed {
+            push(DiffLineKind::Removed, text);
+        }
+        for text in new_changed {
+            push(DiffLineKind::Added, text);
+        }
+    }
+
+    for text in &old[old.len() - suffix..] {
+        push(DiffLineKind::Unchanged, text);
+    }
+    lines
+}
+
+/// Prints a diff like `diff -u` would, with `context` unchanged lines around each change,
+/// coloring added lines green and removed lines red
+pub fn get_unified_diff(lines: &[DiffLine], context: usize) -> String {
+    // the ranges of `lines` to print, merging changes whose context overlaps
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if line.kind == DiffLineKind::Unchanged {
+            continue;
+        }
+        let start = index.saturating_sub(context);
+        let end = (index + context + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut diff = Vec::new();
+    for (start, end) in hunks {
+        let hunk = &lines[start..end];
+        let old_start = hunk.iter().find_map(|line| line.old_line).unwrap_or(0);
+        let new_start = hunk.iter().find_map(|line| line.new_line).unwrap_or(0);
+        let old_count = hunk.iter().filter(|line| line.old_line.is_some()).count();
+        let new_count = hunk.iter().filter(|line| line.new_line.is_some()).count();
+        diff.push(Style::Heading.paint(format!(
+            "@@ -{},{} +{},{} @@",
+            old_start, old_count, new_start, new_count
+        )));
+        for line in hunk {
+            diff.push(match line.kind {
+                DiffLineKind::Unchanged => format!(" {}", line.text),
+                DiffLineKind::Added => Style::DiffAddition.paint(format!("+{}", line.text)),
+                DiffLineKind::Removed => Style::DiffRemoval.paint(format!("-{}", line.text)),
+            });
+        }
+    }
+    diff.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(lines: &[DiffLine]) -> Vec<(DiffLineKind, &str)> {
+        lines
+            .iter()
+            .filter(|line| line.kind != DiffLineKind::Unchanged)
+            .map(|line| (line.kind, line.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn it_finds_added_and_removed_lines() {
+        let lines = diff_lines(
+            "type Query {\n  a: String\n  b: Int\n}\n",
+            "type Query {\n  a: String\n  c: Int\n  d: Int\n}\n",
+        );
+        assert_eq!(
+            changes(&lines),
+            vec![
+                (DiffLineKind::Removed, "  b: Int"),
+                (DiffLineKind::Added, "  c: Int"),
+                (DiffLineKind::Added, "  d: Int")
+            ]
+        );
+        assert_eq!(lines.last().unwrap().old_line, Some(4));
+        assert_eq!(lines.last().unwrap().new_line, Some(5));
+    }
+
+    #[test]
+    fn it_prints_hunks_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\n";
+        let new = "a\nB\nc\nd\ne\nf\nG\n";
+        let diff = get_unified_diff(&diff_lines(old, new), 1);
+        assert!(diff.contains("@@ -1,3 +1,3 @@"));
+        assert!(diff.contains("@@ -6,2 +6,2 @@"));
+        assert!(!diff.contains(" d"));
+        assert!(get_unified_diff(&diff_lines(old, old), 1).is_empty());
+    }
+}
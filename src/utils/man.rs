@@ -0,0 +1,282 @@
+use clap::{Arg, Command};
+
+/// A roff manpage for a single command, i.e. `rover-subgraph-publish.1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManPage {
+    /// The name of the page, which is every command leading to it joined with `-`
+    pub name: String,
+    pub contents: String,
+}
+
+impl ManPage {
+    /// The file name to save this page as in section 1 of the manual
+    pub fn get_file_name(&self) -> String {
+        format!("{}.1", self.name)
+    }
+}
+
+/// Renders a page for `command` and for each of its visible subcommands.
+/// Hidden commands and arguments are left out, as is clap's `help` subcommand.
+pub fn render_all(mut command: Command, version: &str) -> Vec<ManPage> {
+    // building the command propagates global arguments down to every subcommand
+    command.build();
+    let mut pages = Vec::new();
+    let root_name = command.get_name().to_lowercase();
+    render(&command, &[root_name], version, &mut pages);
+    pages
+}
+
+fn render(command: &Command, path: &[String], version: &str, pages: &mut Vec<ManPage>) {
+    let name = path.join("-");
+    let subcommands: Vec<&Command> = command
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set() && subcommand.get_name() != "help")
+        .collect();
+    let (positionals, options): (Vec<&Arg>, Vec<&Arg>) = command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set())
+        .partition(|arg| arg.is_positional());
+
+    let mut roff = format!(
+        ".TH {} 1 \"\" \"{} {}\" \"Rover Manual\"\n",
+        escape(&name.to_uppercase()),
+        escape(&path[0]),
+        escape(version)
+    );
+
+    roff.push_str(".SH NAME\n");
+    match command.get_about() {
+        Some(about) => roff.push_str(&format!("{} \\- {}\n", escape(&name), escape_text(about))),
+        None => roff.push_str(&format!("{}\n", escape(&name))),
+    }
+
+    roff.push_str(".SH SYNOPSIS\n");
+    roff.push_str(&format!("\\fB{}\\fR", escape(&path.join(" "))));
+    if !options.is_empty() {
+        roff.push_str(" [\\fIOPTIONS\\fR]");
+    }
+    for positional in &positionals {
+        let value = format!("\\fI{}\\fR", get_value_names(positional).join(" "));
+        if positional.is_required_set() {
+            roff.push_str(&format!(" {}", value));
+        } else {
+            roff.push_str(&format!(" [{}]", value));
+        }
+    }
+    if !subcommands.is_empty() {
+        roff.push_str(" \\fI<COMMAND>\\fR");
+    }
+    roff.push('\n');
+
+    if let Some(description) = command.get_long_about().or_else(|| command.get_about()) {
+        roff.push_str(".SH DESCRIPTION\n");
+        push_paragraphs(&mut roff, &description.to_string());
+    }
+
+    if !positionals.is_empty() {
+        roff.push_str(".SH ARGUMENTS\n");
+        for positional in positionals {
+            let header = format!("\\fI{}\\fR", get_value_names(positional).join(" "));
+            push_arg(&mut roff, &header, positional);
+        }
+    }
+
+    if !options.is_empty() {
+        roff.push_str(".SH OPTIONS\n");
+        for option in options {
+            push_arg(&mut roff, &get_option_header(option), option);
+        }
+    }
+
+    if !subcommands.is_empty() {
+        roff.push_str(".SH COMMANDS\n");
+        for subcommand in &subcommands {
+            let page = format!("{}-{}", name, subcommand.get_name());
+            roff.push_str(&format!(".TP\n\\fB{}(1)\\fR\n", escape(&page)));
+            if let Some(about) = subcommand.get_about() {
+                roff.push_str(&format!("{}\n", escape_text(about)));
+            }
+        }
+    }
+
+    // the extra help at the bottom of a command is where its examples live
+    if let Some(after_help) = command
+        .get_after_long_help()
+        .or_else(|| command.get_after_help())
+    {
+        roff.push_str(".SH EXAMPLES\n.nf\n");
+        for line in after_help.to_string().trim().lines() {
+            roff.push_str(&format!("{}\n", escape_line(line)));
+        }
+        roff.push_str(".fi\n");
+    }
+
+    if path.len() > 1 {
+        roff.push_str(&format!(
+            ".SH SEE ALSO\n\\fB{}(1)\\fR\n",
+            escape(&path[..path.len() - 1].join("-"))
+        ));
+    }
+
+    pages.push(ManPage {
+        name,
+        contents: roff,
+    });
+
+    for subcommand in subcommands {
+        let mut subcommand_path = path.to_vec();
+        subcommand_path.push(subcommand.get_name().to_string());
+        render(subcommand, &subcommand_path, version, pages);
+    }
+}
+
+fn push_arg(roff: &mut String, header: &str, arg: &Arg) {
+    roff.push_str(&format!(".TP\n{}\n", header));
+    if let Some(help) = arg.get_long_help().or_else(|| arg.get_help()) {
+        push_paragraphs(roff, &help.to_string());
+    }
+    let defaults: Vec<String> = arg
+        .get_default_values()
+        .iter()
+        .map(|value| value.to_string_lossy().to_string())
+        .collect();
+    if !defaults.is_empty() {
+        roff.push_str(&format!("[default: {}]\n", escape(&defaults.join(", "))));
+    }
+}
+
+fn get_option_header(option: &Arg) -> String {
+    let mut flags = Vec::new();
+    if let Some(short) = option.get_short() {
+        flags.push(format!("\\fB\\-{}\\fR", short));
+    }
+    if let Some(long) = option.get_long() {
+        flags.push(format!("\\fB\\-\\-{}\\fR", escape(long)));
+    }
+    let mut header = flags.join(", ");
+    if option.get_action().takes_values() {
+        header.push_str(&format!(" \\fI{}\\fR", get_value_names(option).join(" ")));
+    }
+    header
+}
+
+/// The escaped placeholders for the values an argument takes, i.e. `<GRAPH_REF>`
+fn get_value_names(arg: &Arg) -> Vec<String> {
+    match arg.get_value_names() {
+        Some(names) => names
+            .iter()
+            .map(|name| escape(&format!("<{}>", name)))
+            .collect(),
+        None => vec![escape(&format!(
+            "<{}>",
+            arg.get_id().as_str().to_uppercase()
+        ))],
+    }
+}
+
+/// Writes text separated by blank lines as roff paragraphs
+fn push_paragraphs(roff: &mut String, text: &str) {
+    for (i, paragraph) in text.trim().split("\n\n").enumerate() {
+        if i > 0 {
+            roff.push_str(".PP\n");
+        }
+        for line in paragraph.lines() {
+            roff.push_str(&format!("{}\n", escape_line(line.trim())));
+        }
+    }
+}
+
+fn escape_text(text: impl ToString) -> String {
+    escape(text.to_string().trim())
+}
+
+/// Escapes a line of text so roff doesn't read it as a request
+fn escape_line(line: &str) -> String {
+    let line = escape(line);
+    if line.starts_with('.') || line.starts_with('\'') {
+        format!("\\&{}", line)
+    } else {
+        line
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\e").replace('-', "\\-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use clap::ArgAction;
+
+    fn get_command() -> Command {
+        Command::new("Rover")
+            .about("Rover - Your Graph Companion")
+            .after_help("Get started by running:\n\n    $ rover config auth")
+            .arg(
+                Arg::new("log")
+                    .long("log")
+                    .short('l')
+                    .value_name("LOG_LEVEL")
+                    .global(true)
+                    .help("Specify Rover's log level"),
+            )
+            .subcommand(
+                Command::new("subgraph")
+                    .about("Subgraph schema commands")
+                    .subcommand(
+                        Command::new("publish")
+                            .about("Publish an updated subgraph schema")
+                            .arg(Arg::new("graph_ref").value_name("GRAPH_REF").required(true))
+                            .arg(
+                                Arg::new("convert")
+                                    .long("convert")
+                                    .action(ArgAction::SetTrue)
+                                    .help("Convert a non-federated graph"),
+                            ),
+                    ),
+            )
+            .subcommand(Command::new("info").hide(true))
+    }
+
+    #[test]
+    fn it_renders_a_page_for_every_visible_command() {
+        let names: Vec<String> = render_all(get_command(), "1.0.0")
+            .into_iter()
+            .map(|page| page.get_file_name())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "rover.1".to_string(),
+                "rover-subgraph.1".to_string(),
+                "rover-subgraph-publish.1".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn it_renders_arguments_options_and_examples() {
+        let pages = render_all(get_command(), "1.0.0");
+
+        let root = &pages[0].contents;
+        assert!(root.starts_with(".TH ROVER 1 \"\" \"rover 1.0.0\" \"Rover Manual\"\n"));
+        assert!(root.contains(".SH NAME\nrover \\- Rover \\- Your Graph Companion\n"));
+        assert!(root.contains(".TP\n\\fBrover\\-subgraph(1)\\fR\nSubgraph schema commands\n"));
+        assert!(root.contains(
+            ".SH EXAMPLES\n.nf\nGet started by running:\n\n    $ rover config auth\n.fi\n"
+        ));
+
+        let publish = &pages[2].contents;
+        assert!(publish.contains(
+            ".SH SYNOPSIS\n\\fBrover subgraph publish\\fR [\\fIOPTIONS\\fR] \\fI<GRAPH_REF>\\fR\n"
+        ));
+        assert!(publish.contains(".TP\n\\fB\\-\\-convert\\fR\nConvert a non\\-federated graph\n"));
+        // global arguments are documented on every page
+        assert!(publish.contains(
+            ".TP\n\\fB\\-l\\fR, \\fB\\-\\-log\\fR \\fI<LOG_LEVEL>\\fR\nSpecify Rover's log level\n"
+        ));
+        assert!(publish.contains(".SH SEE ALSO\n\\fBrover\\-subgraph(1)\\fR\n"));
+    }
+}
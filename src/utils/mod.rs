@@ -1,8 +1,33 @@
+pub mod browser;
 pub mod client;
+pub mod composition_cache;
+pub mod duration_budget;
 pub mod env;
+pub mod env_substitution;
+pub mod hooks;
+pub mod introspection_cache;
+pub mod introspection_json;
+pub mod json_input;
+pub mod line_diff;
+pub mod man;
+pub mod operation_anonymize;
 pub mod parsers;
 pub mod pkg;
+pub mod profiles;
+pub mod progress;
+pub mod rover_config;
+pub mod schema_contract;
+pub mod schema_diff;
+pub mod schema_export;
+pub mod schema_lint;
+pub mod schema_mock;
+pub mod schema_model;
+pub mod schema_split;
+pub mod schema_stats;
+pub mod schema_transform;
+pub mod schema_validate;
 pub mod stringify;
+pub mod supergraph_diff;
 pub mod table;
 pub mod telemetry;
 pub mod version;
@@ -0,0 +1,383 @@
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::anyhow;
+use apollo_parser::{ast, Parser, SyntaxKind, SyntaxNode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::RoverResult;
+
+/// Scalars every schema shares, which don't need hiding in variable types
+const BUILT_IN_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+
+/// Renames the operations, fragments, types, fields, arguments, variables, and enum
+/// values in GraphQL operations, and strips their literal values and comments, so that
+/// they can be shared without revealing what the graph is about.
+///
+/// Every original name is always replaced with the same new name, whether that
+/// replacement was provided up front or generated along the way.
+#[derive(Debug, Default)]
+pub struct OperationAnonymizer {
+    mapping: NameMapping,
+    replacements: HashSet<String>,
+}
+
+/// The replacement for every original name, kept apart for each kind of name, since a type,
+/// a field, and an argument can share a name without being related
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NameMapping {
+    pub operations: BTreeMap<String, String>,
+    pub fragments: BTreeMap<String, String>,
+    pub types: BTreeMap<String, String>,
+    /// the fields of object and input types, and aliases
+    pub fields: BTreeMap<String, String>,
+    pub arguments: BTreeMap<String, String>,
+    pub variables: BTreeMap<String, String>,
+    pub enum_values: BTreeMap<String, String>,
+}
+
+impl NameMapping {
+    fn get_mut(&mut self, kind: NameKind) -> &mut BTreeMap<String, String> {
+        match kind {
+            NameKind::Operation => &mut self.operations,
+            NameKind::Fragment => &mut self.fragments,
+            NameKind::Type => &mut self.types,
+            NameKind::Field => &mut self.fields,
+            NameKind::Argument => &mut self.arguments,
+            NameKind::Variable => &mut self.variables,
+            NameKind::EnumValue => &mut self.enum_values,
+        }
+    }
+
+    fn replacements(&self) -> impl Iterator<Item = &String> {
+        self.operations
+            .values()
+            .chain(self.fragments.values())
+            .chain(self.types.values())
+            .chain(self.fields.values())
+            .chain(self.arguments.values())
+            .chain(self.variables.values())
+            .chain(self.enum_values.values())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum NameKind {
+    Operation,
+    Fragment,
+    Type,
+    Field,
+    Argument,
+    Variable,
+    EnumValue,
+}
+
+impl NameKind {
+    fn prefix(&self) -> &'static str {
+        match self {
+            NameKind::Operation => "Operation",
+            NameKind::Fragment => "Fragment",
+            NameKind::Type => "Type",
+            NameKind::Field => "field",
+            NameKind::Argument => "arg",
+            NameKind::Variable => "var",
+            NameKind::EnumValue => "VALUE",
+        }
+    }
+}
+
+impl OperationAnonymizer {
+    /// Creates an anonymizer that replaces the names in `mapping` with their values,
+    /// and generates replacements for any other names.
+    pub fn new(mapping: NameMapping) -> OperationAnonymizer {
+        let replacements = mapping.replacements().cloned().collect();
+        OperationAnonymizer {
+            mapping,
+            replacements,
+        }
+    }
+
+    /// Every replacement made so far, including the ones passed to [OperationAnonymizer::new]
+    pub fn get_mapping(&self) -> &NameMapping {
+        &self.mapping
+    }
+
+    /// Anonymizes every operation and fragment in a GraphQL document.
+    pub fn anonymize_document(&mut self, document: &str) -> RoverResult<String> {
+        use ast::AstNode;
+
+        let parsed_ast = Parser::new(document).parse();
+        if let Some(error) = parsed_ast.errors().next() {
+            let message = error.message();
+            return Err(anyhow!("Could not parse the operations: {}", message).into());
+        }
+
+        let mut replacements = Vec::new();
+        for element in parsed_ast.document().syntax().descendants_with_tokens() {
+            if let Some(token) = element.as_token() {
+                if token.kind() == SyntaxKind::COMMENT {
+                    let range = token.text_range();
+                    replacements.push((range.start().into(), range.end().into(), String::new()));
+                }
+                continue;
+            }
+            let node = match element.into_node() {
+                Some(node) => node,
+                None => continue,
+            };
+
+            let literal = if ast::StringValue::can_cast(node.kind()) {
+                Some("\"\"")
+            } else if ast::IntValue::can_cast(node.kind()) || ast::FloatValue::can_cast(node.kind())
+            {
+                Some("0")
+            } else if ast::BooleanValue::can_cast(node.kind()) {
+                Some("false")
+            } else {
+                None
+            };
+            if let Some(literal) = literal {
+                if let Some((start, end)) = significant_range(&node) {
+                    replacements.push((start, end, literal.to_string()));
+                }
+                continue;
+            }
+
+            if let Some(name) = ast::Name::cast(node) {
+                let kind = match name.syntax().parent().and_then(|parent| get_kind(&parent)) {
+                    Some(kind) => kind,
+                    None => continue,
+                };
+                let text = name.text().to_string();
+                if text.starts_with("__")
+                    || (matches!(kind, NameKind::Type) && BUILT_IN_SCALARS.contains(&text.as_str()))
+                {
+                    continue;
+                }
+                if let Some((start, end)) = significant_range(name.syntax()) {
+                    replacements.push((start, end, self.rename(&text, kind)));
+                }
+            }
+        }
+
+        let mut anonymized = document.to_string();
+        // replace from the end so earlier ranges stay valid
+        replacements.sort_unstable_by_key(|(start, _, _)| *start);
+        for (start, end, replacement) in replacements.into_iter().rev() {
+            anonymized.replace_range(start..end, &replacement);
+        }
+        Ok(anonymized)
+    }
+
+    /// Anonymizes the `name` and `body` of every operation in a persisted query manifest.
+    /// Everything else in the manifest is left as is.
+    pub fn anonymize_manifest(&mut self, mut manifest: Value) -> RoverResult<Value> {
+        let operations = manifest
+            .get_mut("operations")
+            .and_then(Value::as_array_mut)
+            .ok_or_else(|| anyhow!("The manifest does not have an \"operations\" array"))?;
+        for operation in operations {
+            if let Some(body) = operation.get("body").and_then(Value::as_str) {
+                let body = self.anonymize_document(body)?;
+                operation["body"] = Value::String(body);
+            }
+            if let Some(name) = operation.get("name").and_then(Value::as_str) {
+                let name = self.rename(name, NameKind::Operation);
+                operation["name"] = Value::String(name);
+            }
+        }
+        Ok(manifest)
+    }
+
+    fn rename(&mut self, name: &str, kind: NameKind) -> String {
+        if let Some(replacement) = self.mapping.get_mut(kind).get(name) {
+            return replacement.clone();
+        }
+        let mut count = 1;
+        let replacement = loop {
+            let candidate = format!("{}{}", kind.prefix(), count);
+            if !self.replacements.contains(&candidate) {
+                break candidate;
+            }
+            count += 1;
+        };
+        self.replacements.insert(replacement.clone());
+        self.mapping
+            .get_mut(kind)
+            .insert(name.to_string(), replacement.clone());
+        replacement
+    }
+}
+
+/// What a name refers to, based on the node it belongs to. Directive names and their
+/// arguments are left alone.
+fn get_kind(parent: &SyntaxNode) -> Option<NameKind> {
+    use ast::AstNode;
+
+    let kind = parent.kind();
+    if ast::OperationDefinition::can_cast(kind) {
+        Some(NameKind::Operation)
+    } else if ast::FragmentName::can_cast(kind) {
+        Some(NameKind::Fragment)
+    } else if ast::NamedType::can_cast(kind) {
+        Some(NameKind::Type)
+    } else if ast::Field::can_cast(kind) || ast::Alias::can_cast(kind) {
+        Some(NameKind::Field)
+    } else if ast::Argument::can_cast(kind) {
+        // directive arguments belong to the directive, so they're kept too
+        let on_directive = parent
+            .parent()
+            .and_then(|arguments| arguments.parent())
+            .map_or(false, |owner| ast::Directive::can_cast(owner.kind()));
+        (!on_directive).then_some(NameKind::Argument)
+    } else if ast::ObjectField::can_cast(kind) {
+        // the fields of input objects
+        Some(NameKind::Field)
+    } else if ast::Variable::can_cast(kind) {
+        Some(NameKind::Variable)
+    } else if ast::EnumValue::can_cast(kind) {
+        Some(NameKind::EnumValue)
+    } else {
+        None
+    }
+}
+
+/// The byte range of a node without any whitespace, commas, or comments around it
+fn significant_range(node: &SyntaxNode) -> Option<(usize, usize)> {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| {
+            !matches!(
+                token.kind(),
+                SyntaxKind::WHITESPACE | SyntaxKind::COMMENT | SyntaxKind::COMMA
+            )
+        });
+    let first = tokens.next()?;
+    let last = tokens.last().unwrap_or_else(|| first.clone());
+    Some((
+        first.text_range().start().into(),
+        last.text_range().end().into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    const OPERATIONS: &str = r#"# fetches the cart for checkout
+query CheckoutCart($cartId: ID!, $coupon: String = "SPRING50") {
+  cart(id: $cartId) {
+    total: totalPrice(coupon: $coupon, minimum: 10.5)
+    items(first: 20, filter: { category: "shoes", sort: PRICE_ASC }) {
+      ...CartItem
+      __typename
+    }
+  }
+}
+
+fragment CartItem on LineItem {
+  sku
+  quantity @include(if: true)
+}
+"#;
+
+    #[test]
+    fn it_anonymizes_names_and_literals_consistently() {
+        let mut anonymizer = OperationAnonymizer::default();
+        assert_eq!(
+            anonymizer.anonymize_document(OPERATIONS).unwrap(),
+            r#"
+query Operation1($var1: ID!, $var2: String = "") {
+  field1(arg1: $var1) {
+    field2: field3(arg2: $var2, arg3: 0)
+    field4(arg4: 0, arg5: { field5: "", field6: VALUE1 }) {
+      ...Fragment1
+      __typename
+    }
+  }
+}
+
+fragment Fragment1 on Type1 {
+  field7
+  field8 @include(if: false)
+}
+"#
+        );
+        assert_eq!(
+            anonymizer.get_mapping().fragments.get("CartItem"),
+            Some(&"Fragment1".to_string())
+        );
+        assert_eq!(
+            anonymizer.get_mapping().enum_values.get("PRICE_ASC"),
+            Some(&"VALUE1".to_string())
+        );
+    }
+
+    #[test]
+    fn it_keeps_each_kind_of_name_apart() {
+        let mut anonymizer = OperationAnonymizer::default();
+        assert_eq!(
+            anonymizer
+                .anonymize_document(
+                    "query product($product: ID) { product(product: $product) { ... on product { product } } }"
+                )
+                .unwrap(),
+            "query Operation1($var1: ID) { field1(arg1: $var1) { ... on Type1 { field1 } } }"
+        );
+    }
+
+    #[test]
+    fn it_uses_the_provided_mapping() {
+        let mapping = NameMapping {
+            fields: BTreeMap::from([("cart".to_string(), "basket".to_string())]),
+            types: BTreeMap::from([("LineItem".to_string(), "Type1".to_string())]),
+            ..Default::default()
+        };
+        let mut anonymizer = OperationAnonymizer::new(mapping);
+        assert_eq!(
+            anonymizer
+                .anonymize_document("{ cart { ... on LineItem { sku } ... on Discount { code } } }")
+                .unwrap(),
+            "{ basket { ... on Type1 { field1 } ... on Type2 { field2 } } }"
+        );
+    }
+
+    #[test]
+    fn it_anonymizes_manifests() {
+        let mut anonymizer = OperationAnonymizer::default();
+        let manifest = json!({
+            "format": "apollo-persisted-query-manifest",
+            "version": 1,
+            "operations": [{
+                "id": "abc123",
+                "name": "GetCart",
+                "type": "query",
+                "body": "query GetCart { cart(id: \"42\") { total } }"
+            }]
+        });
+        assert_eq!(
+            anonymizer.anonymize_manifest(manifest).unwrap(),
+            json!({
+                "format": "apollo-persisted-query-manifest",
+                "version": 1,
+                "operations": [{
+                    "id": "abc123",
+                    "name": "Operation1",
+                    "type": "query",
+                    "body": "query Operation1 { field1(arg1: \"\") { field2 } }"
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn it_errs_on_invalid_operations() {
+        assert!(OperationAnonymizer::default()
+            .anonymize_document("query {")
+            .is_err());
+    }
+}
@@ -1,8 +1,12 @@
 use anyhow::{anyhow, Context};
+use apollo_federation_types::config::{FederationVersion, RouterVersion};
 use camino::{Utf8Path, Utf8PathBuf};
 use rover_std::Fs;
 
-use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+use crate::{
+    options::SubgraphHeader, utils::env_substitution::expand_env_vars_in_string, RoverError,
+    RoverErrorSuggestion, RoverResult,
+};
 
 use std::{
     fmt,
@@ -98,6 +102,10 @@ impl FromStr for FileDescriptorType {
 
 /// Parses a key:value pair from a string and returns a tuple of key:value.
 /// If a full key:value can't be parsed, it will error.
+///
+/// `${VAR}` in a value is replaced with the value of the `VAR` environment variable,
+/// erroring if it isn't set, the same way as in a supergraph config: `${VAR:-default}`
+/// falls back to `default`, and `$${VAR}` is kept as a literal `${VAR}`.
 pub fn parse_header(header: &str) -> std::result::Result<(String, String), io::Error> {
     // only split once, a header's value may have a ":" in it, but not a key. Right?
     let pair: Vec<&str> = header.splitn(2, ':').collect();
@@ -105,13 +113,167 @@ pub fn parse_header(header: &str) -> std::result::Result<(String, String), io::E
         let msg = format!("Could not parse \"key:value\" pair for provided header: \"{}\". Headers must be provided in key:value pairs, with quotes around the pair if there are any spaces in the key or value.", header);
         Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
     } else {
-        Ok((pair[0].to_string(), pair[1].to_string()))
+        Ok((pair[0].to_string(), expand_env_vars(pair[1])?))
+    }
+}
+
+/// Parses a `--subgraph-header`, which is a `key:value` pair like a `--header`. A key with a subgraph's
+/// name and a `/` before it, i.e. `products/Authorization:Bearer ${TOKEN}`, is only sent to that subgraph
+pub fn parse_subgraph_header(header: &str) -> std::result::Result<SubgraphHeader, io::Error> {
+    let (key, value) = parse_header(header)?;
+    let (subgraph_name, key) = match key.split_once('/') {
+        Some((subgraph_name, key)) => (Some(subgraph_name.trim().to_string()), key.trim()),
+        None => (None, key.trim()),
+    };
+    if key.is_empty() || subgraph_name.as_deref() == Some("") {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Could not parse the subgraph header \"{}\". Pass a \"key:value\" pair to send it to every subgraph, or a \"subgraph/key:value\" pair to send it to one subgraph.",
+                header
+            ),
+        ));
+    }
+    Ok(SubgraphHeader {
+        subgraph_name,
+        key: key.to_string(),
+        value,
+    })
+}
+
+/// Parses the full SHA256 hash of a published schema, normalized to lowercase
+pub fn parse_schema_hash(hash: &str) -> std::result::Result<String, io::Error> {
+    let hash = hash.trim().to_lowercase();
+    if hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(hash)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            anyhow!("Schema hashes must be a full 64 character SHA256 hash, like the one printed by `rover graph publish`. Abbreviated hashes can't be used to fetch a schema."),
+        ))
+    }
+}
+
+/// Parses the `--to` of `graph rollback`, which is the full hash of the published schema to republish.
+/// Studio's API doesn't list a variant's earlier publishes, so there's no way to resolve `previous`
+pub fn parse_rollback_target(to: &str) -> std::result::Result<String, io::Error> {
+    if to.trim().eq_ignore_ascii_case("previous") {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            anyhow!("Rover can't look up a variant's previous publish, so `--to previous` isn't supported. Pass the full hash of the schema to republish, which you can find on the variant's History page with `rover graph open <GRAPH_REF> --page history`."),
+        ))
+    } else {
+        parse_schema_hash(to)
+    }
+}
+
+/// Parses a `--federation-version`, where a bare major version, i.e. `2`,
+/// is the latest version of it
+pub fn parse_federation_version(
+    federation_version: &str,
+) -> std::result::Result<FederationVersion, io::Error> {
+    let federation_version = match federation_version.trim() {
+        "1" => "latest-0",
+        "2" => "latest-2",
+        federation_version => federation_version,
+    };
+    FederationVersion::from_str(federation_version).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "'{}' is not a federation version. Pass '1', '2', or an exact version preceded with an '=', i.e. '=2.3.1'.",
+                federation_version
+            ),
+        )
+    })
+}
+
+/// Parses a `--router-version`, where `1` or `latest` is the latest version of the router
+/// and an exact version can be passed with or without a leading `v`, i.e. `1.10.0`
+pub fn parse_router_version(router_version: &str) -> std::result::Result<RouterVersion, io::Error> {
+    let router_version = match router_version.trim() {
+        "1" | "latest" => "latest".to_string(),
+        exact => format!("v{}", exact.trim_start_matches(|c| c == 'v' || c == '=')),
+    };
+    RouterVersion::from_str(&router_version).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "'{}' is not a router version. Pass 'latest', or an exact version of the router, i.e. '1.10.0'.",
+                router_version.trim_start_matches('v')
+            ),
+        )
+    })
+}
+
+/// Parses the `--session-name` of a `rover dev` session, which names the socket its
+/// processes talk to each other on, so it can only have letters, numbers, `-`, and `_`
+pub fn parse_session_name(session_name: &str) -> std::result::Result<String, io::Error> {
+    if !session_name.is_empty()
+        && session_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(session_name.to_string())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "'{}' is not a valid session name. Session names can only have letters, numbers, '-', and '_'.",
+                session_name
+            ),
+        ))
+    }
+}
+
+/// Parses a `--var` of `rover template use`, which is a `key=value` pair for one of the
+/// variables a template declares. Its key can only have letters, numbers, and `_`
+pub fn parse_template_var(var: &str) -> std::result::Result<(String, String), io::Error> {
+    match var.split_once('=') {
+        Some((key, value))
+            if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+        {
+            Ok((key.to_string(), value.to_string()))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Could not parse the template variable \"{}\". Variables must be provided in key=value pairs, and their keys can only have letters, numbers, and '_'.",
+                var
+            ),
+        )),
+    }
+}
+
+/// Replaces every `${VAR}` in `value` with the value of the `VAR` environment variable.
+/// Expanding variables in Rover rather than in the shell means the unexpanded value
+/// can be committed to CI configuration without the secret it refers to.
+fn expand_env_vars(value: &str) -> std::result::Result<String, io::Error> {
+    let mut missing = Vec::new();
+    let expanded =
+        expand_env_vars_in_string(value, &|name| std::env::var(name).ok(), &mut |name| {
+            missing.push(name.to_string())
+        });
+    if missing.is_empty() {
+        Ok(expanded)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "These environment variables are used in a header, but they aren't set: {}",
+                missing.join(", ")
+            ),
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::FileDescriptorType;
+    use super::{
+        parse_header, parse_rollback_target, parse_router_version, parse_session_name,
+        parse_subgraph_header, parse_template_var, FileDescriptorType,
+    };
+    use apollo_federation_types::config::RouterVersion;
     use assert_fs::prelude::*;
     use camino::Utf8PathBuf;
     use std::convert::TryFrom;
@@ -192,4 +354,104 @@ mod tests {
         let schema_result = fd.read_file_descriptor("SDL", &mut input.as_bytes());
         assert!(schema_result.is_err())
     }
+
+    #[test]
+    fn it_expands_env_vars_in_header_values() {
+        std::env::set_var("ROVER_TEST_HEADER_TOKEN", "token329r");
+        assert_eq!(
+            parse_header("Authorization:Bearer ${ROVER_TEST_HEADER_TOKEN}").unwrap(),
+            ("Authorization".to_string(), "Bearer token329r".to_string())
+        );
+        assert_eq!(
+            parse_header("x-cost:$5 $${ROVER_TEST_HEADER_TOKEN}").unwrap(),
+            (
+                "x-cost".to_string(),
+                "$5 ${ROVER_TEST_HEADER_TOKEN}".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn it_errs_on_unset_env_vars_in_header_values() {
+        std::env::remove_var("ROVER_TEST_HEADER_UNSET");
+        let error = parse_header("Authorization:Bearer ${ROVER_TEST_HEADER_UNSET}").unwrap_err();
+        assert!(error.to_string().contains("ROVER_TEST_HEADER_UNSET"));
+        assert_eq!(
+            parse_header("Authorization:Bearer ${ROVER_TEST_HEADER_UNSET:-anonymous}").unwrap(),
+            ("Authorization".to_string(), "Bearer anonymous".to_string())
+        );
+    }
+
+    #[test]
+    fn it_only_parses_session_names_that_fit_in_a_socket_name() {
+        assert_eq!(
+            parse_session_name("checkout-v2_local").unwrap(),
+            "checkout-v2_local"
+        );
+        assert!(parse_session_name("").is_err());
+        assert!(parse_session_name("../checkout").is_err());
+        assert!(parse_session_name("my session").is_err());
+    }
+
+    #[test]
+    fn it_parses_router_versions_with_or_without_a_v() {
+        assert!(matches!(
+            parse_router_version("latest").unwrap(),
+            RouterVersion::Latest
+        ));
+        assert!(matches!(
+            parse_router_version("1").unwrap(),
+            RouterVersion::Latest
+        ));
+        for router_version in ["1.10.0", "v1.10.0", "=1.10.0"] {
+            assert!(matches!(
+                parse_router_version(router_version).unwrap(),
+                RouterVersion::Exact(version) if version.to_string() == "1.10.0"
+            ));
+        }
+        assert!(parse_router_version("newest").is_err());
+    }
+
+    #[test]
+    fn it_rejects_previous_as_a_rollback_target() {
+        let hash = "A".repeat(64);
+        assert_eq!(parse_rollback_target(&hash).unwrap(), "a".repeat(64));
+        assert!(parse_rollback_target("previous").is_err());
+        assert!(parse_rollback_target("abc123").is_err());
+    }
+
+    #[test]
+    fn it_parses_subgraph_headers_for_every_subgraph_or_one() {
+        let header = parse_subgraph_header("Authorization:Bearer a/b").unwrap();
+        assert_eq!(header.subgraph_name, None);
+        assert_eq!(header.key, "Authorization");
+        assert_eq!(header.value, "Bearer a/b");
+
+        let header = parse_subgraph_header("products/x-api-key:secret").unwrap();
+        assert_eq!(header.subgraph_name.as_deref(), Some("products"));
+        assert_eq!(header.key, "x-api-key");
+        assert_eq!(header.value, "secret");
+
+        assert!(parse_subgraph_header("/x-api-key:secret").is_err());
+        assert!(parse_subgraph_header("products/:secret").is_err());
+        assert!(parse_subgraph_header("x-api-key").is_err());
+    }
+
+    #[test]
+    fn it_parses_template_vars() {
+        assert_eq!(
+            parse_template_var("connection_string=postgres://localhost?user=me").unwrap(),
+            (
+                "connection_string".to_string(),
+                "postgres://localhost?user=me".to_string()
+            )
+        );
+        assert_eq!(
+            parse_template_var("port=").unwrap(),
+            ("port".to_string(), "".to_string())
+        );
+        assert!(parse_template_var("port").is_err());
+        assert!(parse_template_var("=4001").is_err());
+        assert!(parse_template_var("package-name=products").is_err());
+    }
 }
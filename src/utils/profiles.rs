@@ -0,0 +1,87 @@
+use anyhow::anyhow;
+use houston::{HoustonProblem, Profile};
+use serde_json::{json, Value};
+
+use crate::options::{ProfileOpt, ProfilesOpt};
+use crate::utils::client::StudioClientConfig;
+use crate::utils::env::RoverEnvKey;
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+
+/// What a command printed when it was run with a single profile's credentials
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProfileOutput {
+    pub profile_name: String,
+    pub result: Result<Box<RoverOutput>, ProfileError>,
+}
+
+/// An error a command ran into with one profile, which doesn't stop it
+/// from running with the rest of the profiles
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProfileError {
+    pub message: String,
+    /// the error as it would be printed with `--format json`
+    pub json: Value,
+}
+
+impl From<RoverError> for ProfileError {
+    fn from(error: RoverError) -> Self {
+        ProfileError {
+            message: error.message(),
+            json: json!(error),
+        }
+    }
+}
+
+/// Runs `run_with_profile` with the profile passed to `--profile`, or, when
+/// `--all-profiles` is passed, once for every configuration profile.
+///
+/// An error with one profile is reported alongside the outputs of the others,
+/// rather than stopping the rest of the profiles from running.
+pub fn for_each_profile<F>(
+    profiles_opt: &ProfilesOpt,
+    client_config: &StudioClientConfig,
+    run_with_profile: F,
+) -> RoverResult<RoverOutput>
+where
+    F: Fn(&ProfileOpt) -> RoverResult<RoverOutput>,
+{
+    if !profiles_opt.all_profiles {
+        return run_with_profile(&profiles_opt.profile);
+    }
+
+    // the key from the environment is used no matter which profile is selected,
+    // so every profile would give the same result
+    if client_config.config.override_api_key.is_some() {
+        let mut err = RoverError::new(anyhow!(
+            "`--all-profiles` can't be used while ${} is set.",
+            RoverEnvKey::Key
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+            "Unset ${} to use the API key of each profile.",
+            RoverEnvKey::Key
+        )));
+        return Err(err);
+    }
+
+    let mut profile_names = Profile::list(&client_config.config)?;
+    if profile_names.is_empty() {
+        return Err(RoverError::new(HoustonProblem::NoConfigProfiles));
+    }
+    profile_names.sort();
+
+    let outputs = profile_names
+        .into_iter()
+        .map(|profile_name| {
+            let profile = ProfileOpt {
+                profile_name: profile_name.clone(),
+            };
+            ProfileOutput {
+                result: run_with_profile(&profile)
+                    .map(Box::new)
+                    .map_err(ProfileError::from),
+                profile_name,
+            }
+        })
+        .collect();
+    Ok(RoverOutput::ProfileOutputs(outputs))
+}
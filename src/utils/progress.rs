@@ -0,0 +1,283 @@
+use std::fmt;
+use std::sync::{mpsc, Arc, Mutex};
+
+use rover_std::{Emoji, Style};
+use serde::Serialize;
+
+/// Something Rover is doing on the way to a command's output, i.e. downloading a plugin
+/// or resolving one subgraph of a supergraph config
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    PluginDownloadStarted {
+        plugin: String,
+        url: String,
+    },
+    PluginInstalled {
+        plugin: String,
+        path: String,
+    },
+    /// `total_bytes` is `None` if the server didn't say how large the download is
+    DownloadProgress {
+        url: String,
+        downloaded_bytes: u64,
+        total_bytes: Option<u64>,
+    },
+    SupergraphConfigResolutionStarted {
+        config: String,
+    },
+    SubgraphResolutionStarted {
+        subgraph: String,
+        source: String,
+    },
+    SubgraphResolved {
+        subgraph: String,
+    },
+    SubgraphResolutionFailed {
+        subgraph: String,
+        reason: String,
+    },
+    /// Whether a subgraph's endpoints are ready to be composed, checked by `--preflight`.
+    /// `status` is `ready`, `unreachable`, `not_federated`, or `skipped` if there's nothing to probe
+    SubgraphPreflightChecked {
+        subgraph: String,
+        status: String,
+        endpoints: Vec<String>,
+        reason: Option<String>,
+    },
+    /// A subgraph that was left out of composition because of `--allow-missing-subgraphs`
+    SubgraphSkipped {
+        subgraph: String,
+        reason: String,
+    },
+    /// The major federation version chosen for a supergraph config without a `federation_version`,
+    /// and the subgraphs whose `@link` directives require Federation 2
+    FederationVersionDetected {
+        federation_version: String,
+        subgraphs: Vec<String>,
+    },
+    /// Whether the last composition of the supergraph config could be reused,
+    /// and if not, which subgraphs changed since
+    CompositionCacheChecked {
+        hit: bool,
+        changed_subgraphs: Vec<String>,
+    },
+    CompositionStarted {
+        federation_version: String,
+    },
+    CompositionFinished {
+        federation_version: String,
+        succeeded: bool,
+    },
+    /// `profile` is `None` if the schema is fetched without credentials
+    SchemaFetchStarted {
+        graph_ref: String,
+        subgraph: Option<String>,
+        hash: Option<String>,
+        supergraph: bool,
+        profile: Option<String>,
+    },
+    SchemaFetched {
+        graph_ref: String,
+        subgraph: Option<String>,
+    },
+    TemplateDownloadStarted {
+        template_id: String,
+        repository: String,
+        url: String,
+    },
+    TemplateExtracted {
+        template_id: String,
+        path: String,
+    },
+}
+
+impl ProgressEvent {
+    /// What the CLI prints to stderr for this event, if anything
+    pub fn get_message(&self) -> Option<String> {
+        match self {
+            ProgressEvent::PluginDownloadStarted { plugin, url } => {
+                Some(format!("downloading the '{}' plugin from {}", plugin, url))
+            }
+            ProgressEvent::SupergraphConfigResolutionStarted { config } => Some(format!(
+                "{}resolving SDL for subgraphs defined in {}",
+                Emoji::Hourglass,
+                Style::Path.paint(config)
+            )),
+            ProgressEvent::SubgraphPreflightChecked {
+                subgraph,
+                status,
+                endpoints,
+                reason,
+            } => Some(match reason {
+                Some(reason) if status == "skipped" => {
+                    format!(
+                        "not checking the '{}' subgraph, because {}",
+                        subgraph, reason
+                    )
+                }
+                Some(reason) => format!(
+                    "{}the '{}' subgraph isn't ready: {}",
+                    Emoji::Warn,
+                    subgraph,
+                    reason
+                ),
+                None => format!(
+                    "{}the '{}' subgraph is ready at {}",
+                    Emoji::Success,
+                    subgraph,
+                    endpoints.join(", ")
+                ),
+            }),
+            ProgressEvent::SubgraphSkipped { subgraph, reason } => Some(format!(
+                "{}leaving the '{}' subgraph out of composition: {}",
+                Emoji::Warn,
+                subgraph,
+                reason
+            )),
+            ProgressEvent::FederationVersionDetected {
+                federation_version,
+                subgraphs,
+            } => Some(if subgraphs.is_empty() {
+                format!(
+                    "no federation_version is set and no subgraph uses @link, so composing with Federation {}. Set federation_version or pass {} to choose another version",
+                    federation_version,
+                    Style::Command.paint("--federation-version")
+                )
+            } else {
+                format!(
+                    "no federation_version is set, so composing with Federation {} because these subgraphs use @link: {}",
+                    federation_version,
+                    subgraphs.join(", ")
+                )
+            }),
+            ProgressEvent::CompositionCacheChecked { hit: true, .. } => Some(format!(
+                "{}no subgraph changed since the last composition, so reusing it",
+                Emoji::Success
+            )),
+            ProgressEvent::CompositionStarted { federation_version } => Some(format!(
+                "{}composing supergraph with Federation {}",
+                Emoji::Compose,
+                federation_version
+            )),
+            ProgressEvent::SchemaFetchStarted {
+                graph_ref,
+                subgraph,
+                hash,
+                supergraph,
+                profile,
+            } => {
+                let mut message = if *supergraph {
+                    "Fetching supergraph SDL".to_string()
+                } else {
+                    "Fetching SDL".to_string()
+                };
+                if let Some(hash) = hash {
+                    message.push_str(&format!(" with hash {}", Style::Command.paint(hash)));
+                }
+                message.push_str(&format!(" from {}", Style::Link.paint(graph_ref)));
+                if let Some(subgraph) = subgraph {
+                    message.push_str(&format!(" (subgraph: {})", Style::Link.paint(subgraph)));
+                }
+                match profile {
+                    Some(profile) => message.push_str(&format!(
+                        " using credentials from the {} profile.",
+                        Style::Command.paint(profile)
+                    )),
+                    None => message.push_str(" without credentials."),
+                }
+                Some(message)
+            }
+            ProgressEvent::TemplateDownloadStarted {
+                repository, url, ..
+            } => Some(format!("Downloading {}\n\tfrom {}", repository, url)),
+            _ => None,
+        }
+    }
+}
+
+/// Where Rover sends its [`ProgressEvent`]s.
+///
+/// By default the CLI prints each event's message to stderr. Programs that use Rover as a
+/// library can subscribe to the events instead, in which case nothing is printed for them.
+#[derive(Clone, Default)]
+pub struct ProgressReporter {
+    subscriber: Option<Arc<dyn Fn(&ProgressEvent) + Send + Sync>>,
+}
+
+impl ProgressReporter {
+    /// Calls `subscriber` with every event
+    pub fn new(subscriber: impl Fn(&ProgressEvent) + Send + Sync + 'static) -> Self {
+        Self {
+            subscriber: Some(Arc::new(subscriber)),
+        }
+    }
+
+    /// Sends every event to the returned receiver. Events are dropped once it's gone
+    pub fn channel() -> (Self, mpsc::Receiver<ProgressEvent>) {
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        let reporter = Self::new(move |event| {
+            if let Ok(sender) = sender.lock() {
+                let _ = sender.send(event.clone());
+            }
+        });
+        (reporter, receiver)
+    }
+
+    pub fn emit(&self, event: ProgressEvent) {
+        tracing::debug!("progress: {:?}", &event);
+        match &self.subscriber {
+            Some(subscriber) => subscriber(&event),
+            None => {
+                if let Some(message) = event.get_message() {
+                    eprintln!("{}", message);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ProgressReporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressReporter")
+            .field("subscribed", &self.subscriber.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn it_sends_events_to_subscribers() {
+        let (reporter, receiver) = ProgressReporter::channel();
+        let event = ProgressEvent::SubgraphResolved {
+            subgraph: "products".to_string(),
+        };
+        reporter.clone().emit(event.clone());
+        assert_eq!(receiver.try_recv().unwrap(), event);
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn it_tags_serialized_events() {
+        let event = ProgressEvent::DownloadProgress {
+            url: "https://example.com/plugin.tar.gz".to_string(),
+            downloaded_bytes: 1024,
+            total_bytes: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "event": "download_progress",
+                "url": "https://example.com/plugin.tar.gz",
+                "downloaded_bytes": 1024,
+                "total_bytes": null
+            })
+        );
+    }
+}
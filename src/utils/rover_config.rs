@@ -0,0 +1,310 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, Context};
+use camino::{Utf8Path, Utf8PathBuf};
+use rover_std::{Fs, Style};
+use serde::Deserialize;
+
+use rover_client::shared::{
+    ChangeSeverity, CheckConfig, CheckResponse, ClientFilter, GraphRef, SchemaChange,
+    ValidationPeriod,
+};
+use rover_client::RoverClientError;
+
+use crate::options::CheckConfigOpts;
+use crate::utils::schema_lint::{LintConfig, LintLevel, LintRule};
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// The name of the config file that check and lint commands look for
+/// in the current directory and its parents
+pub const ROVER_CONFIG_FILE: &str = ".rover.yaml";
+
+/// The layout of a `.rover.yaml` file, which is meant to be committed
+/// so everyone who runs checks and lints a schema uses the same policy
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoverConfig {
+    #[serde(default)]
+    pub checks: CheckPolicy,
+
+    #[serde(default)]
+    pub lint: LintPolicy,
+}
+
+impl RoverConfig {
+    /// Reads the config at `path`, or the nearest `.rover.yaml` if no path is passed.
+    /// Without either, every command runs with its default policy
+    pub fn load(path: Option<&Utf8Path>) -> RoverResult<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match find_rover_config()? {
+                Some(path) => path,
+                None => return Ok(Self::default()),
+            },
+        };
+        tracing::debug!("reading the check policy from {}", &path);
+        let contents = Fs::read_file(&path)?;
+        Ok(Self::from_yaml(&contents)
+            .with_context(|| format!("{} is not a valid Rover config file.", &path))?)
+    }
+
+    fn from_yaml(contents: &str) -> serde_yaml::Result<Self> {
+        serde_yaml::from_str(contents)
+    }
+}
+
+/// The nearest `.rover.yaml` in the current directory or one of its parents
+fn find_rover_config() -> RoverResult<Option<Utf8PathBuf>> {
+    let current_dir = Utf8PathBuf::try_from(std::env::current_dir()?)?;
+    Ok(current_dir
+        .ancestors()
+        .map(|dir| dir.join(ROVER_CONFIG_FILE))
+        .find(|path| path.is_file()))
+}
+
+/// The `checks` section of a `.rover.yaml` file
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckPolicy {
+    /// codes of changes that never fail a check, i.e. `FIELD_DEPRECATED`.
+    /// They're left out of the check's output
+    #[serde(default)]
+    ignored_changes: BTreeSet<String>,
+
+    /// the severity to report changes with, by code, i.e. `ARG_ADDED: FAIL`
+    #[serde(default)]
+    severity_overrides: BTreeMap<String, ChangeSeverity>,
+
+    /// used unless `--validation-period` is passed, i.e. `2w`
+    validation_period: Option<String>,
+
+    /// used unless `--query-count-threshold` is passed
+    query_count_threshold: Option<i64>,
+
+    /// used unless `--query-percentage-threshold` is passed
+    query_percentage_threshold: Option<f64>,
+
+    /// clients whose operations shouldn't be checked, in addition to
+    /// the ones passed to `--exclude-client`, i.e. `ios-app@3.2.0`
+    #[serde(default)]
+    excluded_clients: Vec<String>,
+}
+
+impl CheckPolicy {
+    /// The config to run a check with. Flags take precedence over the policy,
+    /// except for excluded clients, which are combined
+    pub fn get_check_config(
+        &self,
+        opts: &CheckConfigOpts,
+        excluded_clients: &[ClientFilter],
+    ) -> RoverResult<CheckConfig> {
+        let validation_period = match (&opts.validation_period, &self.validation_period) {
+            (Some(validation_period), _) => Some(validation_period.clone()),
+            (None, Some(validation_period)) => Some(validation_period.parse::<ValidationPeriod>()?),
+            (None, None) => None,
+        };
+
+        let query_count_threshold = opts.query_count_threshold.or(self.query_count_threshold);
+        if matches!(query_count_threshold, Some(threshold) if threshold < 1) {
+            return Err(invalid_policy(
+                "query_count_threshold must be a positive integer.",
+            ));
+        }
+
+        let query_count_threshold_percentage = match (
+            opts.query_percentage_threshold,
+            self.query_percentage_threshold,
+        ) {
+            (Some(threshold), _) => Some(threshold),
+            (None, Some(threshold)) if (0.0..=100.0).contains(&threshold) => {
+                Some(threshold / 100.0)
+            }
+            (None, Some(_)) => {
+                return Err(invalid_policy(
+                    "query_percentage_threshold must be in the range 0 <= x <= 100.",
+                ))
+            }
+            (None, None) => None,
+        };
+
+        let mut all_excluded_clients = excluded_clients.to_vec();
+        for client in &self.excluded_clients {
+            let client = client.parse::<ClientFilter>()?;
+            if !all_excluded_clients.contains(&client) {
+                all_excluded_clients.push(client);
+            }
+        }
+
+        Ok(CheckConfig {
+            query_count_threshold,
+            query_count_threshold_percentage,
+            validation_period,
+            excluded_clients: all_excluded_clients,
+        })
+    }
+
+    /// Applies the ignored changes and severity overrides to the result of a finished check,
+    /// so a check whose failing changes are all ignored passes, and a check with
+    /// a change overridden to `FAIL` fails
+    pub fn apply(
+        &self,
+        result: Result<CheckResponse, RoverClientError>,
+        graph_ref: &GraphRef,
+    ) -> RoverResult<CheckResponse> {
+        let check_response = match result {
+            Ok(check_response) => check_response,
+            Err(RoverClientError::OperationCheckFailure { check_response, .. }) => check_response,
+            Err(e) => return Err(e.into()),
+        };
+        let changes = self.apply_to_changes(check_response.get_changes());
+        Ok(check_response.try_with_changes(changes, graph_ref.clone())?)
+    }
+
+    fn apply_to_changes(&self, changes: &[SchemaChange]) -> Vec<SchemaChange> {
+        changes
+            .iter()
+            .filter(|change| !self.ignored_changes.contains(&change.code))
+            .map(|change| SchemaChange {
+                severity: self
+                    .severity_overrides
+                    .get(&change.code)
+                    .unwrap_or(&change.severity)
+                    .clone(),
+                ..change.clone()
+            })
+            .collect()
+    }
+}
+
+/// The level a lint rule is set to in a `.rover.yaml` file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ConfiguredLintLevel {
+    Error,
+    #[serde(alias = "warn")]
+    Warning,
+    Off,
+}
+
+/// The `lint` section of a `.rover.yaml` file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintPolicy {
+    /// the level of each configured rule, i.e. `INPUT_TYPE_SUFFIX: error`
+    #[serde(default)]
+    rules: BTreeMap<LintRule, ConfiguredLintLevel>,
+}
+
+impl LintPolicy {
+    /// The lint config with the levels in this policy, before any `--rule` flags are applied
+    pub fn get_lint_config(&self) -> LintConfig {
+        self.rules
+            .iter()
+            .fold(LintConfig::default(), |config, (rule, level)| {
+                let level = match level {
+                    ConfiguredLintLevel::Error => Some(LintLevel::Error),
+                    ConfiguredLintLevel::Warning => Some(LintLevel::Warning),
+                    ConfiguredLintLevel::Off => None,
+                };
+                config.with_rule(*rule, level)
+            })
+    }
+}
+
+fn invalid_policy(message: &str) -> RoverError {
+    let mut err = RoverError::new(anyhow!("The check policy is invalid: {}", message));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+        "Fix the `checks` section of your {} file.",
+        Style::Path.paint(ROVER_CONFIG_FILE)
+    )));
+    err
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::CheckReportOpt;
+
+    const CONFIG: &str = r#"
+checks:
+  ignored_changes:
+    - FIELD_DEPRECATED
+  severity_overrides:
+    ARG_ADDED: FAIL
+  validation_period: 2w
+  query_count_threshold: 5
+  excluded_clients:
+    - internal-tools
+lint:
+  rules:
+    INPUT_TYPE_SUFFIX: error
+    DEPRECATED_DIRECTIVE_MISSING_REASON: off
+"#;
+
+    fn change(code: &str, severity: ChangeSeverity) -> SchemaChange {
+        SchemaChange {
+            code: code.to_string(),
+            description: format!("{} change", code),
+            severity,
+        }
+    }
+
+    #[test]
+    fn it_merges_the_check_policy_with_flags() {
+        let config = RoverConfig::from_yaml(CONFIG).unwrap();
+        let opts = CheckConfigOpts {
+            query_count_threshold: Some(10),
+            query_percentage_threshold: None,
+            validation_period: None,
+            background: false,
+            rover_config: None,
+            report: CheckReportOpt::default(),
+        };
+        let check_config = config
+            .checks
+            .get_check_config(&opts, &["ios-app@3.2.0".parse().unwrap()])
+            .unwrap();
+        assert_eq!(check_config.query_count_threshold, Some(10));
+        assert_eq!(check_config.validation_period, "2w".parse().ok());
+        assert_eq!(
+            check_config.excluded_clients,
+            vec![
+                "ios-app@3.2.0".parse().unwrap(),
+                "internal-tools".parse().unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn it_applies_ignored_changes_and_overrides() {
+        let config = RoverConfig::from_yaml(CONFIG).unwrap();
+        let changes = config.checks.apply_to_changes(&[
+            change("FIELD_DEPRECATED", ChangeSeverity::FAIL),
+            change("ARG_ADDED", ChangeSeverity::PASS),
+            change("TYPE_ADDED", ChangeSeverity::PASS),
+        ]);
+        assert_eq!(
+            changes,
+            vec![
+                change("ARG_ADDED", ChangeSeverity::FAIL),
+                change("TYPE_ADDED", ChangeSeverity::PASS)
+            ]
+        );
+
+        let lint_config = config.lint.get_lint_config();
+        assert_eq!(
+            lint_config.get_level(LintRule::InputTypeSuffix),
+            Some(LintLevel::Error)
+        );
+        assert_eq!(
+            lint_config.get_level(LintRule::DeprecatedDirectiveMissingReason),
+            None
+        );
+    }
+
+    #[test]
+    fn it_rejects_unknown_settings() {
+        assert!(RoverConfig::from_yaml("checks:\n  ignore: [FIELD_REMOVED]\n").is_err());
+        assert!(RoverConfig::from_yaml("lint:\n  rules:\n    NOT_A_RULE: error\n").is_err());
+    }
+}
@@ -0,0 +1,540 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::anyhow;
+use apollo_parser::{ast, Parser, SyntaxKind, SyntaxNode};
+use serde::Deserialize;
+
+use crate::utils::introspection_json::{get_name, get_name_and_kind};
+use crate::utils::schema_model::SchemaModel;
+use crate::utils::schema_transform::is_federation_machinery;
+use crate::utils::schema_transform::removal_range;
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// The `filter` section of a supergraph config, which filters the composed supergraph
+/// by `@tag` the way a contract variant in Apollo Studio filters its source variant
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContractFilter {
+    /// if any are given, only the types and members tagged with one of these are kept,
+    /// along with every member of a tagged type
+    #[serde(default)]
+    pub include_tags: Vec<String>,
+
+    /// the types and members tagged with any of these are removed
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
+}
+
+/// The parts of a type that decide what a [`ContractFilter`] removes,
+/// across its definition and extensions
+#[derive(Debug, Default)]
+struct ContractType {
+    tags: Vec<String>,
+    /// whether the type is an object, interface, input object, or enum,
+    /// which is removed along with its last member
+    has_members: bool,
+    /// fields, input fields, and enum values, by name
+    members: BTreeMap<String, ContractMember>,
+    /// the member types of a union, which is removed along with its last member type
+    union_members: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+struct ContractMember {
+    tags: Vec<String>,
+    /// the named type of a field or input field, i.e. `Product` for `[Product!]!`
+    ty: Option<String>,
+    arguments: BTreeMap<String, ContractArgument>,
+}
+
+#[derive(Debug)]
+struct ContractArgument {
+    tags: Vec<String>,
+    ty: String,
+    /// whether removing the argument removes its field, since callers couldn't leave it out
+    required: bool,
+}
+
+/// What a [`ContractFilter`] removes from a schema
+#[derive(Debug, Default)]
+struct Removals {
+    types: BTreeSet<String>,
+    /// (type, member)
+    members: BTreeSet<(String, String)>,
+    /// (type, field, argument)
+    arguments: BTreeSet<(String, String, String)>,
+}
+
+impl ContractFilter {
+    /// Removes what the filter doesn't keep from a schema, along with everything that
+    /// can't be kept without it: fields and arguments of removed types, union members
+    /// and interfaces that were removed, and types left without any member.
+    ///
+    /// Federation types, directive definitions, and everything that's kept are left
+    /// as they are, including their formatting and comments.
+    pub fn apply(&self, sdl: &str) -> RoverResult<String> {
+        use ast::AstNode;
+
+        let parsed_ast = Parser::new(sdl).parse();
+        if let Some(error) = parsed_ast.errors().next() {
+            return Err(anyhow!("Could not filter the schema: {}", error.message()).into());
+        }
+        let document = parsed_ast.document();
+        let model = SchemaModel::new("the supergraph to filter", sdl)?;
+        let types = get_contract_types(&model);
+        let query_type = model.query_type;
+        let removals = self.get_removals(&types);
+        if removals.types.contains(&query_type) {
+            let mut err = RoverError::new(anyhow!(
+                "The `filter` in the supergraph config removes every field of the {} type.",
+                query_type
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Tag at least one field of the query type with one of the `include_tags`, and none of the `exclude_tags`.".to_string(),
+            ));
+            return Err(err);
+        }
+
+        let mut edits = Vec::new();
+        for definition in document.definitions() {
+            use ast::Definition::*;
+
+            let root_operations = match &definition {
+                SchemaDefinition(def) => Some(def.root_operation_type_definitions()),
+                SchemaExtension(def) => Some(def.root_operation_type_definitions()),
+                _ => None,
+            };
+            for root_operation in root_operations.into_iter().flatten() {
+                let type_name = get_name(root_operation.named_type().and_then(|ty| ty.name()));
+                if removals.types.contains(&type_name) {
+                    edits.extend(removal(sdl, root_operation.syntax()));
+                }
+            }
+
+            let type_name = match get_name_and_kind(&definition) {
+                Some((type_name, _)) if !is_federation_machinery(&type_name) => type_name,
+                _ => continue,
+            };
+            if removals.types.contains(&type_name) {
+                edits.extend(definition_removal(sdl, definition.syntax()));
+                continue;
+            }
+            let is_removed =
+                |member_name: String| removals.members.contains(&(type_name.clone(), member_name));
+
+            let fields = match &definition {
+                ObjectTypeDefinition(def) => def.fields_definition(),
+                ObjectTypeExtension(def) => def.fields_definition(),
+                InterfaceTypeDefinition(def) => def.fields_definition(),
+                InterfaceTypeExtension(def) => def.fields_definition(),
+                _ => None,
+            };
+            for field in fields.iter().flat_map(|fields| fields.field_definitions()) {
+                let field_name = get_name(field.name());
+                if is_removed(field_name.clone()) {
+                    edits.extend(removal(sdl, field.syntax()));
+                    continue;
+                }
+                let arguments = match field.arguments_definition() {
+                    Some(arguments) => arguments,
+                    None => continue,
+                };
+                let (removed, kept): (Vec<_>, Vec<_>) =
+                    arguments.input_value_definitions().partition(|argument| {
+                        removals.arguments.contains(&(
+                            type_name.clone(),
+                            field_name.clone(),
+                            get_name(argument.name()),
+                        ))
+                    });
+                // a field without arguments can't keep its parentheses
+                if kept.is_empty() && !removed.is_empty() {
+                    edits.extend(removal(sdl, arguments.syntax()));
+                } else {
+                    for argument in &removed {
+                        edits.extend(removal(sdl, argument.syntax()));
+                    }
+                }
+            }
+
+            let input_fields = match &definition {
+                InputObjectTypeDefinition(def) => def.input_fields_definition(),
+                InputObjectTypeExtension(def) => def.input_fields_definition(),
+                _ => None,
+            };
+            for input_field in input_fields
+                .iter()
+                .flat_map(|input_fields| input_fields.input_value_definitions())
+            {
+                if is_removed(get_name(input_field.name())) {
+                    edits.extend(removal(sdl, input_field.syntax()));
+                }
+            }
+
+            let enum_values = match &definition {
+                EnumTypeDefinition(def) => def.enum_values_definition(),
+                EnumTypeExtension(def) => def.enum_values_definition(),
+                _ => None,
+            };
+            for value in enum_values
+                .iter()
+                .flat_map(|values| values.enum_value_definitions())
+            {
+                if is_removed(get_name(value.enum_value().and_then(|value| value.name()))) {
+                    edits.extend(removal(sdl, value.syntax()));
+                }
+            }
+
+            let interfaces = match &definition {
+                ObjectTypeDefinition(def) => def.implements_interfaces(),
+                ObjectTypeExtension(def) => def.implements_interfaces(),
+                InterfaceTypeDefinition(def) => def.implements_interfaces(),
+                InterfaceTypeExtension(def) => def.implements_interfaces(),
+                _ => None,
+            };
+            if let Some(interfaces) = interfaces {
+                let names: Vec<String> = interfaces
+                    .named_types()
+                    .map(|named_type| get_name(named_type.name()))
+                    .collect();
+                let kept: Vec<&String> = names
+                    .iter()
+                    .filter(|name| !removals.types.contains(*name))
+                    .collect();
+                if kept.is_empty() {
+                    edits.extend(removal(sdl, interfaces.syntax()));
+                } else if kept.len() < names.len() {
+                    if let Some((start, end)) = significant_range(interfaces.syntax()) {
+                        let kept: Vec<&str> = kept.iter().map(|name| name.as_str()).collect();
+                        edits.push((start, end, format!("implements {}", kept.join(" & "))));
+                    }
+                }
+            }
+
+            let union_members = match &definition {
+                UnionTypeDefinition(def) => def.union_member_types(),
+                UnionTypeExtension(def) => def.union_member_types(),
+                _ => None,
+            };
+            if let Some(union_members) = union_members {
+                let names: Vec<String> = union_members
+                    .named_types()
+                    .map(|named_type| get_name(named_type.name()))
+                    .collect();
+                let kept: Vec<&str> = names
+                    .iter()
+                    .filter(|name| !removals.types.contains(*name))
+                    .map(|name| name.as_str())
+                    .collect();
+                if kept.is_empty() {
+                    edits.extend(removal(sdl, union_members.syntax()));
+                } else if kept.len() < names.len() {
+                    if let Some((start, end)) = significant_range(union_members.syntax()) {
+                        edits.push((start, end, format!("= {}", kept.join(" | "))));
+                    }
+                }
+            }
+        }
+
+        // edits inside an edit that covers them, like the fields of a removed type, are skipped
+        edits.sort_unstable_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+        let mut outer_edits: Vec<(usize, usize, String)> = Vec::new();
+        for edit in edits {
+            match outer_edits.last() {
+                Some(outer) if edit.0 < outer.1 => continue,
+                _ => outer_edits.push(edit),
+            }
+        }
+        let mut filtered = sdl.to_string();
+        // edit from the end so earlier ranges stay valid
+        for (start, end, replacement) in outer_edits.into_iter().rev() {
+            filtered.replace_range(start..end, &replacement);
+        }
+        Ok(filtered)
+    }
+
+    /// Decides what's removed by tag, then removes whatever depends on something removed
+    /// until nothing else has to be
+    fn get_removals(&self, types: &BTreeMap<String, ContractType>) -> Removals {
+        let is_included = |tags: &[String]| tags.iter().any(|tag| self.include_tags.contains(tag));
+        let is_excluded = |tags: &[String]| tags.iter().any(|tag| self.exclude_tags.contains(tag));
+
+        let mut removals = Removals::default();
+        for (type_name, contract_type) in types {
+            if is_excluded(&contract_type.tags) {
+                removals.types.insert(type_name.clone());
+                continue;
+            }
+            let type_is_included = self.include_tags.is_empty() || is_included(&contract_type.tags);
+            let mut has_included_member = false;
+            for (member_name, member) in &contract_type.members {
+                has_included_member |= is_included(&member.tags);
+                if is_excluded(&member.tags) || !(type_is_included || is_included(&member.tags)) {
+                    removals
+                        .members
+                        .insert((type_name.clone(), member_name.clone()));
+                }
+                for (argument_name, argument) in &member.arguments {
+                    if is_excluded(&argument.tags) {
+                        removals.arguments.insert((
+                            type_name.clone(),
+                            member_name.clone(),
+                            argument_name.clone(),
+                        ));
+                    }
+                }
+            }
+            if !type_is_included && !has_included_member {
+                removals.types.insert(type_name.clone());
+            }
+        }
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (type_name, contract_type) in types {
+                if removals.types.contains(type_name) {
+                    continue;
+                }
+                for (member_name, member) in &contract_type.members {
+                    let member_key = (type_name.clone(), member_name.clone());
+                    if removals.members.contains(&member_key) {
+                        continue;
+                    }
+                    let mut drops_required_argument = false;
+                    for (argument_name, argument) in &member.arguments {
+                        let argument_key = (
+                            type_name.clone(),
+                            member_name.clone(),
+                            argument_name.clone(),
+                        );
+                        if removals.types.contains(&argument.ty) {
+                            removals.arguments.insert(argument_key.clone());
+                        }
+                        drops_required_argument |=
+                            argument.required && removals.arguments.contains(&argument_key);
+                    }
+                    let has_removed_type =
+                        matches!(&member.ty, Some(ty) if removals.types.contains(ty));
+                    if drops_required_argument || has_removed_type {
+                        removals.members.insert(member_key);
+                        changed = true;
+                    }
+                }
+
+                let has_no_members = contract_type.has_members
+                    && contract_type.members.keys().all(|member_name| {
+                        removals
+                            .members
+                            .contains(&(type_name.clone(), member_name.clone()))
+                    });
+                let has_no_union_members = matches!(
+                    &contract_type.union_members,
+                    Some(union_members) if union_members.iter().all(|name| removals.types.contains(name))
+                );
+                if has_no_members || has_no_union_members {
+                    removals.types.insert(type_name.clone());
+                    changed = true;
+                }
+            }
+        }
+        removals
+    }
+}
+
+/// Every type that isn't part of federation
+fn get_contract_types(model: &SchemaModel) -> BTreeMap<String, ContractType> {
+    model
+        .types
+        .iter()
+        .filter(|(type_name, _)| !is_federation_machinery(type_name))
+        .map(|(type_name, shape)| {
+            let mut members: BTreeMap<String, ContractMember> = shape
+                .fields
+                .iter()
+                .map(|(field_name, field)| {
+                    let arguments = field
+                        .arguments
+                        .iter()
+                        .map(|(argument_name, argument)| {
+                            let argument = ContractArgument {
+                                tags: argument.tags.clone(),
+                                ty: argument.get_named_type().to_string(),
+                                required: argument.is_required(),
+                            };
+                            (argument_name.clone(), argument)
+                        })
+                        .collect();
+                    let member = ContractMember {
+                        tags: field.tags.clone(),
+                        ty: Some(field.get_named_type().to_string()),
+                        arguments,
+                    };
+                    (field_name.clone(), member)
+                })
+                .collect();
+            let union_members = match shape.kind {
+                "union" => Some(shape.members.iter().cloned().collect()),
+                _ => None,
+            };
+            if shape.kind == "enum" {
+                for value in &shape.members {
+                    let member = ContractMember {
+                        tags: shape.value_tags.get(value).cloned().unwrap_or_default(),
+                        ..Default::default()
+                    };
+                    members.insert(value.clone(), member);
+                }
+            }
+            let contract_type = ContractType {
+                tags: shape.tags.clone(),
+                has_members: matches!(shape.kind, "object" | "interface" | "input" | "enum"),
+                members,
+                union_members,
+            };
+            (type_name.clone(), contract_type)
+        })
+        .collect()
+}
+
+/// An edit that removes a node, such as a field, with the whitespace around it
+fn removal(sdl: &str, node: &SyntaxNode) -> Option<(usize, usize, String)> {
+    removal_range(sdl, node).map(|(start, end)| (start, end, String::new()))
+}
+
+/// An edit that removes a definition, along with the blank line that separated it
+/// from the next one
+fn definition_removal(sdl: &str, node: &SyntaxNode) -> Option<(usize, usize, String)> {
+    removal_range(sdl, node).map(|(start, mut end)| {
+        if sdl[end..].starts_with('\n') {
+            end += 1;
+        }
+        (start, end, String::new())
+    })
+}
+
+/// The byte range of a node from its first to its last token that isn't whitespace,
+/// a comma, or a comment
+fn significant_range(node: &SyntaxNode) -> Option<(usize, usize)> {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| {
+            !matches!(
+                token.kind(),
+                SyntaxKind::WHITESPACE | SyntaxKind::COMMENT | SyntaxKind::COMMA
+            )
+        });
+    let first = tokens.next()?;
+    let last = tokens.last().unwrap_or_else(|| first.clone());
+    Some((
+        first.text_range().start().into(),
+        last.text_range().end().into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPERGRAPH: &str = r#"schema
+  @link(url: "https://specs.apollo.dev/join/v0.2", for: EXECUTION)
+{
+  query: Query
+  mutation: Mutation
+}
+
+enum join__Graph {
+  PRODUCTS @join__graph(name: "products", url: "http://products")
+}
+
+type Query {
+  products(first: Int, secret: String @tag(name: "internal")): [Product] @tag(name: "public")
+  search(term: String!): [Result] @tag(name: "public")
+  audit: AuditLog
+}
+
+type Mutation {
+  deleteProduct(id: ID!): Boolean @tag(name: "internal")
+}
+
+type Product implements Node & Audited @tag(name: "public") {
+  id: ID!
+  name: String
+  cost: Float @tag(name: "internal")
+}
+
+interface Node @tag(name: "public") {
+  id: ID!
+}
+
+interface Audited {
+  auditLog: AuditLog
+}
+
+type AuditLog @tag(name: "internal") {
+  entries: [String]
+}
+
+union Result @tag(name: "public") = Product | AuditLog
+"#;
+
+    #[test]
+    fn it_excludes_tagged_elements_and_what_depends_on_them() {
+        let filter = ContractFilter {
+            include_tags: Vec::new(),
+            exclude_tags: vec!["internal".to_string()],
+        };
+        assert_eq!(
+            filter.apply(SUPERGRAPH).unwrap(),
+            r#"schema
+  @link(url: "https://specs.apollo.dev/join/v0.2", for: EXECUTION)
+{
+  query: Query
+}
+
+enum join__Graph {
+  PRODUCTS @join__graph(name: "products", url: "http://products")
+}
+
+type Query {
+  products(first: Int,): [Product] @tag(name: "public")
+  search(term: String!): [Result] @tag(name: "public")
+}
+
+type Product implements Node @tag(name: "public") {
+  id: ID!
+  name: String
+}
+
+interface Node @tag(name: "public") {
+  id: ID!
+}
+
+union Result @tag(name: "public") = Product
+"#
+        );
+    }
+
+    #[test]
+    fn it_only_keeps_included_elements() {
+        let filter = ContractFilter {
+            include_tags: vec!["public".to_string()],
+            exclude_tags: vec!["internal".to_string()],
+        };
+        let filtered = filter.apply(SUPERGRAPH).unwrap();
+        assert!(filtered.contains("type Query {\n  products(first: Int,): [Product]"));
+        assert!(!filtered.contains("audit: AuditLog"));
+        assert!(!filtered.contains("interface Audited"));
+        assert!(filtered.contains("enum join__Graph"));
+
+        let message = ContractFilter {
+            include_tags: vec!["partner".to_string()],
+            exclude_tags: Vec::new(),
+        }
+        .apply(SUPERGRAPH)
+        .unwrap_err()
+        .message();
+        assert!(message.contains("removes every field of the Query type"));
+    }
+}
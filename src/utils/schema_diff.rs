@@ -0,0 +1,437 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::utils::schema_model::{FieldShape, SchemaModel, TypeShape};
+use crate::utils::schema_transform::is_federation_machinery;
+use crate::utils::table::{self, row};
+use crate::RoverResult;
+
+/// How a schema change affects clients that were written against the base schema
+#[derive(Debug, Serialize, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DiffSeverity {
+    /// Existing operations may stop working
+    Breaking,
+
+    /// Existing operations keep working, but clients may see values they don't expect
+    Dangerous,
+
+    /// Existing operations are unaffected
+    Safe,
+}
+
+impl fmt::Display for DiffSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            DiffSeverity::Breaking => "BREAKING",
+            DiffSeverity::Dangerous => "DANGEROUS",
+            DiffSeverity::Safe => "SAFE",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct SchemaDiffChange {
+    /// The code associated with a given change
+    /// e.g. 'TYPE_REMOVED'
+    pub code: &'static str,
+
+    /// Explanation of a given change
+    pub description: String,
+
+    /// The severity of a given change
+    pub severity: DiffSeverity,
+}
+
+/// The changes between two GraphQL schemas, computed locally
+/// without running a check in Apollo Studio
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct SchemaDiff {
+    /// A description of the schema the changes are relative to
+    pub base: String,
+
+    /// A description of the schema that was compared against the base
+    pub target: String,
+
+    pub changes: Vec<SchemaDiffChange>,
+}
+
+impl SchemaDiff {
+    /// Compares two SDL strings, returning the changes needed to get from `base_sdl` to `target_sdl`
+    pub fn new(
+        base: impl Into<String>,
+        base_sdl: &str,
+        target: impl Into<String>,
+        target_sdl: &str,
+    ) -> RoverResult<Self> {
+        Self::compare(base.into(), base_sdl, target.into(), target_sdl, |_| true)
+    }
+
+    /// Compares two supergraph schemas like `new`, leaving out the types federation
+    /// adds to them, so only changes to the graph's own API are reported
+    pub fn new_for_supergraphs(
+        base: impl Into<String>,
+        base_sdl: &str,
+        target: impl Into<String>,
+        target_sdl: &str,
+    ) -> RoverResult<Self> {
+        Self::compare(base.into(), base_sdl, target.into(), target_sdl, |name| {
+            !is_federation_machinery(name)
+        })
+    }
+
+    fn compare(
+        base: String,
+        base_sdl: &str,
+        target: String,
+        target_sdl: &str,
+        keep_type: impl Fn(&str) -> bool,
+    ) -> RoverResult<Self> {
+        let mut base_types = SchemaModel::new(&base, base_sdl)?.types;
+        let mut target_types = SchemaModel::new(&target, target_sdl)?.types;
+        base_types.retain(|name, _| keep_type(name));
+        target_types.retain(|name, _| keep_type(name));
+
+        let mut changes = Vec::new();
+        diff_types(&base_types, &target_types, &mut changes);
+        changes.sort_by(|a, b| a.severity.cmp(&b.severity));
+
+        Ok(Self {
+            base,
+            target,
+            changes,
+        })
+    }
+
+    pub fn count(&self, severity: DiffSeverity) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.severity == severity)
+            .count()
+    }
+
+    pub fn get_table(&self) -> String {
+        if self.changes.is_empty() {
+            return format!(
+                "There were no changes detected between {} and {}.",
+                &self.base, &self.target
+            );
+        }
+
+        let mut msg = format!(
+            "Found {} changes between {} and {} ({} breaking, {} dangerous, {} safe)\n",
+            self.changes.len(),
+            &self.base,
+            &self.target,
+            self.count(DiffSeverity::Breaking),
+            self.count(DiffSeverity::Dangerous),
+            self.count(DiffSeverity::Safe)
+        );
+
+        let mut table = table::get_table();
+
+        // bc => sets top row to be bold and center
+        table.add_row(row![bc => "Change", "Code", "Description"]);
+        for change in &self.changes {
+            table.add_row(row![change.severity, change.code, change.description]);
+        }
+
+        msg.push_str(&table.to_string());
+        msg
+    }
+
+    pub fn get_json(&self) -> Value {
+        json!({
+            "base": self.base,
+            "target": self.target,
+            "changes": self.changes,
+            "breaking_count": self.count(DiffSeverity::Breaking),
+        })
+    }
+}
+
+fn diff_types(
+    base: &BTreeMap<String, TypeShape>,
+    target: &BTreeMap<String, TypeShape>,
+    changes: &mut Vec<SchemaDiffChange>,
+) {
+    for (name, base_type) in base {
+        match target.get(name) {
+            None => changes.push(SchemaDiffChange {
+                code: "TYPE_REMOVED",
+                description: format!("type `{}`: removed", name),
+                severity: DiffSeverity::Breaking,
+            }),
+            Some(target_type) if target_type.kind != base_type.kind => {
+                changes.push(SchemaDiffChange {
+                    code: "TYPE_CHANGED_KIND",
+                    description: format!(
+                        "type `{}`: changed from {} to {}",
+                        name, base_type.kind, target_type.kind
+                    ),
+                    severity: DiffSeverity::Breaking,
+                })
+            }
+            Some(target_type) => diff_type(name, base_type, target_type, changes),
+        }
+    }
+
+    for (name, target_type) in target {
+        if !base.contains_key(name) {
+            changes.push(SchemaDiffChange {
+                code: "TYPE_ADDED",
+                description: format!("type `{}`: added {}", name, target_type.kind),
+                severity: DiffSeverity::Safe,
+            });
+        }
+    }
+}
+
+fn diff_type(
+    type_name: &str,
+    base: &TypeShape,
+    target: &TypeShape,
+    changes: &mut Vec<SchemaDiffChange>,
+) {
+    let is_input = base.kind == "input";
+
+    for (name, base_field) in &base.fields {
+        match target.fields.get(name) {
+            None => changes.push(SchemaDiffChange {
+                code: "FIELD_REMOVED",
+                description: format!("field `{}.{}`: removed", type_name, name),
+                severity: DiffSeverity::Breaking,
+            }),
+            Some(target_field) => {
+                if target_field.ty != base_field.ty {
+                    // clients can always handle an output that's stricter than before,
+                    // and can keep passing the same values to an input that's looser
+                    let is_safe = if is_input {
+                        only_adds_non_null(&target_field.ty, &base_field.ty)
+                    } else {
+                        only_adds_non_null(&base_field.ty, &target_field.ty)
+                    };
+                    changes.push(SchemaDiffChange {
+                        code: "FIELD_CHANGED_TYPE",
+                        description: format!(
+                            "field `{}.{}`: type changed from `{}` to `{}`",
+                            type_name, name, base_field.ty, target_field.ty
+                        ),
+                        severity: if is_safe {
+                            DiffSeverity::Safe
+                        } else {
+                            DiffSeverity::Breaking
+                        },
+                    });
+                }
+                diff_arguments(
+                    &format!("{}.{}", type_name, name),
+                    base_field,
+                    target_field,
+                    changes,
+                );
+            }
+        }
+    }
+
+    for (name, target_field) in &target.fields {
+        if !base.fields.contains_key(name) {
+            // a new input field breaks operations that don't provide it
+            let severity = if is_input && target_field.is_required() {
+                DiffSeverity::Breaking
+            } else {
+                DiffSeverity::Safe
+            };
+            changes.push(SchemaDiffChange {
+                code: "FIELD_ADDED",
+                description: format!("field `{}.{}`: added", type_name, name),
+                severity,
+            });
+        }
+    }
+
+    let (removed_code, added_code) = if base.kind == "enum" {
+        ("ENUM_VALUE_REMOVED", "ENUM_VALUE_ADDED")
+    } else {
+        ("TYPE_REMOVED_FROM_UNION", "TYPE_ADDED_TO_UNION")
+    };
+    for member in base.members.difference(&target.members) {
+        changes.push(SchemaDiffChange {
+            code: removed_code,
+            description: format!("type `{}`: removed `{}`", type_name, member),
+            severity: DiffSeverity::Breaking,
+        });
+    }
+    for member in target.members.difference(&base.members) {
+        // clients that exhaustively match on the values of this type may not handle the new one
+        changes.push(SchemaDiffChange {
+            code: added_code,
+            description: format!("type `{}`: added `{}`", type_name, member),
+            severity: DiffSeverity::Dangerous,
+        });
+    }
+}
+
+fn diff_arguments(
+    field_coordinate: &str,
+    base: &FieldShape,
+    target: &FieldShape,
+    changes: &mut Vec<SchemaDiffChange>,
+) {
+    for (name, base_arg) in &base.arguments {
+        match target.arguments.get(name) {
+            None => changes.push(SchemaDiffChange {
+                code: "ARG_REMOVED",
+                description: format!("field `{}`: argument `{}` removed", field_coordinate, name),
+                severity: DiffSeverity::Breaking,
+            }),
+            Some(target_arg) if target_arg.ty != base_arg.ty => changes.push(SchemaDiffChange {
+                code: "ARG_CHANGED_TYPE",
+                description: format!(
+                    "field `{}`: argument `{}` type changed from `{}` to `{}`",
+                    field_coordinate, name, base_arg.ty, target_arg.ty
+                ),
+                // arguments are inputs, so only making them looser is safe
+                severity: if only_adds_non_null(&target_arg.ty, &base_arg.ty) {
+                    DiffSeverity::Safe
+                } else {
+                    DiffSeverity::Breaking
+                },
+            }),
+            Some(_) => {}
+        }
+    }
+
+    for (name, target_arg) in &target.arguments {
+        if !base.arguments.contains_key(name) {
+            let (code, severity) = if target_arg.is_required() {
+                ("REQUIRED_ARG_ADDED", DiffSeverity::Breaking)
+            } else {
+                ("OPTIONAL_ARG_ADDED", DiffSeverity::Safe)
+            };
+            changes.push(SchemaDiffChange {
+                code,
+                description: format!("field `{}`: argument `{}` added", field_coordinate, name),
+                severity,
+            });
+        }
+    }
+}
+
+/// Whether `strict` is the type `loose` with `!` added at any of its levels,
+/// i.e. `[String!]!` for `[String]`, which makes every value of `strict` a valid `loose`
+fn only_adds_non_null(loose: &str, strict: &str) -> bool {
+    match (loose.strip_suffix('!'), strict.strip_suffix('!')) {
+        (Some(loose), Some(strict)) => only_adds_non_null(loose, strict),
+        (None, Some(strict)) => only_adds_non_null(loose, strict),
+        (Some(_), None) => false,
+        (None, None) => match (list_item_type(loose), list_item_type(strict)) {
+            (Some(loose), Some(strict)) => only_adds_non_null(loose, strict),
+            (None, None) => loose == strict,
+            _ => false,
+        },
+    }
+}
+
+fn list_item_type(ty: &str) -> Option<&str> {
+    ty.strip_prefix('[')?.strip_suffix(']')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(base_sdl: &str, target_sdl: &str) -> Vec<(&'static str, DiffSeverity)> {
+        SchemaDiff::new("base", base_sdl, "target", target_sdl)
+            .unwrap()
+            .changes
+            .into_iter()
+            .map(|change| (change.code, change.severity))
+            .collect()
+    }
+
+    #[test]
+    fn identical_schemas_have_no_changes() {
+        let sdl = "type Query { hello(name: String): String! }";
+        assert!(diff(sdl, sdl).is_empty());
+    }
+
+    #[test]
+    fn formatting_is_not_a_change() {
+        let base = "type Query { hello(name: String): [String!]! }";
+        let target = "type Query {\n  # greets you\n  hello(name: String):\n    [ String! ]!\n}";
+        assert!(diff(base, target).is_empty());
+    }
+
+    #[test]
+    fn it_classifies_type_and_field_changes() {
+        let base = "type Query { a: String b: Int d: [Int!] } type Old { id: ID }";
+        let target = "type Query { a: String! c: Int d: [Int] } type New { id: ID }";
+        assert_eq!(
+            diff(base, target),
+            vec![
+                ("TYPE_REMOVED", DiffSeverity::Breaking),
+                ("FIELD_REMOVED", DiffSeverity::Breaking),
+                ("FIELD_CHANGED_TYPE", DiffSeverity::Breaking),
+                ("FIELD_CHANGED_TYPE", DiffSeverity::Safe),
+                ("FIELD_ADDED", DiffSeverity::Safe),
+                ("TYPE_ADDED", DiffSeverity::Safe),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_classifies_argument_changes() {
+        let base = "type Query { a(v: Int!, x: Int, y: Int): String }";
+        let target = "type Query { a(v: Int, x: Int!, z: Int, w: Int!): String }";
+        assert_eq!(
+            diff(base, target),
+            vec![
+                ("ARG_CHANGED_TYPE", DiffSeverity::Breaking),
+                ("ARG_REMOVED", DiffSeverity::Breaking),
+                ("REQUIRED_ARG_ADDED", DiffSeverity::Breaking),
+                ("ARG_CHANGED_TYPE", DiffSeverity::Safe),
+                ("OPTIONAL_ARG_ADDED", DiffSeverity::Safe),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_classifies_enum_union_and_input_changes() {
+        let base = "enum Color { RED GREEN } union Pet = Cat | Dog input Filter { q: String }";
+        let target =
+            "enum Color { RED BLUE } union Pet = Cat | Fish input Filter { q: String limit: Int! }";
+        assert_eq!(
+            diff(base, target),
+            vec![
+                ("ENUM_VALUE_REMOVED", DiffSeverity::Breaking),
+                ("FIELD_ADDED", DiffSeverity::Breaking),
+                ("TYPE_REMOVED_FROM_UNION", DiffSeverity::Breaking),
+                ("ENUM_VALUE_ADDED", DiffSeverity::Dangerous),
+                ("TYPE_ADDED_TO_UNION", DiffSeverity::Dangerous),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_only_treats_added_non_nulls_as_stricter() {
+        assert!(only_adds_non_null("String", "String!"));
+        assert!(only_adds_non_null("[String]", "[String!]!"));
+        assert!(only_adds_non_null("[String!]", "[String!]"));
+        assert!(!only_adds_non_null("String!", "String"));
+        assert!(!only_adds_non_null("[String]", "[String!"));
+        assert!(!only_adds_non_null("String", "[String]"));
+        assert!(!only_adds_non_null("String", "Int!"));
+    }
+
+    #[test]
+    fn it_errors_on_invalid_sdl() {
+        assert!(
+            SchemaDiff::new("base", "type Query {", "target", "type Query { a: Int }").is_err()
+        );
+    }
+}
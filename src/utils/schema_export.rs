@@ -0,0 +1,585 @@
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use apollo_parser::{ast, Parser, SyntaxNode};
+use serde::Serialize;
+
+use crate::utils::introspection_json::{
+    get_deprecation, get_description, get_name, get_name_and_kind, type_string,
+};
+use crate::utils::schema_transform::is_federation_machinery;
+use crate::utils::schema_transform::{removal_range, DirectiveFilter};
+use crate::RoverResult;
+
+/// Directives that only describe how a supergraph is put together,
+/// which are left out of the text of every record
+const FEDERATION_DIRECTIVES: &[&str] = &["join__*", "link", "link__*", "core", "core__*"];
+
+/// One chunk of an exported schema, describing a type or one of its members
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct ExportRecord {
+    /// the schema coordinate of the element, i.e. `Product` or `Product.name`,
+    /// which stays the same between exports for as long as the element exists
+    pub id: String,
+    /// `OBJECT`, `INTERFACE`, `UNION`, `ENUM`, `INPUT_OBJECT`, or `SCALAR` for types,
+    /// and `FIELD`, `INPUT_FIELD`, or `ENUM_VALUE` for their members
+    pub kind: &'static str,
+    /// the type a member belongs to, which is `None` for types
+    pub parent_type: Option<String>,
+    /// the type of a field or input field, i.e. `[Product!]!`
+    #[serde(rename = "type")]
+    pub ty: Option<String>,
+    pub description: Option<String>,
+    pub deprecated: bool,
+    pub deprecation_reason: Option<String>,
+    /// the name of every `@tag` on the element
+    pub tags: Vec<String>,
+    /// the subgraphs that define the element, which is only known for supergraph schemas
+    pub subgraphs: Vec<String>,
+    /// the content of the chunk: the SDL of a type, or the signature and description of a member
+    pub text: String,
+}
+
+/// Everything exported for one type, across its definition and extensions
+struct ExportedType {
+    record: ExportRecord,
+    members: Vec<ExportRecord>,
+    inaccessible: bool,
+}
+
+/// Splits a schema into one record per type, followed by one record per field,
+/// input field, and enum value of that type, in the order they're defined.
+///
+/// Supergraph schemas are exported as their API schema would be:
+/// federation types and directives are left out, as is anything marked `@inaccessible`,
+/// and every record lists the subgraphs it comes from.
+pub fn export(sdl: &str) -> RoverResult<Vec<ExportRecord>> {
+    use ast::AstNode;
+
+    let parsed_ast = Parser::new(sdl).parse();
+    if let Some(error) = parsed_ast.errors().next() {
+        return Err(anyhow!("Could not export the schema: {}", error.message()).into());
+    }
+    let document = parsed_ast.document();
+    let graph_names = get_graph_names(&document);
+
+    // types are kept in the order they're first defined in, with their extensions merged in
+    let mut order: Vec<String> = Vec::new();
+    let mut types: BTreeMap<String, ExportedType> = BTreeMap::new();
+    for definition in document.definitions() {
+        let (type_name, kind) = match get_name_and_kind(&definition) {
+            Some(name_and_kind) => name_and_kind,
+            None => continue,
+        };
+        if is_federation_machinery(&type_name) {
+            continue;
+        }
+
+        let directives = get_type_directives(&definition);
+        let exported = types.entry(type_name.clone()).or_insert_with(|| {
+            order.push(type_name.clone());
+            ExportedType {
+                record: ExportRecord {
+                    id: type_name.clone(),
+                    kind,
+                    parent_type: None,
+                    ty: None,
+                    description: None,
+                    deprecated: false,
+                    deprecation_reason: None,
+                    tags: Vec::new(),
+                    subgraphs: Vec::new(),
+                    text: String::new(),
+                },
+                members: Vec::new(),
+                inaccessible: false,
+            }
+        });
+        exported.inaccessible |= is_inaccessible(directives.clone());
+
+        let record = &mut exported.record;
+        if let Some(description) = get_description(get_type_description(&definition)) {
+            record.description = Some(description);
+        }
+        if let Some(reason) = get_deprecation(directives.clone()) {
+            record.deprecated = true;
+            record.deprecation_reason = Some(reason);
+        }
+        add_unique(&mut record.tags, get_tags(directives.clone()));
+        add_unique(
+            &mut record.subgraphs,
+            get_subgraphs(directives, &["join__type", "join__owner"], &graph_names),
+        );
+        if !record.text.is_empty() {
+            record.text.push_str("\n\n");
+        }
+        record
+            .text
+            .push_str(&definition_text(sdl, definition.syntax()));
+
+        exported
+            .members
+            .extend(get_members(&type_name, &definition, &graph_names));
+    }
+
+    let mut records = Vec::new();
+    for type_name in order {
+        let exported = match types.remove(&type_name) {
+            Some(exported) if !exported.inaccessible => exported,
+            _ => continue,
+        };
+        let type_subgraphs = exported.record.subgraphs.clone();
+        records.push(exported.record);
+        // members without subgraphs of their own are defined by every subgraph that defines the type
+        records.extend(exported.members.into_iter().map(|mut member| {
+            if member.subgraphs.is_empty() {
+                member.subgraphs = type_subgraphs.clone();
+            }
+            member
+        }));
+    }
+    Ok(records)
+}
+
+/// The records for the fields, input fields, and enum values of one definition or extension
+fn get_members(
+    type_name: &str,
+    definition: &ast::Definition,
+    graph_names: &BTreeMap<String, String>,
+) -> Vec<ExportRecord> {
+    use ast::Definition::*;
+
+    let mut members = Vec::new();
+    let new_member = |name: &str,
+                      kind: &'static str,
+                      ty: Option<String>,
+                      signature: String,
+                      description: Option<ast::Description>,
+                      directives: Option<ast::Directives>,
+                      subgraph_directive: &str| {
+        let description = get_description(description);
+        let deprecation_reason = get_deprecation(directives.clone());
+        let id = format!("{}.{}", type_name, name);
+
+        let mut text = format!("{}{}", id, signature);
+        if let Some(ty) = &ty {
+            text.push_str(&format!(": {}", ty));
+        }
+        if let Some(description) = &description {
+            text.push_str(&format!("\n{}", description));
+        }
+        if let Some(reason) = &deprecation_reason {
+            text.push_str(&format!("\nDeprecated: {}", reason));
+        }
+
+        ExportRecord {
+            id,
+            kind,
+            parent_type: Some(type_name.to_string()),
+            ty,
+            description,
+            deprecated: deprecation_reason.is_some(),
+            deprecation_reason,
+            tags: get_tags(directives.clone()),
+            subgraphs: get_subgraphs(directives, &[subgraph_directive], graph_names),
+            text,
+        }
+    };
+
+    let fields = match definition {
+        ObjectTypeDefinition(def) => def.fields_definition(),
+        ObjectTypeExtension(def) => def.fields_definition(),
+        InterfaceTypeDefinition(def) => def.fields_definition(),
+        InterfaceTypeExtension(def) => def.fields_definition(),
+        _ => None,
+    };
+    for field in fields.iter().flat_map(|fields| fields.field_definitions()) {
+        let name = get_name(field.name());
+        if name.starts_with('_') || is_inaccessible(field.directives()) {
+            continue;
+        }
+        let arguments: Vec<String> = field
+            .arguments_definition()
+            .iter()
+            .flat_map(|args| args.input_value_definitions())
+            .filter(|arg| !is_inaccessible(arg.directives()))
+            .map(|arg| format!("{}: {}", get_name(arg.name()), type_string(arg.ty())))
+            .collect();
+        let signature = if arguments.is_empty() {
+            String::new()
+        } else {
+            format!("({})", arguments.join(", "))
+        };
+        members.push(new_member(
+            &name,
+            "FIELD",
+            Some(type_string(field.ty())),
+            signature,
+            field.description(),
+            field.directives(),
+            "join__field",
+        ));
+    }
+
+    let input_fields = match definition {
+        InputObjectTypeDefinition(def) => def.input_fields_definition(),
+        InputObjectTypeExtension(def) => def.input_fields_definition(),
+        _ => None,
+    };
+    for input_field in input_fields
+        .iter()
+        .flat_map(|input_fields| input_fields.input_value_definitions())
+    {
+        if is_inaccessible(input_field.directives()) {
+            continue;
+        }
+        members.push(new_member(
+            &get_name(input_field.name()),
+            "INPUT_FIELD",
+            Some(type_string(input_field.ty())),
+            String::new(),
+            input_field.description(),
+            input_field.directives(),
+            "join__field",
+        ));
+    }
+
+    let enum_values = match definition {
+        EnumTypeDefinition(def) => def.enum_values_definition(),
+        EnumTypeExtension(def) => def.enum_values_definition(),
+        _ => None,
+    };
+    for value in enum_values
+        .iter()
+        .flat_map(|values| values.enum_value_definitions())
+    {
+        if is_inaccessible(value.directives()) {
+            continue;
+        }
+        members.push(new_member(
+            &get_name(value.enum_value().and_then(|v| v.name())),
+            "ENUM_VALUE",
+            None,
+            String::new(),
+            value.description(),
+            value.directives(),
+            "join__enumValue",
+        ));
+    }
+
+    members
+}
+
+fn get_type_description(definition: &ast::Definition) -> Option<ast::Description> {
+    use ast::Definition::*;
+
+    match definition {
+        ObjectTypeDefinition(def) => def.description(),
+        InterfaceTypeDefinition(def) => def.description(),
+        UnionTypeDefinition(def) => def.description(),
+        EnumTypeDefinition(def) => def.description(),
+        InputObjectTypeDefinition(def) => def.description(),
+        ScalarTypeDefinition(def) => def.description(),
+        _ => None,
+    }
+}
+
+fn get_type_directives(definition: &ast::Definition) -> Option<ast::Directives> {
+    use ast::Definition::*;
+
+    match definition {
+        ObjectTypeDefinition(def) => def.directives(),
+        ObjectTypeExtension(def) => def.directives(),
+        InterfaceTypeDefinition(def) => def.directives(),
+        InterfaceTypeExtension(def) => def.directives(),
+        UnionTypeDefinition(def) => def.directives(),
+        UnionTypeExtension(def) => def.directives(),
+        EnumTypeDefinition(def) => def.directives(),
+        EnumTypeExtension(def) => def.directives(),
+        InputObjectTypeDefinition(def) => def.directives(),
+        InputObjectTypeExtension(def) => def.directives(),
+        ScalarTypeDefinition(def) => def.directives(),
+        ScalarTypeExtension(def) => def.directives(),
+        _ => None,
+    }
+}
+
+/// The SDL of a definition, without federation directives or `@inaccessible` members
+fn definition_text(sdl: &str, node: &SyntaxNode) -> String {
+    use ast::AstNode;
+
+    let start: usize = node.text_range().start().into();
+    let end: usize = node.text_range().end().into();
+    let mut removals: Vec<(usize, usize)> = node
+        .descendants()
+        .filter(|child| {
+            let directives = if let Some(field) = ast::FieldDefinition::cast(child.clone()) {
+                field.directives()
+            } else if let Some(value) = ast::InputValueDefinition::cast(child.clone()) {
+                value.directives()
+            } else if let Some(value) = ast::EnumValueDefinition::cast(child.clone()) {
+                value.directives()
+            } else {
+                return false;
+            };
+            is_inaccessible(directives)
+        })
+        .filter_map(|child| removal_range(sdl, &child))
+        .collect();
+    removals.sort_unstable();
+
+    let mut text = sdl[start..end].to_string();
+    let mut removed_until = end;
+    // remove from the end so earlier ranges stay valid, skipping members of removed members
+    for (removal_start, removal_end) in removals.into_iter().rev() {
+        let removal_end = removal_end.min(removed_until);
+        if removal_start < start || removal_start >= removal_end {
+            continue;
+        }
+        text.replace_range(removal_start - start..removal_end - start, "");
+        removed_until = removal_start;
+    }
+
+    let filter = DirectiveFilter::Exclude(
+        FEDERATION_DIRECTIVES
+            .iter()
+            .map(|directive| directive.to_string())
+            .collect(),
+    );
+    filter.apply(&text).trim().to_string()
+}
+
+/// The names of a supergraph's subgraphs, keyed by their value in the `join__Graph` enum
+fn get_graph_names(document: &ast::Document) -> BTreeMap<String, String> {
+    let mut graph_names = BTreeMap::new();
+    for definition in document.definitions() {
+        let def = match definition {
+            ast::Definition::EnumTypeDefinition(def) if get_name(def.name()) == "join__Graph" => {
+                def
+            }
+            _ => continue,
+        };
+        for value in def
+            .enum_values_definition()
+            .iter()
+            .flat_map(|values| values.enum_value_definitions())
+        {
+            let graph = get_name(value.enum_value().and_then(|v| v.name()));
+            let name = find_directives(value.directives(), "join__graph")
+                .iter()
+                .find_map(|directive| get_string_argument(directive, "name"))
+                .unwrap_or_else(|| graph.to_lowercase());
+            graph_names.insert(graph, name);
+        }
+    }
+    graph_names
+}
+
+/// The subgraphs named by the `graph` argument of `directive_names`,
+/// leaving out the ones that only reference an `external` field
+fn get_subgraphs(
+    directives: Option<ast::Directives>,
+    directive_names: &[&str],
+    graph_names: &BTreeMap<String, String>,
+) -> Vec<String> {
+    use ast::AstNode;
+
+    let mut subgraphs = Vec::new();
+    for name in directive_names {
+        for directive in find_directives(directives.clone(), name) {
+            let is_external = matches!(
+                get_argument(&directive, "external"),
+                Some(ast::Value::BooleanValue(value)) if value.syntax().text() == "true"
+            );
+            if is_external {
+                continue;
+            }
+            let graph = match get_argument(&directive, "graph") {
+                Some(ast::Value::EnumValue(value)) => get_name(value.name()),
+                Some(ast::Value::StringValue(value)) => String::from(value),
+                _ => continue,
+            };
+            let subgraph = graph_names.get(&graph).cloned().unwrap_or(graph);
+            add_unique(&mut subgraphs, vec![subgraph]);
+        }
+    }
+    subgraphs
+}
+
+pub(crate) fn get_tags(directives: Option<ast::Directives>) -> Vec<String> {
+    find_directives(directives, "tag")
+        .iter()
+        .filter_map(|directive| get_string_argument(directive, "name"))
+        .collect()
+}
+
+fn is_inaccessible(directives: Option<ast::Directives>) -> bool {
+    !find_directives(directives, "inaccessible").is_empty()
+}
+
+fn find_directives(directives: Option<ast::Directives>, name: &str) -> Vec<ast::Directive> {
+    directives
+        .iter()
+        .flat_map(|directives| directives.directives())
+        .filter(|directive| directive.name().map_or(false, |n| name == n.text()))
+        .collect()
+}
+
+fn get_argument(directive: &ast::Directive, name: &str) -> Option<ast::Value> {
+    directive
+        .arguments()
+        .iter()
+        .flat_map(|args| args.arguments())
+        .find(|arg| arg.name().map_or(false, |n| name == n.text()))
+        .and_then(|arg| arg.value())
+}
+
+fn get_string_argument(directive: &ast::Directive, name: &str) -> Option<String> {
+    match get_argument(directive, name) {
+        Some(ast::Value::StringValue(value)) => Some(String::from(value)),
+        _ => None,
+    }
+}
+
+fn add_unique(values: &mut Vec<String>, new_values: Vec<String>) {
+    for value in new_values {
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPERGRAPH: &str = r#"schema
+  @link(url: "https://specs.apollo.dev/link/v1.0")
+  @link(url: "https://specs.apollo.dev/join/v0.2", for: EXECUTION)
+{
+  query: Query
+}
+
+directive @join__field(graph: join__Graph!, external: Boolean) repeatable on FIELD_DEFINITION | INPUT_FIELD_DEFINITION
+
+directive @join__type(graph: join__Graph!, key: join__FieldSet) repeatable on OBJECT | INTERFACE | UNION | ENUM | INPUT_OBJECT | SCALAR
+
+directive @join__graph(name: String!, url: String!) on ENUM_VALUE
+
+scalar join__FieldSet
+
+enum join__Graph {
+  PRODUCTS @join__graph(name: "products", url: "http://products")
+  REVIEWS @join__graph(name: "reviews", url: "http://reviews")
+}
+
+type Query
+  @join__type(graph: PRODUCTS)
+  @join__type(graph: REVIEWS)
+{
+  product(id: ID!): Product @join__field(graph: PRODUCTS)
+}
+
+"""
+A product in the catalog
+"""
+type Product
+  @join__type(graph: PRODUCTS, key: "id")
+  @join__type(graph: REVIEWS, key: "id")
+  @tag(name: "public")
+{
+  id: ID!
+  name: String @join__field(graph: PRODUCTS)
+  "The average rating out of 5"
+  rating: Float @join__field(graph: REVIEWS) @deprecated(reason: "Use `reviews` instead")
+  internalCode: String @join__field(graph: PRODUCTS) @inaccessible
+}
+"#;
+
+    fn ids(records: &[ExportRecord]) -> Vec<&str> {
+        records.iter().map(|record| record.id.as_str()).collect()
+    }
+
+    #[test]
+    fn it_exports_types_and_fields() {
+        let records = export(SUPERGRAPH).unwrap();
+        assert_eq!(
+            ids(&records),
+            vec![
+                "Query",
+                "Query.product",
+                "Product",
+                "Product.id",
+                "Product.name",
+                "Product.rating"
+            ]
+        );
+
+        let product = &records[2];
+        assert_eq!(product.kind, "OBJECT");
+        assert_eq!(
+            product.description.as_deref(),
+            Some("A product in the catalog")
+        );
+        assert_eq!(product.tags, vec!["public".to_string()]);
+        assert_eq!(
+            product.subgraphs,
+            vec!["products".to_string(), "reviews".to_string()]
+        );
+        assert_eq!(
+            product.text,
+            "\"\"\"\nA product in the catalog\n\"\"\"\ntype Product\n  @tag(name: \"public\")\n{\n  id: ID!\n  name: String\n  \"The average rating out of 5\"\n  rating: Float @deprecated(reason: \"Use `reviews` instead\")\n}"
+        );
+    }
+
+    #[test]
+    fn it_exports_member_metadata() {
+        let records = export(SUPERGRAPH).unwrap();
+
+        let product = &records[1];
+        assert_eq!(product.kind, "FIELD");
+        assert_eq!(product.parent_type.as_deref(), Some("Query"));
+        assert_eq!(product.ty.as_deref(), Some("Product"));
+        assert_eq!(product.text, "Query.product(id: ID!): Product");
+
+        let id = &records[3];
+        assert_eq!(
+            id.subgraphs,
+            vec!["products".to_string(), "reviews".to_string()]
+        );
+
+        let rating = &records[5];
+        assert!(rating.deprecated);
+        assert_eq!(rating.subgraphs, vec!["reviews".to_string()]);
+        assert_eq!(
+            rating.text,
+            "Product.rating: Float\nThe average rating out of 5\nDeprecated: Use `reviews` instead"
+        );
+    }
+
+    #[test]
+    fn it_exports_enum_values_and_input_fields() {
+        let records = export(
+            "enum Color { RED GREEN @deprecated }\ninput ProductInput { name: String! color: Color }",
+        )
+        .unwrap();
+        assert_eq!(
+            ids(&records),
+            vec![
+                "Color",
+                "Color.RED",
+                "Color.GREEN",
+                "ProductInput",
+                "ProductInput.name",
+                "ProductInput.color"
+            ]
+        );
+        assert_eq!(records[2].kind, "ENUM_VALUE");
+        assert_eq!(
+            records[2].deprecation_reason.as_deref(),
+            Some("No longer supported")
+        );
+        assert_eq!(records[4].kind, "INPUT_FIELD");
+        assert!(records[4].subgraphs.is_empty());
+    }
+}
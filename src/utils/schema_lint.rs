@@ -0,0 +1,507 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use apollo_parser::{ast, Parser};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::utils::table::{self, row};
+
+/// How serious a lint violation is. Errors fail the lint, warnings don't.
+#[derive(Debug, Serialize, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LintLevel {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            LintLevel::Error => "ERROR",
+            LintLevel::Warning => "WARNING",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+/// The rules a schema is linted against
+#[derive(
+    Debug,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Display,
+    EnumIter,
+    EnumString,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum LintRule {
+    /// The schema could not be parsed
+    InvalidGraphqlSyntax,
+
+    /// Names starting with `__` are reserved for introspection
+    RestrictedNames,
+
+    TypeNamesShouldBePascalCase,
+    FieldNamesShouldBeCamelCase,
+    InputArgumentNamesShouldBeCamelCase,
+    EnumValuesShouldBeScreamingSnakeCase,
+
+    /// Input object type names should end with `Input`
+    InputTypeSuffix,
+
+    /// `@deprecated` should always say what to use instead
+    DeprecatedDirectiveMissingReason,
+}
+
+impl LintRule {
+    /// The level a rule reports at unless it is configured otherwise
+    pub fn default_level(&self) -> LintLevel {
+        match self {
+            LintRule::InvalidGraphqlSyntax | LintRule::RestrictedNames => LintLevel::Error,
+            _ => LintLevel::Warning,
+        }
+    }
+}
+
+/// Overrides the level of lint rules. A level of `None` turns a rule off.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintConfig {
+    levels: HashMap<LintRule, Option<LintLevel>>,
+}
+
+impl LintConfig {
+    pub fn with_rule(mut self, rule: LintRule, level: Option<LintLevel>) -> Self {
+        self.levels.insert(rule, level);
+        self
+    }
+
+    pub fn get_level(&self, rule: LintRule) -> Option<LintLevel> {
+        match self.levels.get(&rule) {
+            Some(level) => *level,
+            None => Some(rule.default_level()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct LintViolation {
+    pub rule: LintRule,
+    pub level: LintLevel,
+    pub message: String,
+
+    /// 1-based line of the violation in the linted schema
+    pub line: usize,
+
+    /// 1-based column of the violation in the linted schema
+    pub column: usize,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct LintResponse {
+    pub violations: Vec<LintViolation>,
+}
+
+impl LintResponse {
+    pub fn count(&self, level: LintLevel) -> usize {
+        self.violations
+            .iter()
+            .filter(|violation| violation.level == level)
+            .count()
+    }
+
+    pub fn get_table(&self) -> String {
+        if self.violations.is_empty() {
+            return "There were no lint violations in the schema.".to_string();
+        }
+
+        let mut msg = format!(
+            "Found {} errors and {} warnings\n",
+            self.count(LintLevel::Error),
+            self.count(LintLevel::Warning)
+        );
+
+        let mut table = table::get_table();
+
+        // bc => sets top row to be bold and center
+        table.add_row(row![bc => "Level", "Position", "Rule", "Message"]);
+        for violation in &self.violations {
+            table.add_row(row![
+                violation.level,
+                format!("{}:{}", violation.line, violation.column),
+                violation.rule,
+                violation.message
+            ]);
+        }
+
+        msg.push_str(&table.to_string());
+        msg
+    }
+
+    pub fn get_json(&self) -> Value {
+        json!({
+            "violations": self.violations,
+            "error_count": self.count(LintLevel::Error),
+            "warning_count": self.count(LintLevel::Warning),
+        })
+    }
+}
+
+/// The error returned when a lint finds error-level violations. It carries the
+/// whole response so it can still be printed.
+#[derive(Debug)]
+pub struct LintFailure {
+    pub response: LintResponse,
+}
+
+impl fmt::Display for LintFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let num_errors = self.response.count(LintLevel::Error);
+        match num_errors {
+            1 => write!(f, "The schema has 1 lint error."),
+            _ => write!(f, "The schema has {} lint errors.", num_errors),
+        }
+    }
+}
+
+impl std::error::Error for LintFailure {}
+
+/// Lints an SDL schema, returning every violation of the rules enabled in `config`
+pub fn lint(sdl: &str, config: &LintConfig) -> LintResponse {
+    let mut linter = Linter {
+        sdl,
+        config,
+        violations: Vec::new(),
+    };
+
+    let parsed_ast = Parser::new(sdl).parse();
+    for error in parsed_ast.errors() {
+        linter.report_at(
+            LintRule::InvalidGraphqlSyntax,
+            error.index(),
+            error.message().to_string(),
+        );
+    }
+
+    for definition in parsed_ast.document().definitions() {
+        linter.check_definition(definition);
+    }
+
+    let mut violations = linter.violations;
+    violations.sort_by_key(|violation| (violation.line, violation.column));
+    LintResponse { violations }
+}
+
+struct Linter<'a> {
+    sdl: &'a str,
+    config: &'a LintConfig,
+    violations: Vec<LintViolation>,
+}
+
+impl<'a> Linter<'a> {
+    fn check_definition(&mut self, definition: ast::Definition) {
+        use ast::Definition::*;
+
+        match definition {
+            ObjectTypeDefinition(def) => {
+                self.check_type_name(def.name());
+                self.check_fields(def.fields_definition());
+                self.check_directives(def.directives());
+            }
+            ObjectTypeExtension(def) => self.check_fields(def.fields_definition()),
+            InterfaceTypeDefinition(def) => {
+                self.check_type_name(def.name());
+                self.check_fields(def.fields_definition());
+                self.check_directives(def.directives());
+            }
+            InterfaceTypeExtension(def) => self.check_fields(def.fields_definition()),
+            InputObjectTypeDefinition(def) => {
+                self.check_type_name(def.name());
+                if let Some(name) = def.name() {
+                    let text = name.text().to_string();
+                    if !text.ends_with("Input") {
+                        self.report(
+                            LintRule::InputTypeSuffix,
+                            &name,
+                            format!("input type `{}` should end with `Input`", text),
+                        );
+                    }
+                }
+                self.check_input_values(def.input_fields_definition().map(|f| {
+                    f.input_value_definitions()
+                        .collect::<Vec<ast::InputValueDefinition>>()
+                }));
+                self.check_directives(def.directives());
+            }
+            InputObjectTypeExtension(def) => {
+                self.check_input_values(def.input_fields_definition().map(|f| {
+                    f.input_value_definitions()
+                        .collect::<Vec<ast::InputValueDefinition>>()
+                }));
+            }
+            EnumTypeDefinition(def) => {
+                self.check_type_name(def.name());
+                self.check_enum_values(def.enum_values_definition());
+                self.check_directives(def.directives());
+            }
+            EnumTypeExtension(def) => self.check_enum_values(def.enum_values_definition()),
+            UnionTypeDefinition(def) => {
+                self.check_type_name(def.name());
+                self.check_directives(def.directives());
+            }
+            ScalarTypeDefinition(def) => {
+                self.check_type_name(def.name());
+                self.check_directives(def.directives());
+            }
+            _ => {}
+        }
+    }
+
+    fn check_type_name(&mut self, name: Option<ast::Name>) {
+        let name = match name {
+            Some(name) => name,
+            None => return,
+        };
+        let text = name.text().to_string();
+        if self.check_restricted(&name, &text) {
+            return;
+        }
+        let starts_upper = text
+            .chars()
+            .next()
+            .map_or(false, |c| c.is_ascii_uppercase());
+        if !starts_upper || text.contains('_') {
+            self.report(
+                LintRule::TypeNamesShouldBePascalCase,
+                &name,
+                format!("type `{}` should be PascalCase", text),
+            );
+        }
+    }
+
+    fn check_fields(&mut self, fields: Option<ast::FieldsDefinition>) {
+        for field in fields.iter().flat_map(|f| f.field_definitions()) {
+            if let Some(name) = field.name() {
+                self.check_camel_case(LintRule::FieldNamesShouldBeCamelCase, "field", &name);
+            }
+            if let Some(args) = field.arguments_definition() {
+                for arg in args.input_value_definitions() {
+                    if let Some(name) = arg.name() {
+                        self.check_camel_case(
+                            LintRule::InputArgumentNamesShouldBeCamelCase,
+                            "argument",
+                            &name,
+                        );
+                    }
+                    self.check_directives(arg.directives());
+                }
+            }
+            self.check_directives(field.directives());
+        }
+    }
+
+    fn check_input_values(&mut self, values: Option<Vec<ast::InputValueDefinition>>) {
+        for value in values.into_iter().flatten() {
+            if let Some(name) = value.name() {
+                self.check_camel_case(LintRule::FieldNamesShouldBeCamelCase, "input field", &name);
+            }
+            self.check_directives(value.directives());
+        }
+    }
+
+    fn check_enum_values(&mut self, values: Option<ast::EnumValuesDefinition>) {
+        for value in values.iter().flat_map(|v| v.enum_value_definitions()) {
+            if let Some(name) = value.enum_value().and_then(|v| v.name()) {
+                let text = name.text().to_string();
+                if self.check_restricted(&name, &text) {
+                    continue;
+                }
+                let is_screaming_snake = text
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+                if !is_screaming_snake {
+                    self.report(
+                        LintRule::EnumValuesShouldBeScreamingSnakeCase,
+                        &name,
+                        format!("enum value `{}` should be SCREAMING_SNAKE_CASE", text),
+                    );
+                }
+            }
+            self.check_directives(value.directives());
+        }
+    }
+
+    fn check_camel_case(&mut self, rule: LintRule, kind: &str, name: &ast::Name) {
+        let text = name.text().to_string();
+        if self.check_restricted(name, &text) {
+            return;
+        }
+        // leading underscores are a common convention for internal fields, i.e. `_service`
+        let trimmed = text.trim_start_matches('_');
+        let starts_lower = trimmed
+            .chars()
+            .next()
+            .map_or(true, |c| c.is_ascii_lowercase());
+        if !starts_lower || trimmed.contains('_') {
+            self.report(
+                rule,
+                name,
+                format!("{} `{}` should be camelCase", kind, text),
+            );
+        }
+    }
+
+    /// reports names reserved for introspection, returning whether the name was reserved
+    fn check_restricted(&mut self, name: &ast::Name, text: &str) -> bool {
+        if text.starts_with("__") {
+            self.report(
+                LintRule::RestrictedNames,
+                name,
+                format!(
+                    "`{}` starts with `__`, which is reserved for introspection",
+                    text
+                ),
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    fn check_directives(&mut self, directives: Option<ast::Directives>) {
+        for directive in directives.iter().flat_map(|d| d.directives()) {
+            let name = match directive.name() {
+                Some(name) => name,
+                None => continue,
+            };
+            if "deprecated" != name.text() {
+                continue;
+            }
+            let has_reason = directive
+                .arguments()
+                .iter()
+                .flat_map(|args| args.arguments())
+                .any(|arg| arg.name().map_or(false, |n| "reason" == n.text()));
+            if !has_reason {
+                self.report(
+                    LintRule::DeprecatedDirectiveMissingReason,
+                    &name,
+                    "`@deprecated` should include a `reason` saying what to use instead"
+                        .to_string(),
+                );
+            }
+        }
+    }
+
+    fn report(&mut self, rule: LintRule, name: &ast::Name, message: String) {
+        use ast::AstNode;
+
+        let offset: usize = name.syntax().text_range().start().into();
+        self.report_at(rule, offset, message);
+    }
+
+    fn report_at(&mut self, rule: LintRule, offset: usize, message: String) {
+        if let Some(level) = self.config.get_level(rule) {
+            let (line, column) = get_position(self.sdl, offset);
+            self.violations.push(LintViolation {
+                rule,
+                level,
+                message,
+                line,
+                column,
+            });
+        }
+    }
+}
+
+/// converts a byte offset into a 1-based line and column
+pub(crate) fn get_position(sdl: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(sdl.len());
+    let before = &sdl[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(response: &LintResponse) -> Vec<(LintRule, usize, usize)> {
+        response
+            .violations
+            .iter()
+            .map(|v| (v.rule, v.line, v.column))
+            .collect()
+    }
+
+    #[test]
+    fn a_clean_schema_has_no_violations() {
+        let sdl = "type Query {\n  user(userId: ID!): User\n}\n\ntype User {\n  id: ID!\n  status: Status @deprecated(reason: \"use state\")\n}\n\nenum Status {\n  ACTIVE\n  IN_REVIEW\n}\n\ninput UserInput {\n  displayName: String\n}\n";
+        assert!(lint(sdl, &LintConfig::default()).violations.is_empty());
+    }
+
+    #[test]
+    fn it_reports_violations_with_positions() {
+        let sdl = "type user_profile {\n  Full_name: String @deprecated\n  status: status\n}\n\nenum status { active }\n\ninput Filter { __q: String }\n";
+        let response = lint(sdl, &LintConfig::default());
+        assert_eq!(
+            rules(&response),
+            vec![
+                (LintRule::TypeNamesShouldBePascalCase, 1, 6),
+                (LintRule::FieldNamesShouldBeCamelCase, 2, 3),
+                (LintRule::DeprecatedDirectiveMissingReason, 2, 22),
+                (LintRule::TypeNamesShouldBePascalCase, 6, 6),
+                (LintRule::EnumValuesShouldBeScreamingSnakeCase, 6, 15),
+                (LintRule::InputTypeSuffix, 8, 7),
+                (LintRule::RestrictedNames, 8, 16),
+            ]
+        );
+        assert_eq!(response.count(LintLevel::Error), 1);
+        assert_eq!(response.count(LintLevel::Warning), 6);
+    }
+
+    #[test]
+    fn rules_can_be_configured() {
+        let sdl = "input Filter { q: String }\n";
+        let config = LintConfig::default().with_rule(LintRule::InputTypeSuffix, None);
+        assert!(lint(sdl, &config).violations.is_empty());
+
+        let config =
+            LintConfig::default().with_rule(LintRule::InputTypeSuffix, Some(LintLevel::Error));
+        assert_eq!(lint(sdl, &config).count(LintLevel::Error), 1);
+    }
+
+    #[test]
+    fn it_reports_syntax_errors() {
+        let response = lint("type Query {", &LintConfig::default());
+        assert!(response
+            .violations
+            .iter()
+            .any(|v| v.rule == LintRule::InvalidGraphqlSyntax && v.level == LintLevel::Error));
+    }
+
+    #[test]
+    fn rule_ids_round_trip() {
+        use std::str::FromStr;
+        use strum::IntoEnumIterator;
+
+        for rule in LintRule::iter() {
+            assert_eq!(LintRule::from_str(&rule.to_string()).unwrap(), rule);
+        }
+        assert_eq!(
+            LintRule::InputTypeSuffix.to_string(),
+            "INPUT_TYPE_SUFFIX".to_string()
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use apollo_parser::{ast, Parser};
+use serde_json::{json, Map, Value};
+
+use crate::utils::introspection_json::get_name;
+use crate::utils::schema_model::SchemaModel;
+use crate::RoverResult;
+
+/// The number of items a mocked list has
+const MOCK_LIST_LENGTH: usize = 2;
+
+/// A subgraph schema that resolves operations with fake values instead of real resolvers.
+///
+/// Every value is derived from the path to the field it's for, so the same operation always
+/// gets the same response. Entities passed to `_entities` keep the key fields the router sent
+/// for them, so they can be joined with the entities of other subgraphs
+#[derive(Debug, Clone)]
+pub struct MockSchema {
+    sdl: String,
+    model: SchemaModel,
+}
+
+impl MockSchema {
+    pub fn new(sdl: &str) -> RoverResult<Self> {
+        Ok(Self {
+            sdl: sdl.to_string(),
+            model: SchemaModel::new("the mocked subgraph", sdl)?,
+        })
+    }
+
+    /// Resolves a GraphQL request, i.e. `{ "query": "...", "variables": {...} }`, returning
+    /// its response. A request that can't be resolved gets a response with `errors`
+    pub fn execute(&self, request: &Value) -> Value {
+        match self.execute_request(request) {
+            Ok(data) => json!({ "data": data }),
+            Err(e) => json!({ "errors": [{ "message": e.message() }] }),
+        }
+    }
+
+    fn execute_request(&self, request: &Value) -> RoverResult<Value> {
+        let query = request
+            .get("query")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("The request does not have a \"query\""))?;
+        let operation_name = request.get("operationName").and_then(Value::as_str);
+        let empty_variables = Map::new();
+        let variables = request
+            .get("variables")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty_variables);
+
+        let parsed_ast = Parser::new(query).parse();
+        if let Some(error) = parsed_ast.errors().next() {
+            return Err(anyhow!("Could not parse the operation: {}", error.message()).into());
+        }
+
+        let mut fragments = BTreeMap::new();
+        let mut operations = Vec::new();
+        for definition in parsed_ast.document().definitions() {
+            match definition {
+                ast::Definition::FragmentDefinition(def) => {
+                    let name = get_name(def.fragment_name().and_then(|name| name.name()));
+                    fragments.insert(name, def);
+                }
+                ast::Definition::OperationDefinition(def) => operations.push(def),
+                _ => {}
+            }
+        }
+        let operation = match operation_name {
+            Some(operation_name) => operations
+                .into_iter()
+                .find(|op| get_name(op.name()) == operation_name)
+                .ok_or_else(|| anyhow!("There is no operation named '{}'", operation_name))?,
+            None => operations
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow!("The request does not have an operation"))?,
+        };
+
+        let operation_type = operation.operation_type();
+        let root_type = if operation_type
+            .as_ref()
+            .and_then(|op| op.subscription_token())
+            .is_some()
+        {
+            return Err(anyhow!("Mocked subgraphs do not support subscriptions").into());
+        } else if operation_type.and_then(|op| op.mutation_token()).is_some() {
+            &self.model.mutation_type
+        } else {
+            &self.model.query_type
+        };
+
+        let resolver = Resolver {
+            schema: self,
+            fragments: &fragments,
+            variables,
+        };
+        Ok(Value::Object(resolver.resolve_selection_set(
+            root_type,
+            operation.selection_set(),
+            root_type,
+            None,
+        )?))
+    }
+
+    /// Whether an object is one of the types a fragment's type condition matches
+    fn is_possible_type(&self, type_condition: &str, object_type: &str) -> bool {
+        type_condition == object_type
+            || self
+                .model
+                .types
+                .get(type_condition)
+                .map(|shape| shape.kind == "union" && shape.members.contains(object_type))
+                .unwrap_or(false)
+            || self
+                .model
+                .types
+                .get(object_type)
+                .map(|shape| shape.interfaces.contains(type_condition))
+                .unwrap_or(false)
+    }
+
+    /// The objects an abstract type can resolve to, in a stable order
+    fn get_possible_types(&self, abstract_type: &str) -> Vec<String> {
+        match self.model.types.get(abstract_type) {
+            Some(shape) if shape.kind == "union" => shape.members.iter().cloned().collect(),
+            _ => self
+                .model
+                .types
+                .iter()
+                .filter(|(_, shape)| shape.interfaces.contains(abstract_type))
+                .map(|(name, _)| name.clone())
+                .collect(),
+        }
+    }
+}
+
+struct Resolver<'a> {
+    schema: &'a MockSchema,
+    fragments: &'a BTreeMap<String, ast::FragmentDefinition>,
+    variables: &'a Map<String, Value>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Resolves the fields selected on an object. `path` identifies the object, and
+    /// `known` has the fields of an entity representation, which are returned as they are
+    fn resolve_selection_set(
+        &self,
+        object_type: &str,
+        selection_set: Option<ast::SelectionSet>,
+        path: &str,
+        known: Option<&Map<String, Value>>,
+    ) -> RoverResult<Map<String, Value>> {
+        let mut data = Map::new();
+        for field in self.collect_fields(object_type, selection_set) {
+            let field_name = get_name(field.name());
+            let response_key = match field.alias() {
+                Some(alias) => get_name(alias.name()),
+                None => field_name.clone(),
+            };
+            let field_path = format!("{}.{}", path, field_name);
+
+            let value = match field_name.as_str() {
+                "__typename" => Value::String(object_type.to_string()),
+                "_service" if object_type == self.schema.model.query_type => {
+                    json!({ "sdl": self.schema.sdl })
+                }
+                "_entities" if object_type == self.schema.model.query_type => {
+                    self.resolve_entities(&field)?
+                }
+                _ => match known.and_then(|known| known.get(&field_name)) {
+                    Some(value) if field.selection_set().is_none() => value.clone(),
+                    _ => {
+                        let field_type = self
+                            .schema
+                            .model
+                            .types
+                            .get(object_type)
+                            .and_then(|shape| shape.fields.get(&field_name))
+                            .map(|field| field.ty.as_str())
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "Cannot query field '{}' on type '{}'",
+                                    field_name,
+                                    object_type
+                                )
+                            })?;
+                        self.resolve_value(
+                            field_type,
+                            &field_name,
+                            field.selection_set(),
+                            &field_path,
+                        )?
+                    }
+                },
+            };
+            data.insert(response_key, value);
+        }
+        Ok(data)
+    }
+
+    /// Resolves `_entities`, keeping the fields of each representation the router sent
+    fn resolve_entities(&self, field: &ast::Field) -> RoverResult<Value> {
+        let representations = field
+            .arguments()
+            .iter()
+            .flat_map(|arguments| arguments.arguments())
+            .find(|argument| get_name(argument.name()) == "representations")
+            .and_then(|argument| argument.value())
+            .map(|value| self.get_argument_value(value))
+            .unwrap_or(Value::Null);
+        let representations = representations
+            .as_array()
+            .ok_or_else(|| anyhow!("`_entities` needs a list of representations"))?;
+
+        let mut entities = Vec::with_capacity(representations.len());
+        for representation in representations {
+            let representation = representation
+                .as_object()
+                .ok_or_else(|| anyhow!("Each representation must be an object"))?;
+            let typename = representation
+                .get("__typename")
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("Each representation must have a `__typename`"))?;
+            // entities with the same keys get the same fields
+            let path = format!("{}:{}", typename, Value::Object(representation.clone()));
+            entities.push(Value::Object(self.resolve_selection_set(
+                typename,
+                field.selection_set(),
+                &path,
+                Some(representation),
+            )?));
+        }
+        Ok(Value::Array(entities))
+    }
+
+    fn resolve_value(
+        &self,
+        field_type: &str,
+        field_name: &str,
+        selection_set: Option<ast::SelectionSet>,
+        path: &str,
+    ) -> RoverResult<Value> {
+        let field_type = field_type.trim_end_matches('!');
+        if let Some(item_type) = field_type
+            .strip_prefix('[')
+            .and_then(|ty| ty.strip_suffix(']'))
+        {
+            let mut items = Vec::with_capacity(MOCK_LIST_LENGTH);
+            for index in 0..MOCK_LIST_LENGTH {
+                items.push(self.resolve_value(
+                    item_type,
+                    field_name,
+                    selection_set.clone(),
+                    &format!("{}[{}]", path, index),
+                )?);
+            }
+            return Ok(Value::Array(items));
+        }
+
+        let seed = get_seed(path);
+        let value = match field_type {
+            "Int" => json!(seed % 100),
+            "Float" => json!((seed % 10_000) as f64 / 100.0),
+            "Boolean" => json!(seed % 2 == 0),
+            "ID" => json!(format!("{}", seed % 10_000)),
+            "String" => json!(format!("{} {}", field_name, seed % 1_000)),
+            _ => match self.schema.model.types.get(field_type) {
+                Some(shape) if shape.kind == "object" => Value::Object(
+                    self.resolve_selection_set(field_type, selection_set, path, None)?,
+                ),
+                Some(shape) if matches!(shape.kind, "interface" | "union") => {
+                    let possible_types = self.schema.get_possible_types(field_type);
+                    if possible_types.is_empty() {
+                        Value::Null
+                    } else {
+                        let object_type = &possible_types[seed as usize % possible_types.len()];
+                        Value::Object(self.resolve_selection_set(
+                            object_type,
+                            selection_set,
+                            path,
+                            None,
+                        )?)
+                    }
+                }
+                Some(shape) if shape.kind == "enum" && !shape.members.is_empty() => {
+                    let values: Vec<&String> = shape.members.iter().collect();
+                    json!(values[seed as usize % values.len()])
+                }
+                // custom scalars can be anything, so they're mocked as strings
+                Some(shape) if shape.kind == "scalar" => {
+                    json!(format!("{} {}", field_name, seed % 1_000))
+                }
+                _ => Value::Null,
+            },
+        };
+        Ok(value)
+    }
+
+    /// The fields selected on an object, including the ones in fragments that apply to it
+    fn collect_fields(
+        &self,
+        object_type: &str,
+        selection_set: Option<ast::SelectionSet>,
+    ) -> Vec<ast::Field> {
+        let mut fields = Vec::new();
+        for selection in selection_set.iter().flat_map(|set| set.selections()) {
+            match selection {
+                ast::Selection::Field(field) => fields.push(field),
+                ast::Selection::FragmentSpread(spread) => {
+                    let name = get_name(spread.fragment_name().and_then(|name| name.name()));
+                    if let Some(fragment) = self.fragments.get(&name) {
+                        if self.applies_to(fragment.type_condition(), object_type) {
+                            fields
+                                .extend(self.collect_fields(object_type, fragment.selection_set()));
+                        }
+                    }
+                }
+                ast::Selection::InlineFragment(fragment) => {
+                    if self.applies_to(fragment.type_condition(), object_type) {
+                        fields.extend(self.collect_fields(object_type, fragment.selection_set()));
+                    }
+                }
+            }
+        }
+        fields
+    }
+
+    fn applies_to(&self, type_condition: Option<ast::TypeCondition>, object_type: &str) -> bool {
+        match type_condition.and_then(|condition| condition.named_type()) {
+            Some(named_type) => self
+                .schema
+                .is_possible_type(&get_name(named_type.name()), object_type),
+            None => true,
+        }
+    }
+
+    /// The JSON value of an argument, with its variables replaced with their values
+    fn get_argument_value(&self, value: ast::Value) -> Value {
+        use ast::AstNode;
+
+        match value {
+            ast::Value::Variable(variable) => self
+                .variables
+                .get(&get_name(variable.name()))
+                .cloned()
+                .unwrap_or(Value::Null),
+            ast::Value::StringValue(value) => Value::String(String::from(value)),
+            ast::Value::IntValue(value) => value
+                .syntax()
+                .text()
+                .to_string()
+                .parse::<i64>()
+                .map(Value::from)
+                .unwrap_or(Value::Null),
+            ast::Value::FloatValue(value) => value
+                .syntax()
+                .text()
+                .to_string()
+                .parse::<f64>()
+                .map(Value::from)
+                .unwrap_or(Value::Null),
+            ast::Value::BooleanValue(value) => Value::Bool(value.true_token().is_some()),
+            ast::Value::NullValue(_) => Value::Null,
+            ast::Value::EnumValue(value) => Value::String(get_name(value.name())),
+            ast::Value::ListValue(list) => list
+                .values()
+                .map(|value| self.get_argument_value(value))
+                .collect(),
+            ast::Value::ObjectValue(object) => Value::Object(
+                object
+                    .object_fields()
+                    .filter_map(|field| {
+                        let value = self.get_argument_value(field.value()?);
+                        Some((get_name(field.name()), value))
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// A stable hash of a field's path, so the same field always gets the same value
+fn get_seed(path: &str) -> u64 {
+    // FNV-1a, which unlike the standard library's hasher is the same across Rust versions
+    path.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDL: &str = r#"
+extend schema @link(url: "https://specs.apollo.dev/federation/v2.0", import: ["@key"])
+
+type Query {
+  topProducts(first: Int = 5): [Product!]!
+  search: [SearchResult]
+}
+
+type Product @key(fields: "upc") {
+  upc: String!
+  name: String
+  price: Int
+  status: Status
+}
+
+type Review {
+  body: String!
+}
+
+union SearchResult = Product | Review
+
+enum Status {
+  AVAILABLE
+  SOLD_OUT
+}
+"#;
+
+    #[test]
+    fn it_mocks_the_same_response_every_time() {
+        let schema = MockSchema::new(SDL).unwrap();
+        let request = json!({
+            "query": "query TopProducts { topProducts { upc name price status __typename } search { ... on Review { body } ...ProductName } } fragment ProductName on Product { name }"
+        });
+        let response = schema.execute(&request);
+        assert_eq!(response, schema.execute(&request));
+
+        let products = response["data"]["topProducts"].as_array().unwrap();
+        assert_eq!(products.len(), MOCK_LIST_LENGTH);
+        assert_ne!(products[0]["upc"], products[1]["upc"]);
+        assert!(products[0]["name"].as_str().unwrap().starts_with("name "));
+        assert!(products[0]["price"].is_i64());
+        assert!(["AVAILABLE", "SOLD_OUT"].contains(&products[0]["status"].as_str().unwrap()));
+        assert_eq!(products[0]["__typename"], "Product");
+        for result in response["data"]["search"].as_array().unwrap() {
+            assert!(result.get("body").is_some() || result.get("name").is_some());
+        }
+
+        let response = schema.execute(&json!({ "query": "{ topProducts { weight } }" }));
+        assert_eq!(
+            response["errors"][0]["message"],
+            "Cannot query field 'weight' on type 'Product'"
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_keys_of_entities() {
+        let schema = MockSchema::new(SDL).unwrap();
+        let response = schema.execute(&json!({
+            "query": "query($representations: [_Any!]!) { _entities(representations: $representations) { ... on Product { upc name } } }",
+            "variables": {
+                "representations": [
+                    { "__typename": "Product", "upc": "1" },
+                    { "__typename": "Product", "upc": "2" }
+                ]
+            }
+        }));
+        let entities = response["data"]["_entities"].as_array().unwrap();
+        assert_eq!(entities[0]["upc"], "1");
+        assert_eq!(entities[1]["upc"], "2");
+        assert_ne!(entities[0]["name"], entities[1]["name"]);
+
+        let response = schema.execute(&json!({ "query": "{ _service { sdl } }" }));
+        assert_eq!(response["data"]["_service"]["sdl"], SDL);
+    }
+}
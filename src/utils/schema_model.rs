@@ -0,0 +1,269 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::anyhow;
+use apollo_parser::{ast, Parser};
+
+use crate::utils::introspection_json::{get_name, type_string};
+use crate::utils::schema_export::get_tags;
+use crate::RoverResult;
+
+/// The named types of a schema, merged across their definitions and extensions,
+/// for the tools that work on a schema's shape rather than its text, i.e. `rover graph diff`,
+/// `rover dev --mock`, and the `filter` of a supergraph config
+#[derive(Debug, Clone)]
+pub struct SchemaModel {
+    pub types: BTreeMap<String, TypeShape>,
+    pub query_type: String,
+    pub mutation_type: String,
+}
+
+/// The parts of a named type that can change in a way that affects clients
+#[derive(Debug, Clone, Default)]
+pub struct TypeShape {
+    /// `object`, `interface`, `input`, `enum`, `union`, or `scalar`
+    pub kind: &'static str,
+
+    /// fields of object and interface types, and input fields of input types
+    pub fields: BTreeMap<String, FieldShape>,
+
+    /// enum values, or the member types of a union
+    pub members: BTreeSet<String>,
+
+    /// the interfaces an object implements
+    pub interfaces: BTreeSet<String>,
+
+    /// the names of the type's `@tag`s
+    pub tags: Vec<String>,
+
+    /// the names of the `@tag`s of each enum value
+    pub value_tags: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldShape {
+    /// the type as written in SDL, i.e. `[Product!]!`
+    pub ty: String,
+    pub has_default: bool,
+    pub arguments: BTreeMap<String, FieldShape>,
+
+    /// the names of the field's `@tag`s
+    pub tags: Vec<String>,
+}
+
+impl FieldShape {
+    /// The named type of the field, i.e. `Product` for `[Product!]!`
+    pub fn get_named_type(&self) -> &str {
+        self.ty.trim_matches(|c| matches!(c, '[' | ']' | '!'))
+    }
+
+    /// Whether callers have to pass a value for an argument or input field
+    pub fn is_required(&self) -> bool {
+        self.ty.ends_with('!') && !self.has_default
+    }
+}
+
+impl SchemaModel {
+    /// Parses a schema, describing it as `schema_name` if it can't be parsed
+    pub fn new(schema_name: &str, sdl: &str) -> RoverResult<Self> {
+        use ast::Definition::*;
+
+        let parsed_ast = Parser::new(sdl).parse();
+        if let Some(error) = parsed_ast.errors().next() {
+            return Err(anyhow!(
+                "Could not parse the schema for {}: {}",
+                schema_name,
+                error.message()
+            )
+            .into());
+        }
+
+        let mut model = Self {
+            types: BTreeMap::new(),
+            query_type: "Query".to_string(),
+            mutation_type: "Mutation".to_string(),
+        };
+        for definition in parsed_ast.document().definitions() {
+            match definition {
+                SchemaDefinition(def) => {
+                    model.add_root_operations(def.root_operation_type_definitions())
+                }
+                SchemaExtension(def) => {
+                    model.add_root_operations(def.root_operation_type_definitions())
+                }
+                ObjectTypeDefinition(def) => {
+                    let shape = model.get_type_shape(def.name(), "object");
+                    shape.tags.extend(get_tags(def.directives()));
+                    add_fields(shape, def.fields_definition());
+                    add_interfaces(shape, def.implements_interfaces());
+                }
+                ObjectTypeExtension(def) => {
+                    let shape = model.get_type_shape(def.name(), "object");
+                    shape.tags.extend(get_tags(def.directives()));
+                    add_fields(shape, def.fields_definition());
+                    add_interfaces(shape, def.implements_interfaces());
+                }
+                InterfaceTypeDefinition(def) => {
+                    let shape = model.get_type_shape(def.name(), "interface");
+                    shape.tags.extend(get_tags(def.directives()));
+                    add_fields(shape, def.fields_definition());
+                }
+                InterfaceTypeExtension(def) => {
+                    let shape = model.get_type_shape(def.name(), "interface");
+                    shape.tags.extend(get_tags(def.directives()));
+                    add_fields(shape, def.fields_definition());
+                }
+                InputObjectTypeDefinition(def) => {
+                    let shape = model.get_type_shape(def.name(), "input");
+                    shape.tags.extend(get_tags(def.directives()));
+                    if let Some(fields) = def.input_fields_definition() {
+                        add_input_values(&mut shape.fields, fields.input_value_definitions());
+                    }
+                }
+                InputObjectTypeExtension(def) => {
+                    let shape = model.get_type_shape(def.name(), "input");
+                    shape.tags.extend(get_tags(def.directives()));
+                    if let Some(fields) = def.input_fields_definition() {
+                        add_input_values(&mut shape.fields, fields.input_value_definitions());
+                    }
+                }
+                EnumTypeDefinition(def) => {
+                    let shape = model.get_type_shape(def.name(), "enum");
+                    shape.tags.extend(get_tags(def.directives()));
+                    if let Some(values) = def.enum_values_definition() {
+                        add_enum_values(shape, values);
+                    }
+                }
+                EnumTypeExtension(def) => {
+                    let shape = model.get_type_shape(def.name(), "enum");
+                    shape.tags.extend(get_tags(def.directives()));
+                    if let Some(values) = def.enum_values_definition() {
+                        add_enum_values(shape, values);
+                    }
+                }
+                UnionTypeDefinition(def) => {
+                    let shape = model.get_type_shape(def.name(), "union");
+                    shape.tags.extend(get_tags(def.directives()));
+                    if let Some(members) = def.union_member_types() {
+                        add_union_members(shape, members);
+                    }
+                }
+                UnionTypeExtension(def) => {
+                    let shape = model.get_type_shape(def.name(), "union");
+                    shape.tags.extend(get_tags(def.directives()));
+                    if let Some(members) = def.union_member_types() {
+                        add_union_members(shape, members);
+                    }
+                }
+                ScalarTypeDefinition(def) => {
+                    let shape = model.get_type_shape(def.name(), "scalar");
+                    shape.tags.extend(get_tags(def.directives()));
+                }
+                ScalarTypeExtension(def) => {
+                    let shape = model.get_type_shape(def.name(), "scalar");
+                    shape.tags.extend(get_tags(def.directives()));
+                }
+                _ => {}
+            }
+        }
+        Ok(model)
+    }
+
+    /// The types a schema's `query` and `mutation` operations start from, if it renames them
+    fn add_root_operations(
+        &mut self,
+        root_operations: impl Iterator<Item = ast::RootOperationTypeDefinition>,
+    ) {
+        for root_operation in root_operations {
+            let name = get_name(root_operation.named_type().and_then(|ty| ty.name()));
+            let operation_type = root_operation.operation_type();
+            if operation_type
+                .as_ref()
+                .and_then(|op| op.query_token())
+                .is_some()
+            {
+                self.query_type = name;
+            } else if operation_type.and_then(|op| op.mutation_token()).is_some() {
+                self.mutation_type = name;
+            }
+        }
+    }
+
+    fn get_type_shape(&mut self, name: Option<ast::Name>, kind: &'static str) -> &mut TypeShape {
+        self.types
+            .entry(get_name(name))
+            .or_insert_with(|| TypeShape {
+                kind,
+                ..Default::default()
+            })
+    }
+}
+
+fn add_fields(shape: &mut TypeShape, fields: Option<ast::FieldsDefinition>) {
+    for field in fields.iter().flat_map(|f| f.field_definitions()) {
+        let name = match field.name() {
+            Some(name) => name.text().to_string(),
+            None => continue,
+        };
+        let mut arguments = BTreeMap::new();
+        if let Some(args) = field.arguments_definition() {
+            add_input_values(&mut arguments, args.input_value_definitions());
+        }
+        shape.fields.insert(
+            name,
+            FieldShape {
+                ty: type_string(field.ty()),
+                has_default: false,
+                arguments,
+                tags: get_tags(field.directives()),
+            },
+        );
+    }
+}
+
+fn add_input_values(
+    fields: &mut BTreeMap<String, FieldShape>,
+    values: impl Iterator<Item = ast::InputValueDefinition>,
+) {
+    for value in values {
+        if let Some(name) = value.name() {
+            fields.insert(
+                name.text().to_string(),
+                FieldShape {
+                    ty: type_string(value.ty()),
+                    has_default: value.default_value().is_some(),
+                    arguments: BTreeMap::new(),
+                    tags: get_tags(value.directives()),
+                },
+            );
+        }
+    }
+}
+
+fn add_interfaces(shape: &mut TypeShape, interfaces: Option<ast::ImplementsInterfaces>) {
+    for interface in interfaces.iter().flat_map(|i| i.named_types()) {
+        if let Some(name) = interface.name() {
+            shape.interfaces.insert(name.text().to_string());
+        }
+    }
+}
+
+fn add_enum_values(shape: &mut TypeShape, values: ast::EnumValuesDefinition) {
+    for value in values.enum_value_definitions() {
+        if let Some(name) = value.enum_value().and_then(|v| v.name()) {
+            shape
+                .value_tags
+                .entry(name.text().to_string())
+                .or_default()
+                .extend(get_tags(value.directives()));
+            shape.members.insert(name.text().to_string());
+        }
+    }
+}
+
+fn add_union_members(shape: &mut TypeShape, members: ast::UnionMemberTypes) {
+    for member in members.named_types() {
+        if let Some(name) = member.name() {
+            shape.members.insert(name.text().to_string());
+        }
+    }
+}
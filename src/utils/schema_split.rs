@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context};
+use apollo_parser::{ast, Parser};
+use camino::Utf8Path;
+use rover_std::Fs;
+use serde::{Deserialize, Serialize};
+
+use crate::RoverResult;
+
+/// The name of the file listing every file of a split schema, in the order they were defined
+pub const INDEX_FILE_NAME: &str = "index.json";
+
+/// One file of a split schema
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct SchemaFile {
+    /// the path of the file, relative to the directory it's written to
+    pub path: String,
+    /// the type defined in the file, which is `None` for directives and the schema definition
+    pub type_name: Option<String>,
+    #[serde(skip)]
+    pub contents: String,
+}
+
+/// The layout of [`INDEX_FILE_NAME`]
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct SchemaIndex {
+    files: Vec<SchemaFile>,
+}
+
+/// Splits a schema into one file per type, keeping each type's extensions in its file,
+/// along with `directives.graphql` and `schema.graphql` for everything else.
+/// Comments before a definition stay with that definition.
+pub fn split(sdl: &str) -> RoverResult<Vec<SchemaFile>> {
+    use ast::AstNode;
+    use ast::Definition::*;
+
+    let parsed_ast = Parser::new(sdl).parse();
+    if let Some(error) = parsed_ast.errors().next() {
+        return Err(anyhow!("Could not split the schema: {}", error.message()).into());
+    }
+
+    // files are kept in the order they're first defined in, so the index reads like the schema
+    let mut order: Vec<String> = Vec::new();
+    let mut files: BTreeMap<String, SchemaFile> = BTreeMap::new();
+    let mut previous_end = 0;
+    for definition in parsed_ast.document().definitions() {
+        let type_name = match &definition {
+            SchemaDefinition(_) | SchemaExtension(_) | DirectiveDefinition(_) => None,
+            ScalarTypeDefinition(def) => def.name(),
+            ObjectTypeDefinition(def) => def.name(),
+            InterfaceTypeDefinition(def) => def.name(),
+            UnionTypeDefinition(def) => def.name(),
+            EnumTypeDefinition(def) => def.name(),
+            InputObjectTypeDefinition(def) => def.name(),
+            ScalarTypeExtension(def) => def.name(),
+            ObjectTypeExtension(def) => def.name(),
+            InterfaceTypeExtension(def) => def.name(),
+            UnionTypeExtension(def) => def.name(),
+            EnumTypeExtension(def) => def.name(),
+            InputObjectTypeExtension(def) => def.name(),
+            // operations and fragments don't belong in a schema, so they're left out
+            OperationDefinition(_) | FragmentDefinition(_) => {
+                previous_end = definition.syntax().text_range().end().into();
+                continue;
+            }
+        }
+        .map(|name| name.text().to_string());
+
+        let path = match (&definition, &type_name) {
+            (DirectiveDefinition(_), _) => "directives.graphql".to_string(),
+            (_, Some(type_name)) => format!("{}.graphql", type_name),
+            (_, None) => "schema.graphql".to_string(),
+        };
+
+        let end: usize = definition.syntax().text_range().end().into();
+        let contents = sdl[previous_end..end].trim();
+        previous_end = end;
+
+        let file = files.entry(path.clone()).or_insert_with(|| {
+            order.push(path.clone());
+            SchemaFile {
+                path,
+                type_name,
+                contents: String::new(),
+            }
+        });
+        if !file.contents.is_empty() {
+            file.contents.push_str("\n\n");
+        }
+        file.contents.push_str(contents);
+    }
+
+    Ok(order
+        .into_iter()
+        .filter_map(|path| files.remove(&path))
+        .map(|mut file| {
+            file.contents.push('\n');
+            file
+        })
+        .collect())
+}
+
+/// Writes every file of a split schema to `out_dir`, along with its index.
+/// The files listed in an existing index are removed first,
+/// so types that no longer exist don't leave stale files behind.
+pub fn write(out_dir: &Utf8Path, files: &[SchemaFile]) -> RoverResult<()> {
+    Fs::create_dir_all(out_dir)?;
+
+    let index_path = out_dir.join(INDEX_FILE_NAME);
+    if index_path.exists() {
+        let previous: SchemaIndex = serde_json::from_str(&Fs::read_file(&index_path)?)
+            .with_context(|| format!("{} is not a valid schema index", &index_path))?;
+        for file in previous.files {
+            let path = out_dir.join(&file.path);
+            // only remove files inside the directory, in case the index was edited by hand
+            if path.parent() == Some(out_dir) && path.exists() {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("could not remove {}", &path))?;
+            }
+        }
+    }
+
+    for file in files {
+        Fs::write_file(out_dir.join(&file.path), &file.contents)?;
+    }
+    let index = SchemaIndex {
+        files: files.to_vec(),
+    };
+    Fs::write_file(&index_path, serde_json::to_string_pretty(&index)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use assert_fs::TempDir;
+    use camino::Utf8PathBuf;
+
+    const SDL: &str = r#"directive @cacheControl(maxAge: Int) on FIELD_DEFINITION
+
+schema {
+  query: Query
+}
+
+# the root of every query
+type Query {
+  product(id: ID!): Product
+}
+
+"""
+A product in the catalog
+"""
+type Product {
+  id: ID!
+}
+
+directive @tag(name: String!) on OBJECT
+
+extend type Product {
+  name: String
+}
+"#;
+
+    fn paths(files: &[SchemaFile]) -> Vec<&str> {
+        files.iter().map(|file| file.path.as_str()).collect()
+    }
+
+    #[test]
+    fn it_splits_by_type() {
+        let files = split(SDL).unwrap();
+        assert_eq!(
+            paths(&files),
+            vec![
+                "directives.graphql",
+                "schema.graphql",
+                "Query.graphql",
+                "Product.graphql"
+            ]
+        );
+        assert_eq!(
+            files[0].contents,
+            "directive @cacheControl(maxAge: Int) on FIELD_DEFINITION\n\ndirective @tag(name: String!) on OBJECT\n"
+        );
+        assert_eq!(
+            files[2].contents,
+            "# the root of every query\ntype Query {\n  product(id: ID!): Product\n}\n"
+        );
+        assert_eq!(
+            files[3].contents,
+            "\"\"\"\nA product in the catalog\n\"\"\"\ntype Product {\n  id: ID!\n}\n\nextend type Product {\n  name: String\n}\n"
+        );
+        assert_eq!(files[3].type_name.as_deref(), Some("Product"));
+    }
+
+    #[test]
+    fn it_removes_stale_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let out_dir = Utf8PathBuf::try_from(temp_dir.path().to_path_buf()).unwrap();
+
+        write(&out_dir, &split(SDL).unwrap()).unwrap();
+        assert!(out_dir.join("Product.graphql").exists());
+
+        write(&out_dir, &split("type Query { a: String }").unwrap()).unwrap();
+        assert!(!out_dir.join("Product.graphql").exists());
+        assert!(out_dir.join("Query.graphql").exists());
+        assert!(out_dir.join(INDEX_FILE_NAME).exists());
+    }
+}
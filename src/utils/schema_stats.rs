@@ -0,0 +1,235 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::anyhow;
+use apollo_parser::{ast, Parser};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::utils::introspection_json::{get_deprecation, get_name, get_name_and_kind};
+use crate::utils::schema_transform::is_federation_machinery;
+use crate::utils::table::{self, row};
+use crate::RoverResult;
+
+/// The size of a schema, counted across every definition and extension.
+/// Types and directives that federation adds to a supergraph aren't counted
+#[derive(Debug, Serialize, Clone, Default, Eq, PartialEq)]
+pub struct SchemaStats {
+    /// a description of the schema that was counted, i.e. its graph ref or file
+    pub schema: String,
+    pub types: usize,
+    /// the number of types of each kind, i.e. `OBJECT` or `ENUM`
+    pub types_by_kind: BTreeMap<&'static str, usize>,
+    /// fields of object and interface types
+    pub fields: usize,
+    pub input_fields: usize,
+    pub enum_values: usize,
+    /// arguments of fields, not counting directive arguments
+    pub arguments: usize,
+    /// deprecated fields, input fields, enum values, and arguments
+    pub deprecations: usize,
+    pub directive_definitions: usize,
+    /// the number of times each directive is applied in the schema
+    pub directive_usages: BTreeMap<String, usize>,
+}
+
+impl SchemaStats {
+    pub fn new(schema: impl Into<String>, sdl: &str) -> RoverResult<Self> {
+        use ast::AstNode;
+        use ast::Definition::*;
+
+        let schema = schema.into();
+        let parsed_ast = Parser::new(sdl).parse();
+        if let Some(error) = parsed_ast.errors().next() {
+            return Err(anyhow!(
+                "Could not count the schema of {}: {}",
+                &schema,
+                error.message()
+            )
+            .into());
+        }
+
+        let mut stats = Self {
+            schema,
+            ..Self::default()
+        };
+        let mut type_names = BTreeSet::new();
+        for definition in parsed_ast.document().definitions() {
+            match get_name_and_kind(&definition) {
+                Some((type_name, _)) if is_federation_machinery(&type_name) => continue,
+                Some((type_name, kind)) => {
+                    if type_names.insert(type_name) {
+                        stats.types += 1;
+                        *stats.types_by_kind.entry(kind).or_default() += 1;
+                    }
+                }
+                None => {
+                    if let DirectiveDefinition(def) = &definition {
+                        if is_federation_machinery(&get_name(def.name())) {
+                            continue;
+                        }
+                        stats.directive_definitions += 1;
+                    }
+                }
+            }
+
+            let fields = match &definition {
+                ObjectTypeDefinition(def) => def.fields_definition(),
+                ObjectTypeExtension(def) => def.fields_definition(),
+                InterfaceTypeDefinition(def) => def.fields_definition(),
+                InterfaceTypeExtension(def) => def.fields_definition(),
+                _ => None,
+            };
+            for field in fields.iter().flat_map(|fields| fields.field_definitions()) {
+                stats.fields += 1;
+                stats.count_deprecation(field.directives());
+                for argument in field
+                    .arguments_definition()
+                    .iter()
+                    .flat_map(|args| args.input_value_definitions())
+                {
+                    stats.arguments += 1;
+                    stats.count_deprecation(argument.directives());
+                }
+            }
+
+            let input_fields = match &definition {
+                InputObjectTypeDefinition(def) => def.input_fields_definition(),
+                InputObjectTypeExtension(def) => def.input_fields_definition(),
+                _ => None,
+            };
+            for input_field in input_fields
+                .iter()
+                .flat_map(|fields| fields.input_value_definitions())
+            {
+                stats.input_fields += 1;
+                stats.count_deprecation(input_field.directives());
+            }
+
+            let enum_values = match &definition {
+                EnumTypeDefinition(def) => def.enum_values_definition(),
+                EnumTypeExtension(def) => def.enum_values_definition(),
+                _ => None,
+            };
+            for enum_value in enum_values
+                .iter()
+                .flat_map(|values| values.enum_value_definitions())
+            {
+                stats.enum_values += 1;
+                stats.count_deprecation(enum_value.directives());
+            }
+
+            for directive in definition
+                .syntax()
+                .descendants()
+                .filter_map(ast::Directive::cast)
+            {
+                let name = get_name(directive.name());
+                if !is_federation_machinery(&name) {
+                    *stats.directive_usages.entry(name).or_default() += 1;
+                }
+            }
+        }
+        Ok(stats)
+    }
+
+    fn count_deprecation(&mut self, directives: Option<ast::Directives>) {
+        if get_deprecation(directives).is_some() {
+            self.deprecations += 1;
+        }
+    }
+
+    pub fn get_table(&self) -> String {
+        let mut msg = format!("Counted the schema of {}\n", &self.schema);
+
+        let mut table = table::get_table();
+
+        // bc => sets top row to be bold and center
+        table.add_row(row![bc => "Element", "Count"]);
+        table.add_row(row!["Types", self.types]);
+        for (kind, count) in &self.types_by_kind {
+            table.add_row(row![format!("  {}", kind), count]);
+        }
+        table.add_row(row!["Fields", self.fields]);
+        table.add_row(row!["Arguments", self.arguments]);
+        table.add_row(row!["Input fields", self.input_fields]);
+        table.add_row(row!["Enum values", self.enum_values]);
+        table.add_row(row!["Deprecations", self.deprecations]);
+        table.add_row(row!["Directive definitions", self.directive_definitions]);
+        for (name, count) in &self.directive_usages {
+            table.add_row(row![format!("@{} usages", name), count]);
+        }
+
+        msg.push_str(&table.to_string());
+        msg
+    }
+
+    pub fn get_json(&self) -> Value {
+        json!({
+            "schema": self.schema,
+            "types": self.types,
+            "types_by_kind": self.types_by_kind,
+            "fields": self.fields,
+            "input_fields": self.input_fields,
+            "enum_values": self.enum_values,
+            "arguments": self.arguments,
+            "deprecations": self.deprecations,
+            "directive_definitions": self.directive_definitions,
+            "directive_usages": self.directive_usages,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_counts_schema_elements() {
+        let sdl = r#"
+directive @cacheControl(maxAge: Int) on FIELD_DEFINITION
+
+type Query {
+  products(first: Int, after: String @deprecated): [Product] @cacheControl(maxAge: 30)
+}
+
+type Product @key(fields: "id") {
+  id: ID!
+  name: String @deprecated(reason: "Use title")
+}
+
+extend type Product {
+  title: String @cacheControl(maxAge: 60)
+}
+
+enum Color {
+  RED
+  BLUE @deprecated
+}
+
+input ProductFilter {
+  color: Color
+}
+
+scalar join__FieldSet
+
+directive @join__type(graph: String!) on OBJECT
+"#;
+        let stats = SchemaStats::new("schema.graphql", sdl).unwrap();
+        assert_eq!(stats.types, 4);
+        assert_eq!(stats.types_by_kind.get("OBJECT"), Some(&2));
+        assert_eq!(stats.fields, 4);
+        assert_eq!(stats.arguments, 2);
+        assert_eq!(stats.input_fields, 1);
+        assert_eq!(stats.enum_values, 2);
+        assert_eq!(stats.deprecations, 3);
+        assert_eq!(stats.directive_definitions, 1);
+        assert_eq!(stats.directive_usages.get("cacheControl"), Some(&2));
+        assert_eq!(stats.directive_usages.get("deprecated"), Some(&3));
+        assert_eq!(stats.directive_usages.get("key"), Some(&1));
+    }
+
+    #[test]
+    fn it_fails_on_invalid_sdl() {
+        assert!(SchemaStats::new("schema.graphql", "type Query {").is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use apollo_parser::{ast, Parser, SyntaxKind, SyntaxNode};
+
+/// Decides which directive applications are kept when an SDL schema is
+/// post-processed before it is printed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DirectiveFilter {
+    /// Leave every directive application in place
+    #[default]
+    KeepAll,
+
+    /// Only keep applications of directives matching one of these patterns
+    Include(Vec<String>),
+
+    /// Remove applications of directives matching any of these patterns
+    Exclude(Vec<String>),
+}
+
+impl DirectiveFilter {
+    /// Whether applications of the directive named `name` should stay in the schema
+    pub fn keeps(&self, name: &str) -> bool {
+        match self {
+            DirectiveFilter::KeepAll => true,
+            DirectiveFilter::Include(patterns) => matches_any(patterns, name),
+            DirectiveFilter::Exclude(patterns) => !matches_any(patterns, name),
+        }
+    }
+
+    /// Removes the directive applications this filter doesn't keep from `sdl`.
+    ///
+    /// Directive definitions are left alone, as is everything else in the schema,
+    /// including its formatting and comments.
+    pub fn apply(&self, sdl: &str) -> String {
+        use ast::AstNode;
+
+        if let DirectiveFilter::KeepAll = self {
+            return sdl.to_string();
+        }
+
+        let parsed_ast = Parser::new(sdl).parse();
+        let mut removals = Vec::new();
+        for directive in find_directives(&parsed_ast.document()) {
+            let name = match directive.name() {
+                Some(name) => name.text().to_string(),
+                None => continue,
+            };
+            if !self.keeps(&name) {
+                if let Some(range) = removal_range(sdl, directive.syntax()) {
+                    removals.push(range);
+                }
+            }
+        }
+
+        let mut transformed = sdl.to_string();
+        // remove from the end so earlier ranges stay valid
+        removals.sort_unstable();
+        for (start, end) in removals.into_iter().rev() {
+            transformed.replace_range(start..end, "");
+        }
+        transformed
+    }
+}
+
+/// A pattern matches a directive by name, with or without a leading `@`.
+/// Patterns ending in `*` match every directive starting with the rest of the pattern.
+fn matches_any(patterns: &[String], name: &str) -> bool {
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim().trim_start_matches('@');
+        match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == pattern,
+        }
+    })
+}
+
+fn find_directives(document: &ast::Document) -> Vec<ast::Directive> {
+    use ast::AstNode;
+
+    document
+        .syntax()
+        .descendants()
+        .filter_map(ast::Directive::cast)
+        .collect()
+}
+
+/// Types that federation adds to a schema, which aren't part of the graph's own API
+pub(crate) fn is_federation_machinery(type_name: &str) -> bool {
+    type_name.starts_with('_') || type_name.contains("__")
+}
+
+/// The byte range to remove for a node, such as a directive application, which includes
+/// the whitespace before it, and the whole line if the node was on a line of its own
+pub(crate) fn removal_range(sdl: &str, node: &SyntaxNode) -> Option<(usize, usize)> {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| {
+            !matches!(
+                token.kind(),
+                SyntaxKind::WHITESPACE | SyntaxKind::COMMENT | SyntaxKind::COMMA
+            )
+        });
+    let first = tokens.next()?;
+    let last = tokens.last().unwrap_or_else(|| first.clone());
+    let mut start: usize = first.text_range().start().into();
+    let mut end: usize = last.text_range().end().into();
+
+    let bytes = sdl.as_bytes();
+    while start > 0 && matches!(bytes[start - 1], b' ' | b'\t') {
+        start -= 1;
+    }
+    let mut line_end = end;
+    while line_end < bytes.len() && matches!(bytes[line_end], b' ' | b'\t') {
+        line_end += 1;
+    }
+    let starts_line = start == 0 || bytes[start - 1] == b'\n';
+    let ends_line = line_end == bytes.len() || bytes[line_end] == b'\n';
+    if starts_line && ends_line && line_end < bytes.len() {
+        end = line_end + 1;
+    }
+    Some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPERGRAPH: &str = r#"directive @join__owner(graph: String!) on OBJECT
+
+type Product
+  @join__owner(graph: "products")
+  @key(fields: "upc")
+{
+  upc: String! @join__field(graph: "products")
+  name: String @deprecated(reason: "use title")
+}
+"#;
+
+    #[test]
+    fn it_keeps_everything_by_default() {
+        assert_eq!(DirectiveFilter::KeepAll.apply(SUPERGRAPH), SUPERGRAPH);
+    }
+
+    #[test]
+    fn it_excludes_directives_by_prefix() {
+        let filter = DirectiveFilter::Exclude(vec!["join__*".to_string()]);
+        assert_eq!(
+            filter.apply(SUPERGRAPH),
+            r#"directive @join__owner(graph: String!) on OBJECT
+
+type Product
+  @key(fields: "upc")
+{
+  upc: String!
+  name: String @deprecated(reason: "use title")
+}
+"#
+        );
+    }
+
+    #[test]
+    fn it_includes_only_matching_directives() {
+        let filter = DirectiveFilter::Include(vec!["@deprecated".to_string()]);
+        assert_eq!(
+            filter.apply(SUPERGRAPH),
+            r#"directive @join__owner(graph: String!) on OBJECT
+
+type Product
+{
+  upc: String!
+  name: String @deprecated(reason: "use title")
+}
+"#
+        );
+    }
+}
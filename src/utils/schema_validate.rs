@@ -0,0 +1,509 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use apollo_parser::{ast, Parser};
+use serde::Serialize;
+
+use crate::utils::schema_lint::get_position;
+
+/// A problem with a schema that would make publishing it fail
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct ValidationError {
+    pub message: String,
+    /// 1-based line of the error in the validated schema
+    pub line: usize,
+    /// 1-based column of the error in the validated schema
+    pub column: usize,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// The directives every schema has, along with the locations they can be used in
+const BUILT_IN_DIRECTIVES: &[(&str, &[&str])] = &[
+    (
+        "deprecated",
+        &[
+            "FIELD_DEFINITION",
+            "ARGUMENT_DEFINITION",
+            "INPUT_FIELD_DEFINITION",
+            "ENUM_VALUE",
+        ],
+    ),
+    ("specifiedBy", &["SCALAR"]),
+    ("skip", &["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"]),
+    ("include", &["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"]),
+    ("oneOf", &["INPUT_OBJECT"]),
+    ("defer", &["FRAGMENT_SPREAD", "INLINE_FRAGMENT"]),
+    ("stream", &["FIELD"]),
+];
+
+struct DirectiveShape {
+    locations: Vec<String>,
+    repeatable: bool,
+}
+
+/// Validates an SDL schema without contacting Apollo Studio, returning syntax errors,
+/// duplicate definitions, and directives that aren't defined or are used where they can't be
+pub fn validate(sdl: &str) -> Vec<ValidationError> {
+    let parsed_ast = Parser::new(sdl).parse();
+    let mut validator = Validator {
+        sdl,
+        directives: HashMap::new(),
+        errors: Vec::new(),
+    };
+    for error in parsed_ast.errors() {
+        validator.report_at(error.index(), error.message().to_string());
+    }
+    // syntax errors make the rest of the tree unreliable, so they're reported on their own
+    if !validator.errors.is_empty() {
+        return validator.errors;
+    }
+
+    let document = parsed_ast.document();
+    validator.collect_directive_definitions(&document);
+    validator.check_definitions(&document);
+
+    let mut errors = validator.errors;
+    errors.sort_by_key(|error| (error.line, error.column));
+    errors
+}
+
+struct Validator<'a> {
+    sdl: &'a str,
+    directives: HashMap<String, DirectiveShape>,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    fn collect_directive_definitions(&mut self, document: &ast::Document) {
+        use ast::AstNode;
+
+        for (name, locations) in BUILT_IN_DIRECTIVES {
+            self.directives.insert(
+                name.to_string(),
+                DirectiveShape {
+                    locations: locations.iter().map(|l| l.to_string()).collect(),
+                    repeatable: false,
+                },
+            );
+        }
+
+        let mut defined = HashSet::new();
+        for definition in document.definitions() {
+            if let ast::Definition::DirectiveDefinition(def) = definition {
+                let name = match def.name() {
+                    Some(name) => name,
+                    None => continue,
+                };
+                let text = name.text().to_string();
+                if !defined.insert(text.clone()) {
+                    self.report(
+                        &name,
+                        format!("There can be only one directive named \"@{}\".", text),
+                    );
+                    continue;
+                }
+                let locations = def
+                    .directive_locations()
+                    .iter()
+                    .flat_map(|locations| locations.directive_locations())
+                    .map(|location| {
+                        let text = location.syntax().text().to_string();
+                        text.trim_matches(|c: char| c.is_whitespace() || c == '|')
+                            .to_string()
+                    })
+                    .collect();
+                self.directives.insert(
+                    text,
+                    DirectiveShape {
+                        locations,
+                        repeatable: def.repeatable_token().is_some(),
+                    },
+                );
+            }
+        }
+    }
+
+    fn check_definitions(&mut self, document: &ast::Document) {
+        use ast::Definition::*;
+
+        let mut type_names = HashSet::new();
+        let mut fields_by_type: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut has_schema_definition = false;
+        for definition in document.definitions() {
+            match definition {
+                SchemaDefinition(def) => {
+                    if has_schema_definition {
+                        self.report_node(&def, "There can be only one schema definition.");
+                    }
+                    has_schema_definition = true;
+                    self.check_directives(def.directives(), "SCHEMA");
+                }
+                SchemaExtension(def) => self.check_directives(def.directives(), "SCHEMA"),
+                DirectiveDefinition(def) => {
+                    self.check_arguments(def.arguments_definition());
+                }
+                ScalarTypeDefinition(def) => {
+                    self.check_type_name(def.name(), &mut type_names);
+                    self.check_directives(def.directives(), "SCALAR");
+                }
+                ScalarTypeExtension(def) => self.check_directives(def.directives(), "SCALAR"),
+                ObjectTypeDefinition(def) => {
+                    self.check_type_name(def.name(), &mut type_names);
+                    self.check_directives(def.directives(), "OBJECT");
+                    self.check_fields(def.name(), def.fields_definition(), &mut fields_by_type);
+                }
+                ObjectTypeExtension(def) => {
+                    self.check_directives(def.directives(), "OBJECT");
+                    self.check_fields(def.name(), def.fields_definition(), &mut fields_by_type);
+                }
+                InterfaceTypeDefinition(def) => {
+                    self.check_type_name(def.name(), &mut type_names);
+                    self.check_directives(def.directives(), "INTERFACE");
+                    self.check_fields(def.name(), def.fields_definition(), &mut fields_by_type);
+                }
+                InterfaceTypeExtension(def) => {
+                    self.check_directives(def.directives(), "INTERFACE");
+                    self.check_fields(def.name(), def.fields_definition(), &mut fields_by_type);
+                }
+                UnionTypeDefinition(def) => {
+                    self.check_type_name(def.name(), &mut type_names);
+                    self.check_directives(def.directives(), "UNION");
+                }
+                UnionTypeExtension(def) => self.check_directives(def.directives(), "UNION"),
+                EnumTypeDefinition(def) => {
+                    self.check_type_name(def.name(), &mut type_names);
+                    self.check_directives(def.directives(), "ENUM");
+                    self.check_enum_values(
+                        def.name(),
+                        def.enum_values_definition(),
+                        &mut fields_by_type,
+                    );
+                }
+                EnumTypeExtension(def) => {
+                    self.check_directives(def.directives(), "ENUM");
+                    self.check_enum_values(
+                        def.name(),
+                        def.enum_values_definition(),
+                        &mut fields_by_type,
+                    );
+                }
+                InputObjectTypeDefinition(def) => {
+                    self.check_type_name(def.name(), &mut type_names);
+                    self.check_directives(def.directives(), "INPUT_OBJECT");
+                    self.check_input_fields(
+                        def.name(),
+                        def.input_fields_definition(),
+                        &mut fields_by_type,
+                    );
+                }
+                InputObjectTypeExtension(def) => {
+                    self.check_directives(def.directives(), "INPUT_OBJECT");
+                    self.check_input_fields(
+                        def.name(),
+                        def.input_fields_definition(),
+                        &mut fields_by_type,
+                    );
+                }
+                OperationDefinition(def) => self.report_node(
+                    &def,
+                    "Operations can't be included in a schema. Remove the operation, or check that this is the right file.",
+                ),
+                FragmentDefinition(def) => self.report_node(
+                    &def,
+                    "Fragments can't be included in a schema. Remove the fragment, or check that this is the right file.",
+                ),
+            }
+        }
+    }
+
+    fn check_type_name(&mut self, name: Option<ast::Name>, type_names: &mut HashSet<String>) {
+        if let Some(name) = name {
+            let text = name.text().to_string();
+            if !type_names.insert(text.clone()) {
+                self.report(
+                    &name,
+                    format!(
+                        "There can be only one type named \"{}\". Use `extend type` to add to an existing type.",
+                        text
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_fields(
+        &mut self,
+        type_name: Option<ast::Name>,
+        fields: Option<ast::FieldsDefinition>,
+        fields_by_type: &mut HashMap<String, HashSet<String>>,
+    ) {
+        let type_name = match type_name {
+            Some(type_name) => type_name.text().to_string(),
+            None => return,
+        };
+        for field in fields.iter().flat_map(|fields| fields.field_definitions()) {
+            if let Some(name) = field.name() {
+                self.check_member_name(&type_name, &name, "Field", fields_by_type);
+            }
+            self.check_directives(field.directives(), "FIELD_DEFINITION");
+            self.check_arguments(field.arguments_definition());
+        }
+    }
+
+    fn check_input_fields(
+        &mut self,
+        type_name: Option<ast::Name>,
+        fields: Option<ast::InputFieldsDefinition>,
+        fields_by_type: &mut HashMap<String, HashSet<String>>,
+    ) {
+        let type_name = match type_name {
+            Some(type_name) => type_name.text().to_string(),
+            None => return,
+        };
+        for field in fields
+            .iter()
+            .flat_map(|fields| fields.input_value_definitions())
+        {
+            if let Some(name) = field.name() {
+                self.check_member_name(&type_name, &name, "Field", fields_by_type);
+            }
+            self.check_directives(field.directives(), "INPUT_FIELD_DEFINITION");
+        }
+    }
+
+    fn check_enum_values(
+        &mut self,
+        type_name: Option<ast::Name>,
+        values: Option<ast::EnumValuesDefinition>,
+        fields_by_type: &mut HashMap<String, HashSet<String>>,
+    ) {
+        let type_name = match type_name {
+            Some(type_name) => type_name.text().to_string(),
+            None => return,
+        };
+        for value in values
+            .iter()
+            .flat_map(|values| values.enum_value_definitions())
+        {
+            if let Some(name) = value.enum_value().and_then(|value| value.name()) {
+                self.check_member_name(&type_name, &name, "Enum value", fields_by_type);
+            }
+            self.check_directives(value.directives(), "ENUM_VALUE");
+        }
+    }
+
+    fn check_member_name(
+        &mut self,
+        type_name: &str,
+        name: &ast::Name,
+        kind: &str,
+        fields_by_type: &mut HashMap<String, HashSet<String>>,
+    ) {
+        let text = name.text().to_string();
+        let is_new = fields_by_type
+            .entry(type_name.to_string())
+            .or_default()
+            .insert(text.clone());
+        if !is_new {
+            self.report(
+                name,
+                format!(
+                    "{} \"{}.{}\" can only be defined once.",
+                    kind, type_name, text
+                ),
+            );
+        }
+    }
+
+    fn check_arguments(&mut self, arguments: Option<ast::ArgumentsDefinition>) {
+        let mut names = HashSet::new();
+        for argument in arguments
+            .iter()
+            .flat_map(|arguments| arguments.input_value_definitions())
+        {
+            if let Some(name) = argument.name() {
+                let text = name.text().to_string();
+                if !names.insert(text.clone()) {
+                    self.report(
+                        &name,
+                        format!("There can be only one argument named \"{}\".", text),
+                    );
+                }
+            }
+            self.check_directives(argument.directives(), "ARGUMENT_DEFINITION");
+        }
+    }
+
+    fn check_directives(&mut self, directives: Option<ast::Directives>, location: &str) {
+        let mut used = HashSet::new();
+        for directive in directives
+            .iter()
+            .flat_map(|directives| directives.directives())
+        {
+            let name = match directive.name() {
+                Some(name) => name,
+                None => continue,
+            };
+            let text = name.text().to_string();
+            let (allowed, repeatable) = match self.directives.get(&text) {
+                Some(shape) => (
+                    shape.locations.iter().any(|l| l == location),
+                    shape.repeatable,
+                ),
+                None => {
+                    self.report(&name, format!("Unknown directive \"@{}\".", text));
+                    continue;
+                }
+            };
+            if !allowed {
+                self.report(
+                    &name,
+                    format!("Directive \"@{}\" may not be used on {}.", text, location),
+                );
+            }
+            if !used.insert(text.clone()) && !repeatable {
+                self.report(
+                    &name,
+                    format!(
+                        "The directive \"@{}\" can only be used once at this location.",
+                        text
+                    ),
+                );
+            }
+        }
+    }
+
+    fn report(&mut self, name: &ast::Name, message: String) {
+        self.report_node(name, message)
+    }
+
+    fn report_node<N: ast::AstNode>(&mut self, node: &N, message: impl Into<String>) {
+        let offset: usize = node.syntax().text_range().start().into();
+        self.report_at(offset, message.into());
+    }
+
+    fn report_at(&mut self, offset: usize, message: String) {
+        let (line, column) = get_position(self.sdl, offset);
+        self.errors.push(ValidationError {
+            message,
+            line,
+            column,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(sdl: &str) -> Vec<String> {
+        validate(sdl)
+            .iter()
+            .map(|error| error.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn it_accepts_valid_schemas() {
+        let sdl = r#"directive @tag(name: String!) repeatable on OBJECT | FIELD_DEFINITION
+
+type Query {
+  product(id: ID! @deprecated(reason: "unused")): Product @tag(name: "a") @tag(name: "b")
+}
+
+type Product @tag(name: "catalog") {
+  id: ID!
+}
+
+extend type Product {
+  name: String
+}
+
+enum Color {
+  RED
+  BLUE @deprecated
+}
+"#;
+        assert!(messages(sdl).is_empty());
+    }
+
+    #[test]
+    fn it_knows_the_oneof_defer_and_stream_directives() {
+        let sdl = r#"type Query {
+  product(by: ProductBy!): Product
+}
+
+input ProductBy @oneOf {
+  id: ID
+  sku: String
+}
+
+type Product {
+  id: ID! @defer
+}
+"#;
+        assert_eq!(
+            messages(sdl),
+            vec!["11:12: Directive \"@defer\" may not be used on FIELD_DEFINITION."]
+        );
+    }
+
+    #[test]
+    fn it_reports_syntax_errors_with_positions() {
+        let errors = validate("type Query {\n  a: String\n\ntype Product { id: ID! }\n");
+        assert!(!errors.is_empty());
+        assert!(errors.iter().all(|error| error.line > 1));
+    }
+
+    #[test]
+    fn it_reports_duplicate_definitions() {
+        let sdl = r#"type Query {
+  a: String
+  a: Int
+}
+
+type Query {
+  b: String
+}
+
+extend type Query {
+  b: String
+}
+
+enum Color { RED RED }
+"#;
+        assert_eq!(
+            messages(sdl),
+            vec![
+                "3:3: Field \"Query.a\" can only be defined once.",
+                "6:6: There can be only one type named \"Query\". Use `extend type` to add to an existing type.",
+                "11:3: Field \"Query.b\" can only be defined once.",
+                "14:18: Enum value \"Color.RED\" can only be defined once.",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_directive_usage() {
+        let sdl = r#"directive @cacheControl(maxAge: Int) on FIELD_DEFINITION
+
+type Query @cacheControl(maxAge: 10) {
+  a: String @cacheControl(maxAge: 10) @cacheControl(maxAge: 20)
+  b: String @key
+}
+"#;
+        assert_eq!(
+            messages(sdl),
+            vec![
+                "3:13: Directive \"@cacheControl\" may not be used on OBJECT.",
+                "4:40: The directive \"@cacheControl\" can only be used once at this location.",
+                "5:14: Unknown directive \"@key\".",
+            ]
+        );
+    }
+}
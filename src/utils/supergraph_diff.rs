@@ -0,0 +1,258 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::utils::schema_diff::SchemaDiff;
+use crate::utils::schema_export::{export, ExportRecord};
+use crate::utils::table::{self, row};
+use crate::RoverResult;
+
+/// How a subgraph's part in a supergraph changed
+#[derive(Debug, Serialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SubgraphDiffStatus {
+    /// the subgraph is only in the target supergraph
+    Added,
+    /// the subgraph is only in the base supergraph
+    Removed,
+    /// the subgraph is in both, but defines different parts of the supergraph
+    Changed,
+}
+
+/// The schema coordinates, i.e. `Product.price`, that one subgraph
+/// defines differently in two supergraphs
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct SubgraphDiff {
+    pub subgraph: String,
+    pub status: SubgraphDiffStatus,
+    /// coordinates the subgraph only defines in the target
+    pub added: Vec<String>,
+    /// coordinates the subgraph only defines in the base
+    pub removed: Vec<String>,
+    /// coordinates the subgraph defines in both, whose definition changed
+    pub changed: Vec<String>,
+}
+
+/// The changes between two supergraph schemas, both to the API they serve
+/// and to what each subgraph contributes to it
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct SupergraphDiff {
+    pub schema_diff: SchemaDiff,
+    /// only the subgraphs whose contributions changed, by name
+    pub subgraphs: Vec<SubgraphDiff>,
+}
+
+impl SupergraphDiff {
+    /// Compares two supergraph schemas, returning the changes needed to get from `base_sdl` to `target_sdl`
+    pub fn new(
+        base: impl Into<String>,
+        base_sdl: &str,
+        target: impl Into<String>,
+        target_sdl: &str,
+    ) -> RoverResult<Self> {
+        let schema_diff = SchemaDiff::new_for_supergraphs(base, base_sdl, target, target_sdl)?;
+        let base_contributions = get_contributions(&export(base_sdl)?);
+        let target_contributions = get_contributions(&export(target_sdl)?);
+
+        let names: BTreeSet<&String> = base_contributions
+            .keys()
+            .chain(target_contributions.keys())
+            .collect();
+        let empty = BTreeMap::new();
+        let subgraphs = names
+            .into_iter()
+            .filter_map(|name| {
+                let base = base_contributions.get(name);
+                let target = target_contributions.get(name);
+                let status = match (base, target) {
+                    (None, Some(_)) => SubgraphDiffStatus::Added,
+                    (Some(_), None) => SubgraphDiffStatus::Removed,
+                    _ => SubgraphDiffStatus::Changed,
+                };
+                let (base, target) = (base.unwrap_or(&empty), target.unwrap_or(&empty));
+                let subgraph = SubgraphDiff {
+                    subgraph: name.clone(),
+                    status,
+                    added: target
+                        .keys()
+                        .filter(|id| !base.contains_key(*id))
+                        .cloned()
+                        .collect(),
+                    removed: base
+                        .keys()
+                        .filter(|id| !target.contains_key(*id))
+                        .cloned()
+                        .collect(),
+                    changed: base
+                        .iter()
+                        .filter(|(id, definition)| {
+                            matches!(target.get(*id), Some(target) if target != *definition)
+                        })
+                        .map(|(id, _)| id.clone())
+                        .collect(),
+                };
+                let is_unchanged = subgraph.added.is_empty()
+                    && subgraph.removed.is_empty()
+                    && subgraph.changed.is_empty();
+                (!is_unchanged).then_some(subgraph)
+            })
+            .collect();
+
+        Ok(Self {
+            schema_diff,
+            subgraphs,
+        })
+    }
+
+    pub fn get_table(&self) -> String {
+        let mut msg = self.schema_diff.get_table();
+        if self.subgraphs.is_empty() {
+            msg.push_str("\nNo subgraph's contribution to the supergraph changed.");
+            return msg;
+        }
+
+        msg.push_str(&format!(
+            "\n{} changed what they contribute to the supergraph:\n",
+            match self.subgraphs.len() {
+                1 => "1 subgraph".to_string(),
+                subgraphs => format!("{} subgraphs", subgraphs),
+            }
+        ));
+        let mut table = table::get_table();
+
+        // bc => sets top row to be bold and center
+        table.add_row(row![bc => "Subgraph", "Status", "Added", "Removed", "Changed"]);
+        for subgraph in &self.subgraphs {
+            let status = match subgraph.status {
+                SubgraphDiffStatus::Added => "ADDED",
+                SubgraphDiffStatus::Removed => "REMOVED",
+                SubgraphDiffStatus::Changed => "CHANGED",
+            };
+            table.add_row(row![
+                subgraph.subgraph,
+                status,
+                subgraph.added.join("\n"),
+                subgraph.removed.join("\n"),
+                subgraph.changed.join("\n")
+            ]);
+        }
+        msg.push_str(&table.to_string());
+        msg
+    }
+
+    pub fn get_json(&self) -> Value {
+        let mut json = self.schema_diff.get_json();
+        json["subgraphs"] = json!(self.subgraphs);
+        json
+    }
+}
+
+/// What each subgraph defines, by subgraph name and then by schema coordinate
+fn get_contributions(records: &[ExportRecord]) -> BTreeMap<String, BTreeMap<String, String>> {
+    let mut contributions: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    for record in records {
+        // a type's text includes its members, whose changes are reported on their own
+        let definition = match &record.parent_type {
+            Some(_) => record.text.clone(),
+            None => format!(
+                "{} {:?} {:?}",
+                record.kind, record.description, record.deprecation_reason
+            ),
+        };
+        for subgraph in &record.subgraphs {
+            contributions
+                .entry(subgraph.clone())
+                .or_default()
+                .insert(record.id.clone(), definition.clone());
+        }
+    }
+    contributions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRAPHS: &str = r#"
+enum join__Graph {
+  PRODUCTS @join__graph(name: "products", url: "http://localhost:4001")
+  REVIEWS @join__graph(name: "reviews", url: "http://localhost:4002")
+  INVENTORY @join__graph(name: "inventory", url: "http://localhost:4003")
+}
+"#;
+
+    #[test]
+    fn it_attributes_changes_to_subgraphs() {
+        let base = format!(
+            r#"{}
+type Query @join__type(graph: PRODUCTS) @join__type(graph: REVIEWS) {{
+  products: [Product] @join__field(graph: PRODUCTS)
+  reviews: [Review] @join__field(graph: REVIEWS)
+}}
+
+type Product @join__type(graph: PRODUCTS) @join__type(graph: REVIEWS) {{
+  id: ID!
+  price: Int @join__field(graph: PRODUCTS)
+  cost: Int @join__field(graph: PRODUCTS)
+}}
+
+type Review @join__type(graph: REVIEWS) {{
+  body: String
+}}
+"#,
+            GRAPHS
+        );
+        let target = format!(
+            r#"{}
+type Query @join__type(graph: PRODUCTS) @join__type(graph: REVIEWS) {{
+  products: [Product] @join__field(graph: PRODUCTS)
+  reviews: [Review] @join__field(graph: REVIEWS)
+}}
+
+type Product @join__type(graph: PRODUCTS) @join__type(graph: REVIEWS) @join__type(graph: INVENTORY) {{
+  id: ID!
+  price: Float @join__field(graph: PRODUCTS)
+  inStock: Boolean @join__field(graph: INVENTORY)
+}}
+
+type Review @join__type(graph: REVIEWS) {{
+  body: String
+}}
+"#,
+            GRAPHS
+        );
+
+        let diff = SupergraphDiff::new("the base", &base, "the target", &target).unwrap();
+        assert_eq!(
+            diff.subgraphs,
+            vec![
+                SubgraphDiff {
+                    subgraph: "inventory".to_string(),
+                    status: SubgraphDiffStatus::Added,
+                    added: vec![
+                        "Product".to_string(),
+                        "Product.id".to_string(),
+                        "Product.inStock".to_string()
+                    ],
+                    removed: Vec::new(),
+                    changed: Vec::new(),
+                },
+                SubgraphDiff {
+                    subgraph: "products".to_string(),
+                    status: SubgraphDiffStatus::Changed,
+                    added: Vec::new(),
+                    removed: vec!["Product.cost".to_string()],
+                    changed: vec!["Product.price".to_string()],
+                },
+            ]
+        );
+        // federation types aren't part of the API, so the new join__Graph value isn't a change
+        assert!(diff
+            .schema_diff
+            .changes
+            .iter()
+            .all(|change| !change.description.contains("join__")));
+        assert!(diff.get_table().contains("2 subgraphs changed"));
+    }
+}
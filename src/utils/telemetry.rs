@@ -81,6 +81,10 @@ impl Report for Rover {
     }
 
     fn is_telemetry_enabled(&self) -> Result<bool, SputnikError> {
+        if self.is_offline() {
+            tracing::info!("Telemetry is disabled because the command is running offline.");
+            return Ok(false);
+        }
         let value = self.get_env_var(RoverEnvKey::TelemetryDisabled)?;
         let is_telemetry_disabled = value.is_some();
         if is_telemetry_disabled {